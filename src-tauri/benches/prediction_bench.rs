@@ -0,0 +1,176 @@
+//! 预测管线性能基准：指标计算、背离检测、形态识别、线性模型训练与纯技术分析预测命令
+//! （fixture 写入内存 SQLite，走 `predict_with_technical_only_with_pool` 的完整流程）。
+//!
+//! 运行：`cargo bench --bench prediction_bench`。
+//! 性能预算（发布构建、单核）：单次全量指标计算 < 1ms，完整 5 日预测 < 100ms；
+//! 超出预算时对照 `target/criterion` 下的历史报告定位回归。
+//! 预算在各基准开始前以多次运行的中位数断言（仅优化构建）。
+
+#[path = "../tests/common/mod.rs"]
+mod common;
+
+use biga_lib::commands::stock_prediction::predict_with_technical_only_with_pool;
+use biga_lib::config::presets::IndicatorConfig;
+use biga_lib::db::models::HistoricalData;
+use biga_lib::prediction::analysis::divergence::analyze_all_divergences;
+use biga_lib::prediction::analysis::pattern::recognize_patterns;
+use biga_lib::prediction::indicators::calculate_all_indicators;
+use biga_lib::prediction::model::features::FEATURE_DIM;
+use biga_lib::prediction::model::linear::LinearRegression;
+use biga_lib::prediction::types::TechnicalOnlyRequest;
+use common::{fixture_pool, load_fixture, FIXTURE_CODE};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use std::time::{Duration, Instant};
+
+/// 单次全量指标计算（250 日）的性能预算
+const INDICATORS_BUDGET: Duration = Duration::from_millis(1);
+/// 完整 5 日预测的性能预算
+const PREDICTION_BUDGET: Duration = Duration::from_millis(100);
+/// 预算断言取中位数的运行次数
+const BUDGET_RUNS: usize = 21;
+
+struct Series {
+    opens: Vec<f64>,
+    closes: Vec<f64>,
+    highs: Vec<f64>,
+    lows: Vec<f64>,
+    volumes: Vec<i64>,
+}
+
+/// 最近 `days` 个交易日的行情序列
+fn series(historical: &[HistoricalData], days: usize) -> Series {
+    let bars = &historical[historical.len().saturating_sub(days)..];
+    Series {
+        opens: bars.iter().map(|h| h.open).collect(),
+        closes: bars.iter().map(|h| h.close).collect(),
+        highs: bars.iter().map(|h| h.high).collect(),
+        lows: bars.iter().map(|h| h.low).collect(),
+        volumes: bars.iter().map(|h| h.volume).collect(),
+    }
+}
+
+/// 确定性的训练样本：特征取固定相位的正弦，标签为特征线性组合加扰动
+fn training_samples(n: usize) -> (Vec<[f32; FEATURE_DIM]>, Vec<f64>) {
+    let features: Vec<[f32; FEATURE_DIM]> = (0..n)
+        .map(|i| std::array::from_fn(|j| ((i * (j + 1)) as f32 * 0.37).sin()))
+        .collect();
+    let labels = features
+        .iter()
+        .enumerate()
+        .map(|(i, row)| {
+            let signal: f64 = row
+                .iter()
+                .enumerate()
+                .map(|(j, &x)| x as f64 * (j as f64 - 4.5) * 0.2)
+                .sum();
+            signal + (i as f64 * 1.7).cos() * 0.1
+        })
+        .collect();
+    (features, labels)
+}
+
+/// 断言多次运行耗时的中位数不超过预算；未优化构建（`cargo test --benches`）不检查
+fn assert_within_budget<T>(name: &str, budget: Duration, mut run: impl FnMut() -> T) {
+    if cfg!(debug_assertions) {
+        return;
+    }
+    black_box(run());
+    let mut elapsed: Vec<Duration> = (0..BUDGET_RUNS)
+        .map(|_| {
+            let start = Instant::now();
+            black_box(run());
+            start.elapsed()
+        })
+        .collect();
+    elapsed.sort();
+    let median = elapsed[BUDGET_RUNS / 2];
+    assert!(median <= budget, "{name} 耗时中位数 {median:?} 超出预算 {budget:?}");
+}
+
+fn bench_indicators(c: &mut Criterion) {
+    let historical = load_fixture();
+    let s = series(&historical, 250);
+    let config = IndicatorConfig::default();
+    assert_within_budget("calculate_all_indicators_250d", INDICATORS_BUDGET, || {
+        calculate_all_indicators(&s.closes, &s.highs, &s.lows, &s.volumes, &config)
+    });
+    c.bench_function("calculate_all_indicators_250d", |b| {
+        b.iter(|| {
+            calculate_all_indicators(
+                black_box(&s.closes),
+                black_box(&s.highs),
+                black_box(&s.lows),
+                black_box(&s.volumes),
+                &config,
+            )
+        })
+    });
+}
+
+fn bench_divergences(c: &mut Criterion) {
+    let historical = load_fixture();
+    let s = series(&historical, 100);
+    c.bench_function("analyze_all_divergences_100d", |b| {
+        b.iter(|| {
+            analyze_all_divergences(
+                black_box(&s.closes),
+                black_box(&s.highs),
+                black_box(&s.lows),
+                black_box(&s.volumes),
+            )
+        })
+    });
+}
+
+fn bench_patterns(c: &mut Criterion) {
+    let historical = load_fixture();
+    let s = series(&historical, 60);
+    c.bench_function("recognize_patterns_60d", |b| {
+        b.iter(|| {
+            recognize_patterns(
+                black_box(&s.opens),
+                black_box(&s.closes),
+                black_box(&s.highs),
+                black_box(&s.lows),
+            )
+        })
+    });
+}
+
+fn bench_linear_training(c: &mut Criterion) {
+    let (features, labels) = training_samples(200);
+    c.bench_function("linear_regression_fit_200x10", |b| {
+        b.iter(|| LinearRegression::fit(black_box(&features), black_box(&labels)))
+    });
+}
+
+fn bench_technical_prediction(c: &mut Criterion) {
+    let runtime = tokio::runtime::Runtime::new().expect("应创建 tokio 运行时");
+    let pool = runtime.block_on(fixture_pool(&load_fixture()));
+    let request = TechnicalOnlyRequest {
+        stock_code: FIXTURE_CODE.to_string(),
+        history_days: None,
+        prediction_days: Some(5),
+    };
+    let predict = |request: TechnicalOnlyRequest| {
+        runtime
+            .block_on(predict_with_technical_only_with_pool(request, &pool))
+            .expect("纯技术分析预测应成功")
+    };
+    assert_within_budget("technical_prediction_5d", PREDICTION_BUDGET, || {
+        predict(request.clone())
+    });
+    c.bench_function("technical_prediction_5d", |b| {
+        b.iter(|| predict(black_box(request.clone())))
+    });
+}
+
+criterion_group!(
+    benches,
+    bench_indicators,
+    bench_divergences,
+    bench_patterns,
+    bench_linear_training,
+    bench_technical_prediction
+);
+criterion_main!(benches);
@@ -4,11 +4,12 @@
 
 use biga_lib::db::connection::create_pool;
 use biga_lib::db::models::HistoricalData;
-use biga_lib::db::repository::get_recent_historical_data;
+use biga_lib::db::repository::{get_recent_closes_exact, get_recent_historical_data};
 use biga_lib::prediction::cross_section::{
     build_panel, pearson, rank_latest, walk_forward, walk_forward_orthogonalized,
 };
-use biga_lib::prediction::factor::{factor_dim, factor_names};
+use biga_lib::prediction::factor::{factor_dim, factor_names, BenchmarkCloses};
+use biga_lib::services::beta::DEFAULT_BETA_INDEX;
 use sqlx::Row;
 
 // 默认持有期/估计窗口（诊断用）。注意：该技术截面信号样本外不稳定、对票池敏感，
@@ -46,7 +47,12 @@ async fn main() {
         }
     }
 
-    let panel = build_panel(&stocks, HORIZON);
+    let benchmark: BenchmarkCloses = get_recent_closes_exact(DEFAULT_BETA_INDEX, 800, &pool)
+        .await
+        .unwrap_or_default()
+        .into_iter()
+        .collect();
+    let panel = build_panel(&stocks, &benchmark, HORIZON);
     println!("截面交易日数：{}", panel.len());
 
     // 单因子 IC（全样本日均）
@@ -123,7 +129,7 @@ async fn main() {
     println!("  → {better}（假设每期多空双边成本 {:.1}%）", cost * 100.0);
 
     // 最新排名演示
-    let ranking = rank_latest(&stocks, HORIZON, WINDOW);
+    let ranking = rank_latest(&stocks, &benchmark, HORIZON, WINDOW);
     if !ranking.is_empty() {
         println!("\n----- 最新交易日相对强弱排名（前5/后5）-----");
         for s in ranking.iter().take(5) {
@@ -7,8 +7,10 @@
 
 use biga_lib::db::connection::create_pool;
 use biga_lib::db::models::HistoricalData;
-use biga_lib::db::repository::get_recent_historical_data;
+use biga_lib::db::repository::{get_recent_closes_exact, get_recent_historical_data};
 use biga_lib::prediction::cross_section::{build_panel, walk_forward, PanelRow};
+use biga_lib::prediction::factor::BenchmarkCloses;
+use biga_lib::services::beta::DEFAULT_BETA_INDEX;
 use sqlx::Row;
 
 const COST: f64 = 0.003; // 每次调仓多空双边成本假设 0.3%
@@ -148,11 +150,16 @@ async fn main() {
         }
     }
     println!("满足 ≥300 根的股票数：{}\n", stocks.len());
+    let benchmark: BenchmarkCloses = get_recent_closes_exact(DEFAULT_BETA_INDEX, 800, &pool)
+        .await
+        .unwrap_or_default()
+        .into_iter()
+        .collect();
 
     for &window in WINDOWS {
         println!("----- IC估计窗口 {window} 日 -----");
         for &horizon in HORIZONS {
-            let panel = build_panel(&stocks, horizon);
+            let panel = build_panel(&stocks, &benchmark, horizon);
             if panel.len() < window + horizon + 30 {
                 println!("  持有{horizon}日: 面板不足，跳过");
                 continue;
@@ -1,6 +1,7 @@
 //! 区间预测校准实验：方向不可测但波动可测——验证用 GARCH / 已实现波动率构造的
 //! H 日涨跌区间带是否"校准"（名义 80% 带是否真覆盖 ~80% 的实际结果），
 //! 并求出校准用的 z 倍数与平均带宽。确认能校准后再把区间接进生产预测输出。
+//! realized20 法直接使用生产口径的 σ（含布林带收缩放大），z 分位在放大后的 σ 上求得。
 //!
 //! 用法：cargo run --release --example interval_calibration
 
@@ -8,9 +9,7 @@ use biga_lib::db::connection::create_pool;
 use biga_lib::db::models::HistoricalData;
 use biga_lib::db::repository::get_recent_historical_data_for_symbols;
 use biga_lib::prediction::analysis::prediction_interval;
-use biga_lib::prediction::analysis::volatility_forecast::{
-    calculate_realized_volatility, GarchForecaster,
-};
+use biga_lib::prediction::analysis::volatility_forecast::GarchForecaster;
 use sqlx::Row;
 
 const MIN_LOOKBACK: usize = 80; // GARCH 估参需要足够历史
@@ -69,9 +68,8 @@ async fn main() {
                                 (daily.iter().map(|s| s * s).sum::<f64>()).sqrt()
                             }
                             _ => {
-                                // 近20日已实现日波动 × sqrt(H)
-                                let start = (base_idx + 1).saturating_sub(20);
-                                let sd = calculate_realized_volatility(&closes[start..=base_idx]);
+                                // 生产口径日波动（近20日已实现，收缩时放大）× sqrt(H)
+                                let sd = prediction_interval::interval_daily_sigma(&closes[..=base_idx]);
                                 sd * (horizon as f64).sqrt()
                             }
                         };
@@ -11,9 +11,10 @@
 
 use biga_lib::db::connection::create_pool;
 use biga_lib::db::models::HistoricalData;
-use biga_lib::db::repository::get_recent_historical_data_for_symbols;
+use biga_lib::db::repository::{get_recent_closes_exact, get_recent_historical_data_for_symbols};
 use biga_lib::prediction::cross_section::{build_panel, pearson, PanelRow};
-use biga_lib::prediction::factor::factor_dim;
+use biga_lib::prediction::factor::{factor_dim, BenchmarkCloses};
+use biga_lib::services::beta::DEFAULT_BETA_INDEX;
 use sqlx::Row;
 use std::collections::HashMap;
 
@@ -69,6 +70,11 @@ async fn main() {
             .filter(|(_, h)| h.len() >= 300)
             .collect();
     println!("可用票数（历史≥300 且有市值）：{}", stocks.len());
+    let benchmark: BenchmarkCloses = get_recent_closes_exact(DEFAULT_BETA_INDEX, 900, &pool)
+        .await
+        .unwrap_or_default()
+        .into_iter()
+        .collect();
 
     // 票池：full / 市值≥200亿大中盘 / 市值<200亿（含小盘，做对照）
     let pools: Vec<(&str, Box<dyn Fn(&str) -> bool>)> = vec![
@@ -107,7 +113,7 @@ async fn main() {
         }
 
         for &(horizon, window) in &[(5usize, 250usize), (15usize, 250usize)] {
-            let raw_panel = build_panel(&pool_stocks, horizon);
+            let raw_panel = build_panel(&pool_stocks, &benchmark, horizon);
             if raw_panel.len() < window + horizon {
                 continue;
             }
@@ -18,6 +18,9 @@ async fn main() {
         model_name: None,
         prediction_days: 5,
         use_candle: true,
+        refresh_if_stale: false,
+        include_news: false,
+        include_events: false,
     };
     
     match inference::predict(request).await {
@@ -9,7 +9,8 @@ use biga_lib::prediction::types::PredictionRequest;
 use biga_lib::prediction::model::inference;
 use biga_lib::prediction::analysis::{trend, volume, pattern, support_resistance};
 use biga_lib::prediction::indicators;
-use biga_lib::prediction::strategy::multi_factor;
+use biga_lib::config::presets::IndicatorConfig;
+use biga_lib::prediction::strategy::{mean_reversion, multi_factor};
 use biga_lib::db::{connection::create_temp_pool, repository::get_recent_historical_data};
 
 #[tokio::main]
@@ -68,7 +69,13 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         let volume_signal = volume::analyze_volume_price(&prices, &highs, &lows, &volumes);
         let patterns = pattern::recognize_patterns(&opens, &prices, &highs, &lows);
         let sr = support_resistance::calculate_support_resistance(&prices, &highs, &lows, current_price);
-        let tech_indicators = indicators::calculate_all_indicators(&prices, &highs, &lows, &volumes);
+        let tech_indicators = indicators::calculate_all_indicators(
+            &prices,
+            &highs,
+            &lows,
+            &volumes,
+            &IndicatorConfig::default(),
+        );
         
         // 波动率计算
         let volatility = trend::calculate_historical_volatility(&prices, 20);
@@ -136,12 +143,31 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         println!("   波动率因子: {:.1}", multi_factor_score.volatility_score);
         println!("   信号: {} (强度: {:.0}%)", multi_factor_score.signal, multi_factor_score.signal_strength * 100.0);
         
+        // 均值回归检测（20日均线 / 2倍标准差）
+        let reversion = mean_reversion::detect_mean_reversion_opportunity(&prices, 20, 2.0);
+        println!("\n🔄 均值回归:");
+        println!("   回归目标(MA20): {:.2}元", reversion.reversion_target);
+        println!("   偏离程度: {:+.2}σ", reversion.distance_std);
+        if reversion.triggered {
+            println!("   信号: {} (强度: {:.0}%)", reversion.direction, reversion.signal_strength * 100.0);
+            if reversion.estimated_reversion_days > 0 {
+                println!("   预计回归天数: 约{}个交易日", reversion.estimated_reversion_days);
+            } else {
+                println!("   预计回归天数: 历史半衰期不可估计");
+            }
+        } else {
+            println!("   偏离未超过阈值，暂无均值回归机会");
+        }
+        
         // 进行预测
         let request = PredictionRequest {
             stock_code: stock_code.to_string(),
             prediction_days: 5,
             model_name: None,
             use_candle: true,
+            refresh_if_stale: false,
+            include_news: false,
+            include_events: false,
         };
         
         match inference::predict(request).await {
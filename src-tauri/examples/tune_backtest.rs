@@ -3,12 +3,16 @@
 
 use biga_lib::db::connection::create_pool;
 use biga_lib::db::models::HistoricalData;
-use biga_lib::db::repository::{get_recent_historical_data_for_symbols, get_symbols_with_min_bars};
+use biga_lib::db::repository::{
+    get_recent_closes_exact, get_recent_historical_data_for_symbols, get_symbols_with_min_bars,
+};
 use biga_lib::prediction::backtest::metrics::{compute_metrics, BacktestSample};
 use biga_lib::prediction::backtest::{run_backtest, BacktestObservation};
 use biga_lib::prediction::cross_section::{daily_bias_from_rank, walk_forward_rank_signals};
+use biga_lib::prediction::factor::BenchmarkCloses;
 use biga_lib::prediction::model::inference::MAX_ANALYSIS_DAYS;
 use biga_lib::prediction::strategy::professional_engine::get_stock_price_limits;
+use biga_lib::services::beta::DEFAULT_BETA_INDEX;
 use chrono::NaiveDate;
 use std::collections::HashMap;
 
@@ -111,6 +115,11 @@ async fn main() {
         .into_iter()
         .filter(|(_, hist)| hist.len() >= 120)
         .collect::<Vec<_>>();
+    let benchmark: BenchmarkCloses = get_recent_closes_exact(DEFAULT_BETA_INDEX, 3000, &pool)
+        .await
+        .unwrap_or_default()
+        .into_iter()
+        .collect();
     let eval_symbols = data
         .iter()
         .take(config.max_symbols)
@@ -123,7 +132,7 @@ async fn main() {
         let show_diagnostics = diagnostics_enabled_for_horizon(&config, horizon);
         let track_cross_hits = horizon == 5;
         let cross_bias = if horizon == 5 {
-            cross_section_bias_by_symbol_date(&data, horizon, 120)
+            cross_section_bias_by_symbol_date(&data, &benchmark, horizon, 120)
         } else {
             HashMap::new()
         };
@@ -166,7 +175,7 @@ async fn main() {
         let mut ma5_bullish_threshold_sweeps = one_day_ma5_bullish_threshold_sweeps();
         let mut seven_mid_reversal_sweep = SevenDayMidReversalSweep::default();
         let cross_sweeps = if cross_sweep_enabled(show_diagnostics, horizon) {
-            cross_bias_sweeps_by_symbol_date(&data, horizon, 120, &[0.005, 0.01, 0.02, 0.03])
+            cross_bias_sweeps_by_symbol_date(&data, &benchmark, horizon, 120, &[0.005, 0.01, 0.02, 0.03])
         } else {
             Vec::new()
         };
@@ -1156,10 +1165,11 @@ fn print_horizon_symbol_diagnostics(horizon: usize, reports: &[(String, f64, f64
 
 fn cross_section_bias_by_symbol_date(
     data: &[(String, Vec<biga_lib::db::models::HistoricalData>)],
+    benchmark: &BenchmarkCloses,
     horizon: usize,
     window: usize,
 ) -> BiasMap {
-    walk_forward_rank_signals(data, horizon, window)
+    walk_forward_rank_signals(data, benchmark, horizon, window)
         .into_iter()
         .filter_map(|signal| {
             let daily_bias = daily_bias_from_rank(signal.rank, signal.total);
@@ -1170,11 +1180,12 @@ fn cross_section_bias_by_symbol_date(
 
 fn cross_bias_sweeps_by_symbol_date(
     data: &[(String, Vec<biga_lib::db::models::HistoricalData>)],
+    benchmark: &BenchmarkCloses,
     horizon: usize,
     window: usize,
     magnitudes: &[f64],
 ) -> Vec<(f64, BiasMap)> {
-    let signals = walk_forward_rank_signals(data, horizon, window);
+    let signals = walk_forward_rank_signals(data, benchmark, horizon, window);
     magnitudes
         .iter()
         .map(|&magnitude| {
@@ -0,0 +1,10 @@
+-- 个股因子准确率（自适应权重）。每个因子保存方向判断正确率的指数移动平均，
+-- 由 AdaptiveWeightOptimizer 换算为多因子评分权重，使权重随个股特性个性化。
+CREATE TABLE IF NOT EXISTS factor_weights (
+    symbol       TEXT NOT NULL,
+    factor_name  TEXT NOT NULL,
+    accuracy     REAL NOT NULL,
+    sample_count INTEGER NOT NULL DEFAULT 0,
+    updated_at   TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (symbol, factor_name)
+);
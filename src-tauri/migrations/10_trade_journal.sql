@@ -0,0 +1,17 @@
+-- 交易日志：记录用户依据预测采取的操作，actual_price 由后台对账任务在
+-- 次一交易日收盘后回填，用于统计真实（样本外）预测准确率。
+CREATE TABLE IF NOT EXISTS trade_journal (
+    id               INTEGER PRIMARY KEY AUTOINCREMENT,
+    stock_code       TEXT NOT NULL,
+    action_date      DATE NOT NULL,
+    predicted_signal TEXT NOT NULL,
+    predicted_price  REAL NOT NULL,
+    actual_price     REAL,
+    action_taken     TEXT NOT NULL,
+    result           TEXT,
+    notes            TEXT,
+    created_at       TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_trade_journal_stock_date
+    ON trade_journal (stock_code, action_date);
@@ -0,0 +1,21 @@
+-- 技术指标参数预设。内置预设（DayTrading / SwingTrading / LongTermInvesting）
+-- 由代码定义不落库，此表仅保存用户自定义预设；当前选中的预设名记在 app_settings。
+CREATE TABLE IF NOT EXISTS indicator_presets (
+    name             TEXT PRIMARY KEY,
+    rsi_period       INTEGER NOT NULL,
+    macd_fast        INTEGER NOT NULL,
+    macd_slow        INTEGER NOT NULL,
+    macd_signal      INTEGER NOT NULL,
+    bollinger_period INTEGER NOT NULL,
+    bollinger_std    REAL NOT NULL,
+    kdj_period       INTEGER NOT NULL,
+    atr_period       INTEGER NOT NULL,
+    created_at       TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+-- 应用级键值设置
+CREATE TABLE IF NOT EXISTS app_settings (
+    key        TEXT PRIMARY KEY,
+    value      TEXT NOT NULL,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
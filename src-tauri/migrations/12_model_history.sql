@@ -0,0 +1,15 @@
+-- 模型重训练记录：每次手动或自动重训练写入一行，跟踪准确率随时间的变化。
+CREATE TABLE IF NOT EXISTS model_history (
+    id           INTEGER PRIMARY KEY AUTOINCREMENT,
+    model_id     TEXT NOT NULL,
+    stock_code   TEXT NOT NULL,
+    source       TEXT NOT NULL,
+    old_accuracy REAL,
+    new_accuracy REAL NOT NULL,
+    mae          REAL,
+    rmse         REAL,
+    trained_at   TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_model_history_model_time
+    ON model_history (model_id, trained_at);
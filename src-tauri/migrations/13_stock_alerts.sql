@@ -0,0 +1,13 @@
+-- 个股预警：alert_type 以 JSON 保存预警条件（价格阈值 / 指标背离），
+-- 后台监控任务触发后置 active = 0 并记录触发时间，避免同一条件重复通知。
+CREATE TABLE IF NOT EXISTS stock_alerts (
+    id                INTEGER PRIMARY KEY AUTOINCREMENT,
+    stock_code        TEXT NOT NULL,
+    alert_type        TEXT NOT NULL,
+    active            INTEGER NOT NULL DEFAULT 1,
+    last_triggered_at TIMESTAMP,
+    created_at        TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_stock_alerts_active
+    ON stock_alerts (active, stock_code);
@@ -0,0 +1,7 @@
+-- 常用查询索引：按股票取最近生成的预测记录。迁移完成后由启动流程执行 ANALYZE 更新统计信息。
+-- 按股票取最近 N 根K线（ORDER BY date DESC LIMIT N）直接走 historical_data 的主键
+-- (symbol, date) 索引反向扫描，早期版本建的重复索引在此删除。
+DROP INDEX IF EXISTS idx_historical_stock_date;
+
+CREATE INDEX IF NOT EXISTS idx_predictions_stock
+    ON stock_predictions (symbol, created_at DESC);
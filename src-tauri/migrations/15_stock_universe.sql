@@ -0,0 +1,16 @@
+-- 全市场股票池：系统化筛选与组合构建的候选范围。
+-- code 为规范 6 位代码；exchange 为 SH / SZ / BSE；market_cap_category 按流通市值分为
+-- 大盘 / 中盘 / 小盘（无股本数据时为空串）；更新时本次接口未返回的股票置 is_active = 0。
+CREATE TABLE IF NOT EXISTS stock_universe (
+    code                TEXT PRIMARY KEY,
+    name                TEXT NOT NULL,
+    exchange            TEXT NOT NULL,
+    industry            TEXT NOT NULL DEFAULT '',
+    market_cap_category TEXT NOT NULL DEFAULT '',
+    is_st               INTEGER NOT NULL DEFAULT 0,
+    is_active           INTEGER NOT NULL DEFAULT 1,
+    updated_at          TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_stock_universe_filter
+    ON stock_universe (is_active, exchange, market_cap_category);
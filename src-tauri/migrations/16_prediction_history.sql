@@ -0,0 +1,21 @@
+-- 预测准确率跟踪：每次预测按目标日写入一行，目标日过后由后台任务回填实际价格。
+-- 同一模型同日对同一目标日重复预测时覆盖为最新一次。
+CREATE TABLE IF NOT EXISTS prediction_history (
+    id                    INTEGER PRIMARY KEY AUTOINCREMENT,
+    stock_code            TEXT NOT NULL,
+    model_id              TEXT NOT NULL,
+    prediction_date       DATE NOT NULL,
+    target_date           DATE NOT NULL,
+    base_price            REAL NOT NULL,
+    predicted_price       REAL NOT NULL,
+    predicted_direction   TEXT NOT NULL,
+    actual_price          REAL,
+    actual_direction      TEXT,
+    was_direction_correct INTEGER,
+    price_error_pct       REAL,
+    created_at            TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (stock_code, model_id, prediction_date, target_date)
+);
+
+CREATE INDEX IF NOT EXISTS idx_prediction_history_stock_target
+    ON prediction_history (stock_code, target_date);
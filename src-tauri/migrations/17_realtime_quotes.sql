@@ -0,0 +1,13 @@
+-- 实时行情快照：每次拉取 ssjy 行情写入一行，用于当日分时走势与最新报价。
+-- 同一时间戳重复拉取时覆盖；过期快照在写入时按保留天数清理。
+CREATE TABLE IF NOT EXISTS realtime_quotes (
+    stock_code TEXT NOT NULL,
+    timestamp  TIMESTAMP NOT NULL,
+    price      REAL NOT NULL,
+    change_pct REAL NOT NULL,
+    volume     REAL NOT NULL,
+    turnover   REAL NOT NULL,
+    bid_price  REAL,
+    ask_price  REAL,
+    PRIMARY KEY (stock_code, timestamp)
+);
@@ -0,0 +1,17 @@
+-- 走步回测逐笔交易：记录入场时的模型、MACD、量能与趋势信号，用于按信号来源做收益归因。
+CREATE TABLE IF NOT EXISTS backtest_trades (
+    id            INTEGER PRIMARY KEY AUTOINCREMENT,
+    backtest_id   TEXT NOT NULL,
+    stock_code    TEXT NOT NULL,
+    entry_date    DATE NOT NULL,
+    exit_date     DATE NOT NULL,
+    pnl_pct       REAL NOT NULL,
+    model_signal  TEXT NOT NULL,
+    macd_signal   TEXT NOT NULL,
+    volume_signal TEXT NOT NULL,
+    trend_state   TEXT NOT NULL,
+    created_at    TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_backtest_trades_backtest_id
+    ON backtest_trades (backtest_id);
@@ -0,0 +1,9 @@
+-- 在线学习配置：启用后每日用已回填实际价格的预测误差对模型权重做小批量梯度更新。
+-- last_record_id 为已消费的 prediction_history 最大 id，不足一个批次的记录留待下次。
+CREATE TABLE IF NOT EXISTS online_learning (
+    model_id       TEXT PRIMARY KEY,
+    learning_rate  REAL NOT NULL,
+    enabled        INTEGER NOT NULL DEFAULT 1,
+    last_record_id INTEGER NOT NULL DEFAULT 0,
+    updated_at     TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
@@ -0,0 +1,14 @@
+-- 止损 / 止盈单：order_type 为 StopLoss / TakeProfit，status 为 active / triggered / cancelled。
+-- 后台监控按实时行情检查 active 单，触发后记录触发时间并置为 triggered，避免重复执行。
+CREATE TABLE IF NOT EXISTS stop_orders (
+    id            INTEGER PRIMARY KEY AUTOINCREMENT,
+    stock_code    TEXT NOT NULL,
+    order_type    TEXT NOT NULL,
+    trigger_price REAL NOT NULL,
+    created_at    TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    triggered_at  TIMESTAMP,
+    status        TEXT NOT NULL DEFAULT 'active'
+);
+
+CREATE INDEX IF NOT EXISTS idx_stop_orders_status
+    ON stop_orders (status, stock_code);
@@ -0,0 +1,3 @@
+-- 股票搜索：股票池名称的拼音首字母缩写（如 平安银行 → payh），供前端联想搜索。
+-- 已有记录为空串，下次刷新股票池时回填。
+ALTER TABLE stock_universe ADD COLUMN pinyin_abbr TEXT NOT NULL DEFAULT '';
@@ -0,0 +1,322 @@
+//! 公司事件日历
+//!
+//! 取自东方财富数据中心：定期报告预约披露日、分红除权除息日、股东大会与限售股解禁。
+//! 各类事件分别请求，单个接口失败只记录日志，不影响其余事件。
+//! 事件按股票每日缓存，同一交易日内重复查询不再联网。
+
+use crate::error::AppError;
+use crate::utils::canonical_stock_symbol;
+use chrono::{Duration, Local, NaiveDate};
+use futures::future::join_all;
+use log::warn;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+// 东方财富数据中心通用报表接口
+const DATACENTER_API: &str = "https://datacenter-web.eastmoney.com/api/data/v1/get";
+const CALENDAR_PAGE_SIZE: usize = 50;
+/// 事件请求超时：事件仅用于风险提示，不应拖慢预测流程
+pub const CALENDAR_REQUEST_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5);
+/// 默认向前查看的自然日数
+pub const DEFAULT_EVENT_DAYS_AHEAD: usize = 30;
+
+/// 按股票缓存的当日查询结果：(查询日期, 查询的前瞻天数, 事件)
+type CachedEvents = (NaiveDate, usize, Vec<CorporateEvent>);
+
+static EVENT_CACHE: Mutex<Option<HashMap<String, CachedEvents>>> = Mutex::new(None);
+
+/// 公司事件类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EventType {
+    /// 定期报告（季报/半年报/年报）披露
+    Earnings,
+    /// 分红除权除息
+    ExDividend,
+    /// 股东大会
+    ShareholderMeeting,
+    /// 限售股解禁
+    ShareUnlock,
+}
+
+impl EventType {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Earnings => "定期报告披露",
+            Self::ExDividend => "除权除息",
+            Self::ShareholderMeeting => "股东大会",
+            Self::ShareUnlock => "限售股解禁",
+        }
+    }
+}
+
+/// 公司事件
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CorporateEvent {
+    pub event_type: EventType,
+    pub expected_date: NaiveDate,
+    /// 对股价的可能影响说明
+    pub expected_impact: Option<String>,
+}
+
+/// 一类事件对应的数据中心报表
+struct EventSource {
+    event_type: EventType,
+    report_name: &'static str,
+    date_column: &'static str,
+    /// 附加到影响说明中的方案描述列
+    detail_column: Option<&'static str>,
+    impact: &'static str,
+}
+
+const EVENT_SOURCES: [EventSource; 4] = [
+    EventSource {
+        event_type: EventType::Earnings,
+        report_name: "RPT_PUBLIC_BS_APPOIN",
+        date_column: "FIRST_APPOINT_DATE",
+        detail_column: None,
+        impact: "业绩超预期或不及预期易引发跳空",
+    },
+    EventSource {
+        event_type: EventType::ExDividend,
+        report_name: "RPT_SHAREBONUS_DET",
+        date_column: "EX_DIVIDEND_DATE",
+        detail_column: Some("IMPL_PLAN_PROFILE"),
+        impact: "除权除息日股价按方案下调，技术指标可能失真",
+    },
+    EventSource {
+        event_type: EventType::ShareholderMeeting,
+        report_name: "RPT_GENERALMEETING_DETAIL",
+        date_column: "MEETING_DATE",
+        detail_column: Some("MEETING_TITLE"),
+        impact: "重大议案表决结果可能引发波动",
+    },
+    EventSource {
+        event_type: EventType::ShareUnlock,
+        report_name: "RPT_LIFT_STAGE",
+        date_column: "FREE_DATE",
+        detail_column: None,
+        impact: "解禁股上市流通，短期抛压增加",
+    },
+];
+
+#[derive(Debug, Deserialize)]
+struct DatacenterResponse {
+    result: Option<DatacenterResult>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DatacenterResult {
+    #[serde(default)]
+    data: Vec<Map<String, Value>>,
+}
+
+async fn fetch_event_rows(
+    source: &EventSource,
+    code: &str,
+) -> Result<Vec<Map<String, Value>>, AppError> {
+    let filter = format!("(SECURITY_CODE=\"{code}\")");
+    let page_size = CALENDAR_PAGE_SIZE.to_string();
+    let response = reqwest::Client::new()
+        .get(DATACENTER_API)
+        .query(&[
+            ("reportName", source.report_name),
+            ("columns", "ALL"),
+            ("filter", filter.as_str()),
+            ("sortColumns", source.date_column),
+            ("sortTypes", "-1"),
+            ("pageSize", page_size.as_str()),
+            ("pageNumber", "1"),
+            ("source", "WEB"),
+            ("client", "WEB"),
+        ])
+        .timeout(CALENDAR_REQUEST_TIMEOUT)
+        .send()
+        .await?;
+
+    if !response.status().is_success() {
+        return Err(AppError::InvalidInput(format!(
+            "获取{}失败: {}",
+            source.event_type.label(),
+            response.status()
+        )));
+    }
+
+    let body: DatacenterResponse = response.json().await.map_err(|e| {
+        AppError::DeserializationError(format!("{}数据解析失败: {e}", source.event_type.label()))
+    })?;
+    // 无记录时 result 为 null
+    Ok(body.result.map(|result| result.data).unwrap_or_default())
+}
+
+/// 数据中心日期列形如 "2026-04-25 00:00:00"
+fn parse_row_date(row: &Map<String, Value>, column: &str) -> Option<NaiveDate> {
+    let text = row.get(column)?.as_str()?;
+    NaiveDate::parse_from_str(text.get(..10)?, "%Y-%m-%d").ok()
+}
+
+/// 把报表记录转为 `[from, to]` 区间内的事件（同一日期只保留一条）
+fn parse_event_rows(
+    rows: &[Map<String, Value>],
+    source: &EventSource,
+    from: NaiveDate,
+    to: NaiveDate,
+) -> Vec<CorporateEvent> {
+    let mut events: Vec<CorporateEvent> = rows
+        .iter()
+        .filter_map(|row| {
+            let date = parse_row_date(row, source.date_column)?;
+            if date < from || date > to {
+                return None;
+            }
+            let detail = source
+                .detail_column
+                .and_then(|column| row.get(column)?.as_str())
+                .map(str::trim)
+                .filter(|detail| !detail.is_empty());
+            let impact = match detail {
+                Some(detail) => format!("{detail}：{}", source.impact),
+                None => source.impact.to_string(),
+            };
+            Some(CorporateEvent {
+                event_type: source.event_type,
+                expected_date: date,
+                expected_impact: Some(impact),
+            })
+        })
+        .collect();
+    events.sort_by_key(|event| event.expected_date);
+    events.dedup_by_key(|event| event.expected_date);
+    events
+}
+
+/// 当日已查询过不短于 `days_ahead` 的窗口时，取缓存中 `until` 之前的事件
+fn cached_events(
+    code: &str,
+    today: NaiveDate,
+    days_ahead: usize,
+    until: NaiveDate,
+) -> Option<Vec<CorporateEvent>> {
+    let cache = EVENT_CACHE.lock().ok()?;
+    let (date, cached_days, events) = cache.as_ref()?.get(code)?;
+    (*date == today && *cached_days >= days_ahead).then(|| {
+        events
+            .iter()
+            .filter(|event| event.expected_date <= until)
+            .cloned()
+            .collect()
+    })
+}
+
+/// 写入当日缓存，并清理此前日期的记录
+fn cache_events(code: String, today: NaiveDate, days_ahead: usize, events: &[CorporateEvent]) {
+    if let Ok(mut cache) = EVENT_CACHE.lock() {
+        let cache = cache.get_or_insert_with(HashMap::new);
+        cache.retain(|_, (date, _, _)| *date == today);
+        cache.insert(code, (today, days_ahead, events.to_vec()));
+    }
+}
+
+/// 获取个股未来 `days_ahead` 个自然日内（含今日）的公司事件，按日期升序。
+///
+/// 各类事件接口独立请求，部分失败时返回其余事件；全部失败时返回最后一个错误。
+/// 全部接口成功的结果按股票缓存至当日结束。
+pub async fn fetch_upcoming_events(
+    stock_code: &str,
+    days_ahead: usize,
+) -> Result<Vec<CorporateEvent>, AppError> {
+    let code = canonical_stock_symbol(stock_code);
+    let today = Local::now().date_naive();
+    let until = today + Duration::days(days_ahead as i64);
+    if let Some(events) = cached_events(&code, today, days_ahead, until) {
+        return Ok(events);
+    }
+
+    let results = join_all(EVENT_SOURCES.iter().map(|source| fetch_event_rows(source, &code))).await;
+    let mut events = Vec::new();
+    let mut last_error = None;
+    let mut succeeded = 0;
+    for (source, result) in EVENT_SOURCES.iter().zip(results) {
+        match result {
+            Ok(rows) => {
+                succeeded += 1;
+                events.extend(parse_event_rows(&rows, source, today, until));
+            }
+            Err(e) => {
+                warn!("获取 {code} {}失败: {e}", source.event_type.label());
+                last_error = Some(e);
+            }
+        }
+    }
+    if let (0, Some(e)) = (succeeded, last_error) {
+        return Err(e);
+    }
+    events.sort_by_key(|event| event.expected_date);
+    if succeeded == EVENT_SOURCES.len() {
+        cache_events(code, today, days_ahead, &events);
+    }
+    Ok(events)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Datelike;
+    use serde_json::json;
+
+    fn row(value: Value) -> Map<String, Value> {
+        value.as_object().unwrap().clone()
+    }
+
+    #[test]
+    fn test_parse_event_rows_filters_window_and_adds_detail() {
+        let from = NaiveDate::from_ymd_opt(2026, 4, 1).unwrap();
+        let to = NaiveDate::from_ymd_opt(2026, 4, 30).unwrap();
+        let dividend = &EVENT_SOURCES[1];
+        let rows = vec![
+            row(json!({"EX_DIVIDEND_DATE": "2026-04-20 00:00:00", "IMPL_PLAN_PROFILE": "10派20元(含税)"})),
+            row(json!({"EX_DIVIDEND_DATE": "2025-06-20 00:00:00", "IMPL_PLAN_PROFILE": "10派18元"})),
+            row(json!({"EX_DIVIDEND_DATE": null})),
+        ];
+        let events = parse_event_rows(&rows, dividend, from, to);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].event_type, EventType::ExDividend);
+        assert_eq!(events[0].expected_date, NaiveDate::from_ymd_opt(2026, 4, 20).unwrap());
+        assert!(events[0].expected_impact.as_deref().unwrap().starts_with("10派20元(含税)："));
+
+        // 无方案列的事件使用默认说明，同日重复记录只保留一条
+        let earnings = &EVENT_SOURCES[0];
+        let rows = vec![
+            row(json!({"FIRST_APPOINT_DATE": "2026-04-25 00:00:00"})),
+            row(json!({"FIRST_APPOINT_DATE": "2026-04-25 00:00:00"})),
+            row(json!({"FIRST_APPOINT_DATE": "2026-04-10 00:00:00"})),
+        ];
+        let events = parse_event_rows(&rows, earnings, from, to);
+        let dates: Vec<u32> = events.iter().map(|e| e.expected_date.day()).collect();
+        assert_eq!(dates, vec![10, 25]);
+        assert_eq!(events[0].expected_impact.as_deref(), Some(earnings.impact));
+    }
+
+    #[test]
+    fn test_event_cache_per_stock_and_day() {
+        let today = NaiveDate::from_ymd_opt(2026, 4, 1).unwrap();
+        let event = |day| CorporateEvent {
+            event_type: EventType::Earnings,
+            expected_date: NaiveDate::from_ymd_opt(2026, 4, day).unwrap(),
+            expected_impact: None,
+        };
+        let until = |days| today + Duration::days(days);
+        cache_events("000999".to_string(), today, 30, &[event(5), event(25)]);
+
+        // 窗口不长于缓存窗口时命中并按截止日过滤
+        let hit = cached_events("000999", today, 10, until(10)).unwrap();
+        assert_eq!(hit.len(), 1);
+        assert_eq!(cached_events("000999", today, 30, until(30)).unwrap().len(), 2);
+        // 窗口更长、次日或其他股票均需重新查询
+        assert!(cached_events("000999", today, 60, until(60)).is_none());
+        assert!(cached_events("000999", today.succ_opt().unwrap(), 10, until(10)).is_none());
+        assert!(cached_events("000998", today, 10, until(10)).is_none());
+    }
+}
@@ -0,0 +1,220 @@
+//! 场内 ETF 持仓与净值
+//!
+//! 持仓取自天天基金移动端持仓接口，净值取自东方财富基金历史净值接口；
+//! 均以 6 位基金代码查询，不需要 zhitu token。
+
+use crate::error::AppError;
+use crate::utils::canonical_stock_symbol;
+use chrono::NaiveDate;
+use serde::Deserialize;
+
+// 基金持仓（前十大重仓股）
+const ETF_HOLDINGS_API: &str = "https://fundmobapi.eastmoney.com/FundMNewApi/FundMNInverstPosition";
+// 基金历史单位净值
+const ETF_NAV_API: &str = "https://api.fund.eastmoney.com/f10/lsjz";
+// 历史净值接口校验 Referer
+const ETF_NAV_REFERER: &str = "https://fundf10.eastmoney.com/";
+const ETF_REQUEST_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(15);
+
+#[derive(Debug, Deserialize)]
+struct HoldingsResponse {
+    #[serde(rename = "Datas")]
+    datas: Option<HoldingsData>,
+}
+
+#[derive(Debug, Deserialize)]
+struct HoldingsData {
+    #[serde(rename = "fundStocks", default)]
+    fund_stocks: Option<Vec<HoldingItem>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct HoldingItem {
+    /// 股票代码
+    #[serde(rename = "GPDM", default)]
+    code: String,
+    /// 占净值比例（%），字符串
+    #[serde(rename = "JZBL", default)]
+    weight: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct NavResponse {
+    #[serde(rename = "Data")]
+    data: Option<NavData>,
+}
+
+#[derive(Debug, Deserialize)]
+struct NavData {
+    #[serde(rename = "LSJZList", default)]
+    list: Vec<NavItem>,
+}
+
+#[derive(Debug, Deserialize)]
+struct NavItem {
+    /// 净值日期
+    #[serde(rename = "FSRQ", default)]
+    date: String,
+    /// 单位净值，字符串
+    #[serde(rename = "DWJZ", default)]
+    nav: String,
+}
+
+/// ETF 最新一期重仓股：(股票代码, 占净值比例%)，按权重降序
+pub async fn fetch_etf_holdings(etf_code: &str) -> Result<Vec<(String, f64)>, AppError> {
+    let code = canonical_stock_symbol(etf_code);
+    let response = reqwest::Client::new()
+        .get(ETF_HOLDINGS_API)
+        .query(&[
+            ("FCODE", code.as_str()),
+            ("deviceid", "Wap"),
+            ("plat", "Wap"),
+            ("product", "EFund"),
+            ("version", "2.0.0"),
+        ])
+        .timeout(ETF_REQUEST_TIMEOUT)
+        .send()
+        .await?;
+
+    if !response.status().is_success() {
+        return Err(AppError::InvalidInput(format!(
+            "获取ETF持仓失败: {}",
+            response.status()
+        )));
+    }
+
+    let body: HoldingsResponse = response
+        .json()
+        .await
+        .map_err(|e| AppError::DeserializationError(format!("ETF持仓解析失败: {e}")))?;
+    let items = body
+        .datas
+        .and_then(|data| data.fund_stocks)
+        .unwrap_or_default();
+    Ok(parse_holdings(items))
+}
+
+fn parse_holdings(items: Vec<HoldingItem>) -> Vec<(String, f64)> {
+    let mut holdings: Vec<(String, f64)> = items
+        .into_iter()
+        .filter_map(|item| {
+            let weight = item.weight.trim().parse::<f64>().ok()?;
+            let code = canonical_stock_symbol(&item.code);
+            (!code.is_empty()).then_some((code, weight))
+        })
+        .collect();
+    holdings.sort_by(|a, b| b.1.total_cmp(&a.1));
+    holdings
+}
+
+/// ETF 最近 `days` 条单位净值：(净值日期, 单位净值)，按日期正序
+pub async fn fetch_etf_nav_history(
+    etf_code: &str,
+    days: usize,
+) -> Result<Vec<(NaiveDate, f64)>, AppError> {
+    let code = canonical_stock_symbol(etf_code);
+    let page_size = days.max(1).to_string();
+    let response = reqwest::Client::new()
+        .get(ETF_NAV_API)
+        .header(reqwest::header::REFERER, ETF_NAV_REFERER)
+        .query(&[
+            ("fundCode", code.as_str()),
+            ("pageIndex", "1"),
+            ("pageSize", page_size.as_str()),
+        ])
+        .timeout(ETF_REQUEST_TIMEOUT)
+        .send()
+        .await?;
+
+    if !response.status().is_success() {
+        return Err(AppError::InvalidInput(format!(
+            "获取ETF净值失败: {}",
+            response.status()
+        )));
+    }
+
+    let body: NavResponse = response
+        .json()
+        .await
+        .map_err(|e| AppError::DeserializationError(format!("ETF净值解析失败: {e}")))?;
+    Ok(parse_nav_items(body.data.map(|data| data.list).unwrap_or_default()))
+}
+
+fn parse_nav_items(items: Vec<NavItem>) -> Vec<(NaiveDate, f64)> {
+    let mut navs: Vec<(NaiveDate, f64)> = items
+        .into_iter()
+        .filter_map(|item| {
+            let date = NaiveDate::parse_from_str(item.date.trim(), "%Y-%m-%d").ok()?;
+            let nav = item.nav.trim().parse::<f64>().ok().filter(|nav| *nav > 0.0)?;
+            Some((date, nav))
+        })
+        .collect();
+    navs.sort_by_key(|(date, _)| *date);
+    navs
+}
+
+/// 将净值按交易日对齐：每个交易日取不晚于当日的最近一期净值，之前无净值时为 0
+pub fn align_nav_to_dates(dates: &[NaiveDate], navs: &[(NaiveDate, f64)]) -> Vec<f64> {
+    let mut aligned = Vec::with_capacity(dates.len());
+    let mut cursor = 0;
+    let mut current = 0.0;
+    for date in dates {
+        while cursor < navs.len() && navs[cursor].0 <= *date {
+            current = navs[cursor].1;
+            cursor += 1;
+        }
+        aligned.push(current);
+    }
+    aligned
+}
+
+/// ETF 折溢价率（%）：(收盘价 - 单位净值) / 单位净值 × 100；净值无效时为 None
+pub fn premium_discount_pct(close: f64, nav: f64) -> Option<f64> {
+    (nav > 0.0).then(|| (close - nav) / nav * 100.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 3, day).unwrap()
+    }
+
+    #[test]
+    fn test_parse_holdings_sorted_by_weight() {
+        let item = |code: &str, weight: &str| HoldingItem {
+            code: code.to_string(),
+            weight: weight.to_string(),
+        };
+        let holdings = parse_holdings(vec![
+            item("601318", "6.12"),
+            item("600519", "9.78"),
+            item("600036", "--"),
+        ]);
+        assert_eq!(
+            holdings,
+            vec![("600519".to_string(), 9.78), ("601318".to_string(), 6.12)]
+        );
+    }
+
+    #[test]
+    fn test_parse_and_align_nav() {
+        let item = |date: &str, nav: &str| NavItem {
+            date: date.to_string(),
+            nav: nav.to_string(),
+        };
+        let navs = parse_nav_items(vec![
+            item("2026-03-04", "2.6200"),
+            item("2026-03-02", "2.6000"),
+            item("2026-03-03", ""),
+        ]);
+        assert_eq!(navs, vec![(date(2), 2.6), (date(4), 2.62)]);
+
+        let aligned = align_nav_to_dates(&[date(1), date(2), date(3), date(4), date(5)], &navs);
+        assert_eq!(aligned, vec![0.0, 2.6, 2.6, 2.62, 2.62]);
+
+        assert_eq!(premium_discount_pct(2.6, aligned[0]), None);
+        assert!((premium_discount_pct(2.652, aligned[1]).unwrap() - 2.0).abs() < 1e-9);
+    }
+}
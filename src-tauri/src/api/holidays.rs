@@ -0,0 +1,132 @@
+//! 中国法定节假日
+//!
+//! 取自 timor.tech 节假日接口，按年缓存到 `~/.biga/holidays_{year}.json`；
+//! 缓存命中时不再请求接口。
+
+use crate::error::AppError;
+use crate::utils::date::HolidayCalendar;
+use chrono::NaiveDate;
+use log::{info, warn};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+// 全年节假日与调休
+const HOLIDAY_API: &str = "https://timor.tech/api/holiday/year";
+const HOLIDAY_REQUEST_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(15);
+
+#[derive(Debug, Deserialize)]
+struct HolidayResponse {
+    code: i32,
+    /// 键为 "MM-DD"
+    #[serde(default)]
+    holiday: Option<HashMap<String, HolidayItem>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct HolidayItem {
+    /// true 为节假日，false 为调休补班
+    holiday: bool,
+    date: String,
+}
+
+fn cache_path(year: i32) -> PathBuf {
+    let home = dirs::home_dir().unwrap_or_else(|| PathBuf::from("."));
+    home.join(".biga").join(format!("holidays_{year}.json"))
+}
+
+/// 获取某年的法定节假日（升序）；优先读取本地缓存，接口结果非空时写入缓存。
+///
+/// 次年安排通常在当年 11 月前后公布，公布前接口返回空列表，此时不缓存以便之后重试。
+pub async fn fetch_chinese_holidays(year: i32) -> Result<Vec<NaiveDate>, AppError> {
+    let path = cache_path(year);
+    if let Some(cached) = fs::read_to_string(&path)
+        .ok()
+        .and_then(|content| serde_json::from_str::<Vec<NaiveDate>>(&content).ok())
+    {
+        return Ok(cached);
+    }
+
+    let response = reqwest::Client::new()
+        .get(format!("{HOLIDAY_API}/{year}"))
+        .timeout(HOLIDAY_REQUEST_TIMEOUT)
+        .send()
+        .await?;
+    if !response.status().is_success() {
+        return Err(AppError::InvalidInput(format!(
+            "获取 {year} 年节假日失败: {}",
+            response.status()
+        )));
+    }
+    let body: HolidayResponse = response
+        .json()
+        .await
+        .map_err(|e| AppError::DeserializationError(format!("节假日解析失败: {e}")))?;
+    if body.code != 0 {
+        return Err(AppError::InvalidInput(format!(
+            "节假日接口返回错误码 {}",
+            body.code
+        )));
+    }
+
+    let holidays = parse_holidays(body.holiday.unwrap_or_default());
+    if !holidays.is_empty() {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let content = serde_json::to_string(&holidays)
+            .map_err(|e| AppError::DeserializationError(e.to_string()))?;
+        fs::write(&path, content)?;
+    }
+    Ok(holidays)
+}
+
+fn parse_holidays(items: HashMap<String, HolidayItem>) -> Vec<NaiveDate> {
+    let mut holidays: Vec<NaiveDate> = items
+        .into_values()
+        .filter(|item| item.holiday)
+        .filter_map(|item| NaiveDate::parse_from_str(item.date.trim(), "%Y-%m-%d").ok())
+        .collect();
+    holidays.sort_unstable();
+    holidays
+}
+
+/// 加载各年份节假日到日历；失败或尚未公布的年份保留内置规则
+pub async fn load_holiday_calendar(calendar: &HolidayCalendar, years: &[i32]) {
+    for &year in years {
+        match fetch_chinese_holidays(year).await {
+            Ok(holidays) if !holidays.is_empty() => {
+                info!("已加载 {year} 年节假日 {} 天", holidays.len());
+                calendar.set_year(year, holidays);
+            }
+            Ok(_) => info!("{year} 年节假日尚未公布，使用内置规则"),
+            Err(e) => warn!("获取 {year} 年节假日失败，使用内置规则: {e}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_holidays_skips_makeup_workdays() {
+        let body: HolidayResponse = serde_json::from_str(
+            r#"{"code":0,"holiday":{
+                "10-01":{"holiday":true,"name":"国庆节","wage":3,"date":"2025-10-01"},
+                "09-28":{"holiday":false,"name":"国庆节前补班","wage":1,"after":false,"target":"国庆节","date":"2025-09-28"},
+                "01-01":{"holiday":true,"name":"元旦","wage":3,"date":"2025-01-01"}
+            }}"#,
+        )
+        .unwrap();
+        assert_eq!(body.code, 0);
+        assert_eq!(
+            parse_holidays(body.holiday.unwrap()),
+            vec![
+                NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(),
+                NaiveDate::from_ymd_opt(2025, 10, 1).unwrap(),
+            ]
+        );
+    }
+}
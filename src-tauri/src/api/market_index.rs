@@ -0,0 +1,93 @@
+//! 主要指数实时点位
+//!
+//! 取自东方财富行情列表接口，一次请求上证指数、深证成指、沪深300 与科创50。
+
+use crate::error::AppError;
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::HashMap;
+
+const INDEX_QUOTE_API: &str = "https://push2.eastmoney.com/api/qt/ulist.np/get";
+/// 指数行情请求超时：仅用于概览展示，不应阻塞其余统计
+pub const INDEX_REQUEST_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// 东方财富 secid（市场.代码）
+pub const SHANGHAI_COMPOSITE: &str = "1.000001";
+pub const SHENZHEN_COMPONENT: &str = "0.399001";
+pub const CSI300: &str = "1.000300";
+pub const STAR50: &str = "1.000688";
+
+#[derive(Debug, Deserialize)]
+struct IndexQuoteResponse {
+    data: Option<IndexQuoteData>,
+}
+
+#[derive(Debug, Deserialize)]
+struct IndexQuoteData {
+    #[serde(default)]
+    diff: Vec<HashMap<String, Value>>,
+}
+
+/// 解析行情列表：f13 为市场、f12 为代码、f2 为最新点位（停牌或无数据时为 "-"）
+fn parse_index_quotes(rows: &[HashMap<String, Value>]) -> HashMap<String, f64> {
+    rows.iter()
+        .filter_map(|row| {
+            let market = row.get("f13")?.as_i64()?;
+            let code = row.get("f12")?.as_str()?;
+            let price = row.get("f2")?.as_f64().filter(|p| p.is_finite() && *p > 0.0)?;
+            Some((format!("{market}.{code}"), price))
+        })
+        .collect()
+}
+
+/// 获取指数最新点位，键为 secid；无数据的指数不出现在结果中
+pub async fn fetch_index_quotes(secids: &[&str]) -> Result<HashMap<String, f64>, AppError> {
+    let secids = secids.join(",");
+    let response = reqwest::Client::new()
+        .get(INDEX_QUOTE_API)
+        .query(&[
+            ("fltt", "2"),
+            ("secids", secids.as_str()),
+            ("fields", "f2,f12,f13,f14"),
+        ])
+        .timeout(INDEX_REQUEST_TIMEOUT)
+        .send()
+        .await?;
+
+    if !response.status().is_success() {
+        return Err(AppError::InvalidInput(format!(
+            "获取指数行情失败: {}",
+            response.status()
+        )));
+    }
+
+    let body: IndexQuoteResponse = response
+        .json()
+        .await
+        .map_err(|e| AppError::DeserializationError(format!("指数行情解析失败: {e}")))?;
+    Ok(body
+        .data
+        .map(|data| parse_index_quotes(&data.diff))
+        .unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_index_quotes() {
+        let rows: Vec<HashMap<String, Value>> = serde_json::from_value(json!([
+            {"f2": 3245.12, "f12": "000001", "f13": 1, "f14": "上证指数"},
+            {"f2": 10234.5, "f12": "399001", "f13": 0, "f14": "深证成指"},
+            {"f2": "-", "f12": "000688", "f13": 1, "f14": "科创50"}
+        ]))
+        .unwrap();
+        let quotes = parse_index_quotes(&rows);
+        assert_eq!(quotes.len(), 2);
+        assert_eq!(quotes.get(SHANGHAI_COMPOSITE), Some(&3245.12));
+        assert_eq!(quotes.get(SHENZHEN_COMPONENT), Some(&10234.5));
+        assert!(!quotes.contains_key(STAR50));
+    }
+}
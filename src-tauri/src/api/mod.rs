@@ -1 +1,6 @@
+pub mod corporate_calendar;
+pub mod etf;
+pub mod holidays;
+pub mod market_index;
+pub mod news;
 pub mod stock;
@@ -0,0 +1,209 @@
+//! 个股新闻资讯与关键词情绪评分
+//!
+//! 新闻列表取自东方财富个股资讯接口；情绪评分为标题关键词计数，
+//! 仅作为情绪因子的可选辅助信号，不代表语义理解。
+
+use crate::error::AppError;
+use crate::utils::canonical_stock_symbol;
+use chrono::{Duration, Local, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+
+// 东方财富个股资讯列表
+const NEWS_API: &str = "https://np-listapi.eastmoney.com/comm/web/getListInfo";
+const NEWS_PAGE_SIZE: usize = 100;
+/// 新闻请求超时：新闻仅为辅助信号，不应拖慢预测流程
+pub const NEWS_REQUEST_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5);
+/// 默认回看天数
+pub const DEFAULT_NEWS_DAYS: usize = 7;
+
+const POSITIVE_KEYWORDS: &[&str] = &[
+    "增长",
+    "突破",
+    "创新高",
+    "增持",
+    "回购",
+    "预增",
+    "中标",
+    "涨停",
+    "利好",
+    "超预期",
+];
+const NEGATIVE_KEYWORDS: &[&str] = &[
+    "下跌", "亏损", "风险", "减持", "预亏", "跌停", "利空", "立案", "处罚", "违规",
+];
+
+/// 新闻条目
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NewsItem {
+    pub title: String,
+    /// 发布时间（"%Y-%m-%d %H:%M:%S"）
+    pub publish_time: String,
+    pub source: String,
+    pub url: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct NewsResponse {
+    data: Option<NewsData>,
+}
+
+#[derive(Debug, Deserialize)]
+struct NewsData {
+    #[serde(default)]
+    list: Vec<NewsListItem>,
+}
+
+#[derive(Debug, Deserialize)]
+struct NewsListItem {
+    #[serde(rename = "Art_Title", default)]
+    title: String,
+    #[serde(rename = "Art_ShowTime", default)]
+    show_time: String,
+    #[serde(rename = "Art_MediaName", default)]
+    media_name: String,
+    #[serde(rename = "Art_Url", default)]
+    url: String,
+}
+
+/// 东方财富市场前缀：沪市（5/6/9 开头，含沪市 ETF）为 1，深市与北交所为 0
+fn eastmoney_market_code(stock_code: &str) -> String {
+    let code = canonical_stock_symbol(stock_code);
+    let market = if code.starts_with(['5', '6', '9']) {
+        1
+    } else {
+        0
+    };
+    format!("{market}.{code}")
+}
+
+/// 获取个股近 `days` 天的新闻列表（按发布时间倒序）
+pub async fn fetch_stock_news(stock_code: &str, days: usize) -> Result<Vec<NewsItem>, AppError> {
+    let page_size = NEWS_PAGE_SIZE.to_string();
+    let response = reqwest::Client::new()
+        .get(NEWS_API)
+        .query(&[
+            ("client", "web"),
+            ("type", "1"),
+            ("mTypeAndCode", eastmoney_market_code(stock_code).as_str()),
+            ("pageSize", page_size.as_str()),
+            ("pageIndex", "1"),
+        ])
+        .timeout(NEWS_REQUEST_TIMEOUT)
+        .send()
+        .await?;
+
+    if !response.status().is_success() {
+        return Err(AppError::InvalidInput(format!(
+            "获取新闻失败: {}",
+            response.status()
+        )));
+    }
+
+    let body: NewsResponse = response
+        .json()
+        .await
+        .map_err(|e| AppError::DeserializationError(format!("新闻数据解析失败: {e}")))?;
+    let items = body.data.map(|data| data.list).unwrap_or_default();
+    let since = Local::now().naive_local() - Duration::days(days as i64);
+    Ok(parse_news_items(items, since))
+}
+
+fn parse_news_items(items: Vec<NewsListItem>, since: NaiveDateTime) -> Vec<NewsItem> {
+    items
+        .into_iter()
+        .filter(|item| !item.title.is_empty())
+        .filter(|item| {
+            NaiveDateTime::parse_from_str(&item.show_time, "%Y-%m-%d %H:%M:%S")
+                .is_ok_and(|time| time >= since)
+        })
+        .map(|item| NewsItem {
+            title: item.title,
+            publish_time: item.show_time,
+            source: if item.media_name.is_empty() {
+                "东方财富".to_string()
+            } else {
+                item.media_name
+            },
+            url: item.url,
+        })
+        .collect()
+}
+
+/// 新闻标题关键词情绪分：(正面词数 - 负面词数) / (正面词数 + 负面词数)，范围 -1 ~ +1；
+/// 无新闻或未命中任何关键词时为 0
+pub fn score_news_sentiment(news: &[NewsItem]) -> f64 {
+    let count = |keywords: &[&str]| -> usize {
+        news.iter()
+            .map(|item| {
+                keywords
+                    .iter()
+                    .filter(|kw| item.title.contains(*kw))
+                    .count()
+            })
+            .sum()
+    };
+    let positive = count(POSITIVE_KEYWORDS) as f64;
+    let negative = count(NEGATIVE_KEYWORDS) as f64;
+    if positive + negative == 0.0 {
+        0.0
+    } else {
+        (positive - negative) / (positive + negative)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn news(title: &str) -> NewsItem {
+        NewsItem {
+            title: title.to_string(),
+            publish_time: "2026-01-05 09:30:00".to_string(),
+            source: "东方财富".to_string(),
+            url: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_score_news_sentiment() {
+        assert_eq!(score_news_sentiment(&[]), 0.0);
+        assert_eq!(score_news_sentiment(&[news("公司召开股东大会")]), 0.0);
+        assert_eq!(score_news_sentiment(&[news("业绩预增，股价创新高")]), 1.0);
+        assert_eq!(score_news_sentiment(&[news("大股东减持，股价下跌")]), -1.0);
+        // 1 个正面词、2 个负面词 → (1 - 2) / 3
+        let mixed = [news("营收增长"), news("引发亏损担忧"), news("提示风险")];
+        assert!((score_news_sentiment(&mixed) - (-1.0 / 3.0)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_parse_news_items_filters_by_time() {
+        let item = |title: &str, time: &str| NewsListItem {
+            title: title.to_string(),
+            show_time: time.to_string(),
+            media_name: String::new(),
+            url: "https://example.com".to_string(),
+        };
+        let since =
+            NaiveDateTime::parse_from_str("2026-01-01 00:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
+        let parsed = parse_news_items(
+            vec![
+                item("新", "2026-01-03 10:00:00"),
+                item("旧", "2025-12-20 10:00:00"),
+                item("", "2026-01-03 10:00:00"),
+                item("时间异常", "unknown"),
+            ],
+            since,
+        );
+        assert_eq!(parsed.len(), 1);
+        assert_eq!(parsed[0].title, "新");
+        assert_eq!(parsed[0].source, "东方财富");
+    }
+
+    #[test]
+    fn test_eastmoney_market_code() {
+        assert_eq!(eastmoney_market_code("600519.SH"), "1.600519");
+        assert_eq!(eastmoney_market_code("sz000001"), "0.000001");
+        assert_eq!(eastmoney_market_code("510050"), "1.510050");
+        assert_eq!(eastmoney_market_code("159915"), "0.159915");
+    }
+}
@@ -1,11 +1,12 @@
 use crate::db::models::{
     HistoricalData, HistoricalDataItem, RealtimeQuoteItem, StockFundamental, StockInfo,
-    StockInfoItem,
+    StockInfoItem, UniverseEntry,
 };
 use crate::error::AppError;
 use crate::config::api_token::resolve_api_token;
 use crate::utils::canonical_stock_symbol;
 use chrono::NaiveDate;
+use log::{debug, error, info};
 
 // 查看全部股票名称以及代码
 const ALL_SYMBOL_API: &str = "https://api.zhituapi.com/hs/list/all";
@@ -24,7 +25,12 @@ fn normalize_quote_symbol(symbol: &str) -> String {
 }
 
 pub async fn fetch_stock_infos() -> Result<Vec<StockInfo>, AppError> {
-    println!("开始获取股票信息...");
+    debug!("开始获取股票信息...");
+    parse_stock_info(fetch_symbol_list().await?)
+}
+
+/// 全部 A 股代码、名称与交易所（hs/list/all）
+async fn fetch_symbol_list() -> Result<Vec<StockInfoItem>, AppError> {
     let (token, _) = resolve_api_token().await?;
 
     let response = reqwest::Client::new()
@@ -35,14 +41,51 @@ pub async fn fetch_stock_infos() -> Result<Vec<StockInfo>, AppError> {
         .await?;
     
     if !response.status().is_success() {
-        println!("API请求失败: {}", response.status());
+        error!("API请求失败: {}", response.status());
         return Err(AppError::InvalidInput(format!("API请求失败: {}", response.status())));
     }
     
     let stock_infos: Vec<StockInfoItem> = response.json().await?;
-    println!("获取到 {} 条股票信息", stock_infos.len());
-    
-    parse_stock_info(stock_infos)
+    info!("获取到 {} 条股票信息", stock_infos.len());
+    Ok(stock_infos)
+}
+
+/// 交易所统一为 SH / SZ / BSE；接口字段无法识别时按代码前缀判断：
+/// 北交所为 4/8/92 开头，沪市为 5/6/9 开头（含 900 B 股），深市为 0-3 开头（含 200 B 股）
+pub(crate) fn normalize_exchange(exchange: &str, code: &str) -> String {
+    match exchange.trim().to_ascii_lowercase().as_str() {
+        "sh" => "SH",
+        "sz" => "SZ",
+        "bj" | "bse" => "BSE",
+        _ if code.starts_with("92") => "BSE",
+        _ if code.starts_with(['5', '6', '9']) => "SH",
+        _ if code.starts_with(['0', '1', '2', '3']) => "SZ",
+        _ => "BSE",
+    }
+    .to_string()
+}
+
+fn parse_universe_entry(item: StockInfoItem) -> UniverseEntry {
+    let code = canonical_stock_symbol(&item.symbol);
+    UniverseEntry {
+        exchange: normalize_exchange(&item.exchange, &code),
+        is_st: item.name.to_ascii_uppercase().contains("ST"),
+        name: item.name,
+        code,
+        industry: String::new(),
+        market_cap_category: String::new(),
+        is_active: true,
+    }
+}
+
+/// 全市场股票池（代码 / 名称 / 交易所 / ST 标记）。行业与市值分档不在该接口内，
+/// 由调用方用本地股票详情与股本数据补全。
+pub async fn fetch_universe_from_api() -> Result<Vec<UniverseEntry>, AppError> {
+    Ok(fetch_symbol_list()
+        .await?
+        .into_iter()
+        .map(parse_universe_entry)
+        .collect())
 }
 
 fn parse_stock_info(items: Vec<StockInfoItem>) -> Result<Vec<StockInfo>, AppError> {
@@ -59,7 +102,7 @@ fn parse_stock_info(items: Vec<StockInfoItem>) -> Result<Vec<StockInfo>, AppErro
 }
 
 pub async fn fetch_historical_data(symbol: &str) -> Result<Vec<HistoricalData>, AppError> {
-    println!("开始获取股票 {symbol} 的历史数据...");
+    debug!("开始获取股票 {symbol} 的历史数据...");
 
     let (token, _) = resolve_api_token().await?;
     let url = format!("{HISTORY_API}/{symbol}/d/n");
@@ -72,21 +115,21 @@ pub async fn fetch_historical_data(symbol: &str) -> Result<Vec<HistoricalData>,
         .await?;
     
     if !response.status().is_success() {
-        println!("API请求失败: {}", response.status());
+        error!("API请求失败: {}", response.status());
         return Err(AppError::InvalidInput(format!("获取历史数据失败: {}", response.status())));
     }
     
     let response_text = response.text().await?;
-    println!("API响应长度: {}", response_text.len());
+    debug!("API响应长度: {}", response_text.len());
     
     // 尝试解析JSON
     let historical_items: Vec<HistoricalDataItem> = serde_json::from_str(&response_text)
         .map_err(|e| {
-            println!("JSON解析失败: {e}");
+            error!("JSON解析失败: {e}");
             AppError::DeserializationError(format!("JSON解析失败: {e}"))
         })?;
     
-    println!("解析到 {} 条历史数据", historical_items.len());
+    info!("解析到 {} 条历史数据", historical_items.len());
     
     parse_historical_data(historical_items, symbol)
 }
@@ -325,6 +368,27 @@ mod tests {
         assert_eq!(normalize_quote_symbol("000001"), "000001");
     }
 
+    #[test]
+    fn test_parse_universe_entry() {
+        let entry = parse_universe_entry(StockInfoItem {
+            symbol: "sz000004".to_string(),
+            name: "*ST国华".to_string(),
+            exchange: "sz".to_string(),
+        });
+        assert_eq!(entry.code, "000004");
+        assert_eq!(entry.exchange, "SZ");
+        assert!(entry.is_st);
+
+        assert_eq!(normalize_exchange("", "600519"), "SH");
+        assert_eq!(normalize_exchange("bj", "830799"), "BSE");
+        assert_eq!(normalize_exchange("", "920001"), "BSE");
+        assert_eq!(normalize_exchange("", "430047"), "BSE");
+        assert_eq!(normalize_exchange("", "900901"), "SH");
+        assert_eq!(normalize_exchange("", "200002"), "SZ");
+        assert_eq!(normalize_exchange("", "510050"), "SH");
+        assert_eq!(normalize_exchange("", "159915"), "SZ");
+    }
+
     #[tokio::test]
     async fn validation_uses_a_fallback_after_a_timeout() {
         let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
@@ -0,0 +1,45 @@
+//! 个股预警命令
+//!
+//! 预警由后台监控任务（[`crate::services::alerts`]）检查，触发后推送系统通知并自动停用
+
+use crate::db::models::{AlertType, StockAlert};
+use crate::db::repository;
+use crate::error::AppError;
+use sqlx::SqlitePool;
+use tauri::State;
+
+/// 新建预警（价格阈值或 RSI/MACD 背离）
+#[tauri::command]
+pub async fn create_stock_alert(
+    stock_code: String,
+    alert_type: AlertType,
+    pool: State<'_, SqlitePool>,
+) -> Result<StockAlert, AppError> {
+    if stock_code.trim().is_empty() {
+        return Err(AppError::InvalidInput("股票代码不能为空".to_string()));
+    }
+    if let AlertType::PriceAbove { price } | AlertType::PriceBelow { price } = alert_type {
+        if !(price.is_finite() && price > 0.0) {
+            return Err(AppError::InvalidInput("预警价格必须为正数".to_string()));
+        }
+    }
+    let id = repository::insert_alert(&pool, stock_code.trim(), &alert_type).await?;
+    repository::get_alert(&pool, id)
+        .await?
+        .ok_or_else(|| AppError::InvalidInput(format!("预警 {id} 不存在")))
+}
+
+/// 查询预警，可按股票过滤
+#[tauri::command]
+pub async fn get_stock_alerts(
+    stock_code: Option<String>,
+    pool: State<'_, SqlitePool>,
+) -> Result<Vec<StockAlert>, AppError> {
+    repository::get_alerts(stock_code.as_deref(), &pool).await
+}
+
+/// 删除预警
+#[tauri::command]
+pub async fn delete_stock_alert(id: i64, pool: State<'_, SqlitePool>) -> Result<(), AppError> {
+    repository::delete_alert(&pool, id).await
+}
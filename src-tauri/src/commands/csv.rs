@@ -0,0 +1,125 @@
+use crate::csv::export;
+use crate::csv::import::{import_historical_csv, ImportResult};
+use crate::db::repository::get_recent_historical_data;
+use crate::error::AppError;
+use crate::prediction::model::inference;
+use crate::prediction::types::PredictionRequest;
+use log::error;
+use sqlx::SqlitePool;
+use std::path::PathBuf;
+use tauri::{AppHandle, State};
+use tauri_plugin_dialog::{DialogExt, FilePath};
+use tauri_plugin_opener::OpenerExt;
+
+fn into_local_path(file_path: FilePath) -> Result<PathBuf, AppError> {
+    file_path
+        .into_path()
+        .map_err(|e| AppError::InvalidInput(format!("无法识别所选文件路径: {e}")))
+}
+
+/// 弹出文件选择框选择 CSV 文件，用户取消时返回 None
+async fn pick_csv_file(app: &AppHandle) -> Result<Option<PathBuf>, AppError> {
+    let (tx, rx) = tokio::sync::oneshot::channel();
+    app.dialog()
+        .file()
+        .add_filter("CSV", &["csv"])
+        .pick_file(move |file_path| {
+            let _ = tx.send(file_path);
+        });
+    rx.await.ok().flatten().map(into_local_path).transpose()
+}
+
+/// 弹出保存对话框选择导出路径，用户取消时返回 None
+async fn pick_save_path(app: &AppHandle, file_name: &str) -> Result<Option<PathBuf>, AppError> {
+    let (tx, rx) = tokio::sync::oneshot::channel();
+    app.dialog()
+        .file()
+        .add_filter("CSV", &["csv"])
+        .set_file_name(file_name)
+        .save_file(move |file_path| {
+            let _ = tx.send(file_path);
+        });
+    rx.await.ok().flatten().map(into_local_path).transpose()
+}
+
+/// 导出完成后用系统默认程序打开文件；打开失败不影响导出结果
+fn open_exported_file(app: &AppHandle, path: &std::path::Path) {
+    if let Err(e) = app
+        .opener()
+        .open_path(path.to_string_lossy(), None::<&str>)
+    {
+        error!("打开导出文件失败: {e}");
+    }
+}
+
+/// 从 CSV 导入历史行情；`path` 为空时先弹出文件选择框
+#[tauri::command]
+pub async fn import_csv_data(
+    app: AppHandle,
+    path: String,
+    stock_code: String,
+    pool: State<'_, SqlitePool>,
+) -> Result<ImportResult, AppError> {
+    let path = if path.trim().is_empty() {
+        match pick_csv_file(&app).await? {
+            Some(path) => path,
+            None => return Ok(ImportResult::default()),
+        }
+    } else {
+        PathBuf::from(path.trim())
+    };
+    import_historical_csv(&path, &stock_code, &pool).await
+}
+
+/// 生成预测并导出为 CSV，返回导出路径（用户取消时为 None）
+#[tauri::command]
+pub async fn export_prediction_csv(
+    app: AppHandle,
+    stock_code: String,
+    model_id: String,
+    prediction_days: u32,
+) -> Result<Option<String>, AppError> {
+    let model_id = model_id.trim();
+    let request = PredictionRequest {
+        stock_code: stock_code.clone(),
+        model_name: (!model_id.is_empty()).then(|| model_id.to_string()),
+        prediction_days: prediction_days.max(1) as usize,
+        use_candle: true,
+        refresh_if_stale: false,
+        include_news: false,
+        include_events: false,
+    };
+    let response = inference::predict_with_model(request)
+        .await
+        .map_err(AppError::InvalidInput)?;
+
+    let file_name = format!("{stock_code}_predictions.csv");
+    let Some(path) = pick_save_path(&app, &file_name).await? else {
+        return Ok(None);
+    };
+    export::export_predictions_csv(&response.predictions, &path)?;
+    open_exported_file(&app, &path);
+    Ok(Some(path.display().to_string()))
+}
+
+/// 导出最近 N 天历史行情为 CSV，返回导出路径（用户取消时为 None）
+#[tauri::command]
+pub async fn export_historical_csv(
+    app: AppHandle,
+    stock_code: String,
+    days: usize,
+    pool: State<'_, SqlitePool>,
+) -> Result<Option<String>, AppError> {
+    let data = get_recent_historical_data(&stock_code, days.max(1), &pool).await?;
+    if data.is_empty() {
+        return Err(AppError::InvalidInput(format!("{stock_code} 没有可导出的历史数据")));
+    }
+
+    let file_name = format!("{stock_code}_historical.csv");
+    let Some(path) = pick_save_path(&app, &file_name).await? else {
+        return Ok(None);
+    };
+    export::export_historical_csv(&data, &path)?;
+    open_exported_file(&app, &path);
+    Ok(Some(path.display().to_string()))
+}
@@ -0,0 +1,21 @@
+//! 数据库维护命令
+
+use crate::db::connection::{self, PoolHealthStatus};
+use crate::error::AppError;
+use crate::services::database::{self, DbPerformanceReport};
+use sqlx::SqlitePool;
+use tauri::State;
+
+/// 常用查询耗时与存储碎片检查，必要时建议 VACUUM
+#[tauri::command]
+pub async fn check_db_performance(
+    pool: State<'_, SqlitePool>,
+) -> Result<DbPerformanceReport, AppError> {
+    database::check_db_performance(&pool).await
+}
+
+/// 连接池健康状态（`SELECT 1` 探活耗时与连接数）
+#[tauri::command]
+pub async fn get_db_health(pool: State<'_, SqlitePool>) -> Result<PoolHealthStatus, AppError> {
+    Ok(connection::check_pool_health(&pool).await)
+}
@@ -0,0 +1,208 @@
+//! 深度链接命令
+//!
+//! `biga://analyze/{stock_code}?model={model_id}&days={days}` 打开应用并直达个股分析页，
+//! 便于在用户之间分享同一份分析视图。运行中收到的链接解析后以 `deep_link_received`
+//! 事件推送给前端，由前端完成页面跳转；冷启动链接在前端监听前到达，暂存于托管状态，
+//! 由前端挂载后通过 [`take_pending_deep_link`] 取出。Windows / Linux 上系统为每个链接
+//! 启动新进程，由单实例插件转交给已运行的实例。
+
+use crate::commands::stock_prediction::PREDICTION_DAYS_RANGE;
+use crate::error::AppError;
+use crate::utils::canonical_stock_symbol;
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager, State, Url};
+use tauri_plugin_deep_link::DeepLinkExt;
+
+/// 推送给前端的深度链接事件名
+pub const DEEP_LINK_RECEIVED_EVENT: &str = "deep_link_received";
+/// 应用注册的 URL scheme（同 `tauri.conf.json` 中 `plugins.deep-link`）
+pub const DEEP_LINK_SCHEME: &str = "biga";
+/// 个股分析页的链接 host
+const ANALYZE_HOST: &str = "analyze";
+
+/// `deep_link_received` 事件负载
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeepLinkTarget {
+    pub stock_code: String,
+    /// 模型 ID；未指定时由前端按默认规则选择
+    pub model_name: Option<String>,
+    /// 预测天数；缺失或超出范围时为 None
+    pub days: Option<usize>,
+}
+
+/// 冷启动时收到、尚未被前端取走的深度链接（Tauri 托管状态）
+#[derive(Default)]
+pub struct PendingDeepLink {
+    pub target: Mutex<Option<DeepLinkTarget>>,
+}
+
+/// 6 位 A 股代码（兼容 `sh600000` 等写法）
+fn normalize_stock_code(stock_code: &str) -> Option<String> {
+    let code = canonical_stock_symbol(stock_code);
+    (code.len() == 6 && code.chars().all(|c| c.is_ascii_digit())).then_some(code)
+}
+
+/// 解析 `biga://analyze/{stock_code}` 链接；scheme、路径或股票代码不合法时返回 None
+pub fn parse_deep_link(url: &Url) -> Option<DeepLinkTarget> {
+    if url.scheme() != DEEP_LINK_SCHEME || url.host_str() != Some(ANALYZE_HOST) {
+        return None;
+    }
+    let mut segments = url.path_segments()?.filter(|segment| !segment.is_empty());
+    let stock_code = normalize_stock_code(segments.next()?)?;
+    if segments.next().is_some() {
+        return None;
+    }
+
+    let mut target = DeepLinkTarget {
+        stock_code,
+        model_name: None,
+        days: None,
+    };
+    for (key, value) in url.query_pairs() {
+        match key.as_ref() {
+            "model" if !value.is_empty() => target.model_name = Some(value.into_owned()),
+            "days" => {
+                target.days = value
+                    .parse()
+                    .ok()
+                    .filter(|days| PREDICTION_DAYS_RANGE.contains(days));
+            }
+            _ => {}
+        }
+    }
+    Some(target)
+}
+
+/// 构造分享链接，模型 ID 经 URL 编码
+pub fn build_deep_link(
+    stock_code: &str,
+    model_id: Option<&str>,
+    prediction_days: usize,
+) -> Result<String, AppError> {
+    let code = normalize_stock_code(stock_code)
+        .ok_or_else(|| AppError::InvalidInput(format!("股票代码 `{stock_code}` 无效，应为 6 位 A 股代码")))?;
+    if !PREDICTION_DAYS_RANGE.contains(&prediction_days) {
+        return Err(AppError::InvalidInput(format!(
+            "预测天数须在 {}-{} 之间: {prediction_days}",
+            PREDICTION_DAYS_RANGE.start(),
+            PREDICTION_DAYS_RANGE.end()
+        )));
+    }
+    let mut url = Url::parse(&format!("{DEEP_LINK_SCHEME}://{ANALYZE_HOST}/{code}"))
+        .map_err(|e| AppError::InvalidInput(format!("分享链接构造失败: {e}")))?;
+    {
+        let mut query = url.query_pairs_mut();
+        if let Some(model_id) = model_id.filter(|id| !id.trim().is_empty()) {
+            query.append_pair("model", model_id.trim());
+        }
+        query.append_pair("days", &prediction_days.to_string());
+    }
+    Ok(url.into())
+}
+
+fn emit_deep_links(app: &AppHandle, urls: &[Url]) {
+    for url in urls {
+        let Some(target) = parse_deep_link(url) else {
+            warn!("忽略无法识别的深度链接: {url}");
+            continue;
+        };
+        info!("收到深度链接: {url}");
+        if let Err(e) = app.emit(DEEP_LINK_RECEIVED_EVENT, target) {
+            warn!("深度链接事件推送失败: {e}");
+        }
+    }
+}
+
+/// 注册 `biga://` 链接处理：运行中收到的链接即时转发；冷启动时的链接暂存，等待前端取出
+pub fn register_deep_link_handler(app: &AppHandle) {
+    // Linux / Windows 开发模式下需在运行时注册 scheme；安装包由打包配置注册
+    #[cfg(any(windows, target_os = "linux"))]
+    if let Err(e) = app.deep_link().register_all() {
+        warn!("注册 {DEEP_LINK_SCHEME}:// 链接失败: {e}");
+    }
+
+    let handle = app.clone();
+    app.deep_link().on_open_url(move |event| emit_deep_links(&handle, &event.urls()));
+    match app.deep_link().get_current() {
+        // 此时前端尚未监听事件，只保留最后一个可识别的链接
+        Ok(Some(urls)) => {
+            if let Some(target) = urls.iter().rev().find_map(parse_deep_link) {
+                info!("暂存启动深度链接: {}", target.stock_code);
+                let pending = app.state::<PendingDeepLink>();
+                *pending.target.lock().unwrap_or_else(|e| e.into_inner()) = Some(target);
+            }
+        }
+        Ok(None) => {}
+        Err(e) => warn!("读取启动深度链接失败: {e}"),
+    }
+}
+
+/// 已有实例运行时再次启动（Windows / Linux 上打开链接即如此）：链接由单实例插件转交
+/// 深度链接插件处理，这里只把主窗口切到前台
+pub fn focus_main_window(app: &AppHandle) {
+    let Some(window) = app.get_webview_window("main") else {
+        return;
+    };
+    if let Err(e) = window.unminimize().and_then(|_| window.set_focus()) {
+        warn!("主窗口切换到前台失败: {e}");
+    }
+}
+
+/// 取出冷启动时暂存的深度链接（取出后清空），前端挂载并开始监听事件后调用
+#[tauri::command]
+pub fn take_pending_deep_link(pending: State<'_, PendingDeepLink>) -> Option<DeepLinkTarget> {
+    pending.target.lock().unwrap_or_else(|e| e.into_inner()).take()
+}
+
+/// 生成个股分析视图的分享链接
+#[tauri::command]
+pub fn generate_shareable_link(
+    stock_code: String,
+    model_id: Option<String>,
+    prediction_days: usize,
+) -> Result<String, AppError> {
+    build_deep_link(&stock_code, model_id.as_deref(), prediction_days)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deep_link_round_trip() {
+        let link = build_deep_link("sh600519", Some("lstm 5d&v2"), 5).unwrap();
+        assert_eq!(link, "biga://analyze/600519?model=lstm+5d%26v2&days=5");
+
+        let target = parse_deep_link(&Url::parse(&link).unwrap()).unwrap();
+        assert_eq!(
+            target,
+            DeepLinkTarget {
+                stock_code: "600519".to_string(),
+                model_name: Some("lstm 5d&v2".to_string()),
+                days: Some(5),
+            }
+        );
+
+        let bare = parse_deep_link(&Url::parse("biga://analyze/000001/").unwrap()).unwrap();
+        assert_eq!((bare.model_name, bare.days), (None, None));
+        let out_of_range = parse_deep_link(&Url::parse("biga://analyze/000001?days=90").unwrap());
+        assert_eq!(out_of_range.unwrap().days, None);
+    }
+
+    #[test]
+    fn test_parse_deep_link_rejects_invalid() {
+        for link in [
+            "https://analyze/600519",
+            "biga://settings/600519",
+            "biga://analyze/abc",
+            "biga://analyze/600519/extra",
+            "biga://analyze/",
+        ] {
+            assert!(parse_deep_link(&Url::parse(link).unwrap()).is_none(), "{link}");
+        }
+        assert!(build_deep_link("600519", None, 0).is_err());
+        assert!(build_deep_link("abc", None, 5).is_err());
+    }
+}
@@ -0,0 +1,75 @@
+//! 交易日志命令模块
+//!
+//! 记录用户依据预测采取的操作，实际价格由后台对账任务回填
+
+use crate::db::models::{NewTradeJournalEntry, TradeJournalEntry};
+use crate::db::repository;
+use crate::error::AppError;
+use crate::services::performance_attribution::{
+    attribute_prediction_performance, PerformanceAttribution, DEFAULT_ATTRIBUTION_LOOKBACK_DAYS,
+};
+use sqlx::SqlitePool;
+use tauri::State;
+
+/// 记录一条交易日志，返回新条目 id
+#[tauri::command]
+pub async fn log_trade_journal_entry(
+    entry: NewTradeJournalEntry,
+    pool: State<'_, SqlitePool>,
+) -> Result<i64, AppError> {
+    if entry.stock_code.trim().is_empty() {
+        return Err(AppError::InvalidInput("股票代码不能为空".to_string()));
+    }
+    if !(entry.predicted_price.is_finite() && entry.predicted_price > 0.0) {
+        return Err(AppError::InvalidInput("预测价格必须为正数".to_string()));
+    }
+    repository::insert_journal_entry(&pool, &entry).await
+}
+
+/// 更新交易日志的结果与备注（备注为 None 时保留原值）
+#[tauri::command]
+pub async fn update_journal_entry_result(
+    id: i64,
+    result: String,
+    notes: Option<String>,
+    pool: State<'_, SqlitePool>,
+) -> Result<(), AppError> {
+    repository::update_journal_result(&pool, id, &result, notes.as_deref()).await
+}
+
+/// 查询交易日志，可按股票过滤
+#[tauri::command]
+pub async fn get_trade_journal(
+    stock_code: Option<String>,
+    pool: State<'_, SqlitePool>,
+) -> Result<Vec<TradeJournalEntry>, AppError> {
+    repository::get_journal_entries(stock_code.as_deref(), &pool).await
+}
+
+/// 删除一条交易日志
+#[tauri::command]
+pub async fn delete_trade_journal_entry(
+    id: i64,
+    pool: State<'_, SqlitePool>,
+) -> Result<(), AppError> {
+    repository::delete_journal_entry(&pool, id).await
+}
+
+/// 按市场状态、趋势状态与波动率归因交易日志中预测的方向准确率，
+/// `lookback_days` 为空时回看一年
+#[tauri::command]
+pub async fn get_performance_attribution(
+    stock_code: String,
+    lookback_days: Option<usize>,
+    pool: State<'_, SqlitePool>,
+) -> Result<PerformanceAttribution, AppError> {
+    if stock_code.trim().is_empty() {
+        return Err(AppError::InvalidInput("股票代码不能为空".to_string()));
+    }
+    attribute_prediction_performance(
+        &stock_code,
+        &pool,
+        lookback_days.unwrap_or(DEFAULT_ATTRIBUTION_LOOKBACK_DAYS),
+    )
+    .await
+}
@@ -0,0 +1,153 @@
+//! 市场概况命令模块
+//!
+//! 提供市场概览、全市场宽度（涨跌家数、腾落线、52 周新高/新低）、市场情绪指数、择时信号、个股 Beta 统计、配对交易机会、
+//! 持仓组合压力测试、均值-方差组合优化、行业轮动信号与个股截面特征
+
+use crate::db::repository::get_symbols_with_min_bars;
+use crate::error::AppError;
+use crate::prediction::advanced_features::{
+    get_cross_sectional_feature, CrossSectionalFeature, CrossSectionalFeatureValue,
+};
+use crate::services::beta::{calculate_stock_beta, BetaAnalysis};
+use crate::services::market_breadth::{
+    calculate_advance_decline, MarketBreadth, MARKET_BREADTH_MIN_BARS,
+};
+use crate::services::market_sentiment::{calculate_market_sentiment_index, MarketSentimentIndex};
+use crate::services::market_overview::{calculate_market_overview, MarketOverview};
+use crate::services::market_timing::{calculate_market_timing_signal, MarketTimingSignal};
+use crate::services::pairs_trading::{
+    find_cointegrated_pairs, PairAnalysis, PairsSignal, DEFAULT_PAIRS_LOOKBACK_DAYS,
+};
+use crate::services::portfolio_optimizer::{self, OptimizedPortfolio};
+use crate::services::sector::{
+    calculate_sector_momentum, SectorMomentum, DEFAULT_SECTOR_FORWARD_DAYS,
+    DEFAULT_SECTOR_LOOKBACK_DAYS,
+};
+use crate::services::stress_test::{
+    build_stress_positions, historical_stress_scenarios, run_stress_test, StressScenario,
+    StressTestResult,
+};
+use sqlx::SqlitePool;
+use tauri::State;
+
+/// 获取本地股票池的市场宽度
+#[tauri::command]
+pub async fn get_market_breadth(pool: State<'_, SqlitePool>) -> Result<MarketBreadth, AppError> {
+    let symbols = get_symbols_with_min_bars(MARKET_BREADTH_MIN_BARS, &pool).await?;
+    calculate_advance_decline(&symbols, &pool).await
+}
+
+/// 获取本地股票池的市场情绪指数（站上 MA20 比例、RSI 分布与恐慌/贪婪指数）
+#[tauri::command]
+pub async fn get_market_sentiment_index(
+    pool: State<'_, SqlitePool>,
+) -> Result<MarketSentimentIndex, AppError> {
+    calculate_market_sentiment_index(&pool).await
+}
+
+/// 市场概览：主要指数点位、市场情绪、涨跌幅榜/成交额榜与市场宽度（交易时段内缓存 60 秒）
+#[tauri::command]
+pub async fn get_market_overview(pool: State<'_, SqlitePool>) -> Result<MarketOverview, AppError> {
+    calculate_market_overview(&pool).await
+}
+
+/// 获取本地股票池的择时信号（宽度、均线、RSI 与动量合成的择时得分与建议仓位）
+#[tauri::command]
+pub async fn get_market_timing(pool: State<'_, SqlitePool>) -> Result<MarketTimingSignal, AppError> {
+    calculate_market_timing_signal(&pool).await
+}
+
+/// 计算个股相对指数的 Beta；`index_code` 需与库内指数行情代码精确一致
+#[tauri::command]
+pub async fn get_beta_analysis(
+    stock_code: String,
+    index_code: String,
+    lookback_days: usize,
+    pool: State<'_, SqlitePool>,
+) -> Result<BetaAnalysis, AppError> {
+    calculate_stock_beta(&stock_code, index_code.trim(), lookback_days, &pool)
+        .await?
+        .ok_or_else(|| {
+            AppError::InvalidInput(format!(
+                "{stock_code} 与指数 {index_code} 的对齐行情不足，无法计算 Beta（指数行情需先导入）"
+            ))
+        })
+}
+
+/// 在给定股票池中寻找协整股票对，仅返回价差 |Z| > 2 的可交易信号
+#[tauri::command]
+pub async fn get_pairs_opportunities(
+    stock_codes: Vec<String>,
+    lookback_days: Option<usize>,
+    pool: State<'_, SqlitePool>,
+) -> Result<Vec<PairAnalysis>, AppError> {
+    if stock_codes.len() < 2 {
+        return Err(AppError::InvalidInput("配对交易至少需要两只股票".to_string()));
+    }
+    let lookback_days = lookback_days.unwrap_or(DEFAULT_PAIRS_LOOKBACK_DAYS);
+    let pairs = find_cointegrated_pairs(&stock_codes, &pool, lookback_days).await?;
+    Ok(pairs
+        .into_iter()
+        .filter(|pair| pair.signal != PairsSignal::Neutral)
+        .collect())
+}
+
+/// 对持仓组合（股票代码, 持仓市值）做压力测试；未指定情景时使用 2015 股灾、2020 疫情等历史预设
+#[tauri::command]
+pub async fn run_portfolio_stress_test(
+    positions: Vec<(String, f64)>,
+    scenarios: Option<Vec<StressScenario>>,
+    pool: State<'_, SqlitePool>,
+) -> Result<StressTestResult, AppError> {
+    if positions.is_empty() {
+        return Err(AppError::InvalidInput("压力测试至少需要一个持仓".to_string()));
+    }
+    if positions.iter().any(|(_, value)| !value.is_finite() || *value < 0.0) {
+        return Err(AppError::InvalidInput("持仓市值必须为非负数".to_string()));
+    }
+    let scenarios = scenarios
+        .filter(|scenarios| !scenarios.is_empty())
+        .unwrap_or_else(historical_stress_scenarios);
+    let positions = build_stress_positions(&positions, &pool).await?;
+    Ok(run_stress_test(&positions, &scenarios))
+}
+
+/// 均值-方差组合优化（单只权重 0~30%）：未指定年化目标收益时返回最小方差组合，并附有效前沿
+#[tauri::command]
+pub async fn optimize_portfolio(
+    stock_codes: Vec<String>,
+    target_return: Option<f64>,
+    pool: State<'_, SqlitePool>,
+) -> Result<OptimizedPortfolio, AppError> {
+    if target_return.is_some_and(|target| !target.is_finite()) {
+        return Err(AppError::InvalidInput("目标收益必须为有限数值".to_string()));
+    }
+    portfolio_optimizer::optimize_portfolio(stock_codes, target_return, &pool).await
+}
+
+/// 按动量得分排序的行业轮动信号（进入/持有/退出）；未指定时回看 60 日、持有 20 日
+#[tauri::command]
+pub async fn get_sector_rotation_signals(
+    lookback_days: Option<usize>,
+    forward_days: Option<usize>,
+    pool: State<'_, SqlitePool>,
+) -> Result<Vec<SectorMomentum>, AppError> {
+    calculate_sector_momentum(
+        &pool,
+        lookback_days.unwrap_or(DEFAULT_SECTOR_LOOKBACK_DAYS),
+        forward_days.unwrap_or(DEFAULT_SECTOR_FORWARD_DAYS).max(1),
+    )
+    .await
+}
+
+/// 个股某截面特征（相对强弱、20 日收益、波动率、换手率）在股票池中的百分位排名与 z-score
+#[tauri::command]
+pub async fn get_cross_sectional_features(
+    stock_code: String,
+    feature: CrossSectionalFeature,
+    pool: State<'_, SqlitePool>,
+) -> Result<CrossSectionalFeatureValue, AppError> {
+    get_cross_sectional_feature(&stock_code, feature, &pool)
+        .await?
+        .ok_or_else(|| AppError::InvalidInput(format!("{stock_code} 无本地行情，无法计算截面特征")))
+}
@@ -9,4 +9,16 @@ pub mod stock_realtime;
 pub mod stock_prediction;
 pub mod watchlist;
 pub mod settings;
+pub mod csv;
+pub mod market;
+pub mod journal;
+pub mod notifications;
+pub mod presets;
+pub mod alerts;
+pub mod database;
+pub mod operations;
+pub mod paper_trading;
+pub mod stop_orders;
+pub mod universe;
+pub mod deep_link;
 mod pagination;
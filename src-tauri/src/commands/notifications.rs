@@ -0,0 +1,178 @@
+//! 通知命令
+//!
+//! 通知经通知插件以系统通知展示，同时以 `notification` 事件推送给前端；
+//! 免打扰时段内仅放行 `Critical` 级别。
+
+use crate::config::notifications::{
+    load_notification_preferences, save_notification_preferences, NotificationPreferences,
+};
+use crate::error::AppError;
+use crate::services::alerts::ALERT_TRIGGERED_EVENT;
+use chrono::Timelike;
+use log::warn;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Listener};
+use tauri_plugin_notification::NotificationExt;
+
+/// 推送给前端的通知事件名
+pub const NOTIFICATION_EVENT: &str = "notification";
+
+/// 通知紧急程度
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationUrgency {
+    Info,
+    Warning,
+    Critical,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NotificationPayload {
+    pub title: String,
+    pub body: String,
+    pub urgency: NotificationUrgency,
+}
+
+/// `alert_triggered` 事件负载
+#[derive(Debug, Deserialize)]
+struct AlertTriggered {
+    title: String,
+    #[serde(default)]
+    body: String,
+}
+
+/// 按偏好判断是否推送：免打扰时段仅放行 `Critical`
+fn should_deliver(
+    preferences: &NotificationPreferences,
+    urgency: NotificationUrgency,
+    hour: u8,
+) -> bool {
+    urgency == NotificationUrgency::Critical || !preferences.is_quiet_hour(hour)
+}
+
+/// 按偏好推送通知，返回是否实际推送
+pub fn dispatch_notification(
+    app: &AppHandle,
+    payload: NotificationPayload,
+) -> Result<bool, AppError> {
+    let preferences = load_notification_preferences();
+    let hour = chrono::Local::now().hour() as u8;
+    if !should_deliver(&preferences, payload.urgency, hour) {
+        return Ok(false);
+    }
+    app.notification()
+        .builder()
+        .title(&payload.title)
+        .body(&payload.body)
+        .show()
+        .map_err(|e| AppError::InvalidInput(format!("系统通知展示失败: {e}")))?;
+    // 前端据此在应用内展示；推送失败（窗口已关闭等）不影响系统通知
+    if let Err(e) = app.emit(NOTIFICATION_EVENT, payload) {
+        warn!("通知事件推送失败: {e}");
+    }
+    Ok(true)
+}
+
+/// 信号强度（0–100）超过偏好阈值时推送强买入/卖出信号通知
+pub fn notify_strong_signal(
+    app: &AppHandle,
+    stock_code: &str,
+    signal: &str,
+    signal_strength: f64,
+) -> Result<bool, AppError> {
+    if signal_strength <= load_notification_preferences().signal_threshold {
+        return Ok(false);
+    }
+    dispatch_notification(
+        app,
+        NotificationPayload {
+            title: format!("{stock_code} 强{signal}信号"),
+            body: format!("信号强度 {signal_strength:.0}，请结合风险提示复核"),
+            urgency: NotificationUrgency::Warning,
+        },
+    )
+}
+
+/// 订阅 `alert_triggered` 事件（见 [`crate::services::alerts`]），预警开启时以 `Critical` 级别通知
+pub fn subscribe_alert_notifications(app: &AppHandle) {
+    let handle = app.clone();
+    app.listen(ALERT_TRIGGERED_EVENT, move |event| {
+        if !load_notification_preferences().alerts_enabled {
+            return;
+        }
+        let Ok(alert) = serde_json::from_str::<AlertTriggered>(event.payload()) else {
+            warn!("无法解析预警事件: {}", event.payload());
+            return;
+        };
+        let payload = NotificationPayload {
+            title: alert.title,
+            body: alert.body,
+            urgency: NotificationUrgency::Critical,
+        };
+        if let Err(e) = dispatch_notification(&handle, payload) {
+            warn!("预警通知推送失败: {e}");
+        }
+    });
+}
+
+#[tauri::command]
+pub async fn send_notification(
+    app: AppHandle,
+    title: String,
+    body: String,
+    urgency: NotificationUrgency,
+) -> Result<bool, AppError> {
+    dispatch_notification(
+        &app,
+        NotificationPayload {
+            title,
+            body,
+            urgency,
+        },
+    )
+}
+
+#[tauri::command]
+pub async fn get_notification_preferences() -> Result<NotificationPreferences, AppError> {
+    Ok(load_notification_preferences())
+}
+
+#[tauri::command]
+pub async fn set_notification_preferences(
+    alerts_enabled: bool,
+    signal_threshold: f64,
+    quiet_hours: Option<(u8, u8)>,
+) -> Result<NotificationPreferences, AppError> {
+    let preferences = NotificationPreferences {
+        alerts_enabled,
+        signal_threshold,
+        quiet_hours,
+    };
+    save_notification_preferences(&preferences)?;
+    Ok(preferences)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quiet_hours_only_let_critical_through() {
+        let preferences = NotificationPreferences {
+            quiet_hours: Some((22, 7)),
+            ..NotificationPreferences::default()
+        };
+        assert!(!should_deliver(&preferences, NotificationUrgency::Info, 23));
+        assert!(!should_deliver(
+            &preferences,
+            NotificationUrgency::Warning,
+            3
+        ));
+        assert!(should_deliver(
+            &preferences,
+            NotificationUrgency::Critical,
+            3
+        ));
+        assert!(should_deliver(&preferences, NotificationUrgency::Info, 10));
+    }
+}
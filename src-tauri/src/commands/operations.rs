@@ -0,0 +1,13 @@
+//! 长任务控制命令
+
+use crate::services::progress::OperationRegistry;
+use tauri::State;
+
+/// 取消进行中的长任务（训练、回测、超参数搜索、收藏池刷新）；操作不存在或已结束时返回 false
+#[tauri::command]
+pub async fn cancel_operation(
+    operation_id: String,
+    registry: State<'_, OperationRegistry>,
+) -> Result<bool, String> {
+    Ok(registry.cancel(&operation_id))
+}
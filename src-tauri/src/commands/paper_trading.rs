@@ -0,0 +1,76 @@
+//! 模拟交易命令
+
+use crate::error::AppError;
+use crate::services::paper_trading::{
+    PaperAccountValue, PaperTradeExecutedEvent, PaperTradingAccount, PaperTradingState,
+    Transaction, DEFAULT_INITIAL_CAPITAL, PAPER_TRADE_EXECUTED_EVENT,
+};
+use chrono::Local;
+use log::warn;
+use std::collections::HashMap;
+use tauri::{AppHandle, Emitter, State};
+
+fn emit_executed(app: &AppHandle, transaction: &Transaction, cash: f64) {
+    let event = PaperTradeExecutedEvent {
+        transaction: transaction.clone(),
+        cash,
+    };
+    if let Err(e) = app.emit(PAPER_TRADE_EXECUTED_EVENT, event) {
+        warn!("模拟成交事件推送失败: {e}");
+    }
+}
+
+/// 模拟买入（股数须为 100 股整数倍）
+#[tauri::command]
+pub async fn paper_trade_buy(
+    stock_code: String,
+    shares: u64,
+    price: f64,
+    app: AppHandle,
+    state: State<'_, PaperTradingState>,
+) -> Result<Transaction, AppError> {
+    let mut account = state.account.lock().unwrap_or_else(|e| e.into_inner());
+    let transaction = account.buy(&stock_code, shares, price, Local::now().naive_local())?;
+    emit_executed(&app, &transaction, account.cash);
+    Ok(transaction)
+}
+
+/// 模拟卖出（当日买入部分不可卖出）
+#[tauri::command]
+pub async fn paper_trade_sell(
+    stock_code: String,
+    shares: u64,
+    price: f64,
+    app: AppHandle,
+    state: State<'_, PaperTradingState>,
+) -> Result<Transaction, AppError> {
+    let mut account = state.account.lock().unwrap_or_else(|e| e.into_inner());
+    let transaction = account.sell(&stock_code, shares, price, Local::now().naive_local())?;
+    emit_executed(&app, &transaction, account.cash);
+    Ok(transaction)
+}
+
+/// 按前端提供的最新价（代码 → 价格）估值模拟账户，并统计盈亏与胜率
+#[tauri::command]
+pub async fn get_paper_account_value(
+    current_prices: HashMap<String, f64>,
+    state: State<'_, PaperTradingState>,
+) -> Result<PaperAccountValue, AppError> {
+    let account = state.account.lock().unwrap_or_else(|e| e.into_inner());
+    Ok(account.account_value(&current_prices))
+}
+
+/// 清空持仓与成交记录，以给定初始资金（默认 100 万元）重新开始
+#[tauri::command]
+pub async fn reset_paper_account(
+    initial_capital: Option<f64>,
+    state: State<'_, PaperTradingState>,
+) -> Result<PaperAccountValue, AppError> {
+    let initial_capital = initial_capital.unwrap_or(DEFAULT_INITIAL_CAPITAL);
+    if !(initial_capital.is_finite() && initial_capital > 0.0) {
+        return Err(AppError::InvalidInput("初始资金必须大于 0".to_string()));
+    }
+    let mut account = state.account.lock().unwrap_or_else(|e| e.into_inner());
+    *account = PaperTradingAccount::new(initial_capital);
+    Ok(account.account_value(&HashMap::new()))
+}
@@ -0,0 +1,64 @@
+//! 技术指标预设命令模块
+//!
+//! 列出内置/自定义预设、切换当前预设、创建自定义预设；
+//! 当前预设决定专业策略预测中技术指标的计算周期
+
+use crate::config::presets::{builtin_presets, find_builtin_preset, IndicatorPreset};
+use crate::db::repository;
+use crate::error::AppError;
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use tauri::State;
+
+/// 预设列表及当前选中的预设名
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndicatorPresetList {
+    pub presets: Vec<IndicatorPreset>,
+    pub active_preset: String,
+}
+
+/// 获取全部预设（内置在前，自定义在后）与当前选中的预设名
+#[tauri::command]
+pub async fn get_presets(pool: State<'_, SqlitePool>) -> Result<IndicatorPresetList, AppError> {
+    let mut presets = builtin_presets();
+    presets.extend(repository::get_custom_presets(&pool).await?);
+    let active_preset = repository::get_active_preset_name(&pool).await?;
+    Ok(IndicatorPresetList {
+        presets,
+        active_preset,
+    })
+}
+
+/// 切换当前预设，返回切换后的预设
+#[tauri::command]
+pub async fn set_active_preset(
+    name: String,
+    pool: State<'_, SqlitePool>,
+) -> Result<IndicatorPreset, AppError> {
+    let preset = repository::find_preset(&name, &pool)
+        .await?
+        .ok_or_else(|| AppError::InvalidInput(format!("预设不存在: {name}")))?;
+    repository::set_active_preset_name(&pool, &preset.name).await?;
+    Ok(preset)
+}
+
+/// 创建（或覆盖同名）自定义预设；不可与内置预设重名
+#[tauri::command]
+pub async fn create_custom_preset(
+    preset: IndicatorPreset,
+    pool: State<'_, SqlitePool>,
+) -> Result<IndicatorPreset, AppError> {
+    let preset = IndicatorPreset {
+        name: preset.name.trim().to_string(),
+        ..preset
+    };
+    preset.validate()?;
+    if find_builtin_preset(&preset.name).is_some() {
+        return Err(AppError::InvalidInput(format!(
+            "不能覆盖内置预设: {}",
+            preset.name
+        )));
+    }
+    repository::upsert_custom_preset(&pool, &preset).await?;
+    Ok(preset)
+}
@@ -3,7 +3,12 @@ use crate::config::api_token::{
     api_token_status, clear_api_token as clear_token, resolve_api_token,
     save_api_token as save_token, ApiTokenStatus,
 };
+use crate::config::logging::{apply_log_level, parse_log_level, save_log_level};
+use crate::config::weights::BuySellPointConfig;
+use crate::db::repository;
 use crate::error::AppError;
+use sqlx::SqlitePool;
+use tauri::State;
 
 #[tauri::command]
 pub async fn get_api_token_status() -> Result<ApiTokenStatus, AppError> {
@@ -26,3 +31,28 @@ pub async fn test_api_token() -> Result<bool, AppError> {
     stock::validate_api_token(&token).await?;
     Ok(true)
 }
+
+/// 设置日志级别（off / error / warn / info / debug / trace），立即生效并在重启后保留
+#[tauri::command]
+pub async fn set_log_level(level: String) -> Result<String, AppError> {
+    let level = parse_log_level(&level)?;
+    save_log_level(level)?;
+    apply_log_level(level);
+    Ok(level.as_str().to_ascii_lowercase())
+}
+
+/// 获取买卖点过滤阈值（全局生效，不区分股票）
+#[tauri::command]
+pub async fn get_trading_config(pool: State<'_, SqlitePool>) -> Result<BuySellPointConfig, AppError> {
+    Ok(repository::get_buy_sell_point_config(&pool).await)
+}
+
+/// 保存买卖点过滤阈值；最低风险收益比不能小于 1
+#[tauri::command]
+pub async fn set_trading_config(
+    config: BuySellPointConfig,
+    pool: State<'_, SqlitePool>,
+) -> Result<BuySellPointConfig, AppError> {
+    repository::set_buy_sell_point_config(&pool, &config).await?;
+    Ok(config)
+}
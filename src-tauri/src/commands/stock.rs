@@ -1,7 +1,14 @@
 use crate::csv::handler::read_csv_to_struct;
 use crate::db::{batch_insert_stock, batch_insert_stock_info};
 use crate::error::AppError;
+use crate::api::etf::{align_nav_to_dates, fetch_etf_holdings, fetch_etf_nav_history, premium_discount_pct};
+use crate::api::corporate_calendar::{fetch_upcoming_events, CorporateEvent, DEFAULT_EVENT_DAYS_AHEAD};
+use crate::api::news::{self, NewsItem, DEFAULT_NEWS_DAYS};
+use crate::db::repository::get_recent_historical_data;
+use crate::utils::is_etf;
 use crate::{api::stock, db::models::StockInfo};
+use chrono::NaiveDate;
+use serde::Serialize;
 use sqlx::SqlitePool;
 use tauri::State;
 
@@ -36,3 +43,73 @@ pub async fn refresh_stock_infos(pool: State<'_, SqlitePool>) -> Result<bool, Ap
 
     Ok(true)
 }
+
+/// 获取个股近期新闻（默认近 7 天）
+#[tauri::command]
+pub async fn get_stock_news(
+    stock_code: String,
+    days: Option<usize>,
+) -> Result<Vec<NewsItem>, AppError> {
+    news::fetch_stock_news(&stock_code, days.unwrap_or(DEFAULT_NEWS_DAYS)).await
+}
+
+/// 获取个股未来 `days_ahead` 个自然日内的公司事件（财报披露、除权除息、股东大会、解禁）
+#[tauri::command]
+pub async fn get_upcoming_events(
+    stock_code: String,
+    days_ahead: Option<usize>,
+) -> Result<Vec<CorporateEvent>, AppError> {
+    fetch_upcoming_events(&stock_code, days_ahead.unwrap_or(DEFAULT_EVENT_DAYS_AHEAD)).await
+}
+
+/// ETF 前十大重仓股：(股票代码, 占净值比例%)，用于 ETF 与成分股的联动分析
+#[tauri::command]
+pub async fn get_etf_holdings(etf_code: String) -> Result<Vec<(String, f64)>, AppError> {
+    if !is_etf(&etf_code) {
+        return Err(AppError::InvalidInput(format!("{etf_code} 不是场内ETF代码")));
+    }
+    fetch_etf_holdings(&etf_code).await
+}
+
+/// ETF 单日折溢价
+#[derive(Debug, Clone, Serialize)]
+pub struct EtfPremiumPoint {
+    pub date: NaiveDate,
+    pub close: f64,
+    pub nav: f64,
+    /// (收盘价 - 单位净值) / 单位净值 × 100
+    pub premium_discount_pct: f64,
+}
+
+/// 近 `days` 个交易日的 ETF 折溢价（本地K线收盘价对比当日单位净值）
+#[tauri::command]
+pub async fn get_etf_premium_discount(
+    pool: State<'_, SqlitePool>,
+    etf_code: String,
+    days: usize,
+) -> Result<Vec<EtfPremiumPoint>, AppError> {
+    if !is_etf(&etf_code) {
+        return Err(AppError::InvalidInput(format!("{etf_code} 不是场内ETF代码")));
+    }
+    let historical = get_recent_historical_data(&etf_code, days, &pool).await?;
+    if historical.is_empty() {
+        return Err(AppError::InvalidInput(format!("未找到 {etf_code} 的历史数据")));
+    }
+    // 多取一些净值，覆盖区间起点前最近一期
+    let navs = fetch_etf_nav_history(&etf_code, days + 10).await?;
+    let dates: Vec<NaiveDate> = historical.iter().map(|h| h.date).collect();
+    let aligned = align_nav_to_dates(&dates, &navs);
+
+    Ok(historical
+        .iter()
+        .zip(aligned)
+        .filter_map(|(bar, nav)| {
+            Some(EtfPremiumPoint {
+                date: bar.date,
+                close: bar.close,
+                nav,
+                premium_discount_pct: premium_discount_pct(bar.close, nav)?,
+            })
+        })
+        .collect())
+}
@@ -1,6 +1,8 @@
 use crate::db::get_historical_data as query_historical_data;
+use crate::db::repository::{self, IntegrityReport};
 use crate::db::models::HistoricalData;
 use crate::error::AppError;
+use crate::services::candles::{get_candles, CandlePeriod, CandleStick};
 use crate::services::historical::{refresh_stock_full, RefreshSummary};
 use sqlx::SqlitePool;
 use tauri::State;
@@ -15,6 +17,19 @@ pub async fn get_historical_data(
     query_historical_data(&symbol, &start, &end, &pool).await
 }
 
+/// 图表K线：`period` 为 "1d" / "1w" / "1mo"，返回最近 `limit` 根（时间正序）
+#[tauri::command]
+pub async fn get_candle_data(
+    stock_code: String,
+    period: String,
+    limit: usize,
+    pool: State<'_, SqlitePool>,
+) -> Result<Vec<CandleStick>, AppError> {
+    let period = CandlePeriod::parse(&period)
+        .ok_or_else(|| AppError::InvalidInput(format!("不支持的K线周期: {period}")))?;
+    get_candles(&stock_code, period, limit, &pool).await
+}
+
 /// 刷新单只股票的全部所需数据：历史K线 + 股本/估值(PE/PB) + 基本面 + 量比/换手率回填。
 /// 一次刷新更新全部相关表，避免零散重复操作。返回各步更新汇总（前端用于日志/提示）。
 #[tauri::command]
@@ -24,3 +39,21 @@ pub async fn refresh_historical_data(
 ) -> Result<RefreshSummary, AppError> {
     refresh_stock_full(&symbol, &pool).await
 }
+
+/// 检查历史K线的 OHLCV 逻辑错误（高低价颠倒、收盘价越界、开盘价为 0、成交量为负），不写库
+#[tauri::command]
+pub async fn check_data_integrity(
+    stock_code: String,
+    pool: State<'_, SqlitePool>,
+) -> Result<IntegrityReport, AppError> {
+    repository::check_ohlcv_integrity(&stock_code, &pool).await
+}
+
+/// 修复历史K线的 OHLCV 逻辑错误，返回修复报告
+#[tauri::command]
+pub async fn repair_data_integrity(
+    stock_code: String,
+    pool: State<'_, SqlitePool>,
+) -> Result<IntegrityReport, AppError> {
+    repository::repair_ohlcv_integrity(&stock_code, &pool).await
+}
@@ -1,4 +1,5 @@
-use crate::db::models::Stock;
+use crate::db::models::{Stock, StockSearchResult};
+use crate::db::repository::search_universe;
 use crate::error::AppError;
 use crate::commands::pagination::{normalize_page, PagedResponse};
 use sqlx::SqlitePool;
@@ -66,3 +67,16 @@ pub async fn get_stock_list(
         page_size,
     })
 }
+
+/// 联想搜索单次最多返回条数
+const MAX_SEARCH_RESULTS: usize = 50;
+
+/// 搜索框联想：按代码、名称或拼音首字母（如 payh）匹配股票池中的活跃股票
+#[tauri::command]
+pub async fn search_stocks(
+    pool: State<'_, SqlitePool>,
+    query: String,
+    max_results: usize,
+) -> Result<Vec<StockSearchResult>, AppError> {
+    search_universe(&pool, &query, max_results.min(MAX_SEARCH_RESULTS)).await
+}
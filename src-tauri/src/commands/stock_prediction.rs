@@ -4,14 +4,42 @@
 
 use crate::prediction::{
     types::*,
-    model::{training, inference, management},
+    model::{training, inference, management, optimization, hyperparameter_optimization, onnx_export, ensemble, feature_selection, drift, quantile::QuantileTrainingReport},
     strategy::multi_timeframe::{self, MultiTimeframeSignal},
+    strategy::multi_factor::FundamentalFactor,
+    strategy::adaptive_weights::AdaptiveWeightOptimizer,
+    strategy::mean_reversion::detect_mean_reversion_opportunity,
+    strategy::regime_conditional,
+    strategy::professional_engine::{decayed_confidence, recommend_prediction_horizon},
+    strategy::opening_gap::{predict_opening_gap as predict_gap, GapPrediction, OvernightSignals},
+    strategy::price_model::{
+        analyze_drawdown_characteristics, calculate_atr_trailing_stop, calculate_fibonacci_time_cycles,
+        find_nearest_fibonacci_time_cycle, latest_time_cycle_proximity, select_atr_multiplier,
+        FibonacciTimeCycle, FibonacciTimeCycleAnalysis, FIBONACCI_TIME_CYCLES,
+    },
+    strategy::position_sizing::{calculate_position_size, PositionSizeResult, PositionSizingMethod, PositionSizingParams},
     analysis::*,
+    backtest::attribution::{analyze_signal_attribution, SignalAttributionReport},
+    backtest::regime::format_regime_breakdown_table,
+    backtest::signal_accuracy::{SignalAccuracyReport, SignalType},
+    indicators::{TechnicalIndicatorValues, calculate_supertrend, calculate_wilder_adx, get_supertrend_signal, SupertrendValue},
 };
-use crate::db::{connection::create_temp_pool, repository::{get_historical_data, get_recent_historical_data, get_recent_historical_data_for_symbols, get_symbols_with_min_bars}};
+use crate::config::retrain::{load_auto_retrain_config, save_auto_retrain_config, AutoRetrainConfig};
+use crate::config::weights::{BuySellPointConfig, TradeSignalFilter, SUPPRESSED_CONFIDENCE_FACTOR};
+use crate::config::regime::{self, load_regime_strategy_config, save_regime_strategy_config, RegimeStrategyConfig};
+use crate::db::models::{HistoricalData, ModelHistoryEntry};
+use crate::db::{connection::create_temp_pool, repository::{self, get_historical_data, get_recent_historical_data, get_recent_historical_data_for_symbols, get_symbols_with_min_bars, get_active_indicator_config, check_data_freshness}};
 use crate::services;
-use chrono::NaiveDate;
+use crate::utils::{canonical_stock_symbol, get_trading_day_after};
+use crate::services::progress::{default_operation_id, ProgressReporter};
+use crate::commands::notifications;
+use crate::api::corporate_calendar::{fetch_upcoming_events, CorporateEvent, EventType};
+use crate::api::news::{fetch_stock_news, score_news_sentiment, DEFAULT_NEWS_DAYS};
+use chrono::{Local, NaiveDate};
+use futures::stream::{self, StreamExt};
+use log::{debug, error, info, warn};
 use sqlx::sqlite::SqlitePool;
+use tauri::Emitter;
 
 // =============================================================================
 // 模型管理命令
@@ -29,53 +57,661 @@ pub async fn delete_stock_prediction_model(model_id: String) -> Result<(), Strin
     management::delete_model(&model_id)
 }
 
+/// 将模型提升为所属股票的生产模型（原生产模型降为候选）
+#[tauri::command]
+pub async fn promote_model_to_production(model_id: String) -> Result<ModelInfo, String> {
+    management::promote_to_production(&model_id)
+}
+
+/// 归档模型：不再参与默认模型选择
+#[tauri::command]
+pub async fn archive_model(model_id: String) -> Result<ModelInfo, String> {
+    management::archive_model(&model_id)
+}
+
+/// 获取股票当前的生产模型
+#[tauri::command]
+pub async fn get_production_model(stock_code: String) -> Result<Option<ModelInfo>, String> {
+    Ok(management::get_production_model(&stock_code))
+}
+
+/// 获取模型保留策略
+#[tauri::command]
+pub async fn get_model_retention_policy() -> Result<management::ModelRetentionPolicy, String> {
+    Ok(management::load_retention_policy())
+}
+
+/// 保存模型保留策略并立即按新策略归档过期模型，返回被归档的模型 ID
+#[tauri::command]
+pub async fn set_model_retention_policy(
+    policy: management::ModelRetentionPolicy,
+) -> Result<Vec<String>, String> {
+    management::save_retention_policy(&policy)?;
+    Ok(management::archive_expired_models(
+        &policy,
+        management::get_current_timestamp(),
+    ))
+}
+
+/// 导出模型为 zip（ONNX 图 + 各层权重 .npy + 含特征缩放参数的 manifest.json），返回导出路径。
+///
+/// `output_path` 为空时导出到模型目录下的 `<model_id>_export.zip`。
+#[tauri::command]
+pub async fn export_model(model_id: String, output_path: Option<String>) -> Result<String, String> {
+    let path = output_path
+        .map(|p| p.trim().to_string())
+        .filter(|p| !p.is_empty())
+        .map(std::path::PathBuf::from)
+        .unwrap_or_else(|| management::get_models_dir().join(format!("{model_id}_export.zip")));
+    onnx_export::export_saved_model(&model_id, &path)?;
+    Ok(path.display().to_string())
+}
+
 // =============================================================================
 // 训练命令
 // =============================================================================
 
+/// 训练至少需要的特征数
+const MIN_TRAINING_FEATURES: usize = 3;
+
+/// 检查训练特征：不少于 3 个，且均属于模型特征集（见 [`feature_names`](crate::prediction::model::features::feature_names)）
+fn validate_training_features(features: &[String]) -> Result<(), String> {
+    if features.len() < MIN_TRAINING_FEATURES {
+        return Err(format!(
+            "至少需要选择 {MIN_TRAINING_FEATURES} 个特征，当前 {} 个",
+            features.len()
+        ));
+    }
+    let model_feature_names = crate::prediction::model::features::feature_names();
+    let unknown: Vec<&str> = features
+        .iter()
+        .map(String::as_str)
+        .filter(|name| !model_feature_names.iter().any(|known| known == name))
+        .collect();
+    if !unknown.is_empty() {
+        return Err(format!("无法识别的特征: {}", unknown.join(", ")));
+    }
+    Ok(())
+}
+
 /// 训练股票预测模型
 #[tauri::command]
 pub async fn train_stock_prediction_model(request: TrainingRequest) -> Result<TrainingResult, String> {
     training::train_model(request).await
 }
 
-/// 使用 Candle 训练模型
+/// 使用 Candle 训练模型；按轮推送 `operation_progress`，可经 `cancel_operation(operation_id)` 取消
 #[tauri::command]
-pub async fn train_candle_model(request: TrainingRequest) -> Result<TrainingResult, String> {
-    training::train_model(request).await
+pub async fn train_candle_model(
+    request: TrainingRequest,
+    operation_id: Option<String>,
+    app: tauri::AppHandle,
+) -> Result<TrainingResult, String> {
+    validate_training_features(&request.features)?;
+    let progress =
+        ProgressReporter::start(&app, operation_id.unwrap_or_else(|| default_operation_id("train")))?;
+    training::train_model_with_progress(request, &progress).await
 }
 
-/// 重新训练模型
+/// 训练前按互信息自动选择特征：从训练区间的日线计算候选特征，返回与目标最相关的 `top_k` 个特征名
+#[tauri::command]
+pub async fn auto_select_model_features(
+    stock_code: String,
+    start_date: String,
+    end_date: String,
+    target: String,
+    top_k: usize,
+) -> Result<Vec<String>, String> {
+    let pool = create_temp_pool().await?;
+    let historical = get_historical_data(&stock_code, &start_date, &end_date, &pool)
+        .await
+        .map_err(|e| format!("获取历史数据失败: {e}"))?;
+    feature_selection::auto_select_features(&historical, &target, top_k)
+}
+
+/// 重新训练模型（写入模型训练记录）
 #[tauri::command]
 pub async fn retrain_candle_model(
     model_id: String,
     epochs: u32,
-    batch_size: u32,
+    _batch_size: u32,
     learning_rate: f64,
 ) -> Result<(), String> {
-    training::retrain_model(model_id, epochs, batch_size, learning_rate).await
+    let pool = create_temp_pool().await?;
+    services::retrain_and_record(&model_id, epochs, learning_rate, "manual", &pool).await?;
+    Ok(())
+}
+
+/// 获取自动重训练配置
+#[tauri::command]
+pub async fn get_auto_retrain_config() -> Result<AutoRetrainConfig, String> {
+    Ok(load_auto_retrain_config())
+}
+
+/// 设置自动重训练：生产模型距上次训练超过 `interval_days` 天时自动重训练
+#[tauri::command]
+pub async fn set_auto_retrain_config(
+    enabled: bool,
+    interval_days: u32,
+) -> Result<AutoRetrainConfig, String> {
+    let config = AutoRetrainConfig {
+        enabled,
+        auto_retrain_days: interval_days,
+    };
+    save_auto_retrain_config(&config).map_err(|e| e.to_string())?;
+    Ok(config)
+}
+
+/// 模型的历次重训练记录（准确率变化趋势）
+#[tauri::command]
+pub async fn get_model_history(model_id: String) -> Result<Vec<ModelHistoryEntry>, String> {
+    let pool = create_temp_pool().await?;
+    repository::get_model_history(&model_id, &pool)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// 启用模型在线学习：每日用目标日已过的预测误差，按 mini-batch 对模型权重做一步梯度更新
+#[tauri::command]
+pub async fn enable_online_learning(model_id: String, learning_rate: f64) -> Result<(), String> {
+    if !(learning_rate.is_finite() && learning_rate > 0.0) {
+        return Err(format!("学习率需为正数: {learning_rate}"));
+    }
+    if !management::model_exists(&model_id) {
+        return Err(format!("模型不存在: {model_id}"));
+    }
+    let pool = create_temp_pool().await?;
+    repository::upsert_online_learning(&pool, &model_id, learning_rate)
+        .await
+        .map_err(|e| e.to_string())
 }
 
 // =============================================================================
 // 预测命令
 // =============================================================================
 
+/// 预测天数允许范围
+pub(crate) const PREDICTION_DAYS_RANGE: std::ops::RangeInclusive<usize> = 1..=30;
+/// 纯技术分析请求的历史天数允许范围
+const HISTORY_DAYS_RANGE: std::ops::RangeInclusive<usize> = 60..=1000;
+
+/// 股票代码去掉交易所前后缀后须为 6 位数字
+fn validate_stock_code(stock_code: &str) -> Result<(), String> {
+    let code = canonical_stock_symbol(stock_code);
+    if code.len() == 6 && code.chars().all(|c| c.is_ascii_digit()) {
+        Ok(())
+    } else {
+        Err(format!("股票代码 `{stock_code}` 无效，应为 6 位 A 股代码"))
+    }
+}
+
+fn validate_prediction_days(prediction_days: usize) -> Result<(), String> {
+    if PREDICTION_DAYS_RANGE.contains(&prediction_days) {
+        Ok(())
+    } else {
+        Err(format!(
+            "预测天数 {prediction_days} 超出范围 {}-{}",
+            PREDICTION_DAYS_RANGE.start(),
+            PREDICTION_DAYS_RANGE.end()
+        ))
+    }
+}
+
+/// 预测请求参数检查：股票代码、预测天数，以及指定的模型是否存在
+pub(crate) fn validate_prediction_request(request: &PredictionRequest) -> Result<(), String> {
+    validate_stock_code(&request.stock_code)?;
+    validate_prediction_days(request.prediction_days)?;
+    if request.model_name.as_deref().is_some_and(|name| !name.trim().is_empty()) {
+        inference::select_model_for_request(request)?;
+    }
+    Ok(())
+}
+
+/// 纯技术分析请求参数检查；历史天数与预测天数仅在指定时检查
+fn validate_technical_only_request(request: &TechnicalOnlyRequest) -> Result<(), String> {
+    validate_stock_code(&request.stock_code)?;
+    if let Some(days) = request.prediction_days {
+        validate_prediction_days(days)?;
+    }
+    match request.history_days {
+        Some(days) if !HISTORY_DAYS_RANGE.contains(&days) => Err(format!(
+            "历史天数 {days} 超出范围 {}-{}",
+            HISTORY_DAYS_RANGE.start(),
+            HISTORY_DAYS_RANGE.end()
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// 预测前检查历史数据新鲜度；过期且请求 `refresh_if_stale` 时先刷新再复查。
+/// 返回仍过期时的提示文本；检查或刷新失败不阻断预测
+async fn stale_data_warning(request: &PredictionRequest, pool: &SqlitePool) -> Option<String> {
+    let status = check_data_freshness(&request.stock_code, pool).await.ok()?;
+    if !status.is_stale {
+        return None;
+    }
+    if request.refresh_if_stale {
+        match services::refresh_stock_full(&request.stock_code, pool).await {
+            Ok(_) => {
+                if let Ok(refreshed) = check_data_freshness(&request.stock_code, pool).await {
+                    return refreshed.stale_reason;
+                }
+            }
+            Err(e) => error!("过期数据刷新失败 {}: {e}", request.stock_code),
+        }
+    }
+    status.stale_reason
+}
+
 /// 股票价格预测
 #[tauri::command]
 pub async fn predict_stock_price(request: PredictionRequest) -> Result<PredictionResponse, String> {
-    inference::predict(request).await
+    validate_prediction_request(&request)?;
+    let pool = create_temp_pool().await?;
+    let data_warning = stale_data_warning(&request, &pool).await;
+    let stock_code = request.stock_code.clone();
+    let model_id = request.model_name.clone().unwrap_or_else(|| "default".to_string());
+    let mut response = inference::predict(request.clone()).await?;
+    apply_event_suppression(&request, &mut response).await;
+    response.data_warning = data_warning;
+    services::prediction::record_prediction_history(&pool, &stock_code, &model_id, &response).await;
+    Ok(response)
 }
 
 /// 使用 Candle 进行预测（有已训练模型时走 ML，否则回退规则引擎）
 #[tauri::command]
 pub async fn predict_with_candle(request: PredictionRequest) -> Result<PredictionResponse, String> {
-    inference::predict_with_model(request).await
+    validate_prediction_request(&request)?;
+    let pool = create_temp_pool().await?;
+    let data_warning = stale_data_warning(&request, &pool).await;
+    let stock_code = request.stock_code.clone();
+    let model_id = request.model_name.clone().unwrap_or_else(|| "candle".to_string());
+    let mut response = inference::predict_with_model(request.clone()).await?;
+    apply_event_suppression(&request, &mut response).await;
+    response.data_warning = data_warning;
+    services::prediction::record_prediction_history(&pool, &stock_code, &model_id, &response).await;
+    Ok(response)
+}
+
+/// 批量预测结果事件名：每完成一只股票推送一次 [`BatchPredictionResult`]
+pub const BATCH_PREDICTION_RESULT_EVENT: &str = "batch_prediction_result";
+
+/// 批量预测并发上限
+const MAX_BATCH_CONCURRENCY: usize = 16;
+
+/// 批量预测中的单只股票，返回 (写入预测历史的模型标识, 预测结果)
+async fn predict_for_batch(
+    request: PredictionRequest,
+    config: &BatchPredictionConfig,
+) -> (String, Result<PredictionResponse, String>) {
+    if !config.use_model_if_available {
+        return ("default".to_string(), inference::predict(request).await);
+    }
+    let model_id = request.model_name.clone().unwrap_or_else(|| "candle".to_string());
+    match inference::predict_with_model(request.clone()).await {
+        Err(e) if config.fallback_to_technical => {
+            warn!("{} 模型预测失败，改用规则引擎: {e}", request.stock_code);
+            ("default".to_string(), inference::predict(request).await)
+        }
+        result => (model_id, result),
+    }
+}
+
+/// 批量预测：最多 `max_concurrent` 只并发，单票失败不影响其余股票。
+/// 每完成一只即推送 `batch_prediction_result` 事件，全部完成后按完成顺序返回
+#[tauri::command]
+pub async fn batch_predict_stocks(
+    requests: Vec<PredictionRequest>,
+    config: Option<BatchPredictionConfig>,
+    app: tauri::AppHandle,
+) -> Result<Vec<BatchPredictionResult>, String> {
+    let config = config.unwrap_or_default();
+    let pool = create_temp_pool().await?;
+    let total = requests.len();
+    let (config, pool) = (&config, &pool);
+
+    let mut pending = stream::iter(requests.into_iter().map(|request| async move {
+        let stock_code = request.stock_code.clone();
+        if let Err(e) = validate_prediction_request(&request) {
+            return BatchPredictionResult { stock_code, result: Err(e) };
+        }
+        let data_warning = stale_data_warning(&request, pool).await;
+        let (model_id, mut result) = predict_for_batch(request.clone(), config).await;
+        if let Ok(response) = result.as_mut() {
+            apply_event_suppression(&request, response).await;
+            response.data_warning = data_warning;
+            services::prediction::record_prediction_history(pool, &stock_code, &model_id, response)
+                .await;
+        }
+        BatchPredictionResult { stock_code, result }
+    }))
+    .buffer_unordered(config.max_concurrent.clamp(1, MAX_BATCH_CONCURRENCY));
+
+    let mut results = Vec::with_capacity(total);
+    while let Some(result) = pending.next().await {
+        // 推送失败（窗口已关闭等）不影响批量预测本身
+        if let Err(e) = app.emit(BATCH_PREDICTION_RESULT_EVENT, &result) {
+            warn!("批量预测结果推送失败: {e}");
+        }
+        results.push(result);
+    }
+    Ok(results)
+}
+
+/// 多股预测对比并发数
+const COMPARE_CONCURRENCY: usize = 4;
+/// 预测涨跌幅（%）绝对值低于该值视为平盘，方向记 0
+const FLAT_CHANGE_PERCENT: f64 = 1e-6;
+
+/// 预测方向：上涨 1、下跌 -1、平盘 0（`f64::signum` 对 0.0 返回 1）
+fn change_direction(change_percent: f64) -> f64 {
+    if change_percent.abs() < FLAT_CHANGE_PERCENT {
+        0.0
+    } else {
+        change_percent.signum()
+    }
+}
+
+/// 由纯技术分析结果生成对比摘要与排名得分；无逐日预测时返回 None。
+///
+/// 排名得分 = 方向 × 信号强度 × 置信度 / 风险分（低/中/高风险为 1/2/3），看涨且低风险的排在前面
+fn comparison_summary(
+    stock_code: String,
+    response: &ProfessionalPredictionResponse,
+) -> Option<(StockPredictionSummary, f64)> {
+    let first = response.predictions.predictions.first()?;
+    let analysis = &response.professional_analysis;
+    let risk_score = match RiskLevel::from_label(&analysis.risk_level) {
+        Some(RiskLevel::Low) => 1.0,
+        Some(RiskLevel::High) => 3.0,
+        _ => 2.0,
+    };
+    let direction = change_direction(first.predicted_change_percent);
+    let strength = first.signal_strength.unwrap_or(first.confidence);
+    let rank_score = direction * strength * first.confidence / risk_score;
+    let summary = StockPredictionSummary {
+        stock_code,
+        predicted_return_pct: first.predicted_change_percent,
+        confidence: first.confidence,
+        signal: first
+            .trading_signal
+            .clone()
+            .unwrap_or_else(|| analysis.multi_factor_score.signal.clone()),
+        multi_factor_score: analysis.multi_factor_score.total_score,
+        risk_level: analysis.risk_level.clone(),
+        recommendation_rank: 0,
+    };
+    Some((summary, rank_score))
+}
+
+/// 按排名得分降序排列并填写名次
+fn rank_comparison_summaries(mut scored: Vec<(StockPredictionSummary, f64)>) -> Vec<StockPredictionSummary> {
+    scored.sort_by(|a, b| b.1.total_cmp(&a.1));
+    scored
+        .into_iter()
+        .enumerate()
+        .map(|(i, (summary, _))| StockPredictionSummary {
+            recommendation_rank: i + 1,
+            ..summary
+        })
+        .collect()
+}
+
+/// 多股预测对比：并发执行纯技术分析预测，取首日预测按 方向 × 信号强度 × 置信度 / 风险 排名。
+/// 预测失败的股票记录日志后跳过
+#[tauri::command]
+pub async fn compare_stock_predictions(
+    stock_codes: Vec<String>,
+    prediction_days: usize,
+) -> Result<Vec<StockPredictionSummary>, String> {
+    let pool = create_temp_pool().await?;
+    let pool = &pool;
+    let predictions = stock_codes.into_iter().map(|stock_code| async move {
+        let request = TechnicalOnlyRequest {
+            stock_code: stock_code.clone(),
+            history_days: None,
+            prediction_days: Some(prediction_days.max(1)),
+        };
+        match predict_with_technical_only_with_pool(request, pool).await {
+            Ok(response) => comparison_summary(stock_code, &response),
+            Err(e) => {
+                warn!("{stock_code} 对比预测失败: {e}");
+                None
+            }
+        }
+    });
+    let scored: Vec<(StockPredictionSummary, f64)> = stream::iter(predictions)
+        .buffer_unordered(COMPARE_CONCURRENCY)
+        .filter_map(|summary| async move { summary })
+        .collect()
+        .await;
+    Ok(rank_comparison_summaries(scored))
+}
+
+/// 背离扫描入选门槛：综合置信度须高于该值
+const SCAN_MIN_DIVERGENCE_CONFIDENCE: f64 = 0.65;
+/// 背离扫描入选门槛：所选指标中至少出现的背离数
+const SCAN_MIN_DIVERGENCE_COUNT: usize = 2;
+/// 可按名称筛选的背离指标
+const DIVERGENCE_INDICATORS: [&str; 5] = ["rsi", "macd", "obv", "williams", "roc"];
+
+/// 所选指标（空表示全部）中检测到背离的数量
+fn matched_divergence_count(divergence: &DivergenceAnalysis, divergence_types: &[String]) -> usize {
+    let signals = [
+        &divergence.rsi_divergence,
+        &divergence.macd_divergence,
+        &divergence.obv_divergence,
+        &divergence.williams_divergence,
+        &divergence.roc_divergence,
+    ];
+    DIVERGENCE_INDICATORS
+        .iter()
+        .zip(signals)
+        .filter(|(name, signal)| {
+            signal.is_some()
+                && (divergence_types.is_empty() || divergence_types.iter().any(|t| t == *name))
+        })
+        .count()
+}
+
+/// 按入选门槛过滤，并按 综合置信度 × |综合得分| 降序填写强度排名
+fn rank_divergence_opportunities(
+    candidates: Vec<(String, DivergenceAnalysis, f64)>,
+    divergence_types: &[String],
+) -> Vec<DivergenceOpportunity> {
+    let mut selected: Vec<(String, DivergenceAnalysis, f64)> = candidates
+        .into_iter()
+        .filter(|(_, divergence, _)| {
+            divergence.overall_confidence > SCAN_MIN_DIVERGENCE_CONFIDENCE
+                && matched_divergence_count(divergence, divergence_types) >= SCAN_MIN_DIVERGENCE_COUNT
+        })
+        .collect();
+    let strength = |d: &DivergenceAnalysis| d.overall_confidence * d.composite_score.abs();
+    selected.sort_by(|a, b| strength(&b.1).total_cmp(&strength(&a.1)));
+    selected
+        .into_iter()
+        .enumerate()
+        .map(|(i, (stock_code, divergence, current_price))| DivergenceOpportunity {
+            stock_code,
+            divergence,
+            current_price,
+            strength_rank: i + 1,
+        })
+        .collect()
+}
+
+/// 背离扫描：批量读取各股近期日线，并行执行综合背离分析，
+/// 返回综合置信度 > 0.65 且所选指标（rsi/macd/obv/williams/roc，空表示全部）中至少 2 个背离的股票
+#[tauri::command]
+pub async fn scan_divergence_opportunities(
+    stock_codes: Vec<String>,
+    divergence_types: Vec<String>,
+) -> Result<Vec<DivergenceOpportunity>, String> {
+    let divergence_types: Vec<String> = divergence_types
+        .iter()
+        .map(|t| t.trim().to_lowercase())
+        .collect();
+    if let Some(unknown) = divergence_types
+        .iter()
+        .find(|t| !DIVERGENCE_INDICATORS.contains(&t.as_str()))
+    {
+        return Err(format!(
+            "不支持的背离类型: {unknown}（可选 {}）",
+            DIVERGENCE_INDICATORS.join("/")
+        ));
+    }
+
+    let pool = create_temp_pool().await?;
+    let mut resolved: Vec<(String, String)> = Vec::with_capacity(stock_codes.len());
+    for stock_code in stock_codes {
+        match repository::resolve_historical_symbol(&stock_code, &pool).await {
+            Ok(Some(symbol)) => resolved.push((stock_code, symbol)),
+            Ok(None) => warn!("{stock_code} 无历史数据，跳过背离扫描"),
+            Err(e) => warn!("{stock_code} 查询历史数据失败: {e}"),
+        }
+    }
+    let symbols: Vec<String> = resolved.iter().map(|(_, symbol)| symbol.clone()).collect();
+    let mut bars_by_symbol: std::collections::HashMap<String, Vec<HistoricalData>> =
+        get_recent_historical_data_for_symbols(&symbols, inference::MIN_ANALYSIS_DAYS, &pool)
+            .await
+            .map_err(|e| format!("获取历史数据失败: {e}"))?
+            .into_iter()
+            .collect();
+
+    // 背离检测为纯计算，分派到阻塞线程池并行执行
+    let tasks = resolved.into_iter().filter_map(|(stock_code, symbol)| {
+        let historical = bars_by_symbol.remove(&symbol)?;
+        Some(tokio::task::spawn_blocking(move || {
+            let prices: Vec<f64> = historical.iter().map(|h| h.close).collect();
+            let highs: Vec<f64> = historical.iter().map(|h| h.high).collect();
+            let lows: Vec<f64> = historical.iter().map(|h| h.low).collect();
+            let volumes: Vec<i64> = historical.iter().map(|h| h.volume).collect();
+            let current_price = prices.last().copied().unwrap_or(0.0);
+            (stock_code, analyze_all_divergences(&prices, &highs, &lows, &volumes), current_price)
+        }))
+    });
+    let mut candidates = Vec::new();
+    for result in futures::future::join_all(tasks).await {
+        match result {
+            Ok(candidate) => candidates.push(candidate),
+            Err(e) => error!("背离分析任务异常: {e}"),
+        }
+    }
+    Ok(rank_divergence_opportunities(candidates, &divergence_types))
+}
+
+/// 市场状态条件预测：按当前市场状态选用子策略（趋势市走 Candle 模型、震荡市走均值回归、
+/// 转折点走规则引擎，映射见 [`RegimeStrategyConfig`]），高波动环境下置信度不超过 0.5
+#[tauri::command]
+pub async fn predict_regime_conditional(request: PredictionRequest) -> Result<PredictionResponse, String> {
+    validate_prediction_request(&request)?;
+    let pool = create_temp_pool().await?;
+    let data_warning = stale_data_warning(&request, &pool).await;
+    let historical = get_recent_historical_data(&request.stock_code, inference::MIN_ANALYSIS_DAYS, &pool)
+        .await
+        .map_err(|e| format!("获取历史数据失败: {e}"))?;
+    let prices: Vec<f64> = historical.iter().map(|h| h.close).collect();
+    let highs: Vec<f64> = historical.iter().map(|h| h.high).collect();
+    let lows: Vec<f64> = historical.iter().map(|h| h.low).collect();
+    let regime_analysis = classify_market_regime(&prices, &highs, &lows);
+
+    let config = load_regime_strategy_config();
+    let strategy = config.strategy_for(regime_analysis.regime);
+    let stock_code = request.stock_code.clone();
+    let mut response = match strategy {
+        regime::TREND_FOLLOWING_STRATEGY => inference::predict_with_model(request.clone()).await?,
+        regime::MEAN_REVERSION_STRATEGY => {
+            let mut response = inference::predict(request.clone()).await?;
+            let signal = detect_mean_reversion_opportunity(
+                &prices,
+                MEAN_REVERSION_MA_PERIOD,
+                MEAN_REVERSION_STD_THRESHOLD,
+            );
+            if !regime_conditional::apply_mean_reversion_path(&mut response, &signal) {
+                warn!("{stock_code} 偏离序列不具回归特性，沿用规则引擎预测");
+            }
+            response
+        }
+        _ => inference::predict(request.clone()).await?,
+    };
+    if regime_conditional::is_high_volatility(&regime_analysis) {
+        regime_conditional::cap_prediction_confidence(
+            &mut response,
+            regime_conditional::HIGH_VOLATILITY_CONFIDENCE_CAP,
+        );
+    }
+    regime_conditional::annotate_regime_strategy(&mut response, &regime_analysis, strategy);
+    apply_event_suppression(&request, &mut response).await;
+    response.data_warning = data_warning;
+    services::prediction::record_prediction_history(&pool, &stock_code, "regime_conditional", &response)
+        .await;
+    Ok(response)
+}
+
+/// 获取市场状态 → 子策略映射
+#[tauri::command]
+pub async fn get_regime_strategy_config() -> Result<RegimeStrategyConfig, String> {
+    Ok(load_regime_strategy_config())
+}
+
+/// 保存市场状态 → 子策略映射（子策略名须为 trend_following / mean_reversion / technical）
+#[tauri::command]
+pub async fn set_regime_strategy_config(config: RegimeStrategyConfig) -> Result<(), String> {
+    save_regime_strategy_config(&config).map_err(|e| e.to_string())
 }
 
 /// 简化策略预测
 #[tauri::command]
 pub async fn predict_candle_price_simple(request: PredictionRequest) -> Result<PredictionResponse, String> {
-    inference::predict_simple(request).await
+    validate_prediction_request(&request)?;
+    let pool = create_temp_pool().await?;
+    let data_warning = stale_data_warning(&request, &pool).await;
+    let stock_code = request.stock_code.clone();
+    let mut response = inference::predict_simple(request.clone()).await?;
+    apply_event_suppression(&request, &mut response).await;
+    response.data_warning = data_warning;
+    services::prediction::record_prediction_history(&pool, &stock_code, "simple", &response).await;
+    Ok(response)
+}
+
+/// 集成预测：组合 Candle 模型、中位数回归与量价策略；`config` 为空时三者等权加权平均
+#[tauri::command]
+pub async fn predict_with_ensemble(
+    request: PredictionRequest,
+    config: Option<ensemble::EnsembleConfig>,
+) -> Result<PredictionResponse, String> {
+    validate_prediction_request(&request)?;
+    let pool = create_temp_pool().await?;
+    let data_warning = stale_data_warning(&request, &pool).await;
+    let mut response =
+        ensemble::predict_ensemble(&request, &config.unwrap_or_default(), &pool).await?;
+    apply_event_suppression(&request, &mut response).await;
+    response.data_warning = data_warning;
+    services::prediction::record_prediction_history(&pool, &request.stock_code, "ensemble", &response)
+        .await;
+    Ok(response)
+}
+
+/// 训练分位数回归模型（q0.1 / q0.5 / q0.9），返回留出段区间覆盖率与最新区间预测
+#[tauri::command]
+pub async fn train_quantile_model(
+    stock_code: String,
+    prediction_days: usize,
+) -> Result<QuantileTrainingReport, String> {
+    let pool = create_temp_pool().await?;
+    services::prediction::train_quantile_model(&stock_code, prediction_days, &pool).await
+}
+
+/// 预测准确率历史：最近 `days` 天（按目标日）预测与实际价格的方向命中率、价格误差及滚动统计
+#[tauri::command]
+pub async fn get_prediction_accuracy_history(
+    stock_code: String,
+    days: usize,
+) -> Result<services::prediction::AccuracyHistory, String> {
+    let pool = create_temp_pool().await?;
+    services::prediction::get_prediction_accuracy_history(&stock_code, days, &pool)
+        .await
+        .map_err(|e| e.to_string())
 }
 
 // =============================================================================
@@ -88,11 +724,45 @@ pub async fn evaluate_candle_model(model_id: String) -> Result<EvaluationResult,
     inference::evaluate_model(model_id).await
 }
 
-/// 执行回测（真实 walk-forward：逐日仅用历史数据预测并与未来真实涨跌对比）
+/// 用训练后的样本外残差校准模型的共形预测区间；校准后该模型的预测区间改用共形区间
+#[tauri::command]
+pub async fn calibrate_conformal_predictor(
+    model_id: String,
+) -> Result<ConformalCalibrationResult, String> {
+    inference::calibrate_conformal_predictor(&model_id).await
+}
+
+/// 检测模型漂移：比较训练期与训练后最近 `lookback_days` 个样本的特征分布（PSI）及方向准确率。
+/// 检测到漂移的生产模型由自动重训练调度器重训练
+#[tauri::command]
+pub async fn detect_model_drift(
+    model_id: String,
+    lookback_days: Option<usize>,
+) -> Result<drift::ModelDriftReport, String> {
+    let pool = create_temp_pool().await?;
+    let lookback_days = lookback_days.unwrap_or(services::prediction::DRIFT_LOOKBACK_DAYS);
+    drift::detect_model_drift(&model_id, &pool, lookback_days).await
+}
+
+/// 解释模型对个股最新交易日的预测：逐个特征 ±1 个标准差扰动得到归因，并附技术面原因
+#[tauri::command]
+pub async fn explain_prediction(
+    stock_code: String,
+    model_id: String,
+) -> Result<PredictionExplanationDetail, String> {
+    inference::explain_model_prediction(&stock_code, &model_id).await
+}
+
+/// 执行回测（真实 walk-forward：逐日仅用历史数据预测并与未来真实涨跌对比）；
+/// 每个预测日推送 `operation_progress`，可经 `cancel_operation(operation_id)` 取消
 #[tauri::command]
-pub async fn run_model_backtest(request: BacktestRequest) -> Result<BacktestReport, String> {
+pub async fn run_model_backtest(
+    request: BacktestRequest,
+    operation_id: Option<String>,
+    app: tauri::AppHandle,
+) -> Result<BacktestReport, String> {
     use crate::prediction::backtest::{
-        run_backtest_window, run_backtest_window_with_predictor, MIN_LOOKBACK,
+        run_backtest_window_with_predictor, MIN_LOOKBACK,
     };
     use crate::prediction::model::ml_inference::MlPredictor;
 
@@ -127,13 +797,17 @@ pub async fn run_model_backtest(request: BacktestRequest) -> Result<BacktestRepo
                     && management::get_model_file_path(&model.id).exists()
             })
             .ok_or_else(|| format!("选择的模型 `{name}` 不存在或权重文件不存在"))?;
-        let predictor = MlPredictor::load(&management::get_model_file_path(&model.id))?;
+        let predictor = MlPredictor::for_model(&model)?;
         Some((model, predictor))
     } else {
         None
     };
 
     let horizon = request.prediction_days.max(1);
+    let progress = ProgressReporter::start(
+        &app,
+        operation_id.unwrap_or_else(|| default_operation_id("backtest")),
+    )?;
     let report = if let Some((model, predictor)) = loaded_model.as_ref() {
         run_backtest_window_with_predictor(
             &request.stock_code,
@@ -151,9 +825,10 @@ pub async fn run_model_backtest(request: BacktestRequest) -> Result<BacktestRepo
                     predictor,
                 )
             },
+            &progress,
         )?
     } else {
-        run_backtest_window(
+        run_backtest_window_with_predictor(
             &request.stock_code,
             &historical,
             MIN_LOOKBACK,
@@ -161,6 +836,8 @@ pub async fn run_model_backtest(request: BacktestRequest) -> Result<BacktestRepo
             request.backtest_interval,
             Some(start_date),
             Some(end_date),
+            inference::predict_from_historical,
+            &progress,
         )?
     };
     let m = &report.metrics;
@@ -220,6 +897,23 @@ pub async fn run_model_backtest(request: BacktestRequest) -> Result<BacktestRepo
             )
         })
         .collect();
+
+    // 保存逐笔交易供信号归因；保存失败不影响本次回测结果
+    let backtest_id = uuid::Uuid::new_v4().to_string();
+    if let Err(e) =
+        repository::insert_backtest_trades(&pool, &backtest_id, &request.stock_code, &report.trades)
+            .await
+    {
+        warn!("回测交易记录保存失败 {}: {e}", request.stock_code);
+    }
+    if !report.regime_breakdown.is_empty() {
+        info!(
+            "{} 回测分市场状态表现:\n{}",
+            request.stock_code,
+            format_regime_breakdown_table(&report.regime_breakdown)
+        );
+    }
+
     Ok(BacktestReport {
         stock_code: request.stock_code,
         model_name: report_model_name,
@@ -245,6 +939,9 @@ pub async fn run_model_backtest(request: BacktestRequest) -> Result<BacktestRepo
         stress_95_coverage: m.stress_95_coverage,
         average_interval_80_width: m.average_interval_80_width,
         average_stress_95_width: m.average_stress_95_width,
+        backtest_id,
+        trade_records: report.trades,
+        regime_breakdown: report.regime_breakdown,
     })
 }
 
@@ -280,6 +977,15 @@ fn backtest_entry_from_observation(
             ]),
             interval: observation.interval.clone(),
             stress_interval: observation.stress_interval.clone(),
+            explanation: None,
+            prediction_low: observation
+                .interval
+                .as_ref()
+                .map_or(observation.predicted_price, |interval| interval.lower_price),
+            prediction_high: observation
+                .interval
+                .as_ref()
+                .map_or(observation.predicted_price, |interval| interval.upper_price),
         }],
         actual_prices: vec![observation.actual_price],
         actual_changes: vec![observation.actual_change],
@@ -304,6 +1010,45 @@ fn signal_from_change(change: f64) -> &'static str {
     }
 }
 
+/// 滚动窗口稳定性检验：检验模型表现是否跨市场环境稳定
+#[tauri::command]
+pub async fn run_rolling_window_analysis(
+    stock_code: String,
+    window_size: usize,
+    step_size: usize,
+    prediction_days: usize,
+) -> Result<RollingWindowReport, String> {
+    services::prediction::run_rolling_window_analysis(&stock_code, window_size, step_size, prediction_days)
+        .await
+}
+
+/// 技术信号历史胜率检验：验证某信号在该股票上是否真的有效
+#[tauri::command]
+pub async fn evaluate_signal_accuracy(
+    stock_code: String,
+    signal_type: SignalType,
+    lookback_days: usize,
+) -> Result<SignalAccuracyReport, String> {
+    let pool = create_temp_pool().await?;
+    services::prediction::evaluate_signal_accuracy(&stock_code, signal_type, lookback_days, &pool)
+        .await
+}
+
+/// 回测收益归因：按入场时的模型、MACD、量能、趋势信号分组统计收益与胜率
+#[tauri::command]
+pub async fn analyze_backtest_by_signal(
+    backtest_id: String,
+) -> Result<SignalAttributionReport, String> {
+    let pool = create_temp_pool().await?;
+    let trades = repository::get_backtest_trades(&pool, &backtest_id)
+        .await
+        .map_err(|e| format!("获取回测交易记录失败: {e}"))?;
+    if trades.is_empty() {
+        return Err(format!("未找到回测 {backtest_id} 的交易记录"));
+    }
+    Ok(analyze_signal_attribution(&backtest_id, &trades))
+}
+
 // =============================================================================
 // 截面相对强弱排名（市场中性多因子）
 // =============================================================================
@@ -334,14 +1079,25 @@ pub async fn cross_sectional_ranking() -> Result<Vec<crate::prediction::cross_se
         .filter(|(_, hist)| hist.len() >= 300)
         .collect::<Vec<_>>();
 
+    let benchmark = load_cross_section_benchmark(&pool).await?;
     // 持有期 15 日 + IC 估计窗口 250 日（降换手、稳权重；非收益保证）。
-    let ranking = rank_latest(&stocks, 15, 250);
+    let ranking = rank_latest(&stocks, &benchmark, 15, 250);
     if ranking.is_empty() {
         return Err("数据不足以生成截面排名".to_string());
     }
     Ok(ranking)
 }
 
+/// 截面相对强弱因子的基准：上证指数近 800 个交易日收盘价（库中无指数数据时为空，因子退化为 20 日收益）
+async fn load_cross_section_benchmark(
+    pool: &SqlitePool,
+) -> Result<crate::prediction::factor::BenchmarkCloses, String> {
+    repository::get_recent_closes_exact(services::beta::DEFAULT_BETA_INDEX, 800, pool)
+        .await
+        .map(|closes| closes.into_iter().collect())
+        .map_err(|e| format!("获取基准指数数据失败: {e}"))
+}
+
 // =============================================================================
 // 估值上下文命令（PE/PB + 最新基本面，供预测页参考展示）
 // =============================================================================
@@ -370,76 +1126,542 @@ pub async fn get_valuation_context(symbol: String) -> Result<ValuationContext, S
     use crate::db::repository::{get_stock_capital, get_stock_fundamentals};
 
     let pool = create_temp_pool().await?;
-    let cap = get_stock_capital(&symbol, &pool)
-        .await
-        .map_err(|e| format!("获取股本估值失败: {e}"))?;
-    let funds = get_stock_fundamentals(&symbol, &pool)
+    let cap = get_stock_capital(&symbol, &pool)
+        .await
+        .map_err(|e| format!("获取股本估值失败: {e}"))?;
+    let funds = get_stock_fundamentals(&symbol, &pool)
+        .await
+        .map_err(|e| format!("获取基本面失败: {e}"))?;
+    // 列表按报告期升序，最后一个为最新一期
+    let latest = funds.last();
+
+    // 0 / 非有限值视为"未刷新/无数据"，返回 None
+    let pos = |v: f64| (v.is_finite() && v != 0.0).then_some(v);
+
+    Ok(ValuationContext {
+        symbol,
+        pe: cap.as_ref().and_then(|c| pos(c.pe)),
+        pb: cap.as_ref().and_then(|c| pos(c.pb)),
+        circulating_market_cap_yi: cap
+            .as_ref()
+            .and_then(|c| pos(c.circulating_market_cap).map(|v| v / 1.0e8)),
+        report_date: latest.map(|f| f.report_date.clone()),
+        roe: latest.and_then(|f| f.roe),
+        eps: latest.and_then(|f| f.eps),
+        bps: latest.and_then(|f| f.bps),
+        revenue_growth: latest.and_then(|f| f.revenue_growth),
+        profit_growth: latest.and_then(|f| f.profit_growth),
+    })
+}
+
+/// 从行情接口实时获取基本面因子数据（PE/PB 来自 ssjy，ROE/营收增长来自最新一期 cwzb）。
+///
+/// 财务指标获取失败时仅返回估值字段；0 / 非有限的估值视为缺失。
+#[tauri::command]
+pub async fn get_fundamental_data(stock_code: String) -> Result<FundamentalFactor, String> {
+    use crate::api::stock::{fetch_financial_indicators, fetch_stock_capital};
+
+    let quote = fetch_stock_capital(&stock_code)
+        .await
+        .map_err(|e| format!("获取估值数据失败: {e}"))?;
+    // cwzb 按报告期倒序返回，首个即最新一期
+    let latest = fetch_financial_indicators(&stock_code)
+        .await
+        .ok()
+        .and_then(|rows| rows.into_iter().next());
+
+    let available = |v: f64| (v.is_finite() && v != 0.0).then_some(v);
+    Ok(FundamentalFactor {
+        pe_ratio: available(quote.pe),
+        pb_ratio: available(quote.pb),
+        roe: latest.as_ref().and_then(|f| f.roe),
+        revenue_growth: latest.as_ref().and_then(|f| f.revenue_growth),
+    })
+}
+
+/// 自适应权重回放训练的最大样本天数
+const FACTOR_WEIGHT_REPLAY_DAYS: usize = 120;
+/// 回放时每个样本点使用的分析窗口（根）
+const FACTOR_WEIGHT_REPLAY_WINDOW: usize = 250;
+
+/// 用最近 `lookback_days` 个交易日回放多因子评分，按次日涨跌检验各因子方向并更新个股权重。
+///
+/// 每次从中性重新学习，保证权重只反映最近窗口；返回学习后的权重（顺序同 `OPTIMIZER_FACTOR_NAMES`）。
+#[tauri::command]
+pub async fn train_adaptive_factor_weights(
+    stock_code: String,
+    lookback_days: Option<usize>,
+) -> Result<Vec<f64>, String> {
+    let lookback = lookback_days
+        .unwrap_or(FACTOR_WEIGHT_REPLAY_DAYS)
+        .clamp(10, FACTOR_WEIGHT_REPLAY_DAYS);
+    let pool = create_temp_pool().await?;
+    let historical = get_recent_historical_data(&stock_code, FACTOR_WEIGHT_REPLAY_WINDOW + lookback, &pool)
+        .await
+        .map_err(|e| format!("获取历史数据失败: {e}"))?;
+    if historical.len() < 60 + lookback {
+        return Err(format!("历史数据不足{}天，无法训练自适应权重", 60 + lookback));
+    }
+
+    // 回放与实盘预测使用同一套指标预设，学到的权重才对得上
+    let indicator_config = get_active_indicator_config(&pool).await;
+    let mut optimizer = AdaptiveWeightOptimizer::new(&stock_code);
+    for end in historical.len() - lookback..historical.len() {
+        let window = &historical[end.saturating_sub(FACTOR_WEIGHT_REPLAY_WINDOW)..end];
+        let prices: Vec<f64> = window.iter().map(|h| h.close).collect();
+        let highs: Vec<f64> = window.iter().map(|h| h.high).collect();
+        let lows: Vec<f64> = window.iter().map(|h| h.low).collect();
+        let volumes: Vec<i64> = window.iter().map(|h| h.volume).collect();
+        let opens: Vec<f64> = window.iter().map(|h| h.open).collect();
+        let last = window.last().unwrap();
+
+        let analysis = inference::analyze(
+            &prices,
+            &highs,
+            &lows,
+            &volumes,
+            &opens,
+            inference::AnalysisOptions {
+                turnover_rate: last.turnover_rate,
+                prediction_days: 1,
+                stock_code: Some(&stock_code),
+                market_ad_ratio: None,
+                market_fear_greed: None,
+                news_sentiment: None,
+                learned_factor_weights: None,
+                beta: None,
+                sector_relative_strength: None,
+                fundamental: None,
+                indicator_config: Some(&indicator_config),
+            },
+        );
+        let actual_change = historical[end].close - last.close;
+        optimizer.record_score_outcome(&analysis.multi_factor_score, actual_change);
+    }
+
+    optimizer
+        .save(&pool)
+        .await
+        .map_err(|e| format!("保存自适应权重失败: {e}"))?;
+    Ok(optimizer.get_current_weights())
+}
+
+// =============================================================================
+// 仓位计算命令
+// =============================================================================
+
+/// 按指定方法计算建议仓位（建议股数按每手 100 股向下取整）
+#[tauri::command]
+pub async fn get_position_size(
+    method: PositionSizingMethod,
+    params: PositionSizingParams,
+) -> Result<PositionSizeResult, String> {
+    if params.account_size <= 0.0 || params.price <= 0.0 {
+        return Err("账户规模与股价必须大于 0".to_string());
+    }
+    Ok(calculate_position_size(method, params))
+}
+
+// =============================================================================
+// 超参数搜索命令
+// =============================================================================
+
+/// 超参数搜索默认超时（秒）
+const DEFAULT_HYPERPARAMETER_SEARCH_TIMEOUT_SECS: u64 = 300;
+
+/// 对个股的 candle MLP 做超参数网格搜索；超时后停止启动新组合并返回已评估的最优结果。
+/// 每组参数评估完推送 `operation_progress`，可经 `cancel_operation(operation_id)` 取消
+#[tauri::command]
+pub async fn run_hyperparameter_search(
+    stock_code: String,
+    features: Option<Vec<String>>,
+    config: Option<hyperparameter_optimization::GridSearchConfig>,
+    timeout_secs: Option<u64>,
+    operation_id: Option<String>,
+    app: tauri::AppHandle,
+) -> Result<hyperparameter_optimization::GridSearchResult, String> {
+    let timeout = std::time::Duration::from_secs(
+        timeout_secs.unwrap_or(DEFAULT_HYPERPARAMETER_SEARCH_TIMEOUT_SECS),
+    );
+    let pool = create_temp_pool().await?;
+    let progress = ProgressReporter::start(
+        &app,
+        operation_id.unwrap_or_else(|| default_operation_id("hyperparameter_search")),
+    )?;
+    hyperparameter_optimization::run_grid_search(
+        &stock_code,
+        &features.unwrap_or_default(),
+        config.unwrap_or_default(),
+        &pool,
+        Some(std::time::Instant::now() + timeout),
+        &progress,
+    )
+    .await
+}
+
+/// 从本地库读取基本面因子数据（PE/PB 来自 stock_capital，ROE/营收增长来自最新报告期），
+/// 供预测链路计入多因子评分；不发起网络请求，均未入库时返回 None。
+async fn load_fundamental_factor(stock_code: &str, pool: &SqlitePool) -> Option<FundamentalFactor> {
+    let symbol = canonical_stock_symbol(stock_code);
+    let capital = repository::get_stock_capital(&symbol, pool).await.ok().flatten();
+    let latest = repository::get_stock_fundamentals(&symbol, pool)
+        .await
+        .ok()
+        .and_then(|rows| rows.into_iter().last());
+
+    let available = |v: f64| (v.is_finite() && v != 0.0).then_some(v);
+    let factor = FundamentalFactor {
+        pe_ratio: capital.as_ref().and_then(|c| available(c.pe)),
+        pb_ratio: capital.as_ref().and_then(|c| available(c.pb)),
+        roe: latest.as_ref().and_then(|f| f.roe),
+        revenue_growth: latest.as_ref().and_then(|f| f.revenue_growth),
+    };
+    let has_data = factor.pe_ratio.is_some()
+        || factor.pb_ratio.is_some()
+        || factor.roe.is_some()
+        || factor.revenue_growth.is_some();
+    has_data.then_some(factor)
+}
+
+// =============================================================================
+// 优化建议命令
+// =============================================================================
+
+/// 优化建议结构
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct OptimizationSuggestions {
+    pub stock_code: String,
+    pub model_name: String,
+    pub suggestions: Vec<String>,
+    pub expected_improvement: f64,
+    /// 基于特征相关性、损失曲线与多日准确率诊断出的结构化建议（按优先级排序）
+    #[serde(default)]
+    pub details: Vec<optimization::OptimizationSuggestion>,
+}
+
+/// 获取优化建议
+#[tauri::command]
+pub async fn get_optimization_suggestions(
+    stock_code: String,
+    model_name: String,
+    backtest_report: BacktestReport,
+) -> Result<OptimizationSuggestions, String> {
+    let mut suggestions = Vec::new();
+    let mut expected_improvement = 0.0;
+    
+    if backtest_report.overall_price_accuracy < 0.6 {
+        suggestions.push("增加技术指标特征：添加ATR、Williams%R、ROC等指标".to_string());
+        expected_improvement += 0.08;
+    }
+    
+    if backtest_report.overall_direction_accuracy < 0.7 {
+        suggestions.push("优化趋势识别：实现多时间框架均线系统".to_string());
+        expected_improvement += 0.12;
+    }
+    
+    let pool = create_temp_pool().await?;
+    let details = optimization::OptimizationEngine::suggest_improvements(&stock_code, &pool).await?;
+    suggestions.extend(details.iter().map(|detail| detail.suggestion.clone()));
+    
+    if suggestions.is_empty() {
+        suggestions.push("当前模型表现良好，建议继续观察".to_string());
+    }
+    
+    Ok(OptimizationSuggestions {
+        stock_code,
+        model_name,
+        suggestions,
+        expected_improvement,
+        details,
+    })
+}
+
+// =============================================================================
+// 平均K线命令
+// =============================================================================
+
+/// 平均K线（Heikin-Ashi）序列及最新信号
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct HeikinAshiData {
+    pub symbol: String,
+    /// 与 `candles` 一一对应的交易日
+    pub dates: Vec<String>,
+    pub candles: Vec<pattern::HeikinAshiCandle>,
+    pub signal: String,
+}
+
+/// 获取最近 `days` 个交易日的平均K线
+#[tauri::command]
+pub async fn get_heikin_ashi_data(symbol: String, days: usize) -> Result<HeikinAshiData, String> {
+    let pool = create_temp_pool().await?;
+    let historical = get_recent_historical_data(&symbol, days.max(1), &pool)
+        .await
+        .map_err(|e| format!("获取历史数据失败: {e}"))?;
+
+    let opens: Vec<f64> = historical.iter().map(|h| h.open).collect();
+    let highs: Vec<f64> = historical.iter().map(|h| h.high).collect();
+    let lows: Vec<f64> = historical.iter().map(|h| h.low).collect();
+    let closes: Vec<f64> = historical.iter().map(|h| h.close).collect();
+    let candles = pattern::convert_to_heikin_ashi(&opens, &highs, &lows, &closes);
+    let signal = pattern::classify_heikin_ashi_signal(&candles).to_string();
+
+    Ok(HeikinAshiData {
+        symbol,
+        dates: historical.iter().map(|h| h.date.format("%Y-%m-%d").to_string()).collect(),
+        candles,
+        signal,
+    })
+}
+
+/// 砖形图及末端信号
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct RenkoChart {
+    pub stock_code: String,
+    pub brick_size: f64,
+    pub bricks: Vec<RenkoBrick>,
+    pub signal: String,
+}
+
+/// 获取砖形图；未指定砖高时按 1 倍 ATR 自动确定
+#[tauri::command]
+pub async fn get_renko_chart(stock_code: String, brick_size: Option<f64>) -> Result<RenkoChart, String> {
+    let pool = create_temp_pool().await?;
+    let historical = get_recent_historical_data(&stock_code, inference::MIN_ANALYSIS_DAYS, &pool)
+        .await
+        .map_err(|e| format!("获取历史数据失败: {e}"))?;
+
+    let closes: Vec<f64> = historical.iter().map(|h| h.close).collect();
+    let brick_size = match brick_size {
+        Some(size) if size > 0.0 => size,
+        Some(_) => return Err("砖高必须为正数".to_string()),
+        None => {
+            let highs: Vec<f64> = historical.iter().map(|h| h.high).collect();
+            let lows: Vec<f64> = historical.iter().map(|h| h.low).collect();
+            auto_brick_size_atr(&highs, &lows, &closes)
+        }
+    };
+    if brick_size <= 0.0 {
+        return Err("历史数据不足，无法计算自动砖高".to_string());
+    }
+
+    let bricks = build_renko_chart(&closes, brick_size);
+    let signal = detect_renko_signal(&bricks).to_string();
+    Ok(RenkoChart {
+        stock_code,
+        brick_size,
+        bricks,
+        signal,
+    })
+}
+
+/// Supertrend 默认参数（ATR 周期 / 倍数）
+const SUPERTREND_DEFAULT_PERIOD: usize = 10;
+const SUPERTREND_DEFAULT_MULTIPLIER: f64 = 3.0;
+
+/// Supertrend 序列及末端信号
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct SupertrendData {
+    pub stock_code: String,
+    /// 与 `values` 一一对应的交易日
+    pub dates: Vec<String>,
+    pub values: Vec<SupertrendValue>,
+    pub signal: String,
+    /// 收盘价到 Supertrend 线的价差，可作为止损距离
+    pub stop_distance: f64,
+}
+
+/// 获取 Supertrend；未指定参数时使用 10 日 ATR、3 倍
+#[tauri::command]
+pub async fn get_supertrend_data(
+    stock_code: String,
+    period: Option<usize>,
+    multiplier: Option<f64>,
+) -> Result<SupertrendData, String> {
+    let period = period.unwrap_or(SUPERTREND_DEFAULT_PERIOD);
+    let multiplier = multiplier.unwrap_or(SUPERTREND_DEFAULT_MULTIPLIER);
+    if period == 0 || multiplier <= 0.0 {
+        return Err("ATR 周期与倍数必须为正数".to_string());
+    }
+    let pool = create_temp_pool().await?;
+    let historical = get_recent_historical_data(&stock_code, inference::MIN_ANALYSIS_DAYS, &pool)
+        .await
+        .map_err(|e| format!("获取历史数据失败: {e}"))?;
+
+    let highs: Vec<f64> = historical.iter().map(|h| h.high).collect();
+    let lows: Vec<f64> = historical.iter().map(|h| h.low).collect();
+    let closes: Vec<f64> = historical.iter().map(|h| h.close).collect();
+    let values = calculate_supertrend(&highs, &lows, &closes, period, multiplier);
+    if values.is_empty() {
+        return Err(format!("历史数据不足（{}），无法计算 Supertrend", historical.len()));
+    }
+    let (signal, stop_distance) = get_supertrend_signal(&values);
+    let dates = historical[historical.len() - values.len()..]
+        .iter()
+        .map(|h| h.date.format("%Y-%m-%d").to_string())
+        .collect();
+
+    Ok(SupertrendData {
+        stock_code,
+        dates,
+        values,
+        signal: signal.to_string(),
+        stop_distance,
+    })
+}
+
+/// 斐波那契时间周期：自摆动点日期起第 1、2、3、5…55 根K线对应的交易日，
+/// 超出已有数据的按交易日历外推
+#[tauri::command]
+pub async fn get_fibonacci_time_cycles(
+    stock_code: String,
+    swing_date: String,
+) -> Result<FibonacciTimeCycleAnalysis, String> {
+    let swing = NaiveDate::parse_from_str(&swing_date, "%Y-%m-%d")
+        .map_err(|e| format!("摆动点日期格式错误: {e}"))?;
+    let pool = create_temp_pool().await?;
+    let historical = get_historical_data(&stock_code, &swing_date, "9999-12-31", &pool)
         .await
-        .map_err(|e| format!("获取基本面失败: {e}"))?;
-    // 列表按报告期升序，最后一个为最新一期
-    let latest = funds.last();
-
-    // 0 / 非有限值视为"未刷新/无数据"，返回 None
-    let pos = |v: f64| (v.is_finite() && v != 0.0).then_some(v);
+        .map_err(|e| format!("获取历史数据失败: {e}"))?;
+    if historical.first().map(|bar| bar.date) != Some(swing) {
+        return Err(format!("{swing_date} 无 {stock_code} 的日线数据，请选择交易日作为摆动点"));
+    }
 
-    Ok(ValuationContext {
-        symbol,
-        pe: cap.as_ref().and_then(|c| pos(c.pe)),
-        pb: cap.as_ref().and_then(|c| pos(c.pb)),
-        circulating_market_cap_yi: cap
-            .as_ref()
-            .and_then(|c| pos(c.circulating_market_cap).map(|v| v / 1.0e8)),
-        report_date: latest.map(|f| f.report_date.clone()),
-        roe: latest.and_then(|f| f.roe),
-        eps: latest.and_then(|f| f.eps),
-        bps: latest.and_then(|f| f.bps),
-        revenue_growth: latest.and_then(|f| f.revenue_growth),
-        profit_growth: latest.and_then(|f| f.profit_growth),
+    let current = historical.len() - 1;
+    let last_date = historical[current].date;
+    let last_cycle = FIBONACCI_TIME_CYCLES[FIBONACCI_TIME_CYCLES.len() - 1];
+    let cycle_indices = calculate_fibonacci_time_cycles(0, last_cycle + 1);
+    let cycles: Vec<FibonacciTimeCycle> = cycle_indices
+        .iter()
+        .map(|&index| {
+            let date = match historical.get(index) {
+                Some(bar) => bar.date,
+                None => get_trading_day_after(last_date, index - current),
+            };
+            FibonacciTimeCycle {
+                fibonacci: index,
+                date: date.format("%Y-%m-%d").to_string(),
+                is_projected: index > current,
+            }
+        })
+        .collect();
+    let nearest = find_nearest_fibonacci_time_cycle(current, &cycle_indices);
+
+    Ok(FibonacciTimeCycleAnalysis {
+        swing_date,
+        bars_since_swing: current,
+        nearest_cycle: nearest.and_then(|(index, _)| cycles.iter().find(|c| c.fibonacci == index).cloned()),
+        bars_until_nearest: nearest.map(|(_, offset)| offset),
+        cycles,
     })
 }
 
-// =============================================================================
-// 优化建议命令
-// =============================================================================
+/// 开盘跳空预测所用的历史天数（覆盖跳空统计窗口与月线趋势）
+const OPENING_GAP_HISTORY_DAYS: usize = 250;
 
-/// 优化建议结构
+/// 预测下一交易日开盘跳空：近期跳空统计 + 隔夜新闻情绪 + 多周期趋势；
+/// 暂无盘前期货数据源，期货信号不参与
+#[tauri::command]
+pub async fn predict_opening_gap(stock_code: String) -> Result<GapPrediction, String> {
+    validate_stock_code(&stock_code)?;
+    let pool = create_temp_pool().await?;
+    let historical = get_recent_historical_data(&stock_code, OPENING_GAP_HISTORY_DAYS, &pool)
+        .await
+        .map_err(|e| format!("获取历史数据失败: {e}"))?;
+    let news_sentiment = fetch_stock_news(&stock_code, DEFAULT_NEWS_DAYS)
+        .await
+        .ok()
+        .filter(|news| !news.is_empty())
+        .map(|news| score_news_sentiment(&news));
+    Ok(predict_gap(
+        &stock_code,
+        &historical,
+        OvernightSignals {
+            news_sentiment,
+            futures_change_pct: None,
+        },
+    ))
+}
+
+/// 某日交易信号的事件抑制状态
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
-pub struct OptimizationSuggestions {
+pub struct SuppressionStatus {
     pub stock_code: String,
-    pub model_name: String,
-    pub suggestions: Vec<String>,
-    pub expected_improvement: f64,
+    pub date: String,
+    pub suppressed: bool,
+    /// 触发抑制的公司事件
+    pub events: Vec<CorporateEvent>,
+    pub filter: TradeSignalFilter,
 }
 
-/// 获取优化建议
+/// 查询某日交易信号是否因临近财报披露或除权除息而被抑制。
+///
+/// 事件日历只提供今日及以后的事件，查询过去日期时只能识别其后尚未发生的事件。
 #[tauri::command]
-pub async fn get_optimization_suggestions(
+pub async fn get_signal_suppression_status(
     stock_code: String,
-    model_name: String,
-    backtest_report: BacktestReport,
-) -> Result<OptimizationSuggestions, String> {
-    let mut suggestions = Vec::new();
-    let mut expected_improvement = 0.0;
-    
-    if backtest_report.overall_price_accuracy < 0.6 {
-        suggestions.push("增加技术指标特征：添加ATR、Williams%R、ROC等指标".to_string());
-        expected_improvement += 0.08;
-    }
-    
-    if backtest_report.overall_direction_accuracy < 0.7 {
-        suggestions.push("优化趋势识别：实现多时间框架均线系统".to_string());
-        expected_improvement += 0.12;
+    date: String,
+) -> Result<SuppressionStatus, String> {
+    validate_stock_code(&stock_code)?;
+    let target_date = NaiveDate::parse_from_str(&date, "%Y-%m-%d")
+        .map_err(|e| format!("日期格式错误（应为 YYYY-MM-DD）: {e}"))?;
+    let filter = TradeSignalFilter::default();
+    let days_ahead = (target_date - Local::now().date_naive()).num_days().max(0) as usize
+        + filter.max_window_days();
+    let events = fetch_upcoming_events(&stock_code, days_ahead)
+        .await
+        .map_err(|e| format!("获取公司事件失败: {e}"))?;
+    let nearby: Vec<CorporateEvent> = suppressing_events(target_date, &events, &filter)
+        .into_iter()
+        .cloned()
+        .collect();
+    Ok(SuppressionStatus {
+        stock_code,
+        date,
+        suppressed: !nearby.is_empty(),
+        events: nearby,
+        filter,
+    })
+}
+
+/// 成交量异常分析读取的历史交易日数（约一年）
+const VOLUME_ANOMALY_HISTORY_DAYS: usize = 250;
+
+/// 成交量异常分析：按回看窗口的经验分位数识别放量异常，并统计异常日之后
+/// [`VOLUME_REACTION_DAYS`] 个交易日的价格反应
+#[tauri::command]
+pub async fn analyze_volume_anomalies(
+    stock_code: String,
+    lookback: Option<usize>,
+) -> Result<VolumeAnomalyReport, String> {
+    validate_stock_code(&stock_code)?;
+    let lookback = lookback.unwrap_or(DEFAULT_VOLUME_LOOKBACK);
+    if lookback < 20 {
+        return Err(format!("回看天数至少为 20: {lookback}"));
     }
-    
-    if suggestions.is_empty() {
-        suggestions.push("当前模型表现良好，建议继续观察".to_string());
+    let pool = create_temp_pool().await?;
+    let historical = get_recent_historical_data(&stock_code, VOLUME_ANOMALY_HISTORY_DAYS + lookback, &pool)
+        .await
+        .map_err(|e| format!("获取历史数据失败: {e}"))?;
+    if historical.len() <= lookback {
+        return Err(format!("历史数据不足：需多于 {lookback} 个交易日，当前 {} 个", historical.len()));
     }
-    
-    Ok(OptimizationSuggestions {
+
+    let volumes: Vec<i64> = historical.iter().map(|h| h.volume).collect();
+    let closes: Vec<f64> = historical.iter().map(|h| h.close).collect();
+    let mut anomalies = detect_volume_anomalies(&volumes, lookback);
+    attach_price_reactions(&mut anomalies, &closes, VOLUME_REACTION_DAYS);
+    let anomaly_dates = anomalies
+        .iter()
+        .map(|anomaly| historical[anomaly.day_index].date.format("%Y-%m-%d").to_string())
+        .collect();
+
+    Ok(VolumeAnomalyReport {
+        significant: summarize_anomaly_accuracy(&anomalies, &closes, VolumeAnomalyType::Significant),
+        extreme: summarize_anomaly_accuracy(&anomalies, &closes, VolumeAnomalyType::Extreme),
         stock_code,
-        model_name,
-        suggestions,
-        expected_improvement,
+        lookback,
+        anomalies,
+        anomaly_dates,
     })
 }
 
@@ -485,29 +1707,74 @@ pub async fn analyze_multi_timeframe_prediction_value(symbol: String) -> Result<
 // 专业预测命令
 // =============================================================================
 
-/// 专业策略预测
+/// 专业策略预测并写入预测历史；买卖点信号强度超过通知阈值时推送强信号通知
 #[tauri::command]
-pub async fn predict_with_professional_strategy(request: PredictionRequest) -> Result<ProfessionalPredictionResponse, String> {
-    predict_with_professional_strategy_inner(request, None).await
+pub async fn predict_with_professional_strategy(
+    app: tauri::AppHandle,
+    request: PredictionRequest,
+) -> Result<ProfessionalPredictionResponse, String> {
+    validate_prediction_request(&request)?;
+    let pool = create_temp_pool().await?;
+    let response = predict_with_professional_strategy_with_pool(request.clone(), None, &pool).await?;
+    let model_id = request.model_name.as_deref().unwrap_or("professional");
+    services::prediction::record_prediction_history(&pool, &request.stock_code, model_id, &response.predictions)
+        .await;
+    let analysis = &response.professional_analysis;
+    for point in analysis.buy_points.iter().chain(&analysis.sell_points) {
+        // 买卖点信号强度为 0–1，通知阈值为 0–100
+        if let Err(e) = notifications::notify_strong_signal(
+            &app,
+            &request.stock_code,
+            &point.point_type,
+            point.signal_strength * 100.0,
+        ) {
+            warn!("强信号通知推送失败: {e}");
+        }
+    }
+    Ok(response)
 }
 
+/// 均值回归检测的均线周期与偏离阈值（标准差倍数，对应布林带 20/2）
+const MEAN_REVERSION_MA_PERIOD: usize = 20;
+const MEAN_REVERSION_STD_THRESHOLD: f64 = 2.0;
+/// 趋势过滤使用的 DMI 周期
+const DMI_FILTER_PERIOD: usize = 14;
+/// 最新K线距斐波那契时间周期不超过该根数时视为处于时间窗口
+const TIME_CYCLE_PROXIMITY_BARS: usize = 1;
+/// 时间窗口与K线形态共振时的置信度加成
+const TIME_CYCLE_CONFIDENCE_BOOST: f64 = 0.05;
+/// 买卖点止盈目标最多取的关键位数
+const MAX_TAKE_PROFIT_LEVELS: usize = 3;
+
 pub(crate) async fn predict_with_professional_strategy_inner(
     request: PredictionRequest,
     history_days: Option<usize>,
+) -> Result<ProfessionalPredictionResponse, String> {
+    validate_prediction_request(&request)?;
+    let pool = create_temp_pool().await?;
+    predict_with_professional_strategy_with_pool(request, history_days, &pool).await
+}
+
+async fn predict_with_professional_strategy_with_pool(
+    request: PredictionRequest,
+    history_days: Option<usize>,
+    pool: &SqlitePool,
 ) -> Result<ProfessionalPredictionResponse, String> {
     let analysis_days = history_days
         .unwrap_or(inference::MAX_ANALYSIS_DAYS)
         .clamp(inference::MIN_ANALYSIS_DAYS, inference::MAX_ANALYSIS_DAYS);
 
+    let data_warning = stale_data_warning(&request, pool).await;
+
     let mut predictions = if request.use_candle {
         inference::predict_with_model(request.clone()).await?
     } else {
-        inference::predict_with_history(request.clone(), analysis_days).await?
+        inference::predict_with_history_with_pool(request.clone(), analysis_days, pool).await?
     };
+    predictions.data_warning = data_warning;
 
     // 获取历史数据进行专业分析
-    let pool = create_temp_pool().await?;
-    let historical = get_recent_historical_data(&request.stock_code, analysis_days, &pool)
+    let historical = get_recent_historical_data(&request.stock_code, analysis_days, pool)
         .await
         .map_err(|e| format!("获取历史数据失败: {e}"))?;
     
@@ -524,6 +1791,65 @@ pub(crate) async fn predict_with_professional_strategy_inner(
     let current_price = *prices.last().unwrap();
     let last_data = historical.last().unwrap();
     
+    // 市场宽度：股票池不足时不计入情绪因子，查询失败同样降级为缺省
+    let market_ad_ratio = match get_symbols_with_min_bars(services::MARKET_BREADTH_MIN_BARS, pool).await {
+        Ok(symbols) if symbols.len() >= services::MARKET_BREADTH_MIN_SYMBOLS => {
+            services::calculate_latest_ad_ratio(&symbols, pool).await.ok()
+        }
+        _ => None,
+    };
+    // 全市场情绪指数：样本不足或查询失败时情绪因子回退到个股超买超卖推断
+    let market_fear_greed = match market_ad_ratio {
+        Some(_) => services::market_sentiment_for_prediction(pool)
+            .await
+            .ok()
+            .filter(|index| index.sample_size >= services::MARKET_BREADTH_MIN_SYMBOLS)
+            .map(|index| index.fear_greed_index),
+        None => None,
+    };
+
+    // 个股新闻情绪：仅在请求开启时联网获取；网络不可用或无近期新闻时不计入情绪因子
+    let news_sentiment = if request.include_news {
+        fetch_stock_news(&request.stock_code, DEFAULT_NEWS_DAYS)
+            .await
+            .ok()
+            .filter(|news| !news.is_empty())
+            .map(|news| score_news_sentiment(&news))
+    } else {
+        None
+    };
+
+    // 相对上证综指的 Beta：指数数据未入库时不调整波动率因子
+    let beta = services::calculate_stock_beta(
+        &request.stock_code,
+        services::DEFAULT_BETA_INDEX,
+        services::DEFAULT_BETA_LOOKBACK_DAYS,
+        pool,
+    )
+    .await
+    .ok()
+    .flatten()
+    .map(|analysis| analysis.beta);
+
+    // 所属行业相对全市场的强度：行业信息未导入或成分股不足时不做轮动调整
+    let sector_relative_strength = services::get_stock_sector_strength(&request.stock_code, pool)
+        .await
+        .ok()
+        .flatten();
+
+    // 基本面：估值/财务数据未入库时不计入多因子评分
+    let fundamental = load_fundamental_factor(&request.stock_code, pool).await;
+
+    // 个股学习到的因子权重：未训练或读取失败时仅用市场状态权重
+    let learned_weights = AdaptiveWeightOptimizer::load(&request.stock_code, pool)
+        .await
+        .ok()
+        .filter(AdaptiveWeightOptimizer::has_samples)
+        .map(|optimizer| optimizer.get_current_weights());
+
+    // 当前选中的指标预设（未选择时为默认周期）
+    let indicator_config = get_active_indicator_config(pool).await;
+
     let prediction_days = request.prediction_days.max(1);
     let analysis = inference::analyze(
         &prices,
@@ -535,6 +1861,14 @@ pub(crate) async fn predict_with_professional_strategy_inner(
             turnover_rate: last_data.turnover_rate,
             prediction_days,
             stock_code: Some(&request.stock_code),
+            market_ad_ratio,
+            market_fear_greed,
+            news_sentiment,
+            learned_factor_weights: learned_weights.as_deref(),
+            beta,
+            sector_relative_strength,
+            fundamental,
+            indicator_config: Some(&indicator_config),
         },
     );
     let mut professional_result = analysis.professional_result.clone();
@@ -544,14 +1878,47 @@ pub(crate) async fn predict_with_professional_strategy_inner(
         prediction_days,
         Some(&request.stock_code),
     );
+    // DMI/ADX 趋势过滤（Wilder 平滑 ADX）：无趋势时不给强信号，强趋势顺势时增强
+    let (di_plus, di_minus, adx) =
+        calculate_wilder_adx(&highs, &lows, &prices, DMI_FILTER_PERIOD);
+    professional_result.apply_trend_filter(adx, di_plus, di_minus);
     if let Some(adjustment) =
-        latest_cross_section_adjustment(&request.stock_code, prediction_days, &pool).await?
+        latest_cross_section_adjustment(&request.stock_code, prediction_days, pool).await?
     {
         // 截面排名已降级为"相对强弱描述指标"（见 .claude/CLAUDE.md 结论2，非收益保证），
         // 仅作描述性提示附到 key_factors，不再注入点预测涨跌幅。
         append_prediction_factor(&mut predictions, &adjustment.summary);
         professional_result.key_factors.push(adjustment.summary);
     }
+    let horizon_recommendation = recommend_prediction_horizon(
+        &prices,
+        &analysis.trend_analysis.overall_trend,
+        analysis.volatility,
+    );
+    // 逐日置信度按波动率调整后的衰减比例重算，先于流动性上限与事件抑制
+    for (day_index, prediction) in predictions.predictions.iter_mut().enumerate() {
+        prediction.confidence =
+            decayed_confidence(prediction.confidence, day_index, horizon_recommendation.confidence_decay_rate);
+    }
+
+    // 流动性风险：成交稀少的股票价格易被少量资金推动，置信度上限 0.5
+    let circulating_shares = repository::get_stock_capital(&canonical_stock_symbol(&request.stock_code), pool)
+        .await
+        .ok()
+        .flatten()
+        .map(|capital| capital.circulating_shares.round() as i64);
+    let liquidity = services::calculate_liquidity_score(&volumes, &prices, circulating_shares);
+    let liquidity_warning = liquidity.warning();
+    if let Some(warning) = &liquidity_warning {
+        regime_conditional::cap_prediction_confidence(
+            &mut predictions,
+            services::VERY_LOW_LIQUIDITY_CONFIDENCE_CAP,
+        );
+        professional_result.confidence = professional_result
+            .confidence
+            .min(services::VERY_LOW_LIQUIDITY_CONFIDENCE_CAP);
+        professional_result.key_factors.push(warning.clone());
+    }
     let risk = &professional_result.risk_assessment;
     let diagnostics_risk_level = predictions
         .diagnostics
@@ -559,6 +1926,14 @@ pub(crate) async fn predict_with_professional_strategy_inner(
         .map(|diagnostics| diagnostics.risk_summary.level_label.clone())
         .filter(|label| !label.is_empty());
     
+    // ATR 跟踪止损：止损距离随波动率缩放，高波动股票 2 倍 ATR、低波动 1.5 倍
+    let atr_multiplier = select_atr_multiplier(&prices, &highs, &lows);
+    let trailing_stop = Some(calculate_atr_trailing_stop(&prices, &highs, &lows, atr_multiplier))
+        .filter(|stop| stop.atr_value > 0.0);
+    let atr_stop_distance = trailing_stop.as_ref().map(|stop| stop.atr_value * atr_multiplier);
+    // 斐波那契时间窗口：最新K线距最近摆动点的时间周期
+    let time_cycle_proximity = latest_time_cycle_proximity(&highs, &lows);
+
     // 生成买卖点
     let mut buy_points = Vec::new();
     let mut sell_points = Vec::new();
@@ -571,15 +1946,26 @@ pub(crate) async fn predict_with_professional_strategy_inner(
             .first()
             .copied()
             .unwrap_or(current_price);
-        let stop_loss = price_level * (1.0 - risk.suggested_stop_loss / 100.0);
+        let stop_loss = match atr_stop_distance {
+            Some(distance) => (price_level - distance).max(0.0),
+            None => price_level * (1.0 - risk.suggested_stop_loss / 100.0),
+        };
+        let take_profit = take_profit_levels(
+            price_level,
+            &analysis.support_resistance.resistance_levels,
+            risk.suggested_take_profit,
+            true,
+        );
         
         buy_points.push(BuySellPoint {
             point_type: "买入".to_string(),
             signal_strength: professional_result.confidence,
             price_level,
             stop_loss,
-            take_profit: Vec::new(),
-            risk_reward_ratio: 0.0,
+            trailing_stop: trailing_stop.clone(),
+            time_cycle_proximity,
+            risk_reward_ratio: risk_reward_ratio(price_level, stop_loss, &take_profit),
+            take_profit,
             reasons: vec![
                 format!("专业方向: {}", professional_result.direction.to_string()),
                 format!("量价信号: {}", analysis.volume_signal.signal),
@@ -587,6 +1973,10 @@ pub(crate) async fn predict_with_professional_strategy_inner(
             ],
             confidence: professional_result.confidence,
         });
+        let pattern_aligned = analysis.patterns.iter().any(|p| p.is_bullish);
+        if let Some(point) = buy_points.last_mut() {
+            apply_time_cycle_alignment(point, pattern_aligned);
+        }
     }
     
     // 根据分析结果生成卖点
@@ -597,15 +1987,27 @@ pub(crate) async fn predict_with_professional_strategy_inner(
             .first()
             .copied()
             .unwrap_or(current_price);
-        let stop_loss = price_level * (1.0 + risk.suggested_stop_loss / 100.0);
+        let stop_loss = match atr_stop_distance {
+            Some(distance) => price_level + distance,
+            None => price_level * (1.0 + risk.suggested_stop_loss / 100.0),
+        };
+        let take_profit = take_profit_levels(
+            price_level,
+            &analysis.support_resistance.support_levels,
+            risk.suggested_take_profit,
+            false,
+        );
         
         sell_points.push(BuySellPoint {
             point_type: "卖出".to_string(),
             signal_strength: professional_result.confidence,
             price_level,
             stop_loss,
-            take_profit: Vec::new(),
-            risk_reward_ratio: 0.0,
+            // 跟踪止损位于现价下方，只适用于多头；卖点止损见 stop_loss
+            trailing_stop: None,
+            time_cycle_proximity,
+            risk_reward_ratio: risk_reward_ratio(price_level, stop_loss, &take_profit),
+            take_profit,
             reasons: vec![
                 format!("专业方向: {}", professional_result.direction.to_string()),
                 format!("量价信号: {}", analysis.volume_signal.signal),
@@ -613,23 +2015,58 @@ pub(crate) async fn predict_with_professional_strategy_inner(
             ],
             confidence: professional_result.confidence,
         });
+        let pattern_aligned = analysis.patterns.iter().any(|p| !p.is_bullish);
+        if let Some(point) = sell_points.last_mut() {
+            apply_time_cycle_alignment(point, pattern_aligned);
+        }
     }
 
+    // 按用户风险偏好过滤低风险收益比、低置信度的买卖点
+    let point_config = repository::get_buy_sell_point_config(pool).await;
+    filter_buy_sell_points(&mut buy_points, &mut sell_points, &point_config);
+
     let date = last_data.date.format("%Y-%m-%d").to_string();
     let multi_timeframe = multi_timeframe::get_latest_signal(&prices, &highs, &lows, &date)
         .unwrap_or_else(|| neutral_multi_timeframe_signal(&date));
     
+    // 预测期内的公司事件（财报、除权除息等）：仅在请求开启时查询，接口不可用时不提示
+    let mut current_advice = professional_result.suggested_action.clone();
+    let mut risk_level = diagnostics_risk_level.unwrap_or_else(|| risk.risk_level.clone());
+    let (suppressed_days, in_window) = apply_event_suppression(&request, &mut predictions).await;
+    apply_suppression_to_points(&mut buy_points, &mut sell_points, &mut current_advice, &suppressed_days);
+    flag_upcoming_events(&mut current_advice, &mut risk_level, &in_window);
+    let drawdown = analyze_drawdown_characteristics(&prices);
+    if drawdown.is_high_risk() {
+        risk_level = RiskLevel::High.label().to_string();
+    }
+
     let professional_analysis = ProfessionalPrediction {
         buy_points,
         sell_points,
         support_resistance: analysis.support_resistance,
         multi_timeframe,
         divergence: summarize_divergence(&analysis.divergence_analysis),
-        current_advice: professional_result.suggested_action.clone(),
-        risk_level: diagnostics_risk_level.unwrap_or_else(|| risk.risk_level.clone()),
+        current_advice,
+        risk_level,
         candle_patterns: analysis.patterns,
-        volume_analysis: summarize_volume(&analysis.volume_signal, analysis.tech_indicators.obv_trend),
+        volume_analysis: summarize_volume(&analysis.volume_signal, &analysis.tech_indicators),
         multi_factor_score: analysis.multi_factor_score,
+        mean_reversion: Some(detect_mean_reversion_opportunity(
+            &prices,
+            MEAN_REVERSION_MA_PERIOD,
+            MEAN_REVERSION_STD_THRESHOLD,
+        )),
+        liquidity_warning,
+        horizon_recommendation: Some(horizon_recommendation),
+        drawdown: Some(drawdown),
+        opening_gap: Some(predict_gap(
+            &request.stock_code,
+            &historical,
+            OvernightSignals {
+                news_sentiment,
+                futures_change_pct: None,
+            },
+        )),
     };
     
     Ok(ProfessionalPredictionResponse {
@@ -638,17 +2075,247 @@ pub(crate) async fn predict_with_professional_strategy_inner(
     })
 }
 
+/// 止盈目标：买点取价位之上的阻力位、卖点取价位之下的支撑位，由近及远最多 3 个；
+/// 没有可用关键位时按建议止盈幅度（%）设一个目标
+fn take_profit_levels(price_level: f64, levels: &[f64], take_profit_pct: f64, bullish: bool) -> Vec<f64> {
+    let mut targets: Vec<f64> = levels
+        .iter()
+        .copied()
+        .filter(|&level| if bullish { level > price_level } else { level < price_level })
+        .collect();
+    targets.sort_by(|a, b| (a - price_level).abs().total_cmp(&(b - price_level).abs()));
+    targets.truncate(MAX_TAKE_PROFIT_LEVELS);
+    if targets.is_empty() {
+        let direction = if bullish { 1.0 } else { -1.0 };
+        targets.push(price_level * (1.0 + direction * take_profit_pct / 100.0));
+    }
+    targets
+}
+
+/// 风险收益比：到首个止盈目标的距离 / 到止损位的距离；止损距离为 0 时为 0
+fn risk_reward_ratio(price_level: f64, stop_loss: f64, take_profit: &[f64]) -> f64 {
+    let risk = (price_level - stop_loss).abs();
+    match take_profit.first() {
+        Some(target) if risk > 0.0 => (target - price_level).abs() / risk,
+        _ => 0.0,
+    }
+}
+
+/// 买点须满足风险收益比、置信度与信号强度下限；卖点只看置信度下限
+fn filter_buy_sell_points(
+    buy_points: &mut Vec<BuySellPoint>,
+    sell_points: &mut Vec<BuySellPoint>,
+    config: &BuySellPointConfig,
+) {
+    buy_points.retain(|point| {
+        point.risk_reward_ratio >= config.min_risk_reward_ratio
+            && point.confidence >= config.min_confidence
+            && point.signal_strength >= config.min_signal_strength
+    });
+    sell_points.retain(|point| point.confidence >= config.min_confidence);
+}
+
+/// 时间窗口共振：最新K线落在斐波那契时间周期附近且有同向K线形态时，提高买卖点置信度
+fn apply_time_cycle_alignment(point: &mut BuySellPoint, pattern_aligned: bool) {
+    let Some(proximity) = point.time_cycle_proximity else {
+        return;
+    };
+    if pattern_aligned && proximity <= TIME_CYCLE_PROXIMITY_BARS {
+        point.confidence = (point.confidence + TIME_CYCLE_CONFIDENCE_BOOST).min(1.0);
+        point.signal_strength = (point.signal_strength + TIME_CYCLE_CONFIDENCE_BOOST).min(1.0);
+        point.reasons.push(format!("斐波那契时间窗口（相距 {proximity} 根K线）与K线形态共振"));
+    }
+}
+
+/// 预测期内有公司事件时在操作建议后追加提示，并把风险等级上调一档
+fn flag_upcoming_events(advice: &mut String, risk_level: &mut String, events: &[CorporateEvent]) {
+    if events.is_empty() {
+        return;
+    }
+    let summary = events
+        .iter()
+        .map(|event| format!("{} {}", event.expected_date.format("%m-%d"), event.event_type.label()))
+        .collect::<Vec<_>>()
+        .join("、");
+    advice.push_str(&format!("（注意：预测期内有 {summary}，事件前后波动可能放大）"));
+    if let Some(level) = RiskLevel::from_label(risk_level) {
+        *risk_level = level.escalate().label().to_string();
+    }
+}
+
+/// 距 `target_date` 不超过对应抑制天数（自然日，前后均计）的财报披露与除权除息事件
+fn suppressing_events<'a>(
+    target_date: NaiveDate,
+    events: &'a [CorporateEvent],
+    config: &TradeSignalFilter,
+) -> Vec<&'a CorporateEvent> {
+    events
+        .iter()
+        .filter(|event| {
+            let window = match event.event_type {
+                EventType::Earnings => config.suppress_days_around_earnings,
+                EventType::ExDividend => config.suppress_days_around_dividend,
+                EventType::ShareholderMeeting | EventType::ShareUnlock => return false,
+            };
+            window > 0 && (event.expected_date - target_date).num_days().unsigned_abs() <= window as u64
+        })
+        .collect()
+}
+
+/// 目标日是否处于财报披露或除权除息的信号抑制窗口内（`stock_code` 仅用于调试日志）
+pub fn should_suppress_signal(
+    stock_code: &str,
+    target_date: NaiveDate,
+    events: &[CorporateEvent],
+    config: &TradeSignalFilter,
+) -> bool {
+    let nearby = suppressing_events(target_date, events, config);
+    if let Some(event) = nearby.first() {
+        debug!(
+            "{stock_code} {target_date} 临近 {} {}",
+            event.expected_date,
+            event.event_type.label()
+        );
+    }
+    !nearby.is_empty()
+}
+
+/// 处于抑制窗口的预测日信号改为"观望"、置信度减半，并在关键因素中注明原因；
+/// 返回被抑制的预测日下标（0 为下一交易日）
+fn suppress_signals_near_events(
+    predictions: &mut PredictionResponse,
+    stock_code: &str,
+    events: &[CorporateEvent],
+    config: &TradeSignalFilter,
+) -> Vec<usize> {
+    let mut suppressed_days = Vec::new();
+    for (day_index, prediction) in predictions.predictions.iter_mut().enumerate() {
+        let Ok(target_date) = NaiveDate::parse_from_str(&prediction.target_date, "%Y-%m-%d") else {
+            continue;
+        };
+        if !should_suppress_signal(stock_code, target_date, events, config) {
+            continue;
+        }
+        prediction.trading_signal = Some("观望".to_string());
+        prediction.confidence *= SUPPRESSED_CONFIDENCE_FACTOR;
+        prediction
+            .key_factors
+            .get_or_insert_with(Vec::new)
+            .push("临近财报披露/除权除息，事件驱动波动与技术面无关，信号暂停".to_string());
+        suppressed_days.push(day_index);
+    }
+    suppressed_days
+}
+
+/// 请求开启 `include_events` 时查询预测期内的公司事件，抑制临近财报披露/除权除息的预测日信号。
+/// 返回 (被抑制的预测日下标, 预测期内的事件)；未开启或接口不可用时均为空
+async fn apply_event_suppression(
+    request: &PredictionRequest,
+    response: &mut PredictionResponse,
+) -> (Vec<usize>, Vec<CorporateEvent>) {
+    let window_end = response
+        .predictions
+        .last()
+        .filter(|_| request.include_events)
+        .and_then(|prediction| NaiveDate::parse_from_str(&prediction.target_date, "%Y-%m-%d").ok());
+    let Some(window_end) = window_end else {
+        return (Vec::new(), Vec::new());
+    };
+    // 多取抑制窗口天数，预测期末紧随其后的事件同样抑制期末信号
+    let signal_filter = TradeSignalFilter::default();
+    let days_ahead = (window_end - Local::now().date_naive()).num_days().max(0) as usize
+        + signal_filter.max_window_days();
+    let events = match fetch_upcoming_events(&request.stock_code, days_ahead).await {
+        Ok(events) => events,
+        Err(e) => {
+            warn!("获取 {} 公司事件失败: {e}", request.stock_code);
+            return (Vec::new(), Vec::new());
+        }
+    };
+    let suppressed_days =
+        suppress_signals_near_events(response, &request.stock_code, &events, &signal_filter);
+    if !suppressed_days.is_empty() {
+        info!(
+            "{} 预测期内 {} 个交易日临近财报披露/除权除息，抑制交易信号",
+            request.stock_code,
+            suppressed_days.len()
+        );
+    }
+    let in_window = events.into_iter().filter(|event| event.expected_date <= window_end).collect();
+    (suppressed_days, in_window)
+}
+
+/// 信号抑制同步作用于买卖点：下一交易日即被抑制时清空买卖点、操作建议改为观望；
+/// 仅后续交易日被抑制时保留买卖点，在理由中注明持仓将跨越事件窗口
+fn apply_suppression_to_points(
+    buy_points: &mut Vec<BuySellPoint>,
+    sell_points: &mut Vec<BuySellPoint>,
+    advice: &mut String,
+    suppressed_days: &[usize],
+) {
+    let Some(&first_day) = suppressed_days.first() else {
+        return;
+    };
+    if first_day == 0 {
+        buy_points.clear();
+        sell_points.clear();
+        *advice = "观望：下一交易日临近财报披露/除权除息，技术面买卖点暂停".to_string();
+        return;
+    }
+    let note = format!("预测期第 {} 个交易日起临近财报披露/除权除息，持仓跨越事件需控制仓位", first_day + 1);
+    for point in buy_points.iter_mut().chain(sell_points.iter_mut()) {
+        point.reasons.push(note.clone());
+    }
+}
+
 /// 纯技术分析预测
 #[tauri::command]
 pub async fn predict_with_technical_only(request: TechnicalOnlyRequest) -> Result<ProfessionalPredictionResponse, String> {
+    let pool = create_temp_pool().await?;
+    predict_with_technical_only_with_pool(request, &pool).await
+}
+
+/// 同 [`predict_with_technical_only`]，使用给定连接池（集成测试以内存数据库调用）
+pub async fn predict_with_technical_only_with_pool(
+    request: TechnicalOnlyRequest,
+    pool: &SqlitePool,
+) -> Result<ProfessionalPredictionResponse, String> {
+    validate_technical_only_request(&request)?;
+    let prediction_days = match request.prediction_days {
+        Some(days) => days,
+        None => recommended_prediction_days(&request, pool).await?,
+    };
     let pred_request = PredictionRequest {
         stock_code: request.stock_code.clone(),
         model_name: None,
-        prediction_days: request.prediction_days,
+        prediction_days,
         use_candle: false,
+        refresh_if_stale: false,
+        include_news: false,
+        include_events: false,
     };
-    
-    predict_with_professional_strategy_inner(pred_request, request.history_days).await
+
+    predict_with_professional_strategy_with_pool(pred_request, request.history_days, pool).await
+}
+
+/// 未指定预测天数时按近期趋势与波动率推荐
+async fn recommended_prediction_days(
+    request: &TechnicalOnlyRequest,
+    pool: &SqlitePool,
+) -> Result<usize, String> {
+    let analysis_days = request
+        .history_days
+        .unwrap_or(inference::MAX_ANALYSIS_DAYS)
+        .clamp(inference::MIN_ANALYSIS_DAYS, inference::MAX_ANALYSIS_DAYS);
+    let historical = get_recent_historical_data(&request.stock_code, analysis_days, pool)
+        .await
+        .map_err(|e| format!("获取历史数据失败: {e}"))?;
+    let prices: Vec<f64> = historical.iter().map(|h| h.close).collect();
+    let highs: Vec<f64> = historical.iter().map(|h| h.high).collect();
+    let lows: Vec<f64> = historical.iter().map(|h| h.low).collect();
+    let trend = analyze_trend(&prices, &highs, &lows);
+    let volatility = calculate_historical_volatility(&prices, 20);
+    Ok(recommend_prediction_horizon(&prices, &trend.overall_trend, volatility).recommended_days)
 }
 
 struct CrossSectionAdjustment {
@@ -680,7 +2347,8 @@ async fn latest_cross_section_adjustment(
         .into_iter()
         .filter(|(_, hist)| hist.len() >= 150)
         .collect::<Vec<_>>();
-    let ranking = rank_latest(&stocks, horizon, 120);
+    let benchmark = load_cross_section_benchmark(pool).await?;
+    let ranking = rank_latest(&stocks, &benchmark, horizon, 120);
     if ranking.len() < 20 {
         return Ok(None);
     }
@@ -766,14 +2434,13 @@ fn summarize_divergence(analysis: &DivergenceAnalysis) -> VolumePriceDivergence
     }
 }
 
-fn summarize_volume(signal: &VolumePriceSignal, obv_trend: f64) -> VolumeAnalysisInfo {
+fn summarize_volume(
+    signal: &VolumePriceSignal,
+    indicators: &TechnicalIndicatorValues,
+) -> VolumeAnalysisInfo {
     let volume_price_sync = matches!(signal.direction.as_str(), "上涨" | "下跌")
         && signal.volume_trend.contains("放量");
-    let accumulation_signal = match signal.direction.as_str() {
-        "上涨" => signal.confidence,
-        "下跌" => -signal.confidence,
-        _ => 0.0,
-    };
+    let obv_trend = indicators.obv_trend;
     let obv_trend = if obv_trend > 0.05 {
         "上升"
     } else if obv_trend < -0.05 {
@@ -785,7 +2452,7 @@ fn summarize_volume(signal: &VolumePriceSignal, obv_trend: f64) -> VolumeAnalysi
     VolumeAnalysisInfo {
         volume_trend: signal.volume_trend.clone(),
         volume_price_sync,
-        accumulation_signal,
+        accumulation_signal: indicators.chaikin_oscillator,
         obv_trend: obv_trend.to_string(),
     }
 }
@@ -794,6 +2461,179 @@ fn summarize_volume(signal: &VolumePriceSignal, obv_trend: f64) -> VolumeAnalysi
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_rank_comparison_summaries() {
+        let summary = |code: &str| StockPredictionSummary {
+            stock_code: code.to_string(),
+            predicted_return_pct: 0.0,
+            confidence: 0.6,
+            signal: "持有".to_string(),
+            multi_factor_score: 50.0,
+            risk_level: "中风险".to_string(),
+            recommendation_rank: 0,
+        };
+        let ranked = rank_comparison_summaries(vec![
+            (summary("600000"), -0.2),
+            (summary("000001"), 0.3),
+            (summary("600519"), 0.1),
+        ]);
+        let order: Vec<(&str, usize)> = ranked
+            .iter()
+            .map(|s| (s.stock_code.as_str(), s.recommendation_rank))
+            .collect();
+        assert_eq!(order, vec![("000001", 1), ("600519", 2), ("600000", 3)]);
+    }
+
+    #[test]
+    fn test_change_direction_treats_flat_as_zero() {
+        assert_eq!(change_direction(0.0), 0.0);
+        assert_eq!(change_direction(-0.0), 0.0);
+        assert_eq!(change_direction(1e-9), 0.0);
+        assert_eq!(change_direction(0.5), 1.0);
+        assert_eq!(change_direction(-0.5), -1.0);
+    }
+
+    #[test]
+    fn test_rank_divergence_opportunities() {
+        let signal = |confidence: f64| DivergenceSignal {
+            divergence_type: DivergenceType::RegularBullish,
+            indicator: String::new(),
+            strength: DivergenceStrength::Strong,
+            confidence,
+            price_change: 0.0,
+            indicator_change: 0.0,
+            duration_bars: 10,
+            description: String::new(),
+        };
+        let analysis = |confidence: f64, composite_score: f64, with_macd: bool| DivergenceAnalysis {
+            rsi_divergence: Some(signal(confidence)),
+            macd_divergence: with_macd.then(|| signal(confidence)),
+            obv_divergence: Some(signal(confidence)),
+            composite_score,
+            divergence_count: if with_macd { 3 } else { 2 },
+            overall_confidence: confidence,
+            ..DivergenceAnalysis::default()
+        };
+        let candidates = vec![
+            ("600000".to_string(), analysis(0.7, 0.5, true), 10.0),
+            ("000001".to_string(), analysis(0.8, 0.9, false), 12.0),
+            // 置信度未超过门槛
+            ("600519".to_string(), analysis(0.65, 1.0, true), 1500.0),
+        ];
+
+        let ranked = rank_divergence_opportunities(candidates.clone(), &[]);
+        let order: Vec<(&str, usize)> = ranked
+            .iter()
+            .map(|o| (o.stock_code.as_str(), o.strength_rank))
+            .collect();
+        assert_eq!(order, vec![("000001", 1), ("600000", 2)]);
+        assert_eq!(ranked[0].current_price, 12.0);
+
+        // 只看 RSI 与 MACD 时 000001 仅有 1 个背离
+        let ranked = rank_divergence_opportunities(candidates, &["rsi".to_string(), "macd".to_string()]);
+        assert_eq!(ranked.len(), 1);
+        assert_eq!(ranked[0].stock_code, "600000");
+    }
+
+    #[test]
+    fn test_flag_upcoming_events_escalates_risk() {
+        let mut advice = "持有".to_string();
+        let mut risk_level = "中风险".to_string();
+        flag_upcoming_events(&mut advice, &mut risk_level, &[]);
+        assert_eq!((advice.as_str(), risk_level.as_str()), ("持有", "中风险"));
+
+        let events = [CorporateEvent {
+            event_type: EventType::Earnings,
+            expected_date: NaiveDate::from_ymd_opt(2026, 4, 25).unwrap(),
+            expected_impact: None,
+        }];
+        flag_upcoming_events(&mut advice, &mut risk_level, &events);
+        assert!(advice.starts_with("持有（注意：预测期内有 04-25 定期报告披露"));
+        assert_eq!(risk_level, "高风险");
+        flag_upcoming_events(&mut advice, &mut risk_level, &events);
+        assert_eq!(risk_level, "高风险");
+
+        // 专业引擎风险评估输出"中等风险"
+        let mut risk_level = "中等风险".to_string();
+        flag_upcoming_events(&mut advice, &mut risk_level, &events);
+        assert_eq!(risk_level, "高风险");
+        assert_eq!(RiskLevel::from_label("中等风险"), Some(RiskLevel::Medium));
+    }
+
+    #[test]
+    fn test_should_suppress_signal_around_events() {
+        let date = |d: u32| NaiveDate::from_ymd_opt(2026, 4, d).unwrap();
+        let event = |event_type: EventType, day: u32| CorporateEvent {
+            event_type,
+            expected_date: date(day),
+            expected_impact: None,
+        };
+        let config = TradeSignalFilter::default();
+        let events = [event(EventType::Earnings, 10), event(EventType::ShareUnlock, 20)];
+
+        assert!(should_suppress_signal("600000", date(7), &events, &config));
+        assert!(should_suppress_signal("600000", date(13), &events, &config));
+        assert!(!should_suppress_signal("600000", date(14), &events, &config));
+        // 解禁不在抑制范围内
+        assert!(!should_suppress_signal("600000", date(20), &events, &config));
+
+        let dividend = [event(EventType::ExDividend, 10)];
+        assert!(should_suppress_signal("600000", date(12), &dividend, &config));
+        assert!(!should_suppress_signal("600000", date(13), &dividend, &config));
+        let disabled = TradeSignalFilter {
+            suppress_days_around_dividend: 0,
+            ..config
+        };
+        assert!(!should_suppress_signal("600000", date(10), &dividend, &disabled));
+    }
+
+    #[test]
+    fn test_apply_suppression_to_points() {
+        let point = |point_type: &str| BuySellPoint {
+            point_type: point_type.to_string(),
+            signal_strength: 0.8,
+            price_level: 10.0,
+            stop_loss: 9.5,
+            trailing_stop: None,
+            time_cycle_proximity: None,
+            take_profit: vec![11.0],
+            risk_reward_ratio: 2.0,
+            reasons: Vec::new(),
+            confidence: 0.8,
+        };
+        let (mut buys, mut sells) = (vec![point("买入")], vec![point("卖出")]);
+        let mut advice = "买入".to_string();
+        apply_suppression_to_points(&mut buys, &mut sells, &mut advice, &[]);
+        assert_eq!((buys.len(), sells.len(), advice.as_str()), (1, 1, "买入"));
+
+        // 仅后续交易日被抑制：保留买卖点并注明
+        apply_suppression_to_points(&mut buys, &mut sells, &mut advice, &[2, 3]);
+        assert_eq!(advice, "买入");
+        assert!(buys[0].reasons[0].starts_with("预测期第 3 个交易日起"));
+        assert_eq!(sells[0].reasons.len(), 1);
+
+        // 下一交易日即被抑制：清空买卖点，建议改为观望
+        apply_suppression_to_points(&mut buys, &mut sells, &mut advice, &[0, 1]);
+        assert!(buys.is_empty() && sells.is_empty());
+        assert!(advice.starts_with("观望"));
+    }
+
+    #[test]
+    fn test_batch_prediction_config_fills_missing_fields() {
+        let config: BatchPredictionConfig =
+            serde_json::from_str(r#"{"max_concurrent": 8}"#).unwrap();
+        assert_eq!(config.max_concurrent, 8);
+        assert!(config.use_model_if_available);
+        assert!(config.fallback_to_technical);
+
+        let failed = BatchPredictionResult {
+            stock_code: "600519".to_string(),
+            result: Err("未找到历史数据".to_string()),
+        };
+        let json = serde_json::to_value(&failed).unwrap();
+        assert_eq!(json["result"]["Err"], "未找到历史数据");
+    }
+
     #[test]
     fn test_symbol_digits_normalizes_market_suffix() {
         assert_eq!(symbol_digits("000001.SZ"), "000001");
@@ -816,6 +2656,9 @@ mod tests {
                     key_factors: None,
                     interval: None,
                     stress_interval: None,
+                    explanation: None,
+                    prediction_low: 10.0,
+                    prediction_high: 10.0,
                 },
                 Prediction {
                     target_date: "2026-01-05".to_string(),
@@ -829,6 +2672,9 @@ mod tests {
                     key_factors: None,
                     interval: None,
                     stress_interval: None,
+                    explanation: None,
+                    prediction_low: 10.0,
+                    prediction_high: 10.0,
                 },
             ],
             last_real_data: Some(LastRealData {
@@ -837,6 +2683,7 @@ mod tests {
                 change_percent: 0.0,
             }),
             diagnostics: None,
+            data_warning: None,
         };
 
         append_prediction_factor(&mut response, "截面测试");
@@ -849,4 +2696,71 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_validate_prediction_request() {
+        let request = |stock_code: &str, prediction_days: usize| PredictionRequest {
+            stock_code: stock_code.to_string(),
+            model_name: None,
+            prediction_days,
+            use_candle: false,
+            refresh_if_stale: false,
+            include_news: false,
+            include_events: false,
+        };
+        assert!(validate_prediction_request(&request("600519", 5)).is_ok());
+        assert!(validate_prediction_request(&request("sh600519", 30)).is_ok());
+        assert!(validate_prediction_request(&request("60051", 5)).is_err());
+        assert!(validate_prediction_request(&request("600519", 0)).is_err());
+        assert!(validate_prediction_request(&request("600519", 31)).is_err());
+
+        let technical = |history_days: Option<usize>| TechnicalOnlyRequest {
+            stock_code: "000001".to_string(),
+            history_days,
+            prediction_days: None,
+        };
+        assert!(validate_technical_only_request(&technical(None)).is_ok());
+        assert!(validate_technical_only_request(&technical(Some(60))).is_ok());
+        assert!(validate_technical_only_request(&technical(Some(59))).is_err());
+        assert!(validate_technical_only_request(&technical(Some(1001))).is_err());
+    }
+
+    #[test]
+    fn test_validate_training_features() {
+        let features = |names: &[&str]| names.iter().map(|n| n.to_string()).collect::<Vec<_>>();
+        assert!(validate_training_features(&features(&["ret_1d", "rsi14", "volume_ratio"])).is_ok());
+        assert!(validate_training_features(&features(&["ret_1d", "rsi14"])).is_err());
+        let err = validate_training_features(&features(&["ret_1d", "rsi14", "close_lag_5"])).unwrap_err();
+        assert!(err.contains("close_lag_5"));
+    }
+
+    #[test]
+    fn test_filter_buy_sell_points_by_risk_reward() {
+        // 支撑 10、止损 9.5、最近阻力 10.6：风险收益比 1.2
+        let take_profit = take_profit_levels(10.0, &[12.0, 10.6, 9.0], 5.0, true);
+        assert_eq!(take_profit, vec![10.6, 12.0]);
+        let ratio = risk_reward_ratio(10.0, 9.5, &take_profit);
+        assert!((ratio - 1.2).abs() < 1e-9);
+        assert_eq!(take_profit_levels(10.0, &[], 5.0, false), vec![9.5]);
+
+        let point = |risk_reward_ratio: f64, confidence: f64| BuySellPoint {
+            point_type: "买入".to_string(),
+            signal_strength: confidence,
+            price_level: 10.0,
+            stop_loss: 9.5,
+            trailing_stop: None,
+            time_cycle_proximity: None,
+            take_profit: Vec::new(),
+            risk_reward_ratio,
+            reasons: Vec::new(),
+            confidence,
+        };
+        let config = BuySellPointConfig::default();
+        let mut buys = vec![point(ratio, 0.8), point(2.0, 0.8), point(2.0, 0.2)];
+        let mut sells = vec![point(0.0, 0.5), point(0.0, 0.1)];
+        filter_buy_sell_points(&mut buys, &mut sells, &config);
+        assert_eq!(buys.len(), 1);
+        assert_eq!(buys[0].risk_reward_ratio, 2.0);
+        assert_eq!(sells.len(), 1);
+        assert_eq!(sells[0].confidence, 0.5);
+    }
 }
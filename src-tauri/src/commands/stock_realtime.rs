@@ -1,6 +1,8 @@
-use crate::db::models::RealtimeData;
+use crate::db::models::{RealtimeData, RealtimeQuote};
+use crate::db::repository;
 use crate::error::AppError;
 use crate::commands::pagination::{normalize_page, PagedResponse};
+use chrono::Local;
 use sqlx::SqlitePool;
 use tauri::State;
 
@@ -129,6 +131,15 @@ pub async fn get_realtime_data(
     })
 }
 
+/// 当日行情快照（按时间正序），用于简易分时走势
+#[tauri::command]
+pub async fn get_quote_history_today(
+    stock_code: String,
+    pool: State<'_, SqlitePool>,
+) -> Result<Vec<RealtimeQuote>, AppError> {
+    repository::get_quotes_on_date(&pool, &stock_code, Local::now().date_naive()).await
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
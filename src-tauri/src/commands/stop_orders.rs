@@ -0,0 +1,74 @@
+//! 止损止盈单命令
+//!
+//! 生效中的单据由后台监控任务（[`crate::services::stop_orders`]）在交易时段按实时行情检查，
+//! 触发后推送 `stop_order_triggered` 事件，模拟账户持仓自动卖出
+
+use crate::db::models::{StopOrder, StopOrderType};
+use crate::db::repository;
+use crate::error::AppError;
+use sqlx::SqlitePool;
+use tauri::State;
+
+fn validate_trigger_price(trigger_price: f64) -> Result<(), AppError> {
+    if !(trigger_price.is_finite() && trigger_price > 0.0) {
+        return Err(AppError::InvalidInput("触发价必须为正数".to_string()));
+    }
+    Ok(())
+}
+
+async fn load_stop_order(pool: &SqlitePool, id: i64) -> Result<StopOrder, AppError> {
+    repository::get_stop_order(pool, id)
+        .await?
+        .ok_or_else(|| AppError::InvalidInput(format!("止损止盈单 {id} 不存在")))
+}
+
+/// 新建止损或止盈单
+#[tauri::command]
+pub async fn create_stop_order(
+    stock_code: String,
+    order_type: StopOrderType,
+    trigger_price: f64,
+    pool: State<'_, SqlitePool>,
+) -> Result<StopOrder, AppError> {
+    if stock_code.trim().is_empty() {
+        return Err(AppError::InvalidInput("股票代码不能为空".to_string()));
+    }
+    validate_trigger_price(trigger_price)?;
+    let id = repository::insert_stop_order(&pool, stock_code.trim(), order_type, trigger_price)
+        .await?;
+    load_stop_order(&pool, id).await
+}
+
+/// 查询止损止盈单，可按股票过滤
+#[tauri::command]
+pub async fn get_stop_orders(
+    stock_code: Option<String>,
+    pool: State<'_, SqlitePool>,
+) -> Result<Vec<StopOrder>, AppError> {
+    repository::get_stop_orders(stock_code.as_deref(), &pool).await
+}
+
+/// 修改生效中单据的触发价
+#[tauri::command]
+pub async fn update_stop_order(
+    id: i64,
+    trigger_price: f64,
+    pool: State<'_, SqlitePool>,
+) -> Result<StopOrder, AppError> {
+    validate_trigger_price(trigger_price)?;
+    repository::update_stop_order_price(&pool, id, trigger_price).await?;
+    load_stop_order(&pool, id).await
+}
+
+/// 撤销生效中的单据（保留记录）
+#[tauri::command]
+pub async fn cancel_stop_order(id: i64, pool: State<'_, SqlitePool>) -> Result<StopOrder, AppError> {
+    repository::cancel_stop_order(&pool, id).await?;
+    load_stop_order(&pool, id).await
+}
+
+/// 删除止损止盈单
+#[tauri::command]
+pub async fn delete_stop_order(id: i64, pool: State<'_, SqlitePool>) -> Result<(), AppError> {
+    repository::delete_stop_order(&pool, id).await
+}
@@ -0,0 +1,22 @@
+//! 全市场股票池命令
+
+use crate::db::repository::{self, UniverseCriteria};
+use crate::error::AppError;
+use crate::services::universe;
+use sqlx::SqlitePool;
+use tauri::State;
+
+/// 从接口刷新全市场股票池，返回活跃股票数
+#[tauri::command]
+pub async fn update_universe(pool: State<'_, SqlitePool>) -> Result<usize, AppError> {
+    universe::update_universe(&pool).await
+}
+
+/// 按交易所、行业、市值分档与是否排除 ST 筛选股票代码
+#[tauri::command]
+pub async fn filter_stock_universe(
+    pool: State<'_, SqlitePool>,
+    criteria: UniverseCriteria,
+) -> Result<Vec<String>, AppError> {
+    repository::filter_universe(&pool, &criteria).await
+}
@@ -20,16 +20,29 @@ use crate::error::AppError;
 use crate::prediction::types::{
     PredictionInterval, PredictionRequest, ProfessionalPredictionResponse, RiskSummary,
 };
+use crate::services::historical::refresh_stock_full;
+use crate::services::prediction::record_prediction_history;
+use crate::services::progress::{default_operation_id, ProgressReporter};
+use crate::utils::progress::ProgressSink;
 use crate::utils::canonical_stock_symbol;
 use chrono::{Datelike, Duration, Local, NaiveDate};
+use futures::stream::{self, StreamExt};
+use log::error;
 use sqlx::SqlitePool;
 use std::collections::HashMap;
-use tauri::State;
+use std::time::Instant;
+use tauri::{AppHandle, Emitter, State};
 
 /// 概览/综合预测的取数窗口（根）：覆盖一年约 244 个交易日 + YTD 最坏回溯与假期余量
 const OVERVIEW_BARS: usize = 270;
 /// 综合预测的分析历史窗口（与预测页"纯技术分析"同路径，inner 内部会 clamp 到 [120, 3000]）
 const COMPREHENSIVE_HISTORY_DAYS: usize = 1500;
+/// 综合预测写入预测历史时的模型标识
+const COMPREHENSIVE_MODEL_ID: &str = "comprehensive";
+/// 批量刷新的并发上限：同时在途的单票刷新数（每票 3 个 zhitu 请求，控制总并发避免触发限流）
+const REFRESH_CONCURRENCY: usize = 5;
+/// 批量刷新进度事件名
+pub const REFRESH_PROGRESS_EVENT: &str = "refresh_progress";
 
 // =============================================================================
 // 指标纯函数（全部诚实缺省：数据不足返回 None，不用不完整窗口凑数）
@@ -334,6 +347,96 @@ pub async fn get_watchlist_symbols(pool: State<'_, SqlitePool>) -> Result<Vec<St
     Ok(rows.into_iter().map(|(s,)| s).collect())
 }
 
+// =============================================================================
+// 一键批量刷新
+// =============================================================================
+
+/// 收藏池批量刷新汇总
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct RefreshSummary {
+    /// 刷新成功的股票数
+    pub stocks_updated: usize,
+    /// 刷新失败的股票代码
+    pub stocks_failed: Vec<String>,
+    /// 新写入的历史K线总条数
+    pub total_new_records: usize,
+    /// 总耗时（毫秒）
+    pub duration_ms: u64,
+}
+
+/// `refresh_progress` 事件负载：每完成一只股票推送一次
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct RefreshProgress {
+    pub completed: usize,
+    pub total: usize,
+    pub current_stock: String,
+}
+
+/// 一键刷新收藏池全部股票（历史K线 + 股本/估值 + 基本面，同 `refresh_historical_data`）。
+/// 最多 `REFRESH_CONCURRENCY` 只并发；单票失败不阻断其余股票，计入 `stocks_failed`。
+/// 应用目前只有一个收藏池，`watchlist_id` 为预留参数，暂不参与查询。
+/// 同时推送 `operation_progress`；经 `cancel_operation(operation_id)` 取消后不再启动新的股票，
+/// 已完成的部分照常汇总返回。
+#[tauri::command]
+pub async fn refresh_all_watchlist_data(
+    watchlist_id: String,
+    operation_id: Option<String>,
+    app: AppHandle,
+    pool: State<'_, SqlitePool>,
+) -> Result<RefreshSummary, AppError> {
+    let _ = watchlist_id;
+    let progress = ProgressReporter::start(
+        &app,
+        operation_id.unwrap_or_else(|| default_operation_id("watchlist_refresh")),
+    )
+    .map_err(AppError::InvalidInput)?;
+    let started = Instant::now();
+    let rows: Vec<(String,)> =
+        sqlx::query_as("SELECT symbol FROM watchlist ORDER BY sort_order, added_at")
+            .fetch_all(&*pool)
+            .await?;
+    let total = rows.len();
+    let pool: &SqlitePool = &pool;
+
+    let mut results = stream::iter(rows.into_iter().map(|(symbol,)| async move {
+        let result = refresh_stock_full(&symbol, pool).await;
+        (symbol, result)
+    }))
+    .buffer_unordered(REFRESH_CONCURRENCY);
+
+    let mut summary = RefreshSummary::default();
+    let mut completed = 0usize;
+    while let Some((symbol, result)) = results.next().await {
+        completed += 1;
+        progress.report(completed, total, &format!("已刷新 {symbol}"));
+        match result {
+            Ok(stock_summary) => {
+                summary.stocks_updated += 1;
+                summary.total_new_records += stock_summary.bars as usize;
+            }
+            Err(e) => {
+                error!("收藏池刷新 {symbol} 失败: {e}");
+                summary.stocks_failed.push(symbol.clone());
+            }
+        }
+        // 进度推送失败（窗口已关闭等）不影响刷新本身
+        let _ = app.emit(
+            REFRESH_PROGRESS_EVENT,
+            RefreshProgress {
+                completed,
+                total,
+                current_stock: symbol,
+            },
+        );
+        if progress.is_cancelled() {
+            break;
+        }
+    }
+
+    summary.duration_ms = started.elapsed().as_millis() as u64;
+    Ok(summary)
+}
+
 // =============================================================================
 // 一键综合预测
 // =============================================================================
@@ -400,7 +503,7 @@ pub struct ComprehensiveReport {
 
 /// 一键综合预测：专业策略预测（纯技术路径，与预测页"纯技术分析"同口径）
 /// + 估值上下文 + 描述性动量/52周位置 + 历史基准率，一次调用聚合成报告。
-/// 纯本地计算，不消耗 API 额度。
+/// 除公司事件日历（临近财报披露/除权除息时抑制信号，按股票每日缓存）外纯本地计算，不消耗 API 额度。
 #[tauri::command]
 pub async fn comprehensive_predict(
     symbol: String,
@@ -418,6 +521,9 @@ pub async fn comprehensive_predict(
         model_name: None,
         prediction_days,
         use_candle: false,
+        refresh_if_stale: false,
+        include_news: false,
+        include_events: true,
     };
     let prediction =
         predict_with_professional_strategy_inner(request, Some(COMPREHENSIVE_HISTORY_DAYS)).await?;
@@ -440,6 +546,8 @@ pub async fn comprehensive_predict(
 
     // 3) 描述性动量/52周位置 + 历史基准率（单票查询内部自带 symbol 变体解析）
     let pool = create_temp_pool().await?;
+    record_prediction_history(&pool, &canonical, COMPREHENSIVE_MODEL_ID, &prediction.predictions)
+        .await;
     let bars = get_recent_historical_data(&canonical, OVERVIEW_BARS, &pool)
         .await
         .map_err(|e| format!("获取历史数据失败: {e}"))?;
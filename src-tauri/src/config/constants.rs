@@ -63,6 +63,12 @@ pub const MAX_VOLATILITY: f64 = 0.10;
 pub const A_STOCK_LIMIT_UP: f64 = 10.0;
 /// A股跌停限制 (%)
 pub const A_STOCK_LIMIT_DOWN: f64 = -10.0;
+/// 科创板/创业板涨跌停限制 (%)
+pub const GROWTH_BOARD_LIMIT_UP: f64 = 20.0;
+pub const GROWTH_BOARD_LIMIT_DOWN: f64 = -20.0;
+/// 北交所涨跌停限制 (%)
+pub const BSE_LIMIT_UP: f64 = 30.0;
+pub const BSE_LIMIT_DOWN: f64 = -30.0;
 
 // =============================================================================
 // 信号阈值
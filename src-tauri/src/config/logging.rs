@@ -0,0 +1,78 @@
+//! 日志级别配置
+//!
+//! 级别以 JSON 保存在 `~/.biga/log_config.json`，启动时应用，可经 `set_log_level` 运行时调整；
+//! 文件不存在或损坏时使用 INFO。
+//!
+//! 级别约定：
+//! - DEBUG：逐项技术指标取值等诊断细节
+//! - INFO：预测结论、训练/回填/归档等状态变化
+//! - WARN：数据质量问题、非关键的写入或推送失败
+//! - ERROR：外部 API 调用失败、后台任务失败
+
+use crate::error::AppError;
+use log::LevelFilter;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+/// 默认日志级别
+pub const DEFAULT_LOG_LEVEL: LevelFilter = LevelFilter::Info;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct LogConfig {
+    level: String,
+}
+
+fn config_path() -> PathBuf {
+    let home = dirs::home_dir().unwrap_or_else(|| PathBuf::from("."));
+    home.join(".biga").join("log_config.json")
+}
+
+/// 解析日志级别（off / error / warn / info / debug / trace，不区分大小写）
+pub fn parse_log_level(level: &str) -> Result<LevelFilter, AppError> {
+    LevelFilter::from_str(level.trim())
+        .map_err(|_| AppError::InvalidInput(format!("未知日志级别: {level}")))
+}
+
+/// 读取已保存的日志级别；文件不存在或无法解析时返回默认值
+pub fn load_log_level() -> LevelFilter {
+    fs::read_to_string(config_path())
+        .ok()
+        .and_then(|content| serde_json::from_str::<LogConfig>(&content).ok())
+        .and_then(|config| parse_log_level(&config.level).ok())
+        .unwrap_or(DEFAULT_LOG_LEVEL)
+}
+
+/// 保存日志级别
+pub fn save_log_level(level: LevelFilter) -> Result<(), AppError> {
+    let path = config_path();
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let config = LogConfig {
+        level: level.as_str().to_ascii_lowercase(),
+    };
+    let content = serde_json::to_string_pretty(&config)
+        .map_err(|e| AppError::DeserializationError(e.to_string()))?;
+    fs::write(path, content)?;
+    Ok(())
+}
+
+/// 应用日志级别（全局生效，对所有日志目标立即起作用）
+pub fn apply_log_level(level: LevelFilter) {
+    log::set_max_level(level);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_log_level() {
+        assert_eq!(parse_log_level("debug").unwrap(), LevelFilter::Debug);
+        assert_eq!(parse_log_level(" WARN ").unwrap(), LevelFilter::Warn);
+        assert_eq!(parse_log_level("off").unwrap(), LevelFilter::Off);
+        assert!(parse_log_level("verbose").is_err());
+    }
+}
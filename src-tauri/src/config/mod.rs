@@ -4,10 +4,20 @@
 //! - 预测权重配置
 //! - 技术指标参数
 //! - 系统常量
+//! - 通知偏好
+//! - 技术指标参数预设
+//! - 模型自动重训练
+//! - 日志级别
+//! - 市场状态条件预测的策略映射
 
 pub mod weights;
 pub mod constants;
 pub mod api_token;
+pub mod notifications;
+pub mod presets;
+pub mod retrain;
+pub mod logging;
+pub mod regime;
 
 pub use weights::*;
 pub use constants::*;
@@ -0,0 +1,131 @@
+//! 通知偏好配置
+//!
+//! 偏好以 JSON 保存在 `~/.biga/notification_preferences.json`（与模型目录同级），
+//! 文件不存在或损坏时使用默认值。
+
+use crate::error::AppError;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+/// 默认强信号通知阈值（信号强度 0–100）
+pub const DEFAULT_SIGNAL_THRESHOLD: f64 = 80.0;
+
+/// 通知偏好
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NotificationPreferences {
+    /// 是否推送预警通知
+    pub alerts_enabled: bool,
+    /// 信号强度（0–100）超过该值时推送强买入/卖出信号通知
+    pub signal_threshold: f64,
+    /// 免打扰时段 (开始小时, 结束小时)，左闭右开，允许跨午夜，如 (22, 7)
+    #[serde(default)]
+    pub quiet_hours: Option<(u8, u8)>,
+}
+
+impl Default for NotificationPreferences {
+    fn default() -> Self {
+        Self {
+            alerts_enabled: true,
+            signal_threshold: DEFAULT_SIGNAL_THRESHOLD,
+            quiet_hours: None,
+        }
+    }
+}
+
+impl NotificationPreferences {
+    /// 给定小时（0–23）是否处于免打扰时段
+    pub fn is_quiet_hour(&self, hour: u8) -> bool {
+        match self.quiet_hours {
+            Some((start, end)) if start == end => false,
+            Some((start, end)) if start < end => (start..end).contains(&hour),
+            Some((start, end)) => hour >= start || hour < end,
+            None => false,
+        }
+    }
+
+    fn validate(&self) -> Result<(), AppError> {
+        if !(0.0..=100.0).contains(&self.signal_threshold) {
+            return Err(AppError::InvalidInput(format!(
+                "信号阈值须在 0–100 之间: {}",
+                self.signal_threshold
+            )));
+        }
+        if let Some((start, end)) = self.quiet_hours {
+            if start > 23 || end > 23 {
+                return Err(AppError::InvalidInput(format!(
+                    "免打扰时段小时须在 0–23 之间: ({start}, {end})"
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+fn preferences_path() -> PathBuf {
+    let home = dirs::home_dir().unwrap_or_else(|| PathBuf::from("."));
+    home.join(".biga").join("notification_preferences.json")
+}
+
+/// 读取通知偏好；文件不存在或无法解析时返回默认值
+pub fn load_notification_preferences() -> NotificationPreferences {
+    fs::read_to_string(preferences_path())
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+/// 校验并保存通知偏好
+pub fn save_notification_preferences(
+    preferences: &NotificationPreferences,
+) -> Result<(), AppError> {
+    preferences.validate()?;
+    let path = preferences_path();
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let content = serde_json::to_string_pretty(preferences)
+        .map_err(|e| AppError::DeserializationError(e.to_string()))?;
+    fs::write(path, content)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn with_quiet_hours(quiet_hours: Option<(u8, u8)>) -> NotificationPreferences {
+        NotificationPreferences {
+            quiet_hours,
+            ..NotificationPreferences::default()
+        }
+    }
+
+    #[test]
+    fn test_quiet_hours_including_overnight() {
+        let daytime = with_quiet_hours(Some((12, 14)));
+        assert!(daytime.is_quiet_hour(12));
+        assert!(daytime.is_quiet_hour(13));
+        assert!(!daytime.is_quiet_hour(14));
+
+        let overnight = with_quiet_hours(Some((22, 7)));
+        assert!(overnight.is_quiet_hour(23));
+        assert!(overnight.is_quiet_hour(0));
+        assert!(overnight.is_quiet_hour(6));
+        assert!(!overnight.is_quiet_hour(7));
+        assert!(!overnight.is_quiet_hour(21));
+
+        assert!(!with_quiet_hours(None).is_quiet_hour(3));
+        assert!(!with_quiet_hours(Some((5, 5))).is_quiet_hour(5));
+    }
+
+    #[test]
+    fn test_validate_rejects_out_of_range() {
+        assert!(NotificationPreferences::default().validate().is_ok());
+        let mut prefs = with_quiet_hours(Some((22, 24)));
+        assert!(prefs.validate().is_err());
+        prefs.quiet_hours = None;
+        prefs.signal_threshold = 120.0;
+        assert!(prefs.validate().is_err());
+    }
+}
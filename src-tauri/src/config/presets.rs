@@ -0,0 +1,220 @@
+//! 技术指标参数预设
+//!
+//! 不同交易风格需要不同的指标周期：日内/短线偏好短周期 RSI、MACD，波段与长线
+//! 偏好更长周期。内置 `DayTrading` / `SwingTrading` / `LongTermInvesting` 三套预设，
+//! 用户可另建自定义预设；当前选中的预设与自定义预设保存在数据库中。
+
+use crate::config::constants::{
+    ATR_PERIOD, BOLLINGER_PERIOD, BOLLINGER_STD_DEV, KDJ_PERIOD, MACD_FAST_PERIOD,
+    MACD_SIGNAL_PERIOD, MACD_SLOW_PERIOD, RSI_PERIOD,
+};
+use crate::error::AppError;
+use serde::{Deserialize, Serialize};
+
+/// 日内/短线预设名
+pub const DAY_TRADING_PRESET: &str = "DayTrading";
+/// 波段预设名（即系统默认参数）
+pub const SWING_TRADING_PRESET: &str = "SwingTrading";
+/// 长线投资预设名
+pub const LONG_TERM_INVESTING_PRESET: &str = "LongTermInvesting";
+/// 未选择预设时使用的默认预设
+pub const DEFAULT_PRESET: &str = SWING_TRADING_PRESET;
+
+/// 指标计算参数，由 `calculate_all_indicators` 使用
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct IndicatorConfig {
+    pub rsi_period: usize,
+    pub macd_fast: usize,
+    pub macd_slow: usize,
+    pub macd_signal: usize,
+    pub bollinger_period: usize,
+    pub bollinger_std: f64,
+    pub kdj_period: usize,
+    pub atr_period: usize,
+}
+
+impl Default for IndicatorConfig {
+    fn default() -> Self {
+        Self {
+            rsi_period: RSI_PERIOD,
+            macd_fast: MACD_FAST_PERIOD,
+            macd_slow: MACD_SLOW_PERIOD,
+            macd_signal: MACD_SIGNAL_PERIOD,
+            bollinger_period: BOLLINGER_PERIOD,
+            bollinger_std: BOLLINGER_STD_DEV,
+            kdj_period: KDJ_PERIOD,
+            atr_period: ATR_PERIOD,
+        }
+    }
+}
+
+/// 命名的指标参数预设
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IndicatorPreset {
+    pub name: String,
+    pub rsi_period: usize,
+    pub macd_fast: usize,
+    pub macd_slow: usize,
+    pub macd_signal: usize,
+    pub bollinger_period: usize,
+    pub bollinger_std: f64,
+    pub kdj_period: usize,
+    pub atr_period: usize,
+}
+
+impl IndicatorPreset {
+    fn from_config(name: &str, config: IndicatorConfig) -> Self {
+        Self {
+            name: name.to_string(),
+            rsi_period: config.rsi_period,
+            macd_fast: config.macd_fast,
+            macd_slow: config.macd_slow,
+            macd_signal: config.macd_signal,
+            bollinger_period: config.bollinger_period,
+            bollinger_std: config.bollinger_std,
+            kdj_period: config.kdj_period,
+            atr_period: config.atr_period,
+        }
+    }
+
+    /// 日内/短线：短周期，信号灵敏
+    pub fn day_trading() -> Self {
+        Self::from_config(
+            DAY_TRADING_PRESET,
+            IndicatorConfig {
+                rsi_period: 6,
+                macd_fast: 6,
+                macd_slow: 13,
+                macd_signal: 5,
+                bollinger_period: 10,
+                bollinger_std: 2.0,
+                kdj_period: 5,
+                atr_period: 7,
+            },
+        )
+    }
+
+    /// 波段：经典参数（RSI14 / MACD 12-26-9 / BOLL 20,2 / KDJ9 / ATR14）
+    pub fn swing_trading() -> Self {
+        Self::from_config(SWING_TRADING_PRESET, IndicatorConfig::default())
+    }
+
+    /// 长线：长周期，过滤短期噪声
+    pub fn long_term_investing() -> Self {
+        Self::from_config(
+            LONG_TERM_INVESTING_PRESET,
+            IndicatorConfig {
+                rsi_period: 21,
+                macd_fast: 19,
+                macd_slow: 39,
+                macd_signal: 9,
+                bollinger_period: 50,
+                bollinger_std: 2.5,
+                kdj_period: 14,
+                atr_period: 21,
+            },
+        )
+    }
+
+    pub fn config(&self) -> IndicatorConfig {
+        IndicatorConfig {
+            rsi_period: self.rsi_period,
+            macd_fast: self.macd_fast,
+            macd_slow: self.macd_slow,
+            macd_signal: self.macd_signal,
+            bollinger_period: self.bollinger_period,
+            bollinger_std: self.bollinger_std,
+            kdj_period: self.kdj_period,
+            atr_period: self.atr_period,
+        }
+    }
+
+    /// 校验参数：名称非空、周期为正、MACD 快线短于慢线、布林带倍数为正
+    pub fn validate(&self) -> Result<(), AppError> {
+        if self.name.trim().is_empty() {
+            return Err(AppError::InvalidInput("预设名称不能为空".to_string()));
+        }
+        let periods = [
+            self.rsi_period,
+            self.macd_fast,
+            self.macd_slow,
+            self.macd_signal,
+            self.bollinger_period,
+            self.kdj_period,
+            self.atr_period,
+        ];
+        if periods.contains(&0) {
+            return Err(AppError::InvalidInput("指标周期必须大于 0".to_string()));
+        }
+        if self.macd_fast >= self.macd_slow {
+            return Err(AppError::InvalidInput(format!(
+                "MACD 快线周期须小于慢线周期: {} >= {}",
+                self.macd_fast, self.macd_slow
+            )));
+        }
+        if !(self.bollinger_std.is_finite() && self.bollinger_std > 0.0) {
+            return Err(AppError::InvalidInput(format!(
+                "布林带标准差倍数必须为正数: {}",
+                self.bollinger_std
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// 全部内置预设
+pub fn builtin_presets() -> Vec<IndicatorPreset> {
+    vec![
+        IndicatorPreset::day_trading(),
+        IndicatorPreset::swing_trading(),
+        IndicatorPreset::long_term_investing(),
+    ]
+}
+
+/// 按名称查找内置预设
+pub fn find_builtin_preset(name: &str) -> Option<IndicatorPreset> {
+    builtin_presets().into_iter().find(|preset| preset.name == name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_swing_trading_matches_default_config() {
+        assert_eq!(
+            IndicatorPreset::swing_trading().config(),
+            IndicatorConfig::default()
+        );
+        assert_eq!(
+            find_builtin_preset(DEFAULT_PRESET).map(|preset| preset.config()),
+            Some(IndicatorConfig::default())
+        );
+    }
+
+    #[test]
+    fn test_builtin_presets_are_valid() {
+        for preset in builtin_presets() {
+            assert!(preset.validate().is_ok(), "{} 应通过校验", preset.name);
+        }
+    }
+
+    #[test]
+    fn test_validate_rejects_invalid_periods() {
+        let mut preset = IndicatorPreset::swing_trading();
+        preset.name = "  ".to_string();
+        assert!(preset.validate().is_err());
+
+        let mut preset = IndicatorPreset::swing_trading();
+        preset.rsi_period = 0;
+        assert!(preset.validate().is_err());
+
+        let mut preset = IndicatorPreset::swing_trading();
+        preset.macd_fast = preset.macd_slow;
+        assert!(preset.validate().is_err());
+
+        let mut preset = IndicatorPreset::swing_trading();
+        preset.bollinger_std = 0.0;
+        assert!(preset.validate().is_err());
+    }
+}
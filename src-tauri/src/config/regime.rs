@@ -0,0 +1,117 @@
+//! 市场状态条件预测配置
+//!
+//! 每种市场状态对应一个子策略名，以 JSON 保存在 `~/.biga/regime_strategy_config.json`，
+//! 文件不存在或损坏时使用默认映射。
+
+use crate::error::AppError;
+use crate::prediction::analysis::MarketRegime;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+/// 趋势跟踪：使用 Candle 模型预测（无模型时回退规则引擎）
+pub const TREND_FOLLOWING_STRATEGY: &str = "trend_following";
+/// 均值回归：沿历史半衰期向 MA20 回归
+pub const MEAN_REVERSION_STRATEGY: &str = "mean_reversion";
+/// 多因子规则引擎（含转折点识别）
+pub const TECHNICAL_STRATEGY: &str = "technical";
+
+/// 可配置的子策略名
+pub const REGIME_STRATEGIES: [&str; 3] = [
+    TREND_FOLLOWING_STRATEGY,
+    MEAN_REVERSION_STRATEGY,
+    TECHNICAL_STRATEGY,
+];
+
+/// 市场状态 → 子策略映射
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RegimeStrategyConfig {
+    pub regime_strategy_mapping: HashMap<MarketRegime, String>,
+}
+
+impl Default for RegimeStrategyConfig {
+    fn default() -> Self {
+        let mapping = [
+            (MarketRegime::StrongUptrend, TREND_FOLLOWING_STRATEGY),
+            (MarketRegime::ModerateUptrend, TREND_FOLLOWING_STRATEGY),
+            (MarketRegime::ModerateDowntrend, TREND_FOLLOWING_STRATEGY),
+            (MarketRegime::StrongDowntrend, TREND_FOLLOWING_STRATEGY),
+            (MarketRegime::Ranging, MEAN_REVERSION_STRATEGY),
+            (MarketRegime::PotentialTop, TECHNICAL_STRATEGY),
+            (MarketRegime::PotentialBottom, TECHNICAL_STRATEGY),
+        ];
+        Self {
+            regime_strategy_mapping: mapping
+                .into_iter()
+                .map(|(regime, strategy)| (regime, strategy.to_string()))
+                .collect(),
+        }
+    }
+}
+
+impl RegimeStrategyConfig {
+    /// 该市场状态使用的子策略；未配置时退回多因子规则引擎
+    pub fn strategy_for(&self, regime: MarketRegime) -> &str {
+        self.regime_strategy_mapping
+            .get(&regime)
+            .map_or(TECHNICAL_STRATEGY, String::as_str)
+    }
+}
+
+fn config_path() -> PathBuf {
+    let home = dirs::home_dir().unwrap_or_else(|| PathBuf::from("."));
+    home.join(".biga").join("regime_strategy_config.json")
+}
+
+/// 读取市场状态策略映射；文件不存在或无法解析时返回默认值
+pub fn load_regime_strategy_config() -> RegimeStrategyConfig {
+    fs::read_to_string(config_path())
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+/// 校验并保存市场状态策略映射
+pub fn save_regime_strategy_config(config: &RegimeStrategyConfig) -> Result<(), AppError> {
+    if let Some(unknown) = config
+        .regime_strategy_mapping
+        .values()
+        .find(|strategy| !REGIME_STRATEGIES.contains(&strategy.as_str()))
+    {
+        return Err(AppError::InvalidInput(format!(
+            "未知的子策略: {unknown}（可选 {}）",
+            REGIME_STRATEGIES.join(" / ")
+        )));
+    }
+    let path = config_path();
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let content = serde_json::to_string_pretty(config)
+        .map_err(|e| AppError::DeserializationError(e.to_string()))?;
+    fs::write(path, content)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_mapping_round_trips_through_json() {
+        let config = RegimeStrategyConfig::default();
+        assert_eq!(config.strategy_for(MarketRegime::StrongUptrend), TREND_FOLLOWING_STRATEGY);
+        assert_eq!(config.strategy_for(MarketRegime::Ranging), MEAN_REVERSION_STRATEGY);
+
+        let json = serde_json::to_string(&config).unwrap();
+        assert!(json.contains(r#""Ranging":"mean_reversion""#));
+        let parsed: RegimeStrategyConfig = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, config);
+
+        let empty = RegimeStrategyConfig {
+            regime_strategy_mapping: HashMap::new(),
+        };
+        assert_eq!(empty.strategy_for(MarketRegime::Ranging), TECHNICAL_STRATEGY);
+    }
+}
@@ -0,0 +1,57 @@
+//! 自动重训练配置
+//!
+//! 配置以 JSON 保存在 `~/.biga/auto_retrain_config.json`，
+//! 文件不存在或损坏时使用默认值。
+
+use crate::error::AppError;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+/// 默认自动重训练间隔（天）：生产模型距上次训练超过该天数即重训练
+pub const DEFAULT_AUTO_RETRAIN_DAYS: u32 = 14;
+
+/// 自动重训练配置
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AutoRetrainConfig {
+    pub enabled: bool,
+    /// 生产模型距上次训练超过该天数时自动重训练
+    pub auto_retrain_days: u32,
+}
+
+impl Default for AutoRetrainConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            auto_retrain_days: DEFAULT_AUTO_RETRAIN_DAYS,
+        }
+    }
+}
+
+fn config_path() -> PathBuf {
+    let home = dirs::home_dir().unwrap_or_else(|| PathBuf::from("."));
+    home.join(".biga").join("auto_retrain_config.json")
+}
+
+/// 读取自动重训练配置；文件不存在或无法解析时返回默认值
+pub fn load_auto_retrain_config() -> AutoRetrainConfig {
+    fs::read_to_string(config_path())
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+/// 校验并保存自动重训练配置
+pub fn save_auto_retrain_config(config: &AutoRetrainConfig) -> Result<(), AppError> {
+    if config.auto_retrain_days == 0 {
+        return Err(AppError::InvalidInput("自动重训练间隔必须大于 0 天".to_string()));
+    }
+    let path = config_path();
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let content = serde_json::to_string_pretty(config)
+        .map_err(|e| AppError::DeserializationError(e.to_string()))?;
+    fs::write(path, content)?;
+    Ok(())
+}
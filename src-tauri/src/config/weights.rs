@@ -8,6 +8,9 @@
 //! - 添加背离检测相关权重
 //! - 优化信号确认阈值
 
+use crate::error::AppError;
+use serde::{Deserialize, Serialize};
+
 // =============================================================================
 // 一、预测基础权重 (优化版)
 // =============================================================================
@@ -266,3 +269,109 @@ pub const SIGNAL_DIFF_CONFIDENCE_BOOST: f64 = 0.05;
 /// 信号差异置信度加成（弱信号）
 pub const WEAK_SIGNAL_DIFF_CONFIDENCE_BOOST: f64 = 0.03;
 
+// =============================================================================
+// 十二、买卖点过滤阈值
+// =============================================================================
+
+/// 买点最低风险收益比（默认）
+pub const DEFAULT_MIN_RISK_REWARD_RATIO: f64 = 1.5;
+/// 买卖点最低置信度（默认）
+pub const DEFAULT_MIN_POINT_CONFIDENCE: f64 = 0.3;
+/// 买点最低信号强度（默认）
+pub const DEFAULT_MIN_POINT_SIGNAL_STRENGTH: f64 = 0.3;
+
+/// 买卖点过滤阈值：反映个人风险偏好，全局生效（不区分股票），保存在 `app_settings`
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct BuySellPointConfig {
+    /// 买点风险收益比下限，不低于 1
+    pub min_risk_reward_ratio: f64,
+    /// 买点与卖点的置信度下限（0-1）
+    pub min_confidence: f64,
+    /// 买点信号强度下限（0-1）
+    pub min_signal_strength: f64,
+}
+
+impl Default for BuySellPointConfig {
+    fn default() -> Self {
+        Self {
+            min_risk_reward_ratio: DEFAULT_MIN_RISK_REWARD_RATIO,
+            min_confidence: DEFAULT_MIN_POINT_CONFIDENCE,
+            min_signal_strength: DEFAULT_MIN_POINT_SIGNAL_STRENGTH,
+        }
+    }
+}
+
+impl BuySellPointConfig {
+    /// 校验阈值：风险收益比不低于 1，置信度与信号强度在 0-1 之间
+    pub fn validate(&self) -> Result<(), AppError> {
+        if !(self.min_risk_reward_ratio.is_finite() && self.min_risk_reward_ratio >= 1.0) {
+            return Err(AppError::InvalidInput(format!(
+                "最低风险收益比不能小于 1: {}",
+                self.min_risk_reward_ratio
+            )));
+        }
+        for (label, value) in [
+            ("最低置信度", self.min_confidence),
+            ("最低信号强度", self.min_signal_strength),
+        ] {
+            if !(0.0..=1.0).contains(&value) {
+                return Err(AppError::InvalidInput(format!("{label}须在 0-1 之间: {value}")));
+            }
+        }
+        Ok(())
+    }
+}
+
+// =============================================================================
+// 十三、公司事件前后的信号抑制
+// =============================================================================
+
+/// 定期报告披露日前后抑制信号的自然日数（默认）
+pub const DEFAULT_SUPPRESS_DAYS_AROUND_EARNINGS: usize = 3;
+/// 除权除息日前后抑制信号的自然日数（默认）
+pub const DEFAULT_SUPPRESS_DAYS_AROUND_DIVIDEND: usize = 2;
+/// 被抑制的预测日置信度乘数
+pub const SUPPRESSED_CONFIDENCE_FACTOR: f64 = 0.5;
+
+/// 交易信号过滤：财报披露、除权除息前后价格跳变与技术面无关，
+/// 距事件日不超过设定自然日数的预测日改为"观望"并降低置信度（0 表示不抑制该类事件）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TradeSignalFilter {
+    pub suppress_days_around_earnings: usize,
+    pub suppress_days_around_dividend: usize,
+}
+
+impl Default for TradeSignalFilter {
+    fn default() -> Self {
+        Self {
+            suppress_days_around_earnings: DEFAULT_SUPPRESS_DAYS_AROUND_EARNINGS,
+            suppress_days_around_dividend: DEFAULT_SUPPRESS_DAYS_AROUND_DIVIDEND,
+        }
+    }
+}
+
+impl TradeSignalFilter {
+    /// 各类事件中最大的抑制窗口（自然日），用于确定需要查询的事件范围
+    pub fn max_window_days(&self) -> usize {
+        self.suppress_days_around_earnings.max(self.suppress_days_around_dividend)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_buy_sell_point_config_validate() {
+        assert!(BuySellPointConfig::default().validate().is_ok());
+        let config = |min_risk_reward_ratio: f64, min_confidence: f64| BuySellPointConfig {
+            min_risk_reward_ratio,
+            min_confidence,
+            ..BuySellPointConfig::default()
+        };
+        assert!(config(1.0, 0.0).validate().is_ok());
+        assert!(config(0.9, 0.5).validate().is_err());
+        assert!(config(f64::NAN, 0.5).validate().is_err());
+        assert!(config(2.0, 1.2).validate().is_err());
+    }
+}
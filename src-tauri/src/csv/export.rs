@@ -0,0 +1,124 @@
+//! 预测结果与历史行情 CSV 导出
+
+use crate::db::models::HistoricalData;
+use crate::error::AppError;
+use crate::prediction::types::Prediction;
+use serde::Serialize;
+use std::path::Path;
+
+/// 预测结果 CSV 行
+#[derive(Debug, Serialize)]
+struct PredictionCsvRow<'a> {
+    date: &'a str,
+    predicted_price: f64,
+    change_pct: f64,
+    confidence: f64,
+    signal: &'a str,
+    rsi: Option<f64>,
+    macd_histogram: Option<f64>,
+    kdj_j: Option<f64>,
+}
+
+impl<'a> From<&'a Prediction> for PredictionCsvRow<'a> {
+    fn from(prediction: &'a Prediction) -> Self {
+        let indicators = prediction.technical_indicators.as_ref();
+        Self {
+            date: &prediction.target_date,
+            predicted_price: prediction.predicted_price,
+            change_pct: prediction.predicted_change_percent,
+            confidence: prediction.confidence,
+            signal: prediction.trading_signal.as_deref().unwrap_or(""),
+            rsi: indicators.map(|t| t.rsi),
+            macd_histogram: indicators.map(|t| t.macd_histogram),
+            kdj_j: indicators.map(|t| t.kdj_j),
+        }
+    }
+}
+
+fn csv_error(e: csv::Error) -> AppError {
+    match e.into_kind() {
+        csv::ErrorKind::Io(io) => AppError::IoError(io),
+        kind => AppError::InvalidInput(format!("CSV 写入失败: {kind:?}")),
+    }
+}
+
+/// 将预测结果写入 CSV，指标缺失的列留空
+pub fn export_predictions_csv(predictions: &[Prediction], path: &Path) -> Result<(), AppError> {
+    let mut writer = csv::Writer::from_path(path).map_err(csv_error)?;
+    for prediction in predictions {
+        writer
+            .serialize(PredictionCsvRow::from(prediction))
+            .map_err(csv_error)?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// 将历史行情按原字段写入 CSV
+pub fn export_historical_csv(data: &[HistoricalData], path: &Path) -> Result<(), AppError> {
+    let mut writer = csv::Writer::from_path(path).map_err(csv_error)?;
+    for row in data {
+        writer.serialize(row).map_err(csv_error)?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prediction::types::TechnicalIndicatorValues;
+
+    fn prediction(technical_indicators: Option<TechnicalIndicatorValues>) -> Prediction {
+        Prediction {
+            target_date: "2026-01-02".to_string(),
+            predicted_price: 10.5,
+            predicted_change_percent: 5.0,
+            confidence: 0.6,
+            trading_signal: Some("买入".to_string()),
+            signal_strength: Some(0.6),
+            technical_indicators,
+            prediction_reason: None,
+            key_factors: None,
+            interval: None,
+            stress_interval: None,
+            explanation: None,
+            prediction_low: 10.5,
+            prediction_high: 10.5,
+        }
+    }
+
+    #[test]
+    fn test_export_predictions_csv_columns() {
+        let indicators = TechnicalIndicatorValues {
+            rsi: 55.0,
+            macd_histogram: 0.12,
+            kdj_j: 80.0,
+            cci: 0.0,
+            obv_trend: 0.0,
+            macd_dif: 0.0,
+            macd_dea: 0.0,
+            kdj_k: 0.0,
+            kdj_d: 0.0,
+            macd_golden_cross: false,
+            macd_death_cross: false,
+            kdj_golden_cross: false,
+            kdj_death_cross: false,
+            kdj_overbought: false,
+            kdj_oversold: false,
+        };
+        let path = std::env::temp_dir().join(format!("biga_export_{}.csv", uuid::Uuid::new_v4()));
+        export_predictions_csv(&[prediction(Some(indicators)), prediction(None)], &path)
+            .expect("导出应成功");
+        let content = std::fs::read_to_string(&path).expect("应能读取导出文件");
+        std::fs::remove_file(&path).ok();
+
+        let lines: Vec<&str> = content.lines().collect();
+        assert_eq!(
+            lines[0],
+            "date,predicted_price,change_pct,confidence,signal,rsi,macd_histogram,kdj_j"
+        );
+        assert_eq!(lines[1], "2026-01-02,10.5,5.0,0.6,买入,55.0,0.12,80.0");
+        assert_eq!(lines[2], "2026-01-02,10.5,5.0,0.6,买入,,,");
+    }
+}
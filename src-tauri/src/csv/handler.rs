@@ -24,7 +24,7 @@ mod tests {
         let result = read_csv_to_struct("data/stock_basic.csv");
         match result {
             Ok(stocks) => {
-                println!("{:?}", stocks.first().unwrap_or(&Stock::default()));
+                assert!(!stocks.is_empty(), "stock_basic.csv 应至少包含一只股票");
             }
             Err(err) => {
                 panic!("Failed to read CSV: {err}");
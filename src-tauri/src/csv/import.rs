@@ -0,0 +1,257 @@
+//! 历史行情 CSV 导入
+//!
+//! 读取 `date,open,high,low,close,volume` 格式的 CSV，逐行校验后写入 historical_data。
+//! 可选列 `change,change_percent,amplitude` 存在时按文件取值，缺失时由前一交易日收盘价推算。
+
+use crate::db::models::HistoricalData;
+use crate::db::repository;
+use crate::error::AppError;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use std::path::Path;
+
+/// 支持的日期格式
+const DATE_FORMATS: [&str; 3] = ["%Y-%m-%d", "%Y/%m/%d", "%Y%m%d"];
+
+/// 错误明细最多保留条数，避免整份坏文件把结果撑爆
+const MAX_REPORTED_ERRORS: usize = 50;
+
+/// 导入结果汇总
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ImportResult {
+    pub rows_imported: usize,
+    pub rows_skipped: usize,
+    pub errors: Vec<String>,
+}
+
+/// CSV 原始行（列名大小写敏感，需为小写）
+#[derive(Debug, Deserialize)]
+struct CsvHistoricalRow {
+    date: String,
+    open: f64,
+    high: f64,
+    low: f64,
+    close: f64,
+    volume: f64,
+    #[serde(default)]
+    change: Option<f64>,
+    #[serde(default)]
+    change_percent: Option<f64>,
+    #[serde(default)]
+    amplitude: Option<f64>,
+}
+
+/// 按支持的格式依次尝试解析日期
+pub fn parse_csv_date(value: &str) -> Option<NaiveDate> {
+    let value = value.trim();
+    DATE_FORMATS
+        .iter()
+        .find_map(|format| NaiveDate::parse_from_str(value, format).ok())
+}
+
+/// 校验单根K线的 OHLCV 逻辑合法性
+pub fn validate_historical_data(data: &HistoricalData) -> Result<(), String> {
+    let prices = [data.open, data.high, data.low, data.close];
+    if prices.iter().any(|p| !p.is_finite() || *p <= 0.0) {
+        return Err("价格必须为正数".to_string());
+    }
+    if data.high < data.low {
+        return Err(format!("最高价 {} 低于最低价 {}", data.high, data.low));
+    }
+    if data.open > data.high || data.open < data.low {
+        return Err(format!("开盘价 {} 超出高低价区间", data.open));
+    }
+    if data.close > data.high || data.close < data.low {
+        return Err(format!("收盘价 {} 超出高低价区间", data.close));
+    }
+    if data.volume < 0 {
+        return Err("成交量不能为负".to_string());
+    }
+    Ok(())
+}
+
+/// 解析 CSV 内容为按日期升序的历史数据，非法行记入错误并跳过
+fn parse_historical_rows<R: std::io::Read>(
+    reader: R,
+    stock_code: &str,
+    result: &mut ImportResult,
+) -> Vec<HistoricalData> {
+    let mut rdr = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .from_reader(reader);
+
+    // 附带文件是否提供了涨跌额/涨跌幅/振幅
+    let mut rows: Vec<(HistoricalData, (bool, bool, bool))> = Vec::new();
+    for (index, record) in rdr.deserialize::<CsvHistoricalRow>().enumerate() {
+        // 表头占第 1 行
+        let line = index + 2;
+        let row = match record {
+            Ok(row) => row,
+            Err(e) => {
+                skip_row(result, format!("第 {line} 行格式错误: {e}"));
+                continue;
+            }
+        };
+        let Some(date) = parse_csv_date(&row.date) else {
+            skip_row(result, format!("第 {line} 行日期无法识别: {}", row.date));
+            continue;
+        };
+
+        let data = HistoricalData {
+            symbol: stock_code.to_string(),
+            date,
+            open: row.open,
+            close: row.close,
+            high: row.high,
+            low: row.low,
+            volume: row.volume.round() as i64,
+            // 文件不含成交额，按收盘价估算
+            amount: row.close * row.volume,
+            amplitude: row.amplitude.unwrap_or(0.0),
+            turnover_rate: 0.0,
+            volume_ratio: 0.0,
+            change_percent: row.change_percent.unwrap_or(0.0),
+            change: row.change.unwrap_or(0.0),
+        };
+        if let Err(e) = validate_historical_data(&data) {
+            skip_row(result, format!("第 {line} 行校验失败: {e}"));
+            continue;
+        }
+        let provided = (
+            row.change.is_some(),
+            row.change_percent.is_some(),
+            row.amplitude.is_some(),
+        );
+        rows.push((data, provided));
+    }
+
+    rows.sort_by_key(|(row, _)| row.date);
+    let before = rows.len();
+    rows.dedup_by_key(|(row, _)| row.date);
+    let duplicates = before - rows.len();
+    if duplicates > 0 {
+        result.rows_skipped += duplicates;
+        result.errors.push(format!("忽略 {duplicates} 行重复日期"));
+    }
+
+    // 文件未提供的涨跌额/涨跌幅/振幅依赖前一交易日收盘价推算，首行无前值时保持 0
+    for i in 1..rows.len() {
+        let pre_close = rows[i - 1].0.close;
+        let (row, (has_change, has_change_percent, has_amplitude)) = &mut rows[i];
+        if !*has_change {
+            row.change = row.close - pre_close;
+        }
+        if !*has_change_percent {
+            row.change_percent = (row.close - pre_close) / pre_close * 100.0;
+        }
+        if !*has_amplitude {
+            row.amplitude = (row.high - row.low) / pre_close * 100.0;
+        }
+    }
+
+    rows.into_iter().map(|(row, _)| row).collect()
+}
+
+fn skip_row(result: &mut ImportResult, error: String) {
+    result.rows_skipped += 1;
+    if result.errors.len() < MAX_REPORTED_ERRORS {
+        result.errors.push(error);
+    }
+}
+
+/// 从 CSV 文件导入某股票的历史数据。
+///
+/// 同日期已存在的记录以文件内容覆盖；导入后回填量比/换手率。
+pub async fn import_historical_csv(
+    path: &Path,
+    stock_code: &str,
+    pool: &SqlitePool,
+) -> Result<ImportResult, AppError> {
+    let stock_code = stock_code.trim();
+    if stock_code.is_empty() {
+        return Err(AppError::InvalidInput("股票代码不能为空".to_string()));
+    }
+
+    let file = std::fs::File::open(path)?;
+    let mut result = ImportResult::default();
+    let rows = parse_historical_rows(file, stock_code, &mut result);
+    if rows.is_empty() {
+        return Ok(result);
+    }
+
+    repository::upsert_historical_data(stock_code, pool, &rows).await?;
+    repository::backfill_volume_metrics(stock_code, pool).await?;
+    result.rows_imported = rows.len();
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_csv_date_formats() {
+        let expected = NaiveDate::from_ymd_opt(2024, 3, 8);
+        assert_eq!(parse_csv_date("2024-03-08"), expected);
+        assert_eq!(parse_csv_date("2024/03/08"), expected);
+        assert_eq!(parse_csv_date(" 20240308 "), expected);
+        assert_eq!(parse_csv_date("08.03.2024"), None);
+    }
+
+    #[test]
+    fn test_parse_rows_skips_invalid_and_derives_change() {
+        let csv = "date,open,high,low,close,volume\n\
+                   2024/01/03,10.5,11.2,10.4,11.0,1200\n\
+                   2024-01-02,10.0,10.6,9.9,10.0,1000\n\
+                   2024-01-04,11,10,12,11,900\n\
+                   bad-date,1,1,1,1,1\n\
+                   20240105,11.0,11.1,10.8,10.9,abc\n";
+        let mut result = ImportResult::default();
+        let rows = parse_historical_rows(csv.as_bytes(), "000001", &mut result);
+
+        assert_eq!(rows.len(), 2);
+        assert_eq!(result.rows_skipped, 3);
+        assert_eq!(result.errors.len(), 3);
+        assert_eq!(rows[0].date.to_string(), "2024-01-02");
+        assert!((rows[1].change - 1.0).abs() < 1e-9);
+        assert!((rows[1].change_percent - 10.0).abs() < 1e-9);
+        assert_eq!(rows[0].change_percent, 0.0);
+    }
+
+    #[test]
+    fn test_parse_rows_keeps_file_change_values() {
+        let csv = "date,open,high,low,close,volume,change,change_percent\n\
+                   2024-01-02,10.0,10.6,9.9,10.0,1000,0.4,4.17\n\
+                   2024-01-03,10.5,11.2,10.4,11.0,1200,,\n";
+        let mut result = ImportResult::default();
+        let rows = parse_historical_rows(csv.as_bytes(), "000001", &mut result);
+
+        assert_eq!(rows.len(), 2);
+        assert!(result.errors.is_empty());
+        // 首行沿用文件值，次行缺失时按前收盘推算
+        assert_eq!((rows[0].change, rows[0].change_percent), (0.4, 4.17));
+        assert!((rows[1].change - 1.0).abs() < 1e-9);
+        assert!((rows[1].change_percent - 10.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_validate_rejects_close_outside_range() {
+        let data = HistoricalData {
+            symbol: "000001".to_string(),
+            date: NaiveDate::from_ymd_opt(2024, 1, 2).unwrap(),
+            open: 10.0,
+            close: 12.0,
+            high: 11.0,
+            low: 9.5,
+            volume: 100,
+            amount: 0.0,
+            amplitude: 0.0,
+            turnover_rate: 0.0,
+            volume_ratio: 0.0,
+            change_percent: 0.0,
+            change: 0.0,
+        };
+        assert!(validate_historical_data(&data).is_err());
+    }
+}
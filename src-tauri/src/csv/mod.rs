@@ -1 +1,3 @@
+pub mod export;
 pub mod handler;
+pub mod import;
@@ -1,8 +1,14 @@
 //! 数据库连接管理
 
-use sqlx::{Pool, Sqlite, sqlite::SqlitePoolOptions};
-use std::path::PathBuf;
+use log::warn;
+use serde::Serialize;
+use sqlx::{
+    sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions, SqliteSynchronous},
+    Pool, Sqlite,
+};
+use std::path::{Path, PathBuf};
 use std::fs;
+use std::time::{Duration, Instant};
 
 /// 数据库连接池类型
 pub type DbPool = Pool<Sqlite>;
@@ -25,6 +31,15 @@ pub fn find_database_path() -> Option<PathBuf> {
     None
 }
 
+/// 连接参数：WAL 模式允许读写并发，WAL 下 synchronous=NORMAL 仍保证崩溃一致性。
+/// 以连接参数而非单条 PRAGMA 设置，保证连接池中每个连接都生效。
+fn connect_options(db_path: &Path) -> SqliteConnectOptions {
+    SqliteConnectOptions::new()
+        .filename(db_path)
+        .journal_mode(SqliteJournalMode::Wal)
+        .synchronous(SqliteSynchronous::Normal)
+}
+
 /// 创建数据库连接池
 pub async fn create_pool() -> Result<DbPool, sqlx::Error> {
     let current_dir = std::env::current_dir().map_err(sqlx::Error::Io)?;
@@ -59,21 +74,155 @@ pub async fn create_pool() -> Result<DbPool, sqlx::Error> {
         }
     };
     
-    let connection_string = format!("sqlite://{}", final_db_path.display());
-    
-    let pool = SqlitePoolOptions::new()
+    SqlitePoolOptions::new()
         .max_connections(5)
         .min_connections(2)
         .acquire_timeout(std::time::Duration::from_secs(30))
-        .connect(&connection_string)
-        .await?;
-    
-    // 启用 WAL 模式
-    sqlx::query("PRAGMA journal_mode=WAL;")
-        .execute(&pool)
-        .await?;
-    
-    Ok(pool)
+        .connect_with(connect_options(&final_db_path))
+        .await
+}
+
+/// 创建连接池，失败时按指数退避重试（第 n 次重试前等待 `delay_ms × 2^(n-1)`）。
+///
+/// 连接池建立后，断开的连接由 sqlx 在取用时自动重建；此处只处理启动时数据库暂不可用。
+pub async fn create_pool_with_retry(max_attempts: usize, delay_ms: u64) -> Result<DbPool, sqlx::Error> {
+    let max_attempts = max_attempts.max(1);
+    let mut attempt = 1;
+    loop {
+        match create_pool().await {
+            Ok(pool) => return Ok(pool),
+            Err(e) if attempt < max_attempts => {
+                let delay = retry_delay(delay_ms, attempt);
+                warn!(
+                    "数据库连接失败（第 {attempt}/{max_attempts} 次），{}ms 后重试: {e}",
+                    delay.as_millis()
+                );
+                tokio::time::sleep(delay).await;
+                attempt += 1;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// 第 `attempt` 次失败后的退避时长
+fn retry_delay(delay_ms: u64, attempt: usize) -> Duration {
+    let exponent = u32::try_from(attempt.saturating_sub(1)).unwrap_or(u32::MAX).min(16);
+    Duration::from_millis(delay_ms.saturating_mul(1 << exponent))
+}
+
+/// 探活查询超过该耗时视为缓慢
+pub const SLOW_HEALTH_CHECK_MS: f64 = 100.0;
+
+/// 连接池健康状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DbHealthState {
+    Healthy,
+    /// 探活成功但耗时超过 [`SLOW_HEALTH_CHECK_MS`]
+    Slow,
+    /// 连接池已关闭或探活失败
+    Unhealthy,
+}
+
+/// 连接池健康检查结果
+#[derive(Debug, Clone, Serialize)]
+pub struct PoolHealthStatus {
+    pub status: DbHealthState,
+    /// `SELECT 1` 往返耗时；探活失败时为 None
+    pub latency_ms: Option<f64>,
+    /// 当前连接数（含使用中）
+    pub connections: u32,
+    pub idle_connections: usize,
+    pub message: String,
+}
+
+/// 执行 `SELECT 1` 并计时，判断连接池是否健康
+pub async fn check_pool_health(pool: &DbPool) -> PoolHealthStatus {
+    let connections = pool.size();
+    let idle_connections = pool.num_idle();
+    if pool.is_closed() {
+        return PoolHealthStatus {
+            status: DbHealthState::Unhealthy,
+            latency_ms: None,
+            connections,
+            idle_connections,
+            message: "数据库连接池已关闭".to_string(),
+        };
+    }
+
+    let start = Instant::now();
+    let result = sqlx::query_scalar::<_, i64>("SELECT 1").fetch_one(pool).await;
+    let latency_ms = start.elapsed().as_secs_f64() * 1000.0;
+    let (status, latency_ms, message) = match result {
+        Ok(_) if latency_ms > SLOW_HEALTH_CHECK_MS => (
+            DbHealthState::Slow,
+            Some(latency_ms),
+            format!("数据库响应缓慢：探活耗时 {latency_ms:.0}ms"),
+        ),
+        Ok(_) => (DbHealthState::Healthy, Some(latency_ms), "数据库连接正常".to_string()),
+        Err(e) => (DbHealthState::Unhealthy, None, format!("数据库探活失败: {e}")),
+    };
+    PoolHealthStatus {
+        status,
+        latency_ms,
+        connections: pool.size(),
+        idle_connections: pool.num_idle(),
+        message,
+    }
+}
+
+/// 按顺序执行的迁移脚本
+pub const MIGRATION_FILES: &[&str] = &[
+    "01_create_tables.sql",
+    "02_stock_prediction_model.sql",
+    "03_volume_metrics.sql",
+    "04_stock_fundamentals.sql",
+    "05_capital_valuation.sql",
+    "06_stock_category.sql",
+    "07_watchlist.sql",
+    "08_canonical_stock_symbols.sql",
+    "09_factor_weights.sql",
+    "10_trade_journal.sql",
+    "11_indicator_presets.sql",
+    "12_model_history.sql",
+    "13_stock_alerts.sql",
+    "14_add_indices.sql",
+    "15_stock_universe.sql",
+    "16_prediction_history.sql",
+    "17_realtime_quotes.sql",
+    "18_backtest_trades.sql",
+    "19_online_learning.sql",
+    "20_stop_orders.sql",
+    "21_stock_search.sql",
+];
+
+/// 依次执行 `dir` 下的迁移脚本（缺失的文件跳过）。
+///
+/// 按语句拆分执行，幂等地忽略 "duplicate column" 错误
+/// （SQLite 不支持 ALTER TABLE ADD COLUMN IF NOT EXISTS）
+pub async fn run_migrations(pool: &DbPool, dir: &Path) -> Result<(), String> {
+    for file in MIGRATION_FILES {
+        let path = dir.join(file);
+        if !path.exists() {
+            continue;
+        }
+        let sql = fs::read_to_string(&path)
+            .map_err(|e| format!("Failed to read migration file {file}: {e}"))?;
+        for statement in sql.split(';') {
+            let statement = statement.trim();
+            if statement.is_empty() {
+                continue;
+            }
+            if let Err(e) = sqlx::query(statement).execute(pool).await {
+                if e.to_string().contains("duplicate column name") {
+                    continue;
+                }
+                return Err(format!("Failed to execute migration {file}: {e}"));
+            }
+        }
+    }
+    Ok(())
 }
 
 /// 创建临时数据库连接
@@ -81,11 +230,9 @@ pub async fn create_temp_pool() -> Result<DbPool, String> {
     let db_path = find_database_path()
         .ok_or_else(|| "找不到数据库文件".to_string())?;
     
-    let connection_string = format!("sqlite://{}", db_path.display());
-    
     SqlitePoolOptions::new()
         .max_connections(1)
-        .connect(&connection_string)
+        .connect_with(connect_options(&db_path))
         .await
         .map_err(|e| format!("数据库连接失败: {e}"))
 }
@@ -108,6 +255,31 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_retry_delay_doubles() {
+        assert_eq!(retry_delay(200, 1), Duration::from_millis(200));
+        assert_eq!(retry_delay(200, 2), Duration::from_millis(400));
+        assert_eq!(retry_delay(200, 4), Duration::from_millis(1600));
+    }
+
+    #[tokio::test]
+    async fn test_check_pool_health() {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("应创建内存 SQLite");
+        let health = check_pool_health(&pool).await;
+        assert_ne!(health.status, DbHealthState::Unhealthy);
+        assert!(health.latency_ms.is_some());
+        assert_eq!(health.connections, 1);
+
+        pool.close().await;
+        let health = check_pool_health(&pool).await;
+        assert_eq!(health.status, DbHealthState::Unhealthy);
+        assert!(health.latency_ms.is_none());
+    }
+
     async fn run_symbol_migration(pool: &SqlitePool) {
         run_migration(
             pool,
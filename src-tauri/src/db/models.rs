@@ -1,6 +1,7 @@
 //! 数据模型定义
 
-use chrono::NaiveDate;
+use crate::prediction::analysis::divergence::{DivergenceStrength, DivergenceType};
+use chrono::{NaiveDate, NaiveDateTime};
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 
@@ -142,6 +143,16 @@ impl HistoricalDataItem {
     }
 }
 
+/// 历史K线新鲜度：最新K线之后应有但缺失的交易日数
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DataFreshnessStatus {
+    pub last_record_date: NaiveDate,
+    /// 最新K线之后已收盘但库中缺失的交易日数
+    pub days_stale: i64,
+    pub is_stale: bool,
+    pub stale_reason: Option<String>,
+}
+
 // =============================================================================
 // 实时数据
 // =============================================================================
@@ -209,12 +220,355 @@ pub struct RealtimeQuoteItem {
     /// 市净率
     #[serde(rename = "sjl", default)]
     pub pb: f64,
+    /// 最新价
+    #[serde(rename = "p", default)]
+    pub price: f64,
+    /// 涨跌幅（%）
+    #[serde(rename = "pc", default)]
+    pub change_percent: f64,
+    /// 成交量
+    #[serde(rename = "v", default)]
+    pub volume: f64,
+    /// 成交额（元）
+    #[serde(rename = "cje", default)]
+    pub turnover: f64,
+    /// 行情时间（yyyy-MM-dd HH:mm:ss）
+    #[serde(rename = "t", default)]
+    pub time: String,
+}
+
+impl RealtimeQuoteItem {
+    /// 转换为行情快照；无有效价格时返回 None，行情时间缺失或无法解析时使用 `fetched_at`
+    pub fn to_realtime_quote(&self, stock_code: &str, fetched_at: NaiveDateTime) -> Option<RealtimeQuote> {
+        if !(self.price.is_finite() && self.price > 0.0) {
+            return None;
+        }
+        let timestamp = NaiveDateTime::parse_from_str(self.time.trim(), "%Y-%m-%d %H:%M:%S")
+            .unwrap_or(fetched_at);
+        Some(RealtimeQuote {
+            stock_code: stock_code.to_string(),
+            timestamp,
+            price: self.price,
+            change_pct: self.change_percent,
+            volume: self.volume,
+            turnover: self.turnover,
+            bid_price: None,
+            ask_price: None,
+        })
+    }
+}
+
+/// 实时行情快照（`realtime_quotes` 表）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, FromRow)]
+pub struct RealtimeQuote {
+    pub stock_code: String,
+    pub timestamp: NaiveDateTime,
+    pub price: f64,
+    /// 涨跌幅（%）
+    pub change_pct: f64,
+    pub volume: f64,
+    /// 成交额（元）
+    pub turnover: f64,
+    /// 买一 / 卖一价；ssjy 行情不含盘口，暂为空
+    pub bid_price: Option<f64>,
+    pub ask_price: Option<f64>,
+}
+
+// =============================================================================
+// 自适应因子权重
+// =============================================================================
+
+/// 个股单个因子的方向准确率（EMA）
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct FactorAccuracy {
+    pub symbol: String,
+    pub factor_name: String,
+    pub accuracy: f64,
+    pub sample_count: i64,
+}
+
+// =============================================================================
+// 交易日志
+// =============================================================================
+
+/// 交易日志条目
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct TradeJournalEntry {
+    pub id: i64,
+    pub stock_code: String,
+    /// 操作日（预测发出日）
+    pub action_date: NaiveDate,
+    pub predicted_signal: String,
+    /// 预测的次一交易日价格
+    pub predicted_price: f64,
+    /// 次一交易日实际收盘价，由对账任务回填
+    pub actual_price: Option<f64>,
+    /// 实际采取的操作（买入/卖出/观望等）
+    pub action_taken: String,
+    pub result: Option<String>,
+    pub notes: Option<String>,
+}
+
+/// 新建交易日志条目的请求
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NewTradeJournalEntry {
+    pub stock_code: String,
+    pub action_date: NaiveDate,
+    pub predicted_signal: String,
+    pub predicted_price: f64,
+    pub action_taken: String,
+    pub notes: Option<String>,
+}
+
+// =============================================================================
+// 个股预警
+// =============================================================================
+
+/// 预警条件
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum AlertType {
+    /// 最新收盘价不低于该价格
+    PriceAbove { price: f64 },
+    /// 最新收盘价不高于该价格
+    PriceBelow { price: f64 },
+    /// 出现指定类型、强度不低于 `min_strength` 的 RSI 背离
+    RsiDivergence {
+        divergence_type: DivergenceType,
+        min_strength: DivergenceStrength,
+    },
+    /// 出现指定类型、强度不低于 `min_strength` 的 MACD 背离
+    MacdDivergence {
+        divergence_type: DivergenceType,
+        min_strength: DivergenceStrength,
+    },
+}
+
+/// 个股预警
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StockAlert {
+    pub id: i64,
+    pub stock_code: String,
+    pub alert_type: AlertType,
+    /// 触发后置为 false，不再重复通知
+    pub active: bool,
+    pub last_triggered_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+}
+
+// =============================================================================
+// 止损止盈单
+// =============================================================================
+
+/// 止损 / 止盈
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StopOrderType {
+    StopLoss,
+    TakeProfit,
+}
+
+impl StopOrderType {
+    /// 入库值
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::StopLoss => "StopLoss",
+            Self::TakeProfit => "TakeProfit",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "StopLoss" => Some(Self::StopLoss),
+            "TakeProfit" => Some(Self::TakeProfit),
+            _ => None,
+        }
+    }
+
+    /// 现价是否触发：止损为跌至触发价及以下，止盈为涨至触发价及以上
+    pub fn is_triggered(self, trigger_price: f64, current_price: f64) -> bool {
+        match self {
+            Self::StopLoss => current_price <= trigger_price,
+            Self::TakeProfit => current_price >= trigger_price,
+        }
+    }
+}
+
+/// 止损止盈单状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StopOrderStatus {
+    Active,
+    Triggered,
+    Cancelled,
+}
+
+impl StopOrderStatus {
+    /// 入库值
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Active => "active",
+            Self::Triggered => "triggered",
+            Self::Cancelled => "cancelled",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "active" => Some(Self::Active),
+            "triggered" => Some(Self::Triggered),
+            "cancelled" => Some(Self::Cancelled),
+            _ => None,
+        }
+    }
+}
+
+/// 止损止盈单
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StopOrder {
+    pub id: i64,
+    pub stock_code: String,
+    pub order_type: StopOrderType,
+    pub trigger_price: f64,
+    pub created_at: NaiveDateTime,
+    pub triggered_at: Option<NaiveDateTime>,
+    pub status: StopOrderStatus,
+}
+
+// =============================================================================
+// 全市场股票池
+// =============================================================================
+
+/// 流通市值分档：大盘 ≥ 500 亿，中盘 200-500 亿，小盘 < 200 亿
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum MarketCapCategory {
+    #[serde(rename = "小盘")]
+    Small,
+    #[serde(rename = "中盘")]
+    Mid,
+    #[serde(rename = "大盘")]
+    Large,
+}
+
+impl MarketCapCategory {
+    /// 大盘流通市值下限（元）
+    pub const LARGE_CAP_MIN: f64 = 500.0e8;
+    /// 中盘流通市值下限（元），与截面排名的流动域门槛一致
+    pub const MID_CAP_MIN: f64 = 200.0e8;
+
+    pub const ALL: [MarketCapCategory; 3] = [Self::Small, Self::Mid, Self::Large];
+
+    /// 按流通市值（元）分档；无有效市值时返回 None
+    pub fn from_circulating_cap(cap: f64) -> Option<Self> {
+        if !(cap.is_finite() && cap > 0.0) {
+            None
+        } else if cap >= Self::LARGE_CAP_MIN {
+            Some(Self::Large)
+        } else if cap >= Self::MID_CAP_MIN {
+            Some(Self::Mid)
+        } else {
+            Some(Self::Small)
+        }
+    }
+
+    /// 入库标签
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Small => "小盘",
+            Self::Mid => "中盘",
+            Self::Large => "大盘",
+        }
+    }
+}
+
+/// 股票池条目
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, FromRow)]
+pub struct UniverseEntry {
+    /// 规范 6 位代码
+    pub code: String,
+    pub name: String,
+    /// SH / SZ / BSE
+    pub exchange: String,
+    pub industry: String,
+    /// 大盘 / 中盘 / 小盘，无股本数据时为空串
+    pub market_cap_category: String,
+    pub is_st: bool,
+    pub is_active: bool,
+}
+
+/// 股票联想搜索结果
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, FromRow)]
+pub struct StockSearchResult {
+    pub code: String,
+    pub name: String,
+    /// SH / SZ / BSE
+    pub exchange: String,
+    pub is_st: bool,
 }
 
 // =============================================================================
 // 预测模型相关
 // =============================================================================
 
+/// 预测准确率跟踪记录；实际值字段在目标日过后由后台任务回填
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct PredictionHistoryRecord {
+    pub id: i64,
+    pub stock_code: String,
+    /// 模型 id；未指定模型时为预测方式（default / candle / simple / ensemble）
+    pub model_id: String,
+    /// 预测所依据的最新K线日期
+    pub prediction_date: NaiveDate,
+    pub target_date: NaiveDate,
+    /// 预测依据日收盘价，用于判定实际涨跌方向
+    pub base_price: f64,
+    pub predicted_price: f64,
+    /// 上涨 / 下跌 / 横盘
+    pub predicted_direction: String,
+    pub actual_price: Option<f64>,
+    pub actual_direction: Option<String>,
+    pub was_direction_correct: Option<bool>,
+    /// (预测价 - 实际价) / 实际价 × 100
+    pub price_error_pct: Option<f64>,
+}
+
+/// 新增预测记录
+#[derive(Debug, Clone)]
+pub struct NewPredictionRecord {
+    pub stock_code: String,
+    pub model_id: String,
+    pub prediction_date: NaiveDate,
+    pub target_date: NaiveDate,
+    pub base_price: f64,
+    pub predicted_price: f64,
+    pub predicted_direction: String,
+}
+
+/// 模型在线学习配置
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct OnlineLearningConfig {
+    pub model_id: String,
+    pub learning_rate: f64,
+    pub enabled: bool,
+    /// 已用于更新的 prediction_history 最大 id
+    pub last_record_id: i64,
+}
+
+/// 模型（重）训练记录，用于跟踪准确率随时间的变化
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ModelHistoryEntry {
+    pub id: i64,
+    pub model_id: String,
+    pub stock_code: String,
+    /// 触发方式：manual 手动 / auto 自动调度 / online 在线学习
+    pub source: String,
+    /// 重训练前的方向准确率
+    pub old_accuracy: Option<f64>,
+    pub new_accuracy: f64,
+    pub mae: Option<f64>,
+    pub rmse: Option<f64>,
+    pub trained_at: NaiveDateTime,
+}
+
 // =============================================================================
 // 基本面财务指标（非技术数据，来自 zhitu hs/gs/cwzb）
 // =============================================================================
@@ -12,6 +12,33 @@ use crate::utils::volume_metrics::{
 use sqlx::{QueryBuilder, sqlite::SqlitePool};
 use std::collections::BTreeMap;
 
+mod alerts;
+mod backtest;
+mod historical;
+mod journal;
+mod maintenance;
+mod model_history;
+mod online_learning;
+mod prediction_history;
+mod realtime;
+mod presets;
+mod settings;
+mod stop_orders;
+mod universe;
+pub use alerts::*;
+pub use backtest::*;
+pub use historical::*;
+pub use journal::*;
+pub use maintenance::*;
+pub use model_history::*;
+pub use online_learning::*;
+pub use prediction_history::*;
+pub use realtime::*;
+pub use presets::*;
+pub use settings::*;
+pub use stop_orders::*;
+pub use universe::*;
+
 const VALID_HISTORICAL_BAR_FILTER: &str = "open > 0 AND close > 0 AND high > 0 AND low > 0 AND high >= low AND high >= open AND high >= close AND low <= open AND low <= close";
 
 fn historical_symbol_variants(symbol: &str) -> Vec<String> {
@@ -184,6 +211,21 @@ pub async fn get_stock_info(
     }
 }
 
+/// 获取股票所属行业（股票基础信息未导入时返回 None）
+pub async fn get_stock_industry(
+    symbol: &str,
+    pool: &SqlitePool,
+) -> Result<Option<String>, AppError> {
+    let industry: Option<(String,)> = sqlx::query_as(
+        "SELECT COALESCE(industry, '') FROM stock WHERE symbol = ? LIMIT 1",
+    )
+    .bind(canonical_stock_symbol(symbol))
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(industry.map(|(industry,)| industry).filter(|industry| !industry.is_empty()))
+}
+
 // =============================================================================
 // 历史数据仓库
 // =============================================================================
@@ -270,6 +312,64 @@ pub async fn batch_insert_historical_data(
     Ok(batch_size)
 }
 
+/// 批量写入历史数据（按 (symbol, date) 覆盖更新）
+///
+/// 与 `batch_insert_historical_data` 不同：不要求 stock_info 中已有该股票，也不同步
+/// realtime_data，用于外部文件导入等以文件内容为准的场景。
+pub async fn upsert_historical_data(
+    symbol: &str,
+    pool: &SqlitePool,
+    data_list: &[HistoricalData],
+) -> Result<u64, AppError> {
+    if data_list.is_empty() {
+        return Ok(0);
+    }
+
+    let symbol = canonical_stock_symbol(symbol);
+    let mut tx = pool.begin().await?;
+    let mut affected_rows = 0;
+
+    for chunk in data_list.chunks(BATCH_SIZE) {
+        let mut query_builder = QueryBuilder::new(
+            "INSERT INTO historical_data (symbol, date, open, close, high, low, volume,
+            amount, amplitude, turnover_rate, volume_ratio, change, change_percent) ",
+        );
+        query_builder.push_values(chunk, |mut b, data| {
+            b.push_bind(&symbol)
+                .push_bind(data.date)
+                .push_bind(data.open)
+                .push_bind(data.close)
+                .push_bind(data.high)
+                .push_bind(data.low)
+                .push_bind(data.volume)
+                .push_bind(data.amount)
+                .push_bind(data.amplitude)
+                .push_bind(data.turnover_rate)
+                .push_bind(data.volume_ratio)
+                .push_bind(data.change)
+                .push_bind(data.change_percent);
+        });
+        query_builder.push(
+            r#" ON CONFLICT(symbol, date) DO UPDATE SET
+                open = EXCLUDED.open,
+                close = EXCLUDED.close,
+                high = EXCLUDED.high,
+                low = EXCLUDED.low,
+                volume = EXCLUDED.volume,
+                amount = EXCLUDED.amount,
+                amplitude = EXCLUDED.amplitude,
+                change = EXCLUDED.change,
+                change_percent = EXCLUDED.change_percent
+            "#,
+        );
+        let result = query_builder.build().execute(&mut *tx).await?;
+        affected_rows += result.rows_affected();
+    }
+
+    tx.commit().await?;
+    Ok(affected_rows)
+}
+
 /// 查询历史数据
 pub async fn get_historical_data(
     symbol: &str,
@@ -382,6 +482,38 @@ pub async fn get_recent_historical_data_for_symbols(
     Ok(grouped.into_iter().collect())
 }
 
+/// 按精确 symbol 获取最近 `days` 根有效K线的 (日期, 收盘价)，时间正序。
+///
+/// 不做代码变体解析：指数代码（如 sh000001）与个股纯 6 位代码可能重叠，必须精确匹配。
+pub async fn get_recent_closes_exact(
+    symbol: &str,
+    days: usize,
+    pool: &SqlitePool,
+) -> Result<Vec<(chrono::NaiveDate, f64)>, AppError> {
+    let mut rows: Vec<(chrono::NaiveDate, f64)> = sqlx::query_as(&format!(
+        "SELECT date, close FROM historical_data WHERE symbol = ? AND {VALID_HISTORICAL_BAR_FILTER} \
+         ORDER BY date DESC LIMIT ?"
+    ))
+    .bind(symbol)
+    .bind(days as i64)
+    .fetch_all(pool)
+    .await?;
+    rows.reverse();
+    Ok(rows)
+}
+
+/// 本地历史数据中最新的有效交易日；无数据时返回 None
+pub async fn get_latest_historical_date(
+    pool: &SqlitePool,
+) -> Result<Option<chrono::NaiveDate>, AppError> {
+    let (date,): (Option<chrono::NaiveDate>,) = sqlx::query_as(&format!(
+        "SELECT MAX(date) FROM historical_data WHERE {VALID_HISTORICAL_BAR_FILTER}"
+    ))
+    .fetch_one(pool)
+    .await?;
+    Ok(date)
+}
+
 /// 获取历史数据足够长的股票代码列表（用于截面排名）
 pub async fn get_symbols_with_min_bars(
     min_bars: i64,
@@ -550,6 +682,19 @@ pub async fn get_realtime_list(
     Ok((data, total))
 }
 
+/// 最新交易日的全部实时数据
+pub async fn get_latest_realtime_snapshot(pool: &SqlitePool) -> Result<Vec<RealtimeData>, AppError> {
+    let data = sqlx::query_as::<_, RealtimeData>(
+        "SELECT symbol, name, date, close, volume, amount, amplitude,
+                turnover_rate, volume_ratio, change_percent, change
+         FROM realtime_data
+         WHERE date = (SELECT MAX(date) FROM realtime_data)",
+    )
+    .fetch_all(pool)
+    .await?;
+    Ok(data)
+}
+
 // =============================================================================
 // 股本与量比/换手率
 // =============================================================================
@@ -720,6 +865,52 @@ pub async fn backfill_volume_metrics(
     Ok(updated)
 }
 
+// =============================================================================
+// 自适应因子权重
+// =============================================================================
+
+/// 读取某股票全部因子的准确率记录
+pub async fn get_factor_accuracies(
+    symbol: &str,
+    pool: &SqlitePool,
+) -> Result<Vec<FactorAccuracy>, AppError> {
+    let rows = sqlx::query_as::<_, FactorAccuracy>(
+        "SELECT symbol, factor_name, accuracy, sample_count FROM factor_weights WHERE symbol = ?",
+    )
+    .bind(canonical_stock_symbol(symbol))
+    .fetch_all(pool)
+    .await?;
+    Ok(rows)
+}
+
+/// 批量写入/更新某股票的因子准确率（upsert）
+pub async fn upsert_factor_accuracies(
+    pool: &SqlitePool,
+    accuracies: &[FactorAccuracy],
+) -> Result<(), AppError> {
+    if accuracies.is_empty() {
+        return Ok(());
+    }
+    let mut builder = QueryBuilder::new(
+        "INSERT INTO factor_weights (symbol, factor_name, accuracy, sample_count, updated_at) ",
+    );
+    builder.push_values(accuracies, |mut row, item| {
+        row.push_bind(canonical_stock_symbol(&item.symbol))
+            .push_bind(&item.factor_name)
+            .push_bind(item.accuracy)
+            .push_bind(item.sample_count)
+            .push("CURRENT_TIMESTAMP");
+    });
+    builder.push(
+        " ON CONFLICT(symbol, factor_name) DO UPDATE SET \
+         accuracy = EXCLUDED.accuracy, \
+         sample_count = EXCLUDED.sample_count, \
+         updated_at = CURRENT_TIMESTAMP",
+    );
+    builder.build().execute(pool).await?;
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
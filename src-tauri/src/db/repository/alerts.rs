@@ -0,0 +1,117 @@
+//! 个股预警仓库
+
+use crate::db::models::{AlertType, StockAlert};
+use crate::error::AppError;
+use crate::utils::canonical_stock_symbol;
+use chrono::NaiveDateTime;
+use log::warn;
+use sqlx::sqlite::SqlitePool;
+
+const ALERT_COLUMNS: &str = "id, stock_code, alert_type, active, last_triggered_at, created_at";
+
+type AlertRow = (i64, String, String, bool, Option<NaiveDateTime>, NaiveDateTime);
+
+fn alert_from_row(row: AlertRow) -> Result<StockAlert, AppError> {
+    let (id, stock_code, alert_type, active, last_triggered_at, created_at) = row;
+    let alert_type = serde_json::from_str::<AlertType>(&alert_type)
+        .map_err(|e| AppError::DeserializationError(format!("预警 {id} 条件解析失败: {e}")))?;
+    Ok(StockAlert {
+        id,
+        stock_code,
+        alert_type,
+        active,
+        last_triggered_at,
+        created_at,
+    })
+}
+
+/// 新建预警，返回新预警 id
+pub async fn insert_alert(
+    pool: &SqlitePool,
+    stock_code: &str,
+    alert_type: &AlertType,
+) -> Result<i64, AppError> {
+    let alert_type = serde_json::to_string(alert_type)
+        .map_err(|e| AppError::DeserializationError(e.to_string()))?;
+    let result = sqlx::query("INSERT INTO stock_alerts (stock_code, alert_type) VALUES (?, ?)")
+        .bind(canonical_stock_symbol(stock_code))
+        .bind(alert_type)
+        .execute(pool)
+        .await?;
+    Ok(result.last_insert_rowid())
+}
+
+/// 按 id 查询预警
+pub async fn get_alert(pool: &SqlitePool, id: i64) -> Result<Option<StockAlert>, AppError> {
+    let row: Option<AlertRow> =
+        sqlx::query_as(&format!("SELECT {ALERT_COLUMNS} FROM stock_alerts WHERE id = ?"))
+            .bind(id)
+            .fetch_optional(pool)
+            .await?;
+    row.map(alert_from_row).transpose()
+}
+
+/// 查询预警（按创建时间倒序），`stock_code` 为 None 时返回全部
+pub async fn get_alerts(
+    stock_code: Option<&str>,
+    pool: &SqlitePool,
+) -> Result<Vec<StockAlert>, AppError> {
+    let rows: Vec<AlertRow> = match stock_code {
+        Some(code) => {
+            sqlx::query_as(&format!(
+                "SELECT {ALERT_COLUMNS} FROM stock_alerts WHERE stock_code = ? \
+                 ORDER BY created_at DESC, id DESC"
+            ))
+            .bind(canonical_stock_symbol(code))
+            .fetch_all(pool)
+            .await?
+        }
+        None => {
+            sqlx::query_as(&format!(
+                "SELECT {ALERT_COLUMNS} FROM stock_alerts ORDER BY created_at DESC, id DESC"
+            ))
+            .fetch_all(pool)
+            .await?
+        }
+    };
+    rows.into_iter().map(alert_from_row).collect()
+}
+
+/// 尚未触发的预警；条件无法解析的行跳过
+pub async fn get_active_alerts(pool: &SqlitePool) -> Result<Vec<StockAlert>, AppError> {
+    let rows: Vec<AlertRow> = sqlx::query_as(&format!(
+        "SELECT {ALERT_COLUMNS} FROM stock_alerts WHERE active = 1 ORDER BY stock_code, id"
+    ))
+    .fetch_all(pool)
+    .await?;
+    Ok(rows
+        .into_iter()
+        .filter_map(|row| match alert_from_row(row) {
+            Ok(alert) => Some(alert),
+            Err(e) => {
+                warn!("跳过无效预警: {e}");
+                None
+            }
+        })
+        .collect())
+}
+
+/// 标记预警已触发：停用并记录触发时间
+pub async fn mark_alert_triggered(pool: &SqlitePool, id: i64) -> Result<(), AppError> {
+    sqlx::query(
+        "UPDATE stock_alerts SET active = 0, last_triggered_at = CURRENT_TIMESTAMP WHERE id = ?",
+    )
+    .bind(id)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// 删除预警
+pub async fn delete_alert(pool: &SqlitePool, id: i64) -> Result<(), AppError> {
+    sqlx::query("DELETE FROM stock_alerts WHERE id = ?")
+        .bind(id)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
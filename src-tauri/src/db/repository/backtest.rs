@@ -0,0 +1,55 @@
+//! 回测交易记录仓库
+
+use crate::error::AppError;
+use crate::prediction::backtest::attribution::BacktestTradeRecord;
+use crate::utils::canonical_stock_symbol;
+use sqlx::sqlite::SqlitePool;
+
+/// 写入一次回测的全部交易记录
+pub async fn insert_backtest_trades(
+    pool: &SqlitePool,
+    backtest_id: &str,
+    stock_code: &str,
+    trades: &[BacktestTradeRecord],
+) -> Result<(), AppError> {
+    let stock_code = canonical_stock_symbol(stock_code);
+    let mut tx = pool.begin().await?;
+    for trade in trades {
+        sqlx::query(
+            r#"
+            INSERT INTO backtest_trades
+                (backtest_id, stock_code, entry_date, exit_date, pnl_pct,
+                 model_signal, macd_signal, volume_signal, trend_state)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(backtest_id)
+        .bind(&stock_code)
+        .bind(trade.entry_date)
+        .bind(trade.exit_date)
+        .bind(trade.pnl_pct)
+        .bind(&trade.model_signal)
+        .bind(&trade.macd_signal)
+        .bind(&trade.volume_signal)
+        .bind(&trade.trend_state)
+        .execute(&mut *tx)
+        .await?;
+    }
+    tx.commit().await?;
+    Ok(())
+}
+
+/// 某次回测的交易记录（按入场日正序）
+pub async fn get_backtest_trades(
+    pool: &SqlitePool,
+    backtest_id: &str,
+) -> Result<Vec<BacktestTradeRecord>, AppError> {
+    let trades = sqlx::query_as::<_, BacktestTradeRecord>(
+        "SELECT entry_date, exit_date, pnl_pct, model_signal, macd_signal, volume_signal, trend_state \
+         FROM backtest_trades WHERE backtest_id = ? ORDER BY entry_date, id",
+    )
+    .bind(backtest_id)
+    .fetch_all(pool)
+    .await?;
+    Ok(trades)
+}
@@ -0,0 +1,291 @@
+//! 历史数据新鲜度与 OHLCV 完整性检查
+
+use super::{resolve_historical_symbol, VALID_HISTORICAL_BAR_FILTER};
+use crate::db::models::DataFreshnessStatus;
+use crate::error::AppError;
+use crate::utils::date::count_trading_days;
+use chrono::{Duration, Local, NaiveDate, NaiveDateTime, Timelike};
+use log::info;
+use serde::{Deserialize, Serialize};
+use sqlx::sqlite::SqlitePool;
+
+/// 当日K线视为应已入库的时刻（15:00 收盘后数据源约一小时完成更新）
+const DAILY_BAR_READY_HOUR: u32 = 16;
+
+/// 按当前时刻计算新鲜度：最新K线之后、截至最近一个已可获取日K的交易日，缺失几个交易日。
+/// 周末与节假日不计入，长假后首个交易日收盘前不会误报
+pub fn data_freshness_at(last_record_date: NaiveDate, now: NaiveDateTime) -> DataFreshnessStatus {
+    let reference = if now.hour() >= DAILY_BAR_READY_HOUR {
+        now.date()
+    } else {
+        now.date() - Duration::days(1)
+    };
+    let days_stale = count_trading_days(
+        last_record_date + Duration::days(1),
+        reference + Duration::days(1),
+    ) as i64;
+    let is_stale = days_stale > 0;
+    let stale_reason = is_stale.then(|| {
+        format!(
+            "最新K线为 {}，其后 {} 个交易日的数据尚未刷新，预测基于过期数据",
+            last_record_date.format("%Y-%m-%d"),
+            days_stale
+        )
+    });
+
+    DataFreshnessStatus {
+        last_record_date,
+        days_stale,
+        is_stale,
+        stale_reason,
+    }
+}
+
+/// 检查某股票历史K线是否过期；库中无有效K线时返回 Err
+pub async fn check_data_freshness(
+    stock_code: &str,
+    pool: &SqlitePool,
+) -> Result<DataFreshnessStatus, AppError> {
+    let actual_symbol = resolve_historical_symbol(stock_code, pool)
+        .await?
+        .unwrap_or_else(|| stock_code.to_string());
+    let query = format!(
+        "SELECT MAX(date) FROM historical_data WHERE symbol = ? AND {VALID_HISTORICAL_BAR_FILTER}"
+    );
+    let (last_record_date,): (Option<NaiveDate>,) = sqlx::query_as(&query)
+        .bind(&actual_symbol)
+        .fetch_one(pool)
+        .await?;
+    let last_record_date = last_record_date
+        .ok_or_else(|| AppError::InvalidInput(format!("{stock_code} 无历史数据")))?;
+
+    Ok(data_freshness_at(last_record_date, Local::now().naive_local()))
+}
+
+/// OHLCV 完整性检查 / 修复结果
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct IntegrityReport {
+    pub total_records: usize,
+    /// 发现的问题数（同一条K线的多个问题分别计数）
+    pub issues_found: usize,
+    /// 已修复（仅检查时为可修复）的问题数
+    pub issues_repaired: usize,
+    /// 无法自动修复的问题描述
+    pub unrepairable: Vec<String>,
+}
+
+/// 参与完整性检查的K线字段
+#[derive(Debug, Clone, PartialEq, sqlx::FromRow)]
+struct OhlcvRow {
+    date: NaiveDate,
+    open: f64,
+    close: f64,
+    high: f64,
+    low: f64,
+    volume: i64,
+}
+
+/// 按日期升序逐条检查并修复：high < low 互换；close 超出 [low, high] 截断到边界；
+/// open 为 0 取前一日收盘价；成交量为负置 0。返回报告与被修改的K线
+fn repair_ohlcv_rows(rows: &[OhlcvRow]) -> (IntegrityReport, Vec<OhlcvRow>) {
+    let mut report = IntegrityReport {
+        total_records: rows.len(),
+        ..IntegrityReport::default()
+    };
+    let mut changed = Vec::new();
+    let mut prev_close: Option<f64> = None;
+
+    for row in rows {
+        let mut fixed = row.clone();
+        let date = row.date.format("%Y-%m-%d");
+        if fixed.high < fixed.low {
+            std::mem::swap(&mut fixed.high, &mut fixed.low);
+            report.issues_found += 1;
+            report.issues_repaired += 1;
+        }
+        if fixed.close > fixed.high {
+            fixed.close = fixed.high;
+            report.issues_found += 1;
+            report.issues_repaired += 1;
+        } else if fixed.close < fixed.low {
+            fixed.close = fixed.low;
+            report.issues_found += 1;
+            report.issues_repaired += 1;
+        }
+        if fixed.open == 0.0 {
+            report.issues_found += 1;
+            match prev_close.filter(|c| *c > 0.0) {
+                Some(close) => {
+                    fixed.open = close;
+                    report.issues_repaired += 1;
+                }
+                None => report
+                    .unrepairable
+                    .push(format!("{date}: 开盘价为 0 且无前一日收盘价")),
+            }
+        }
+        if fixed.volume < 0 {
+            fixed.volume = 0;
+            report.issues_found += 1;
+            report.issues_repaired += 1;
+        }
+        if fixed.low <= 0.0 {
+            // 价格缺失无法由相邻数据可靠推断，留给重新拉取
+            report.issues_found += 1;
+            report
+                .unrepairable
+                .push(format!("{date}: 最低价 {} 非正", fixed.low));
+        }
+
+        prev_close = Some(fixed.close);
+        if fixed != *row {
+            changed.push(fixed);
+        }
+    }
+    (report, changed)
+}
+
+async fn load_ohlcv_rows(
+    stock_code: &str,
+    pool: &SqlitePool,
+) -> Result<(String, Vec<OhlcvRow>), AppError> {
+    let actual_symbol = resolve_historical_symbol(stock_code, pool)
+        .await?
+        .unwrap_or_else(|| stock_code.to_string());
+    let rows = sqlx::query_as::<_, OhlcvRow>(
+        "SELECT date, open, close, high, low, volume FROM historical_data WHERE symbol = ? ORDER BY date ASC",
+    )
+    .bind(&actual_symbol)
+    .fetch_all(pool)
+    .await?;
+    Ok((actual_symbol, rows))
+}
+
+/// 只检查不写库；`issues_repaired` 为修复时可自动处理的问题数
+pub async fn check_ohlcv_integrity(
+    stock_code: &str,
+    pool: &SqlitePool,
+) -> Result<IntegrityReport, AppError> {
+    let (_, rows) = load_ohlcv_rows(stock_code, pool).await?;
+    Ok(repair_ohlcv_rows(&rows).0)
+}
+
+/// 检查并修复某股票全部历史K线的 OHLCV 逻辑错误
+pub async fn repair_ohlcv_integrity(
+    stock_code: &str,
+    pool: &SqlitePool,
+) -> Result<IntegrityReport, AppError> {
+    let (actual_symbol, rows) = load_ohlcv_rows(stock_code, pool).await?;
+    let (report, changed) = repair_ohlcv_rows(&rows);
+    if changed.is_empty() {
+        return Ok(report);
+    }
+
+    let mut tx = pool.begin().await?;
+    for row in &changed {
+        sqlx::query(
+            "UPDATE historical_data SET open = ?, close = ?, high = ?, low = ?, volume = ? WHERE symbol = ? AND date = ?",
+        )
+        .bind(row.open)
+        .bind(row.close)
+        .bind(row.high)
+        .bind(row.low)
+        .bind(row.volume)
+        .bind(&actual_symbol)
+        .bind(row.date)
+        .execute(&mut *tx)
+        .await?;
+    }
+    tx.commit().await?;
+    info!(
+        "{stock_code} K线完整性修复：{} 条记录，修复 {} 处，无法修复 {} 处",
+        changed.len(),
+        report.issues_repaired,
+        report.unrepairable.len()
+    );
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(date: &str, hour: u32) -> NaiveDateTime {
+        NaiveDate::parse_from_str(date, "%Y-%m-%d")
+            .unwrap()
+            .and_hms_opt(hour, 0, 0)
+            .unwrap()
+    }
+
+    fn date(date: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(date, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn test_weekend_is_not_stale() {
+        // 2025-06-13 周五收盘后数据已入库，周一收盘前不算过期
+        let status = data_freshness_at(date("2025-06-13"), at("2025-06-16", 10));
+        assert_eq!(status.days_stale, 0);
+        assert!(!status.is_stale);
+        assert!(status.stale_reason.is_none());
+    }
+
+    #[test]
+    fn test_missing_trading_days_are_stale() {
+        // 周一收盘后仍只有上周四的数据：缺周五、周一两个交易日
+        let status = data_freshness_at(date("2025-06-12"), at("2025-06-16", 17));
+        assert_eq!(status.days_stale, 2);
+        assert!(status.is_stale);
+        assert!(status.stale_reason.is_some());
+    }
+
+    #[test]
+    fn test_national_day_holiday_is_not_stale() {
+        // 国庆长假（10/1-10/7）期间不产生新交易日
+        let status = data_freshness_at(date("2025-09-30"), at("2025-10-07", 18));
+        assert!(!status.is_stale);
+    }
+
+    fn row(day: &str, open: f64, close: f64, high: f64, low: f64, volume: i64) -> OhlcvRow {
+        OhlcvRow {
+            date: date(day),
+            open,
+            close,
+            high,
+            low,
+            volume,
+        }
+    }
+
+    #[test]
+    fn test_repair_ohlcv_rows() {
+        let rows = vec![
+            // 首条开盘价为 0：无前一日收盘价，无法修复
+            row("2025-06-09", 0.0, 10.0, 10.5, 9.5, 1000),
+            // 高低价颠倒且收盘价高于（互换后的）最高价
+            row("2025-06-10", 10.0, 11.0, 9.8, 10.6, 1200),
+            // 开盘价为 0 → 前一日（修复后）收盘价；成交量为负
+            row("2025-06-11", 0.0, 10.4, 10.8, 10.2, -5),
+            row("2025-06-12", 10.4, 10.5, 10.6, 10.3, 800),
+        ];
+        let (report, changed) = repair_ohlcv_rows(&rows);
+
+        assert_eq!(report.total_records, 4);
+        assert_eq!(report.issues_found, 5);
+        assert_eq!(report.issues_repaired, 4);
+        assert_eq!(report.unrepairable.len(), 1);
+        assert!(report.unrepairable[0].starts_with("2025-06-09"));
+
+        assert_eq!(changed.len(), 2);
+        assert_eq!(changed[0], row("2025-06-10", 10.0, 10.6, 10.6, 9.8, 1200));
+        assert_eq!(changed[1], row("2025-06-11", 10.6, 10.4, 10.8, 10.2, 0));
+    }
+
+    #[test]
+    fn test_clean_rows_are_untouched() {
+        let rows = vec![row("2025-06-12", 10.4, 10.5, 10.6, 10.3, 800)];
+        let (report, changed) = repair_ohlcv_rows(&rows);
+        assert_eq!(report.issues_found, 0);
+        assert!(changed.is_empty());
+    }
+}
@@ -0,0 +1,114 @@
+//! 交易日志仓库
+
+use crate::db::models::{NewTradeJournalEntry, TradeJournalEntry};
+use crate::error::AppError;
+use crate::utils::canonical_stock_symbol;
+use sqlx::sqlite::SqlitePool;
+
+const JOURNAL_COLUMNS: &str = "id, stock_code, action_date, predicted_signal, predicted_price, \
+     actual_price, action_taken, result, notes";
+
+/// 写入一条交易日志，返回新条目 id
+pub async fn insert_journal_entry(
+    pool: &SqlitePool,
+    entry: &NewTradeJournalEntry,
+) -> Result<i64, AppError> {
+    let result = sqlx::query(
+        r#"
+        INSERT INTO trade_journal
+            (stock_code, action_date, predicted_signal, predicted_price, action_taken, notes)
+        VALUES (?, ?, ?, ?, ?, ?)
+        "#,
+    )
+    .bind(canonical_stock_symbol(&entry.stock_code))
+    .bind(entry.action_date)
+    .bind(&entry.predicted_signal)
+    .bind(entry.predicted_price)
+    .bind(&entry.action_taken)
+    .bind(&entry.notes)
+    .execute(pool)
+    .await?;
+    Ok(result.last_insert_rowid())
+}
+
+/// 查询交易日志（按操作日倒序），`stock_code` 为 None 时返回全部
+pub async fn get_journal_entries(
+    stock_code: Option<&str>,
+    pool: &SqlitePool,
+) -> Result<Vec<TradeJournalEntry>, AppError> {
+    let entries = match stock_code {
+        Some(code) => {
+            sqlx::query_as::<_, TradeJournalEntry>(&format!(
+                "SELECT {JOURNAL_COLUMNS} FROM trade_journal WHERE stock_code = ? \
+                 ORDER BY action_date DESC, id DESC"
+            ))
+            .bind(canonical_stock_symbol(code))
+            .fetch_all(pool)
+            .await?
+        }
+        None => {
+            sqlx::query_as::<_, TradeJournalEntry>(&format!(
+                "SELECT {JOURNAL_COLUMNS} FROM trade_journal ORDER BY action_date DESC, id DESC"
+            ))
+            .fetch_all(pool)
+            .await?
+        }
+    };
+    Ok(entries)
+}
+
+/// 尚未回填实际价格的条目
+pub async fn get_unreconciled_journal_entries(
+    pool: &SqlitePool,
+) -> Result<Vec<TradeJournalEntry>, AppError> {
+    let entries = sqlx::query_as::<_, TradeJournalEntry>(&format!(
+        "SELECT {JOURNAL_COLUMNS} FROM trade_journal WHERE actual_price IS NULL ORDER BY action_date"
+    ))
+    .fetch_all(pool)
+    .await?;
+    Ok(entries)
+}
+
+/// 更新条目的结果与备注；条目不存在时返回 InvalidInput
+pub async fn update_journal_result(
+    pool: &SqlitePool,
+    id: i64,
+    result: &str,
+    notes: Option<&str>,
+) -> Result<(), AppError> {
+    let updated = sqlx::query(
+        "UPDATE trade_journal SET result = ?, notes = COALESCE(?, notes) WHERE id = ?",
+    )
+    .bind(result)
+    .bind(notes)
+    .bind(id)
+    .execute(pool)
+    .await?;
+    if updated.rows_affected() == 0 {
+        return Err(AppError::InvalidInput(format!("交易日志 {id} 不存在")));
+    }
+    Ok(())
+}
+
+/// 回填条目的实际价格
+pub async fn set_journal_actual_price(
+    pool: &SqlitePool,
+    id: i64,
+    actual_price: f64,
+) -> Result<(), AppError> {
+    sqlx::query("UPDATE trade_journal SET actual_price = ? WHERE id = ?")
+        .bind(actual_price)
+        .bind(id)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+/// 删除一条交易日志
+pub async fn delete_journal_entry(pool: &SqlitePool, id: i64) -> Result<(), AppError> {
+    sqlx::query("DELETE FROM trade_journal WHERE id = ?")
+        .bind(id)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
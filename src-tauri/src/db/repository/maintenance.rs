@@ -0,0 +1,57 @@
+//! 数据库维护仓库：存储统计、ANALYZE
+
+use crate::error::AppError;
+use serde::Serialize;
+use sqlx::sqlite::SqlitePool;
+
+/// 数据库文件页统计
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct DbStorageStats {
+    pub page_size: i64,
+    pub page_count: i64,
+    /// 空闲页数（删除数据后未回收的页，VACUUM 可回收）
+    pub freelist_count: i64,
+}
+
+pub async fn get_storage_stats(pool: &SqlitePool) -> Result<DbStorageStats, AppError> {
+    let (page_size,): (i64,) = sqlx::query_as("PRAGMA page_size").fetch_one(pool).await?;
+    let (page_count,): (i64,) = sqlx::query_as("PRAGMA page_count").fetch_one(pool).await?;
+    let (freelist_count,): (i64,) = sqlx::query_as("PRAGMA freelist_count")
+        .fetch_one(pool)
+        .await?;
+    Ok(DbStorageStats {
+        page_size,
+        page_count,
+        freelist_count,
+    })
+}
+
+/// 更新查询规划器统计信息（迁移或大批量导入后执行）
+pub async fn analyze_database(pool: &SqlitePool) -> Result<(), AppError> {
+    sqlx::query("ANALYZE").execute(pool).await?;
+    Ok(())
+}
+
+/// 任取一只有历史K线的股票，供性能检查使用
+pub async fn get_any_historical_symbol(pool: &SqlitePool) -> Result<Option<String>, AppError> {
+    let symbol: Option<(String,)> = sqlx::query_as("SELECT symbol FROM historical_data LIMIT 1")
+        .fetch_optional(pool)
+        .await?;
+    Ok(symbol.map(|(symbol,)| symbol))
+}
+
+/// 按股票取最近的预测记录数（走 idx_predictions_stock）
+pub async fn count_recent_predictions(
+    pool: &SqlitePool,
+    symbol: &str,
+    limit: i64,
+) -> Result<usize, AppError> {
+    let rows: Vec<(i64,)> = sqlx::query_as(
+        "SELECT id FROM stock_predictions WHERE symbol = ? ORDER BY created_at DESC LIMIT ?",
+    )
+    .bind(symbol)
+    .bind(limit)
+    .fetch_all(pool)
+    .await?;
+    Ok(rows.len())
+}
@@ -0,0 +1,47 @@
+//! 模型训练记录仓库
+
+use crate::db::models::ModelHistoryEntry;
+use crate::error::AppError;
+use crate::prediction::types::ModelInfo;
+use sqlx::sqlite::SqlitePool;
+
+/// 记录一次重训练，`source` 为 manual / auto；返回新记录 id
+pub async fn insert_model_history(
+    pool: &SqlitePool,
+    model: &ModelInfo,
+    source: &str,
+    old_accuracy: Option<f64>,
+) -> Result<i64, AppError> {
+    let result = sqlx::query(
+        r#"
+        INSERT INTO model_history
+            (model_id, stock_code, source, old_accuracy, new_accuracy, mae, rmse)
+        VALUES (?, ?, ?, ?, ?, ?, ?)
+        "#,
+    )
+    .bind(&model.id)
+    .bind(&model.stock_code)
+    .bind(source)
+    .bind(old_accuracy)
+    .bind(model.accuracy)
+    .bind(model.mae)
+    .bind(model.rmse)
+    .execute(pool)
+    .await?;
+    Ok(result.last_insert_rowid())
+}
+
+/// 某模型的全部训练记录（按时间正序）
+pub async fn get_model_history(
+    model_id: &str,
+    pool: &SqlitePool,
+) -> Result<Vec<ModelHistoryEntry>, AppError> {
+    let entries = sqlx::query_as::<_, ModelHistoryEntry>(
+        "SELECT id, model_id, stock_code, source, old_accuracy, new_accuracy, mae, rmse, trained_at \
+         FROM model_history WHERE model_id = ? ORDER BY trained_at, id",
+    )
+    .bind(model_id)
+    .fetch_all(pool)
+    .await?;
+    Ok(entries)
+}
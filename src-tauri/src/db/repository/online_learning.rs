@@ -0,0 +1,58 @@
+//! 在线学习配置仓库
+
+use crate::db::models::OnlineLearningConfig;
+use crate::error::AppError;
+use sqlx::sqlite::SqlitePool;
+
+/// 启用（或更新学习率）模型的在线学习；已有消费进度保持不变
+pub async fn upsert_online_learning(
+    pool: &SqlitePool,
+    model_id: &str,
+    learning_rate: f64,
+) -> Result<(), AppError> {
+    sqlx::query(
+        r#"
+        INSERT INTO online_learning (model_id, learning_rate, enabled)
+        VALUES (?, ?, 1)
+        ON CONFLICT(model_id) DO UPDATE SET
+            learning_rate = excluded.learning_rate,
+            enabled = 1,
+            updated_at = CURRENT_TIMESTAMP
+        "#,
+    )
+    .bind(model_id)
+    .bind(learning_rate)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// 全部已启用在线学习的模型
+pub async fn get_enabled_online_learning(
+    pool: &SqlitePool,
+) -> Result<Vec<OnlineLearningConfig>, AppError> {
+    let configs = sqlx::query_as::<_, OnlineLearningConfig>(
+        "SELECT model_id, learning_rate, enabled, last_record_id \
+         FROM online_learning WHERE enabled = 1 ORDER BY model_id",
+    )
+    .fetch_all(pool)
+    .await?;
+    Ok(configs)
+}
+
+/// 推进已消费的预测记录 id
+pub async fn set_online_learning_cursor(
+    pool: &SqlitePool,
+    model_id: &str,
+    last_record_id: i64,
+) -> Result<(), AppError> {
+    sqlx::query(
+        "UPDATE online_learning SET last_record_id = ?, updated_at = CURRENT_TIMESTAMP \
+         WHERE model_id = ?",
+    )
+    .bind(last_record_id)
+    .bind(model_id)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
@@ -0,0 +1,119 @@
+//! 预测准确率跟踪仓库
+
+use crate::db::models::{NewPredictionRecord, PredictionHistoryRecord};
+use crate::error::AppError;
+use crate::utils::canonical_stock_symbol;
+use chrono::NaiveDate;
+use sqlx::sqlite::SqlitePool;
+
+const PREDICTION_HISTORY_COLUMNS: &str = "id, stock_code, model_id, prediction_date, target_date, \
+     base_price, predicted_price, predicted_direction, actual_price, actual_direction, \
+     was_direction_correct, price_error_pct";
+
+/// 写入预测记录；同一模型同日对同一目标日的重复预测覆盖旧值并清空已回填的实际值
+pub async fn insert_prediction_records(
+    pool: &SqlitePool,
+    records: &[NewPredictionRecord],
+) -> Result<(), AppError> {
+    let mut tx = pool.begin().await?;
+    for record in records {
+        sqlx::query(
+            r#"
+            INSERT INTO prediction_history
+                (stock_code, model_id, prediction_date, target_date, base_price,
+                 predicted_price, predicted_direction)
+            VALUES (?, ?, ?, ?, ?, ?, ?)
+            ON CONFLICT(stock_code, model_id, prediction_date, target_date) DO UPDATE SET
+                base_price = excluded.base_price,
+                predicted_price = excluded.predicted_price,
+                predicted_direction = excluded.predicted_direction,
+                actual_price = NULL,
+                actual_direction = NULL,
+                was_direction_correct = NULL,
+                price_error_pct = NULL,
+                created_at = CURRENT_TIMESTAMP
+            "#,
+        )
+        .bind(canonical_stock_symbol(&record.stock_code))
+        .bind(&record.model_id)
+        .bind(record.prediction_date)
+        .bind(record.target_date)
+        .bind(record.base_price)
+        .bind(record.predicted_price)
+        .bind(&record.predicted_direction)
+        .execute(&mut *tx)
+        .await?;
+    }
+    tx.commit().await?;
+    Ok(())
+}
+
+/// 目标日不晚于 `as_of` 且尚未回填实际价格的记录（按目标日正序）
+pub async fn get_pending_prediction_records(
+    pool: &SqlitePool,
+    as_of: NaiveDate,
+) -> Result<Vec<PredictionHistoryRecord>, AppError> {
+    let records = sqlx::query_as::<_, PredictionHistoryRecord>(&format!(
+        "SELECT {PREDICTION_HISTORY_COLUMNS} FROM prediction_history \
+         WHERE actual_price IS NULL AND target_date <= ? ORDER BY target_date, id"
+    ))
+    .bind(as_of)
+    .fetch_all(pool)
+    .await?;
+    Ok(records)
+}
+
+/// id 大于 `after_id` 且已回填实际价格的记录（按 id 正序），供在线学习增量消费
+pub async fn get_reconciled_prediction_records_after(
+    pool: &SqlitePool,
+    after_id: i64,
+) -> Result<Vec<PredictionHistoryRecord>, AppError> {
+    let records = sqlx::query_as::<_, PredictionHistoryRecord>(&format!(
+        "SELECT {PREDICTION_HISTORY_COLUMNS} FROM prediction_history \
+         WHERE actual_price IS NOT NULL AND id > ? ORDER BY id"
+    ))
+    .bind(after_id)
+    .fetch_all(pool)
+    .await?;
+    Ok(records)
+}
+
+/// 回填实际值
+pub async fn set_prediction_actual(
+    pool: &SqlitePool,
+    id: i64,
+    actual_price: f64,
+    actual_direction: &str,
+    was_direction_correct: bool,
+    price_error_pct: f64,
+) -> Result<(), AppError> {
+    sqlx::query(
+        "UPDATE prediction_history SET actual_price = ?, actual_direction = ?, \
+         was_direction_correct = ?, price_error_pct = ? WHERE id = ?",
+    )
+    .bind(actual_price)
+    .bind(actual_direction)
+    .bind(was_direction_correct)
+    .bind(price_error_pct)
+    .bind(id)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// 某股票目标日不早于 `since` 的全部记录（按目标日正序）
+pub async fn get_prediction_records_since(
+    pool: &SqlitePool,
+    stock_code: &str,
+    since: NaiveDate,
+) -> Result<Vec<PredictionHistoryRecord>, AppError> {
+    let records = sqlx::query_as::<_, PredictionHistoryRecord>(&format!(
+        "SELECT {PREDICTION_HISTORY_COLUMNS} FROM prediction_history \
+         WHERE stock_code = ? AND target_date >= ? ORDER BY target_date, id"
+    ))
+    .bind(canonical_stock_symbol(stock_code))
+    .bind(since)
+    .fetch_all(pool)
+    .await?;
+    Ok(records)
+}
@@ -0,0 +1,128 @@
+//! 指标预设仓库
+
+use crate::config::presets::{
+    find_builtin_preset, IndicatorConfig, IndicatorPreset, DEFAULT_PRESET,
+};
+use crate::error::AppError;
+use sqlx::sqlite::SqlitePool;
+
+/// 当前选中预设在 app_settings 中的键
+const ACTIVE_PRESET_KEY: &str = "active_indicator_preset";
+
+type PresetRow = (String, i64, i64, i64, i64, i64, f64, i64, i64);
+
+fn preset_from_row(row: PresetRow) -> IndicatorPreset {
+    let (name, rsi, fast, slow, signal, boll, boll_std, kdj, atr) = row;
+    IndicatorPreset {
+        name,
+        rsi_period: rsi.max(1) as usize,
+        macd_fast: fast.max(1) as usize,
+        macd_slow: slow.max(1) as usize,
+        macd_signal: signal.max(1) as usize,
+        bollinger_period: boll.max(1) as usize,
+        bollinger_std: boll_std,
+        kdj_period: kdj.max(1) as usize,
+        atr_period: atr.max(1) as usize,
+    }
+}
+
+const PRESET_COLUMNS: &str = "name, rsi_period, macd_fast, macd_slow, macd_signal, \
+     bollinger_period, bollinger_std, kdj_period, atr_period";
+
+/// 全部自定义预设（按创建时间排序）
+pub async fn get_custom_presets(pool: &SqlitePool) -> Result<Vec<IndicatorPreset>, AppError> {
+    let rows: Vec<PresetRow> = sqlx::query_as(&format!(
+        "SELECT {PRESET_COLUMNS} FROM indicator_presets ORDER BY created_at, name"
+    ))
+    .fetch_all(pool)
+    .await?;
+    Ok(rows.into_iter().map(preset_from_row).collect())
+}
+
+/// 按名称查找预设：先查内置预设，再查自定义预设
+pub async fn find_preset(
+    name: &str,
+    pool: &SqlitePool,
+) -> Result<Option<IndicatorPreset>, AppError> {
+    if let Some(preset) = find_builtin_preset(name) {
+        return Ok(Some(preset));
+    }
+    let row: Option<PresetRow> = sqlx::query_as(&format!(
+        "SELECT {PRESET_COLUMNS} FROM indicator_presets WHERE name = ?"
+    ))
+    .bind(name)
+    .fetch_optional(pool)
+    .await?;
+    Ok(row.map(preset_from_row))
+}
+
+/// 写入或覆盖一个自定义预设
+pub async fn upsert_custom_preset(
+    pool: &SqlitePool,
+    preset: &IndicatorPreset,
+) -> Result<(), AppError> {
+    sqlx::query(
+        r#"
+        INSERT INTO indicator_presets
+            (name, rsi_period, macd_fast, macd_slow, macd_signal,
+             bollinger_period, bollinger_std, kdj_period, atr_period)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+        ON CONFLICT(name) DO UPDATE SET
+            rsi_period = EXCLUDED.rsi_period,
+            macd_fast = EXCLUDED.macd_fast,
+            macd_slow = EXCLUDED.macd_slow,
+            macd_signal = EXCLUDED.macd_signal,
+            bollinger_period = EXCLUDED.bollinger_period,
+            bollinger_std = EXCLUDED.bollinger_std,
+            kdj_period = EXCLUDED.kdj_period,
+            atr_period = EXCLUDED.atr_period
+        "#,
+    )
+    .bind(&preset.name)
+    .bind(preset.rsi_period as i64)
+    .bind(preset.macd_fast as i64)
+    .bind(preset.macd_slow as i64)
+    .bind(preset.macd_signal as i64)
+    .bind(preset.bollinger_period as i64)
+    .bind(preset.bollinger_std)
+    .bind(preset.kdj_period as i64)
+    .bind(preset.atr_period as i64)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// 当前选中的预设名，未设置时返回 `DEFAULT_PRESET`
+pub async fn get_active_preset_name(pool: &SqlitePool) -> Result<String, AppError> {
+    let row: Option<(String,)> = sqlx::query_as("SELECT value FROM app_settings WHERE key = ?")
+        .bind(ACTIVE_PRESET_KEY)
+        .fetch_optional(pool)
+        .await?;
+    Ok(row.map(|(name,)| name).unwrap_or_else(|| DEFAULT_PRESET.to_string()))
+}
+
+/// 记录当前选中的预设名
+pub async fn set_active_preset_name(pool: &SqlitePool, name: &str) -> Result<(), AppError> {
+    sqlx::query(
+        "INSERT INTO app_settings (key, value, updated_at) VALUES (?, ?, CURRENT_TIMESTAMP) \
+         ON CONFLICT(key) DO UPDATE SET value = EXCLUDED.value, updated_at = CURRENT_TIMESTAMP",
+    )
+    .bind(ACTIVE_PRESET_KEY)
+    .bind(name)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// 当前选中预设的指标参数；预设已被删除或查询失败时回退默认参数
+pub async fn get_active_indicator_config(pool: &SqlitePool) -> IndicatorConfig {
+    let Ok(name) = get_active_preset_name(pool).await else {
+        return IndicatorConfig::default();
+    };
+    find_preset(&name, pool)
+        .await
+        .ok()
+        .flatten()
+        .map(|preset| preset.config())
+        .unwrap_or_default()
+}
@@ -0,0 +1,146 @@
+//! 实时行情快照仓库
+
+use crate::db::models::RealtimeQuote;
+use crate::error::AppError;
+use crate::utils::canonical_stock_symbol;
+use chrono::{Duration, NaiveDate};
+use sqlx::sqlite::SqlitePool;
+
+/// 行情快照保留天数，写入时清理更早的记录
+pub const QUOTE_RETENTION_DAYS: i64 = 30;
+
+const QUOTE_COLUMNS: &str =
+    "stock_code, timestamp, price, change_pct, volume, turnover, bid_price, ask_price";
+
+/// 写入行情快照（同一时间戳覆盖），并清理该股票超过保留期的快照
+pub async fn upsert_realtime_quote(pool: &SqlitePool, quote: &RealtimeQuote) -> Result<(), AppError> {
+    let stock_code = canonical_stock_symbol(&quote.stock_code);
+    let mut tx = pool.begin().await?;
+    sqlx::query(
+        r#"
+        INSERT INTO realtime_quotes
+            (stock_code, timestamp, price, change_pct, volume, turnover, bid_price, ask_price)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+        ON CONFLICT(stock_code, timestamp) DO UPDATE SET
+            price = excluded.price,
+            change_pct = excluded.change_pct,
+            volume = excluded.volume,
+            turnover = excluded.turnover,
+            bid_price = excluded.bid_price,
+            ask_price = excluded.ask_price
+        "#,
+    )
+    .bind(&stock_code)
+    .bind(quote.timestamp)
+    .bind(quote.price)
+    .bind(quote.change_pct)
+    .bind(quote.volume)
+    .bind(quote.turnover)
+    .bind(quote.bid_price)
+    .bind(quote.ask_price)
+    .execute(&mut *tx)
+    .await?;
+    sqlx::query("DELETE FROM realtime_quotes WHERE stock_code = ? AND timestamp < ?")
+        .bind(&stock_code)
+        .bind(quote.timestamp - Duration::days(QUOTE_RETENTION_DAYS))
+        .execute(&mut *tx)
+        .await?;
+    tx.commit().await?;
+    Ok(())
+}
+
+/// 某股票最新的行情快照
+pub async fn get_latest_quote(
+    pool: &SqlitePool,
+    stock_code: &str,
+) -> Result<Option<RealtimeQuote>, AppError> {
+    let quote = sqlx::query_as::<_, RealtimeQuote>(&format!(
+        "SELECT {QUOTE_COLUMNS} FROM realtime_quotes WHERE stock_code = ? \
+         ORDER BY timestamp DESC LIMIT 1"
+    ))
+    .bind(canonical_stock_symbol(stock_code))
+    .fetch_optional(pool)
+    .await?;
+    Ok(quote)
+}
+
+/// 某股票某日的全部行情快照（按时间正序）
+pub async fn get_quotes_on_date(
+    pool: &SqlitePool,
+    stock_code: &str,
+    date: NaiveDate,
+) -> Result<Vec<RealtimeQuote>, AppError> {
+    let start = date.and_hms_opt(0, 0, 0).unwrap_or_default();
+    let quotes = sqlx::query_as::<_, RealtimeQuote>(&format!(
+        "SELECT {QUOTE_COLUMNS} FROM realtime_quotes \
+         WHERE stock_code = ? AND timestamp >= ? AND timestamp < ? ORDER BY timestamp"
+    ))
+    .bind(canonical_stock_symbol(stock_code))
+    .bind(start)
+    .bind(start + Duration::days(1))
+    .fetch_all(pool)
+    .await?;
+    Ok(quotes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDateTime;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    async fn quote_pool() -> SqlitePool {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("应创建内存 SQLite");
+        sqlx::query(include_str!("../../../migrations/17_realtime_quotes.sql"))
+            .execute(&pool)
+            .await
+            .expect("应创建行情快照表");
+        pool
+    }
+
+    fn quote(timestamp: &str, price: f64) -> RealtimeQuote {
+        RealtimeQuote {
+            stock_code: "600519.SH".to_string(),
+            timestamp: NaiveDateTime::parse_from_str(timestamp, "%Y-%m-%d %H:%M:%S").unwrap(),
+            price,
+            change_pct: 0.5,
+            volume: 1000.0,
+            turnover: 1.5e6,
+            bid_price: None,
+            ask_price: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_quote_history_and_retention() {
+        let pool = quote_pool().await;
+        for q in [
+            quote("2025-05-01 10:00:00", 1500.0),
+            quote("2025-06-13 09:35:00", 1580.0),
+            quote("2025-06-13 10:00:00", 1585.0),
+            // 同一时间戳重复拉取覆盖
+            quote("2025-06-13 10:00:00", 1586.0),
+        ] {
+            upsert_realtime_quote(&pool, &q).await.unwrap();
+        }
+
+        let latest = get_latest_quote(&pool, "600519").await.unwrap().unwrap();
+        assert_eq!(latest.stock_code, "600519");
+        assert_eq!(latest.price, 1586.0);
+
+        let date = NaiveDate::from_ymd_opt(2025, 6, 13).unwrap();
+        let today = get_quotes_on_date(&pool, "600519", date).await.unwrap();
+        assert_eq!(
+            today.iter().map(|q| q.price).collect::<Vec<_>>(),
+            vec![1580.0, 1586.0]
+        );
+
+        // 超过保留期的 5 月快照已清理
+        let may = NaiveDate::from_ymd_opt(2025, 5, 1).unwrap();
+        assert!(get_quotes_on_date(&pool, "600519", may).await.unwrap().is_empty());
+    }
+}
@@ -0,0 +1,48 @@
+//! 应用级设置仓库（`app_settings` 键值表）
+
+use crate::config::weights::BuySellPointConfig;
+use crate::error::AppError;
+use log::warn;
+use sqlx::sqlite::SqlitePool;
+
+/// 买卖点过滤阈值在 app_settings 中的键
+const BUY_SELL_POINT_CONFIG_KEY: &str = "buy_sell_point_config";
+
+/// 读取买卖点过滤阈值；未设置、查询失败或内容损坏时返回默认值
+pub async fn get_buy_sell_point_config(pool: &SqlitePool) -> BuySellPointConfig {
+    let row: Result<Option<(String,)>, _> =
+        sqlx::query_as("SELECT value FROM app_settings WHERE key = ?")
+            .bind(BUY_SELL_POINT_CONFIG_KEY)
+            .fetch_optional(pool)
+            .await;
+    match row {
+        Ok(Some((value,))) => serde_json::from_str(&value).unwrap_or_else(|e| {
+            warn!("买卖点过滤阈值解析失败，使用默认值: {e}");
+            BuySellPointConfig::default()
+        }),
+        Ok(None) => BuySellPointConfig::default(),
+        Err(e) => {
+            warn!("读取买卖点过滤阈值失败，使用默认值: {e}");
+            BuySellPointConfig::default()
+        }
+    }
+}
+
+/// 校验并保存买卖点过滤阈值
+pub async fn set_buy_sell_point_config(
+    pool: &SqlitePool,
+    config: &BuySellPointConfig,
+) -> Result<(), AppError> {
+    config.validate()?;
+    let value = serde_json::to_string(config)
+        .map_err(|e| AppError::DeserializationError(e.to_string()))?;
+    sqlx::query(
+        "INSERT INTO app_settings (key, value, updated_at) VALUES (?, ?, CURRENT_TIMESTAMP) \
+         ON CONFLICT(key) DO UPDATE SET value = EXCLUDED.value, updated_at = CURRENT_TIMESTAMP",
+    )
+    .bind(BUY_SELL_POINT_CONFIG_KEY)
+    .bind(value)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
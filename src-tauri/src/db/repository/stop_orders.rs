@@ -0,0 +1,221 @@
+//! 止损止盈单仓库
+
+use crate::db::models::{StopOrder, StopOrderStatus, StopOrderType};
+use crate::error::AppError;
+use crate::utils::canonical_stock_symbol;
+use chrono::NaiveDateTime;
+use log::warn;
+use sqlx::sqlite::SqlitePool;
+
+const STOP_ORDER_COLUMNS: &str =
+    "id, stock_code, order_type, trigger_price, created_at, triggered_at, status";
+
+type StopOrderRow = (i64, String, String, f64, NaiveDateTime, Option<NaiveDateTime>, String);
+
+fn stop_order_from_row(row: StopOrderRow) -> Result<StopOrder, AppError> {
+    let (id, stock_code, order_type, trigger_price, created_at, triggered_at, status) = row;
+    let order_type = StopOrderType::parse(&order_type).ok_or_else(|| {
+        AppError::DeserializationError(format!("止损止盈单 {id} 类型无效: {order_type}"))
+    })?;
+    let status = StopOrderStatus::parse(&status).ok_or_else(|| {
+        AppError::DeserializationError(format!("止损止盈单 {id} 状态无效: {status}"))
+    })?;
+    Ok(StopOrder {
+        id,
+        stock_code,
+        order_type,
+        trigger_price,
+        created_at,
+        triggered_at,
+        status,
+    })
+}
+
+/// 新建止损止盈单，返回新单 id
+pub async fn insert_stop_order(
+    pool: &SqlitePool,
+    stock_code: &str,
+    order_type: StopOrderType,
+    trigger_price: f64,
+) -> Result<i64, AppError> {
+    let result = sqlx::query(
+        "INSERT INTO stop_orders (stock_code, order_type, trigger_price, status) VALUES (?, ?, ?, ?)",
+    )
+    .bind(canonical_stock_symbol(stock_code))
+    .bind(order_type.as_str())
+    .bind(trigger_price)
+    .bind(StopOrderStatus::Active.as_str())
+    .execute(pool)
+    .await?;
+    Ok(result.last_insert_rowid())
+}
+
+/// 按 id 查询
+pub async fn get_stop_order(pool: &SqlitePool, id: i64) -> Result<Option<StopOrder>, AppError> {
+    let row: Option<StopOrderRow> =
+        sqlx::query_as(&format!("SELECT {STOP_ORDER_COLUMNS} FROM stop_orders WHERE id = ?"))
+            .bind(id)
+            .fetch_optional(pool)
+            .await?;
+    row.map(stop_order_from_row).transpose()
+}
+
+/// 查询止损止盈单（按创建时间倒序），`stock_code` 为 None 时返回全部
+pub async fn get_stop_orders(
+    stock_code: Option<&str>,
+    pool: &SqlitePool,
+) -> Result<Vec<StopOrder>, AppError> {
+    let rows: Vec<StopOrderRow> = match stock_code {
+        Some(code) => {
+            sqlx::query_as(&format!(
+                "SELECT {STOP_ORDER_COLUMNS} FROM stop_orders WHERE stock_code = ? \
+                 ORDER BY created_at DESC, id DESC"
+            ))
+            .bind(canonical_stock_symbol(code))
+            .fetch_all(pool)
+            .await?
+        }
+        None => {
+            sqlx::query_as(&format!(
+                "SELECT {STOP_ORDER_COLUMNS} FROM stop_orders ORDER BY created_at DESC, id DESC"
+            ))
+            .fetch_all(pool)
+            .await?
+        }
+    };
+    rows.into_iter().map(stop_order_from_row).collect()
+}
+
+/// 生效中的止损止盈单；无法解析的行跳过
+pub async fn get_active_stop_orders(pool: &SqlitePool) -> Result<Vec<StopOrder>, AppError> {
+    let rows: Vec<StopOrderRow> = sqlx::query_as(&format!(
+        "SELECT {STOP_ORDER_COLUMNS} FROM stop_orders WHERE status = ? ORDER BY stock_code, id"
+    ))
+    .bind(StopOrderStatus::Active.as_str())
+    .fetch_all(pool)
+    .await?;
+    Ok(rows
+        .into_iter()
+        .filter_map(|row| match stop_order_from_row(row) {
+            Ok(order) => Some(order),
+            Err(e) => {
+                warn!("跳过无效止损止盈单: {e}");
+                None
+            }
+        })
+        .collect())
+}
+
+/// 修改生效中单据的触发价；单据不存在或已失效时返回 InvalidInput
+pub async fn update_stop_order_price(
+    pool: &SqlitePool,
+    id: i64,
+    trigger_price: f64,
+) -> Result<(), AppError> {
+    let updated = sqlx::query("UPDATE stop_orders SET trigger_price = ? WHERE id = ? AND status = ?")
+        .bind(trigger_price)
+        .bind(id)
+        .bind(StopOrderStatus::Active.as_str())
+        .execute(pool)
+        .await?
+        .rows_affected();
+    if updated == 0 {
+        return Err(AppError::InvalidInput(format!("止损止盈单 {id} 不存在或已失效")));
+    }
+    Ok(())
+}
+
+/// 将生效中的单据置为已触发并记录时间；返回是否由本次调用触发（已失效的单据不重复触发）
+pub async fn mark_stop_order_triggered(pool: &SqlitePool, id: i64) -> Result<bool, AppError> {
+    let updated = sqlx::query(
+        "UPDATE stop_orders SET status = ?, triggered_at = CURRENT_TIMESTAMP \
+         WHERE id = ? AND status = ?",
+    )
+    .bind(StopOrderStatus::Triggered.as_str())
+    .bind(id)
+    .bind(StopOrderStatus::Active.as_str())
+    .execute(pool)
+    .await?
+    .rows_affected();
+    Ok(updated > 0)
+}
+
+/// 撤销生效中的单据；单据不存在或已失效时返回 InvalidInput
+pub async fn cancel_stop_order(pool: &SqlitePool, id: i64) -> Result<(), AppError> {
+    let updated = sqlx::query("UPDATE stop_orders SET status = ? WHERE id = ? AND status = ?")
+        .bind(StopOrderStatus::Cancelled.as_str())
+        .bind(id)
+        .bind(StopOrderStatus::Active.as_str())
+        .execute(pool)
+        .await?
+        .rows_affected();
+    if updated == 0 {
+        return Err(AppError::InvalidInput(format!("止损止盈单 {id} 不存在或已失效")));
+    }
+    Ok(())
+}
+
+/// 删除止损止盈单
+pub async fn delete_stop_order(pool: &SqlitePool, id: i64) -> Result<(), AppError> {
+    sqlx::query("DELETE FROM stop_orders WHERE id = ?")
+        .bind(id)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    async fn stop_order_pool() -> SqlitePool {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("应创建内存 SQLite");
+        for statement in include_str!("../../../migrations/20_stop_orders.sql").split(';') {
+            if !statement.trim().is_empty() {
+                sqlx::query(statement)
+                    .execute(&pool)
+                    .await
+                    .expect("应创建止损止盈单表");
+            }
+        }
+        pool
+    }
+
+    #[tokio::test]
+    async fn test_stop_order_lifecycle() {
+        let pool = stop_order_pool().await;
+        let stop = insert_stop_order(&pool, "600519.SH", StopOrderType::StopLoss, 1500.0)
+            .await
+            .unwrap();
+        let take = insert_stop_order(&pool, "600519", StopOrderType::TakeProfit, 1800.0)
+            .await
+            .unwrap();
+
+        update_stop_order_price(&pool, stop, 1520.0).await.unwrap();
+        let order = get_stop_order(&pool, stop).await.unwrap().unwrap();
+        assert_eq!(order.stock_code, "600519");
+        assert_eq!(order.trigger_price, 1520.0);
+        assert_eq!(order.status, StopOrderStatus::Active);
+        assert_eq!(get_active_stop_orders(&pool).await.unwrap().len(), 2);
+
+        // 只触发一次，触发后不可修改或撤销
+        assert!(mark_stop_order_triggered(&pool, stop).await.unwrap());
+        assert!(!mark_stop_order_triggered(&pool, stop).await.unwrap());
+        assert!(update_stop_order_price(&pool, stop, 1400.0).await.is_err());
+        let order = get_stop_order(&pool, stop).await.unwrap().unwrap();
+        assert_eq!(order.status, StopOrderStatus::Triggered);
+        assert!(order.triggered_at.is_some());
+
+        cancel_stop_order(&pool, take).await.unwrap();
+        assert!(get_active_stop_orders(&pool).await.unwrap().is_empty());
+        assert_eq!(get_stop_orders(Some("600519.SH"), &pool).await.unwrap().len(), 2);
+
+        delete_stop_order(&pool, take).await.unwrap();
+        assert!(get_stop_order(&pool, take).await.unwrap().is_none());
+    }
+}
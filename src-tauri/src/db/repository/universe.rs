@@ -0,0 +1,299 @@
+//! 全市场股票池仓库
+
+use crate::db::models::{MarketCapCategory, StockSearchResult, UniverseEntry};
+use crate::error::AppError;
+use crate::utils::pinyin_abbr;
+use serde::{Deserialize, Serialize};
+use sqlx::{sqlite::SqlitePool, QueryBuilder};
+use std::collections::HashMap;
+
+/// 股票池筛选条件；列表为空表示不限
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UniverseCriteria {
+    pub exclude_st: bool,
+    /// SH / SZ / BSE
+    pub exchanges: Vec<String>,
+    pub industries: Vec<String>,
+    /// 最低市值分档；设置后排除无股本数据的股票
+    pub min_market_cap_category: Option<MarketCapCategory>,
+}
+
+/// 用最新一次接口结果替换股票池：本次返回的股票写入并置为活跃，其余置为不活跃
+pub async fn replace_universe(pool: &SqlitePool, entries: &[UniverseEntry]) -> Result<(), AppError> {
+    let mut tx = pool.begin().await?;
+    sqlx::query("UPDATE stock_universe SET is_active = 0")
+        .execute(&mut *tx)
+        .await?;
+    for entry in entries {
+        sqlx::query(
+            r#"
+            INSERT INTO stock_universe
+                (code, name, exchange, industry, market_cap_category, is_st, pinyin_abbr, is_active, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, 1, CURRENT_TIMESTAMP)
+            ON CONFLICT(code) DO UPDATE SET
+                name = excluded.name,
+                pinyin_abbr = excluded.pinyin_abbr,
+                exchange = excluded.exchange,
+                industry = excluded.industry,
+                market_cap_category = excluded.market_cap_category,
+                is_st = excluded.is_st,
+                is_active = 1,
+                updated_at = CURRENT_TIMESTAMP
+            "#,
+        )
+        .bind(&entry.code)
+        .bind(&entry.name)
+        .bind(&entry.exchange)
+        .bind(&entry.industry)
+        .bind(&entry.market_cap_category)
+        .bind(entry.is_st)
+        .bind(pinyin_abbr(&entry.name))
+        .execute(&mut *tx)
+        .await?;
+    }
+    tx.commit().await?;
+    Ok(())
+}
+
+/// 按条件筛选活跃股票，返回代码列表（升序）
+pub async fn filter_universe(
+    pool: &SqlitePool,
+    criteria: &UniverseCriteria,
+) -> Result<Vec<String>, AppError> {
+    let mut query = QueryBuilder::new("SELECT code FROM stock_universe WHERE is_active = 1");
+    if criteria.exclude_st {
+        query.push(" AND is_st = 0");
+    }
+    let mut push_in = |column: &str, values: &[&str]| {
+        query.push(format!(" AND {column} IN ("));
+        let mut separated = query.separated(", ");
+        for value in values {
+            separated.push_bind(value.to_string());
+        }
+        separated.push_unseparated(")");
+    };
+    if !criteria.exchanges.is_empty() {
+        let exchanges: Vec<String> = criteria
+            .exchanges
+            .iter()
+            .map(|e| e.trim().to_ascii_uppercase())
+            .collect();
+        push_in("exchange", &exchanges.iter().map(String::as_str).collect::<Vec<_>>());
+    }
+    if !criteria.industries.is_empty() {
+        push_in(
+            "industry",
+            &criteria.industries.iter().map(String::as_str).collect::<Vec<_>>(),
+        );
+    }
+    if let Some(min) = criteria.min_market_cap_category {
+        let labels: Vec<&str> = MarketCapCategory::ALL
+            .into_iter()
+            .filter(|category| *category >= min)
+            .map(MarketCapCategory::label)
+            .collect();
+        push_in("market_cap_category", &labels);
+    }
+    query.push(" ORDER BY code");
+
+    let rows: Vec<(String,)> = query.build_query_as().fetch_all(pool).await?;
+    Ok(rows.into_iter().map(|(code,)| code).collect())
+}
+
+/// 按代码、名称或拼音首字母模糊搜索活跃股票。
+///
+/// 排序：代码完全匹配 > 代码前缀 > 名称或拼音前缀 > 其余包含匹配，同级按代码升序。
+pub async fn search_universe(
+    pool: &SqlitePool,
+    query: &str,
+    limit: usize,
+) -> Result<Vec<StockSearchResult>, AppError> {
+    let query = query.trim();
+    if query.is_empty() || limit == 0 {
+        return Ok(Vec::new());
+    }
+    let pinyin = query.to_ascii_lowercase();
+    let contains = format!("%{query}%");
+    let prefix = format!("{query}%");
+    let pinyin_contains = format!("%{pinyin}%");
+    let pinyin_prefix = format!("{pinyin}%");
+    let results = sqlx::query_as::<_, StockSearchResult>(
+        r#"
+        SELECT code, name, exchange, is_st
+        FROM stock_universe
+        WHERE is_active = 1 AND (code LIKE ? OR name LIKE ? OR pinyin_abbr LIKE ?)
+        ORDER BY
+            CASE
+                WHEN code = ? THEN 0
+                WHEN code LIKE ? THEN 1
+                WHEN name LIKE ? OR pinyin_abbr LIKE ? THEN 2
+                ELSE 3
+            END,
+            code
+        LIMIT ?
+        "#,
+    )
+    .bind(&contains)
+    .bind(&contains)
+    .bind(&pinyin_contains)
+    .bind(query)
+    .bind(&prefix)
+    .bind(&prefix)
+    .bind(&pinyin_prefix)
+    .bind(limit as i64)
+    .fetch_all(pool)
+    .await?;
+    Ok(results)
+}
+
+/// 本地股票详情中的行业（代码 → 行业）
+pub async fn get_stock_industries(pool: &SqlitePool) -> Result<HashMap<String, String>, AppError> {
+    let rows: Vec<(String, Option<String>)> = sqlx::query_as("SELECT symbol, industry FROM stock")
+        .fetch_all(pool)
+        .await?;
+    Ok(rows
+        .into_iter()
+        .filter_map(|(symbol, industry)| Some((symbol, industry?)))
+        .collect())
+}
+
+/// 本地股本快照中的流通市值（代码 → 元）
+pub async fn get_circulating_market_caps(
+    pool: &SqlitePool,
+) -> Result<HashMap<String, f64>, AppError> {
+    let rows: Vec<(String, f64)> =
+        sqlx::query_as("SELECT symbol, circulating_market_cap FROM stock_capital")
+            .fetch_all(pool)
+            .await?;
+    Ok(rows.into_iter().collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    async fn universe_pool() -> SqlitePool {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("应创建内存 SQLite");
+        let sql = [
+            include_str!("../../../migrations/15_stock_universe.sql"),
+            include_str!("../../../migrations/21_stock_search.sql"),
+        ]
+        .join(";");
+        for statement in sql.split(';').map(str::trim).filter(|s| !s.is_empty()) {
+            sqlx::query(statement)
+                .execute(&pool)
+                .await
+                .expect("应创建股票池表");
+        }
+        pool
+    }
+
+    fn entry(code: &str, exchange: &str, industry: &str, cap: &str, is_st: bool) -> UniverseEntry {
+        UniverseEntry {
+            code: code.to_string(),
+            name: code.to_string(),
+            exchange: exchange.to_string(),
+            industry: industry.to_string(),
+            market_cap_category: cap.to_string(),
+            is_st,
+            is_active: true,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_replace_and_filter_universe() {
+        let pool = universe_pool().await;
+        replace_universe(
+            &pool,
+            &[
+                entry("600519", "SH", "白酒", "大盘", false),
+                entry("000004", "SZ", "软件服务", "小盘", true),
+                entry("300750", "SZ", "电池", "中盘", false),
+                entry("830799", "BSE", "电池", "", false),
+            ],
+        )
+        .await
+        .unwrap();
+        // 第二次更新未返回 600519：应置为不活跃
+        replace_universe(
+            &pool,
+            &[
+                entry("000004", "SZ", "软件服务", "小盘", true),
+                entry("300750", "SZ", "电池", "大盘", false),
+                entry("830799", "BSE", "电池", "", false),
+            ],
+        )
+        .await
+        .unwrap();
+
+        let all = filter_universe(&pool, &UniverseCriteria::default()).await.unwrap();
+        assert_eq!(all, vec!["000004", "300750", "830799"]);
+
+        let criteria = UniverseCriteria {
+            exclude_st: true,
+            exchanges: vec!["sz".to_string(), "BSE".to_string()],
+            industries: vec!["电池".to_string()],
+            min_market_cap_category: None,
+        };
+        assert_eq!(
+            filter_universe(&pool, &criteria).await.unwrap(),
+            vec!["300750", "830799"]
+        );
+
+        let large_only = UniverseCriteria {
+            min_market_cap_category: Some(MarketCapCategory::Mid),
+            ..UniverseCriteria::default()
+        };
+        assert_eq!(filter_universe(&pool, &large_only).await.unwrap(), vec!["300750"]);
+    }
+
+    #[tokio::test]
+    async fn test_search_universe_ranks_code_prefix_first() {
+        let pool = universe_pool().await;
+        let named = |code: &str, name: &str, is_st: bool| UniverseEntry {
+            name: name.to_string(),
+            ..entry(code, if code.starts_with('6') { "SH" } else { "SZ" }, "", "", is_st)
+        };
+        replace_universe(
+            &pool,
+            &[
+                named("000001", "平安银行", false),
+                named("601318", "中国平安", false),
+                named("600000", "浦发银行", false),
+                named("000010", "美丽生态", true),
+            ],
+        )
+        .await
+        .unwrap();
+
+        let codes = |results: Vec<StockSearchResult>| -> Vec<String> {
+            results.into_iter().map(|r| r.code).collect()
+        };
+        // 代码前缀优先于代码中间包含
+        assert_eq!(
+            codes(search_universe(&pool, "0000", 10).await.unwrap()),
+            vec!["000001", "000010", "600000"]
+        );
+        assert_eq!(
+            codes(search_universe(&pool, "000001", 10).await.unwrap()),
+            vec!["000001"]
+        );
+        // 名称前缀优先于名称包含
+        assert_eq!(
+            codes(search_universe(&pool, "平安", 10).await.unwrap()),
+            vec!["000001", "601318"]
+        );
+        let by_pinyin = search_universe(&pool, "PAYH", 10).await.unwrap();
+        assert_eq!(by_pinyin.len(), 1);
+        assert_eq!(by_pinyin[0].name, "平安银行");
+        assert_eq!(by_pinyin[0].exchange, "SZ");
+        assert_eq!(codes(search_universe(&pool, "yh", 1).await.unwrap()), vec!["000001"]);
+        assert!(search_universe(&pool, "  ", 10).await.unwrap().is_empty());
+    }
+}
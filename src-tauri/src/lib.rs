@@ -16,19 +16,30 @@ pub mod prediction;
 pub mod services;
 
 // 命令层
-mod commands;
+pub mod commands;
 
 // CSV 处理
 mod csv;
 
-use db::connection::create_pool;
+use chrono::Datelike;
+use db::connection::create_pool_with_retry;
+use log::{info, warn};
 use std::path::Path;
-use std::fs;
 use tauri::Manager;
 
+/// 启动时数据库连接的最大尝试次数与首次重试间隔
+const DB_CONNECT_ATTEMPTS: usize = 5;
+const DB_CONNECT_RETRY_DELAY_MS: u64 = 500;
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
-    tauri::Builder::default()
+    let builder = tauri::Builder::default();
+    // 单实例须最先注册：再次启动（含打开 biga:// 链接）转交已运行的实例
+    #[cfg(desktop)]
+    let builder = builder.plugin(tauri_plugin_single_instance::init(|app, _argv, _cwd| {
+        commands::deep_link::focus_main_window(app);
+    }));
+    builder
         .plugin(
             tauri_plugin_log::Builder::new()
                 .targets([
@@ -36,91 +47,217 @@ pub fn run() {
                     tauri_plugin_log::Target::new(tauri_plugin_log::TargetKind::LogDir { file_name: None }),
                     tauri_plugin_log::Target::new(tauri_plugin_log::TargetKind::Webview),
                 ])
+                // 插件保留全部级别，实际输出由全局级别控制（启动时应用已保存设置，可运行时调整）
+                .level(log::LevelFilter::Trace)
+                // sqlx 在 DEBUG 下逐条输出 SQL，仅保留慢查询等警告
+                .level_for("sqlx", log::LevelFilter::Warn)
                 .build(),
         )
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_dialog::init())
+        .plugin(tauri_plugin_deep_link::init())
+        .plugin(tauri_plugin_notification::init())
+        // 进行中的可取消长任务（训练 / 回测 / 超参数搜索 / 批量刷新）
+        .manage(services::progress::OperationRegistry::default())
+        // 模拟交易账户（内存中，重启后重置）
+        .manage(services::paper_trading::PaperTradingState::default())
+        // 冷启动时收到的 biga:// 链接，等待前端取出
+        .manage(commands::deep_link::PendingDeepLink::default())
         .invoke_handler(tauri::generate_handler![
             // 股票列表命令
             commands::stock_list::get_stock_list,
+            commands::stock_list::search_stocks,
             // 股票信息命令
             commands::stock::get_stock_infos,
             commands::stock::refresh_stock_infos,
+            commands::stock::get_stock_news,
+            commands::stock::get_upcoming_events,
+            commands::stock::get_etf_holdings,
+            commands::stock::get_etf_premium_discount,
             // 实时数据命令
             commands::stock_realtime::get_realtime_data,
+            commands::stock_realtime::get_quote_history_today,
             // 历史数据命令
             commands::stock_historical::get_historical_data,
+            commands::stock_historical::get_candle_data,
             commands::stock_historical::refresh_historical_data,
+            commands::stock_historical::check_data_integrity,
+            commands::stock_historical::repair_data_integrity,
             // 预测命令
             commands::stock_prediction::train_stock_prediction_model,
             commands::stock_prediction::predict_stock_price,
             commands::stock_prediction::list_stock_prediction_models,
             commands::stock_prediction::delete_stock_prediction_model,
+            commands::stock_prediction::promote_model_to_production,
+            commands::stock_prediction::archive_model,
+            commands::stock_prediction::get_production_model,
+            commands::stock_prediction::get_model_retention_policy,
+            commands::stock_prediction::set_model_retention_policy,
+            commands::stock_prediction::export_model,
             commands::stock_prediction::train_candle_model,
             commands::stock_prediction::predict_with_candle,
             commands::stock_prediction::predict_candle_price_simple,
+            commands::stock_prediction::predict_regime_conditional,
+            commands::stock_prediction::batch_predict_stocks,
+            commands::stock_prediction::compare_stock_predictions,
+            commands::stock_prediction::scan_divergence_opportunities,
+            commands::stock_prediction::get_regime_strategy_config,
+            commands::stock_prediction::set_regime_strategy_config,
             commands::stock_prediction::retrain_candle_model,
+            commands::stock_prediction::get_auto_retrain_config,
+            commands::stock_prediction::set_auto_retrain_config,
+            commands::stock_prediction::get_model_history,
+            commands::stock_prediction::enable_online_learning,
+            commands::stock_prediction::auto_select_model_features,
             commands::stock_prediction::evaluate_candle_model,
+            commands::stock_prediction::calibrate_conformal_predictor,
+            commands::stock_prediction::explain_prediction,
+            commands::stock_prediction::detect_model_drift,
             commands::stock_prediction::run_model_backtest,
             commands::stock_prediction::get_optimization_suggestions,
             commands::stock_prediction::get_multi_timeframe_signals,
             commands::stock_prediction::get_latest_multi_timeframe_signal,
             commands::stock_prediction::analyze_multi_timeframe_prediction_value,
+            commands::stock_prediction::get_heikin_ashi_data,
+            commands::stock_prediction::get_renko_chart,
+            commands::stock_prediction::get_supertrend_data,
+            commands::stock_prediction::get_fibonacci_time_cycles,
+            commands::stock_prediction::predict_opening_gap,
+            commands::stock_prediction::get_signal_suppression_status,
+            commands::stock_prediction::analyze_volume_anomalies,
             commands::stock_prediction::predict_with_professional_strategy,
             commands::stock_prediction::predict_with_technical_only,
             commands::stock_prediction::cross_sectional_ranking,
             commands::stock_prediction::get_valuation_context,
+            commands::stock_prediction::get_fundamental_data,
+            commands::stock_prediction::train_adaptive_factor_weights,
+            commands::stock_prediction::run_rolling_window_analysis,
+            commands::stock_prediction::evaluate_signal_accuracy,
+            commands::stock_prediction::analyze_backtest_by_signal,
+            commands::stock_prediction::run_hyperparameter_search,
+            commands::stock_prediction::predict_with_ensemble,
+            commands::stock_prediction::train_quantile_model,
+            commands::stock_prediction::get_prediction_accuracy_history,
+            commands::stock_prediction::get_position_size,
             // 收藏池命令
             commands::watchlist::get_watchlist_overview,
             commands::watchlist::add_to_watchlist,
             commands::watchlist::remove_from_watchlist,
             commands::watchlist::get_watchlist_symbols,
+            commands::watchlist::refresh_all_watchlist_data,
             commands::watchlist::comprehensive_predict,
             // 安全设置命令
             commands::settings::get_api_token_status,
             commands::settings::save_api_token,
             commands::settings::clear_api_token,
-            commands::settings::test_api_token
+            commands::settings::test_api_token,
+            commands::settings::set_log_level,
+            commands::settings::get_trading_config,
+            commands::settings::set_trading_config,
+            // CSV 导入导出命令
+            commands::csv::import_csv_data,
+            commands::csv::export_prediction_csv,
+            commands::csv::export_historical_csv,
+            // 市场宽度、情绪、Beta、配对交易、压力测试、行业轮动与截面特征命令
+            commands::market::get_market_overview,
+            commands::market::get_market_breadth,
+            commands::market::get_market_sentiment_index,
+            commands::market::get_market_timing,
+            commands::market::get_beta_analysis,
+            commands::market::get_pairs_opportunities,
+            commands::market::run_portfolio_stress_test,
+            commands::market::optimize_portfolio,
+            commands::market::get_sector_rotation_signals,
+            commands::market::get_cross_sectional_features,
+            // 交易日志命令
+            commands::journal::log_trade_journal_entry,
+            commands::journal::update_journal_entry_result,
+            commands::journal::get_trade_journal,
+            commands::journal::delete_trade_journal_entry,
+            commands::journal::get_performance_attribution,
+            // 通知命令
+            commands::notifications::send_notification,
+            commands::notifications::get_notification_preferences,
+            commands::notifications::set_notification_preferences,
+            // 分享链接命令
+            commands::deep_link::generate_shareable_link,
+            commands::deep_link::take_pending_deep_link,
+            // 指标预设命令
+            commands::presets::get_presets,
+            commands::presets::set_active_preset,
+            commands::presets::create_custom_preset,
+            // 个股预警命令
+            commands::alerts::create_stock_alert,
+            commands::alerts::get_stock_alerts,
+            commands::alerts::delete_stock_alert,
+            // 数据库维护命令
+            commands::database::check_db_performance,
+            commands::database::get_db_health,
+            // 全市场股票池命令
+            commands::universe::update_universe,
+            commands::universe::filter_stock_universe,
+            // 长任务取消
+            commands::operations::cancel_operation,
+            // 模拟交易命令
+            commands::paper_trading::paper_trade_buy,
+            commands::paper_trading::paper_trade_sell,
+            commands::paper_trading::get_paper_account_value,
+            commands::paper_trading::reset_paper_account,
+            // 止损止盈单命令
+            commands::stop_orders::create_stop_order,
+            commands::stop_orders::get_stop_orders,
+            commands::stop_orders::update_stop_order,
+            commands::stop_orders::cancel_stop_order,
+            commands::stop_orders::delete_stop_order
         ])
         .setup(|app| {
+            config::logging::apply_log_level(config::logging::load_log_level());
             tauri::async_runtime::block_on(async {
-                let pool = create_pool().await
+                let pool = create_pool_with_retry(DB_CONNECT_ATTEMPTS, DB_CONNECT_RETRY_DELAY_MS)
+                    .await
                     .expect("Failed to create database pool");
                 
                 // 执行迁移脚本
-                let migration_files = [
-                    "01_create_tables.sql",
-                    "02_stock_prediction_model.sql",
-                    "03_volume_metrics.sql",
-                    "04_stock_fundamentals.sql",
-                    "05_capital_valuation.sql",
-                    "06_stock_category.sql",
-                    "07_watchlist.sql",
-                    "08_canonical_stock_symbols.sql",
-                ];
-                for file in &migration_files {
-                    let path = Path::new("migrations").join(file);
-                    if path.exists() {
-                        let sql = fs::read_to_string(&path)
-                            .expect("Failed to read migration file");
-                        // 按语句拆分执行，幂等地忽略 "duplicate column" 错误
-                        // （SQLite 不支持 ALTER TABLE ADD COLUMN IF NOT EXISTS）
-                        for statement in sql.split(';') {
-                            let statement = statement.trim();
-                            if statement.is_empty() {
-                                continue;
-                            }
-                            if let Err(e) = sqlx::query(statement).execute(&pool).await {
-                                let msg = e.to_string();
-                                if msg.contains("duplicate column name") {
-                                    continue;
-                                }
-                                panic!("Failed to execute migration {file}: {e}");
-                            }
-                        }
-                    }
+                if let Err(e) = db::connection::run_migrations(&pool, Path::new("migrations")).await {
+                    panic!("{e}");
+                }
+                // 迁移可能新建索引，更新查询规划器统计信息
+                if let Err(e) = db::repository::analyze_database(&pool).await {
+                    warn!("ANALYZE 执行失败: {e}");
                 }
                 
+                // 交易日志与预测历史后台对账（回填已过去预测的实际价格）
+                services::journal::spawn_journal_reconciler(pool.clone());
+                // 启用在线学习的模型每日用已回填的预测误差微调权重
+                services::OnlineLearningService::new(pool.clone()).spawn();
+                // 按保留策略归档过期的预测模型
+                let archived = prediction::model::management::archive_expired_models(
+                    &prediction::model::management::load_retention_policy(),
+                    prediction::model::management::get_current_timestamp(),
+                );
+                if !archived.is_empty() {
+                    info!("已归档 {} 个过期模型", archived.len());
+                }
+                // 生产模型自动重训练（启动时检查，此后每周）
+                services::RetrainScheduler::new(app.handle().clone(), pool.clone()).spawn();
+                // 预警事件 → 系统通知
+                commands::notifications::subscribe_alert_notifications(app.handle());
+                // biga:// 分享链接 → 前端跳转
+                commands::deep_link::register_deep_link_handler(app.handle());
+                // 连接池定期探活，异常时通知前端
+                services::database::spawn_db_health_monitor(app.handle().clone(), pool.clone());
+                // 个股预警（价格 / 背离）后台监控
+                services::alerts::spawn_alert_monitor(app.handle().clone(), pool.clone());
+                // 止损止盈单：交易时段按实时行情检查，触发后模拟账户自动卖出
+                services::stop_orders::spawn_stop_order_monitor(app.handle().clone(), pool.clone());
+                // 节假日日历：后台加载今明两年（接口失败时交易日判断回退内置规则）
+                let calendar = utils::date::HolidayCalendar::global().clone();
+                let year = chrono::Local::now().year();
+                let loading = calendar.clone();
+                tauri::async_runtime::spawn(async move {
+                    api::holidays::load_holiday_calendar(&loading, &[year, year + 1]).await;
+                });
+                app.manage(calendar);
                 app.manage(pool);
             });
             Ok(())
@@ -0,0 +1,303 @@
+//! 截面特征
+//!
+//! 需要多只股票数据才能计算的特征：相对强弱、截面百分位排名与截面 z-score。
+//! 股票池取自 `stock_universe`（未同步时退回本地有足够历史的股票）。
+
+use crate::db::models::HistoricalData;
+use crate::db::repository::{self, UniverseCriteria};
+use crate::error::AppError;
+use crate::services::market_breadth::MARKET_BREADTH_MIN_BARS;
+use crate::utils::canonical_stock_symbol;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use std::collections::BTreeMap;
+
+/// 截面特征的回看窗口（交易日）
+pub const CROSS_SECTION_WINDOW: usize = 20;
+
+/// 可查询的截面特征
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CrossSectionalFeature {
+    /// 相对股票池等权基准的 20 日相对强弱
+    RelativeStrength,
+    /// 20 日收益率（%）
+    Return20d,
+    /// 20 日日收益率标准差（%）
+    Volatility20d,
+    /// 20 日平均换手率（%）
+    TurnoverRate,
+}
+
+/// 个股某截面特征的取值与在股票池中的位置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CrossSectionalFeatureValue {
+    pub stock_code: String,
+    pub feature: CrossSectionalFeature,
+    /// 截面日（个股最新交易日）
+    pub date: String,
+    pub value: f64,
+    /// 百分位排名（0-100，取值越大排名越高）
+    pub percentile_rank: f64,
+    pub z_score: f64,
+    /// 参与截面的股票数（含自身）
+    pub universe_size: usize,
+}
+
+/// 相对强弱 RS = 个股累计收益 / 基准累计收益（以毛收益 ∏(1 + r) 计，避免基准收益接近 0 时失真）。
+///
+/// RS > 1 表示跑赢基准；序列为空或长度不一致时返回 1.0。
+pub fn calculate_relative_strength(stock_returns: &[f64], benchmark_returns: &[f64]) -> f64 {
+    if stock_returns.is_empty() || stock_returns.len() != benchmark_returns.len() {
+        return 1.0;
+    }
+    let growth = |returns: &[f64]| returns.iter().map(|r| 1.0 + r).product::<f64>();
+    let benchmark = growth(benchmark_returns);
+    if benchmark <= 0.0 {
+        return 1.0;
+    }
+    growth(stock_returns) / benchmark
+}
+
+/// 百分位排名（0-100）：低于该值的占比加上相等值的一半；股票池为空时返回 50
+pub fn calculate_percentile_rank(stock_value: f64, universe_values: &[f64]) -> f64 {
+    if universe_values.is_empty() {
+        return 50.0;
+    }
+    let below = universe_values.iter().filter(|&&v| v < stock_value).count() as f64;
+    let equal = universe_values.iter().filter(|&&v| v == stock_value).count() as f64;
+    (below + 0.5 * equal) / universe_values.len() as f64 * 100.0
+}
+
+/// 一组值各自在组内的百分位排名（口径同 [`calculate_percentile_rank`]，排序后二分查找，O(n log n)）
+pub fn percentile_ranks(values: &[f64]) -> Vec<f64> {
+    let mut sorted = values.to_vec();
+    sorted.sort_by(f64::total_cmp);
+    let n = values.len() as f64;
+    values
+        .iter()
+        .map(|&v| {
+            let below = sorted.partition_point(|&x| x < v) as f64;
+            let not_above = sorted.partition_point(|&x| x <= v) as f64;
+            (below + 0.5 * (not_above - below)) / n * 100.0
+        })
+        .collect()
+}
+
+/// 相对股票池的 z-score；股票池为空或无离散度时返回 0
+pub fn calculate_z_score_vs_universe(value: f64, universe: &[f64]) -> f64 {
+    if universe.is_empty() {
+        return 0.0;
+    }
+    let n = universe.len() as f64;
+    let mean = universe.iter().sum::<f64>() / n;
+    let std = (universe.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / n).sqrt();
+    if std > 1e-12 {
+        (value - mean) / std
+    } else {
+        0.0
+    }
+}
+
+/// 时间正序日线的逐日收益（日期取当日）
+fn daily_returns(history: &[HistoricalData]) -> Vec<(NaiveDate, f64)> {
+    history
+        .windows(2)
+        .filter(|pair| pair[0].close > 0.0)
+        .map(|pair| (pair[1].date, pair[1].close / pair[0].close - 1.0))
+        .collect()
+}
+
+/// 单只股票的截面特征原始值；`benchmark` 为股票池逐日等权收益
+fn feature_value(
+    history: &[HistoricalData],
+    feature: CrossSectionalFeature,
+    benchmark: &BTreeMap<NaiveDate, f64>,
+) -> Option<f64> {
+    let window = &history[history.len().saturating_sub(CROSS_SECTION_WINDOW + 1)..];
+    let returns = daily_returns(window);
+    if returns.is_empty() {
+        return None;
+    }
+    let value = match feature {
+        CrossSectionalFeature::RelativeStrength => {
+            let (stock, bench): (Vec<f64>, Vec<f64>) = returns
+                .iter()
+                .filter_map(|(date, r)| benchmark.get(date).map(|b| (*r, *b)))
+                .unzip();
+            calculate_relative_strength(&stock, &bench)
+        }
+        CrossSectionalFeature::Return20d => {
+            (returns.iter().map(|(_, r)| 1.0 + r).product::<f64>() - 1.0) * 100.0
+        }
+        CrossSectionalFeature::Volatility20d => {
+            let n = returns.len() as f64;
+            let mean = returns.iter().map(|(_, r)| r).sum::<f64>() / n;
+            (returns.iter().map(|(_, r)| (r - mean).powi(2)).sum::<f64>() / n).sqrt() * 100.0
+        }
+        CrossSectionalFeature::TurnoverRate => {
+            let recent = &window[1..];
+            recent.iter().map(|bar| bar.turnover_rate).sum::<f64>() / recent.len() as f64
+        }
+    };
+    value.is_finite().then_some(value)
+}
+
+/// 计算个股在股票池截面中的特征位置。
+///
+/// 仅与最新交易日和个股相同的股票比较（停牌或未刷新的不计入）；个股不在 `stocks` 中时返回 None。
+pub fn compute_cross_sectional_feature(
+    stock_code: &str,
+    stocks: &[(String, Vec<HistoricalData>)],
+    feature: CrossSectionalFeature,
+) -> Option<CrossSectionalFeatureValue> {
+    let target = canonical_stock_symbol(stock_code);
+    let (_, target_history) = stocks
+        .iter()
+        .find(|(symbol, _)| canonical_stock_symbol(symbol) == target)?;
+    let date = target_history.last()?.date;
+    let peers: Vec<&[HistoricalData]> = stocks
+        .iter()
+        .filter(|(_, history)| history.last().is_some_and(|bar| bar.date == date))
+        .map(|(_, history)| history.as_slice())
+        .collect();
+
+    let mut sums: BTreeMap<NaiveDate, (f64, usize)> = BTreeMap::new();
+    for history in &peers {
+        let window = &history[history.len().saturating_sub(CROSS_SECTION_WINDOW + 1)..];
+        for (day, r) in daily_returns(window) {
+            let entry = sums.entry(day).or_insert((0.0, 0));
+            entry.0 += r;
+            entry.1 += 1;
+        }
+    }
+    let benchmark: BTreeMap<NaiveDate, f64> = sums
+        .into_iter()
+        .map(|(day, (sum, count))| (day, sum / count as f64))
+        .collect();
+
+    let value = feature_value(target_history, feature, &benchmark)?;
+    let universe: Vec<f64> = peers
+        .iter()
+        .filter_map(|history| feature_value(history, feature, &benchmark))
+        .collect();
+    Some(CrossSectionalFeatureValue {
+        stock_code: target,
+        feature,
+        date: date.format("%Y-%m-%d").to_string(),
+        value,
+        percentile_rank: calculate_percentile_rank(value, &universe),
+        z_score: calculate_z_score_vs_universe(value, &universe),
+        universe_size: universe.len(),
+    })
+}
+
+/// 读取股票池最近行情并计算个股的截面特征
+pub async fn get_cross_sectional_feature(
+    stock_code: &str,
+    feature: CrossSectionalFeature,
+    pool: &SqlitePool,
+) -> Result<Option<CrossSectionalFeatureValue>, AppError> {
+    let criteria = UniverseCriteria {
+        exclude_st: true,
+        ..UniverseCriteria::default()
+    };
+    let mut symbols = repository::filter_universe(pool, &criteria).await?;
+    if symbols.is_empty() {
+        symbols = repository::get_symbols_with_min_bars(MARKET_BREADTH_MIN_BARS, pool).await?;
+    }
+    let target = canonical_stock_symbol(stock_code);
+    if !symbols.iter().any(|symbol| canonical_stock_symbol(symbol) == target) {
+        symbols.push(target);
+    }
+    let stocks =
+        repository::get_recent_historical_data_for_symbols(&symbols, CROSS_SECTION_WINDOW + 1, pool)
+            .await?;
+    Ok(compute_cross_sectional_feature(stock_code, &stocks, feature))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn history(symbol: &str, daily_change_pct: f64, days: usize) -> (String, Vec<HistoricalData>) {
+        let start = NaiveDate::from_ymd_opt(2026, 1, 1).unwrap();
+        let mut close = 10.0;
+        let bars = (0..days)
+            .map(|i| {
+                if i > 0 {
+                    close *= 1.0 + daily_change_pct / 100.0;
+                }
+                HistoricalData {
+                    symbol: symbol.to_string(),
+                    date: start + Duration::days(i as i64),
+                    open: close,
+                    close,
+                    high: close,
+                    low: close,
+                    volume: 1000,
+                    amount: close * 1000.0,
+                    amplitude: 0.0,
+                    turnover_rate: 1.0 + daily_change_pct,
+                    volume_ratio: 1.0,
+                    change_percent: daily_change_pct,
+                    change: 0.0,
+                }
+            })
+            .collect();
+        (symbol.to_string(), bars)
+    }
+
+    #[test]
+    fn test_basic_cross_sectional_statistics() {
+        let rs = calculate_relative_strength(&[0.1, 0.1], &[0.0, 0.0]);
+        assert!((rs - 1.21).abs() < 1e-12);
+        assert_eq!(calculate_relative_strength(&[0.1], &[]), 1.0);
+
+        let universe = [1.0, 2.0, 3.0, 4.0];
+        assert_eq!(calculate_percentile_rank(3.0, &universe), 62.5);
+        assert_eq!(calculate_percentile_rank(5.0, &universe), 100.0);
+        assert_eq!(calculate_percentile_rank(1.0, &[]), 50.0);
+        let ranks = percentile_ranks(&[3.0, 1.0, 3.0]);
+        assert!((ranks[0] - 200.0 / 3.0).abs() < 1e-9);
+        assert!((ranks[1] - 50.0 / 3.0).abs() < 1e-9);
+        assert_eq!(ranks[0], ranks[2]);
+
+        let z = calculate_z_score_vs_universe(4.0, &universe);
+        assert!((z - 1.5 / 1.25f64.sqrt()).abs() < 1e-12);
+        assert_eq!(calculate_z_score_vs_universe(4.0, &[2.0, 2.0]), 0.0);
+    }
+
+    #[test]
+    fn test_compute_cross_sectional_feature_ranks_against_same_day_peers() {
+        let stocks = vec![
+            history("600000", 1.0, 30),
+            history("600001", 0.0, 30),
+            history("600002", -1.0, 30),
+            history("600004", 0.5, 30),
+            // 停牌（最新日不同）不参与截面
+            history("600003", 5.0, 25),
+        ];
+
+        let strong =
+            compute_cross_sectional_feature("600000.SH", &stocks, CrossSectionalFeature::RelativeStrength)
+                .unwrap();
+        assert_eq!(strong.stock_code, "600000");
+        assert_eq!(strong.universe_size, 4);
+        assert_eq!(strong.percentile_rank, 87.5);
+        assert!(strong.value > 1.0);
+        assert!(strong.z_score > 0.0);
+
+        let weak =
+            compute_cross_sectional_feature("600002", &stocks, CrossSectionalFeature::Return20d).unwrap();
+        assert!((weak.value - (0.99f64.powi(20) - 1.0) * 100.0).abs() < 1e-9);
+        assert_eq!(weak.percentile_rank, 12.5);
+
+        assert!(
+            compute_cross_sectional_feature("000001", &stocks, CrossSectionalFeature::TurnoverRate)
+                .is_none()
+        );
+    }
+}
@@ -2,8 +2,8 @@
 
 use super::DivergenceStrength;
 
-/// 寻找局部极值点
-pub(super) fn find_local_extremes(
+/// 寻找局部极值点：返回 (低点, 高点)，元素为 (索引, 值)
+pub(crate) fn find_local_extremes(
     data: &[f64],
     window: usize,
 ) -> (Vec<(usize, f64)>, Vec<(usize, f64)>) {
@@ -33,6 +33,7 @@ pub use detectors::{
 };
 
 use action::generate_divergence_action_enhanced;
+pub(crate) use extremes::find_local_extremes;
 
 /// 背离类型
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -0,0 +1,208 @@
+//! 动态时间规整（DTW）历史形态类比
+//!
+//! 在个股自身历史中寻找与当前价格走势最相似的 K 段窗口，
+//! 用这些窗口之后的真实涨跌幅给出"历史上类似走势之后发生了什么"的共识估计。
+//! 比较前各窗口按首日价格归一化为累计涨跌幅，只比较形态、不比较价位。
+
+use serde::{Deserialize, Serialize};
+
+/// 默认比较窗口长度（交易日）
+pub const DEFAULT_PATTERN_WINDOW: usize = 20;
+/// 默认取最相似的窗口数
+pub const DEFAULT_PATTERN_TOP_K: usize = 5;
+/// 后续收益的最长观察期（交易日）
+const PATTERN_FORWARD_DAYS: usize = 10;
+
+/// 一段相似的历史窗口
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PatternMatch {
+    /// 窗口在历史价格序列中的起始下标
+    pub start_index: usize,
+    pub dtw_distance: f64,
+    /// 窗口结束后 5 个交易日涨跌幅（%）
+    pub subsequent_return_5d: f64,
+    /// 窗口结束后 10 个交易日涨跌幅（%）
+    pub subsequent_return_10d: f64,
+}
+
+/// 相似窗口的后续收益共识（按距离倒数加权）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PatternConsensus {
+    pub matches: Vec<PatternMatch>,
+    /// 加权平均 5 日后续涨跌幅（%）
+    pub expected_return_5d: f64,
+    /// 加权平均 10 日后续涨跌幅（%）
+    pub expected_return_10d: f64,
+    /// 5 日后上涨的窗口占比
+    pub up_ratio_5d: f64,
+}
+
+/// DTW 距离：逐点绝对差，允许时间轴伸缩对齐；任一序列为空时返回无穷大
+pub fn calculate_dtw_distance(a: &[f64], b: &[f64]) -> f64 {
+    if a.is_empty() || b.is_empty() {
+        return f64::INFINITY;
+    }
+
+    // 滚动两行：prev[j] 为 a[..i] 与 b[..j] 的最小累计代价
+    let mut prev = vec![f64::INFINITY; b.len() + 1];
+    let mut curr = vec![f64::INFINITY; b.len() + 1];
+    prev[0] = 0.0;
+    for x in a {
+        curr[0] = f64::INFINITY;
+        for (j, y) in b.iter().enumerate() {
+            let cost = (x - y).abs();
+            curr[j + 1] = cost + prev[j].min(prev[j + 1]).min(curr[j]);
+        }
+        std::mem::swap(&mut prev, &mut curr);
+    }
+    prev[b.len()]
+}
+
+/// 按首日价格归一化为累计涨跌幅（%）
+fn normalize_window(window: &[f64]) -> Vec<f64> {
+    let base = window.first().copied().unwrap_or(0.0);
+    if base <= 0.0 {
+        return vec![0.0; window.len()];
+    }
+    window.iter().map(|p| (p / base - 1.0) * 100.0).collect()
+}
+
+/// 在历史价格中寻找与 `current_window` 最相似的 `top_k` 段长度为 `window_size` 的窗口。
+///
+/// 仅考虑其后仍有 10 个交易日真实数据的窗口；入选窗口之间互不重叠，
+/// 避免同一段行情平移几天后被重复计入。结果按 DTW 距离升序。
+pub fn find_similar_historical_patterns(
+    current_window: &[f64],
+    historical_prices: &[f64],
+    window_size: usize,
+    top_k: usize,
+) -> Vec<PatternMatch> {
+    if window_size < 2
+        || top_k == 0
+        || current_window.len() < window_size
+        || historical_prices.len() < window_size + PATTERN_FORWARD_DAYS
+    {
+        return Vec::new();
+    }
+
+    let target = normalize_window(&current_window[current_window.len() - window_size..]);
+    let last_start = historical_prices.len() - window_size - PATTERN_FORWARD_DAYS;
+    let mut candidates: Vec<PatternMatch> = (0..=last_start)
+        .filter_map(|start| {
+            let end = start + window_size - 1;
+            let base = historical_prices[end];
+            if base <= 0.0 {
+                return None;
+            }
+            let window = normalize_window(&historical_prices[start..=end]);
+            let forward = |days: usize| (historical_prices[end + days] / base - 1.0) * 100.0;
+            Some(PatternMatch {
+                start_index: start,
+                dtw_distance: calculate_dtw_distance(&target, &window),
+                subsequent_return_5d: forward(5),
+                subsequent_return_10d: forward(PATTERN_FORWARD_DAYS),
+            })
+        })
+        .collect();
+    candidates.sort_by(|a, b| a.dtw_distance.total_cmp(&b.dtw_distance));
+
+    let mut selected: Vec<PatternMatch> = Vec::with_capacity(top_k);
+    for candidate in candidates {
+        let overlaps = selected
+            .iter()
+            .any(|m| m.start_index.abs_diff(candidate.start_index) < window_size);
+        if !overlaps {
+            selected.push(candidate);
+            if selected.len() == top_k {
+                break;
+            }
+        }
+    }
+    selected
+}
+
+/// 汇总相似窗口的后续收益；无匹配时返回 None
+pub fn pattern_consensus(matches: Vec<PatternMatch>) -> Option<PatternConsensus> {
+    if matches.is_empty() {
+        return None;
+    }
+    let weights: Vec<f64> = matches.iter().map(|m| 1.0 / (1.0 + m.dtw_distance)).collect();
+    let total_weight: f64 = weights.iter().sum();
+    let weighted = |value: fn(&PatternMatch) -> f64| {
+        matches
+            .iter()
+            .zip(&weights)
+            .map(|(m, w)| value(m) * w)
+            .sum::<f64>()
+            / total_weight
+    };
+    let expected_return_5d = weighted(|m| m.subsequent_return_5d);
+    let expected_return_10d = weighted(|m| m.subsequent_return_10d);
+    let up_ratio_5d = matches
+        .iter()
+        .filter(|m| m.subsequent_return_5d > 0.0)
+        .count() as f64
+        / matches.len() as f64;
+
+    Some(PatternConsensus {
+        matches,
+        expected_return_5d,
+        expected_return_10d,
+        up_ratio_5d,
+    })
+}
+
+/// 用最近 `window_size` 根收盘价在全部历史中做形态类比（当前窗口自身不参与匹配）
+pub fn analyze_pattern_analogy(
+    prices: &[f64],
+    window_size: usize,
+    top_k: usize,
+) -> Option<PatternConsensus> {
+    let current = prices.get(prices.len().checked_sub(window_size)?..)?;
+    let history = &prices[..prices.len() - window_size];
+    pattern_consensus(find_similar_historical_patterns(current, history, window_size, top_k))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dtw_distance_handles_time_shift() {
+        let a = [0.0, 1.0, 2.0, 3.0, 2.0, 1.0];
+        let shifted = [0.0, 0.0, 1.0, 2.0, 3.0, 2.0, 1.0];
+        let flat = [0.0; 6];
+
+        assert_eq!(calculate_dtw_distance(&a, &a), 0.0);
+        assert_eq!(calculate_dtw_distance(&a, &shifted), 0.0);
+        assert!(calculate_dtw_distance(&a, &flat) > 0.0);
+        assert!(calculate_dtw_distance(&a, &[]).is_infinite());
+    }
+
+    #[test]
+    fn test_finds_repeated_pattern_and_its_forward_return() {
+        // 重复出现的"下跌 5 日后反弹"形态，序列以又一段 5 日下跌结尾
+        let cycle: Vec<f64> = (0..5)
+            .map(|i| 10.0 - i as f64 * 0.2)
+            .chain((0..10).map(|i| 9.2 + i as f64 * 0.1))
+            .collect();
+        let prices: Vec<f64> = cycle.iter().cycle().take(cycle.len() * 5 + 5).copied().collect();
+
+        let consensus = analyze_pattern_analogy(&prices, 5, 3).expect("应找到相似窗口");
+
+        assert_eq!(consensus.matches.len(), 3);
+        assert!(consensus.matches[0].dtw_distance < 1e-9);
+        assert!(consensus.expected_return_5d > 0.0);
+        assert_eq!(consensus.up_ratio_5d, 1.0);
+        for pair in consensus.matches.windows(2) {
+            assert!(pair[0].start_index.abs_diff(pair[1].start_index) >= 5);
+        }
+    }
+
+    #[test]
+    fn test_insufficient_history_yields_no_matches() {
+        let prices = vec![10.0; 20];
+        assert!(find_similar_historical_patterns(&prices, &prices, 20, 5).is_empty());
+        assert!(pattern_consensus(Vec::new()).is_none());
+    }
+}
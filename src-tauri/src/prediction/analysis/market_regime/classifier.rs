@@ -1,6 +1,6 @@
 //! 转折点检测与市场状态判定
 
-use super::{MarketRegime, VolatilityLevel};
+use super::{interpret_hurst, HurstRegime, MarketRegime, VolatilityLevel};
 use crate::prediction::indicators::{macd, rsi};
 
 /// 检测潜在转折点
@@ -59,6 +59,7 @@ pub(super) fn determine_regime(
     is_potential_top: bool,
     is_potential_bottom: bool,
     volatility_level: VolatilityLevel,
+    hurst_regime: HurstRegime,
 ) -> (MarketRegime, f64) {
     // 优先检测转折点
     if is_potential_top && adx > 25.0 {
@@ -68,9 +69,15 @@ pub(super) fn determine_regime(
         return (MarketRegime::PotentialBottom, 0.70);
     }
 
-    // 趋势强度判断
-    let is_strong_trend = adx > 35.0;
-    let is_moderate_trend = adx > 20.0;
+    // 趋势强度判断：Hurst 指数为首要依据，持续性序列放宽 ADX 门槛，
+    // 反持续性序列只认强趋势（中等 ADX 多为回归中的摆动）
+    let (strong_adx, moderate_adx) = match hurst_regime {
+        HurstRegime::Trending => (30.0, 15.0),
+        HurstRegime::RangeBound => (35.0, 20.0),
+        HurstRegime::MeanReverting => (35.0, 35.0),
+    };
+    let is_strong_trend = adx > strong_adx;
+    let is_moderate_trend = adx > moderate_adx;
 
     // 方向判断
     let is_bullish = ma_alignment > 0.3 && momentum > 0.1;
@@ -100,38 +107,24 @@ pub(super) fn determine_regime(
         VolatilityLevel::VeryLow => 0.95, // 极低波动率可能预示突破
     };
 
-    let confidence = (base_confidence * volatility_factor).clamp(0.3_f64, 0.95_f64);
-
-    (regime, confidence)
-}
-
-/// 计算趋势强度值
-pub(super) fn calculate_trend_strength_value(
-    ma_alignment: f64,
-    adx: f64,
-    momentum: f64,
-    regime: &MarketRegime,
-) -> f64 {
-    let direction = if regime.is_bullish() {
-        1.0
-    } else if regime.is_bearish() {
-        -1.0
+    // Hurst 与判定结果一致（趋势市持续性 / 震荡市非持续性）时提高置信度
+    let hurst_agrees = if regime.is_trending() {
+        hurst_regime == HurstRegime::Trending
     } else {
-        0.0
+        hurst_regime != HurstRegime::Trending
     };
+    let hurst_factor = if hurst_agrees { 1.05 } else { 1.0 };
 
-    let strength = (ma_alignment.abs() * 0.40
-        + (adx / 50.0).min(1.0) * 0.35
-        + momentum.abs() * 0.25)
-        .min(1.0);
+    let confidence = (base_confidence * volatility_factor * hurst_factor).clamp(0.3_f64, 0.95_f64);
 
-    direction * strength
+    (regime, confidence)
 }
 
 /// 生成状态描述
 pub(super) fn generate_regime_description(
     regime: &MarketRegime,
     adx: f64,
+    hurst: f64,
     volatility: VolatilityLevel,
     ma_alignment: f64,
 ) -> String {
@@ -156,10 +149,12 @@ pub(super) fn generate_regime_description(
     };
 
     format!(
-        "{} | {} | ADX={:.1} | 波动率:{} | {}",
+        "{} | {} | ADX={:.1} | Hurst={:.2}({}) | 波动率:{} | {}",
         regime.to_string(),
         trend_desc,
         adx,
+        hurst,
+        interpret_hurst(hurst).label(),
         volatility.to_string(),
         ma_desc
     )
@@ -0,0 +1,161 @@
+//! Hurst 指数（重标极差 R/S 分析）
+//!
+//! 衡量价格序列的"记忆"：H > 0.5 为持续性（趋势延续），H ≈ 0.5 接近随机游走，
+//! H < 0.5 为反持续性（均值回归）。按对数等距的块长 n 把对数收益率切成不重叠的块，
+//! 取各块 R/S 的均值。短块下独立序列的 R/S 本身偏高，直接回归会把随机游走误判为趋势，
+//! 因此按 Anis-Lloyd 修正：H = 0.5 + [log(R/S) - log E(R/S)] 对 log(n) 回归的斜率。
+
+use serde::{Deserialize, Serialize};
+
+/// 市场状态分类使用的价格窗口（交易日）：窗口过短时估计噪声大，随机游走易被误判
+pub const HURST_LOOKBACK: usize = 250;
+/// 默认最大块长
+pub const HURST_MAX_LAG: usize = 60;
+/// 最小块长：过短的块 R/S 偏差过大
+const HURST_MIN_LAG: usize = 8;
+/// 相邻块长的倍数
+const HURST_LAG_GROWTH: f64 = 1.5;
+/// 高于该值视为趋势性
+const HURST_TRENDING_THRESHOLD: f64 = 0.6;
+/// 低于该值视为均值回归
+const HURST_MEAN_REVERTING_THRESHOLD: f64 = 0.45;
+
+/// 按 Hurst 指数划分的价格记忆类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HurstRegime {
+    /// 持续性：趋势倾向延续
+    Trending,
+    /// 接近随机游走：区间波动
+    RangeBound,
+    /// 反持续性：偏离倾向回归
+    MeanReverting,
+}
+
+impl HurstRegime {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Trending => "趋势性",
+            Self::RangeBound => "随机游走",
+            Self::MeanReverting => "均值回归",
+        }
+    }
+}
+
+/// 单块的重标极差：累计离差的极差 / 标准差；无波动时为 None
+fn rescaled_range(chunk: &[f64]) -> Option<f64> {
+    let n = chunk.len() as f64;
+    let mean = chunk.iter().sum::<f64>() / n;
+    let std = (chunk.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / n).sqrt();
+    if std <= 1e-12 {
+        return None;
+    }
+    let (mut cumulative, mut max, mut min) = (0.0f64, f64::NEG_INFINITY, f64::INFINITY);
+    for x in chunk {
+        cumulative += x - mean;
+        max = max.max(cumulative);
+        min = min.min(cumulative);
+    }
+    Some((max - min) / std)
+}
+
+/// 独立同分布正态序列在块长 n 下的 R/S 期望（Anis-Lloyd）
+fn expected_rescaled_range(n: usize) -> f64 {
+    // Γ((n-1)/2) / Γ(n/2)，由 Γ(x+1) = xΓ(x) 从 n = 2 或 3 递推
+    let mut gamma_ratio = if n.is_multiple_of(2) {
+        std::f64::consts::PI.sqrt()
+    } else {
+        2.0 / std::f64::consts::PI.sqrt()
+    };
+    let mut k = 2 + n % 2;
+    while k < n {
+        gamma_ratio *= (k as f64 - 1.0) / k as f64;
+        k += 2;
+    }
+    let sum: f64 = (1..n).map(|i| ((n - i) as f64 / i as f64).sqrt()).sum();
+    gamma_ratio / std::f64::consts::PI.sqrt() * sum
+}
+
+/// 以修正 R/S 分析估计 Hurst 指数（0-1）。
+///
+/// 块长从 8 起按 1.5 倍递增，不超过 `max_lag` 与收益率个数的一半；
+/// 可用块长不足两个（数据过短或价格无波动）时返回 0.5。
+pub fn calculate_hurst_exponent(prices: &[f64], max_lag: usize) -> f64 {
+    let returns: Vec<f64> = prices
+        .windows(2)
+        .filter(|pair| pair[0] > 0.0 && pair[1] > 0.0)
+        .map(|pair| (pair[1] / pair[0]).ln())
+        .collect();
+    let max_lag = max_lag.min(returns.len() / 2);
+
+    let mut points: Vec<(f64, f64)> = Vec::new();
+    let mut lag = HURST_MIN_LAG as f64;
+    while lag.round() as usize <= max_lag {
+        let size = lag.round() as usize;
+        lag *= HURST_LAG_GROWTH;
+        let ranges: Vec<f64> = returns.chunks_exact(size).filter_map(rescaled_range).collect();
+        if ranges.is_empty() {
+            continue;
+        }
+        let mean_rs = ranges.iter().sum::<f64>() / ranges.len() as f64;
+        let excess = mean_rs.ln() - expected_rescaled_range(size).ln();
+        points.push(((size as f64).ln(), excess));
+    }
+    if points.len() < 2 {
+        return 0.5;
+    }
+
+    let n = points.len() as f64;
+    let mean_x = points.iter().map(|(x, _)| x).sum::<f64>() / n;
+    let mean_y = points.iter().map(|(_, y)| y).sum::<f64>() / n;
+    let covariance: f64 = points.iter().map(|(x, y)| (x - mean_x) * (y - mean_y)).sum();
+    let variance: f64 = points.iter().map(|(x, _)| (x - mean_x).powi(2)).sum();
+    (0.5 + covariance / variance).clamp(0.0, 1.0)
+}
+
+/// 按阈值解读 Hurst 指数：> 0.6 趋势性，0.45-0.6 随机游走，< 0.45 均值回归
+pub fn interpret_hurst(h: f64) -> HurstRegime {
+    if h > HURST_TRENDING_THRESHOLD {
+        HurstRegime::Trending
+    } else if h >= HURST_MEAN_REVERTING_THRESHOLD {
+        HurstRegime::RangeBound
+    } else {
+        HurstRegime::MeanReverting
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 由收益率序列生成价格
+    fn prices_from_returns(returns: impl Iterator<Item = f64>) -> Vec<f64> {
+        let mut price = 100.0;
+        let mut prices = vec![price];
+        for r in returns {
+            price *= r.exp();
+            prices.push(price);
+        }
+        prices
+    }
+
+    #[test]
+    fn test_hurst_exponent_separates_memory_types() {
+        // 收益率缓慢起伏（强持续性）
+        let persistent = prices_from_returns((0..240).map(|i| 0.01 * (i as f64 / 40.0).sin()));
+        let h = calculate_hurst_exponent(&persistent, HURST_MAX_LAG);
+        assert!(h > 0.6, "persistent H = {h}");
+        assert_eq!(interpret_hurst(h), HurstRegime::Trending);
+
+        // 过度差分的噪声 r_t = e_t - e_{t-1}（强反持续性）
+        let noise = |i: usize| ((i * 7919) % 101) as f64 / 5000.0 - 0.01;
+        let reverting = prices_from_returns((1..241).map(|i| noise(i) - noise(i - 1)));
+        let h = calculate_hurst_exponent(&reverting, HURST_MAX_LAG);
+        assert!(h < 0.45, "reverting H = {h}");
+        assert_eq!(interpret_hurst(h), HurstRegime::MeanReverting);
+
+        // 数据不足或无波动时返回 0.5
+        assert_eq!(calculate_hurst_exponent(&persistent[..20], HURST_MAX_LAG), 0.5);
+        assert_eq!(calculate_hurst_exponent(&[10.0; 200], HURST_MAX_LAG), 0.5);
+        assert_eq!(interpret_hurst(0.5), HurstRegime::RangeBound);
+    }
+}
@@ -1,6 +1,6 @@
 //! 市场状态分类所用的指标：均线、ADX、动量
 
-use crate::prediction::indicators::rsi;
+use crate::prediction::indicators::{calculate_dmi, rsi};
 
 /// 计算简单移动平均
 pub(super) fn calculate_ma(prices: &[f64], period: usize) -> f64 {
@@ -59,57 +59,17 @@ pub(super) fn calculate_ma_alignment_score(
     score.clamp(-1.0, 1.0)
 }
 
-/// 计算ADX（简化版本）
-pub(super) fn calculate_adx(highs: &[f64], lows: &[f64], prices: &[f64], period: usize) -> f64 {
-    if highs.len() < period + 1 || lows.len() < period + 1 || prices.len() < period + 1 {
-        return 25.0; // 默认中性值
-    }
-
-    let len = prices.len();
-    let mut plus_dm_sum = 0.0;
-    let mut minus_dm_sum = 0.0;
-    let mut tr_sum = 0.0;
-
-    for i in (len - period)..len {
-        if i == 0 {
-            continue;
-        }
-
-        let high_diff = highs[i] - highs[i - 1];
-        let low_diff = lows[i - 1] - lows[i];
-
-        // +DM
-        if high_diff > low_diff && high_diff > 0.0 {
-            plus_dm_sum += high_diff;
-        }
-        // -DM
-        if low_diff > high_diff && low_diff > 0.0 {
-            minus_dm_sum += low_diff;
-        }
-
-        // True Range
-        let tr = (highs[i] - lows[i])
-            .max((highs[i] - prices[i - 1]).abs())
-            .max((lows[i] - prices[i - 1]).abs());
-        tr_sum += tr;
-    }
-
-    if tr_sum == 0.0 {
-        return 25.0;
-    }
-
-    let plus_di = (plus_dm_sum / tr_sum) * 100.0;
-    let minus_di = (minus_dm_sum / tr_sum) * 100.0;
+/// 数据不足或无方向性波动时的中性 ADX
+const NEUTRAL_ADX: f64 = 25.0;
 
-    let di_sum = plus_di + minus_di;
-    if di_sum == 0.0 {
-        return 25.0;
+/// 计算ADX（见 `indicators::dmi`），无法计算时返回中性值
+pub(super) fn calculate_adx(highs: &[f64], lows: &[f64], prices: &[f64], period: usize) -> f64 {
+    let (di_plus, di_minus, adx, _) = calculate_dmi(highs, lows, prices, period);
+    if di_plus + di_minus > 0.0 {
+        adx
+    } else {
+        NEUTRAL_ADX
     }
-
-    let dx = ((plus_di - minus_di).abs() / di_sum) * 100.0;
-
-    // 返回DX作为ADX的近似值
-    dx.clamp(0.0, 100.0)
 }
 
 /// 计算动量得分
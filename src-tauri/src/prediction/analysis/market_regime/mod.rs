@@ -11,26 +11,28 @@
 //! - [`indicators`]：均线/ADX/动量等指标
 //! - [`volatility`]：波动率及其百分位、收敛
 //! - [`classifier`]：转折点检测与状态判定
+//! - [`hurst`]：Hurst 指数（价格记忆），与 ADX 共同作为趋势/震荡的首要判据
 
-use crate::prediction::indicators::bollinger;
+use crate::prediction::indicators::{bollinger, calculate_trend_strength_index};
 use serde::{Deserialize, Serialize};
 
 mod classifier;
+mod hurst;
 mod indicators;
 mod volatility;
 
-use classifier::{
-    calculate_trend_strength_value, detect_turning_points, determine_regime,
-    generate_regime_description,
-};
+pub use hurst::{calculate_hurst_exponent, interpret_hurst, HurstRegime, HURST_LOOKBACK, HURST_MAX_LAG};
+pub use volatility::calculate_atr_ratio;
+
+use classifier::{detect_turning_points, determine_regime, generate_regime_description};
 use indicators::{calculate_adx, calculate_ma, calculate_ma_alignment_score, calculate_momentum_score};
 use volatility::{
-    calculate_volatility, calculate_volatility_contraction, calculate_volatility_percentile,
-    classify_volatility_level,
+    adjust_volatility_level_by_atr, calculate_volatility,
+    calculate_volatility_contraction, calculate_volatility_percentile, classify_volatility_level,
 };
 
 /// 市场状态
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum MarketRegime {
     /// 强势上涨趋势
     StrongUptrend,
@@ -125,6 +127,9 @@ pub struct MarketRegimeAnalysis {
     pub volatility_percentile: f64,
     /// ADX趋势强度指标
     pub adx_value: f64,
+    /// Hurst 指数（> 0.6 趋势性，< 0.45 均值回归）
+    #[serde(default = "neutral_hurst")]
+    pub hurst_exponent: f64,
     /// 布林带宽度 (波动率指标)
     pub bollinger_width: f64,
     /// 市场阶段描述
@@ -219,11 +224,20 @@ pub fn classify_market_regime(prices: &[f64], highs: &[f64], lows: &[f64]) -> Ma
     // 7. 计算波动率及其百分位
     let current_volatility = calculate_volatility(prices, 20);
     let volatility_percentile = calculate_volatility_percentile(prices, current_volatility);
-    let volatility_level = classify_volatility_level(volatility_percentile);
+    // ATR 扩张/收敛修正：与 90 日均值比较识别高波动，与 20 日均值比较识别区间收敛
+    let volatility_level = adjust_volatility_level_by_atr(
+        classify_volatility_level(volatility_percentile),
+        calculate_atr_ratio(highs, lows, prices, 20),
+        calculate_atr_ratio(highs, lows, prices, 90),
+    );
 
     // 8. 波动率收敛检测（用于预测突破）
     let volatility_contraction_score = calculate_volatility_contraction(prices, 20);
 
+    // 8.5 Hurst 指数：价格记忆决定 ADX 趋势门槛
+    let hurst_exponent =
+        calculate_hurst_exponent(&prices[len.saturating_sub(HURST_LOOKBACK)..], HURST_MAX_LAG);
+
     // 9. 检测潜在转折点
     let (is_potential_top, is_potential_bottom) =
         detect_turning_points(prices, highs, lows, &ma_alignment_score, momentum_score);
@@ -236,15 +250,27 @@ pub fn classify_market_regime(prices: &[f64], highs: &[f64], lows: &[f64]) -> Ma
         is_potential_top,
         is_potential_bottom,
         volatility_level,
+        interpret_hurst(hurst_exponent),
     );
 
-    // 11. 计算趋势强度
-    let trend_strength =
-        calculate_trend_strength_value(ma_alignment_score, adx_value, momentum_score, &regime);
+    // 11. 趋势强度：趋势强度指数的强度，方向取自市场状态
+    let direction = if regime.is_bullish() {
+        1.0
+    } else if regime.is_bearish() {
+        -1.0
+    } else {
+        0.0
+    };
+    let trend_strength = direction * calculate_trend_strength_index(prices, highs, lows).score;
 
     // 12. 生成描述
-    let description =
-        generate_regime_description(&regime, adx_value, volatility_level, ma_alignment_score);
+    let description = generate_regime_description(
+        &regime,
+        adx_value,
+        hurst_exponent,
+        volatility_level,
+        ma_alignment_score,
+    );
 
     let indicator_scores = RegimeIndicatorScores {
         ma_alignment_score,
@@ -261,6 +287,7 @@ pub fn classify_market_regime(prices: &[f64], highs: &[f64], lows: &[f64]) -> Ma
         volatility_level,
         volatility_percentile,
         adx_value,
+        hurst_exponent,
         bollinger_width,
         description,
         recommended_strategy: regime.recommended_strategy(),
@@ -277,6 +304,7 @@ fn default_regime_analysis() -> MarketRegimeAnalysis {
         volatility_level: VolatilityLevel::Normal,
         volatility_percentile: 50.0,
         adx_value: 25.0,
+        hurst_exponent: neutral_hurst(),
         bollinger_width: 0.04,
         description: "数据不足，无法准确判断市场状态".to_string(),
         recommended_strategy: StrategyType::MeanReversion,
@@ -289,3 +317,8 @@ fn default_regime_analysis() -> MarketRegimeAnalysis {
         },
     }
 }
+
+/// 未计算 Hurst 指数时视为随机游走
+fn neutral_hurst() -> f64 {
+    0.5
+}
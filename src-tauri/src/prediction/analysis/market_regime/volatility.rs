@@ -2,6 +2,13 @@
 
 use super::VolatilityLevel;
 
+/// ATR 计算周期
+const ATR_PERIOD: usize = 14;
+/// 当前 ATR 超过 90 日均值该倍数视为高波动
+const ATR_EXPANSION_RATIO: f64 = 2.0;
+/// 当前 ATR 低于 20 日均值该倍数视为区间收敛
+const ATR_CONTRACTION_RATIO: f64 = 0.5;
+
 /// 计算波动率
 pub(super) fn calculate_volatility(prices: &[f64], period: usize) -> f64 {
     if prices.len() < period + 1 {
@@ -86,3 +93,91 @@ pub(super) fn calculate_volatility_contraction(prices: &[f64], period: usize) ->
     let contraction_ratio = 1.0 - (current_vol / prev_vol);
     contraction_ratio.clamp(0.0, 1.0)
 }
+
+/// 当前 ATR(14) 与近 `window` 日 ATR 均值之比；数据不足返回 None
+pub fn calculate_atr_ratio(
+    highs: &[f64],
+    lows: &[f64],
+    closes: &[f64],
+    window: usize,
+) -> Option<f64> {
+    let len = highs.len().min(lows.len()).min(closes.len());
+    if window == 0 || len < ATR_PERIOD + window {
+        return None;
+    }
+
+    let trs: Vec<f64> = (1..len)
+        .map(|i| {
+            (highs[i] - lows[i])
+                .max((highs[i] - closes[i - 1]).abs())
+                .max((lows[i] - closes[i - 1]).abs())
+        })
+        .collect();
+    let atrs: Vec<f64> = trs
+        .windows(ATR_PERIOD)
+        .map(|w| w.iter().sum::<f64>() / ATR_PERIOD as f64)
+        .collect();
+    if atrs.len() < window {
+        return None;
+    }
+
+    let mean = atrs[atrs.len() - window..].iter().sum::<f64>() / window as f64;
+    (mean > 0.0).then(|| atrs[atrs.len() - 1] / mean)
+}
+
+/// 用 ATR 扩张/收敛修正百分位得出的波动率水平：
+/// ATR 超过 90 日均值 2 倍直接判为极高；低于 20 日均值一半则最多判为低波动（区间收敛）
+pub(super) fn adjust_volatility_level_by_atr(
+    level: VolatilityLevel,
+    atr_ratio_20: Option<f64>,
+    atr_ratio_90: Option<f64>,
+) -> VolatilityLevel {
+    if atr_ratio_90.is_some_and(|r| r > ATR_EXPANSION_RATIO) {
+        return VolatilityLevel::VeryHigh;
+    }
+    if atr_ratio_20.is_some_and(|r| r < ATR_CONTRACTION_RATIO) {
+        return match level {
+            VolatilityLevel::VeryLow => VolatilityLevel::VeryLow,
+            _ => VolatilityLevel::Low,
+        };
+    }
+    level
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bars(ranges: &[f64]) -> (Vec<f64>, Vec<f64>, Vec<f64>) {
+        let closes = vec![10.0; ranges.len()];
+        let highs = ranges.iter().map(|r| 10.0 + r / 2.0).collect();
+        let lows = ranges.iter().map(|r| 10.0 - r / 2.0).collect();
+        (highs, lows, closes)
+    }
+
+    #[test]
+    fn test_atr_ratio_detects_expansion() {
+        let mut ranges = vec![0.2; 120];
+        ranges.extend(vec![2.0; 14]);
+        let (highs, lows, closes) = bars(&ranges);
+        let ratio_90 = calculate_atr_ratio(&highs, &lows, &closes, 90).unwrap();
+        assert!(ratio_90 > ATR_EXPANSION_RATIO);
+        assert_eq!(
+            adjust_volatility_level_by_atr(VolatilityLevel::Normal, None, Some(ratio_90)),
+            VolatilityLevel::VeryHigh
+        );
+        assert!(calculate_atr_ratio(&highs[..50], &lows[..50], &closes[..50], 90).is_none());
+    }
+
+    #[test]
+    fn test_atr_contraction_caps_level() {
+        assert_eq!(
+            adjust_volatility_level_by_atr(VolatilityLevel::High, Some(0.4), Some(1.0)),
+            VolatilityLevel::Low
+        );
+        assert_eq!(
+            adjust_volatility_level_by_atr(VolatilityLevel::High, Some(0.8), Some(1.0)),
+            VolatilityLevel::High
+        );
+    }
+}
@@ -4,7 +4,9 @@
 
 pub mod trend;
 pub mod volume;
+pub mod volume_anomaly;
 pub mod pattern;
+pub mod renko;
 pub mod support_resistance;
 pub mod market_regime;
 pub mod divergence;
@@ -12,10 +14,14 @@ pub mod signal_confirmation;
 pub mod volatility_forecast;
 pub mod prediction_interval;
 pub mod risk_warning;
+pub mod stock_signals;
+pub mod dtw;
 
 pub use trend::*;
 pub use volume::*;
+pub use volume_anomaly::*;
 pub use pattern::*;
+pub use renko::*;
 pub use support_resistance::*;
 pub use market_regime::*;
 pub use divergence::*;
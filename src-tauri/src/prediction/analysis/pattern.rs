@@ -1,5 +1,7 @@
 //! K线形态分析模块
 
+use crate::prediction::analysis::support_resistance::SupportResistance;
+use crate::prediction::indicators::TradingSignal;
 use serde::{Deserialize, Serialize};
 
 /// K线形态类型
@@ -22,6 +24,10 @@ pub enum PatternType {
     // 中性形态
     Doji,                // 十字星
     SpinningTop,         // 纺锤线
+
+    // 平均K线（Heikin-Ashi）形态
+    HeikinAshiStrongBullish, // 平均K线无下影阳线
+    HeikinAshiStrongBearish, // 平均K线无上影阴线
 }
 
 impl PatternType {
@@ -39,20 +45,22 @@ impl PatternType {
             Self::ThreeBlackCrows => "三只乌鸦".to_string(),
             Self::Doji => "十字星".to_string(),
             Self::SpinningTop => "纺锤线".to_string(),
+            Self::HeikinAshiStrongBullish => "平均K线强势阳线".to_string(),
+            Self::HeikinAshiStrongBearish => "平均K线强势阴线".to_string(),
         }
     }
     
     pub fn is_bullish(&self) -> bool {
         matches!(self, 
             Self::Hammer | Self::InvertedHammer | Self::BullishEngulfing |
-            Self::MorningStar | Self::ThreeWhiteSoldiers
+            Self::MorningStar | Self::ThreeWhiteSoldiers | Self::HeikinAshiStrongBullish
         )
     }
     
     pub fn is_bearish(&self) -> bool {
         matches!(self,
             Self::HangingMan | Self::ShootingStar | Self::BearishEngulfing |
-            Self::EveningStar | Self::ThreeBlackCrows
+            Self::EveningStar | Self::ThreeBlackCrows | Self::HeikinAshiStrongBearish
         )
     }
 }
@@ -64,6 +72,10 @@ pub struct PatternRecognition {
     pub is_bullish: bool,
     pub reliability: f64,
     pub description: String,
+    /// 形态确认度 (0-1)：放量、次日K线同向、位于关键位附近与多周期同向各自加分，
+    /// 见 [`score_pattern_confirmation`]；未评估时为 0
+    #[serde(default)]
+    pub confirmation_score: f64,
 }
 
 /// 识别K线形态
@@ -103,9 +115,221 @@ pub fn recognize_patterns(
         }
     }
     
+    // 平均K线趋势形态（基于完整序列平滑）
+    let ha_candles = convert_to_heikin_ashi(opens, highs, lows, closes);
+    if let Some(pattern) = heikin_ashi_pattern(&classify_heikin_ashi_signal(&ha_candles)) {
+        patterns.push(pattern);
+    }
+    
     patterns
 }
 
+/// 形态日成交量超过前 N 日均量的该倍数视为放量确认
+const CONFIRMATION_VOLUME_RATIO: f64 = 1.5;
+/// 放量确认的均量周期
+const CONFIRMATION_VOLUME_PERIOD: usize = 20;
+/// 形态日收盘价距关键支撑/压力位不超过该比例视为位于关键位附近
+const KEY_LEVEL_PROXIMITY: f64 = 0.02;
+/// 各确认条件的加分：放量、次日K线同向、关键位附近、多周期同向
+const VOLUME_CONFIRMATION_SCORE: f64 = 0.3;
+const NEXT_CANDLE_CONFIRMATION_SCORE: f64 = 0.3;
+const KEY_LEVEL_CONFIRMATION_SCORE: f64 = 0.2;
+const TIMEFRAME_CONFIRMATION_SCORE: f64 = 0.2;
+
+/// 形态确认所需的行情上下文
+pub struct PatternConfirmationContext<'a> {
+    pub closes: &'a [f64],
+    pub volumes: &'a [i64],
+    /// 形态完成当日（形态最后一根K线）的索引
+    pub pattern_index: usize,
+    pub support_resistance: &'a SupportResistance,
+    /// 多周期共振方向（"看涨" / "看跌"），未知时为 None
+    pub multi_timeframe_direction: Option<&'a str>,
+}
+
+/// 计算形态确认度：
+/// 形态日放量超过前 20 日均量 50%（+0.3）；次日K线收盘与形态同向（+0.3）；
+/// 看涨形态靠近支撑 / 看跌形态靠近压力（+0.2）；多周期共振方向与形态同向（+0.2）。
+///
+/// 无法检查的条件（形态在最新K线上完成时无次日K线、均量窗口不足、多周期方向未知）不计入分母，
+/// 得分按可检查条件的满分重新缩放到 0 ~ 1。
+pub fn score_pattern_confirmation(
+    pattern: &PatternRecognition,
+    ctx: &PatternConfirmationContext<'_>,
+) -> f64 {
+    let i = ctx.pattern_index;
+    let Some(&close) = ctx.closes.get(i) else {
+        return 0.0;
+    };
+    let mut earned = 0.0;
+    let mut available = 0.0;
+    let mut check = |weight: f64, passed: Option<bool>| {
+        if let Some(passed) = passed {
+            available += weight;
+            if passed {
+                earned += weight;
+            }
+        }
+    };
+
+    let volume_confirmed = (i >= CONFIRMATION_VOLUME_PERIOD && i < ctx.volumes.len()).then(|| {
+        let window = &ctx.volumes[i - CONFIRMATION_VOLUME_PERIOD..i];
+        let avg = window.iter().sum::<i64>() as f64 / CONFIRMATION_VOLUME_PERIOD as f64;
+        avg > 0.0 && ctx.volumes[i] as f64 > avg * CONFIRMATION_VOLUME_RATIO
+    });
+    check(VOLUME_CONFIRMATION_SCORE, volume_confirmed);
+
+    let next_confirmed = ctx
+        .closes
+        .get(i + 1)
+        .map(|&next| (next > close) == pattern.is_bullish && next != close);
+    check(NEXT_CANDLE_CONFIRMATION_SCORE, next_confirmed);
+
+    let key_levels = if pattern.is_bullish {
+        &ctx.support_resistance.support_levels
+    } else {
+        &ctx.support_resistance.resistance_levels
+    };
+    let near_key_level = close > 0.0
+        && key_levels
+            .iter()
+            .any(|level| ((close - level) / close).abs() <= KEY_LEVEL_PROXIMITY);
+    check(KEY_LEVEL_CONFIRMATION_SCORE, Some(near_key_level));
+
+    let aligned_direction = if pattern.is_bullish { "看涨" } else { "看跌" };
+    let timeframe_confirmed = ctx
+        .multi_timeframe_direction
+        .map(|direction| direction == aligned_direction);
+    check(TIMEFRAME_CONFIRMATION_SCORE, timeframe_confirmed);
+
+    if available > 0.0 {
+        (earned / available).min(1.0)
+    } else {
+        0.0
+    }
+}
+
+/// 为同一形态日识别出的全部形态填入确认度
+pub fn apply_pattern_confirmation(
+    patterns: &mut [PatternRecognition],
+    ctx: &PatternConfirmationContext<'_>,
+) {
+    for pattern in patterns {
+        pattern.confirmation_score = score_pattern_confirmation(pattern, ctx);
+    }
+}
+
+/// 筛选确认度不低于 `min_score` 的形态
+pub fn require_confirmation_score(
+    patterns: &[PatternRecognition],
+    min_score: f64,
+) -> Vec<&PatternRecognition> {
+    patterns
+        .iter()
+        .filter(|pattern| pattern.confirmation_score >= min_score)
+        .collect()
+}
+
+/// 平均K线（Heikin-Ashi）
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct HeikinAshiCandle {
+    pub ha_open: f64,
+    pub ha_high: f64,
+    pub ha_low: f64,
+    pub ha_close: f64,
+}
+
+/// 实体占全幅比例低于该值视为平均K线十字星
+const HA_DOJI_BODY_RATIO: f64 = 0.1;
+/// 影线占全幅比例低于该值视为"无影线"
+const HA_NO_SHADOW_RATIO: f64 = 0.02;
+
+/// 将普通K线转换为平均K线。
+///
+/// 首根 ha_open 取 (O + C) / 2；输入长度不一致时按最短序列截断。
+pub fn convert_to_heikin_ashi(
+    opens: &[f64],
+    highs: &[f64],
+    lows: &[f64],
+    closes: &[f64],
+) -> Vec<HeikinAshiCandle> {
+    let len = opens.len().min(highs.len()).min(lows.len()).min(closes.len());
+    let mut candles: Vec<HeikinAshiCandle> = Vec::with_capacity(len);
+    
+    for i in 0..len {
+        let ha_close = (opens[i] + highs[i] + lows[i] + closes[i]) / 4.0;
+        let ha_open = match candles.last() {
+            Some(prev) => (prev.ha_open + prev.ha_close) / 2.0,
+            None => (opens[i] + closes[i]) / 2.0,
+        };
+        candles.push(HeikinAshiCandle {
+            ha_open,
+            ha_high: highs[i].max(ha_open).max(ha_close),
+            ha_low: lows[i].min(ha_open).min(ha_close),
+            ha_close,
+        });
+    }
+    
+    candles
+}
+
+/// 根据最新平均K线判断信号：无下影阳线为强势上涨，无上影阴线为强势下跌，十字星为趋势犹豫
+pub fn classify_heikin_ashi_signal(candles: &[HeikinAshiCandle]) -> TradingSignal {
+    let Some(last) = candles.last() else {
+        return TradingSignal::Hold;
+    };
+    let range = last.ha_high - last.ha_low;
+    if range <= 0.0 {
+        return TradingSignal::Hold;
+    }
+    
+    let body = last.ha_close - last.ha_open;
+    if body.abs() / range < HA_DOJI_BODY_RATIO {
+        return TradingSignal::Hold;
+    }
+    
+    let upper_shadow = last.ha_high - last.ha_open.max(last.ha_close);
+    let lower_shadow = last.ha_open.min(last.ha_close) - last.ha_low;
+    // 连续两根同向平均K线才视为强势，单根仅作普通信号
+    let prev_same_direction = candles.len() >= 2 && {
+        let prev = &candles[candles.len() - 2];
+        (prev.ha_close - prev.ha_open) * body > 0.0
+    };
+    
+    if body > 0.0 {
+        if lower_shadow / range < HA_NO_SHADOW_RATIO && prev_same_direction {
+            TradingSignal::StrongBuy
+        } else {
+            TradingSignal::Buy
+        }
+    } else if upper_shadow / range < HA_NO_SHADOW_RATIO && prev_same_direction {
+        TradingSignal::StrongSell
+    } else {
+        TradingSignal::Sell
+    }
+}
+
+/// 仅强势平均K线计入形态识别（普通阴阳线在平滑序列中过于常见，不具区分度）
+fn heikin_ashi_pattern(signal: &TradingSignal) -> Option<PatternRecognition> {
+    match signal {
+        TradingSignal::StrongBuy => Some(PatternRecognition {
+            pattern_type: PatternType::HeikinAshiStrongBullish.to_string(),
+            is_bullish: true,
+            reliability: 0.60,
+            description: "平均K线连续无下影阳线，上涨趋势延续".to_string(),
+            confirmation_score: 0.0,
+        }),
+        TradingSignal::StrongSell => Some(PatternRecognition {
+            pattern_type: PatternType::HeikinAshiStrongBearish.to_string(),
+            is_bullish: false,
+            reliability: 0.60,
+            description: "平均K线连续无上影阴线，下跌趋势延续".to_string(),
+            confirmation_score: 0.0,
+        }),
+        _ => None,
+    }
+}
+
 /// 检测单根K线形态
 fn detect_single_candle(open: &f64, close: &f64, high: &f64, low: &f64) -> Option<PatternRecognition> {
     let body = (close - open).abs();
@@ -126,6 +350,7 @@ fn detect_single_candle(open: &f64, close: &f64, high: &f64, low: &f64) -> Optio
             is_bullish: false,
             reliability: 0.6,
             description: "十字星，市场犹豫不决".to_string(),
+            confirmation_score: 0.0,
         });
     }
     
@@ -137,6 +362,7 @@ fn detect_single_candle(open: &f64, close: &f64, high: &f64, low: &f64) -> Optio
             is_bullish,
             reliability: 0.65,
             description: if is_bullish { "锤子线，可能反转上涨".to_string() } else { "吊颈线，可能见顶".to_string() },
+            confirmation_score: 0.0,
         });
     }
     
@@ -148,6 +374,7 @@ fn detect_single_candle(open: &f64, close: &f64, high: &f64, low: &f64) -> Optio
             is_bullish,
             reliability: 0.60,
             description: if is_bullish { "倒锤子，可能反转上涨".to_string() } else { "流星线，可能见顶".to_string() },
+            confirmation_score: 0.0,
         });
     }
     
@@ -158,6 +385,7 @@ fn detect_single_candle(open: &f64, close: &f64, high: &f64, low: &f64) -> Optio
             is_bullish: false,
             reliability: 0.5,
             description: "纺锤线，市场方向不明".to_string(),
+            confirmation_score: 0.0,
         });
     }
     
@@ -186,6 +414,7 @@ fn detect_double_candle(
                 is_bullish: true,
                 reliability: 0.70,
                 description: "看涨吞没形态，可能反转上涨".to_string(),
+                confirmation_score: 0.0,
             });
         }
     }
@@ -198,6 +427,7 @@ fn detect_double_candle(
                 is_bullish: false,
                 reliability: 0.70,
                 description: "看跌吞没形态，可能反转下跌".to_string(),
+                confirmation_score: 0.0,
             });
         }
     }
@@ -228,6 +458,7 @@ fn detect_triple_candle(
                 is_bullish: true,
                 reliability: 0.75,
                 description: "三只白兵形态，强烈看涨信号".to_string(),
+                confirmation_score: 0.0,
             });
         }
     }
@@ -240,6 +471,7 @@ fn detect_triple_candle(
                 is_bullish: false,
                 reliability: 0.75,
                 description: "三只乌鸦形态，强烈看跌信号".to_string(),
+                confirmation_score: 0.0,
             });
         }
     }
@@ -253,6 +485,7 @@ fn detect_triple_candle(
                 is_bullish: true,
                 reliability: 0.70,
                 description: "早晨之星形态，可能反转上涨".to_string(),
+                confirmation_score: 0.0,
             });
         }
     }
@@ -265,6 +498,7 @@ fn detect_triple_candle(
                 is_bullish: false,
                 reliability: 0.70,
                 description: "黄昏之星形态，可能反转下跌".to_string(),
+                confirmation_score: 0.0,
             });
         }
     }
@@ -272,3 +506,83 @@ fn detect_triple_candle(
     None
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_convert_to_heikin_ashi_formulas() {
+        let candles = convert_to_heikin_ashi(
+            &[10.0, 11.0],
+            &[12.0, 13.0],
+            &[9.0, 10.5],
+            &[11.0, 12.5],
+        );
+        assert_eq!(candles.len(), 2);
+        assert!((candles[0].ha_close - 10.5).abs() < 1e-9);
+        assert!((candles[0].ha_open - 10.5).abs() < 1e-9);
+        // 第二根 ha_open = (10.5 + 10.5) / 2, ha_close = (11 + 13 + 10.5 + 12.5) / 4
+        assert!((candles[1].ha_open - 10.5).abs() < 1e-9);
+        assert!((candles[1].ha_close - 11.75).abs() < 1e-9);
+        assert_eq!(candles[1].ha_high, 13.0);
+        assert_eq!(candles[1].ha_low, 10.5);
+    }
+
+    #[test]
+    fn test_classify_heikin_ashi_signal() {
+        let rising: Vec<f64> = (0..10).map(|i| 10.0 + i as f64).collect();
+        let opens: Vec<f64> = rising.iter().map(|p| p - 0.2).collect();
+        let highs: Vec<f64> = rising.iter().map(|p| p + 0.5).collect();
+        let candles = convert_to_heikin_ashi(&opens, &highs, &opens, &rising);
+        assert_eq!(classify_heikin_ashi_signal(&candles), TradingSignal::StrongBuy);
+
+        let falling: Vec<f64> = rising.iter().rev().copied().collect();
+        let opens: Vec<f64> = falling.iter().map(|p| p + 0.2).collect();
+        let lows: Vec<f64> = falling.iter().map(|p| p - 0.5).collect();
+        let candles = convert_to_heikin_ashi(&opens, &opens, &lows, &falling);
+        assert_eq!(classify_heikin_ashi_signal(&candles), TradingSignal::StrongSell);
+
+        let doji = HeikinAshiCandle { ha_open: 10.0, ha_high: 11.0, ha_low: 9.0, ha_close: 10.05 };
+        assert_eq!(classify_heikin_ashi_signal(&[doji]), TradingSignal::Hold);
+        assert_eq!(classify_heikin_ashi_signal(&[]), TradingSignal::Hold);
+    }
+
+    #[test]
+    fn test_pattern_confirmation_score() {
+        let hammer = PatternRecognition {
+            pattern_type: PatternType::Hammer.to_string(),
+            is_bullish: true,
+            reliability: 0.65,
+            description: String::new(),
+            confirmation_score: 0.0,
+        };
+        // 第 20 根放量 2 倍、次日收涨、贴近 10.0 支撑、多周期看涨
+        let mut closes = vec![10.0; 22];
+        closes[21] = 10.3;
+        let mut volumes = vec![1_000i64; 22];
+        volumes[20] = 2_000;
+        let sr = SupportResistance {
+            support_levels: vec![9.9],
+            resistance_levels: vec![11.0],
+            current_position: String::new(),
+        };
+        let ctx = |pattern_index, direction| PatternConfirmationContext {
+            closes: &closes,
+            volumes: &volumes,
+            pattern_index,
+            support_resistance: &sr,
+            multi_timeframe_direction: direction,
+        };
+        assert!((score_pattern_confirmation(&hammer, &ctx(20, Some("看涨"))) - 1.0).abs() < 1e-9);
+        // 最新K线上完成的形态无次日K线：按其余 0.7 满分缩放，多周期看跌不加分
+        assert!((score_pattern_confirmation(&hammer, &ctx(21, Some("看跌"))) - 0.0).abs() < 1e-9);
+        assert!((score_pattern_confirmation(&hammer, &ctx(21, Some("看涨"))) - 0.2 / 0.7).abs() < 1e-9);
+        // 多周期方向未知时不计入分母
+        assert!((score_pattern_confirmation(&hammer, &ctx(20, None)) - 1.0).abs() < 1e-9);
+
+        let mut patterns = vec![hammer.clone(), hammer];
+        apply_pattern_confirmation(&mut patterns[..1], &ctx(20, Some("看跌")));
+        assert!((patterns[0].confirmation_score - 0.8).abs() < 1e-9);
+        assert_eq!(require_confirmation_score(&patterns, 0.5).len(), 1);
+    }
+}
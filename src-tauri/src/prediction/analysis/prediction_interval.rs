@@ -8,8 +8,13 @@
 //! H 日收益的标准差）。z 倍数经 `examples/interval_calibration.rs` 在 40 票、h=1/5/10 的
 //! walk-forward 上校准：realized20 法名义 80% 带（z≈1.34）经验覆盖 ~80%，90%→z≈1.90，
 //! 95%→z≈2.50（A 股收益轻微厚尾，故 90/95 档 z 大于正态值）。
+//!
+//! 布林带收缩（带宽处于近 60 日低位）时，近 20 日已实现波动率会低估随后的突破幅度，
+//! σ 按收缩强度最多放大 `SQUEEZE_VOL_BOOST`。放大在 z 校准之前完成：校准实验与生产
+//! 使用同一 σ（[`interval_daily_sigma`]），z 倍数针对放大后的 σ 求经验分位。
 
 use crate::prediction::analysis::volatility_forecast::calculate_realized_volatility;
+use crate::prediction::indicators::bollinger::detect_bollinger_squeeze;
 use crate::prediction::types::{Prediction, PredictionInterval};
 
 /// 已实现波动率回看窗口（交易日）
@@ -18,6 +23,12 @@ const REALIZED_VOL_WINDOW: usize = 20;
 /// 序列化到响应中的稳定方法名。
 pub const METHOD: &str = "realized_volatility_calibrated";
 
+/// 布林带收缩检测：带宽周期 / 百分位回看窗口
+const SQUEEZE_PERIOD: usize = 20;
+const SQUEEZE_LOOKBACK: usize = 60;
+/// 收缩强度为 1 时 σ 的放大比例
+const SQUEEZE_VOL_BOOST: f64 = 0.3;
+
 /// 默认名义覆盖率
 pub const DEFAULT_COVERAGE: f64 = 0.80;
 
@@ -46,6 +57,14 @@ pub fn realized_daily_vol(closes: &[f64]) -> f64 {
     }
 }
 
+/// 区间带使用的日波动率：近 20 日已实现波动率，布林带收缩时按收缩强度放大。
+///
+/// 校准实验（`examples/interval_calibration.rs`）与生产区间共用此口径，z 倍数在其上校准。
+pub fn interval_daily_sigma(closes: &[f64]) -> f64 {
+    let squeeze = detect_bollinger_squeeze(closes, SQUEEZE_PERIOD, SQUEEZE_LOOKBACK);
+    realized_daily_vol(closes) * (1.0 + SQUEEZE_VOL_BOOST * squeeze.squeeze_intensity)
+}
+
 /// 为每个预测日填充校准区间带。
 ///
 /// - `closes`：发起日（含）之前的收盘价序列，用于估计已实现波动率
@@ -53,6 +72,8 @@ pub fn realized_daily_vol(closes: &[f64]) -> f64 {
 /// - `confidence`：名义覆盖率（默认 [`DEFAULT_COVERAGE`]）
 ///
 /// 区间居中于各日点预测价（点预测已近乎不动，区间表达真实不确定性）。
+/// 名义覆盖率对应的区间价格同时写入 `prediction_low` / `prediction_high`，供前端绘制扇形图；
+/// 80% 档取校准 z≈1.34 而非正态 1.28，因 A 股收益轻微厚尾；σ 见 [`interval_daily_sigma`]。
 pub fn attach_prediction_intervals(
     predictions: &mut [Prediction],
     closes: &[f64],
@@ -62,17 +83,14 @@ pub fn attach_prediction_intervals(
     if base_price <= 0.0 || !base_price.is_finite() {
         return;
     }
-    let sigma = realized_daily_vol(closes);
+    let sigma = interval_daily_sigma(closes);
     for (idx, prediction) in predictions.iter_mut().enumerate() {
         let day = (idx + 1) as f64;
         let cum_change = (prediction.predicted_price - base_price) / base_price * 100.0;
-        prediction.interval = Some(build_interval(
-            base_price,
-            cum_change,
-            sigma,
-            day,
-            confidence,
-        ));
+        let interval = build_interval(base_price, cum_change, sigma, day, confidence);
+        prediction.prediction_low = interval.lower_price;
+        prediction.prediction_high = interval.upper_price;
+        prediction.interval = Some(interval);
         prediction.stress_interval = Some(build_interval(
             base_price,
             cum_change,
@@ -127,6 +145,9 @@ mod tests {
                     key_factors: None,
                     interval: None,
                     stress_interval: None,
+                    explanation: None,
+                    prediction_low: last,
+                    prediction_high: last,
                 }
             })
             .collect()
@@ -156,6 +177,8 @@ mod tests {
             let iv = p.interval.as_ref().unwrap();
             let stress = p.stress_interval.as_ref().unwrap();
             assert!(iv.lower_price < p.predicted_price && p.predicted_price < iv.upper_price);
+            assert_eq!(p.prediction_low, iv.lower_price);
+            assert_eq!(p.prediction_high, iv.upper_price);
             assert!((iv.confidence - 0.80).abs() < 1e-9);
             assert!((stress.confidence - 0.95).abs() < 1e-9);
             assert!(stress.lower_price <= iv.lower_price);
@@ -165,6 +188,24 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_bollinger_squeeze_widens_interval() {
+        // 前段大幅震荡、近期横盘：已实现波动率相近的两条序列，收缩者区间更宽
+        let mut squeezed: Vec<f64> = (0..100)
+            .map(|i| 100.0 + if i % 2 == 0 { 8.0 } else { -8.0 })
+            .collect();
+        squeezed.extend((0..30).map(|i| 100.0 * 1.002_f64.powi(i)));
+        let steady: Vec<f64> = (0..130).map(|i| 100.0 * 1.002_f64.powi(i)).collect();
+        let width = |closes: &[f64]| {
+            let base = *closes.last().unwrap();
+            let mut preds = make_predictions(base, 0.0, 1);
+            attach_prediction_intervals(&mut preds, closes, base, DEFAULT_COVERAGE);
+            let iv = preds[0].interval.clone().unwrap();
+            iv.upper_change_percent - iv.lower_change_percent
+        };
+        assert!(width(&squeezed) > width(&steady));
+    }
+
     #[test]
     fn test_zero_base_price_is_noop() {
         let mut preds = make_predictions(100.0, 0.0, 3);
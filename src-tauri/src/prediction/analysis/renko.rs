@@ -0,0 +1,125 @@
+//! 砖形图（Renko）分析模块
+//!
+//! 仅当收盘价相对上一块砖移动满一个砖高时才生成新砖，过滤小幅波动。
+
+use crate::prediction::indicators::{atr, TradingSignal};
+use serde::{Deserialize, Serialize};
+
+/// 自动砖高使用的 ATR 周期
+const RENKO_ATR_PERIOD: usize = 14;
+
+/// 连续同向砖数达到该值视为趋势信号
+const RENKO_SIGNAL_RUN: usize = 3;
+
+/// 砖块方向
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BrickDirection {
+    Up,
+    Down,
+}
+
+/// 砖块
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RenkoBrick {
+    pub direction: BrickDirection,
+    pub open: f64,
+    pub close: f64,
+    /// 生成该砖时对应的原始K线下标
+    pub brick_index: usize,
+}
+
+/// 由收盘价序列构建砖形图。
+///
+/// 以首个价格为基准；反转需反向移动两个砖高（越过上一块砖的开盘价再走一砖）。
+pub fn build_renko_chart(prices: &[f64], brick_size: f64) -> Vec<RenkoBrick> {
+    let mut bricks: Vec<RenkoBrick> = Vec::new();
+    if !(brick_size > 0.0 && brick_size.is_finite()) {
+        return bricks;
+    }
+    let Some(&first) = prices.first() else {
+        return bricks;
+    };
+
+    // 当前砖顶/砖底
+    let (mut top, mut bottom) = (first, first);
+    for (index, &price) in prices.iter().enumerate().skip(1) {
+        while price >= top + brick_size {
+            bricks.push(RenkoBrick {
+                direction: BrickDirection::Up,
+                open: top,
+                close: top + brick_size,
+                brick_index: index,
+            });
+            top += brick_size;
+            bottom = top - brick_size;
+        }
+        while price <= bottom - brick_size {
+            bricks.push(RenkoBrick {
+                direction: BrickDirection::Down,
+                open: bottom,
+                close: bottom - brick_size,
+                brick_index: index,
+            });
+            bottom -= brick_size;
+            top = bottom + brick_size;
+        }
+    }
+
+    bricks
+}
+
+/// 末端连续同向砖 ≥ 3 块：上涨为买入，下跌为卖出；否则持有
+pub fn detect_renko_signal(bricks: &[RenkoBrick]) -> TradingSignal {
+    let Some(last) = bricks.last() else {
+        return TradingSignal::Hold;
+    };
+    let run = bricks
+        .iter()
+        .rev()
+        .take_while(|brick| brick.direction == last.direction)
+        .count();
+
+    match (run >= RENKO_SIGNAL_RUN, last.direction) {
+        (true, BrickDirection::Up) => TradingSignal::Buy,
+        (true, BrickDirection::Down) => TradingSignal::Sell,
+        _ => TradingSignal::Hold,
+    }
+}
+
+/// 以 1 倍 ATR(14) 作为砖高；数据不足时返回 0
+pub fn auto_brick_size_atr(highs: &[f64], lows: &[f64], closes: &[f64]) -> f64 {
+    atr::calculate_atr(highs, lows, closes, RENKO_ATR_PERIOD)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_renko_chart_requires_double_move_to_reverse() {
+        let prices = [10.0, 10.5, 11.0, 12.1, 11.5, 10.9, 9.9];
+        let bricks = build_renko_chart(&prices, 1.0);
+        let directions: Vec<_> = bricks.iter().map(|b| b.direction).collect();
+
+        // 10→11→12 两块上涨砖；回落到 10.9 不足两砖不反转，9.9 反转出一块下跌砖
+        assert_eq!(
+            directions,
+            vec![BrickDirection::Up, BrickDirection::Up, BrickDirection::Down]
+        );
+        assert_eq!(bricks[1].brick_index, 3);
+        assert!((bricks[2].open - 11.0).abs() < 1e-9);
+        assert!((bricks[2].close - 10.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_detect_renko_signal() {
+        let up: Vec<f64> = (0..6).map(|i| 10.0 + i as f64).collect();
+        assert_eq!(detect_renko_signal(&build_renko_chart(&up, 1.0)), TradingSignal::Buy);
+
+        let down: Vec<f64> = up.iter().rev().copied().collect();
+        assert_eq!(detect_renko_signal(&build_renko_chart(&down, 1.0)), TradingSignal::Sell);
+
+        assert_eq!(detect_renko_signal(&build_renko_chart(&[10.0, 11.0, 12.0], 1.0)), TradingSignal::Hold);
+        assert!(build_renko_chart(&up, 0.0).is_empty());
+    }
+}
@@ -0,0 +1,43 @@
+//! 个股技术信号快照
+//!
+//! 技术指标与背离检测的统一入口，预测管线（`inference::analyze`）与预警监控共用，
+//! 保证两处对同一根K线给出一致的指标与背离判断。
+
+use crate::config::presets::IndicatorConfig;
+use crate::prediction::analysis::divergence::{analyze_all_divergences, DivergenceAnalysis};
+use crate::prediction::indicators::{calculate_all_indicators, TechnicalIndicatorValues};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+/// 计算信号快照时读取的最近K线数（覆盖背离检测与 MACD 预热所需窗口）
+pub const STOCK_SIGNAL_BARS: usize = 120;
+
+/// 技术指标 + 背离分析
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TechnicalSignalSet {
+    pub indicators: TechnicalIndicatorValues,
+    pub divergence: DivergenceAnalysis,
+}
+
+/// 某只股票最新一根K线上的技术信号
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StockSignals {
+    pub stock_code: String,
+    pub date: NaiveDate,
+    pub close: f64,
+    pub signals: TechnicalSignalSet,
+}
+
+/// 按价格序列计算技术指标与全部背离
+pub fn compute_technical_signals(
+    prices: &[f64],
+    highs: &[f64],
+    lows: &[f64],
+    volumes: &[i64],
+    config: &IndicatorConfig,
+) -> TechnicalSignalSet {
+    TechnicalSignalSet {
+        indicators: calculate_all_indicators(prices, highs, lows, volumes, config),
+        divergence: analyze_all_divergences(prices, highs, lows, volumes),
+    }
+}
@@ -1,7 +1,18 @@
 //! 支撑阻力位分析模块
 
+use crate::prediction::analysis::divergence::find_local_extremes;
+use crate::prediction::indicators::calculate_ema;
 use serde::{Deserialize, Serialize};
 
+/// 枢轴点识别窗口：左右各 N 根K线内的最高/最低点
+const PIVOT_WINDOW: usize = 3;
+/// 枢轴点回看K线数
+const PIVOT_LOOKBACK: usize = 120;
+/// 价位聚类容差（相对价格）
+const CLUSTER_TOLERANCE: f64 = 0.005;
+/// 每侧保留的关键价位数
+const MAX_KEY_LEVELS: usize = 5;
+
 /// 支撑阻力位
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SupportResistance {
@@ -10,6 +21,25 @@ pub struct SupportResistance {
     pub current_position: String,
 }
 
+/// 聚类得到的关键价位
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PriceLevel {
+    pub price: f64,
+    /// 相对强度 0-1，最强价位为 1
+    pub strength: f64,
+    /// 在该价位附近形成的枢轴点（测试并被拒绝）次数
+    pub test_count: usize,
+    /// 最近一次测试所在的K线索引
+    pub last_tested_index: usize,
+}
+
+/// 基于枢轴点聚类的关键支撑/阻力位，均按强度降序
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SupportResistanceLevels {
+    pub key_supports: Vec<PriceLevel>,
+    pub key_resistances: Vec<PriceLevel>,
+}
+
 /// 计算支撑阻力位
 pub fn calculate_support_resistance(
     prices: &[f64],
@@ -29,18 +59,21 @@ pub fn calculate_support_resistance(
     let mut all_levels = Vec::new();
     
     // 1. 计算均线支撑/阻力
-    let calc_ma = |window: usize| -> f64 {
-        if n >= window {
-            prices[n - window..].iter().sum::<f64>() / window as f64
+    // 使用 EMA 而非 SMA：SMA 对窗口内各日等权，趋势行情中均线系统性滞后于价格，
+    // 上涨时支撑位偏低、下跌时阻力位偏高；EMA 对近期价格加权，滞后更小。
+    // 数据不足周期（如不足 60 日）时以当前价代替
+    let calc_ema = |period: usize| -> f64 {
+        if n >= period {
+            calculate_ema(prices, period)
         } else {
             current_price
         }
     };
-    
-    all_levels.push(calc_ma(5));
-    all_levels.push(calc_ma(10));
-    all_levels.push(calc_ma(20));
-    all_levels.push(calc_ma(60));
+
+    all_levels.push(calc_ema(5));
+    all_levels.push(calc_ema(10));
+    all_levels.push(calc_ema(20));
+    all_levels.push(calc_ema(60));
     
     // 2. 历史高低点
     let lookback = n.min(60);
@@ -60,16 +93,21 @@ pub fn calculate_support_resistance(
     all_levels.sort_by(|a, b| a.partial_cmp(b).unwrap());
     all_levels.dedup_by(|a, b| (*a - *b).abs() < current_price * 0.01);
     
-    // 分类支撑和阻力
-    let mut support_levels: Vec<f64> = all_levels.iter()
-        .filter(|&&l| l < current_price && l > current_price * 0.85)
-        .copied()
-        .collect();
-    
-    let mut resistance_levels: Vec<f64> = all_levels.iter()
-        .filter(|&&l| l > current_price && l < current_price * 1.15)
-        .copied()
-        .collect();
+    // 分类支撑和阻力：优先采用枢轴点聚类价位（多次测试、更稳定），
+    // 某一侧没有枢轴价位时回退到均线/斐波那契候选
+    let key_levels = calculate_key_levels(highs, lows, current_price);
+    let in_support_band = |l: f64| l < current_price && l > current_price * 0.85;
+    let in_resistance_band = |l: f64| l > current_price && l < current_price * 1.15;
+    let pick = |pivots: &[PriceLevel], in_band: &dyn Fn(f64) -> bool| -> Vec<f64> {
+        let from_pivots: Vec<f64> = pivots.iter().map(|l| l.price).filter(|&l| in_band(l)).collect();
+        if from_pivots.is_empty() {
+            all_levels.iter().copied().filter(|&l| in_band(l)).collect()
+        } else {
+            from_pivots
+        }
+    };
+    let mut support_levels = pick(&key_levels.key_supports, &in_support_band);
+    let mut resistance_levels = pick(&key_levels.key_resistances, &in_resistance_band);
     
     // 按距离排序
     support_levels.sort_by(|a, b| (current_price - a).partial_cmp(&(current_price - b)).unwrap());
@@ -103,6 +141,81 @@ pub fn calculate_support_resistance(
     }
 }
 
+/// 枢轴点聚类识别关键支撑/阻力位
+///
+/// 1. 在最近 `PIVOT_LOOKBACK` 根K线的最高价/最低价上寻找局部极值（枢轴点）；
+/// 2. 按价格排序后凝聚聚类：反复合并中心相距不超过 `CLUSTER_TOLERANCE` 的相邻簇；
+/// 3. 每个簇的测试次数为其枢轴点数，强度按测试次数与时间新近度加权后归一化；
+/// 4. 低于当前价的簇为支撑、高于当前价的为阻力（突破后的阻力转为支撑）。
+pub fn calculate_key_levels(
+    highs: &[f64],
+    lows: &[f64],
+    current_price: f64,
+) -> SupportResistanceLevels {
+    let n = highs.len().min(lows.len());
+    let start = n.saturating_sub(PIVOT_LOOKBACK);
+    let (_, pivot_highs) = find_local_extremes(&highs[start..n], PIVOT_WINDOW);
+    let (pivot_lows, _) = find_local_extremes(&lows[start..n], PIVOT_WINDOW);
+
+    let mut pivots: Vec<(usize, f64)> = pivot_highs
+        .into_iter()
+        .chain(pivot_lows)
+        .map(|(i, price)| (start + i, price))
+        .filter(|(_, price)| price.is_finite() && *price > 0.0)
+        .collect();
+    if pivots.is_empty() {
+        return SupportResistanceLevels::default();
+    }
+    pivots.sort_by(|a, b| a.1.total_cmp(&b.1));
+
+    // 相邻簇凝聚合并：每轮合并中心距离最近且在容差内的一对
+    let mut clusters: Vec<Vec<(usize, f64)>> = pivots.into_iter().map(|p| vec![p]).collect();
+    let center = |cluster: &[(usize, f64)]| {
+        cluster.iter().map(|(_, price)| price).sum::<f64>() / cluster.len() as f64
+    };
+    loop {
+        let closest = clusters
+            .windows(2)
+            .enumerate()
+            .map(|(i, pair)| {
+                let (lower, upper) = (center(&pair[0]), center(&pair[1]));
+                (i, (upper - lower) / lower)
+            })
+            .filter(|(_, gap)| *gap <= CLUSTER_TOLERANCE)
+            .min_by(|a, b| a.1.total_cmp(&b.1));
+        let Some((i, _)) = closest else { break };
+        let upper = clusters.remove(i + 1);
+        clusters[i].extend(upper);
+    }
+
+    let span = n.saturating_sub(1).max(1) as f64;
+    let mut levels: Vec<PriceLevel> = clusters
+        .iter()
+        .map(|cluster| PriceLevel {
+            price: center(cluster),
+            // 近期测试权重更高：最早一根 0.5，最新一根 1.0
+            strength: cluster.iter().map(|(i, _)| 0.5 + 0.5 * *i as f64 / span).sum(),
+            test_count: cluster.len(),
+            last_tested_index: cluster.iter().map(|(i, _)| *i).max().unwrap_or(0),
+        })
+        .collect();
+    let max_strength = levels.iter().map(|l| l.strength).fold(0.0, f64::max);
+    for level in &mut levels {
+        level.strength /= max_strength;
+    }
+    levels.sort_by(|a, b| b.strength.total_cmp(&a.strength));
+
+    let (mut key_supports, mut key_resistances): (Vec<_>, Vec<_>) =
+        levels.into_iter().partition(|l| l.price < current_price);
+    key_resistances.retain(|l| l.price > current_price);
+    key_supports.truncate(MAX_KEY_LEVELS);
+    key_resistances.truncate(MAX_KEY_LEVELS);
+    SupportResistanceLevels {
+        key_supports,
+        key_resistances,
+    }
+}
+
 /// 计算价格与支撑阻力位的关系
 pub fn calculate_sr_influence(
     current_price: f64,
@@ -130,3 +243,63 @@ pub fn is_breakdown(current_price: f64, support: f64, volume_ratio: f64) -> bool
     current_price < support * 0.99 && volume_ratio > 1.2
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 在 100 与 110 之间往返震荡，顶/底各被测试多次，末端收于 105 附近
+    fn range_bars() -> (Vec<f64>, Vec<f64>, Vec<f64>) {
+        let tops = [110.0, 110.3, 109.9, 110.2];
+        let bottoms = [100.0, 100.2, 99.8, 100.1];
+        let mut closes = Vec::new();
+        for (top, bottom) in tops.iter().zip(&bottoms) {
+            for step in 0..=8 {
+                closes.push(bottom + (top - bottom) * step as f64 / 8.0);
+            }
+            for step in 1..8 {
+                closes.push(top - (top - bottom) * step as f64 / 8.0);
+            }
+        }
+        closes.extend([100.5, 102.0, 103.5, 105.0]);
+        let highs = closes.iter().map(|c| c + 0.1).collect();
+        let lows = closes.iter().map(|c| c - 0.1).collect();
+        (closes, highs, lows)
+    }
+
+    #[test]
+    fn test_key_levels_cluster_repeated_pivots() {
+        let (closes, highs, lows) = range_bars();
+        let levels = calculate_key_levels(&highs, &lows, *closes.last().unwrap());
+
+        let resistance = &levels.key_resistances[0];
+        assert_eq!(resistance.test_count, 4);
+        assert!((resistance.price - 110.2).abs() < 0.1, "{resistance:?}");
+
+        // 支撑位最近一次测试更晚，强度最高
+        let support = &levels.key_supports[0];
+        assert_eq!(support.test_count, 4);
+        assert!((support.price - 100.05).abs() < 0.1, "{support:?}");
+        assert!(support.last_tested_index > resistance.last_tested_index);
+        assert!((support.strength - 1.0).abs() < 1e-9);
+        assert!(resistance.strength < support.strength);
+    }
+
+    #[test]
+    fn test_support_resistance_prefers_pivot_levels() {
+        let (closes, highs, lows) = range_bars();
+        let sr = calculate_support_resistance(&closes, &highs, &lows, *closes.last().unwrap());
+        assert_eq!(sr.support_levels.len(), 1);
+        assert_eq!(sr.resistance_levels.len(), 1);
+        assert!((sr.support_levels[0] - 100.05).abs() < 0.1);
+        assert!((sr.resistance_levels[0] - 110.2).abs() < 0.1);
+    }
+
+    #[test]
+    fn test_key_levels_empty_without_pivots() {
+        let rising: Vec<f64> = (0..30).map(|i| 10.0 + i as f64).collect();
+        let levels = calculate_key_levels(&rising, &rising, 50.0);
+        assert!(levels.key_supports.is_empty());
+        assert!(levels.key_resistances.is_empty());
+    }
+}
@@ -31,8 +31,33 @@ impl TrendState {
     pub fn is_bearish(&self) -> bool {
         matches!(self, Self::StrongBearish | Self::Bearish)
     }
+
+    /// 方向强度分：强烈上涨 2、上涨 1、震荡 0、下跌 -1、强烈下跌 -2
+    pub fn score(&self) -> i8 {
+        match self {
+            Self::StrongBullish => 2,
+            Self::Bullish => 1,
+            Self::Neutral => 0,
+            Self::Bearish => -1,
+            Self::StrongBearish => -2,
+        }
+    }
+}
+
+/// 趋势状态在相邻两个交易日之间的变化
+pub struct TrendStateTransition;
+
+impl TrendStateTransition {
+    /// 单日内趋势状态最多变化两档（如强烈下跌 → 震荡）；
+    /// 强烈下跌直接变为上涨或强烈上涨说明数据或判定有误
+    pub fn is_consistent(from: &TrendState, to: &TrendState) -> bool {
+        (to.score() - from.score()).abs() <= 2
+    }
 }
 
+/// 综合趋势相对前一交易日跳变不合理时的置信度折扣
+const INCONSISTENT_TRANSITION_CONFIDENCE_FACTOR: f64 = 0.5;
+
 /// 趋势分析结果
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TrendAnalysis {
@@ -45,6 +70,22 @@ pub struct TrendAnalysis {
     pub description: String,
 }
 
+impl TrendAnalysis {
+    /// 与前一交易日的综合趋势比较：变化超过两档时置信度减半并在描述中提示，返回是否一致
+    pub fn flag_transition_from(&mut self, previous: &TrendState) -> bool {
+        if TrendStateTransition::is_consistent(previous, &self.overall_trend) {
+            return true;
+        }
+        self.trend_confidence *= INCONSISTENT_TRANSITION_CONFIDENCE_FACTOR;
+        self.description.push_str(&format!(
+            "（较前一交易日由{}跳变为{}，趋势判定可信度下降）",
+            previous.to_string(),
+            self.overall_trend.to_string()
+        ));
+        false
+    }
+}
+
 /// 分析股票趋势
 pub fn analyze_trend(
     prices: &[f64],
@@ -177,18 +218,23 @@ fn analyze_weekly_trend(prices: &[f64], highs: &[f64], lows: &[f64]) -> TrendSta
     }
 }
 
-/// 综合趋势
-fn combine_trends(daily: &TrendState, weekly: &TrendState) -> TrendState {
-    match (daily, weekly) {
-        (TrendState::StrongBullish, TrendState::Bullish | TrendState::StrongBullish) => TrendState::StrongBullish,
-        (TrendState::StrongBearish, TrendState::Bearish | TrendState::StrongBearish) => TrendState::StrongBearish,
-        (TrendState::StrongBullish, _) => TrendState::Bullish,
-        (TrendState::StrongBearish, _) => TrendState::Bearish,
-        (TrendState::Bullish, _) => TrendState::Bullish,
-        (TrendState::Bearish, _) => TrendState::Bearish,
-        (TrendState::Neutral, TrendState::Bullish | TrendState::StrongBullish) => TrendState::Bullish,
-        (TrendState::Neutral, TrendState::Bearish | TrendState::StrongBearish) => TrendState::Bearish,
-        _ => TrendState::Neutral,
+/// 综合日线与周线趋势（多周期分析：同向叠加，反向抵消）
+///
+/// - 日线与周线方向相反：短期波动与大级别趋势冲突，判为震荡；
+/// - 方向一致（或一方震荡）：按方向强度分之和，≥ 3 为强烈、1-2 为一般、0 为震荡。
+///
+/// 全部 25 种组合见 `tests/trend_state_tests.rs`。
+pub fn combine_trends(daily: &TrendState, weekly: &TrendState) -> TrendState {
+    let (daily_score, weekly_score) = (daily.score(), weekly.score());
+    if daily_score * weekly_score < 0 {
+        return TrendState::Neutral;
+    }
+    match daily_score + weekly_score {
+        3.. => TrendState::StrongBullish,
+        1..=2 => TrendState::Bullish,
+        0 => TrendState::Neutral,
+        -2..=-1 => TrendState::Bearish,
+        _ => TrendState::StrongBearish,
     }
 }
 
@@ -0,0 +1,179 @@
+//! 成交量异常检测
+//!
+//! 以每日之前 `lookback` 个交易日的成交量经验分布为基准，当日成交量的分位数
+//! 不低于 95% 记为显著异常、不低于 99% 记为极端异常；不假设成交量服从正态分布。
+//! 异常日之后的价格反应用于统计异常信号的历史有效性。
+
+use serde::{Deserialize, Serialize};
+
+/// 默认经验分布回看交易日数
+pub const DEFAULT_VOLUME_LOOKBACK: usize = 60;
+/// 显著异常分位数阈值
+pub const SIGNIFICANT_VOLUME_PERCENTILE: f64 = 0.95;
+/// 极端异常分位数阈值
+pub const EXTREME_VOLUME_PERCENTILE: f64 = 0.99;
+/// 价格反应的观察交易日数
+pub const VOLUME_REACTION_DAYS: usize = 5;
+
+/// 成交量异常级别
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VolumeAnomalyType {
+    /// 超过 95% 分位
+    Significant,
+    /// 超过 99% 分位
+    Extreme,
+}
+
+/// 单日成交量异常
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VolumeAnomaly {
+    /// 在输入序列中的下标
+    pub day_index: usize,
+    pub volume: i64,
+    /// 当日成交量在回看窗口经验分布中的分位数（0-1，窗口内严格小于当日量的占比）
+    pub percentile: f64,
+    pub anomaly_type: VolumeAnomalyType,
+    /// 异常日收盘后 [`VOLUME_REACTION_DAYS`] 个交易日的涨跌幅（%）；后续数据不足时为 None
+    pub price_reaction: Option<f64>,
+}
+
+/// 异常信号的历史表现
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct VolumeAnomalyAccuracy {
+    /// 已有价格反应的异常数
+    pub samples: usize,
+    /// 后续涨跌方向与异常日涨跌方向一致的比例（0-1）；无样本时为 None
+    pub follow_through_rate: Option<f64>,
+    /// 后续涨跌幅绝对值均值（%）
+    pub avg_abs_reaction: f64,
+}
+
+/// 成交量异常分析结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VolumeAnomalyReport {
+    pub stock_code: String,
+    pub lookback: usize,
+    pub anomalies: Vec<VolumeAnomaly>,
+    /// 与 `anomalies` 一一对应的日期
+    pub anomaly_dates: Vec<String>,
+    pub significant: VolumeAnomalyAccuracy,
+    pub extreme: VolumeAnomalyAccuracy,
+}
+
+/// 检测成交量异常：第 i 日与其之前 `lookback` 日（不含当日）的经验分布比较。
+///
+/// 前 `lookback` 日没有完整窗口，不参与检测；返回结果的 `price_reaction` 均为 None，
+/// 由 [`attach_price_reactions`] 补充。
+pub fn detect_volume_anomalies(volumes: &[i64], lookback: usize) -> Vec<VolumeAnomaly> {
+    if lookback == 0 {
+        return Vec::new();
+    }
+    (lookback..volumes.len())
+        .filter_map(|day_index| {
+            let volume = volumes[day_index];
+            let window = &volumes[day_index - lookback..day_index];
+            let below = window.iter().filter(|&&v| v < volume).count();
+            let percentile = below as f64 / lookback as f64;
+            let anomaly_type = if percentile >= EXTREME_VOLUME_PERCENTILE {
+                VolumeAnomalyType::Extreme
+            } else if percentile >= SIGNIFICANT_VOLUME_PERCENTILE {
+                VolumeAnomalyType::Significant
+            } else {
+                return None;
+            };
+            Some(VolumeAnomaly {
+                day_index,
+                volume,
+                percentile,
+                anomaly_type,
+                price_reaction: None,
+            })
+        })
+        .collect()
+}
+
+/// 按收盘价补充各异常日之后 `horizon` 个交易日的涨跌幅
+pub fn attach_price_reactions(anomalies: &mut [VolumeAnomaly], closes: &[f64], horizon: usize) {
+    for anomaly in anomalies {
+        let base = closes.get(anomaly.day_index).copied().unwrap_or(0.0);
+        anomaly.price_reaction = closes
+            .get(anomaly.day_index + horizon)
+            .filter(|_| base > 0.0)
+            .map(|&later| (later / base - 1.0) * 100.0);
+    }
+}
+
+/// 统计某一级别异常的历史表现：异常日涨跌方向（相对前收盘）是否在随后延续
+pub fn summarize_anomaly_accuracy(
+    anomalies: &[VolumeAnomaly],
+    closes: &[f64],
+    anomaly_type: VolumeAnomalyType,
+) -> VolumeAnomalyAccuracy {
+    let outcomes: Vec<(bool, f64)> = anomalies
+        .iter()
+        .filter(|anomaly| anomaly.anomaly_type == anomaly_type && anomaly.day_index > 0)
+        .filter_map(|anomaly| {
+            let reaction = anomaly.price_reaction?;
+            let day_change = closes.get(anomaly.day_index)? - closes.get(anomaly.day_index - 1)?;
+            Some((day_change * reaction > 0.0, reaction.abs()))
+        })
+        .collect();
+    if outcomes.is_empty() {
+        return VolumeAnomalyAccuracy::default();
+    }
+    let samples = outcomes.len();
+    VolumeAnomalyAccuracy {
+        samples,
+        follow_through_rate: Some(
+            outcomes.iter().filter(|(hit, _)| *hit).count() as f64 / samples as f64,
+        ),
+        avg_abs_reaction: outcomes.iter().map(|(_, abs)| abs).sum::<f64>() / samples as f64,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_volume_anomalies_by_percentile() {
+        // 基准成交量 100..=199，末尾三日分别为普通、显著、极端
+        let mut volumes: Vec<i64> = (0..100).map(|i| 100 + i).collect();
+        volumes.extend([150, 196, 500]);
+        let anomalies = detect_volume_anomalies(&volumes, 100);
+
+        assert_eq!(anomalies.len(), 2);
+        assert_eq!(anomalies[0].day_index, 101);
+        assert_eq!(anomalies[0].anomaly_type, VolumeAnomalyType::Significant);
+        // 窗口 101..=199 与 150 中有 96 个低于 196
+        assert!((anomalies[0].percentile - 0.96).abs() < 1e-9);
+        assert_eq!(anomalies[1].anomaly_type, VolumeAnomalyType::Extreme);
+        assert_eq!(anomalies[1].percentile, 1.0);
+        assert!(detect_volume_anomalies(&volumes, 0).is_empty());
+    }
+
+    #[test]
+    fn test_anomaly_price_reaction_accuracy() {
+        let closes = [10.0, 11.0, 11.0, 12.1, 9.0, 9.0, 9.0];
+        let anomaly = |day_index: usize| VolumeAnomaly {
+            day_index,
+            volume: 1_000,
+            percentile: 1.0,
+            anomaly_type: VolumeAnomalyType::Extreme,
+            price_reaction: None,
+        };
+        let mut anomalies = vec![anomaly(1), anomaly(3), anomaly(5)];
+        attach_price_reactions(&mut anomalies, &closes, 2);
+
+        assert!((anomalies[0].price_reaction.unwrap() - 10.0).abs() < 1e-9);
+        assert_eq!(anomalies[2].price_reaction, None);
+
+        // 第 1 日上涨后延续上涨，第 3 日上涨后下跌
+        let accuracy = summarize_anomaly_accuracy(&anomalies, &closes, VolumeAnomalyType::Extreme);
+        assert_eq!(accuracy.samples, 2);
+        assert_eq!(accuracy.follow_through_rate, Some(0.5));
+        let missing = summarize_anomaly_accuracy(&anomalies, &closes, VolumeAnomalyType::Significant);
+        assert_eq!(missing.follow_through_rate, None);
+    }
+}
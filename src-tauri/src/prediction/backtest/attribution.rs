@@ -0,0 +1,242 @@
+//! 回测收益的信号归因
+//!
+//! 为每笔走步回测交易记录入场时的模型方向、MACD、量能与趋势状态，
+//! 按信号分组统计平均收益与胜率，找出历史上最有效的信号组合。
+
+use crate::db::models::HistoricalData;
+use crate::prediction::indicators::macd::calculate_macd_full;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// 组合至少需要的交易笔数，避免少数样本的偶然高收益被选为最佳组合
+pub const MIN_COMBINATION_TRADES: usize = 3;
+
+/// 放量 / 缩量阈值（当日量 / 前 5 日均量）
+const VOLUME_SURGE_RATIO: f64 = 1.5;
+const VOLUME_SHRINK_RATIO: f64 = 0.7;
+
+/// 单笔回测交易：按模型预测方向在预测日收盘入场、目标日收盘出场
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, sqlx::FromRow)]
+pub struct BacktestTradeRecord {
+    pub entry_date: NaiveDate,
+    pub exit_date: NaiveDate,
+    /// 方向 × 实际涨跌幅（百分点），与回测策略收益同口径
+    pub pnl_pct: f64,
+    /// 看涨 / 看跌 / 中性
+    pub model_signal: String,
+    /// 金叉 / 死叉 / 多头 / 空头
+    pub macd_signal: String,
+    /// 放量 / 缩量 / 平量
+    pub volume_signal: String,
+    /// 上升趋势 / 下降趋势 / 震荡
+    pub trend_state: String,
+}
+
+/// 单个信号取值的表现
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignalPerformance {
+    /// 信号来源：模型 / MACD / 量能 / 趋势
+    pub source: String,
+    pub signal: String,
+    pub trades: usize,
+    pub avg_pnl_pct: f64,
+    pub win_rate: f64,
+}
+
+/// 信号归因报告
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignalAttributionReport {
+    pub backtest_id: String,
+    pub total_trades: usize,
+    pub avg_pnl_pct: f64,
+    pub win_rate: f64,
+    /// 按来源、信号排序的分组表现
+    pub by_signal: Vec<SignalPerformance>,
+    /// 平均收益最高的信号组合（如 ["模型:看涨", "MACD:金叉", ...]）；
+    /// 无组合满足 [`MIN_COMBINATION_TRADES`] 时为空
+    pub best_signal_combination: Vec<String>,
+    pub best_combination_avg_pnl_pct: Option<f64>,
+}
+
+/// 预测涨跌幅对应的模型信号
+pub fn model_signal_label(predicted_change: f64) -> &'static str {
+    if predicted_change > 0.0 {
+        "看涨"
+    } else if predicted_change < 0.0 {
+        "看跌"
+    } else {
+        "中性"
+    }
+}
+
+/// 入场时（`history` 最后一根K线）的 MACD、量能与趋势状态
+pub fn classify_entry_signals(history: &[HistoricalData]) -> (String, String, String) {
+    let closes: Vec<f64> = history.iter().map(|h| h.close).collect();
+    let n = closes.len();
+
+    let (dif, dea, _) = calculate_macd_full(&closes);
+    let (prev_dif, prev_dea, _) = calculate_macd_full(&closes[..n.saturating_sub(1)]);
+    let macd = if prev_dif <= prev_dea && dif > dea {
+        "金叉"
+    } else if prev_dif >= prev_dea && dif < dea {
+        "死叉"
+    } else if dif >= dea {
+        "多头"
+    } else {
+        "空头"
+    };
+
+    let volume = match history.split_last() {
+        Some((last, before)) if before.len() >= 5 => {
+            let avg = before[before.len() - 5..]
+                .iter()
+                .map(|h| h.volume as f64)
+                .sum::<f64>()
+                / 5.0;
+            let ratio = if avg > 0.0 { last.volume as f64 / avg } else { 1.0 };
+            if ratio > VOLUME_SURGE_RATIO {
+                "放量"
+            } else if ratio < VOLUME_SHRINK_RATIO {
+                "缩量"
+            } else {
+                "平量"
+            }
+        }
+        _ => "平量",
+    };
+
+    let ma = |period: usize| closes[n - period..].iter().sum::<f64>() / period as f64;
+    let trend = if n >= 60 {
+        let (close, ma20, ma60) = (closes[n - 1], ma(20), ma(60));
+        if close > ma20 && ma20 > ma60 {
+            "上升趋势"
+        } else if close < ma20 && ma20 < ma60 {
+            "下降趋势"
+        } else {
+            "震荡"
+        }
+    } else {
+        "震荡"
+    };
+
+    (macd.to_string(), volume.to_string(), trend.to_string())
+}
+
+/// 信号来源名称及从交易记录取信号的方法
+type SignalSource = (&'static str, fn(&BacktestTradeRecord) -> &str);
+
+const SIGNAL_SOURCES: [SignalSource; 4] = [
+    ("模型", |t| &t.model_signal),
+    ("MACD", |t| &t.macd_signal),
+    ("量能", |t| &t.volume_signal),
+    ("趋势", |t| &t.trend_state),
+];
+
+fn summarize(trades: &[&BacktestTradeRecord]) -> (f64, f64) {
+    if trades.is_empty() {
+        return (0.0, 0.0);
+    }
+    let n = trades.len() as f64;
+    let avg = trades.iter().map(|t| t.pnl_pct).sum::<f64>() / n;
+    let win_rate = trades.iter().filter(|t| t.pnl_pct > 0.0).count() as f64 / n;
+    (avg, win_rate)
+}
+
+/// 按信号来源分组统计，并选出平均收益最高的完整信号组合
+pub fn analyze_signal_attribution(
+    backtest_id: &str,
+    trades: &[BacktestTradeRecord],
+) -> SignalAttributionReport {
+    let mut by_signal = Vec::new();
+    for (source, signal_of) in SIGNAL_SOURCES {
+        let mut groups: BTreeMap<&str, Vec<&BacktestTradeRecord>> = BTreeMap::new();
+        for trade in trades {
+            groups.entry(signal_of(trade)).or_default().push(trade);
+        }
+        for (signal, group) in groups {
+            let (avg_pnl_pct, win_rate) = summarize(&group);
+            by_signal.push(SignalPerformance {
+                source: source.to_string(),
+                signal: signal.to_string(),
+                trades: group.len(),
+                avg_pnl_pct,
+                win_rate,
+            });
+        }
+    }
+
+    let mut combinations: BTreeMap<Vec<String>, Vec<&BacktestTradeRecord>> = BTreeMap::new();
+    for trade in trades {
+        let key = SIGNAL_SOURCES
+            .iter()
+            .map(|(source, signal_of)| format!("{source}:{}", signal_of(trade)))
+            .collect();
+        combinations.entry(key).or_default().push(trade);
+    }
+    let best = combinations
+        .into_iter()
+        .filter(|(_, group)| group.len() >= MIN_COMBINATION_TRADES)
+        .map(|(key, group)| (key, summarize(&group).0))
+        .max_by(|a, b| a.1.total_cmp(&b.1));
+
+    let all: Vec<&BacktestTradeRecord> = trades.iter().collect();
+    let (avg_pnl_pct, win_rate) = summarize(&all);
+    SignalAttributionReport {
+        backtest_id: backtest_id.to_string(),
+        total_trades: trades.len(),
+        avg_pnl_pct,
+        win_rate,
+        by_signal,
+        best_combination_avg_pnl_pct: best.as_ref().map(|(_, avg)| *avg),
+        best_signal_combination: best.map(|(key, _)| key).unwrap_or_default(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trade(pnl_pct: f64, model: &str, macd: &str) -> BacktestTradeRecord {
+        BacktestTradeRecord {
+            entry_date: NaiveDate::from_ymd_opt(2025, 6, 2).unwrap(),
+            exit_date: NaiveDate::from_ymd_opt(2025, 6, 3).unwrap(),
+            pnl_pct,
+            model_signal: model.to_string(),
+            macd_signal: macd.to_string(),
+            volume_signal: "平量".to_string(),
+            trend_state: "震荡".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_signal_attribution() {
+        let trades = vec![
+            trade(2.0, "看涨", "金叉"),
+            trade(1.0, "看涨", "金叉"),
+            trade(-0.5, "看涨", "金叉"),
+            trade(-1.0, "看跌", "空头"),
+            trade(-2.0, "看跌", "空头"),
+            trade(-1.5, "看跌", "空头"),
+            // 单笔高收益组合不足最少笔数，不应被选为最佳
+            trade(9.0, "看涨", "死叉"),
+        ];
+        let report = analyze_signal_attribution("bt-1", &trades);
+        assert_eq!(report.total_trades, 7);
+
+        let bullish = report
+            .by_signal
+            .iter()
+            .find(|s| s.source == "模型" && s.signal == "看涨")
+            .unwrap();
+        assert_eq!(bullish.trades, 4);
+        assert!((bullish.avg_pnl_pct - 2.875).abs() < 1e-9);
+        assert!((bullish.win_rate - 0.75).abs() < 1e-9);
+
+        assert_eq!(
+            report.best_signal_combination,
+            vec!["模型:看涨", "MACD:金叉", "量能:平量", "趋势:震荡"]
+        );
+        assert!((report.best_combination_avg_pnl_pct.unwrap() - 2.5 / 3.0).abs() < 1e-9);
+    }
+}
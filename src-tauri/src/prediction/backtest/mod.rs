@@ -4,13 +4,21 @@
 //! （[`crate::prediction::model::inference::predict_from_historical`]），将预测涨跌幅与未来真实涨跌幅
 //! 对比，量化方向准确率、误差与简单策略收益。
 
+pub mod attribution;
 pub mod metrics;
+pub mod regime;
+pub mod rolling;
+pub mod signal_accuracy;
 
 use crate::db::models::HistoricalData;
+use crate::prediction::analysis::market_regime::{classify_market_regime, MarketRegime};
 use crate::prediction::model::inference::{predict_from_historical, MAX_ANALYSIS_DAYS};
 use crate::prediction::types::{PredictionInterval, PredictionRequest, PredictionResponse};
+use crate::utils::progress::{NoProgress, ProgressSink, OPERATION_CANCELLED};
+use attribution::{classify_entry_signals, model_signal_label, BacktestTradeRecord};
 use chrono::NaiveDate;
 use metrics::{compute_metrics, BacktestMetrics, BacktestSample};
+use regime::{summarize_by_regime, BacktestRegimeBreakdown};
 
 /// 回测最小回看窗口（分析管线要求 ≥60 个交易日）
 pub const MIN_LOOKBACK: usize = 60;
@@ -25,6 +33,10 @@ pub struct BacktestReport {
     pub metrics: BacktestMetrics,
     /// 样本明细，用于前端展示和按日期核验
     pub observations: Vec<BacktestObservation>,
+    /// 与策略收益同口径的逐笔交易及入场信号，用于收益归因
+    pub trades: Vec<BacktestTradeRecord>,
+    /// 按入场市场状态分组的交易表现
+    pub regime_breakdown: Vec<BacktestRegimeBreakdown>,
 }
 
 /// 单次走步预测明细
@@ -43,6 +55,8 @@ pub struct BacktestObservation {
     pub prediction_reason: Option<String>,
     pub interval: Option<PredictionInterval>,
     pub stress_interval: Option<PredictionInterval>,
+    /// 预测日（入场时）仅用可见数据识别的市场状态
+    pub entry_regime: MarketRegime,
 }
 
 /// 走步回测。
@@ -81,10 +95,13 @@ pub fn run_backtest_window(
         start_date,
         end_date,
         predict_from_historical,
+        &NoProgress,
     )
 }
 
 /// 按预测发起日期窗口做走步回测，并允许调用方注入生产预测函数。
+///
+/// 每完成一个预测日上报一次进度；`progress` 请求取消时中止并返回错误。
 #[allow(clippy::too_many_arguments)]
 pub fn run_backtest_window_with_predictor(
     stock_code: &str,
@@ -95,6 +112,7 @@ pub fn run_backtest_window_with_predictor(
     start_date: Option<NaiveDate>,
     end_date: Option<NaiveDate>,
     mut predict: impl FnMut(&PredictionRequest, &[HistoricalData]) -> Result<PredictionResponse, String>,
+    progress: &dyn ProgressSink,
 ) -> Result<BacktestReport, String> {
     let lookback = lookback.max(MIN_LOOKBACK);
     let step = step.max(1);
@@ -110,8 +128,21 @@ pub fn run_backtest_window_with_predictor(
         ));
     }
 
+    let in_window = |date: NaiveDate| {
+        start_date.is_none_or(|start| date >= start) && end_date.is_none_or(|end| date <= end)
+    };
+    let total_days = (lookback..=historical.len() - horizon)
+        .step_by(step)
+        .filter(|&t| in_window(historical[t - 1].date))
+        .count();
+
+    let closes: Vec<f64> = historical.iter().map(|h| h.close).collect();
+    let highs: Vec<f64> = historical.iter().map(|h| h.high).collect();
+    let lows: Vec<f64> = historical.iter().map(|h| h.low).collect();
+
     let mut samples = Vec::new();
     let mut observations = Vec::new();
+    let mut trades = Vec::new();
     let mut interval_80_total = 0usize;
     let mut interval_80_covered = 0usize;
     let mut interval_80_width_sum = 0.0;
@@ -121,12 +152,13 @@ pub fn run_backtest_window_with_predictor(
     let mut t = lookback;
     while t + horizon <= historical.len() {
         let prediction_date = historical[t - 1].date;
-        if start_date.is_some_and(|start| prediction_date < start)
-            || end_date.is_some_and(|end| prediction_date > end)
-        {
+        if !in_window(prediction_date) {
             t += step;
             continue;
         }
+        if progress.is_cancelled() {
+            return Err(OPERATION_CANCELLED.to_string());
+        }
 
         // 仅使用预测日前可见数据，并裁到生产预测同款最大窗口。
         let visible_start = visible_history_start(t, MAX_ANALYSIS_DAYS);
@@ -135,6 +167,9 @@ pub fn run_backtest_window_with_predictor(
             model_name: None,
             prediction_days: horizon,
             use_candle: false,
+            refresh_if_stale: false,
+            include_news: false,
+            include_events: false,
         };
         let response = predict(&request, &historical[visible_start..t])?;
         let prediction = response
@@ -181,6 +216,17 @@ pub fn run_backtest_window_with_predictor(
             predicted_change,
             actual_change,
         });
+        let (macd_signal, volume_signal, trend_state) =
+            classify_entry_signals(&historical[visible_start..t]);
+        trades.push(BacktestTradeRecord {
+            entry_date: prediction_date,
+            exit_date: historical[t - 1 + horizon].date,
+            pnl_pct: predicted_change.signum() * actual_change,
+            model_signal: model_signal_label(predicted_change).to_string(),
+            macd_signal,
+            volume_signal,
+            trend_state,
+        });
         observations.push(BacktestObservation {
             prediction_date,
             target_date: historical[t - 1 + horizon].date,
@@ -199,7 +245,18 @@ pub fn run_backtest_window_with_predictor(
             prediction_reason: prediction.prediction_reason.clone(),
             interval: prediction.interval.clone(),
             stress_interval: prediction.stress_interval.clone(),
+            entry_regime: classify_market_regime(
+                &closes[visible_start..t],
+                &highs[visible_start..t],
+                &lows[visible_start..t],
+            )
+            .regime,
         });
+        progress.report(
+            observations.len(),
+            total_days,
+            &format!("回测 {prediction_date}（{}/{total_days}）", observations.len()),
+        );
         t += step;
     }
 
@@ -210,12 +267,20 @@ pub fn run_backtest_window_with_predictor(
     metrics.stress_95_coverage = ratio(stress_95_covered, stress_95_total);
     metrics.average_interval_80_width = average(interval_80_width_sum, interval_80_total);
     metrics.average_stress_95_width = average(stress_95_width_sum, stress_95_total);
+    let regime_trades: Vec<(MarketRegime, f64)> = observations
+        .iter()
+        .zip(&trades)
+        .map(|(observation, trade)| (observation.entry_regime, trade.pnl_pct))
+        .collect();
+    let regime_breakdown = summarize_by_regime(&regime_trades, horizon);
 
     Ok(BacktestReport {
         stock_code: stock_code.to_string(),
         horizon,
         metrics,
         observations,
+        trades,
+        regime_breakdown,
     })
 }
 
@@ -326,11 +391,16 @@ mod tests {
                         key_factors: None,
                         interval: None,
                         stress_interval: None,
+                        explanation: None,
+                        prediction_low: last.close * 1.10,
+                        prediction_high: last.close * 1.10,
                     }],
                     last_real_data: None,
                     diagnostics: None,
+                    data_warning: None,
                 })
             },
+            &NoProgress,
         )
         .unwrap();
 
@@ -378,11 +448,16 @@ mod tests {
                         key_factors: None,
                         interval: Some(interval),
                         stress_interval: Some(stress),
+                        explanation: None,
+                        prediction_low: last.close,
+                        prediction_high: last.close,
                     }],
                     last_real_data: None,
                     diagnostics: None,
+                    data_warning: None,
                 })
             },
+            &NoProgress,
         )
         .unwrap();
 
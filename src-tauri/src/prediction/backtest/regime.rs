@@ -0,0 +1,109 @@
+//! 按市场状态分组的回测表现
+//!
+//! 每笔走步回测交易按入场时（仅用入场日及之前数据）识别的市场状态分组，
+//! 回答"模型在趋势市还是震荡市更有效"，供决定策略部署场景。
+
+use crate::prediction::analysis::market_regime::MarketRegime;
+use serde::{Deserialize, Serialize};
+
+/// 每年交易日数（夏普年化）
+const TRADING_DAYS_PER_YEAR: f64 = 252.0;
+
+/// 单一市场状态下的交易表现
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BacktestRegimeBreakdown {
+    pub regime: MarketRegime,
+    pub trade_count: usize,
+    /// 胜率 (0-1)
+    pub win_rate: f64,
+    /// 平均单笔收益（百分点）
+    pub avg_return: f64,
+    /// 按持有期年化的夏普比率；收益无波动时为 0
+    pub sharpe: f64,
+}
+
+/// 按入场市场状态汇总交易（市场状态, 收益百分点），按交易笔数降序
+pub fn summarize_by_regime(
+    trades: &[(MarketRegime, f64)],
+    horizon: usize,
+) -> Vec<BacktestRegimeBreakdown> {
+    let mut groups: Vec<(MarketRegime, Vec<f64>)> = Vec::new();
+    for &(regime, pnl) in trades {
+        match groups.iter_mut().find(|(r, _)| *r == regime) {
+            Some((_, returns)) => returns.push(pnl),
+            None => groups.push((regime, vec![pnl])),
+        }
+    }
+
+    let annualize = (TRADING_DAYS_PER_YEAR / horizon.max(1) as f64).sqrt();
+    let mut breakdown: Vec<BacktestRegimeBreakdown> = groups
+        .into_iter()
+        .map(|(regime, returns)| {
+            let count = returns.len() as f64;
+            let mean = returns.iter().sum::<f64>() / count;
+            let std = (returns.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / count).sqrt();
+            BacktestRegimeBreakdown {
+                regime,
+                trade_count: returns.len(),
+                win_rate: returns.iter().filter(|&&r| r > 0.0).count() as f64 / count,
+                avg_return: mean,
+                sharpe: if std > 1e-12 { mean / std * annualize } else { 0.0 },
+            }
+        })
+        .collect();
+    breakdown.sort_by_key(|row| std::cmp::Reverse(row.trade_count));
+    breakdown
+}
+
+/// 格式化为文本表格，用于日志输出
+pub fn format_regime_breakdown_table(breakdown: &[BacktestRegimeBreakdown]) -> String {
+    let mut table = format!(
+        "{:<10}{:>8}{:>10}{:>12}{:>10}",
+        "市场状态", "交易数", "胜率", "平均收益%", "夏普"
+    );
+    for row in breakdown {
+        table.push_str(&format!(
+            "\n{:<10}{:>8}{:>9.1}%{:>+12.2}{:>10.2}",
+            row.regime.to_string(),
+            row.trade_count,
+            row.win_rate * 100.0,
+            row.avg_return,
+            row.sharpe
+        ));
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_summarize_by_regime() {
+        let trades = [
+            (MarketRegime::Ranging, 1.0),
+            (MarketRegime::StrongUptrend, 2.0),
+            (MarketRegime::Ranging, -1.0),
+            (MarketRegime::Ranging, 3.0),
+        ];
+        let breakdown = summarize_by_regime(&trades, 1);
+        assert_eq!(breakdown.len(), 2);
+
+        let ranging = &breakdown[0];
+        assert_eq!(ranging.regime, MarketRegime::Ranging);
+        assert_eq!(ranging.trade_count, 3);
+        assert!((ranging.win_rate - 2.0 / 3.0).abs() < 1e-9);
+        assert!((ranging.avg_return - 1.0).abs() < 1e-9);
+        // 总体标准差 sqrt(8/3)，年化因子 sqrt(252)
+        let expected_sharpe = 1.0 / (8.0f64 / 3.0).sqrt() * 252f64.sqrt();
+        assert!((ranging.sharpe - expected_sharpe).abs() < 1e-9);
+
+        // 单笔交易无波动，夏普为 0
+        assert_eq!(breakdown[1].trade_count, 1);
+        assert_eq!(breakdown[1].sharpe, 0.0);
+
+        let table = format_regime_breakdown_table(&breakdown);
+        assert_eq!(table.lines().count(), 3);
+        assert!(table.contains("震荡整理"));
+    }
+}
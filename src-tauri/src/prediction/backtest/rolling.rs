@@ -0,0 +1,189 @@
+//! 滚动窗口稳定性检验
+//!
+//! 在每个滚动窗口上重新拟合线性回归，预测紧随其后的 `step` 个样本，
+//! 检验模型表现是跨市场环境稳定，还是集中在某一段行情。
+
+use crate::prediction::model::features::DatedSample;
+use crate::prediction::model::linear::LinearRegression;
+use crate::prediction::types::{RollingWindowReport, RollingWindowResult};
+
+/// 每年交易日数（夏普年化）
+const TRADING_DAYS_PER_YEAR: f64 = 252.0;
+/// 窗口准确率标准差超过该值视为不稳定
+const UNSTABLE_ACCURACY_STD: f64 = 0.08;
+/// 平均方向准确率低于该值视为无方向预测力
+const MIN_USEFUL_ACCURACY: f64 = 0.52;
+
+/// 对按日期升序的样本逐窗口训练与样本外评估。
+///
+/// 训练段与测试段之间留出 `horizon` 个样本，避免训练标签窗口覆盖测试期。
+pub fn run_rolling_windows(
+    samples: &[DatedSample],
+    window_size: usize,
+    step_size: usize,
+    horizon: usize,
+) -> Vec<RollingWindowResult> {
+    let (window_size, step_size, horizon) = (window_size, step_size.max(1), horizon.max(1));
+    let mut results = Vec::new();
+    let mut start = 0;
+    while start + window_size + horizon + step_size <= samples.len() {
+        let train = &samples[start..start + window_size];
+        let test_start = start + window_size + horizon;
+        let test = &samples[test_start..test_start + step_size];
+        start += step_size;
+
+        let features: Vec<_> = train.iter().map(|s| s.features).collect();
+        let labels: Vec<f64> = train.iter().map(|s| s.fwd_return).collect();
+        let Some(model) = LinearRegression::fit(&features, &labels) else {
+            continue;
+        };
+        results.push(evaluate_window(&model, test, horizon));
+    }
+    results
+}
+
+fn evaluate_window(
+    model: &LinearRegression,
+    test: &[DatedSample],
+    horizon: usize,
+) -> RollingWindowResult {
+    let mut correct = 0usize;
+    let mut sq_sum = 0.0;
+    let mut strategy_returns = Vec::with_capacity(test.len());
+    for sample in test {
+        let predicted = model.predict(&sample.features);
+        let actual = sample.fwd_return;
+        if (predicted > 0.0 && actual > 0.0) || (predicted < 0.0 && actual < 0.0) {
+            correct += 1;
+        }
+        sq_sum += ((predicted - actual) * 100.0).powi(2);
+        strategy_returns.push(predicted.signum() * actual);
+    }
+
+    let count = test.len().max(1) as f64;
+    let mean = strategy_returns.iter().sum::<f64>() / count;
+    let std = (strategy_returns
+        .iter()
+        .map(|r| (r - mean).powi(2))
+        .sum::<f64>()
+        / count)
+        .sqrt();
+    let sharpe = if std > 1e-12 {
+        mean / std * (TRADING_DAYS_PER_YEAR / horizon as f64).sqrt()
+    } else {
+        0.0
+    };
+
+    RollingWindowResult {
+        test_start_date: test.first().map(|s| s.date.to_string()).unwrap_or_default(),
+        test_end_date: test.last().map(|s| s.date.to_string()).unwrap_or_default(),
+        test_samples: test.len(),
+        direction_accuracy: correct as f64 / count,
+        rmse: (sq_sum / count).sqrt(),
+        sharpe,
+    }
+}
+
+/// 汇总各窗口结果为稳定性报告
+pub fn summarize_rolling_windows(
+    stock_code: &str,
+    window_size: usize,
+    step_size: usize,
+    prediction_days: usize,
+    windows: Vec<RollingWindowResult>,
+) -> RollingWindowReport {
+    let accuracies: Vec<f64> = windows.iter().map(|w| w.direction_accuracy).collect();
+    let count = accuracies.len().max(1) as f64;
+    let mean_accuracy = accuracies.iter().sum::<f64>() / count;
+    let stability_score = (accuracies
+        .iter()
+        .map(|a| (a - mean_accuracy).powi(2))
+        .sum::<f64>()
+        / count)
+        .sqrt();
+
+    let recommendation = if windows.len() < 3 {
+        "滚动窗口数量不足，请缩小窗口/步长或补充历史数据后再评估".to_string()
+    } else if mean_accuracy < MIN_USEFUL_ACCURACY {
+        format!(
+            "平均方向准确率 {:.1}% 接近随机，模型缺乏稳定的方向预测力，建议仅参考区间预测",
+            mean_accuracy * 100.0
+        )
+    } else if stability_score > UNSTABLE_ACCURACY_STD {
+        let best = windows
+            .iter()
+            .max_by(|a, b| a.direction_accuracy.total_cmp(&b.direction_accuracy))
+            .map(|w| w.test_start_date.as_str())
+            .unwrap_or_default();
+        format!(
+            "窗口间准确率波动较大（标准差 {:.1}%），表现集中在 {best} 前后的行情，需警惕市场环境切换",
+            stability_score * 100.0
+        )
+    } else {
+        format!(
+            "各窗口表现稳定（平均准确率 {:.1}%，标准差 {:.1}%），模型在不同市场环境下具一致性",
+            mean_accuracy * 100.0,
+            stability_score * 100.0
+        )
+    };
+
+    RollingWindowReport {
+        stock_code: stock_code.to_string(),
+        window_size,
+        step_size,
+        prediction_days,
+        time_series_of_accuracy: windows
+            .iter()
+            .map(|w| (w.test_start_date.clone(), w.direction_accuracy))
+            .collect(),
+        windows,
+        mean_accuracy,
+        stability_score,
+        recommendation,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prediction::model::features::FEATURE_DIM;
+    use chrono::NaiveDate;
+
+    fn samples(len: usize) -> Vec<DatedSample> {
+        let base = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+        (0..len)
+            .map(|i| {
+                let mut features = [0.0f32; FEATURE_DIM];
+                for (j, value) in features.iter_mut().enumerate() {
+                    *value = ((i * (j + 2)) as f32 * 0.61).sin();
+                }
+                DatedSample {
+                    date: base + chrono::Duration::days(i as i64),
+                    fwd_return: 0.01 * features[0] as f64,
+                    features,
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_rolling_windows_on_learnable_signal() {
+        let windows = run_rolling_windows(&samples(300), 100, 20, 1);
+        assert_eq!(windows.len(), 9);
+        assert!(windows.iter().all(|w| w.test_samples == 20));
+        assert!(windows.iter().all(|w| w.direction_accuracy > 0.95));
+
+        let report = summarize_rolling_windows("600519", 100, 20, 1, windows);
+        assert_eq!(report.time_series_of_accuracy.len(), 9);
+        assert!(report.stability_score < UNSTABLE_ACCURACY_STD);
+        assert!(report.recommendation.contains("稳定"));
+    }
+
+    #[test]
+    fn test_rolling_windows_need_enough_samples() {
+        let windows = run_rolling_windows(&samples(50), 100, 20, 1);
+        assert!(windows.is_empty());
+        let report = summarize_rolling_windows("600519", 100, 20, 1, windows);
+        assert!(report.recommendation.contains("不足"));
+    }
+}
@@ -0,0 +1,333 @@
+//! 技术信号历史胜率检验
+//!
+//! 在个股历史K线上找出某个技术信号的每次出现，统计其后 1/3/5 个交易日收盘价
+//! 是否高于信号日收盘价，并按趋势与量能环境分组，给出该信号在哪种环境下最有效。
+
+use crate::config::constants::{
+    BOLLINGER_PERIOD, BOLLINGER_STD_DEV, KDJ_PERIOD, MACD_SIGNAL_PERIOD, MACD_SLOW_PERIOD,
+    RSI_PERIOD,
+};
+use crate::db::models::HistoricalData;
+use crate::prediction::indicators::kdj::{KDJ_D_SMOOTH, KDJ_K_SMOOTH};
+use crate::prediction::indicators::{
+    calculate_bollinger_bands, calculate_dif_series, calculate_kdj_series,
+    calculate_rsi_with_period, is_golden_cross, is_kdj_golden_cross,
+};
+use crate::utils::math::calculate_ema_series;
+use serde::{Deserialize, Serialize};
+
+/// 信号出现前至少需要的K线数（覆盖 MACD 慢线 + 信号线的预热期）
+pub const SIGNAL_WARMUP_BARS: usize = MACD_SLOW_PERIOD + MACD_SIGNAL_PERIOD;
+/// 统计的最长持有期（交易日）
+pub const MAX_SIGNAL_HORIZON: usize = 5;
+/// 分组统计时每组至少需要的样本数
+const MIN_CONDITION_OCCURRENCES: usize = 3;
+/// 趋势分组使用的均线周期
+const TREND_MA_PERIOD: usize = 20;
+/// 成交量超过前 5 日均量该倍数视为放量
+const VOLUME_SURGE_RATIO: f64 = 1.5;
+
+/// 可检验的技术信号
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SignalType {
+    /// DIF 上穿 DEA
+    MacdGoldenCross,
+    /// K 上穿 D
+    KdjGoldenCross,
+    /// RSI 由阈值之上跌破阈值（只计进入超卖区的首日，避免连续多日重复计数）
+    RsiBelowThreshold(f64),
+    /// 最低价触及布林带下轨（只计首次触及日）
+    BollingerBandTouch,
+}
+
+/// 信号胜率报告；准确率为收盘价高于信号日收盘价的比例，收益率为百分比
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignalAccuracyReport {
+    pub occurrences: usize,
+    pub accuracy_t1: f64,
+    pub accuracy_t3: f64,
+    pub accuracy_t5: f64,
+    pub avg_return_t5: f64,
+    /// T+5 胜率最高的市场环境（趋势/量能分组），样本不足时说明原因
+    pub best_performing_condition: String,
+}
+
+/// 环境分组：标签 + 判定条件
+type ConditionFilter = (&'static str, fn(&SignalOutcome) -> bool);
+
+/// 单次信号出现的后续表现
+#[derive(Debug, Clone, Copy)]
+struct SignalOutcome {
+    /// 各持有期收益率（%），未来数据不足时为 None
+    returns: [Option<f64>; 3],
+    above_ma: bool,
+    volume_surge: bool,
+}
+
+/// 信号检测所需的逐日指标
+struct IndicatorSeries {
+    dif: Vec<Option<f64>>,
+    dea: Vec<Option<f64>>,
+    kdj: Vec<Option<(f64, f64)>>,
+}
+
+impl IndicatorSeries {
+    fn new(historical: &[HistoricalData]) -> Self {
+        let len = historical.len();
+        let closes: Vec<f64> = historical.iter().map(|h| h.close).collect();
+        let highs: Vec<f64> = historical.iter().map(|h| h.high).collect();
+        let lows: Vec<f64> = historical.iter().map(|h| h.low).collect();
+
+        // DIF 首个元素对应第 MACD_SLOW_PERIOD 根K线，DEA 再晚 MACD_SIGNAL_PERIOD - 1 根
+        let dif_series = calculate_dif_series(&closes);
+        let dea_series = calculate_ema_series(&dif_series, MACD_SIGNAL_PERIOD);
+        let mut dif = vec![None; len];
+        let mut dea = vec![None; len];
+        for (offset, value) in dif_series.iter().enumerate() {
+            dif[MACD_SLOW_PERIOD - 1 + offset] = Some(*value);
+        }
+        for (offset, value) in dea_series.iter().enumerate() {
+            dea[MACD_SLOW_PERIOD + MACD_SIGNAL_PERIOD - 2 + offset] = Some(*value);
+        }
+
+        let mut kdj = vec![None; len];
+        let kdj_series =
+            calculate_kdj_series(&highs, &lows, &closes, KDJ_PERIOD, KDJ_K_SMOOTH, KDJ_D_SMOOTH);
+        for (offset, value) in kdj_series.iter().enumerate() {
+            kdj[KDJ_PERIOD - 1 + offset] = Some((value.k, value.d));
+        }
+
+        Self { dif, dea, kdj }
+    }
+}
+
+/// 第 `i` 根K线当日是否出现信号（需要 `i >= 1`）
+fn signal_fires(
+    signal: SignalType,
+    historical: &[HistoricalData],
+    series: &IndicatorSeries,
+    closes: &[f64],
+    i: usize,
+) -> bool {
+    match signal {
+        SignalType::MacdGoldenCross => {
+            match (series.dif[i - 1], series.dea[i - 1], series.dif[i], series.dea[i]) {
+                (Some(pd), Some(pe), Some(cd), Some(ce)) => is_golden_cross(pd, pe, cd, ce),
+                _ => false,
+            }
+        }
+        SignalType::KdjGoldenCross => match (series.kdj[i - 1], series.kdj[i]) {
+            (Some((pk, pd)), Some((ck, cd))) => is_kdj_golden_cross(pk, pd, ck, cd),
+            _ => false,
+        },
+        SignalType::RsiBelowThreshold(threshold) => {
+            if i < RSI_PERIOD + 1 {
+                return false;
+            }
+            let prev = calculate_rsi_with_period(&closes[..i], RSI_PERIOD);
+            let curr = calculate_rsi_with_period(&closes[..=i], RSI_PERIOD);
+            prev >= threshold && curr < threshold
+        }
+        SignalType::BollingerBandTouch => {
+            if i < BOLLINGER_PERIOD {
+                return false;
+            }
+            let touches = |idx: usize| {
+                let bands =
+                    calculate_bollinger_bands(&closes[..=idx], BOLLINGER_PERIOD, BOLLINGER_STD_DEV);
+                historical[idx].low <= bands.lower
+            };
+            touches(i) && !touches(i - 1)
+        }
+    }
+}
+
+/// 统计最近 `lookback_days` 根K线内信号的历次表现（`historical` 按日期升序）。
+///
+/// 更早的K线只用于指标预热；信号日之后不足 N 日的样本不计入 T+N 胜率。
+pub fn evaluate_signal_history(
+    historical: &[HistoricalData],
+    signal: SignalType,
+    lookback_days: usize,
+) -> SignalAccuracyReport {
+    let closes: Vec<f64> = historical.iter().map(|h| h.close).collect();
+    let series = IndicatorSeries::new(historical);
+    let start = historical
+        .len()
+        .saturating_sub(lookback_days)
+        .max(SIGNAL_WARMUP_BARS)
+        .max(1);
+
+    let mut outcomes = Vec::new();
+    for i in start..historical.len().saturating_sub(1) {
+        if !signal_fires(signal, historical, &series, &closes, i) {
+            continue;
+        }
+        let base = closes[i];
+        let returns = [1, 3, MAX_SIGNAL_HORIZON].map(|horizon| {
+            closes
+                .get(i + horizon)
+                .map(|future| (future - base) / base * 100.0)
+        });
+        let ma_start = (i + 1).saturating_sub(TREND_MA_PERIOD);
+        let ma = closes[ma_start..=i].iter().sum::<f64>() / (i + 1 - ma_start) as f64;
+        let vol_start = i.saturating_sub(5);
+        let prev_volumes = &historical[vol_start..i];
+        let avg_volume = prev_volumes.iter().map(|h| h.volume as f64).sum::<f64>()
+            / prev_volumes.len().max(1) as f64;
+        outcomes.push(SignalOutcome {
+            returns,
+            above_ma: base >= ma,
+            volume_surge: avg_volume > 0.0
+                && historical[i].volume as f64 >= avg_volume * VOLUME_SURGE_RATIO,
+        });
+    }
+
+    summarize_outcomes(&outcomes)
+}
+
+/// 指定持有期的胜率与平均收益率（%）；无样本时为 0
+fn horizon_stats<'a>(
+    outcomes: impl Iterator<Item = &'a SignalOutcome>,
+    horizon_index: usize,
+) -> (usize, f64, f64) {
+    let returns: Vec<f64> = outcomes
+        .filter_map(|outcome| outcome.returns[horizon_index])
+        .collect();
+    if returns.is_empty() {
+        return (0, 0.0, 0.0);
+    }
+    let wins = returns.iter().filter(|r| **r > 0.0).count();
+    let count = returns.len();
+    (
+        count,
+        wins as f64 / count as f64,
+        returns.iter().sum::<f64>() / count as f64,
+    )
+}
+
+fn summarize_outcomes(outcomes: &[SignalOutcome]) -> SignalAccuracyReport {
+    let (_, accuracy_t1, _) = horizon_stats(outcomes.iter(), 0);
+    let (_, accuracy_t3, _) = horizon_stats(outcomes.iter(), 1);
+    let (_, accuracy_t5, avg_return_t5) = horizon_stats(outcomes.iter(), 2);
+
+    let conditions: [ConditionFilter; 4] = [
+        ("收盘价位于 MA20 上方", |o| o.above_ma),
+        ("收盘价位于 MA20 下方", |o| !o.above_ma),
+        ("放量（≥前5日均量1.5倍）", |o| o.volume_surge),
+        ("未放量", |o| !o.volume_surge),
+    ];
+    let best = conditions
+        .iter()
+        .map(|(label, matches)| {
+            let (count, accuracy, _) =
+                horizon_stats(outcomes.iter().filter(|o| matches(o)), 2);
+            (*label, count, accuracy)
+        })
+        .filter(|(_, count, _)| *count >= MIN_CONDITION_OCCURRENCES)
+        .max_by(|a, b| a.2.total_cmp(&b.2));
+    let best_performing_condition = match best {
+        Some((label, count, accuracy)) => {
+            format!("{label}（{count} 次，T+5 胜率 {:.1}%）", accuracy * 100.0)
+        }
+        None => format!("样本不足：各环境分组均少于 {MIN_CONDITION_OCCURRENCES} 次"),
+    };
+
+    SignalAccuracyReport {
+        occurrences: outcomes.len(),
+        accuracy_t1,
+        accuracy_t3,
+        accuracy_t5,
+        avg_return_t5,
+        best_performing_condition,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, NaiveDate};
+
+    fn history(closes: &[f64]) -> Vec<HistoricalData> {
+        let start = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+        closes
+            .iter()
+            .enumerate()
+            .map(|(i, &close)| HistoricalData {
+                symbol: "test".to_string(),
+                date: start + Duration::days(i as i64),
+                open: close,
+                close,
+                high: close + 0.2,
+                low: close - 0.2,
+                volume: 10_000,
+                amount: close * 10_000.0,
+                amplitude: 1.0,
+                turnover_rate: 1.0,
+                volume_ratio: 1.0,
+                change_percent: 0.0,
+                change: 0.0,
+            })
+            .collect()
+    }
+
+    fn outcome(t5: f64, above_ma: bool) -> SignalOutcome {
+        SignalOutcome {
+            returns: [Some(t5), Some(t5), Some(t5)],
+            above_ma,
+            volume_surge: false,
+        }
+    }
+
+    #[test]
+    fn test_summary_picks_condition_with_best_t5_accuracy() {
+        let outcomes = [
+            outcome(2.0, true),
+            outcome(1.0, true),
+            outcome(3.0, true),
+            outcome(-1.0, false),
+            outcome(-2.0, false),
+            outcome(1.0, false),
+        ];
+
+        let report = summarize_outcomes(&outcomes);
+
+        assert_eq!(report.occurrences, 6);
+        assert!((report.accuracy_t5 - 4.0 / 6.0).abs() < 1e-9);
+        assert!((report.avg_return_t5 - 4.0 / 6.0).abs() < 1e-9);
+        assert!(report.best_performing_condition.starts_with("收盘价位于 MA20 上方"));
+    }
+
+    #[test]
+    fn test_macd_golden_cross_after_downtrend_reversal() {
+        // 加速下跌后反转上涨：DIF 只在反转后上穿 DEA 一次，信号后价格持续走高
+        let bottom = 20.0 - 0.002 * 59.0 * 59.0;
+        let closes: Vec<f64> = (0..60)
+            .map(|i| 20.0 - 0.002 * (i * i) as f64)
+            .chain((1..=30).map(|i| bottom + i as f64 * 0.2))
+            .collect();
+
+        let report = evaluate_signal_history(&history(&closes), SignalType::MacdGoldenCross, 90);
+
+        assert_eq!(report.occurrences, 1);
+        assert_eq!(report.accuracy_t1, 1.0);
+        assert_eq!(report.accuracy_t5, 1.0);
+        assert!(report.avg_return_t5 > 0.0);
+    }
+
+    #[test]
+    fn test_no_occurrences_yields_empty_report() {
+        let closes: Vec<f64> = (0..80).map(|i| 10.0 + i as f64 * 0.1).collect();
+
+        let report = evaluate_signal_history(
+            &history(&closes),
+            SignalType::RsiBelowThreshold(30.0),
+            60,
+        );
+
+        assert_eq!(report.occurrences, 0);
+        assert_eq!(report.accuracy_t5, 0.0);
+        assert!(report.best_performing_condition.starts_with("样本不足"));
+    }
+}
@@ -4,7 +4,10 @@
 //! → 合成打分 → 排序选相对强弱。这是经验证有正样本外 IC 的方法。
 
 use crate::db::models::HistoricalData;
-use crate::prediction::factor::{compute_factor_row, factor_dim, FACTOR_LOOKBACK};
+use crate::prediction::advanced_features::percentile_ranks;
+use crate::prediction::factor::{
+    compute_factor_row, factor_dim, BenchmarkCloses, FACTOR_LOOKBACK, RS_RANK_FACTOR,
+};
 use chrono::NaiveDate;
 use std::collections::BTreeMap;
 
@@ -70,9 +73,10 @@ const MIN_CROSS: usize = 5;
 /// 构建按日截面面板：逐日做因子截面 z-score。
 ///
 /// `horizon`>0 时计算未来收益（评估用）；=0 时 fwd_return 记为 0（仅用于打分）。
-/// 返回按日期升序的截面序列。
+/// `benchmark` 为相对强弱因子的基准指数收盘价。返回按日期升序的截面序列。
 pub fn build_panel(
     stocks: &[(String, Vec<HistoricalData>)],
+    benchmark: &BenchmarkCloses,
     horizon: usize,
 ) -> Vec<Vec<PanelRow>> {
     let dim = factor_dim();
@@ -86,7 +90,7 @@ pub fn build_panel(
             len
         };
         for i in FACTOR_LOOKBACK..end {
-            if let Some(f) = compute_factor_row(hist, i) {
+            if let Some(f) = compute_factor_row(hist, i, benchmark) {
                 let base = hist[i].close;
                 let fwd = if horizon > 0 && base > 0.0 {
                     (hist[i + horizon].close - base) / base
@@ -111,8 +115,12 @@ pub fn build_panel(
     panel
 }
 
-/// 对一个截面做因子 z-score
-fn standardize(date: NaiveDate, items: Vec<(String, Vec<f64>, f64)>, dim: usize) -> Vec<PanelRow> {
+/// 对一个截面做因子 z-score（相对强弱因子先转为截面百分位排名）
+fn standardize(date: NaiveDate, mut items: Vec<(String, Vec<f64>, f64)>, dim: usize) -> Vec<PanelRow> {
+    let returns: Vec<f64> = items.iter().map(|(_, f, _)| f[RS_RANK_FACTOR]).collect();
+    for ((_, f, _), rank) in items.iter_mut().zip(percentile_ranks(&returns)) {
+        f[RS_RANK_FACTOR] = rank;
+    }
     let n = items.len() as f64;
     let mut mean = vec![0.0; dim];
     let mut std = vec![0.0; dim];
@@ -353,10 +361,11 @@ pub fn walk_forward_orthogonalized(
 /// 前向滚动输出每个样本外交易日的截面排名信号。
 pub fn walk_forward_rank_signals(
     stocks: &[(String, Vec<HistoricalData>)],
+    benchmark: &BenchmarkCloses,
     horizon: usize,
     window: usize,
 ) -> Vec<CrossSectionRankSignal> {
-    let hist_panel = build_panel(stocks, horizon.max(1));
+    let hist_panel = build_panel(stocks, benchmark, horizon.max(1));
     if hist_panel.len() < window.max(20) {
         return Vec::new();
     }
@@ -415,9 +424,11 @@ pub fn daily_bias_from_rank(rank: usize, total: usize) -> f64 {
 
 /// 生产打分：用最近 `window` 个截面日（带未来收益）估权重，对**最新交易日**截面打分排序。
 ///
-/// `stocks` 为各股票按日期升序的历史数据；`horizon` 为权重估计所用的未来收益周期。
+/// `stocks` 为各股票按日期升序的历史数据；`benchmark` 为基准指数收盘价（见 [`build_panel`]）；
+/// `horizon` 为权重估计所用的未来收益周期。
 pub fn rank_latest(
     stocks: &[(String, Vec<HistoricalData>)],
+    benchmark: &BenchmarkCloses,
     horizon: usize,
     window: usize,
 ) -> Vec<RankedStock> {
@@ -445,7 +456,7 @@ pub fn rank_latest(
         .collect();
 
     // 1. 历史面板（带 fwd）→ 估权重
-    let hist_panel = build_panel(&training_stocks, horizon);
+    let hist_panel = build_panel(&training_stocks, benchmark, horizon);
     if hist_panel.len() < window.max(20) {
         return Vec::new();
     }
@@ -460,7 +471,7 @@ pub fn rank_latest(
         if hist.last().is_none_or(|bar| bar.date != ranking_date) {
             continue;
         }
-        if let Some(f) = compute_factor_row(hist, hist.len() - 1) {
+        if let Some(f) = compute_factor_row(hist, hist.len() - 1, benchmark) {
             latest.push((sym.clone(), f, 0.0));
         }
     }
@@ -584,6 +595,26 @@ mod tests {
         assert!(report.long_short_spread > 0.0);
     }
 
+    #[test]
+    fn test_standardize_ranks_relative_strength() {
+        let dim = factor_dim();
+        // 含极端值的 20 日收益：排名后 z-score 对称，不受离群值放大
+        let items: Vec<(String, Vec<f64>, f64)> = [0.3, -0.1, 0.05, 10.0, 0.0]
+            .iter()
+            .enumerate()
+            .map(|(s, &ret)| {
+                let mut factors = vec![0.0; dim];
+                factors[RS_RANK_FACTOR] = ret;
+                (format!("s{s}"), factors, 0.0)
+            })
+            .collect();
+        let rows = standardize(NaiveDate::MIN, items, dim);
+        let z: Vec<f64> = rows.iter().map(|r| r.factors[RS_RANK_FACTOR]).collect();
+        assert!((z[3] + z[1]).abs() < 1e-9);
+        assert!(z[3] > z[0] && z[0] > z[2] && z[2] > z[4] && z[4] > z[1]);
+        assert!((z[3] - 2.0f64.sqrt()).abs() < 1e-9);
+    }
+
     #[test]
     fn test_daily_bias_from_rank_boundaries() {
         assert_eq!(daily_bias_from_rank(0, 10), 0.0);
@@ -604,7 +635,7 @@ mod tests {
             .collect::<Vec<_>>();
         stocks.push(("stale".to_string(), make_history("stale", 59, 10.0)));
 
-        let ranking = rank_latest(&stocks, 1, 20);
+        let ranking = rank_latest(&stocks, &BenchmarkCloses::new(), 1, 20);
 
         assert!(!ranking.is_empty());
         assert_eq!(ranking.len(), 5);
@@ -630,7 +661,7 @@ mod tests {
             (symbol, hist)
         }));
 
-        let ranking = rank_latest(&stocks, 1, 20);
+        let ranking = rank_latest(&stocks, &BenchmarkCloses::new(), 1, 20);
 
         // 回退到主体共同日 D：18 只全部参与（而非塌缩到 6 只新票）。
         assert_eq!(ranking.len(), 18);
@@ -650,7 +681,7 @@ mod tests {
             (symbol, make_history(&format!("fallback{idx}"), 59, idx as f64 + 4.0))
         }));
 
-        let ranking = rank_latest(&stocks, 1, 20);
+        let ranking = rank_latest(&stocks, &BenchmarkCloses::new(), 1, 20);
 
         assert_eq!(ranking.len(), 6);
         assert!(ranking.iter().any(|ranked| ranked.symbol == "fallback0"));
@@ -675,7 +706,7 @@ mod tests {
             (symbol, hist)
         }));
 
-        let ranking = rank_latest(&stocks, 1, 20);
+        let ranking = rank_latest(&stocks, &BenchmarkCloses::new(), 1, 20);
 
         assert_eq!(ranking.len(), 5);
         assert!(ranking.iter().all(|ranked| ranked.symbol.starts_with("seasoned")));
@@ -699,7 +730,7 @@ mod tests {
             (symbol, hist)
         }));
 
-        let ranking = rank_latest(&stocks, 1, 20);
+        let ranking = rank_latest(&stocks, &BenchmarkCloses::new(), 1, 20);
 
         assert_eq!(ranking.len(), 5);
         assert!(ranking.iter().all(|ranked| ranked.symbol.starts_with("seasoned")));
@@ -726,7 +757,7 @@ mod tests {
             (symbol, hist)
         }));
 
-        let ranking = rank_latest(&stocks, 1, 20);
+        let ranking = rank_latest(&stocks, &BenchmarkCloses::new(), 1, 20);
 
         assert_eq!(ranking.len(), 5);
         assert!(ranking.iter().all(|ranked| ranked.symbol.starts_with("seasoned")));
@@ -6,10 +6,22 @@
 //! 重点：包含**量比 × 换手率**的交互/组合因子，验证两者结合的预测力。
 
 use crate::db::models::HistoricalData;
+use crate::services::beta::calculate_beta;
+use chrono::NaiveDate;
+use std::collections::HashMap;
 
 /// 因子所需的最小回看窗口
 pub const FACTOR_LOOKBACK: usize = 20;
 
+/// 基准指数按交易日的收盘价（相对强弱因子的比较基准）
+pub type BenchmarkCloses = HashMap<NaiveDate, f64>;
+
+/// 截面相对强弱排名因子的下标：[`compute_factor_row`] 先填入相对指数的 20 日残差收益
+/// （见 [`residual_return20`]），截面标准化前由 `cross_section` 替换为当日截面百分位排名。
+///
+/// 同期指数收益对同一截面相同，直接相减不改变排名，故按个股 Beta 扣除指数贡献。
+pub const RS_RANK_FACTOR: usize = 16;
+
 /// 因子名称（顺序与 [`compute_factor_row`] 一致）
 pub fn factor_names() -> Vec<String> {
     [
@@ -30,6 +42,7 @@ pub fn factor_names() -> Vec<String> {
         "volprice_corr10", // 10日量价配合度（量变与价变的相关系数）
         "obv_slope10",     // OBV 10日归一化斜率（资金流方向）
         "vr_x_rangepos",   // 量比 × 区间位置去中心（高位放量出货 / 低位放量吸筹）
+        "rs_rank20",       // 相对指数的 20 日残差收益截面百分位排名（见 RS_RANK_FACTOR）
     ]
     .iter()
     .map(|s| s.to_string())
@@ -63,8 +76,33 @@ fn corr(xs: &[f64], ys: &[f64]) -> f64 {
     cov / (vx.sqrt() * vy.sqrt())
 }
 
+/// 相对指数的 20 日残差收益：20 日收益 − β × 同期指数收益，β 取同一窗口日收益回归。
+///
+/// 窗口内任一交易日缺少指数收盘价时返回 None。
+pub fn residual_return20(h: &[HistoricalData], i: usize, benchmark: &BenchmarkCloses) -> Option<f64> {
+    if i < FACTOR_LOOKBACK || i >= h.len() {
+        return None;
+    }
+    let window = &h[i - FACTOR_LOOKBACK..=i];
+    let index_closes: Vec<f64> = window
+        .iter()
+        .map(|bar| benchmark.get(&bar.date).copied().filter(|&close| close > 0.0))
+        .collect::<Option<_>>()?;
+    if window.iter().any(|bar| bar.close <= 0.0) {
+        return None;
+    }
+    let daily = |closes: &[f64]| -> Vec<f64> { closes.windows(2).map(|w| w[1] / w[0] - 1.0).collect() };
+    let stock_closes: Vec<f64> = window.iter().map(|bar| bar.close).collect();
+    let beta = calculate_beta(&daily(&stock_closes), &daily(&index_closes)).beta;
+    let stock_ret = stock_closes[FACTOR_LOOKBACK] / stock_closes[0] - 1.0;
+    let index_ret = index_closes[FACTOR_LOOKBACK] / index_closes[0] - 1.0;
+    Some(stock_ret - beta * index_ret)
+}
+
 /// 计算索引 `i` 处的原始因子向量（仅用 ≤ i 的数据）。i 须 ≥ FACTOR_LOOKBACK。
-pub fn compute_factor_row(h: &[HistoricalData], i: usize) -> Option<Vec<f64>> {
+///
+/// 缺少基准指数数据时相对强弱因子退化为 20 日收益；同一交易日各股的缺失情况一致，截面内口径不混杂。
+pub fn compute_factor_row(h: &[HistoricalData], i: usize, benchmark: &BenchmarkCloses) -> Option<Vec<f64>> {
     if i < FACTOR_LOOKBACK || i >= h.len() {
         return None;
     }
@@ -196,6 +234,7 @@ pub fn compute_factor_row(h: &[HistoricalData], i: usize) -> Option<Vec<f64>> {
         volprice_corr10,
         obv_slope10,
         vr_x_rangepos,
+        residual_return20(h, i, benchmark).unwrap_or(ret_20),
     ])
 }
 
@@ -227,23 +266,39 @@ mod tests {
     #[test]
     fn test_factor_row_dim() {
         let h = make(40);
-        let row = compute_factor_row(&h, 30).expect("应有因子");
+        let row = compute_factor_row(&h, 30, &BenchmarkCloses::new()).expect("应有因子");
         assert_eq!(row.len(), factor_dim());
         assert_eq!(factor_names().len(), factor_dim());
+        assert_eq!(factor_names()[RS_RANK_FACTOR], "rs_rank20");
         // 所有因子有限
         assert!(row.iter().all(|x| x.is_finite()));
     }
 
+    #[test]
+    fn test_residual_return20_removes_index_contribution() {
+        let h = make(40);
+        // 指数走势与个股相同：β = 1，残差收益为 0
+        let same: BenchmarkCloses = h.iter().map(|bar| (bar.date, bar.close * 100.0)).collect();
+        assert!(residual_return20(&h, 30, &same).unwrap().abs() < 1e-9);
+
+        let mut gapped = same.clone();
+        gapped.remove(&h[25].date);
+        assert!(residual_return20(&h, 30, &gapped).is_none());
+        let row = compute_factor_row(&h, 30, &gapped).unwrap();
+        let ret_20 = factor_names().iter().position(|n| n == "ret_20d").unwrap();
+        assert_eq!(row[RS_RANK_FACTOR], row[ret_20]);
+    }
+
     #[test]
     fn test_factor_row_insufficient() {
         let h = make(40);
-        assert!(compute_factor_row(&h, 10).is_none());
+        assert!(compute_factor_row(&h, 10, &BenchmarkCloses::new()).is_none());
     }
 
     #[test]
     fn test_vr_x_turnover_present() {
         let h = make(40);
-        let row = compute_factor_row(&h, 30).unwrap();
+        let row = compute_factor_row(&h, 30, &BenchmarkCloses::new()).unwrap();
         let names = factor_names();
         let idx = names.iter().position(|n| n == "vr_x_turnover").unwrap();
         // 量比≈1，换手5日均=3 → 交互≈3
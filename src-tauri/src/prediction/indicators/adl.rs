@@ -0,0 +1,102 @@
+//! Chaikin 累积/派发线（A/D Line）与 Chaikin 振荡器
+//!
+//! - 资金流乘数 = ((收盘 - 最低) - (最高 - 收盘)) / (最高 - 最低)
+//! - ADL 逐日累加：ADL += 资金流乘数 × 成交量
+//! - Chaikin 振荡器 = EMA(ADL, fast) - EMA(ADL, slow)，正值为吸筹、负值为派发
+
+use crate::utils::math::calculate_ema;
+
+/// Chaikin 振荡器快线周期
+pub const CHAIKIN_FAST_PERIOD: usize = 3;
+/// Chaikin 振荡器慢线周期
+pub const CHAIKIN_SLOW_PERIOD: usize = 10;
+
+/// 计算 ADL 逐日累计序列；最高价等于最低价（一字板）的当日不计入
+pub fn calculate_accumulation_distribution_line(
+    highs: &[f64],
+    lows: &[f64],
+    closes: &[f64],
+    volumes: &[i64],
+) -> Vec<f64> {
+    let len = highs
+        .len()
+        .min(lows.len())
+        .min(closes.len())
+        .min(volumes.len());
+    let mut series = Vec::with_capacity(len);
+    let mut adl = 0.0;
+    for i in 0..len {
+        let range = highs[i] - lows[i];
+        if range > 0.0 {
+            let multiplier = ((closes[i] - lows[i]) - (highs[i] - closes[i])) / range;
+            adl += multiplier * volumes[i] as f64;
+        }
+        series.push(adl);
+    }
+    series
+}
+
+/// 计算 Chaikin 振荡器：ADL 的快慢 EMA 之差；数据不足慢线周期返回 0
+pub fn calculate_chaikin_oscillator(adl: &[f64], fast: usize, slow: usize) -> f64 {
+    if fast == 0 || slow == 0 || adl.len() < fast.max(slow) {
+        return 0.0;
+    }
+    calculate_ema(adl, fast) - calculate_ema(adl, slow)
+}
+
+/// 以慢线周期内日均成交量归一化的 Chaikin 振荡器（3/10 周期），
+/// 消除股本规模差异，便于跨股票比较；数据不足或无成交返回 0
+pub fn calculate_normalized_chaikin_oscillator(
+    highs: &[f64],
+    lows: &[f64],
+    closes: &[f64],
+    volumes: &[i64],
+) -> f64 {
+    let adl = calculate_accumulation_distribution_line(highs, lows, closes, volumes);
+    if adl.len() < CHAIKIN_SLOW_PERIOD {
+        return 0.0;
+    }
+    let recent = &volumes[adl.len() - CHAIKIN_SLOW_PERIOD..adl.len()];
+    let avg_volume = recent.iter().sum::<i64>() as f64 / CHAIKIN_SLOW_PERIOD as f64;
+    if avg_volume <= 0.0 {
+        return 0.0;
+    }
+    calculate_chaikin_oscillator(&adl, CHAIKIN_FAST_PERIOD, CHAIKIN_SLOW_PERIOD) / avg_volume
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_adl_accumulates_money_flow() {
+        // 收于最高 → 乘数 +1；收于最低 → -1；收于中间 → 0；一字板不计
+        let highs = vec![11.0, 11.0, 11.0, 10.0];
+        let lows = vec![9.0, 9.0, 9.0, 10.0];
+        let closes = vec![11.0, 9.0, 10.0, 10.0];
+        let volumes = vec![100, 40, 500, 900];
+        let adl = calculate_accumulation_distribution_line(&highs, &lows, &closes, &volumes);
+        assert_eq!(adl, vec![100.0, 60.0, 60.0, 60.0]);
+    }
+
+    #[test]
+    fn test_chaikin_oscillator_sign() {
+        let n = 30;
+        let highs = vec![11.0; n];
+        let lows = vec![9.0; n];
+        let volumes = vec![1000; n];
+
+        // 前段收于低位派发，后段收于高位吸筹 → 快线领先，振荡器为正
+        let closes: Vec<f64> = (0..n).map(|i| if i < 20 { 9.2 } else { 10.8 }).collect();
+        let adl = calculate_accumulation_distribution_line(&highs, &lows, &closes, &volumes);
+        assert!(calculate_chaikin_oscillator(&adl, 3, 10) > 0.0);
+        let normalized = calculate_normalized_chaikin_oscillator(&highs, &lows, &closes, &volumes);
+        assert!(normalized > 0.0);
+
+        let closes: Vec<f64> = (0..n).map(|i| if i < 20 { 10.8 } else { 9.2 }).collect();
+        let adl = calculate_accumulation_distribution_line(&highs, &lows, &closes, &volumes);
+        assert!(calculate_chaikin_oscillator(&adl, 3, 10) < 0.0);
+
+        assert_eq!(calculate_chaikin_oscillator(&adl[..5], 3, 10), 0.0);
+    }
+}
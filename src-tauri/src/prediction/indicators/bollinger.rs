@@ -57,12 +57,91 @@ pub fn calculate_bollinger_position(prices: &[f64], current_price: f64) -> f64 {
     }
 }
 
-/// 计算布林带宽度
-pub fn calculate_bandwidth(bands: &BollingerBands) -> f64 {
+/// 收缩判定分位：带宽低于回看窗口第 20 百分位视为收缩
+const SQUEEZE_PERCENTILE: f64 = 20.0;
+
+/// 布林带收缩（Squeeze）检测结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BollingerSqueeze {
+    /// 当前带宽低于回看窗口第 20 百分位
+    pub is_squeezed: bool,
+    /// 当前带宽在回看窗口中的百分位（0-100）
+    pub bandwidth_percentile: f64,
+    /// 截至当日连续处于收缩状态的天数
+    pub days_in_squeeze: usize,
+    /// 收缩强度（0-1）：1 - 当前带宽 / 回看窗口平均带宽，未收缩为 0
+    pub squeeze_intensity: f64,
+}
+
+impl Default for BollingerSqueeze {
+    fn default() -> Self {
+        Self {
+            is_squeezed: false,
+            bandwidth_percentile: 50.0,
+            days_in_squeeze: 0,
+            squeeze_intensity: 0.0,
+        }
+    }
+}
+
+/// 计算布林带宽度：(上轨 - 下轨) / 中轨；数据不足或中轨为 0 返回 0
+pub fn calculate_bandwidth(prices: &[f64], period: usize, std_dev: f64) -> f64 {
+    if period == 0 || prices.len() < period {
+        return 0.0;
+    }
+    let bands = calculate_bollinger_bands(prices, period, std_dev);
     if bands.middle == 0.0 {
         0.0
     } else {
-        (bands.upper - bands.lower) / bands.middle * 100.0
+        (bands.upper - bands.lower) / bands.middle
+    }
+}
+
+/// 计算布林带宽度逐日序列，首个元素对应第 `period` 根 K 线
+pub fn calculate_bandwidth_series(prices: &[f64], period: usize, std_dev: f64) -> Vec<f64> {
+    if period == 0 || prices.len() < period {
+        return Vec::new();
+    }
+    (period..=prices.len())
+        .map(|end| calculate_bandwidth(&prices[..end], period, std_dev))
+        .collect()
+}
+
+/// 检测布林带收缩：当前带宽（2 倍标准差）低于近 `lookback` 日带宽的第 20 百分位。
+/// 收缩往往预示大幅波动即将到来；数据不足 `period + lookback - 1` 时返回未收缩
+pub fn detect_bollinger_squeeze(prices: &[f64], period: usize, lookback: usize) -> BollingerSqueeze {
+    let series = calculate_bandwidth_series(prices, period, 2.0);
+    if lookback < 2 || series.len() < lookback {
+        return BollingerSqueeze::default();
+    }
+
+    let window = &series[series.len() - lookback..];
+    let current = window[lookback - 1];
+    let below = window.iter().filter(|&&bw| bw < current).count();
+    let bandwidth_percentile = below as f64 / lookback as f64 * 100.0;
+
+    let mut sorted = window.to_vec();
+    sorted.sort_by(|a, b| a.total_cmp(b));
+    let threshold = sorted[((lookback - 1) as f64 * SQUEEZE_PERCENTILE / 100.0).round() as usize];
+    let is_squeezed = bandwidth_percentile < SQUEEZE_PERCENTILE;
+    let days_in_squeeze = if is_squeezed {
+        window.iter().rev().take_while(|&&bw| bw <= threshold).count()
+    } else {
+        0
+    };
+
+    let mean = window.iter().sum::<f64>() / lookback as f64;
+    let squeeze_intensity = if is_squeezed && mean > 0.0 {
+        (1.0 - current / mean).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+
+    BollingerSqueeze {
+        is_squeezed,
+        bandwidth_percentile,
+        days_in_squeeze,
+        squeeze_intensity,
     }
 }
 
@@ -91,6 +170,40 @@ mod tests {
         assert!(bands.middle > bands.lower);
     }
 
+    #[test]
+    fn test_bandwidth_series() {
+        let prices: Vec<f64> = (0..30).map(|i| 10.0 + (i % 3) as f64).collect();
+        let series = calculate_bandwidth_series(&prices, 20, 2.0);
+        assert_eq!(series.len(), 11);
+        assert!((series[10] - calculate_bandwidth(&prices, 20, 2.0)).abs() < 1e-12);
+        assert_eq!(calculate_bandwidth(&[10.0; 20], 20, 2.0), 0.0);
+    }
+
+    #[test]
+    fn test_detect_bollinger_squeeze() {
+        // 前段大幅震荡，最后 8 日几乎横盘 → 带宽降至回看窗口低位
+        let mut prices: Vec<f64> = (0..100)
+            .map(|i| 10.0 + if i % 2 == 0 { 1.0 } else { -1.0 })
+            .collect();
+        prices.extend((0..30).map(|i| 10.0 + (i % 2) as f64 * 0.05));
+        let squeeze = detect_bollinger_squeeze(&prices, 20, 60);
+        assert!(squeeze.is_squeezed);
+        assert!(squeeze.bandwidth_percentile < 20.0);
+        assert!(squeeze.days_in_squeeze >= 1);
+        assert!(squeeze.squeeze_intensity > 0.5);
+
+        // 波动持续放大：带宽处于窗口高位
+        let expanding: Vec<f64> = (0..100)
+            .map(|i| 10.0 + if i % 2 == 0 { 0.01 * i as f64 } else { -0.01 * i as f64 })
+            .collect();
+        let squeeze = detect_bollinger_squeeze(&expanding, 20, 60);
+        assert!(!squeeze.is_squeezed);
+        assert_eq!(squeeze.days_in_squeeze, 0);
+        assert_eq!(squeeze.squeeze_intensity, 0.0);
+
+        assert!(!detect_bollinger_squeeze(&prices[..30], 20, 60).is_squeezed);
+    }
+
     #[test]
     fn test_bollinger_position() {
         let prices = vec![10.0; 20];
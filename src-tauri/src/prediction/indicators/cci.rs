@@ -6,31 +6,77 @@
 //! MD = TP 的 N 日平均偏差
 //! CCI = (TP - MA) / (0.015 × MD)
 
-/// 计算 CCI 指标
-pub fn calculate_cci(highs: &[f64], lows: &[f64], closes: &[f64], period: usize) -> f64 {
-    if highs.len() < period || lows.len() < period || closes.len() < period {
+use super::TradingSignal;
+
+/// Lambert 常数：使约 70%-80% 的 CCI 值落在 ±100 之间
+pub const CCI_LAMBERT_CONSTANT: f64 = 0.015;
+
+/// 平均偏差相对均值低于该比例时视为 0：窗口内典型价相同时，均值的舍入误差会留下
+/// 极小的偏差，使 CCI 变成 ±1/0.015 ≈ ±66.7 的伪信号
+const CCI_FLAT_TOLERANCE: f64 = 1e-12;
+
+/// 由典型价窗口计算末值的 CCI；平均偏差为平均绝对偏差（非标准差），为 0 时返回 0
+fn cci_of_window(tp_window: &[f64]) -> f64 {
+    let ma = tp_window.iter().sum::<f64>() / tp_window.len() as f64;
+    let md = tp_window.iter().map(|&tp| (tp - ma).abs()).sum::<f64>() / tp_window.len() as f64;
+    if md <= ma.abs() * CCI_FLAT_TOLERANCE {
         return 0.0;
     }
-    
-    let start = highs.len().saturating_sub(period);
-    let mut tp_values = Vec::with_capacity(period);
-    
-    for i in start..highs.len() {
-        let tp = (highs[i] + lows[i] + closes[i]) / 3.0;
-        tp_values.push(tp);
+    let current_tp = tp_window[tp_window.len() - 1];
+    (current_tp - ma) / (CCI_LAMBERT_CONSTANT * md)
+}
+
+/// 计算 CCI 序列：第一个值对应第 `period` 根K线，长度为 `len - period + 1`；数据不足时返回空
+pub fn calculate_cci_series(highs: &[f64], lows: &[f64], closes: &[f64], period: usize) -> Vec<f64> {
+    let tp_values: Vec<f64> = highs
+        .iter()
+        .zip(lows)
+        .zip(closes)
+        .map(|((h, l), c)| (h + l + c) / 3.0)
+        .collect();
+    if period == 0 || tp_values.len() < period {
+        return Vec::new();
     }
-    
-    let ma = tp_values.iter().sum::<f64>() / tp_values.len() as f64;
-    let md = tp_values.iter()
-        .map(|&tp| (tp - ma).abs())
-        .sum::<f64>() / tp_values.len() as f64;
-    
-    if md == 0.0 {
+    tp_values.windows(period).map(cci_of_window).collect()
+}
+
+/// 计算 CCI 指标（最新值）
+pub fn calculate_cci(highs: &[f64], lows: &[f64], closes: &[f64], period: usize) -> f64 {
+    let n = highs.len().min(lows.len()).min(closes.len());
+    if period == 0 || n < period {
         return 0.0;
     }
-    
-    let current_tp = (highs.last().unwrap() + lows.last().unwrap() + closes.last().unwrap()) / 3.0;
-    (current_tp - ma) / (0.015 * md)
+    let start = n - period;
+    calculate_cci_series(&highs[start..n], &lows[start..n], &closes[start..n], period)
+        .last()
+        .copied()
+        .unwrap_or(0.0)
+}
+
+/// CCI 交易信号：
+/// - 高于 +100 超买看空，自高位回落时为强烈卖出
+/// - 低于 -100 超卖看多，自低位回升时为强烈买入
+/// - ±100 之间上穿零轴买入、下穿零轴卖出，其余持有
+pub fn cci_signal(cci: f64, prev_cci: f64) -> TradingSignal {
+    if cci > 100.0 {
+        if cci < prev_cci {
+            TradingSignal::StrongSell
+        } else {
+            TradingSignal::Sell
+        }
+    } else if cci < -100.0 {
+        if cci > prev_cci {
+            TradingSignal::StrongBuy
+        } else {
+            TradingSignal::Buy
+        }
+    } else if prev_cci <= 0.0 && cci > 0.0 {
+        TradingSignal::Buy
+    } else if prev_cci >= 0.0 && cci < 0.0 {
+        TradingSignal::Sell
+    } else {
+        TradingSignal::Hold
+    }
 }
 
 /// 判断 CCI 超买
@@ -72,5 +118,31 @@ mod tests {
         // 上涨趋势，CCI 应该为正
         assert!(cci > 0.0);
     }
+
+    #[test]
+    fn test_cci_matches_reference_calculation() {
+        // 典型价 1, 2, 6：MA = 3，平均绝对偏差 = (2 + 1 + 3) / 3 = 2，
+        // CCI = (6 - 3) / (0.015 × 2) = 100；若误用标准差（√(14/3)）则约为 92.58
+        let tp = [1.0, 2.0, 6.0];
+        assert!((calculate_cci(&tp, &tp, &tp, 3) - 100.0).abs() < 1e-9);
+
+        // 典型价 6, 1, 2：MA = 3，MD = 2，CCI = (2 - 3) / 0.03
+        let tp = [1.0, 2.0, 6.0, 1.0, 2.0];
+        let series = calculate_cci_series(&tp, &tp, &tp, 3);
+        assert_eq!(series.len(), 3);
+        assert!((series[0] - 100.0).abs() < 1e-9);
+        assert!((series[2] - (-1.0 / 0.03)).abs() < 1e-9);
+        assert_eq!(calculate_cci(&tp, &tp, &tp, 3), series[2]);
+    }
+
+    #[test]
+    fn test_cci_signal() {
+        assert_eq!(cci_signal(150.0, 180.0), TradingSignal::StrongSell);
+        assert_eq!(cci_signal(150.0, 120.0), TradingSignal::Sell);
+        assert_eq!(cci_signal(-150.0, -180.0), TradingSignal::StrongBuy);
+        assert_eq!(cci_signal(20.0, -10.0), TradingSignal::Buy);
+        assert_eq!(cci_signal(-20.0, 10.0), TradingSignal::Sell);
+        assert_eq!(cci_signal(50.0, 30.0), TradingSignal::Hold);
+    }
 }
 
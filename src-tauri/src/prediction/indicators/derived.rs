@@ -0,0 +1,197 @@
+//! 派生特征：滞后特征、滚动统计特征与交叉特征
+//!
+//! 派生特征以名称编码，由 `calculate_feature_value` 按名称解析计算：
+//! - 滞后：`{基础特征}_lag_{n}`，如 `close_lag_5`、`rsi_lag_3`（取 n 个交易日前的基础特征值）
+//! - 滚动统计：`{基础特征}_rolling_{mean|std|min|max}_{w}`，如 `close_rolling_std_20`
+//! - 交叉：`price_to_ma20_ratio`（收盘价 / 20 日均线）、`volume_to_vol_ma5_ratio`（成交量 / 5 日均量）
+//!
+//! 只引用 `index` 及更早的数据，不引入未来信息。
+
+use serde::{Deserialize, Serialize};
+
+use crate::utils::math::calculate_std_dev;
+
+/// 收盘价相对 20 日均线
+pub const PRICE_TO_MA20_RATIO: &str = "price_to_ma20_ratio";
+/// 成交量相对 5 日均量
+pub const VOLUME_TO_VOL_MA5_RATIO: &str = "volume_to_vol_ma5_ratio";
+
+/// 滚动统计量
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RollingStat {
+    Mean,
+    Std,
+    Min,
+    Max,
+}
+
+impl RollingStat {
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Mean => "mean",
+            Self::Std => "std",
+            Self::Min => "min",
+            Self::Max => "max",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "mean" => Some(Self::Mean),
+            "std" => Some(Self::Std),
+            "min" => Some(Self::Min),
+            "max" => Some(Self::Max),
+            _ => None,
+        }
+    }
+
+    /// 对窗口内取值计算统计量；空窗口返回 0
+    pub fn apply(self, values: &[f64]) -> f64 {
+        if values.is_empty() {
+            return 0.0;
+        }
+        match self {
+            Self::Mean => values.iter().sum::<f64>() / values.len() as f64,
+            Self::Std => calculate_std_dev(values),
+            Self::Min => values.iter().copied().fold(f64::INFINITY, f64::min),
+            Self::Max => values.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+        }
+    }
+}
+
+/// 生成滞后特征名：`add_lag_features("close", &[1, 5])` → `["close_lag_1", "close_lag_5"]`
+pub fn add_lag_features(base_feature: &str, lags: &[usize]) -> Vec<String> {
+    lags.iter()
+        .filter(|&&lag| lag > 0)
+        .map(|lag| format!("{base_feature}_lag_{lag}"))
+        .collect()
+}
+
+/// 生成滚动统计特征名（窗口 × 统计量）：
+/// `add_rolling_stat_features("close", &[5], &[RollingStat::Mean])` → `["close_rolling_mean_5"]`
+pub fn add_rolling_stat_features(
+    base_feature: &str,
+    windows: &[usize],
+    stats: &[RollingStat],
+) -> Vec<String> {
+    windows
+        .iter()
+        .filter(|&&window| window > 0)
+        .flat_map(|window| {
+            stats
+                .iter()
+                .map(move |stat| format!("{base_feature}_rolling_{}_{window}", stat.name()))
+        })
+        .collect()
+}
+
+/// 已解析的派生特征
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum DerivedFeature<'a> {
+    Lag {
+        base: &'a str,
+        lag: usize,
+    },
+    Rolling {
+        base: &'a str,
+        stat: RollingStat,
+        window: usize,
+    },
+}
+
+/// 按名称解析派生特征；非派生特征返回 None
+pub(crate) fn parse_derived_feature(feature_name: &str) -> Option<DerivedFeature<'_>> {
+    let (head, last) = feature_name.rsplit_once('_')?;
+    let n: usize = last.parse().ok().filter(|&n| n > 0)?;
+    if let Some(base) = head.strip_suffix("_lag") {
+        return (!base.is_empty()).then_some(DerivedFeature::Lag { base, lag: n });
+    }
+    let (prefix, stat) = head.rsplit_once('_')?;
+    let stat = RollingStat::from_name(stat)?;
+    let base = prefix.strip_suffix("_rolling")?;
+    (!base.is_empty()).then_some(DerivedFeature::Rolling {
+        base,
+        stat,
+        window: n,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prediction::indicators::{calculate_feature_value, get_feature_required_days};
+
+    #[test]
+    fn test_feature_name_generation_and_parsing() {
+        assert_eq!(
+            add_lag_features("close", &[1, 5]),
+            vec!["close_lag_1", "close_lag_5"]
+        );
+        assert_eq!(
+            add_rolling_stat_features("rsi", &[5, 20], &[RollingStat::Mean, RollingStat::Max]),
+            vec![
+                "rsi_rolling_mean_5",
+                "rsi_rolling_max_5",
+                "rsi_rolling_mean_20",
+                "rsi_rolling_max_20"
+            ]
+        );
+        assert_eq!(
+            parse_derived_feature("rsi_lag_3"),
+            Some(DerivedFeature::Lag {
+                base: "rsi",
+                lag: 3
+            })
+        );
+        assert_eq!(
+            parse_derived_feature("change_percent_rolling_std_10"),
+            Some(DerivedFeature::Rolling {
+                base: "change_percent",
+                stat: RollingStat::Std,
+                window: 10
+            })
+        );
+        assert_eq!(parse_derived_feature("ma20"), None);
+        assert_eq!(parse_derived_feature("close_lag_0"), None);
+        assert_eq!(parse_derived_feature("close_rolling_median_5"), None);
+    }
+
+    #[test]
+    fn test_derived_feature_values() {
+        let prices: Vec<f64> = (1..=30).map(f64::from).collect();
+        let volumes: Vec<i64> = (1..=30).map(|v| v * 100).collect();
+        let value = |name: &str, index: usize| {
+            calculate_feature_value(name, &prices, &volumes, index, None, None, None)
+        };
+
+        assert_eq!(value("close_lag_5", 10), 6.0);
+        assert_eq!(value("close_lag_5", 3), 0.0);
+        assert_eq!(value("close_rolling_mean_5", 10), 9.0);
+        assert_eq!(value("close_rolling_min_5", 10), 7.0);
+        assert_eq!(value("close_rolling_max_5", 10), 11.0);
+        assert!((value("close_rolling_std_5", 10) - 2f64.sqrt()).abs() < 1e-9);
+        // 派生特征可作用于任意基础特征：5 日前的 ma5
+        assert_eq!(value("ma5_lag_5", 10), 4.0);
+
+        assert!((value(PRICE_TO_MA20_RATIO, 19) - 20.0 / 10.5).abs() < 1e-9);
+        assert!((value(VOLUME_TO_VOL_MA5_RATIO, 4) - 500.0 / 300.0).abs() < 1e-9);
+
+        // ETF 折溢价率按对齐净值计算，无净值时为 0；滞后特征沿用同一净值序列
+        let mut navs = vec![0.0; prices.len()];
+        navs[10] = 10.0;
+        let premium = |name: &str, index: usize| {
+            calculate_feature_value(name, &prices, &volumes, index, None, None, Some(&navs))
+        };
+        assert!((premium("etf_premium_discount", 10) - 0.1).abs() < 1e-9);
+        assert_eq!(premium("etf_premium_discount", 11), 0.0);
+        assert!((premium("etf_premium_discount_lag_1", 11) - 0.1).abs() < 1e-9);
+        assert_eq!(value("etf_premium_discount", 10), 0.0);
+
+        assert_eq!(get_feature_required_days("ma20_lag_5"), 25);
+        assert_eq!(get_feature_required_days("ma5_rolling_mean_10"), 14);
+        assert_eq!(get_feature_required_days(PRICE_TO_MA20_RATIO), 20);
+        // 未知基础特征的派生特征按未知特征处理
+        assert_eq!(get_feature_required_days("unknown_lag_5"), 1);
+    }
+}
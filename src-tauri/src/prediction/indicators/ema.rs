@@ -0,0 +1,81 @@
+//! EMA (Exponential Moving Average) 指数移动平均
+//!
+//! 以前 N 日简单均值起算，此后按 multiplier = 2 / (N + 1) 递推，最新价权重高于 SMA，
+//! 均线滞后更小。`calculate_ema` / `calculate_ema_series` 复用 `utils::math` 的实现，
+//! 与 MACD 等指标口径一致。
+
+use super::TradingSignal;
+pub use crate::utils::math::{calculate_ema, calculate_ema_series};
+
+/// 快慢 EMA 交叉状态
+#[derive(Debug, Clone, PartialEq)]
+pub struct EmaCrossover {
+    pub fast_ema: f64,
+    pub slow_ema: f64,
+    /// 最近一次交叉：金叉为 Buy，死叉为 Sell；序列内无交叉时为 None
+    pub crossover_type: Option<TradingSignal>,
+    /// 最近一次交叉距今的K线数（0 表示最新一根K线发生交叉）；无交叉时为可比较的K线数
+    pub bars_since_cross: usize,
+}
+
+/// 计算快慢 EMA 及最近一次交叉；数据不足慢线周期时两条均线为 0、无交叉
+pub fn calculate_ema_crossover(prices: &[f64], fast: usize, slow: usize) -> EmaCrossover {
+    let fast_series = calculate_ema_series(prices, fast);
+    let slow_series = calculate_ema_series(prices, slow);
+    // 两条序列右对齐到同一根K线
+    let len = fast_series.len().min(slow_series.len());
+    let fast_series = &fast_series[fast_series.len() - len..];
+    let slow_series = &slow_series[slow_series.len() - len..];
+
+    let mut crossover_type = None;
+    let mut bars_since_cross = len;
+    for i in (1..len).rev() {
+        let (prev_diff, curr_diff) = (
+            fast_series[i - 1] - slow_series[i - 1],
+            fast_series[i] - slow_series[i],
+        );
+        if prev_diff <= 0.0 && curr_diff > 0.0 {
+            crossover_type = Some(TradingSignal::Buy);
+        } else if prev_diff >= 0.0 && curr_diff < 0.0 {
+            crossover_type = Some(TradingSignal::Sell);
+        } else {
+            continue;
+        }
+        bars_since_cross = len - 1 - i;
+        break;
+    }
+
+    EmaCrossover {
+        fast_ema: fast_series.last().copied().unwrap_or(0.0),
+        slow_ema: slow_series.last().copied().unwrap_or(0.0),
+        crossover_type,
+        bars_since_cross,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ema_golden_cross_after_reversal() {
+        // 30 日单边下跌后连涨 10 日：EMA5 在倒数第 4 根K线上穿 EMA20
+        let prices: Vec<f64> = (0..30)
+            .map(|i| 20.0 - 0.5 * i as f64)
+            .chain((1..=10).map(|i| 5.5 + 0.8 * i as f64))
+            .collect();
+        let crossover = calculate_ema_crossover(&prices, 5, 20);
+        assert_eq!(crossover.crossover_type, Some(TradingSignal::Buy));
+        assert_eq!(crossover.bars_since_cross, 3);
+        assert!(crossover.fast_ema > crossover.slow_ema);
+        assert!((crossover.fast_ema - calculate_ema(&prices, 5)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_ema_crossover_insufficient_data() {
+        let crossover = calculate_ema_crossover(&[10.0; 10], 5, 20);
+        assert_eq!(crossover.crossover_type, None);
+        assert_eq!(crossover.slow_ema, 0.0);
+        assert_eq!(crossover.bars_since_cross, 0);
+    }
+}
@@ -16,42 +16,60 @@ pub struct KdjData {
     pub j: f64,
 }
 
-/// 计算 KDJ 指标
-pub fn calculate_kdj(
+/// 默认 K 值平滑周期（K = 2/3 × 前K + 1/3 × RSV）
+pub const KDJ_K_SMOOTH: usize = 3;
+/// 默认 D 值平滑周期（D = 2/3 × 前D + 1/3 × K）
+pub const KDJ_D_SMOOTH: usize = 3;
+
+/// 计算 KDJ 逐日序列：K、D 以 50 起算并逐日递推平滑，
+/// 首个元素对应第 `period` 根 K 线；N 日最高价等于最低价时 RSV 无定义，沿用前值
+pub fn calculate_kdj_series(
     highs: &[f64],
     lows: &[f64],
     closes: &[f64],
     period: usize,
-) -> (f64, f64, f64) {
-    if highs.len() < period || lows.len() < period || closes.len() < period {
-        return (50.0, 50.0, 50.0);
+    k_smooth: usize,
+    d_smooth: usize,
+) -> Vec<KdjData> {
+    let len = highs.len().min(lows.len()).min(closes.len());
+    if period == 0 || len < period {
+        return Vec::new();
     }
-    
-    let len = highs.len();
+
+    let k_smooth = k_smooth.max(1) as f64;
+    let d_smooth = d_smooth.max(1) as f64;
     let mut k = 50.0;
     let mut d = 50.0;
-    
-    for i in period..=len {
-        let start = i.saturating_sub(period);
-        let end = i;
-        
+    let mut series = Vec::with_capacity(len - period + 1);
+
+    for end in period..=len {
+        let start = end - period;
         let highest = highs[start..end].iter().fold(f64::NEG_INFINITY, |a, &b| a.max(b));
         let lowest = lows[start..end].iter().fold(f64::INFINITY, |a, &b| a.min(b));
-        
-        if highest == lowest {
-            continue;
+
+        if highest > lowest {
+            let rsv = (closes[end - 1] - lowest) / (highest - lowest) * 100.0;
+            k = ((k_smooth - 1.0) * k + rsv) / k_smooth;
+            d = ((d_smooth - 1.0) * d + k) / d_smooth;
         }
-        
-        let close = closes[end - 1];
-        let rsv = (close - lowest) / (highest - lowest) * 100.0;
-        
-        k = (2.0 / 3.0) * k + (1.0 / 3.0) * rsv;
-        d = (2.0 / 3.0) * d + (1.0 / 3.0) * k;
+
+        series.push(KdjData { k, d, j: 3.0 * k - 2.0 * d });
     }
-    
-    let j = 3.0 * k - 2.0 * d;
-    
-    (k, d, j)
+
+    series
+}
+
+/// 计算 KDJ 指标（递推序列的最新值）
+pub fn calculate_kdj(
+    highs: &[f64],
+    lows: &[f64],
+    closes: &[f64],
+    period: usize,
+) -> (f64, f64, f64) {
+    calculate_kdj_series(highs, lows, closes, period, KDJ_K_SMOOTH, KDJ_D_SMOOTH)
+        .last()
+        .map(|last| (last.k, last.d, last.j))
+        .unwrap_or((50.0, 50.0, 50.0))
 }
 
 /// 计算 KDJ 数据结构
@@ -112,6 +130,28 @@ mod tests {
         assert!(d >= 0.0 && d <= 100.0);
     }
 
+    #[test]
+    fn test_kdj_series_smooths_iteratively() {
+        // 收盘恒在区间顶部：RSV 恒为 100，K、D 逐日向 100 递推逼近
+        let highs: Vec<f64> = (0..20).map(|i| 10.0 + i as f64).collect();
+        let lows: Vec<f64> = (0..20).map(|i| 9.0 + i as f64).collect();
+        let closes = highs.clone();
+        let series = calculate_kdj_series(&highs, &lows, &closes, 9, 3, 3);
+        assert_eq!(series.len(), 12);
+
+        let k1 = 50.0 * 2.0 / 3.0 + 100.0 / 3.0;
+        let d1 = 50.0 * 2.0 / 3.0 + k1 / 3.0;
+        assert!((series[0].k - k1).abs() < 1e-9);
+        assert!((series[0].d - d1).abs() < 1e-9);
+        let k2 = k1 * 2.0 / 3.0 + 100.0 / 3.0;
+        assert!((series[1].k - k2).abs() < 1e-9);
+        assert!(series.windows(2).all(|w| w[1].k > w[0].k && w[1].d > w[0].d));
+
+        let last = series.last().unwrap();
+        assert_eq!(calculate_kdj(&highs, &lows, &closes, 9), (last.k, last.d, last.j));
+        assert!(calculate_kdj_series(&highs[..5], &lows[..5], &closes[..5], 9, 3, 3).is_empty());
+    }
+
     #[test]
     fn test_kdj_cross() {
         assert!(is_kdj_golden_cross(30.0, 40.0, 45.0, 40.0));
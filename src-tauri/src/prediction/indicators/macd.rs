@@ -4,7 +4,10 @@
 //! - DIF = EMA(12) - EMA(26)
 //! - DEA = EMA(DIF, 9)
 //! - MACD柱 = 2 × (DIF - DEA)
+//!
+//! 成交量加权 MACD（VWMACD）以成交量加权均价 VWMA 代替 EMA，放量K线的价格变动权重更高
 
+use crate::config::constants::{MACD_FAST_PERIOD, MACD_SIGNAL_PERIOD, MACD_SLOW_PERIOD};
 use crate::utils::math::{calculate_ema, calculate_ema_series};
 use serde::{Deserialize, Serialize};
 
@@ -16,59 +19,69 @@ pub struct MacdData {
     pub histogram: f64,
 }
 
+/// 计算 DIF 逐日序列：真 EMA（乘数 2/(N+1)，以前 N 日均值起算）快慢线之差，
+/// 首个元素对应第 `MACD_SLOW_PERIOD` 根 K 线；数据不足返回空序列
+pub fn calculate_dif_series(prices: &[f64]) -> Vec<f64> {
+    calculate_dif_series_with_periods(prices, MACD_FAST_PERIOD, MACD_SLOW_PERIOD)
+}
+
+/// 按指定快慢线周期计算 DIF 逐日序列，首个元素对应第 `slow` 根 K 线；
+/// 数据不足或 `fast >= slow` 时返回空序列
+pub fn calculate_dif_series_with_periods(prices: &[f64], fast: usize, slow: usize) -> Vec<f64> {
+    if fast == 0 || fast >= slow {
+        return Vec::new();
+    }
+    let fast_series = calculate_ema_series(prices, fast);
+    let slow_series = calculate_ema_series(prices, slow);
+    if slow_series.is_empty() {
+        return Vec::new();
+    }
+
+    // 快线序列比慢线序列早 slow - fast 根开始，按价格下标对齐
+    let offset = slow - fast;
+    slow_series
+        .iter()
+        .zip(&fast_series[offset..])
+        .map(|(slow, fast)| fast - slow)
+        .collect()
+}
+
 /// 计算 MACD (DIF 值)
 pub fn calculate_macd(prices: &[f64]) -> f64 {
-    if prices.len() < 26 {
+    if prices.len() < MACD_SLOW_PERIOD {
         return 0.0;
     }
-    
-    let ema12 = calculate_ema(prices, 12);
-    let ema26 = calculate_ema(prices, 26);
-    ema12 - ema26
+
+    calculate_ema(prices, MACD_FAST_PERIOD) - calculate_ema(prices, MACD_SLOW_PERIOD)
 }
 
 /// 计算完整 MACD 指标 (DIF, DEA, MACD柱)
 pub fn calculate_macd_full(prices: &[f64]) -> (f64, f64, f64) {
-    if prices.len() < 26 {
-        return (0.0, 0.0, 0.0);
-    }
-    
-    // 计算 EMA 序列
-    let ema12_series = calculate_ema_series(prices, 12);
-    let ema26_series = calculate_ema_series(prices, 26);
-    
-    if ema12_series.is_empty() || ema26_series.is_empty() {
-        return (0.0, 0.0, 0.0);
-    }
-    
-    // 计算 DIF 序列（对齐两个 EMA 序列）
-    let mut dif_series = Vec::new();
-    let offset = 14; // 26 - 12 = 14
-    
-    for i in 0..ema26_series.len() {
-        let ema12_idx = offset + i;
-        if ema12_idx < ema12_series.len() {
-            let dif = ema12_series[ema12_idx] - ema26_series[i];
-            dif_series.push(dif);
-        }
-    }
-    
-    if dif_series.is_empty() {
+    calculate_macd_full_with_periods(prices, MACD_FAST_PERIOD, MACD_SLOW_PERIOD, MACD_SIGNAL_PERIOD)
+}
+
+/// 按指定快线/慢线/信号线周期计算完整 MACD 指标 (DIF, DEA, MACD柱)
+pub fn calculate_macd_full_with_periods(
+    prices: &[f64],
+    fast: usize,
+    slow: usize,
+    signal: usize,
+) -> (f64, f64, f64) {
+    let dif_series = calculate_dif_series_with_periods(prices, fast, slow);
+    let Some(&dif) = dif_series.last() else {
         return (0.0, 0.0, 0.0);
-    }
-    
-    let dif = *dif_series.last().unwrap();
-    
-    // 计算 DEA (DIF 的 9 日 EMA)
-    let dea = if dif_series.len() >= 9 {
-        calculate_ema(&dif_series, 9)
+    };
+
+    // 计算 DEA (DIF 的 signal 日 EMA)
+    let dea = if signal > 0 && dif_series.len() >= signal {
+        calculate_ema(&dif_series, signal)
     } else {
         dif
     };
-    
+
     // 计算 MACD 柱状图
     let histogram = 2.0 * (dif - dea);
-    
+
     (dif, dea, histogram)
 }
 
@@ -78,6 +91,57 @@ pub fn calculate_macd_data(prices: &[f64]) -> MacdData {
     MacdData { dif, dea, histogram }
 }
 
+/// 成交量加权均价逐日序列：VWMA = Σ(价格 × 成交量) / Σ(成交量)，首个元素对应第 `period` 根 K 线；
+/// 窗口内成交量全为 0 时取简单均价
+fn calculate_vwma_series(prices: &[f64], volumes: &[i64], period: usize) -> Vec<f64> {
+    let len = prices.len().min(volumes.len());
+    if period == 0 || len < period {
+        return Vec::new();
+    }
+    (period - 1..len)
+        .map(|i| {
+            let window = i + 1 - period..=i;
+            let volume: f64 = volumes[window.clone()].iter().map(|&v| v as f64).sum();
+            if volume > 0.0 {
+                window.map(|j| prices[j] * volumes[j] as f64).sum::<f64>() / volume
+            } else {
+                prices[window].iter().sum::<f64>() / period as f64
+            }
+        })
+        .collect()
+}
+
+/// 计算成交量加权 MACD (DIF, DEA, MACD柱)：DIF = VWMA(fast) - VWMA(slow)，
+/// DEA 为 DIF 的 `signal` 日 EMA，柱 = 2 × (DIF - DEA)，与标准 MACD 同口径；
+/// 数据不足或 `fast >= slow` 时返回全 0
+pub fn calculate_vwmacd(
+    prices: &[f64],
+    volumes: &[i64],
+    fast: usize,
+    slow: usize,
+    signal: usize,
+) -> (f64, f64, f64) {
+    if fast == 0 || fast >= slow {
+        return (0.0, 0.0, 0.0);
+    }
+    let fast_series = calculate_vwma_series(prices, volumes, fast);
+    let slow_series = calculate_vwma_series(prices, volumes, slow);
+    let dif_series: Vec<f64> = slow_series
+        .iter()
+        .zip(&fast_series[fast_series.len() - slow_series.len()..])
+        .map(|(slow, fast)| fast - slow)
+        .collect();
+    let Some(&dif) = dif_series.last() else {
+        return (0.0, 0.0, 0.0);
+    };
+    let dea = if signal > 0 && dif_series.len() >= signal {
+        calculate_ema(&dif_series, signal)
+    } else {
+        dif
+    };
+    (dif, dea, 2.0 * (dif - dea))
+}
+
 /// 判断 MACD 金叉
 pub fn is_golden_cross(prev_dif: f64, prev_dea: f64, curr_dif: f64, curr_dea: f64) -> bool {
     prev_dif <= prev_dea && curr_dif > curr_dea
@@ -112,6 +176,54 @@ mod tests {
         // 注意: 对于线性上涨序列，hist可能接近0，因为DIF和DEA趋近收敛
     }
 
+    #[test]
+    fn test_dif_matches_reference_for_linear_series() {
+        // 斜率为 s 的线性序列：以均值起算的 N 日 EMA 恰好滞后 (N-1)/2 个周期，
+        // 故 DIF = s × ((26-1)/2 - (12-1)/2) = 7s，DEA 同为 7s，柱为 0
+        let prices: Vec<f64> = (0..60).map(|i| 10.0 + 0.5 * i as f64).collect();
+        let (dif, dea, hist) = calculate_macd_full(&prices);
+        assert!((dif - 3.5).abs() < 1e-9);
+        assert!((dea - 3.5).abs() < 1e-9);
+        assert!(hist.abs() < 1e-9);
+
+        let series = calculate_dif_series(&prices);
+        assert_eq!(series.len(), prices.len() - MACD_SLOW_PERIOD + 1);
+        assert!(series.iter().all(|d| (d - 3.5).abs() < 1e-9));
+        assert!((calculate_macd(&prices) - dif).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_dif_uses_exponential_not_simple_average() {
+        // 末日跳涨：SMA 快慢线差为 10/12 - 10/26，真 EMA 对最新价权重更高
+        let mut prices = vec![10.0; 40];
+        prices.push(20.0);
+        let dif = calculate_macd(&prices);
+        let sma_dif = 10.0 / 12.0 - 10.0 / 26.0;
+        let ema_dif = 10.0 * (2.0 / 13.0) - 10.0 * (2.0 / 27.0);
+        assert!((dif - ema_dif).abs() < 1e-9);
+        assert!((dif - sma_dif).abs() > 1e-3);
+    }
+
+    #[test]
+    fn test_vwmacd_weights_high_volume_bars() {
+        // 量能均匀时 VWMA 即简单均线：线性序列 DIF = s × ((26-1)/2 - (12-1)/2) = 7s
+        let prices: Vec<f64> = (0..60).map(|i| 10.0 + 0.5 * i as f64).collect();
+        let (dif, dea, hist) = calculate_vwmacd(&prices, &[1000; 60], 12, 26, 9);
+        assert!((dif - 3.5).abs() < 1e-9);
+        assert!((dea - 3.5).abs() < 1e-9);
+        assert!(hist.abs() < 1e-9);
+
+        // 末日放量跳涨：VWMACD 对该K线的反应强于标准 MACD
+        let mut prices = vec![10.0; 40];
+        prices.push(11.0);
+        let mut volumes = vec![1000; 40];
+        volumes.push(10_000);
+        let (vw_dif, _, _) = calculate_vwmacd(&prices, &volumes, 12, 26, 9);
+        assert!((vw_dif - (1.0 * 10.0 / 21.0 - 1.0 * 10.0 / 35.0)).abs() < 1e-9);
+        assert!(vw_dif > calculate_macd(&prices));
+        assert_eq!(calculate_vwmacd(&prices[..20], &volumes[..20], 12, 26, 9), (0.0, 0.0, 0.0));
+    }
+
     #[test]
     fn test_golden_cross() {
         assert!(is_golden_cross(-1.0, 0.0, 0.5, 0.0));
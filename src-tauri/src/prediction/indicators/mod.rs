@@ -15,16 +15,22 @@ pub mod roc;
 pub mod emv;
 pub mod brar;
 pub mod vwap;
+pub mod adl;
+pub mod derived;
+pub mod ema;
+pub mod supertrend;
+pub mod trend_strength;
 
 // 选择性重导出，避免名称冲突
-pub use macd::{calculate_macd, calculate_macd_full, calculate_macd_data, MacdData};
+pub use macd::{calculate_macd, calculate_macd_full, calculate_macd_full_with_periods, calculate_macd_data, calculate_dif_series, calculate_vwmacd, MacdData};
 pub use macd::{is_golden_cross, is_death_cross, is_zero_cross_up, is_zero_cross_down};
-pub use kdj::{calculate_kdj, calculate_kdj_data, calculate_stochastic_k, KdjData};
+pub use kdj::{calculate_kdj, calculate_kdj_data, calculate_kdj_series, calculate_stochastic_k, KdjData};
 pub use kdj::{is_kdj_golden_cross, is_kdj_death_cross};
 pub use rsi::{calculate_rsi, calculate_rsi_with_period, rsi_signal_strength};
 pub use bollinger::{calculate_bollinger_bands, calculate_bollinger_position, BollingerBands};
-pub use obv::calculate_obv;
-pub use cci::calculate_cci;
+pub use bollinger::{calculate_bandwidth, calculate_bandwidth_series, detect_bollinger_squeeze, BollingerSqueeze};
+pub use obv::{calculate_obv, calculate_obv_trend_strength, OBVTrend};
+pub use cci::{calculate_cci, calculate_cci_series, cci_signal};
 pub use dmi::{calculate_dmi, calculate_dmi_data, DmiData};
 pub use atr::calculate_atr;
 pub use williams::{calculate_williams_r, analyze_williams_signal, WilliamsSignal, WilliamsZone};
@@ -32,9 +38,21 @@ pub use roc::{calculate_roc, analyze_roc_signal, analyze_multi_period_roc, RocSi
 pub use emv::{calculate_emv, analyze_emv_signal, EmvSignal};
 pub use brar::{calculate_brar, analyze_brar_signal, BrarSignal};
 pub use vwap::{calculate_vwap, calculate_rolling_vwap, analyze_vwap_signal, VwapSignal, VwapBands};
+pub use adl::{calculate_accumulation_distribution_line, calculate_chaikin_oscillator, calculate_normalized_chaikin_oscillator};
+pub use derived::{add_lag_features, add_rolling_stat_features, RollingStat};
+pub use ema::{calculate_ema, calculate_ema_series, calculate_ema_crossover, EmaCrossover};
+pub use supertrend::{calculate_supertrend, get_supertrend_signal, SupertrendValue};
+pub use trend_strength::{calculate_trend_strength_index, calculate_wilder_adx, TrendStrengthIndex};
 
+use crate::config::constants::{MACD_FAST_PERIOD, MACD_SIGNAL_PERIOD, MACD_SLOW_PERIOD};
+use crate::config::presets::IndicatorConfig;
+use crate::prediction::analysis::market_regime::{calculate_hurst_exponent, HURST_MAX_LAG};
+use crate::prediction::strategy::multi_factor::FundamentalFactor;
 use serde::{Deserialize, Serialize};
 
+/// `hurst_exponent` 特征的价格窗口：比市场状态分类短，避免训练样本因预热期过长而大量丢失
+const HURST_FEATURE_WINDOW: usize = 120;
+
 // =============================================================================
 // 综合技术指标结果
 // =============================================================================
@@ -46,11 +64,22 @@ pub struct TechnicalIndicatorValues {
     pub macd_dif: f64,
     pub macd_dea: f64,
     pub macd_histogram: f64,
+    /// 成交量加权 MACD 柱（周期同 MACD）；无成交量数据时为 None，动量因子回退标准 MACD 柱
+    #[serde(default)]
+    pub vwmacd_histogram: Option<f64>,
     pub kdj_k: f64,
     pub kdj_d: f64,
     pub kdj_j: f64,
     pub cci: f64,
     pub obv_trend: f64,
+    /// OBV 偏离其 20 日均线的百分比（见 `OBVTrend::strength`）
+    pub obv_strength: f64,
+    /// Chaikin 振荡器（3/10 周期，按 10 日均量归一化）：正值吸筹、负值派发
+    #[serde(default)]
+    pub chaikin_oscillator: f64,
+    /// 收盘价在布林带中的位置（-0.5 = 下轨，0 = 中轨，0.5 = 上轨），周期与倍数取自指标配置
+    #[serde(default)]
+    pub bollinger_position: f64,
     pub macd_golden_cross: bool,
     pub macd_death_cross: bool,
     pub kdj_golden_cross: bool,
@@ -71,6 +100,27 @@ pub struct TechnicalIndicatorValues {
     pub volume_ratio: f64,
     /// 换手率（%），由历史数据回填，调用方填充
     pub turnover_rate: f64,
+    /// 市场涨跌家数比（全市场宽度），调用方填充；缺失时情绪因子不计市场分量
+    #[serde(default)]
+    pub market_ad_ratio: Option<f64>,
+    /// 全市场恐慌/贪婪指数（0-100，见 `services::market_sentiment`），调用方填充；
+    /// 存在时情绪因子以其替代个股超买超卖推断
+    #[serde(default)]
+    pub market_fear_greed: Option<f64>,
+    /// 个股新闻关键词情绪分（-1 ~ +1，见 `api::news`），调用方填充；缺失时情绪因子不计新闻分量
+    #[serde(default)]
+    pub news_sentiment: Option<f64>,
+    /// 相对市场指数的 Beta，调用方填充；缺失时波动率因子不做 Beta 调整
+    #[serde(default)]
+    pub beta: Option<f64>,
+    /// 所属行业近 20 日收益相对全市场的强度（百分点，见 `services::sector`），调用方填充；
+    /// 缺失时动量因子不做行业轮动调整
+    #[serde(default)]
+    pub sector_relative_strength: Option<f64>,
+    /// 基本面估值与成长数据（见 `strategy::multi_factor::fundamental`），调用方填充；
+    /// 缺失时多因子评分不计基本面因子
+    #[serde(default)]
+    pub fundamental: Option<FundamentalFactor>,
 }
 
 impl Default for TechnicalIndicatorValues {
@@ -80,11 +130,15 @@ impl Default for TechnicalIndicatorValues {
             macd_dif: 0.0,
             macd_dea: 0.0,
             macd_histogram: 0.0,
+            vwmacd_histogram: None,
             kdj_k: 50.0,
             kdj_d: 50.0,
             kdj_j: 50.0,
             cci: 0.0,
             obv_trend: 0.0,
+            obv_strength: 0.0,
+            chaikin_oscillator: 0.0,
+            bollinger_position: 0.0,
             macd_golden_cross: false,
             macd_death_cross: false,
             kdj_golden_cross: false,
@@ -103,6 +157,12 @@ impl Default for TechnicalIndicatorValues {
             atr: 0.0,
             volume_ratio: 1.0,
             turnover_rate: 0.0,
+            market_ad_ratio: None,
+            market_fear_greed: None,
+            news_sentiment: None,
+            beta: None,
+            sector_relative_strength: None,
+            fundamental: None,
         }
     }
 }
@@ -159,60 +219,100 @@ impl TradingSignal {
 // 综合计算函数
 // =============================================================================
 
-/// 计算所有技术指标
+/// 计算所有技术指标，RSI/MACD/布林带/KDJ/ATR 周期取自 `config`（见 `config::presets`）
 pub fn calculate_all_indicators(
     prices: &[f64],
     highs: &[f64],
     lows: &[f64],
     volumes: &[i64],
+    config: &IndicatorConfig,
 ) -> TechnicalIndicatorValues {
     let mut result = TechnicalIndicatorValues::default();
     
     // RSI
-    if prices.len() >= 15 {
-        result.rsi = rsi::calculate_rsi(prices);
+    if prices.len() > config.rsi_period {
+        result.rsi = rsi::calculate_rsi_with_period(prices, config.rsi_period);
     }
     
     // MACD
-    if prices.len() >= 26 {
-        let (dif, dea, hist) = macd::calculate_macd_full(prices);
+    if prices.len() >= config.macd_slow {
+        let (dif, dea, hist) = macd::calculate_macd_full_with_periods(
+            prices,
+            config.macd_fast,
+            config.macd_slow,
+            config.macd_signal,
+        );
         result.macd_dif = dif;
         result.macd_dea = dea;
         result.macd_histogram = hist;
         
         // 金叉死叉判断
-        let prev_macd = if prices.len() > 27 {
+        let prev_macd = if prices.len() > config.macd_slow + 1 {
             let prev_prices = &prices[..prices.len() - 1];
-            macd::calculate_macd_full(prev_prices)
+            macd::calculate_macd_full_with_periods(
+                prev_prices,
+                config.macd_fast,
+                config.macd_slow,
+                config.macd_signal,
+            )
         } else {
             (dif, dea, hist)
         };
         
         result.macd_golden_cross = prev_macd.0 <= prev_macd.1 && dif > dea;
         result.macd_death_cross = prev_macd.0 >= prev_macd.1 && dif < dea;
+
+        if volumes.len() == prices.len() && volumes.iter().any(|&v| v > 0) {
+            let (_, _, vw_hist) = macd::calculate_vwmacd(
+                prices,
+                volumes,
+                config.macd_fast,
+                config.macd_slow,
+                config.macd_signal,
+            );
+            result.vwmacd_histogram = Some(vw_hist);
+        }
+    }
+
+    // 布林带位置
+    if config.bollinger_period > 0 && prices.len() >= config.bollinger_period {
+        let bands = bollinger::calculate_bollinger_bands(
+            prices,
+            config.bollinger_period,
+            config.bollinger_std,
+        );
+        if bands.upper > bands.lower {
+            let close = prices[prices.len() - 1];
+            result.bollinger_position = (close - bands.lower) / (bands.upper - bands.lower) - 0.5;
+        }
     }
     
     // KDJ
-    if highs.len() >= 9 && lows.len() >= 9 && prices.len() >= 9 {
-        let (k, d, j) = kdj::calculate_kdj(highs, lows, prices, 9);
-        result.kdj_k = k;
-        result.kdj_d = d;
-        result.kdj_j = j;
-        
-        result.kdj_overbought = j > 80.0;
-        result.kdj_oversold = j < 20.0;
-        
-        // KDJ 金叉死叉
-        if highs.len() > 10 {
-            let prev_len = highs.len() - 1;
-            let (prev_k, prev_d, _) = kdj::calculate_kdj(
-                &highs[..prev_len],
-                &lows[..prev_len],
-                &prices[..prev_len],
-                9,
-            );
-            result.kdj_golden_cross = prev_k <= prev_d && k > d;
-            result.kdj_death_cross = prev_k >= prev_d && k < d;
+    let kdj_period = config.kdj_period;
+    if highs.len() >= kdj_period && lows.len() >= kdj_period && prices.len() >= kdj_period {
+        let series = kdj::calculate_kdj_series(
+            highs,
+            lows,
+            prices,
+            kdj_period,
+            kdj::KDJ_K_SMOOTH,
+            kdj::KDJ_D_SMOOTH,
+        );
+        if let Some(last) = series.last() {
+            let (k, d, j) = (last.k, last.d, last.j);
+            result.kdj_k = k;
+            result.kdj_d = d;
+            result.kdj_j = j;
+
+            result.kdj_overbought = j > 80.0;
+            result.kdj_oversold = j < 20.0;
+
+            // KDJ 金叉死叉：取同一递推序列的前一日值
+            if series.len() >= 2 {
+                let prev = &series[series.len() - 2];
+                result.kdj_golden_cross = prev.k <= prev.d && k > d;
+                result.kdj_death_cross = prev.k >= prev.d && k < d;
+            }
         }
     }
     
@@ -226,6 +326,13 @@ pub fn calculate_all_indicators(
         let obv = obv::calculate_obv(prices, volumes);
         let avg_vol = volumes.iter().sum::<i64>() as f64 / volumes.len() as f64;
         result.obv_trend = obv / (avg_vol * volumes.len() as f64);
+        result.obv_strength = obv::calculate_obv_trend_strength(prices, volumes, 20).strength;
+    }
+
+    // Chaikin 累积/派发振荡器
+    if highs.len() >= adl::CHAIKIN_SLOW_PERIOD && lows.len() >= adl::CHAIKIN_SLOW_PERIOD {
+        result.chaikin_oscillator =
+            adl::calculate_normalized_chaikin_oscillator(highs, lows, prices, volumes);
     }
     
     // Williams %R
@@ -254,8 +361,9 @@ pub fn calculate_all_indicators(
     }
     
     // ATR 平均真实波幅
-    if highs.len() >= 14 && lows.len() >= 14 && prices.len() >= 14 {
-        result.atr = atr::calculate_atr(highs, lows, prices, 14);
+    let atr_period = config.atr_period;
+    if highs.len() >= atr_period && lows.len() >= atr_period && prices.len() >= atr_period {
+        result.atr = atr::calculate_atr(highs, lows, prices, atr_period);
     }
 
     // 量比（当日成交量 / 过去N日平均成交量）
@@ -276,6 +384,8 @@ pub fn calculate_all_indicators(
 }
 
 /// 计算单个特征值
+///
+/// `navs` 为按交易日对齐的 ETF 单位净值，仅 `etf_premium_discount` 使用；个股传 `None`
 pub fn calculate_feature_value(
     feature_name: &str,
     prices: &[f64],
@@ -283,6 +393,7 @@ pub fn calculate_feature_value(
     index: usize,
     highs: Option<&[f64]>,
     lows: Option<&[f64]>,
+    navs: Option<&[f64]>,
 ) -> f64 {
     match feature_name {
         "close" => prices.get(index).copied().unwrap_or(0.0),
@@ -320,6 +431,14 @@ pub fn calculate_feature_value(
                 prices[index]
             }
         }
+        "ema5" | "ema10" | "ema20" => {
+            let period = get_feature_required_days(feature_name);
+            if index + 1 >= period {
+                ema::calculate_ema(&prices[..=index], period)
+            } else {
+                prices[index]
+            }
+        }
         "rsi" => {
             if index >= 14 {
                 rsi::calculate_rsi(&prices[index - 14..=index])
@@ -328,8 +447,9 @@ pub fn calculate_feature_value(
             }
         }
         "macd" => {
+            // 使用完整历史递推 EMA：仅取 26 日窗口时慢线退化为简单均线
             if index >= 25 {
-                macd::calculate_macd(&prices[index - 25..=index])
+                macd::calculate_macd(&prices[..=index])
             } else {
                 0.0
             }
@@ -348,16 +468,32 @@ pub fn calculate_feature_value(
                 0.0
             }
         }
+        "vwmacd" | "vwmacd_signal" | "vwmacd_histogram" => {
+            if index >= 26 && volumes.len() > index {
+                let (dif, dea, hist) = macd::calculate_vwmacd(
+                    &prices[..=index],
+                    &volumes[..=index],
+                    MACD_FAST_PERIOD,
+                    MACD_SLOW_PERIOD,
+                    MACD_SIGNAL_PERIOD,
+                );
+                let current = prices[index];
+                match feature_name {
+                    "vwmacd" => dif / current,
+                    "vwmacd_signal" => dea / current,
+                    "vwmacd_histogram" => hist / current,
+                    _ => 0.0,
+                }
+            } else {
+                0.0
+            }
+        }
         "kdj_k" | "kdj_d" | "kdj_j" => {
             if let (Some(h), Some(l)) = (highs, lows) {
                 if index >= 9 && h.len() > index && l.len() > index {
-                    let start = index.saturating_sub(8);
-                    let (k, d, j) = kdj::calculate_kdj(
-                        &h[start..=index],
-                        &l[start..=index],
-                        &prices[start..=index],
-                        9,
-                    );
+                    // 使用完整历史递推：仅取 9 日窗口时 K、D 只平滑一次
+                    let (k, d, j) =
+                        kdj::calculate_kdj(&h[..=index], &l[..=index], &prices[..=index], 9);
                     match feature_name {
                         "kdj_k" => k / 100.0,
                         "kdj_d" => d / 100.0,
@@ -413,23 +549,82 @@ pub fn calculate_feature_value(
                 0.5
             }
         }
-        _ => 0.0,
+        // 价格记忆：窗口不足时为中性 0.5
+        "hurst_exponent" => {
+            if index + 1 >= HURST_FEATURE_WINDOW {
+                calculate_hurst_exponent(&prices[index + 1 - HURST_FEATURE_WINDOW..=index], HURST_MAX_LAG)
+            } else {
+                0.5
+            }
+        }
+        // ETF 折溢价率：(收盘价 - 单位净值) / 单位净值；无净值时为 0
+        "etf_premium_discount" => match navs.and_then(|navs| navs.get(index)) {
+            Some(&nav) if nav > 0.0 => (prices[index] - nav) / nav,
+            _ => 0.0,
+        },
+        derived::PRICE_TO_MA20_RATIO => {
+            let ma20 = calculate_feature_value("ma20", prices, volumes, index, highs, lows, navs);
+            if ma20 > 0.0 {
+                prices[index] / ma20
+            } else {
+                1.0
+            }
+        }
+        derived::VOLUME_TO_VOL_MA5_RATIO => {
+            let start = index.saturating_sub(4);
+            let window = &volumes[start..=index];
+            let avg = window.iter().sum::<i64>() as f64 / window.len() as f64;
+            if avg > 0.0 {
+                volumes[index] as f64 / avg
+            } else {
+                1.0
+            }
+        }
+        _ => match derived::parse_derived_feature(feature_name) {
+            // 滞后特征：数据不足滞后天数时为 0
+            Some(derived::DerivedFeature::Lag { base, lag }) if index >= lag => {
+                calculate_feature_value(base, prices, volumes, index - lag, highs, lows, navs)
+            }
+            Some(derived::DerivedFeature::Lag { .. }) => 0.0,
+            // 滚动统计：数据不足窗口时使用已有部分
+            Some(derived::DerivedFeature::Rolling { base, stat, window }) => {
+                let values: Vec<f64> = (index.saturating_sub(window - 1)..=index)
+                    .map(|i| calculate_feature_value(base, prices, volumes, i, highs, lows, navs))
+                    .collect();
+                stat.apply(&values)
+            }
+            None => 0.0,
+        },
     }
 }
 
-/// 获取特征所需的历史天数
+/// 获取特征所需的历史天数；未知特征按 1 天处理
 pub fn get_feature_required_days(feature_name: &str) -> usize {
-    match feature_name {
-        "close" | "volume" | "change_percent" => 1,
-        "ma5" => 5,
-        "ma10" => 10,
-        "ma20" | "bollinger" | "cci" => 20,
+    known_feature_required_days(feature_name).unwrap_or(1)
+}
+
+fn known_feature_required_days(feature_name: &str) -> Option<usize> {
+    let days = match feature_name {
+        "close" | "volume" | "change_percent" | "etf_premium_discount" => 1,
+        "ma5" | "ema5" => 5,
+        "ma10" | "ema10" => 10,
+        "ma20" | "ema20" | "bollinger" | "cci" => 20,
         "rsi" | "stochastic_k" | "stochastic_d" | "dmi_plus" | "dmi_minus" | "adx" => 14,
         "macd" | "macd_dif" | "macd_dea" | "macd_histogram" => 26,
+        "vwmacd" | "vwmacd_signal" | "vwmacd_histogram" => 26,
         "momentum" => 10,
         "kdj_k" | "kdj_d" | "kdj_j" => 9,
         "obv" => 2,
-        _ => 1,
-    }
+        "hurst_exponent" => HURST_FEATURE_WINDOW,
+        derived::PRICE_TO_MA20_RATIO => 20,
+        derived::VOLUME_TO_VOL_MA5_RATIO => 5,
+        _ => match derived::parse_derived_feature(feature_name)? {
+            derived::DerivedFeature::Lag { base, lag } => known_feature_required_days(base)? + lag,
+            derived::DerivedFeature::Rolling { base, window, .. } => {
+                known_feature_required_days(base)? + window - 1
+            }
+        },
+    };
+    Some(days)
 }
 
@@ -5,6 +5,90 @@
 //! - 价格下跌：OBV -= 成交量
 //! - 价格不变：OBV 不变
 
+use serde::{Deserialize, Serialize};
+
+/// OBV 趋势强度
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OBVTrend {
+    /// 最新 OBV 累计值
+    pub obv_value: f64,
+    /// 最近 ma_period 日 OBV 均值
+    pub obv_ma: f64,
+    /// "上升" / "下降" / "平稳"
+    pub trend_direction: String,
+    /// OBV 偏离其均线的百分比：(OBV / |OBV_MA| - sign(OBV_MA)) × 100，即 OBV/OBV_MA - 1 的
+    /// 符号稳健版（OBV_MA 为负时方向不翻转）
+    pub strength: f64,
+    /// 价格与 OBV 在均线周期内方向相反（价涨量能流出或价跌量能流入）
+    pub divergence_with_price: bool,
+}
+
+/// 强度绝对值低于该百分比视为平稳
+const OBV_FLAT_THRESHOLD_PCT: f64 = 2.0;
+
+/// 计算 OBV 逐日累计序列（首日为 0）
+pub fn calculate_obv_series(prices: &[f64], volumes: &[i64]) -> Vec<f64> {
+    let len = prices.len().min(volumes.len());
+    let mut series = Vec::with_capacity(len);
+    let mut obv = 0.0;
+    for i in 0..len {
+        if i > 0 {
+            if prices[i] > prices[i - 1] {
+                obv += volumes[i] as f64;
+            } else if prices[i] < prices[i - 1] {
+                obv -= volumes[i] as f64;
+            }
+        }
+        series.push(obv);
+    }
+    series
+}
+
+/// 计算 OBV 趋势强度：OBV 相对其 ma_period 日均线的偏离，以及与价格的背离
+pub fn calculate_obv_trend_strength(prices: &[f64], volumes: &[i64], ma_period: usize) -> OBVTrend {
+    let series = calculate_obv_series(prices, volumes);
+    let period = ma_period.max(1);
+    if series.len() < period + 1 {
+        return OBVTrend {
+            obv_value: series.last().copied().unwrap_or(0.0),
+            obv_ma: 0.0,
+            trend_direction: "平稳".to_string(),
+            strength: 0.0,
+            divergence_with_price: false,
+        };
+    }
+
+    let len = series.len();
+    let obv_value = series[len - 1];
+    let obv_ma = series[len - period..].iter().sum::<f64>() / period as f64;
+    let strength = if obv_ma.abs() > f64::EPSILON {
+        (obv_value - obv_ma) / obv_ma.abs() * 100.0
+    } else {
+        0.0
+    };
+
+    let trend_direction = if strength > OBV_FLAT_THRESHOLD_PCT {
+        "上升"
+    } else if strength < -OBV_FLAT_THRESHOLD_PCT {
+        "下降"
+    } else {
+        "平稳"
+    };
+
+    let price_change = prices[len - 1] - prices[len - 1 - period];
+    let obv_change = obv_value - series[len - 1 - period];
+    let divergence_with_price =
+        (price_change > 0.0 && obv_change < 0.0) || (price_change < 0.0 && obv_change > 0.0);
+
+    OBVTrend {
+        obv_value,
+        obv_ma,
+        trend_direction: trend_direction.to_string(),
+        strength,
+        divergence_with_price,
+    }
+}
+
 /// 计算 OBV 指标
 pub fn calculate_obv(prices: &[f64], volumes: &[i64]) -> f64 {
     if prices.len() < 2 || volumes.len() < 2 {
@@ -78,5 +162,33 @@ mod tests {
         let obv = calculate_obv(&prices, &volumes);
         assert!(obv < 0.0);
     }
+
+    #[test]
+    fn test_obv_trend_strength_direction_and_divergence() {
+        let prices: Vec<f64> = (0..30).map(|i| 10.0 + i as f64 * 0.1).collect();
+        let volumes = vec![1000; 30];
+        let trend = calculate_obv_trend_strength(&prices, &volumes, 10);
+        assert_eq!(trend.trend_direction, "上升");
+        assert!(trend.strength > 0.0);
+        assert!(!trend.divergence_with_price);
+
+        // 价格创新高但上涨日缩量、下跌日放量 → OBV 下行，形成背离
+        let mut prices = vec![10.0; 25];
+        let mut volumes = vec![0i64; 25];
+        for i in 1..25 {
+            let up = i % 2 == 1;
+            prices[i] = prices[i - 1] + if up { 0.3 } else { -0.2 };
+            volumes[i] = if up { 100 } else { 1000 };
+        }
+        let trend = calculate_obv_trend_strength(&prices, &volumes, 10);
+        assert!(trend.divergence_with_price);
+    }
+
+    #[test]
+    fn test_obv_trend_strength_short_input_is_flat() {
+        let trend = calculate_obv_trend_strength(&[10.0, 11.0], &[100, 200], 20);
+        assert_eq!(trend.strength, 0.0);
+        assert_eq!(trend.trend_direction, "平稳");
+    }
 }
 
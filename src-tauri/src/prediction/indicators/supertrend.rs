@@ -0,0 +1,175 @@
+//! Supertrend 超级趋势指标
+//!
+//! 以 (最高价 + 最低价) / 2 为中轴、上下各加减 multiplier × ATR 构成轨道：
+//! - 上轨只降不升、下轨只升不降（前一日收盘突破时重置），形成跟随价格的阶梯线
+//! - 多头时 Supertrend 取下轨，收盘跌破下轨转空；空头时取上轨，收盘突破上轨转多
+//!
+//! ATR 与 `atr::calculate_atr` 同口径（真实波幅的 N 日简单平均）。
+
+use super::TradingSignal;
+use serde::{Deserialize, Serialize};
+
+/// 单根K线的 Supertrend 值
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SupertrendValue {
+    pub upper_band: f64,
+    pub lower_band: f64,
+    /// 当前生效的趋势线：多头为下轨，空头为上轨
+    pub supertrend: f64,
+    /// +1 多头，-1 空头
+    pub direction: i8,
+    /// 当日收盘价，用于计算止损距离
+    pub close: f64,
+}
+
+/// 计算 Supertrend 序列。
+///
+/// 第一个值对应第 `period` 根K线（下标从 0 起，需 `period` 个真实波幅），
+/// 即结果与输入末尾对齐、长度为 `len - period`；数据不足或参数无效时返回空
+pub fn calculate_supertrend(
+    highs: &[f64],
+    lows: &[f64],
+    closes: &[f64],
+    period: usize,
+    multiplier: f64,
+) -> Vec<SupertrendValue> {
+    let n = closes.len().min(highs.len()).min(lows.len());
+    if period == 0 || n <= period {
+        return Vec::new();
+    }
+
+    // trs[i - 1] 为第 i 根K线的真实波幅
+    let trs: Vec<f64> = (1..n)
+        .map(|i| {
+            (highs[i] - lows[i])
+                .max((highs[i] - closes[i - 1]).abs())
+                .max((lows[i] - closes[i - 1]).abs())
+        })
+        .collect();
+
+    let mut values: Vec<SupertrendValue> = Vec::with_capacity(n - period);
+    let mut tr_sum: f64 = trs[..period - 1].iter().sum();
+    for i in period..n {
+        tr_sum += trs[i - 1];
+        if i > period {
+            tr_sum -= trs[i - 1 - period];
+        }
+        let atr = tr_sum / period as f64;
+        let mid = (highs[i] + lows[i]) / 2.0;
+        let basic_upper = mid + multiplier * atr;
+        let basic_lower = mid - multiplier * atr;
+        let close = closes[i];
+
+        let value = match values.last() {
+            None => {
+                let direction = if close >= mid { 1 } else { -1 };
+                SupertrendValue {
+                    upper_band: basic_upper,
+                    lower_band: basic_lower,
+                    supertrend: if direction > 0 { basic_lower } else { basic_upper },
+                    direction,
+                    close,
+                }
+            }
+            Some(prev) => {
+                let upper_band = if basic_upper < prev.upper_band || prev.close > prev.upper_band {
+                    basic_upper
+                } else {
+                    prev.upper_band
+                };
+                let lower_band = if basic_lower > prev.lower_band || prev.close < prev.lower_band {
+                    basic_lower
+                } else {
+                    prev.lower_band
+                };
+                let direction = match prev.direction {
+                    d if d > 0 && close < lower_band => -1,
+                    d if d < 0 && close > upper_band => 1,
+                    d => d,
+                };
+                SupertrendValue {
+                    upper_band,
+                    lower_band,
+                    supertrend: if direction > 0 { lower_band } else { upper_band },
+                    direction,
+                    close,
+                }
+            }
+        };
+        values.push(value);
+    }
+    values
+}
+
+/// 末根K线的 Supertrend 信号与止损距离。
+///
+/// 方向在末根K线翻转时给出买入 / 卖出，趋势延续时为持有；
+/// 止损距离为收盘价到 Supertrend 线的价差（多头时即跌破转空前的回撤空间）
+pub fn get_supertrend_signal(values: &[SupertrendValue]) -> (TradingSignal, f64) {
+    let Some(last) = values.last() else {
+        return (TradingSignal::Hold, 0.0);
+    };
+    let flipped = values.len() >= 2 && values[values.len() - 2].direction != last.direction;
+    let signal = match (flipped, last.direction > 0) {
+        (true, true) => TradingSignal::Buy,
+        (true, false) => TradingSignal::Sell,
+        (false, _) => TradingSignal::Hold,
+    };
+    (signal, (last.close - last.supertrend).abs())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bars(closes: &[f64]) -> (Vec<f64>, Vec<f64>) {
+        (
+            closes.iter().map(|c| c + 0.5).collect(),
+            closes.iter().map(|c| c - 0.5).collect(),
+        )
+    }
+
+    #[test]
+    fn test_supertrend_follows_trend_and_flips() {
+        // 30 日稳步上涨后连续大跌
+        let mut closes: Vec<f64> = (0..30).map(|i| 10.0 + 0.3 * i as f64).collect();
+        closes.extend([16.0, 13.0, 10.0]);
+        let (highs, lows) = bars(&closes);
+        let values = calculate_supertrend(&highs, &lows, &closes, 10, 3.0);
+        assert_eq!(values.len(), closes.len() - 10);
+
+        let uptrend = &values[..values.len() - 3];
+        assert!(uptrend.iter().all(|v| v.direction == 1 && v.supertrend < v.close));
+        // 多头下轨只升不降
+        assert!(uptrend.windows(2).all(|w| w[1].lower_band >= w[0].lower_band));
+
+        let last = values.last().unwrap();
+        assert_eq!(last.direction, -1);
+        assert_eq!(last.supertrend, last.upper_band);
+        assert!(last.close < last.supertrend);
+    }
+
+    #[test]
+    fn test_supertrend_signal() {
+        let value = |direction: i8, supertrend: f64, close: f64| SupertrendValue {
+            upper_band: supertrend.max(close) + 1.0,
+            lower_band: supertrend.min(close) - 1.0,
+            supertrend,
+            direction,
+            close,
+        };
+        let (signal, stop) = get_supertrend_signal(&[value(-1, 11.0, 10.0), value(1, 9.5, 10.2)]);
+        assert_eq!(signal, TradingSignal::Buy);
+        assert!((stop - 0.7).abs() < 1e-9);
+
+        let (signal, _) = get_supertrend_signal(&[value(1, 9.5, 10.2), value(1, 9.6, 10.4)]);
+        assert_eq!(signal, TradingSignal::Hold);
+        assert_eq!(get_supertrend_signal(&[]).0, TradingSignal::Hold);
+    }
+
+    #[test]
+    fn test_supertrend_insufficient_data() {
+        let (highs, lows) = bars(&[10.0; 10]);
+        assert!(calculate_supertrend(&highs, &lows, &[10.0; 10], 10, 3.0).is_empty());
+    }
+}
@@ -0,0 +1,249 @@
+//! 趋势强度指数（TSI）
+//!
+//! 综合 ADX（趋势强度）、10 日动量与均线排列三个维度：
+//! - score = 40% × ADX 分量 + 30% × |动量分量| + 30% × |均线排列分量|，范围 0 ~ 1
+//! - direction 为动量与均线排列的平均方向，范围 -1 ~ +1
+//!
+//! ADX 按 Wilder 法平滑（见 [`calculate_wilder_adx`]），而非单根K线窗口的 DX。
+
+use serde::{Deserialize, Serialize};
+
+/// ADX 计算周期
+const TSI_ADX_PERIOD: usize = 14;
+/// ADX 达到该值时 ADX 分量取满分
+const TSI_ADX_FULL: f64 = 50.0;
+/// 动量回看天数
+const TSI_MOMENTUM_DAYS: usize = 10;
+/// 10 日涨跌幅达到 ±10% 时动量分量取满分
+const TSI_MOMENTUM_FULL: f64 = 0.10;
+/// 均线排列使用的周期（需按从短到长排列）
+const TSI_MA_PERIODS: [usize; 4] = [5, 10, 20, 60];
+
+const ADX_WEIGHT: f64 = 0.40;
+const MOMENTUM_WEIGHT: f64 = 0.30;
+const MA_ALIGNMENT_WEIGHT: f64 = 0.30;
+
+/// 趋势强度指数
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TrendStrengthIndex {
+    /// 综合趋势强度（0 ~ 1，不含方向）
+    pub score: f64,
+    /// 趋势方向（-1 ~ +1）
+    pub direction: f64,
+    /// ADX / 50，封顶 1
+    pub adx_component: f64,
+    /// 10 日涨跌幅 / 10%，范围 -1 ~ +1
+    pub momentum_component: f64,
+    /// MA5>MA10、MA10>MA20、MA20>MA60 成立数减反向成立数，再除以 3，范围 -1 ~ +1
+    pub ma_alignment_component: f64,
+    pub interpretation: String,
+}
+
+fn simple_ma(prices: &[f64], period: usize) -> f64 {
+    let window = &prices[prices.len() - period..];
+    window.iter().sum::<f64>() / period as f64
+}
+
+/// 均线排列分量；数据不足 60 日时为 0
+fn ma_alignment_component(prices: &[f64]) -> f64 {
+    let longest = TSI_MA_PERIODS[TSI_MA_PERIODS.len() - 1];
+    if prices.len() < longest {
+        return 0.0;
+    }
+    let mas: Vec<f64> = TSI_MA_PERIODS.iter().map(|&p| simple_ma(prices, p)).collect();
+    let pairs = mas.len() - 1;
+    let net: i32 = mas
+        .windows(2)
+        .map(|pair| match pair[0].partial_cmp(&pair[1]) {
+            Some(std::cmp::Ordering::Greater) => 1,
+            Some(std::cmp::Ordering::Less) => -1,
+            _ => 0,
+        })
+        .sum();
+    net as f64 / pairs as f64
+}
+
+fn momentum_component(prices: &[f64]) -> f64 {
+    if prices.len() <= TSI_MOMENTUM_DAYS {
+        return 0.0;
+    }
+    let base = prices[prices.len() - 1 - TSI_MOMENTUM_DAYS];
+    let last = prices[prices.len() - 1];
+    if base <= 0.0 {
+        return 0.0;
+    }
+    ((last / base - 1.0) / TSI_MOMENTUM_FULL).clamp(-1.0, 1.0)
+}
+
+fn interpret(score: f64, direction: f64) -> String {
+    let side = if direction > 0.0 { "上升" } else { "下降" };
+    if score >= 0.6 && direction.abs() >= 0.5 {
+        format!("强势{side}趋势")
+    } else if score >= 0.35 && direction.abs() >= 0.2 {
+        format!("温和{side}趋势")
+    } else {
+        "无明显趋势".to_string()
+    }
+}
+
+/// Wilder 平滑的 DMI（时间正序），返回 (+DI, -DI, ADX)。
+///
+/// TR 与 ±DM 先按 Wilder 法平滑（首值为前 `period` 项之和，之后 S = S - S/period + x），
+/// 再对逐日 DX 做同样的平滑得到 ADX；DX 不足 `period` 个时取其均值。
+pub fn calculate_wilder_adx(
+    highs: &[f64],
+    lows: &[f64],
+    closes: &[f64],
+    period: usize,
+) -> (f64, f64, f64) {
+    let n = highs.len().min(lows.len()).min(closes.len());
+    if period == 0 || n < period + 1 {
+        return (0.0, 0.0, 0.0);
+    }
+
+    let mut tr = Vec::with_capacity(n - 1);
+    let mut dm_plus = Vec::with_capacity(n - 1);
+    let mut dm_minus = Vec::with_capacity(n - 1);
+    for i in 1..n {
+        tr.push(
+            (highs[i] - lows[i])
+                .max((highs[i] - closes[i - 1]).abs())
+                .max((lows[i] - closes[i - 1]).abs()),
+        );
+        let up_move = highs[i] - highs[i - 1];
+        let down_move = lows[i - 1] - lows[i];
+        dm_plus.push(if up_move > down_move && up_move > 0.0 { up_move } else { 0.0 });
+        dm_minus.push(if down_move > up_move && down_move > 0.0 { down_move } else { 0.0 });
+    }
+
+    let directional = |tr_sum: f64, plus_sum: f64, minus_sum: f64| -> (f64, f64, f64) {
+        if tr_sum <= 0.0 {
+            return (0.0, 0.0, 0.0);
+        }
+        let di_plus = plus_sum / tr_sum * 100.0;
+        let di_minus = minus_sum / tr_sum * 100.0;
+        let dx = if di_plus + di_minus > 0.0 {
+            (di_plus - di_minus).abs() / (di_plus + di_minus) * 100.0
+        } else {
+            0.0
+        };
+        (di_plus, di_minus, dx)
+    };
+
+    let p = period as f64;
+    let mut tr_sum: f64 = tr[..period].iter().sum();
+    let mut plus_sum: f64 = dm_plus[..period].iter().sum();
+    let mut minus_sum: f64 = dm_minus[..period].iter().sum();
+    let mut latest = directional(tr_sum, plus_sum, minus_sum);
+    let mut dx_values = vec![latest.2];
+    for j in period..tr.len() {
+        tr_sum += tr[j] - tr_sum / p;
+        plus_sum += dm_plus[j] - plus_sum / p;
+        minus_sum += dm_minus[j] - minus_sum / p;
+        latest = directional(tr_sum, plus_sum, minus_sum);
+        dx_values.push(latest.2);
+    }
+
+    let adx = if dx_values.len() >= period {
+        let seed = dx_values[..period].iter().sum::<f64>() / p;
+        dx_values[period..]
+            .iter()
+            .fold(seed, |adx, &dx| (adx * (p - 1.0) + dx) / p)
+    } else {
+        dx_values.iter().sum::<f64>() / dx_values.len() as f64
+    };
+    (latest.0, latest.1, adx)
+}
+
+/// 计算趋势强度指数（时间正序的收盘价、最高价、最低价）
+pub fn calculate_trend_strength_index(
+    prices: &[f64],
+    highs: &[f64],
+    lows: &[f64],
+) -> TrendStrengthIndex {
+    let (_, _, adx) = calculate_wilder_adx(highs, lows, prices, TSI_ADX_PERIOD);
+    let adx_component = (adx / TSI_ADX_FULL).clamp(0.0, 1.0);
+    let momentum_component = momentum_component(prices);
+    let ma_alignment_component = ma_alignment_component(prices);
+
+    let score = ADX_WEIGHT * adx_component
+        + MOMENTUM_WEIGHT * momentum_component.abs()
+        + MA_ALIGNMENT_WEIGHT * ma_alignment_component.abs();
+    let direction = ((momentum_component + ma_alignment_component) / 2.0).clamp(-1.0, 1.0);
+
+    TrendStrengthIndex {
+        score,
+        direction,
+        adx_component,
+        momentum_component,
+        ma_alignment_component,
+        interpretation: interpret(score, direction),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prediction::indicators::dmi::calculate_dmi;
+
+    fn bars(closes: &[f64]) -> (Vec<f64>, Vec<f64>) {
+        (
+            closes.iter().map(|c| c * 1.01).collect(),
+            closes.iter().map(|c| c * 0.99).collect(),
+        )
+    }
+
+    #[test]
+    fn test_steady_uptrend_scores_strong() {
+        // 每日上涨 1%：10 日涨幅约 10.5%，均线多头排列，ADX 为 100
+        let closes: Vec<f64> = (0..80).map(|i| 10.0 * 1.01f64.powi(i)).collect();
+        let (highs, lows) = bars(&closes);
+        let tsi = calculate_trend_strength_index(&closes, &highs, &lows);
+        assert_eq!(tsi.adx_component, 1.0);
+        assert_eq!(tsi.momentum_component, 1.0);
+        assert_eq!(tsi.ma_alignment_component, 1.0);
+        assert!((tsi.score - 1.0).abs() < 1e-12);
+        assert_eq!(tsi.direction, 1.0);
+        assert_eq!(tsi.interpretation, "强势上升趋势");
+
+        let falling: Vec<f64> = closes.iter().rev().copied().collect();
+        let (highs, lows) = bars(&falling);
+        let down = calculate_trend_strength_index(&falling, &highs, &lows);
+        assert_eq!(down.ma_alignment_component, -1.0);
+        assert!(down.direction < -0.9);
+        assert_eq!(down.interpretation, "强势下降趋势");
+    }
+
+    #[test]
+    fn test_wilder_adx_lags_a_short_pullback() {
+        // 上涨 40 日后回落 5 日：单窗口 DX 约 28，平滑 ADX 仍保留此前的强趋势
+        let mut closes: Vec<f64> = (0..40).map(|i| 10.0 * 1.01f64.powi(i)).collect();
+        for _ in 0..5 {
+            closes.push(closes[closes.len() - 1] * 0.99);
+        }
+        let (highs, lows) = bars(&closes);
+        let (di_plus, di_minus, adx) = calculate_wilder_adx(&highs, &lows, &closes, 14);
+        let (_, _, raw_dx, _) = calculate_dmi(&highs, &lows, &closes, 14);
+        assert!(di_plus > di_minus);
+        assert!(adx > 80.0, "adx = {adx}");
+        assert!(raw_dx < 30.0, "raw_dx = {raw_dx}");
+
+        let short = calculate_wilder_adx(&highs[..10], &lows[..10], &closes[..10], 14);
+        assert_eq!(short, (0.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn test_flat_prices_have_no_trend() {
+        let closes = vec![10.0; 80];
+        let (highs, lows) = bars(&closes);
+        let tsi = calculate_trend_strength_index(&closes, &highs, &lows);
+        assert_eq!(tsi.score, 0.0);
+        assert_eq!(tsi.direction, 0.0);
+        assert_eq!(tsi.interpretation, "无明显趋势");
+
+        // 数据不足时各分量为 0
+        let short = calculate_trend_strength_index(&[10.0, 10.5], &[10.6, 10.7], &[9.9, 10.2]);
+        assert_eq!(short.ma_alignment_component, 0.0);
+        assert_eq!(short.momentum_component, 0.0);
+    }
+}
@@ -5,6 +5,7 @@
 //! - 趋势/量价/形态分析
 //! - 多因子评分策略
 //! - 机器学习模型
+//! - 截面特征与截面多因子排序
 
 pub mod types;
 pub mod indicators;
@@ -14,6 +15,7 @@ pub mod model;
 pub mod backtest;
 pub mod factor;
 pub mod cross_section;
+pub mod advanced_features;
 
 // 重新导出常用类型
 pub use types::*;
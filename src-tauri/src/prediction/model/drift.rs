@@ -0,0 +1,250 @@
+//! 模型漂移检测
+//!
+//! 市场状态变化后，按旧数据训练的模型会逐渐失准。漂移从两方面衡量：
+//! - 输入漂移：训练期与训练结束后近期样本在模型所用特征上的分布差异，用群体稳定性指数
+//!   PSI = Σ (实际占比 - 期望占比) × ln(实际占比 / 期望占比) 度量，按训练期十分位分箱；
+//! - 性能漂移：训练后近期样本的方向准确率相对训练准确率的下降。
+
+use crate::db::models::HistoricalData;
+use crate::db::repository::get_historical_data;
+use crate::prediction::model::features::{
+    build_samples, feature_names, model_feature_columns, model_input_dim, DatedSample,
+};
+use crate::prediction::model::inference::model_training_horizon;
+use crate::prediction::model::management::{get_model_file_path, load_model_metadata};
+use crate::prediction::model::ml_inference::MlPredictor;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+
+/// PSI 超过该值视为显著漂移
+pub const PSI_DRIFT_THRESHOLD: f64 = 0.25;
+/// 近期方向准确率比训练准确率低出该值（小数）以上视为性能漂移
+pub const ACCURACY_DROP_THRESHOLD: f64 = 0.10;
+/// PSI 分箱数（按训练期分位数）
+const PSI_BINS: usize = 10;
+/// 分箱占比下限，避免空箱时 ln(0)
+const PSI_MIN_PCT: f64 = 1e-4;
+/// 训练期与近期各自至少需要的样本数
+const MIN_DRIFT_SAMPLES: usize = 20;
+
+/// 模型漂移检测报告
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelDriftReport {
+    pub model_id: String,
+    /// 模型实际使用的各特征的 PSI（顺序同 [`feature_names`]）
+    pub psi_per_feature: Vec<(String, f64)>,
+    pub max_psi: f64,
+    /// 训练时的方向准确率（0-1）
+    pub training_accuracy: f64,
+    /// 训练后近期样本的方向准确率（0-1）；模型推理全部失败时为 None
+    pub recent_accuracy: Option<f64>,
+    pub recent_samples: usize,
+    pub drift_detected: bool,
+    pub recommendation: String,
+}
+
+/// 以 `expected` 的分位数分箱，计算 `actual` 相对 `expected` 的 PSI；任一侧为空时返回 0
+pub fn population_stability_index(expected: &[f64], actual: &[f64]) -> f64 {
+    if expected.is_empty() || actual.is_empty() {
+        return 0.0;
+    }
+    let mut sorted = expected.to_vec();
+    sorted.sort_by(f64::total_cmp);
+    let mut edges: Vec<f64> = (1..PSI_BINS)
+        .map(|k| sorted[k * sorted.len() / PSI_BINS])
+        .collect();
+    edges.dedup();
+
+    let distribution = |values: &[f64]| {
+        let mut counts = vec![0usize; edges.len() + 1];
+        for value in values {
+            counts[edges.partition_point(|edge| edge <= value)] += 1;
+        }
+        counts
+            .into_iter()
+            .map(|count| (count as f64 / values.len() as f64).max(PSI_MIN_PCT))
+            .collect::<Vec<_>>()
+    };
+    distribution(expected)
+        .iter()
+        .zip(distribution(actual))
+        .map(|(expected_pct, actual_pct)| (actual_pct - expected_pct) * (actual_pct / expected_pct).ln())
+        .sum()
+}
+
+/// 逐特征计算 PSI，只统计 `columns` 中的特征列（模型屏蔽或未使用的特征不影响漂移结论）
+fn feature_psi(training: &[DatedSample], recent: &[DatedSample], columns: &[usize]) -> Vec<(String, f64)> {
+    let column = |samples: &[DatedSample], j: usize| -> Vec<f64> {
+        samples.iter().map(|s| s.features[j] as f64).collect()
+    };
+    feature_names()
+        .into_iter()
+        .enumerate()
+        .filter(|(j, _)| columns.contains(j))
+        .map(|(j, name)| {
+            (name, population_stability_index(&column(training, j), &column(recent, j)))
+        })
+        .collect()
+}
+
+/// 由特征 PSI 与准确率变化汇总漂移结论
+fn summarize_drift(
+    psi_per_feature: &[(String, f64)],
+    training_accuracy: f64,
+    recent_accuracy: Option<f64>,
+) -> (f64, bool, String) {
+    let max_psi = psi_per_feature.iter().map(|(_, psi)| *psi).fold(0.0, f64::max);
+    let drifted: Vec<&str> = psi_per_feature
+        .iter()
+        .filter(|(_, psi)| *psi > PSI_DRIFT_THRESHOLD)
+        .map(|(name, _)| name.as_str())
+        .collect();
+    let accuracy_drop = recent_accuracy
+        .map(|recent| training_accuracy - recent)
+        .filter(|drop| *drop > ACCURACY_DROP_THRESHOLD);
+
+    let mut reasons = Vec::new();
+    if !drifted.is_empty() {
+        reasons.push(format!("特征分布显著漂移（PSI > {PSI_DRIFT_THRESHOLD}）：{}", drifted.join("、")));
+    }
+    if let Some(drop) = accuracy_drop {
+        reasons.push(format!("近期方向准确率较训练时下降 {:.1} 个百分点", drop * 100.0));
+    }
+    let drift_detected = !reasons.is_empty();
+    let recommendation = if drift_detected {
+        format!("{}，建议用最新数据重训练", reasons.join("；"))
+    } else if max_psi > PSI_DRIFT_THRESHOLD / 2.0 {
+        format!("特征分布轻度变化（最大 PSI {max_psi:.3}），继续观察")
+    } else {
+        "模型输入分布与准确率稳定，无需重训练".to_string()
+    };
+    (max_psi, drift_detected, recommendation)
+}
+
+/// 近期样本的方向准确率；无可评估样本时为 None
+fn recent_direction_accuracy(predictor: &MlPredictor, samples: &[DatedSample]) -> Option<f64> {
+    let outcomes: Vec<bool> = samples
+        .iter()
+        .filter_map(|sample| {
+            let pred = predictor.predict(&sample.features).ok()?;
+            Some(pred * sample.fwd_return > 0.0)
+        })
+        .collect();
+    (!outcomes.is_empty())
+        .then(|| outcomes.iter().filter(|&&hit| hit).count() as f64 / outcomes.len() as f64)
+}
+
+fn parse_metadata_date(date: Option<&str>, label: &str) -> Result<NaiveDate, String> {
+    let date = date.ok_or_else(|| format!("旧模型缺少训练{label}元数据，无法检测漂移"))?;
+    NaiveDate::parse_from_str(date, "%Y-%m-%d").map_err(|e| format!("模型训练{label}元数据格式错误: {e}"))
+}
+
+/// 检测模型漂移：训练窗口内样本为基准分布，训练结束后最近 `lookback_days` 个样本为近期分布
+pub async fn detect_model_drift(
+    model_id: &str,
+    pool: &SqlitePool,
+    lookback_days: usize,
+) -> Result<ModelDriftReport, String> {
+    let metadata = load_model_metadata(model_id)?;
+    let training_start = parse_metadata_date(metadata.training_start_date.as_deref(), "开始日期")?;
+    let training_end = parse_metadata_date(metadata.training_end_date.as_deref(), "结束日期")?;
+    let model_path = get_model_file_path(model_id);
+    if !model_path.exists() {
+        return Err("模型权重文件不存在，请先训练".to_string());
+    }
+
+    let historical: Vec<HistoricalData> =
+        get_historical_data(&metadata.stock_code, "1900-01-01", "9999-12-31", pool)
+            .await
+            .map_err(|e| format!("获取历史数据失败: {e}"))?;
+    let horizon = model_training_horizon(&metadata.model_type, metadata.prediction_days);
+    let (training, after): (Vec<DatedSample>, Vec<DatedSample>) = build_samples(&historical, horizon)
+        .into_iter()
+        .filter(|sample| sample.date >= training_start)
+        .partition(|sample| sample.date <= training_end);
+    let recent = &after[after.len().saturating_sub(lookback_days)..];
+    if training.len() < MIN_DRIFT_SAMPLES || recent.len() < MIN_DRIFT_SAMPLES {
+        return Err(format!(
+            "样本不足：训练期 {} 个、训练后近期 {} 个，至少各需 {MIN_DRIFT_SAMPLES} 个",
+            training.len(),
+            recent.len()
+        ));
+    }
+
+    let predictor = MlPredictor::for_model(&metadata)?;
+    let recent_accuracy = recent_direction_accuracy(&predictor, recent);
+    // 模型训练时选用的特征；旧特征集模型不含末尾新增的特征
+    let input_dim = model_input_dim(metadata.feature_set_version);
+    let columns: Vec<usize> = model_feature_columns(&metadata.features)
+        .into_iter()
+        .filter(|&j| j < input_dim)
+        .collect();
+    let psi_per_feature = feature_psi(&training, recent, &columns);
+    let (max_psi, drift_detected, recommendation) =
+        summarize_drift(&psi_per_feature, metadata.accuracy, recent_accuracy);
+
+    Ok(ModelDriftReport {
+        model_id: model_id.to_string(),
+        psi_per_feature,
+        max_psi,
+        training_accuracy: metadata.accuracy,
+        recent_accuracy,
+        recent_samples: recent.len(),
+        drift_detected,
+        recommendation,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_population_stability_index() {
+        let expected: Vec<f64> = (0..1000).map(|i| i as f64 / 1000.0).collect();
+        assert!(population_stability_index(&expected, &expected).abs() < 1e-12);
+
+        // 整体右移 0.5：六成样本落入最高分箱，最低五个分箱为空
+        let shifted: Vec<f64> = expected.iter().map(|v| v + 0.5).collect();
+        let psi = population_stability_index(&expected, &shifted);
+        assert!(psi > PSI_DRIFT_THRESHOLD);
+        assert_eq!(population_stability_index(&[], &shifted), 0.0);
+    }
+
+    #[test]
+    fn test_feature_psi_skips_unused_columns() {
+        let sample = |k: usize, shift: f32| DatedSample {
+            date: NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(),
+            features: std::array::from_fn(|j| if j == 0 { k as f32 } else { k as f32 + shift }),
+            fwd_return: 0.0,
+        };
+        let training: Vec<DatedSample> = (0..100).map(|k| sample(k, 0.0)).collect();
+        let recent: Vec<DatedSample> = (0..100).map(|k| sample(k, 80.0)).collect();
+
+        let psi = feature_psi(&training, &recent, &[0]);
+        assert_eq!(psi.len(), 1);
+        assert_eq!(psi[0].0, "ret_1d");
+        assert!(psi[0].1.abs() < 1e-12);
+        assert!(feature_psi(&training, &recent, &[0, 4])[1].1 > PSI_DRIFT_THRESHOLD);
+    }
+
+    #[test]
+    fn test_summarize_drift() {
+        let stable = vec![("ret_1d".to_string(), 0.02), ("rsi14".to_string(), 0.05)];
+        let (max_psi, drifted, _) = summarize_drift(&stable, 0.56, Some(0.54));
+        assert_eq!(max_psi, 0.05);
+        assert!(!drifted);
+
+        // 准确率下降超过 10 个百分点即视为漂移
+        let (_, drifted, recommendation) = summarize_drift(&stable, 0.60, Some(0.45));
+        assert!(drifted);
+        assert!(recommendation.contains("15.0"));
+
+        let shifted = vec![("ret_1d".to_string(), 0.02), ("rsi14".to_string(), 0.4)];
+        let (max_psi, drifted, recommendation) = summarize_drift(&shifted, 0.56, None);
+        assert_eq!(max_psi, 0.4);
+        assert!(drifted);
+        assert!(recommendation.contains("rsi14"));
+    }
+}
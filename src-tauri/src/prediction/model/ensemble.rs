@@ -0,0 +1,570 @@
+//! 集成学习预测
+//!
+//! 组合三类互相独立的预测源：
+//! 1. Candle MLP 模型（该股已训练模型，未训练时跳过）
+//! 2. 线性中位数回归（在同一特征集上即时拟合的 q0.5 分位数回归）
+//! 3. 量价策略（量价信号方向 × 已实现波动率）
+//!
+//! 各源给出预测周期内的累计涨跌幅（%），按配置的组合方式合成；
+//! 集成置信度为"与集成方向一致"的成员置信度的加权均值，成员分歧越大置信度越低。
+//! 预测区间由同一特征集上的 q0.1 / q0.9 分位数回归给出。
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::db::{models::HistoricalData, repository::get_recent_historical_data, DbPool};
+use crate::prediction::analysis::{prediction_interval, volume};
+use crate::prediction::model::features::{
+    build_samples, latest_features, DatedSample, FEATURE_DIM,
+};
+use crate::prediction::model::inference::{
+    analyze, attach_live_data_staleness, daily_change_from_horizon_change,
+    diagnostics_from_analysis, ml_daily_change_for_day, model_training_horizon,
+    select_model_for_request, signal_from_change_percent, AnalysisOptions,
+};
+use crate::prediction::model::linear::LinearRegression;
+use crate::prediction::model::ml_inference::MlPredictor;
+use crate::prediction::model::quantile::{
+    apply_quantile_bands, fit_quantile_regression, sample_features, to_quantile_input,
+    QuantileBands, QuantileForecast, QuantileRegressionModel, MEDIAN_QUANTILE,
+};
+use crate::prediction::strategy::professional_engine;
+use crate::prediction::types::{
+    LastRealData, ModelInfo, Prediction, PredictionRequest, PredictionResponse,
+};
+use crate::utils::date::get_next_trading_day;
+
+/// 成员名称（`EnsembleConfig::model_weights` 的键）；
+/// 线性成员已改为中位数回归，键名沿用以兼容已保存的权重配置
+pub const CANDLE_MEMBER: &str = "candle_mlp";
+pub const LINEAR_MEMBER: &str = "linear_regression";
+pub const VOLUME_PRICE_MEMBER: &str = "volume_price";
+
+/// 集成预测使用的历史窗口
+const ENSEMBLE_HISTORY_DAYS: usize = 500;
+/// 近期表现评估样本数（Stacking / BestOfRecent 使用）
+const RECENT_EVAL_SAMPLES: usize = 40;
+/// 线性（中位数）回归至少需要的训练样本数
+const MIN_LINEAR_SAMPLES: usize = 60;
+/// 线性（中位数）回归置信度评估的留出比例
+const LINEAR_HOLDOUT_RATIO: f64 = 0.2;
+/// 集成置信度上限（与单模型路径一致）
+const MAX_CONFIDENCE: f64 = 0.92;
+
+/// 成员预测的组合方式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CombinationMethod {
+    /// 按 `model_weights` 加权平均
+    #[default]
+    WeightedAverage,
+    /// 以近期各成员预测为特征、实际收益为标签拟合非负组合权重
+    Stacking,
+    /// 仅采用近期平均绝对误差最小的成员
+    BestOfRecent,
+}
+
+/// 集成配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnsembleConfig {
+    /// 成员权重（键见 `CANDLE_MEMBER` 等）；未列出的成员权重为 0
+    pub model_weights: HashMap<String, f64>,
+    #[serde(default)]
+    pub combination_method: CombinationMethod,
+}
+
+impl Default for EnsembleConfig {
+    fn default() -> Self {
+        Self {
+            model_weights: [CANDLE_MEMBER, LINEAR_MEMBER, VOLUME_PRICE_MEMBER]
+                .into_iter()
+                .map(|name| (name.to_string(), 1.0))
+                .collect(),
+            combination_method: CombinationMethod::WeightedAverage,
+        }
+    }
+}
+
+/// 单个成员的预测
+#[derive(Debug, Clone, PartialEq)]
+pub struct MemberPrediction {
+    pub name: &'static str,
+    /// 预测周期内累计涨跌幅（%）
+    pub change_percent: f64,
+    pub confidence: f64,
+}
+
+/// 合成结果：累计涨跌幅、置信度与实际使用的成员权重
+#[derive(Debug, Clone)]
+pub struct EnsembleOutcome {
+    pub change_percent: f64,
+    pub confidence: f64,
+    pub weights: Vec<(&'static str, f64)>,
+}
+
+/// 按权重合成成员预测；权重全为 0 时退化为等权
+pub fn combine_members(members: &[MemberPrediction], weights: &[f64]) -> EnsembleOutcome {
+    let mut weights: Vec<f64> = weights.iter().map(|w| w.max(0.0)).collect();
+    if weights.iter().sum::<f64>() <= 0.0 {
+        weights = vec![1.0; members.len()];
+    }
+    let total: f64 = weights.iter().sum();
+    if members.is_empty() || total <= 0.0 {
+        return EnsembleOutcome {
+            change_percent: 0.0,
+            confidence: 0.0,
+            weights: Vec::new(),
+        };
+    }
+
+    let change_percent = members
+        .iter()
+        .zip(&weights)
+        .map(|(m, w)| m.change_percent * w)
+        .sum::<f64>()
+        / total;
+    // 与集成方向相反的成员不贡献置信度，分歧即稀释
+    let confidence = members
+        .iter()
+        .zip(&weights)
+        .filter(|(m, _)| m.change_percent * change_percent >= 0.0)
+        .map(|(m, w)| m.confidence * w)
+        .sum::<f64>()
+        / total;
+
+    EnsembleOutcome {
+        change_percent,
+        confidence: confidence.clamp(0.0, MAX_CONFIDENCE),
+        weights: members
+            .iter()
+            .zip(weights)
+            .map(|(m, w)| (m.name, w / total))
+            .collect(),
+    }
+}
+
+/// Stacking 权重：带截距岭回归 actual ≈ b + Σ wᵢ·predᵢ，只取成员权重 wᵢ（截距 b 不参与组合），负权重截断为 0
+pub fn fit_stacking_weights(history: &[Vec<f64>], actual: &[f64]) -> Vec<f64> {
+    let k = history.first().map_or(0, Vec::len);
+    if k == 0 || history.len() != actual.len() {
+        return vec![1.0; k];
+    }
+    let columns: Vec<[f32; FEATURE_DIM]> = history
+        .iter()
+        .map(|row| {
+            let mut padded = [0.0f32; FEATURE_DIM];
+            for (slot, value) in padded.iter_mut().zip(row) {
+                *slot = *value as f32;
+            }
+            padded
+        })
+        .collect();
+    // 复用线性回归求解器：成员数远小于 FEATURE_DIM，余下维度恒为 0
+    match LinearRegression::fit(&columns, actual) {
+        Some(model) => model.weights[..k].iter().map(|w| w.max(0.0)).collect(),
+        None => vec![1.0; k],
+    }
+}
+
+/// 近期平均绝对误差最小的成员下标
+pub fn best_recent_member(history: &[Vec<f64>], actual: &[f64]) -> Option<usize> {
+    let k = history.first().map_or(0, Vec::len);
+    (0..k).min_by(|&a, &b| {
+        let mae = |idx: usize| {
+            history
+                .iter()
+                .zip(actual)
+                .map(|(row, y)| (row[idx] - y).abs())
+                .sum::<f64>()
+        };
+        mae(a).total_cmp(&mae(b))
+    })
+}
+
+/// 由单日收益按预测天数复利得到累计涨跌幅（%）
+fn compound_daily_change(daily_change: f64, days: usize) -> f64 {
+    ((1.0 + daily_change / 100.0).powi(days as i32) - 1.0) * 100.0
+}
+
+/// 量价策略成员：量价信号方向 × 信号置信度 × 预测周期内已实现波动幅度
+fn volume_price_change(historical: &[HistoricalData], horizon: usize) -> (f64, f64) {
+    let prices: Vec<f64> = historical.iter().map(|h| h.close).collect();
+    let highs: Vec<f64> = historical.iter().map(|h| h.high).collect();
+    let lows: Vec<f64> = historical.iter().map(|h| h.low).collect();
+    let volumes: Vec<i64> = historical.iter().map(|h| h.volume).collect();
+    let signal = volume::analyze_volume_price(&prices, &highs, &lows, &volumes);
+    let direction = match signal.direction.as_str() {
+        "上涨" => 1.0,
+        "下跌" => -1.0,
+        _ => 0.0,
+    };
+    let sigma = prediction_interval::realized_daily_vol(&prices);
+    let change = direction * signal.confidence * sigma * (horizon as f64).sqrt() * 100.0;
+    (change, signal.confidence)
+}
+
+fn sample_labels(samples: &[DatedSample]) -> Vec<f64> {
+    samples.iter().map(|s| s.fwd_return).collect()
+}
+
+/// 线性成员：中位数回归（pinball 损失对收益厚尾比最小二乘稳健）
+fn fit_linear(samples: &[DatedSample]) -> Option<QuantileRegressionModel> {
+    if samples.len() < MIN_LINEAR_SAMPLES {
+        return None;
+    }
+    Some(fit_quantile_regression(
+        &sample_features(samples),
+        &sample_labels(samples),
+        MEDIAN_QUANTILE,
+    ))
+}
+
+/// 线性（中位数）回归留出段方向准确率
+fn linear_holdout_accuracy(samples: &[DatedSample], horizon: usize) -> f64 {
+    let test_len = ((samples.len() as f64) * LINEAR_HOLDOUT_RATIO) as usize;
+    let train_end = samples.len().saturating_sub(test_len + horizon);
+    let Some(model) = fit_linear(&samples[..train_end]) else {
+        return 0.0;
+    };
+    let test = &samples[samples.len() - test_len..];
+    if test.is_empty() {
+        return 0.0;
+    }
+    let correct = test
+        .iter()
+        .filter(|s| model.predict(&to_quantile_input(&s.features)) * s.fwd_return > 0.0)
+        .count();
+    correct as f64 / test.len() as f64
+}
+
+/// 已加载的 Candle 成员
+struct CandleMember {
+    model: ModelInfo,
+    predictor: MlPredictor,
+}
+
+impl CandleMember {
+    fn horizon_change(&self, features: &[f32], horizon: usize) -> Option<f64> {
+        let ml_return = self.predictor.predict(features).ok()?;
+        let model_horizon =
+            model_training_horizon(&self.model.model_type, self.model.prediction_days);
+        let daily = daily_change_from_horizon_change(ml_return, model_horizon);
+        let gross = (1..=horizon)
+            .map(|day| 1.0 + ml_daily_change_for_day(daily, model_horizon, day) / 100.0)
+            .product::<f64>();
+        let change = (gross - 1.0) * 100.0;
+        change.is_finite().then_some(change)
+    }
+}
+
+/// 在近期样本上回放各成员预测：返回每个样本的成员预测（%）与实际收益（%）。
+/// 线性（中位数）回归只用该样本标签窗口之前的数据拟合；Candle 模型的训练集可能覆盖这些样本，
+/// 其近期误差偏乐观。
+fn replay_recent(
+    historical: &[HistoricalData],
+    samples: &[DatedSample],
+    horizon: usize,
+    candle: Option<&CandleMember>,
+) -> (Vec<Vec<f64>>, Vec<f64>) {
+    let mut history = Vec::new();
+    let mut actual = Vec::new();
+    let start = samples.len().saturating_sub(RECENT_EVAL_SAMPLES);
+    for (idx, sample) in samples.iter().enumerate().skip(start) {
+        let Some(bar_idx) = historical.iter().position(|h| h.date == sample.date) else {
+            continue;
+        };
+        let Some(linear) = fit_linear(&samples[..idx.saturating_sub(horizon)]) else {
+            continue;
+        };
+        let mut row = Vec::with_capacity(3);
+        if let Some(candle) = candle {
+            let Some(change) = candle.horizon_change(&sample.features, horizon) else {
+                continue;
+            };
+            row.push(change);
+        }
+        row.push(linear.predict(&to_quantile_input(&sample.features)) * 100.0);
+        row.push(volume_price_change(&historical[..=bar_idx], horizon).0);
+        history.push(row);
+        actual.push(sample.fwd_return * 100.0);
+    }
+    (history, actual)
+}
+
+/// 运行全部成员并按配置合成预测
+pub async fn predict_ensemble(
+    request: &PredictionRequest,
+    config: &EnsembleConfig,
+    pool: &DbPool,
+) -> Result<PredictionResponse, String> {
+    let historical = get_recent_historical_data(&request.stock_code, ENSEMBLE_HISTORY_DAYS, pool)
+        .await
+        .map_err(|e| format!("获取历史数据失败: {e}"))?;
+    if historical.len() < 120 {
+        return Err("历史数据不足120天，无法进行集成预测".to_string());
+    }
+    let horizon = request.prediction_days.max(1);
+
+    // 成员 1：Candle 模型（无已训练模型时跳过）
+    let candle = match select_model_for_request(request)? {
+        Some(model) => Some(CandleMember {
+            predictor: MlPredictor::for_model(&model)?,
+            model,
+        }),
+        None => None,
+    };
+    let latest = latest_features(&historical).ok_or("数据不足以构造特征")?;
+    let mut members = Vec::with_capacity(3);
+    if let Some(candle) = &candle {
+        if let Some(change) = candle.horizon_change(&latest, horizon) {
+            members.push(MemberPrediction {
+                name: CANDLE_MEMBER,
+                change_percent: change,
+                confidence: candle.model.accuracy.clamp(0.0, MAX_CONFIDENCE),
+            });
+        }
+    }
+
+    // 成员 2：线性中位数回归；q0.1 / q0.9 同时给出预测区间
+    let samples = build_samples(&historical, horizon);
+    if samples.len() < MIN_LINEAR_SAMPLES {
+        return Err("样本不足，无法拟合分位数回归".to_string());
+    }
+    let bands = QuantileBands::fit(&sample_features(&samples), &sample_labels(&samples));
+    let quantiles = bands.predict(&to_quantile_input(&latest));
+    members.push(MemberPrediction {
+        name: LINEAR_MEMBER,
+        change_percent: quantiles.median * 100.0,
+        confidence: linear_holdout_accuracy(&samples, horizon),
+    });
+
+    // 成员 3：量价策略
+    let (volume_change, volume_confidence) = volume_price_change(&historical, horizon);
+    members.push(MemberPrediction {
+        name: VOLUME_PRICE_MEMBER,
+        change_percent: volume_change,
+        confidence: volume_confidence,
+    });
+
+    let configured: Vec<f64> = members
+        .iter()
+        .map(|m| config.model_weights.get(m.name).copied().unwrap_or(0.0))
+        .collect();
+    let weights = match config.combination_method {
+        CombinationMethod::WeightedAverage => configured,
+        CombinationMethod::Stacking | CombinationMethod::BestOfRecent => {
+            let (history, actual) = replay_recent(&historical, &samples, horizon, candle.as_ref());
+            if history.is_empty() || history[0].len() != members.len() {
+                configured
+            } else if config.combination_method == CombinationMethod::Stacking {
+                fit_stacking_weights(&history, &actual)
+            } else {
+                let best = best_recent_member(&history, &actual);
+                (0..members.len())
+                    .map(|idx| if Some(idx) == best { 1.0 } else { 0.0 })
+                    .collect()
+            }
+        }
+    };
+    let outcome = combine_members(&members, &weights);
+
+    build_response(
+        request,
+        &historical,
+        &members,
+        &outcome,
+        &quantiles,
+        config.combination_method,
+    )
+}
+
+fn build_response(
+    request: &PredictionRequest,
+    historical: &[HistoricalData],
+    members: &[MemberPrediction],
+    outcome: &EnsembleOutcome,
+    quantiles: &QuantileForecast,
+    method: CombinationMethod,
+) -> Result<PredictionResponse, String> {
+    let last_data = historical.last().ok_or("未找到历史数据")?;
+    let current_price = last_data.close;
+    let horizon = request.prediction_days.max(1);
+    let daily_change = daily_change_from_horizon_change(outcome.change_percent, horizon);
+    let (limit_down, limit_up) =
+        professional_engine::get_stock_price_limits(Some(&request.stock_code));
+
+    let mut key_factors: Vec<String> = members
+        .iter()
+        .map(|m| {
+            let weight = outcome
+                .weights
+                .iter()
+                .find(|(name, _)| *name == m.name)
+                .map_or(0.0, |(_, w)| *w);
+            format!(
+                "{}: {:+.2}%（置信 {:.0}%，权重 {:.0}%）",
+                m.name,
+                m.change_percent,
+                m.confidence * 100.0,
+                weight * 100.0
+            )
+        })
+        .collect();
+    key_factors.push(format!("组合方式: {method:?}"));
+    key_factors.push(format!(
+        "{horizon}日分位数区间 q0.1 {:+.2}% / q0.5 {:+.2}% / q0.9 {:+.2}%",
+        quantiles.lower * 100.0,
+        quantiles.median * 100.0,
+        quantiles.upper * 100.0
+    ));
+    key_factors.push(format!(
+        "{horizon}日集成预期 {:+.2}%（单日等效 {:+.2}%）",
+        outcome.change_percent,
+        compound_daily_change(daily_change, 1)
+    ));
+
+    let mut predictions = Vec::with_capacity(horizon);
+    let mut last_date = last_data.date;
+    let mut last_price = current_price;
+    for _ in 0..horizon {
+        let target_date = get_next_trading_day(last_date);
+        let change_percent = daily_change.clamp(limit_down, limit_up);
+        let predicted_price = last_price * (1.0 + change_percent / 100.0);
+        predictions.push(Prediction {
+            target_date: target_date.format("%Y-%m-%d").to_string(),
+            predicted_price,
+            predicted_change_percent: change_percent,
+            confidence: outcome.confidence,
+            trading_signal: Some(signal_from_change_percent(change_percent).to_string()),
+            signal_strength: Some(outcome.confidence),
+            technical_indicators: None,
+            prediction_reason: Some(format!(
+                "集成预测（{} 个成员，成员方向分歧会降低置信度）",
+                members.len()
+            )),
+            key_factors: Some(key_factors.clone()),
+            interval: None,
+            stress_interval: None,
+            explanation: None,
+            prediction_low: predicted_price,
+            prediction_high: predicted_price,
+        });
+        last_date = target_date;
+        last_price = predicted_price;
+    }
+
+    let closes: Vec<f64> = historical.iter().map(|h| h.close).collect();
+    prediction_interval::attach_prediction_intervals(
+        &mut predictions,
+        &closes,
+        current_price,
+        prediction_interval::DEFAULT_COVERAGE,
+    );
+    // 最低/最高价改用分位数回归的 80% 区间，`interval` 仍保留波动率校准带供对照
+    apply_quantile_bands(&mut predictions, quantiles);
+
+    let highs: Vec<f64> = historical.iter().map(|h| h.high).collect();
+    let lows: Vec<f64> = historical.iter().map(|h| h.low).collect();
+    let volumes: Vec<i64> = historical.iter().map(|h| h.volume).collect();
+    let opens: Vec<f64> = historical.iter().map(|h| h.open).collect();
+    let analysis = analyze(
+        &closes,
+        &highs,
+        &lows,
+        &volumes,
+        &opens,
+        AnalysisOptions {
+            turnover_rate: last_data.turnover_rate,
+            prediction_days: horizon,
+            stock_code: Some(&request.stock_code),
+            market_ad_ratio: None,
+            market_fear_greed: None,
+            news_sentiment: None,
+            learned_factor_weights: None,
+            beta: None,
+            sector_relative_strength: None,
+            fundamental: None,
+            indicator_config: None,
+        },
+    );
+    let diagnostics = diagnostics_from_analysis(
+        historical,
+        &analysis,
+        &predictions,
+        "ensemble",
+        "点估计为 Candle 模型、中位数回归与量价策略的组合；最低/最高价为 q0.1/q0.9 分位数回归区间；成员置信度不是上涨概率，需与走步回测对照。",
+        None,
+    );
+
+    let mut response = PredictionResponse {
+        predictions,
+        last_real_data: Some(LastRealData {
+            date: last_data.date.format("%Y-%m-%d").to_string(),
+            price: current_price,
+            change_percent: last_data.change_percent,
+        }),
+        diagnostics: Some(diagnostics),
+        data_warning: None,
+    };
+    attach_live_data_staleness(&mut response, last_data.date);
+    Ok(response)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn member(name: &'static str, change: f64, confidence: f64) -> MemberPrediction {
+        MemberPrediction {
+            name,
+            change_percent: change,
+            confidence,
+        }
+    }
+
+    #[test]
+    fn test_combine_members_weights_and_disagreement() {
+        let members = [
+            member(CANDLE_MEMBER, 2.0, 0.6),
+            member(LINEAR_MEMBER, 1.0, 0.5),
+            member(VOLUME_PRICE_MEMBER, -1.0, 0.7),
+        ];
+        let outcome = combine_members(&members, &[1.0, 1.0, 3.0]);
+        assert!((outcome.change_percent - 0.0).abs() < 1e-9);
+
+        let outcome = combine_members(&members, &[1.0, 1.0, 1.0]);
+        assert!((outcome.change_percent - 2.0 / 3.0).abs() < 1e-9);
+        // 量价成员方向相反，不贡献置信度
+        assert!((outcome.confidence - (0.6 + 0.5) / 3.0).abs() < 1e-9);
+
+        let agreeing = combine_members(&members[..2], &[1.0, 1.0]);
+        assert!(agreeing.confidence > outcome.confidence);
+
+        // 权重全为 0 退化为等权
+        let fallback = combine_members(&members, &[0.0, 0.0, 0.0]);
+        assert!((fallback.change_percent - 2.0 / 3.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_stacking_and_best_of_recent() {
+        // 实际收益恰为第 1 个成员的预测，第 2 个成员为噪声
+        let history: Vec<Vec<f64>> = (0..40)
+            .map(|i| {
+                let x = (i as f64 * 0.7).sin();
+                vec![x, (i as f64 * 2.3).cos()]
+            })
+            .collect();
+        let actual: Vec<f64> = history.iter().map(|row| row[0]).collect();
+        let weights = fit_stacking_weights(&history, &actual);
+        assert!(weights[0] > 0.9);
+        assert!(weights[1].abs() < 0.1);
+        assert_eq!(best_recent_member(&history, &actual), Some(0));
+    }
+
+    #[test]
+    fn test_compound_daily_change_round_trip() {
+        let daily = daily_change_from_horizon_change(10.0, 5);
+        assert!((compound_daily_change(daily, 5) - 10.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,201 @@
+//! 基于互信息的特征选择
+//!
+//! 用 k 近邻互信息估计（Kraskov-Stögbauer-Grassberger 算法 1）衡量每个候选特征与训练目标的相关性，
+//! 可捕捉线性相关系数看不到的非线性关系。各列先做 z-score 标准化，使最大范数下各维尺度可比。
+
+use crate::db::models::HistoricalData;
+use crate::prediction::model::features::{build_dataset_for_horizon, feature_names, FEATURE_DIM};
+
+/// 近邻数：KSG 推荐 3~10，样本几百条时取 3 偏差与方差较均衡
+const KSG_NEIGHBORS: usize = 3;
+/// 参与估计的最少样本数
+pub const MIN_SELECTION_SAMPLES: usize = 60;
+
+/// 估计各特征列与目标的互信息，返回得分最高的 `top_k` 个特征下标（按得分降序）。
+///
+/// `features[i]` 为第 i 个特征列，长度须与 `targets` 一致；长度不一致的列得分记为 0。
+pub fn select_features_by_mutual_information(
+    features: &[Vec<f64>],
+    targets: &[f64],
+    top_k: usize,
+) -> Vec<usize> {
+    let scores = mutual_information_scores(features, targets);
+    let mut ranked: Vec<usize> = (0..features.len()).collect();
+    ranked.sort_by(|&a, &b| scores[b].total_cmp(&scores[a]).then(a.cmp(&b)));
+    ranked.truncate(top_k);
+    ranked
+}
+
+/// 各特征列与目标的互信息估计值（nats，负估计截断为 0）
+pub fn mutual_information_scores(features: &[Vec<f64>], targets: &[f64]) -> Vec<f64> {
+    let y = standardize(targets);
+    features
+        .iter()
+        .map(|column| match (&y, column.len() == targets.len()) {
+            (Some(y), true) => standardize(column)
+                .map_or(0.0, |x| ksg_mutual_information(&x, y, KSG_NEIGHBORS)),
+            _ => 0.0,
+        })
+        .collect()
+}
+
+/// 从日线构造模型特征（见 [`feature_names`]），按与训练目标的互信息选出 `top_k` 个特征名。
+///
+/// 样本与训练一致取自 [`build_dataset_for_horizon`]（已剔除回看窗口与指标预热期）；
+/// 目标口径：`close` / `change_percent` 为次日收益率，`direction` 为次日涨跌方向（±1）。
+pub fn auto_select_features(
+    stock_data: &[HistoricalData],
+    target: &str,
+    top_k: usize,
+) -> Result<Vec<String>, String> {
+    if !matches!(target, "close" | "change_percent" | "direction") {
+        return Err(format!("不支持的训练目标: {target}"));
+    }
+    let (rows, labels, n) = build_dataset_for_horizon(stock_data, 1);
+    if n < MIN_SELECTION_SAMPLES {
+        return Err(format!(
+            "历史数据不足：特征选择至少需要 {MIN_SELECTION_SAMPLES} 个有效样本，当前 {n}"
+        ));
+    }
+    let targets: Vec<f64> = labels
+        .iter()
+        .map(|&ret| match target {
+            "direction" => (ret as f64).signum(),
+            _ => ret as f64,
+        })
+        .collect();
+    let columns: Vec<Vec<f64>> = (0..FEATURE_DIM)
+        .map(|j| rows.chunks(FEATURE_DIM).map(|row| row[j] as f64).collect())
+        .collect();
+
+    let names = feature_names();
+    Ok(select_features_by_mutual_information(&columns, &targets, top_k)
+        .into_iter()
+        .map(|i| names[i].clone())
+        .collect())
+}
+
+/// z-score 标准化；存在非有限值或方差为 0 时返回 None
+fn standardize(values: &[f64]) -> Option<Vec<f64>> {
+    if values.is_empty() || values.iter().any(|v| !v.is_finite()) {
+        return None;
+    }
+    let n = values.len() as f64;
+    let mean = values.iter().sum::<f64>() / n;
+    let std = (values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / n).sqrt();
+    if std < 1e-12 {
+        return None;
+    }
+    Some(values.iter().map(|v| (v - mean) / std).collect())
+}
+
+/// KSG 互信息估计：I(X;Y) = ψ(k) + ψ(N) - <ψ(n_x + 1) + ψ(n_y + 1)>。
+///
+/// ε_i 为联合空间（最大范数）中第 k 近邻的距离，n_x、n_y 为边缘空间中距离严格小于 ε_i 的样本数。
+fn ksg_mutual_information(x: &[f64], y: &[f64], k: usize) -> f64 {
+    let n = x.len();
+    if n <= k {
+        return 0.0;
+    }
+    let mut joint = Vec::with_capacity(n - 1);
+    let mut marginal_sum = 0.0;
+    for i in 0..n {
+        joint.clear();
+        joint.extend(
+            (0..n)
+                .filter(|&j| j != i)
+                .map(|j| (x[i] - x[j]).abs().max((y[i] - y[j]).abs())),
+        );
+        let (_, &mut eps, _) = joint.select_nth_unstable_by(k - 1, f64::total_cmp);
+        let nx = (0..n).filter(|&j| j != i && (x[i] - x[j]).abs() < eps).count();
+        let ny = (0..n).filter(|&j| j != i && (y[i] - y[j]).abs() < eps).count();
+        marginal_sum += digamma((nx + 1) as f64) + digamma((ny + 1) as f64);
+    }
+    (digamma(k as f64) + digamma(n as f64) - marginal_sum / n as f64).max(0.0)
+}
+
+/// 双伽马函数 ψ(x)（x > 0）：先用递推 ψ(x) = ψ(x + 1) - 1/x 抬升到 x ≥ 6，再用渐近展开
+fn digamma(mut x: f64) -> f64 {
+    let mut result = 0.0;
+    while x < 6.0 {
+        result -= 1.0 / x;
+        x += 1.0;
+    }
+    let inv2 = 1.0 / (x * x);
+    result + x.ln() - 0.5 / x
+        - inv2 * (1.0 / 12.0 - inv2 * (1.0 / 120.0 - inv2 * (1.0 / 252.0)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    #[test]
+    fn test_digamma_known_values() {
+        // ψ(1) = -γ，ψ(n + 1) = ψ(n) + 1/n
+        assert!((digamma(1.0) + 0.577_215_664_901_532_9).abs() < 1e-10);
+        assert!((digamma(4.0) - (digamma(3.0) + 1.0 / 3.0)).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_select_features_ranks_dependent_columns_first() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let n = 300;
+        let noise: Vec<f64> = (0..n).map(|_| rng.gen_range(-1.0..1.0)).collect();
+        let signal: Vec<f64> = (0..n).map(|_| rng.gen_range(-1.0..1.0)).collect();
+        let targets: Vec<f64> = signal
+            .iter()
+            .map(|s| s + rng.gen_range(-0.05..0.05))
+            .collect();
+        // 与目标非线性相关（线性相关系数约为 0），噪声较大故得分低于线性信号
+        let squared: Vec<f64> = signal
+            .iter()
+            .map(|s| s * s + rng.gen_range(-0.1..0.1))
+            .collect();
+        let constant = vec![1.0; n];
+        let features = vec![noise, signal, squared, constant];
+
+        let scores = mutual_information_scores(&features, &targets);
+        assert!(scores[0] < 0.1, "独立特征互信息应接近 0: {}", scores[0]);
+        assert_eq!(scores[3], 0.0);
+        assert!(scores[2] > scores[0]);
+
+        assert_eq!(select_features_by_mutual_information(&features, &targets, 2), vec![1, 2]);
+        assert_eq!(select_features_by_mutual_information(&features, &targets, 10).len(), 4);
+    }
+
+    #[test]
+    fn test_auto_select_features_returns_model_feature_names() {
+        let mut rng = StdRng::seed_from_u64(11);
+        let start = chrono::NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+        let mut close = 10.0;
+        let history: Vec<HistoricalData> = (0..150)
+            .map(|i| {
+                close *= 1.0 + rng.gen_range(-0.03..0.03);
+                HistoricalData {
+                    symbol: "600000".to_string(),
+                    date: start + chrono::Duration::days(i),
+                    open: close,
+                    close,
+                    high: close * 1.01,
+                    low: close * 0.99,
+                    volume: rng.gen_range(1_000..5_000),
+                    amount: close * 1_000.0,
+                    amplitude: 2.0,
+                    turnover_rate: rng.gen_range(0.5..3.0),
+                    volume_ratio: 1.0,
+                    change_percent: 0.0,
+                    change: 0.0,
+                }
+            })
+            .collect();
+
+        let selected = auto_select_features(&history, "direction", 4).unwrap();
+        assert_eq!(selected.len(), 4);
+        assert!(selected.iter().all(|name| feature_names().contains(name)));
+        assert!(auto_select_features(&history[..60], "close", 4).is_err());
+        assert!(auto_select_features(&history, "volume", 4).is_err());
+    }
+}
@@ -4,11 +4,68 @@
 //! 标签为指定预测周期收益率（回归目标），其符号即方向。
 
 use crate::db::models::HistoricalData;
+use crate::prediction::analysis::market_regime::{calculate_hurst_exponent, HURST_MAX_LAG};
+use crate::prediction::indicators::get_feature_required_days;
+use serde::Serialize;
 
 /// 特征维度
-pub const FEATURE_DIM: usize = 10;
+pub const FEATURE_DIM: usize = 11;
+/// 特征集版本：特征列变更时递增，随模型元数据保存。新特征只追加在末尾，
+/// 旧版本模型取当前特征的前若干列即可预测
+pub const FEATURE_SET_VERSION: u32 = 2;
 /// 特征所需的最小回看窗口
 const LOOKBACK: usize = 20;
+/// Hurst 指数的价格窗口；不足该窗口时取中性值 0.5（仅实时预测会用到）
+const HURST_WINDOW: usize = 60;
+
+/// 指标预热期（K线数）：序列开头不足预热期的指标值只是部分窗口的近似（如 RSI 取 50、
+/// Hurst 取 0.5），作为训练样本会引入边缘效应
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WarmupConfig {
+    pub rsi_warmup: usize,
+    pub macd_warmup: usize,
+    pub kdj_warmup: usize,
+    pub cci_warmup: usize,
+    pub hurst_warmup: usize,
+}
+
+impl Default for WarmupConfig {
+    /// 默认取各指标的最少计算天数（见 [`get_feature_required_days`]）；
+    /// Hurst 在第 `HURST_WINDOW` 根K线起才有完整窗口
+    fn default() -> Self {
+        Self {
+            rsi_warmup: get_feature_required_days("rsi"),
+            macd_warmup: get_feature_required_days("macd"),
+            kdj_warmup: get_feature_required_days("kdj_k"),
+            cci_warmup: get_feature_required_days("cci"),
+            hurst_warmup: HURST_WINDOW - 1,
+        }
+    }
+}
+
+impl WarmupConfig {
+    /// 需要剔除的样本数：各指标预热期的最大值
+    pub fn max_warmup(&self) -> usize {
+        self.rsi_warmup
+            .max(self.macd_warmup)
+            .max(self.kdj_warmup)
+            .max(self.cci_warmup)
+            .max(self.hurst_warmup)
+    }
+}
+
+/// 剔除按时间正序排列的样本中前 `max_warmup` 条（`features[i]` 为第 i 根K线的特征行）
+pub fn filter_warmup_samples<F: Clone, T: Copy>(
+    features: &[F],
+    targets: &[T],
+    config: &WarmupConfig,
+) -> (Vec<F>, Vec<T>) {
+    let skip = config.max_warmup();
+    (
+        features.iter().skip(skip).cloned().collect(),
+        targets.iter().skip(skip).copied().collect(),
+    )
+}
 
 /// 各特征名称（用于模型元数据）
 pub fn feature_names() -> Vec<String> {
@@ -23,12 +80,115 @@ pub fn feature_names() -> Vec<String> {
         "turnover_rate",
         "range_position20",
         "amplitude",
+        "hurst60",
     ]
     .iter()
     .map(|s| s.to_string())
     .collect()
 }
 
+/// 特征集版本对应的模型输入维度；元数据无版本字段的旧模型为版本 1（不含 hurst60）
+pub fn model_input_dim(feature_set_version: Option<u32>) -> usize {
+    match feature_set_version {
+        None | Some(1) => FEATURE_DIM - 1,
+        Some(_) => FEATURE_DIM,
+    }
+}
+
+/// 扁平 n×FEATURE_DIM 特征每行只保留前 `input_dim` 列（旧特征集模型的输入）
+pub fn truncate_feature_columns(features: &[f32], input_dim: usize) -> Vec<f32> {
+    if input_dim >= FEATURE_DIM {
+        return features.to_vec();
+    }
+    features
+        .chunks(FEATURE_DIM)
+        .flat_map(|row| row[..input_dim].iter().copied())
+        .collect()
+}
+
+/// 特征名 → 特征列索引；空列表表示使用全部特征
+pub fn select_feature_columns(features: &[String]) -> Result<Vec<usize>, String> {
+    let names = feature_names();
+    if features.is_empty() {
+        return Ok((0..names.len()).collect());
+    }
+    features
+        .iter()
+        .map(|feature| {
+            names
+                .iter()
+                .position(|name| name == feature)
+                .ok_or_else(|| format!("未知特征: {feature}"))
+        })
+        .collect()
+}
+
+/// 模型元数据中的特征名 → 特征列；早期元数据含未知名称时视为使用全部特征
+pub fn model_feature_columns(features: &[String]) -> Vec<usize> {
+    select_feature_columns(features).unwrap_or_else(|_| (0..FEATURE_DIM).collect())
+}
+
+/// 将扁平 n×FEATURE_DIM 特征中未选中的列置零。
+///
+/// 网络输入固定为 FEATURE_DIM 维，未选中的特征在训练与预测时都以 0 输入，等价于从模型中剔除。
+pub fn mask_unselected_features(features: &mut [f32], columns: &[usize]) {
+    if columns.len() >= FEATURE_DIM {
+        return;
+    }
+    for row in features.chunks_mut(FEATURE_DIM) {
+        for (j, value) in row.iter_mut().enumerate() {
+            if !columns.contains(&j) {
+                *value = 0.0;
+            }
+        }
+    }
+}
+
+/// 特征量纲缩放参数：`scaled = clamp((raw - offset) * scale)`。
+///
+/// 与 [`features_at`] 末尾的确定性缩放一一对应，随导出模型一起提供，便于在外部复现特征。
+#[derive(Debug, Clone, Serialize)]
+pub struct FeatureNormalization {
+    pub name: String,
+    /// 原始特征口径
+    pub raw_definition: String,
+    pub offset: f64,
+    pub scale: f64,
+    /// 缩放后的截断区间
+    pub clip: Option<(f64, f64)>,
+}
+
+/// (原始口径, offset, scale, 截断区间)
+type FeatureScale = (&'static str, f64, f64, Option<(f64, f64)>);
+
+/// 各特征的缩放参数（顺序同 [`feature_names`]）
+pub fn feature_normalization() -> Vec<FeatureNormalization> {
+    let spec: [FeatureScale; FEATURE_DIM] = [
+        ("1日收益率（小数）", 0.0, 25.0, None),
+        ("5日收益率（小数）", 0.0, 12.0, None),
+        ("10日收益率（小数）", 0.0, 8.0, None),
+        ("MA5/MA20 - 1", 0.0, 15.0, None),
+        ("RSI(14)，0-100", 50.0, 0.02, None),
+        ("10日收益率标准差（小数）", 0.0, 30.0, None),
+        ("当日成交量 / 5日均量", 1.0, 1.0, Some((-2.0, 3.0))),
+        ("换手率（%）", 0.0, 0.1, None),
+        ("20日区间位置，0-1", 0.5, 2.0, None),
+        ("振幅（%）", 0.0, 0.2, None),
+        ("60日 Hurst 指数，0-1", 0.5, 4.0, None),
+    ];
+    feature_names()
+        .into_iter()
+        .zip(spec)
+        .map(|(name, (raw_definition, offset, scale, clip))| FeatureNormalization {
+            name,
+            raw_definition: raw_definition.to_string(),
+            offset,
+            scale,
+            clip,
+        })
+        .collect()
+}
+
 /// 计算索引 `i` 处的特征向量（仅使用 ≤ i 的数据）
 fn features_at(h: &[HistoricalData], i: usize) -> [f32; FEATURE_DIM] {
     let close = |k: usize| h[k].close;
@@ -108,6 +268,14 @@ fn features_at(h: &[HistoricalData], i: usize) -> [f32; FEATURE_DIM] {
 
     let amplitude = (h[i].amplitude / 10.0) as f32;
 
+    // 价格记忆：>0.5 趋势延续，<0.5 均值回归
+    let hurst = if i + 1 >= HURST_WINDOW {
+        let prices: Vec<f64> = (i + 1 - HURST_WINDOW..=i).map(close).collect();
+        calculate_hurst_exponent(&prices, HURST_MAX_LAG) as f32
+    } else {
+        0.5
+    };
+
     // 确定性量纲缩放：把各特征拉到 ~±1 同一量级，避免收益率被量比淹没。
     // 训练与推理使用同一变换，无需存储统计量。
     [
@@ -121,6 +289,7 @@ fn features_at(h: &[HistoricalData], i: usize) -> [f32; FEATURE_DIM] {
         turnover, // 换手率/10，缺数据时为 0
         pos * 2.0,
         amplitude * 2.0,
+        (hurst - 0.5) * 4.0,
     ]
 }
 
@@ -136,24 +305,28 @@ pub fn build_dataset_for_horizon(
     historical: &[HistoricalData],
     horizon: usize,
 ) -> (Vec<f32>, Vec<f32>, usize) {
-    let len = historical.len();
     let horizon = horizon.max(1);
-    if len < LOOKBACK + horizon + 1 {
-        return (Vec::new(), Vec::new(), 0);
-    }
+    // 逐日特征行（行号即K线索引，需要 i+horizon 作为标签）；回看窗口不足或价格无效的行为 None
+    let end = historical.len().saturating_sub(horizon);
+    let (rows, targets): (Vec<Option<[f32; FEATURE_DIM]>>, Vec<f32>) = (0..end)
+        .map(|i| {
+            let base = historical[i].close;
+            if i < LOOKBACK || base <= 0.0 {
+                return (None, 0.0);
+            }
+            let next_ret = ((historical[i + horizon].close - base) / base * 100.0) as f32;
+            (Some(features_at(historical, i)), next_ret)
+        })
+        .unzip();
+    let (rows, targets) = filter_warmup_samples(&rows, &targets, &WarmupConfig::default());
 
     let mut features = Vec::new();
     let mut labels = Vec::new();
-    // i 从 LOOKBACK 到 len-horizon-1（需要 i+horizon 作为标签）
-    for i in LOOKBACK..(len - horizon) {
-        let feat = features_at(historical, i);
-        let base = historical[i].close;
-        if base <= 0.0 {
-            continue;
+    for (row, label) in rows.into_iter().zip(targets) {
+        if let Some(row) = row {
+            features.extend_from_slice(&row);
+            labels.push(label);
         }
-        let next_ret = ((historical[i + horizon].close - base) / base * 100.0) as f32;
-        features.extend_from_slice(&feat);
-        labels.push(next_ret);
     }
     let n = labels.len();
     (features, labels, n)
@@ -171,11 +344,13 @@ pub struct DatedSample {
 pub fn build_samples(historical: &[HistoricalData], horizon: usize) -> Vec<DatedSample> {
     let len = historical.len();
     let h = horizon.max(1);
-    if len < LOOKBACK + h + 1 {
+    // 与 build_dataset_for_horizon 一致：跳过回看窗口与指标预热期
+    let first = WarmupConfig::default().max_warmup().max(LOOKBACK);
+    if len < first + h + 1 {
         return Vec::new();
     }
     let mut out = Vec::new();
-    for i in LOOKBACK..(len - h) {
+    for i in first..(len - h) {
         let base = historical[i].close;
         if base <= 0.0 {
             continue;
@@ -226,11 +401,44 @@ mod tests {
 
     #[test]
     fn test_build_dataset_shape() {
-        let h = make(60);
+        let h = make(90);
         let (features, labels, n) = build_dataset(&h);
         assert_eq!(n, labels.len());
         assert_eq!(features.len(), n * FEATURE_DIM);
-        assert!(n > 0);
+        assert_eq!(n, 90 - HURST_WINDOW);
+        // 首个样本的 Hurst 已有完整窗口
+        assert_eq!(build_samples(&h, 1)[0].date, h[HURST_WINDOW - 1].date);
+        assert_eq!(build_dataset(&h[..HURST_WINDOW]).2, 0);
+    }
+
+    #[test]
+    fn test_filter_warmup_samples() {
+        let config = WarmupConfig::default();
+        assert_eq!(config.max_warmup(), HURST_WINDOW - 1);
+
+        let features: Vec<Vec<f64>> = (0..30).map(|i| vec![i as f64, -(i as f64)]).collect();
+        let targets: Vec<f64> = (0..30).map(|i| i as f64 * 0.1).collect();
+        let short = WarmupConfig {
+            rsi_warmup: 14,
+            macd_warmup: 26,
+            kdj_warmup: 9,
+            cci_warmup: 20,
+            hurst_warmup: 0,
+        };
+        let (rows, kept) = filter_warmup_samples(&features, &targets, &short);
+        assert_eq!(rows, vec![vec![26.0, -26.0], vec![27.0, -27.0], vec![28.0, -28.0], vec![29.0, -29.0]]);
+        assert_eq!(kept.len(), 4);
+        assert!((kept[0] - 2.6).abs() < 1e-12);
+        assert!(filter_warmup_samples(&features, &targets, &config).0.is_empty());
+    }
+
+    #[test]
+    fn test_hurst_feature_neutral_before_window() {
+        let h = make(90);
+        let hurst = feature_names().iter().position(|name| name == "hurst60").unwrap();
+        assert_eq!(features_at(&h, HURST_WINDOW - 2)[hurst], 0.0);
+        let value = features_at(&h, 89)[hurst];
+        assert!(value.is_finite() && value.abs() <= 2.0);
     }
 
     #[test]
@@ -242,13 +450,49 @@ mod tests {
 
     #[test]
     fn test_build_dataset_for_horizon_uses_requested_target() {
-        let h = make(60);
+        let h = make(90);
         let (_, labels_1, n_1) = build_dataset_for_horizon(&h, 1);
         let (_, labels_5, n_5) = build_dataset_for_horizon(&h, 5);
 
         assert_eq!(n_5, n_1 - 4);
         assert!(labels_5[0] > labels_1[0]);
-        let expected = ((h[25].close - h[20].close) / h[20].close * 100.0) as f32;
+        let expected = ((h[64].close - h[59].close) / h[59].close * 100.0) as f32;
         assert!((labels_5[0] - expected).abs() < 1e-6);
     }
+
+    #[test]
+    fn test_select_feature_columns() {
+        assert_eq!(select_feature_columns(&[]).unwrap().len(), FEATURE_DIM);
+        assert_eq!(
+            select_feature_columns(&["rsi14".to_string(), "ret_1d".to_string()]).unwrap(),
+            vec![4, 0]
+        );
+        assert!(select_feature_columns(&["unknown".to_string()]).is_err());
+        assert_eq!(model_feature_columns(&["close".to_string()]).len(), FEATURE_DIM);
+    }
+
+    #[test]
+    fn test_legacy_models_use_leading_columns() {
+        assert_eq!(model_input_dim(None), FEATURE_DIM - 1);
+        assert_eq!(model_input_dim(Some(FEATURE_SET_VERSION)), FEATURE_DIM);
+        assert_eq!(feature_names()[FEATURE_DIM - 1], "hurst60");
+
+        let features: Vec<f32> = (0..FEATURE_DIM * 2).map(|v| v as f32).collect();
+        let legacy = truncate_feature_columns(&features, FEATURE_DIM - 1);
+        assert_eq!(legacy.len(), (FEATURE_DIM - 1) * 2);
+        assert_eq!(legacy[FEATURE_DIM - 1], FEATURE_DIM as f32);
+        assert_eq!(truncate_feature_columns(&features, FEATURE_DIM), features);
+    }
+
+    #[test]
+    fn test_mask_unselected_features() {
+        let mut features: Vec<f32> = (0..FEATURE_DIM * 2).map(|v| v as f32 + 1.0).collect();
+        mask_unselected_features(&mut features, &[0, 4]);
+        let kept: Vec<usize> = (0..features.len()).filter(|&k| features[k] != 0.0).collect();
+        assert_eq!(kept, vec![0, 4, FEATURE_DIM, FEATURE_DIM + 4]);
+
+        let mut all: Vec<f32> = vec![1.0; FEATURE_DIM];
+        mask_unselected_features(&mut all, &model_feature_columns(&[]));
+        assert!(all.iter().all(|&v| v == 1.0));
+    }
 }
@@ -0,0 +1,204 @@
+//! 超参数网格搜索
+//!
+//! 对 candle MLP 的隐藏层宽度、学习率、dropout 与训练轮数做网格搜索，
+//! 每组参数以走步验证（扩张训练窗 + 紧随其后的验证段）评估平均验证损失。
+
+use crate::db::{connection::DbPool, repository::get_recent_historical_data};
+use crate::prediction::model::features::{build_dataset_for_horizon, select_feature_columns, FEATURE_DIM};
+use crate::prediction::model::network::{train_eval_with_hyperparams, MlpHyperparams};
+use crate::prediction::model::HORIZON_AWARE_MODEL_TYPE;
+use crate::prediction::types::ModelConfig;
+use crate::utils::progress::{ProgressSink, OPERATION_CANCELLED};
+use serde::{Deserialize, Serialize};
+use std::time::Instant;
+
+/// 网格搜索使用的历史K线数
+const GRID_SEARCH_HISTORY_BARS: usize = 800;
+/// 走步验证折数，每折验证段占样本的 10%
+const VALIDATION_FOLDS: usize = 3;
+const VALIDATION_FOLD_RATIO: f64 = 0.10;
+/// 网格组合数上限，防止误传超大网格
+pub const MAX_GRID_COMBINATIONS: usize = 256;
+
+/// 网格搜索空间
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GridSearchConfig {
+    pub hidden_sizes: Vec<usize>,
+    pub learning_rates: Vec<f64>,
+    pub dropout_rates: Vec<f64>,
+    pub n_epochs_list: Vec<usize>,
+}
+
+impl Default for GridSearchConfig {
+    fn default() -> Self {
+        Self {
+            hidden_sizes: vec![8, 16, 32],
+            learning_rates: vec![0.001, 0.01],
+            dropout_rates: vec![0.0, 0.2],
+            n_epochs_list: vec![100, 200],
+        }
+    }
+}
+
+impl GridSearchConfig {
+    fn combinations(&self) -> Vec<MlpHyperparams> {
+        let mut combos = Vec::new();
+        for &hidden_size in &self.hidden_sizes {
+            for &learning_rate in &self.learning_rates {
+                for &dropout in &self.dropout_rates {
+                    for &epochs in &self.n_epochs_list {
+                        combos.push(MlpHyperparams {
+                            hidden_size,
+                            dropout,
+                            learning_rate,
+                            epochs,
+                        });
+                    }
+                }
+            }
+        }
+        combos
+    }
+}
+
+/// 单组超参数的走步验证结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HyperparamResult {
+    pub hidden_size: usize,
+    pub learning_rate: f64,
+    pub dropout: f64,
+    pub n_epochs: usize,
+    /// 各折验证集 MSE 的均值（收益率%²）
+    pub val_loss: f64,
+    pub val_direction_accuracy: f64,
+}
+
+/// 网格搜索结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GridSearchResult {
+    pub best_config: ModelConfig,
+    pub best_epochs: usize,
+    pub best_val_loss: f64,
+    /// 按验证损失升序
+    pub all_results: Vec<HyperparamResult>,
+    /// 是否因超时提前结束（未评估完全部组合）
+    pub timed_out: bool,
+}
+
+/// 对个股运行超参数网格搜索。
+///
+/// `features` 为参与训练的特征名（空则使用全部特征）；到达 `deadline` 后不再启动新组合。
+/// 每评估完一组参数上报一次进度，`progress` 请求取消时返回错误。
+pub async fn run_grid_search(
+    stock_code: &str,
+    features: &[String],
+    config: GridSearchConfig,
+    pool: &DbPool,
+    deadline: Option<Instant>,
+    progress: &dyn ProgressSink,
+) -> Result<GridSearchResult, String> {
+    let columns = select_feature_columns(features)?;
+    let combos = config.combinations();
+    if combos.is_empty() {
+        return Err("超参数网格为空".to_string());
+    }
+    if combos.len() > MAX_GRID_COMBINATIONS {
+        return Err(format!(
+            "超参数组合数 {} 超过上限 {MAX_GRID_COMBINATIONS}",
+            combos.len()
+        ));
+    }
+
+    let historical = get_recent_historical_data(stock_code, GRID_SEARCH_HISTORY_BARS, pool)
+        .await
+        .map_err(|e| format!("获取历史数据失败: {e}"))?;
+    let (flat, labels, n) = build_dataset_for_horizon(&historical, 1);
+    let fold_size = (n as f64 * VALIDATION_FOLD_RATIO) as usize;
+    if fold_size < 10 || n < fold_size * (VALIDATION_FOLDS + 3) {
+        return Err(format!("有效样本不足（{n}），无法进行走步验证"));
+    }
+    let x: Vec<f32> = flat
+        .chunks(FEATURE_DIM)
+        .flat_map(|row| columns.iter().map(move |&c| row[c]))
+        .collect();
+    let dim = columns.len();
+
+    let total = combos.len();
+    let mut all_results = Vec::with_capacity(total);
+    let mut timed_out = false;
+    for params in combos {
+        if progress.is_cancelled() {
+            return Err(OPERATION_CANCELLED.to_string());
+        }
+        if deadline.is_some_and(|d| Instant::now() >= d) {
+            timed_out = true;
+            break;
+        }
+        let (mut loss_sum, mut acc_sum) = (0.0, 0.0);
+        for fold in 0..VALIDATION_FOLDS {
+            // 验证段从尾部往前排列；训练段截止于验证段前 1 个样本（次日标签不重叠）
+            let val_end = n - fold * fold_size;
+            let val_start = val_end - fold_size;
+            let train_end = val_start - 1;
+            let (loss, acc) = train_eval_with_hyperparams(
+                &x[..train_end * dim],
+                &labels[..train_end],
+                &x[val_start * dim..val_end * dim],
+                &labels[val_start..val_end],
+                dim,
+                params,
+            )?;
+            loss_sum += loss;
+            acc_sum += acc;
+        }
+        all_results.push(HyperparamResult {
+            hidden_size: params.hidden_size,
+            learning_rate: params.learning_rate,
+            dropout: params.dropout,
+            n_epochs: params.epochs,
+            val_loss: loss_sum / VALIDATION_FOLDS as f64,
+            val_direction_accuracy: acc_sum / VALIDATION_FOLDS as f64,
+        });
+        progress.report(
+            all_results.len(),
+            total,
+            &format!("已评估 {}/{total} 组超参数", all_results.len()),
+        );
+    }
+
+    all_results.sort_by(|a, b| a.val_loss.total_cmp(&b.val_loss));
+    let best = all_results
+        .first()
+        .ok_or_else(|| "超时前未完成任何超参数组合的评估".to_string())?;
+    Ok(GridSearchResult {
+        best_config: ModelConfig {
+            model_type: HORIZON_AWARE_MODEL_TYPE.to_string(),
+            input_size: dim,
+            hidden_size: best.hidden_size,
+            output_size: 1,
+            dropout: best.dropout,
+            learning_rate: best.learning_rate,
+            n_layers: 2,
+            n_heads: 0,
+            max_seq_len: 1,
+        },
+        best_epochs: best.n_epochs,
+        best_val_loss: best.val_loss,
+        timed_out,
+        all_results,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grid_combinations_cover_full_product() {
+        let combos = GridSearchConfig::default().combinations();
+        assert_eq!(combos.len(), 3 * 2 * 2 * 2);
+        assert!(combos
+            .iter()
+            .any(|c| c.hidden_size == 32 && c.dropout == 0.2));
+    }
+}
@@ -12,7 +12,8 @@
 
 use crate::prediction::types::{
     PredictionRequest, PredictionResponse, Prediction, LastRealData,
-    EvaluationResult, TechnicalIndicatorValues, ModelInfo, PredictionDiagnostics,
+    EvaluationResult, TechnicalIndicatorValues, ModelInfo, ModelStatus, PredictionDiagnostics,
+    ConformalCalibrationResult, PredictionInterval, PredictionExplanationDetail, MacdVariantComparison,
 };
 use crate::prediction::model::ml_inference::MlPredictor;
 use crate::prediction::model::management::load_model_metadata;
@@ -20,15 +21,19 @@ use crate::prediction::model::HORIZON_AWARE_MODEL_TYPE;
 use crate::prediction::indicators;
 use crate::prediction::analysis::{trend, volume, pattern, support_resistance};
 use crate::prediction::analysis::{market_regime, divergence, signal_confirmation, volatility_forecast};
-use crate::prediction::analysis::prediction_interval;
+use crate::prediction::analysis::{dtw, prediction_interval, stock_signals};
 use crate::prediction::analysis::risk_warning::{self, ModelRiskInput, RiskAnalysisInput};
-use crate::prediction::strategy::{multi_factor, professional_engine, adaptive_weights, price_model};
+use crate::prediction::strategy::{multi_factor, multi_timeframe, professional_engine, adaptive_weights, price_model};
 use crate::utils::date::get_next_trading_day;
+use crate::config::presets::IndicatorConfig;
 use crate::db::{
     connection::create_temp_pool,
     models::HistoricalData,
     repository::{get_historical_data, get_recent_historical_data},
 };
+use log::{debug, info, warn};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
 
 pub const MIN_ANALYSIS_DAYS: usize = 120;
 pub const MAX_ANALYSIS_DAYS: usize = 3000;
@@ -43,10 +48,19 @@ pub async fn predict_with_history(
     request: PredictionRequest,
     history_days: usize,
 ) -> Result<PredictionResponse, String> {
-    // 获取足够长的真实历史数据，用于指标计算与走步校准
     let pool = create_temp_pool().await?;
+    predict_with_history_with_pool(request, history_days, &pool).await
+}
+
+/// 同 [`predict_with_history`]，从给定连接池读取历史数据
+pub async fn predict_with_history_with_pool(
+    request: PredictionRequest,
+    history_days: usize,
+    pool: &SqlitePool,
+) -> Result<PredictionResponse, String> {
+    // 获取足够长的真实历史数据，用于指标计算与走步校准
     let history_days = history_days.clamp(MIN_ANALYSIS_DAYS, MAX_ANALYSIS_DAYS);
-    let historical = get_recent_historical_data(&request.stock_code, history_days, &pool)
+    let historical = get_recent_historical_data(&request.stock_code, history_days, pool)
         .await
         .map_err(|e| format!("获取历史数据失败: {e}"))?;
 
@@ -95,6 +109,14 @@ pub fn predict_from_historical(
             turnover_rate: last_data.turnover_rate,
             prediction_days,
             stock_code: Some(&request.stock_code),
+            market_ad_ratio: None,
+            market_fear_greed: None,
+            news_sentiment: None,
+            learned_factor_weights: None,
+            beta: None,
+            sector_relative_strength: None,
+            fundamental: None,
+            indicator_config: None,
         },
     );
     let mut professional_result = analysis.professional_result.clone();
@@ -104,6 +126,29 @@ pub fn predict_from_historical(
         prediction_days,
         Some(&request.stock_code),
     );
+    let ind = &analysis.tech_indicators;
+    debug!(
+        "{} 指标 rsi={:.2} dif={:.4} dea={:.4} hist={:.4} k={:.2} d={:.2} j={:.2} cci={:.2} boll_pos={:.3} obv_strength={:.2}",
+        request.stock_code,
+        ind.rsi,
+        ind.macd_dif,
+        ind.macd_dea,
+        ind.macd_histogram,
+        ind.kdj_k,
+        ind.kdj_d,
+        ind.kdj_j,
+        ind.cci,
+        ind.bollinger_position,
+        ind.obv_strength
+    );
+    info!(
+        "{} 预测结论 horizon={prediction_days} direction={} expected_change={:.2}% confidence={:.2} regime={:?}",
+        request.stock_code,
+        professional_result.direction.to_string(),
+        professional_result.expected_change,
+        professional_result.confidence,
+        professional_result.market_regime
+    );
 
     // =========================================================================
     // 第十一阶段：生成预测序列
@@ -112,6 +157,8 @@ pub fn predict_from_historical(
     let mut last_date = last_data.date;
     let mut last_price = current_price;
     
+    let explanation = explain_analysis(&analysis, current_price);
+    
     for day in 1..=prediction_days {
         let target_date = get_next_trading_day(last_date);
         
@@ -164,6 +211,9 @@ pub fn predict_from_historical(
             key_factors: Some(key_factors),
             interval: None,
             stress_interval: None,
+            explanation: Some(explanation.clone()),
+            prediction_low: predicted_price,
+            prediction_high: predicted_price,
         });
         
         last_date = target_date;
@@ -195,10 +245,11 @@ pub fn predict_from_historical(
             change_percent: last_data.change_percent,
         }),
         diagnostics: Some(diagnostics),
+        data_warning: None,
     })
 }
 
-fn signal_from_change_percent(change: f64) -> &'static str {
+pub(crate) fn signal_from_change_percent(change: f64) -> &'static str {
     if change > 0.0 {
         "看涨"
     } else if change < 0.0 {
@@ -208,7 +259,7 @@ fn signal_from_change_percent(change: f64) -> &'static str {
     }
 }
 
-fn diagnostics_from_analysis(
+pub(crate) fn diagnostics_from_analysis(
     historical: &[HistoricalData],
     analysis: &AnalysisBundle,
     predictions: &[Prediction],
@@ -239,7 +290,7 @@ fn diagnostics_from_analysis(
     }
 }
 
-fn attach_live_data_staleness(response: &mut PredictionResponse, latest_date: chrono::NaiveDate) {
+pub(crate) fn attach_live_data_staleness(response: &mut PredictionResponse, latest_date: chrono::NaiveDate) {
     let staleness_days = (chrono::Local::now().date_naive() - latest_date)
         .num_days()
         .max(0);
@@ -269,6 +320,22 @@ pub struct AnalysisOptions<'a> {
     pub turnover_rate: f64,
     pub prediction_days: usize,
     pub stock_code: Option<&'a str>,
+    /// 市场涨跌家数比（来自市场宽度统计），作为情绪因子的全市场恐慌/贪婪分量
+    pub market_ad_ratio: Option<f64>,
+    /// 全市场恐慌/贪婪指数（来自市场情绪统计），存在时替代个股情绪推断
+    pub market_fear_greed: Option<f64>,
+    /// 个股新闻关键词情绪分（见 `api::news`），None 时情绪因子不计新闻分量
+    pub news_sentiment: Option<f64>,
+    /// 个股学习到的多因子权重（见 `AdaptiveWeightOptimizer`），None 时仅用市场状态权重
+    pub learned_factor_weights: Option<&'a [f64]>,
+    /// 相对市场指数的 Beta（见 `services::beta`），None 时不调整波动率因子
+    pub beta: Option<f64>,
+    /// 所属行业相对全市场的近 20 日强度（见 `services::sector`），None 时不做行业轮动调整
+    pub sector_relative_strength: Option<f64>,
+    /// 已入库的基本面数据，None 时多因子评分不计基本面因子
+    pub fundamental: Option<multi_factor::FundamentalFactor>,
+    /// 技术指标参数（当前选中的指标预设），None 时使用默认周期
+    pub indicator_config: Option<&'a IndicatorConfig>,
 }
 
 /// 执行完整分析管线（不含逐日预测序列生成），供 predict 与回测复用。
@@ -290,16 +357,51 @@ pub fn analyze(
     let regime_analysis = market_regime::classify_market_regime(prices, highs, lows);
 
     // 第二阶段：技术分析
-    let trend_analysis = trend::analyze_trend(prices, highs, lows);
+    let mut trend_analysis = trend::analyze_trend(prices, highs, lows);
+    // 与前一交易日的综合趋势比较，跳变过大时降低趋势置信度
+    let previous_len = prices.len() - 1;
+    let previous_trend =
+        trend::analyze_trend(&prices[..previous_len], &highs[..previous_len], &lows[..previous_len]);
+    if !trend_analysis.flag_transition_from(&previous_trend.overall_trend) {
+        warn!(
+            "{} 趋势状态单日跳变: {} → {}",
+            options.stock_code.unwrap_or("-"),
+            previous_trend.overall_trend.to_string(),
+            trend_analysis.overall_trend.to_string()
+        );
+    }
     let volume_signal = volume::analyze_volume_price(prices, highs, lows, volumes);
-    let patterns = pattern::recognize_patterns(opens, prices, highs, lows);
+    let mut patterns = pattern::recognize_patterns(opens, prices, highs, lows);
     let sr = support_resistance::calculate_support_resistance(prices, highs, lows, current_price);
-    let mut tech_indicators = indicators::calculate_all_indicators(prices, highs, lows, volumes);
+    // 形态均在最新K线上完成，无次日K线：按放量、关键位与多周期方向评估确认度（按这三项满分缩放）
+    let timeframe_signal = multi_timeframe::get_latest_signal(prices, highs, lows, "");
+    pattern::apply_pattern_confirmation(
+        &mut patterns,
+        &pattern::PatternConfirmationContext {
+            closes: prices,
+            volumes,
+            pattern_index: prices.len() - 1,
+            support_resistance: &sr,
+            multi_timeframe_direction: timeframe_signal
+                .as_ref()
+                .map(|signal| signal.resonance_direction.as_str()),
+        },
+    );
+    let indicator_config = options.indicator_config.copied().unwrap_or_default();
+    let stock_signals::TechnicalSignalSet {
+        indicators: mut tech_indicators,
+        divergence: divergence_analysis,
+    } = stock_signals::compute_technical_signals(prices, highs, lows, volumes, &indicator_config);
     // 换手率来自历史数据回填（量比已在 calculate_all_indicators 内计算）
     tech_indicators.turnover_rate = options.turnover_rate;
+    tech_indicators.market_ad_ratio = options.market_ad_ratio;
+    tech_indicators.market_fear_greed = options.market_fear_greed;
+    tech_indicators.news_sentiment = options.news_sentiment;
+    tech_indicators.beta = options.beta;
+    tech_indicators.sector_relative_strength = options.sector_relative_strength;
+    tech_indicators.fundamental = options.fundamental;
 
-    // 第三阶段：背离
-    let divergence_analysis = divergence::analyze_all_divergences(prices, highs, lows, volumes);
+    // 第三阶段：背离（已随技术指标一并计算）
 
     // 第四阶段：GARCH 波动率
     let volatility = trend::calculate_historical_volatility(prices, 20);
@@ -323,7 +425,7 @@ pub fn analyze(
     );
 
     // 第七阶段：自适应多因子评分
-    let multi_factor_score = multi_factor::calculate_adaptive_multi_factor_score(
+    let multi_factor_score = multi_factor::calculate_learned_multi_factor_score(
         &trend_analysis.overall_trend,
         &volume_signal,
         &tech_indicators,
@@ -332,11 +434,16 @@ pub fn analyze(
         volatility,
         Some(&regime_analysis.regime),
         Some(&regime_analysis.volatility_level),
+        options.learned_factor_weights,
     );
 
     // 第八阶段：VWAP 与布林带
     let vwap_signal = indicators::vwap::analyze_vwap_signal(highs, lows, prices, volumes, 20);
-    let bb = indicators::bollinger::calculate_bollinger_bands(prices, 20, 2.0);
+    let bb = indicators::bollinger::calculate_bollinger_bands(
+        prices,
+        indicator_config.bollinger_period,
+        indicator_config.bollinger_std,
+    );
     let bollinger_position = (current_price - bb.middle) / (bb.upper - bb.lower).max(0.001);
 
     // 第九阶段：增强价格预测模型
@@ -373,6 +480,11 @@ pub fn analyze(
         support_resistance: sr.clone(),
         multi_factor_score: multi_factor_score.clone(),
         volatility,
+        pattern_analogy: dtw::analyze_pattern_analogy(
+            prices,
+            dtw::DEFAULT_PATTERN_WINDOW,
+            dtw::DEFAULT_PATTERN_TOP_K,
+        ),
     };
     let professional_result = professional_engine::execute_professional_prediction(&prediction_ctx);
 
@@ -575,7 +687,7 @@ fn calculate_drift_daily_prediction(
     (change_percent, confidence)
 }
 
-fn daily_change_from_horizon_change(change: f64, horizon: usize) -> f64 {
+pub(crate) fn daily_change_from_horizon_change(change: f64, horizon: usize) -> f64 {
     let horizon = horizon.max(1);
     if horizon == 1 {
         return change;
@@ -589,8 +701,22 @@ fn daily_change_from_horizon_change(change: f64, horizon: usize) -> f64 {
     (gross.powf(1.0 / horizon as f64) - 1.0) * 100.0
 }
 
+/// 由分析结果生成逐项信号解释
+fn explain_analysis(
+    analysis: &AnalysisBundle,
+    current_price: f64,
+) -> professional_engine::PredictionExplanation {
+    professional_engine::PredictionExplainer {
+        trend: &analysis.trend_analysis.overall_trend,
+        indicators: &analysis.tech_indicators,
+        multi_factor: &analysis.multi_factor_score,
+        support_resistance: &analysis.support_resistance,
+        current_price,
+    }
+    .explain()
+}
+
 /// 生成预测原因说明
-#[allow(dead_code)]
 fn generate_prediction_reason(
     result: &professional_engine::ProfessionalPredictionResult,
     regime: &market_regime::MarketRegimeAnalysis,
@@ -704,35 +830,47 @@ pub async fn predict_simple(request: PredictionRequest) -> Result<PredictionResp
     predict(request).await
 }
 
-/// 使用已训练的 Candle 模型预测；该股无可用模型时回退到规则引擎。
-pub async fn predict_with_model(request: PredictionRequest) -> Result<PredictionResponse, String> {
+/// 选取请求对应的 Candle 模型：优先用户指定模型；未指定时使用该股的生产模型，
+/// 没有生产模型时在未归档的可用模型中优先选取训练周期匹配请求天数的模型。
+/// 指定模型不存在时报错；未指定且该股无可用模型时返回 None。
+pub(crate) fn select_model_for_request(
+    request: &PredictionRequest,
+) -> Result<Option<ModelInfo>, String> {
     use crate::prediction::model::management::{
         get_model_file_path, list_models, model_matches_identifier,
     };
 
-    // 优先使用用户指定模型；未指定时优先选取训练周期匹配请求天数的可用模型。
     let models = list_models(&request.stock_code);
     let selected_name = request
         .model_name
         .as_deref()
         .map(str::trim)
         .filter(|name| !name.is_empty());
-    let model = if let Some(name) = selected_name {
-        models
+    if let Some(name) = selected_name {
+        return models
             .into_iter()
             .find(|m| model_matches_identifier(m, name) && get_model_file_path(&m.id).exists())
-            .ok_or_else(|| format!("选择的模型 `{name}` 不存在或权重文件不存在"))?
-    } else {
-        let available = models
-            .into_iter()
-            .filter(|m| get_model_file_path(&m.id).exists())
-            .collect::<Vec<_>>();
-        if available.is_empty() {
-            return predict(request).await; // 无模型 → 规则引擎
-        }
+            .map(Some)
+            .ok_or_else(|| format!("选择的模型 `{name}` 不存在或权重文件不存在"));
+    }
 
-        select_default_model(available, request.prediction_days.max(1))
-            .ok_or_else(|| "没有可用模型".to_string())?
+    let available = models
+        .into_iter()
+        .filter(|m| m.model_status != ModelStatus::Archived && get_model_file_path(&m.id).exists())
+        .collect::<Vec<_>>();
+    if let Some(production) = available
+        .iter()
+        .find(|m| m.model_status == ModelStatus::Production)
+    {
+        return Ok(Some(production.clone()));
+    }
+    Ok(select_default_model(available, request.prediction_days.max(1)))
+}
+
+/// 使用已训练的 Candle 模型预测；该股无可用模型时回退到规则引擎。
+pub async fn predict_with_model(request: PredictionRequest) -> Result<PredictionResponse, String> {
+    let Some(model) = select_model_for_request(&request)? else {
+        return predict(request).await; // 无模型 → 规则引擎
     };
 
     let pool = create_temp_pool().await?;
@@ -743,15 +881,74 @@ pub async fn predict_with_model(request: PredictionRequest) -> Result<Prediction
         return predict(request).await;
     }
 
-    let predictor = MlPredictor::load(&get_model_file_path(&model.id))?;
+    let predictor = MlPredictor::for_model(&model)?;
     let mut response =
         predict_with_model_from_historical(&request, &historical, &model, &predictor)?;
+    // 已做共形校准的模型用样本外残差区间替换波动率区间
+    match load_conformal_predictor(&model.id) {
+        Ok(Some(conformal)) => {
+            let horizon = model_training_horizon(&model.model_type, model.prediction_days);
+            let base_price = historical.last().map_or(0.0, |bar| bar.close);
+            if attach_conformal_intervals(&mut response.predictions, &conformal, base_price, horizon) {
+                if let Some(diagnostics) = response.diagnostics.as_mut() {
+                    diagnostics.uncertainty_method = CONFORMAL_METHOD.to_string();
+                }
+            }
+        }
+        Ok(None) => {}
+        Err(e) => warn!("读取模型 {} 共形校准失败，沿用波动率区间: {e}", model.id),
+    }
     if let Some(last) = historical.last() {
         attach_live_data_staleness(&mut response, last.date);
     }
     Ok(response)
 }
 
+/// 由分析结果提取参与共振检测的指标方向：RSI/KDJ 超买超卖、MACD 金叉死叉、OBV 相对均线、是否接近支撑/压力
+fn confluence_signals(analysis: &AnalysisBundle) -> Vec<multi_factor::IndicatorSignal> {
+    let ind = &analysis.tech_indicators;
+    let rsi = if ind.rsi < 30.0 {
+        1
+    } else if ind.rsi > 70.0 {
+        -1
+    } else {
+        0
+    };
+    let macd = if ind.macd_golden_cross {
+        1
+    } else if ind.macd_death_cross {
+        -1
+    } else {
+        0
+    };
+    let kdj = if ind.kdj_oversold {
+        1
+    } else if ind.kdj_overbought {
+        -1
+    } else {
+        0
+    };
+    let obv = if ind.obv_strength > 0.0 {
+        1
+    } else if ind.obv_strength < 0.0 {
+        -1
+    } else {
+        0
+    };
+    let support_resistance = match analysis.support_resistance.current_position.as_str() {
+        "接近关键支撑" => 1,
+        "接近关键压力" => -1,
+        _ => 0,
+    };
+    vec![
+        multi_factor::IndicatorSignal::new("rsi", rsi, 1.0),
+        multi_factor::IndicatorSignal::new("macd", macd, 0.8),
+        multi_factor::IndicatorSignal::new("kdj", kdj, 0.6),
+        multi_factor::IndicatorSignal::new("obv", obv, 0.8),
+        multi_factor::IndicatorSignal::new("support_resistance", support_resistance, 0.8),
+    ]
+}
+
 /// 使用已加载模型和调用方提供的可见历史数据预测；回测复用该函数以避免未来函数。
 pub fn predict_with_model_from_historical(
     request: &PredictionRequest,
@@ -814,6 +1011,9 @@ pub fn predict_with_model_from_historical(
             ]),
             interval: None,
             stress_interval: None,
+            explanation: None,
+            prediction_low: predicted_price,
+            prediction_high: predicted_price,
         });
 
         last_date = target_date;
@@ -844,8 +1044,37 @@ pub fn predict_with_model_from_historical(
             turnover_rate: last_data.turnover_rate,
             prediction_days,
             stock_code: Some(&request.stock_code),
+            market_ad_ratio: None,
+            market_fear_greed: None,
+            news_sentiment: None,
+            learned_factor_weights: None,
+            beta: None,
+            sector_relative_strength: None,
+            fundamental: None,
+            indicator_config: None,
         },
     );
+    // 模型输出本身不可解释，附上同一时点的技术面解释供参考
+    let explanation = explain_analysis(&analysis, current_price);
+    for prediction in &mut predictions {
+        if let Some(reason) = prediction.prediction_reason.as_mut() {
+            reason.push_str("；技术面参考：");
+            reason.push_str(&explanation.plain_language_summary);
+        }
+        prediction.explanation = Some(explanation.clone());
+    }
+
+    // 多个独立指标强共振时，交易信号不再取决于模型输出的涨跌方向
+    let confluence = multi_factor::detect_signal_confluence(&confluence_signals(&analysis));
+    if let Some(signal) = confluence.strong_signal() {
+        for prediction in &mut predictions {
+            prediction.trading_signal = Some(signal.to_string());
+            if let Some(factors) = prediction.key_factors.as_mut() {
+                factors.push(confluence.description.clone());
+            }
+        }
+    }
+
     let diagnostics = diagnostics_from_analysis(
         historical,
         &analysis,
@@ -866,6 +1095,7 @@ pub fn predict_with_model_from_historical(
             change_percent: last_data.change_percent,
         }),
         diagnostics: Some(diagnostics),
+        data_warning: None,
     })
 }
 
@@ -882,11 +1112,11 @@ pub async fn evaluate_model(model_id: String) -> Result<EvaluationResult, String
         return Err("模型权重文件不存在，请先训练".to_string());
     }
 
-    let predictor = MlPredictor::load(&model_path)?;
+    let predictor = MlPredictor::for_model(&metadata)?;
 
     let pool = create_temp_pool().await?;
     let horizon = model_training_horizon(&metadata.model_type, metadata.prediction_days);
-    let ((direction_accuracy, mae, rmse, test_samples), evaluation_scope, evaluation_note) =
+    let ((direction_accuracy, mae, rmse, test_samples), evaluation_scope, evaluation_note, macd_comparison) =
         if let Some(training_end_date) = metadata.training_end_date.as_deref() {
             let training_end = chrono::NaiveDate::parse_from_str(training_end_date, "%Y-%m-%d")
                 .map_err(|e| format!("模型训练结束日期元数据格式错误: {e}"))?;
@@ -909,6 +1139,7 @@ pub async fn evaluate_model(model_id: String) -> Result<EvaluationResult, String
                     "训练特征结束日 {training_end_date}，仅统计训练标签截止日 {} 之后已产生真实标签的样本",
                     evaluation_cutoff.format("%Y-%m-%d")
                 ),
+                compare_macd_variants(&historical, horizon, Some(evaluation_cutoff)),
             )
         } else {
             let historical = get_recent_historical_data(&metadata.stock_code, 250, &pool)
@@ -918,6 +1149,7 @@ pub async fn evaluate_model(model_id: String) -> Result<EvaluationResult, String
                 evaluate_on_horizon(&historical, &predictor, horizon),
                 "最近历史样本评估".to_string(),
                 "旧模型缺少训练窗口元数据，评估可能包含训练期样本".to_string(),
+                compare_macd_variants(&historical, horizon, None),
             )
         };
     if test_samples == 0 {
@@ -939,6 +1171,369 @@ pub async fn evaluate_model(model_id: String) -> Result<EvaluationResult, String
         evaluation_date: chrono::Local::now().format("%Y-%m-%d").to_string(),
         evaluation_scope,
         evaluation_note,
+        macd_comparison,
+    })
+}
+
+/// MACD 变体对比所需的最少样本数
+const MIN_MACD_COMPARISON_SAMPLES: usize = 20;
+
+/// 以 MACD 柱符号预测 `horizon` 日后涨跌，对比标准 MACD 与成交量加权 MACD 的方向命中率；
+/// 仅统计特征日期晚于 `after` 的样本，样本不足时返回 None
+fn compare_macd_variants(
+    historical: &[HistoricalData],
+    horizon: usize,
+    after: Option<chrono::NaiveDate>,
+) -> Option<MacdVariantComparison> {
+    use crate::config::constants::{MACD_FAST_PERIOD, MACD_SIGNAL_PERIOD, MACD_SLOW_PERIOD};
+    use crate::prediction::indicators::{calculate_macd_full, calculate_vwmacd};
+
+    let horizon = horizon.max(1);
+    let prices: Vec<f64> = historical.iter().map(|h| h.close).collect();
+    let volumes: Vec<i64> = historical.iter().map(|h| h.volume).collect();
+    let hit = |histogram: f64, actual: f64| histogram * actual > 0.0;
+    let (mut samples, mut macd_hits, mut vwmacd_hits) = (0usize, 0usize, 0usize);
+    for i in MACD_SLOW_PERIOD + MACD_SIGNAL_PERIOD..historical.len().saturating_sub(horizon) {
+        if after.is_some_and(|date| historical[i].date <= date) {
+            continue;
+        }
+        let actual = prices[i + horizon] - prices[i];
+        let (_, _, macd_hist) = calculate_macd_full(&prices[..=i]);
+        let (_, _, vwmacd_hist) = calculate_vwmacd(
+            &prices[..=i],
+            &volumes[..=i],
+            MACD_FAST_PERIOD,
+            MACD_SLOW_PERIOD,
+            MACD_SIGNAL_PERIOD,
+        );
+        samples += 1;
+        macd_hits += usize::from(hit(macd_hist, actual));
+        vwmacd_hits += usize::from(hit(vwmacd_hist, actual));
+    }
+    (samples >= MIN_MACD_COMPARISON_SAMPLES).then(|| MacdVariantComparison {
+        samples,
+        macd_direction_accuracy: macd_hits as f64 / samples as f64,
+        vwmacd_direction_accuracy: vwmacd_hits as f64 / samples as f64,
+    })
+}
+
+/// 共形区间的方法名
+pub const CONFORMAL_METHOD: &str = "split_conformal";
+/// 共形校准所需的最少样本外样本数
+const MIN_CONFORMAL_SAMPLES: usize = 20;
+/// 样本外数据中按时间留出最后一段检验覆盖率的比例（不参与校准）
+const CONFORMAL_HOLDOUT_FRACTION: f64 = 0.3;
+/// 压力区间的 alpha（95% 覆盖）
+const STRESS_ALPHA: f64 = 0.05;
+
+/// 分割共形预测器：以样本外残差 |预测 - 实际| 为不一致性得分，
+/// 区间半宽取得分的 (1 - alpha) 有限样本分位数，在可交换假设下覆盖率不低于 1 - alpha。
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConformalPredictor {
+    /// 升序排列的不一致性得分
+    pub nonconformity_scores: Vec<f64>,
+    pub alpha: f64,
+}
+
+impl ConformalPredictor {
+    pub fn new(alpha: f64) -> Self {
+        Self {
+            nonconformity_scores: Vec::new(),
+            alpha,
+        }
+    }
+
+    /// 用校准集的预测与实际值计算不一致性得分（忽略非有限值）
+    pub fn calibrate(&mut self, predictions: &[f64], actuals: &[f64]) {
+        let mut scores: Vec<f64> = predictions
+            .iter()
+            .zip(actuals)
+            .map(|(p, a)| (p - a).abs())
+            .filter(|score| score.is_finite())
+            .collect();
+        scores.sort_by(f64::total_cmp);
+        self.nonconformity_scores = scores;
+    }
+
+    /// 得分的第 ⌈(n + 1)(1 - alpha)⌉ 小值；超过样本数时有限区间无法保证覆盖率，返回 None
+    pub fn quantile(&self) -> Option<f64> {
+        let n = self.nonconformity_scores.len();
+        let rank = ((n + 1) as f64 * (1.0 - self.alpha)).ceil() as usize;
+        if n == 0 || rank > n {
+            return None;
+        }
+        self.nonconformity_scores.get(rank.max(1) - 1).copied()
+    }
+
+    /// 点预测的 (1 - alpha) 区间；未校准或样本不足时为无界区间
+    pub fn predict_interval(&self, point_prediction: f64) -> (f64, f64) {
+        match self.quantile() {
+            Some(q) => (point_prediction - q, point_prediction + q),
+            None => (f64::NEG_INFINITY, f64::INFINITY),
+        }
+    }
+
+    /// 区间在另一组预测与实际值上的经验覆盖率；未校准或无有效样本时为 None
+    pub fn empirical_coverage(&self, predictions: &[f64], actuals: &[f64]) -> Option<f64> {
+        let half_width = self.quantile()?;
+        let errors: Vec<f64> = predictions
+            .iter()
+            .zip(actuals)
+            .map(|(p, a)| (p - a).abs())
+            .filter(|error| error.is_finite())
+            .collect();
+        if errors.is_empty() {
+            return None;
+        }
+        let covered = errors.iter().filter(|&&error| error <= half_width).count();
+        Some(covered as f64 / errors.len() as f64)
+    }
+
+    /// 同一组得分在另一覆盖率下的预测器
+    pub fn with_alpha(&self, alpha: f64) -> Self {
+        Self {
+            alpha,
+            ..self.clone()
+        }
+    }
+}
+
+/// 读取模型的共形校准；未校准时返回 None
+pub fn load_conformal_predictor(model_id: &str) -> Result<Option<ConformalPredictor>, String> {
+    let path = crate::prediction::model::management::get_conformal_file_path(model_id);
+    if !path.exists() {
+        return Ok(None);
+    }
+    let json = std::fs::read_to_string(&path).map_err(|e| format!("读取共形校准失败: {e}"))?;
+    serde_json::from_str(&json)
+        .map(Some)
+        .map_err(|e| format!("解析共形校准失败: {e}"))
+}
+
+/// 按共形残差为各预测日重建区间，返回是否替换成功。
+///
+/// 得分为模型训练周期的收益率误差（%），第 d 日半宽按 √(d / horizon) 缩放；
+/// 压力区间在样本足以支撑 95% 覆盖时一并替换。
+pub fn attach_conformal_intervals(
+    predictions: &mut [Prediction],
+    conformal: &ConformalPredictor,
+    base_price: f64,
+    horizon: usize,
+) -> bool {
+    if base_price <= 0.0 || !base_price.is_finite() || conformal.quantile().is_none() {
+        return false;
+    }
+    let stress = conformal.with_alpha(STRESS_ALPHA);
+    let horizon = horizon.max(1) as f64;
+    let samples = conformal.nonconformity_scores.len();
+    let build = |predictor: &ConformalPredictor, cum_change: f64, scale: f64| {
+        let q = predictor.quantile()?;
+        let lower = cum_change - q * scale;
+        let upper = cum_change + q * scale;
+        Some(PredictionInterval {
+            confidence: 1.0 - predictor.alpha,
+            lower_change_percent: lower,
+            upper_change_percent: upper,
+            lower_price: (base_price * (1.0 + lower / 100.0)).max(0.0),
+            upper_price: base_price * (1.0 + upper / 100.0),
+            method: CONFORMAL_METHOD.to_string(),
+            lookback_days: samples,
+        })
+    };
+    for (idx, prediction) in predictions.iter_mut().enumerate() {
+        let cum_change = (prediction.predicted_price - base_price) / base_price * 100.0;
+        let scale = ((idx + 1) as f64 / horizon).sqrt();
+        if let Some(interval) = build(conformal, cum_change, scale) {
+            prediction.prediction_low = interval.lower_price;
+            prediction.prediction_high = interval.upper_price;
+            prediction.interval = Some(interval);
+        }
+        if let Some(interval) = build(&stress, cum_change, scale) {
+            prediction.stress_interval = Some(interval);
+        }
+    }
+    true
+}
+
+/// 归因文字说明中列出的特征数
+const EXPLANATION_TOP_FEATURES: usize = 3;
+
+/// 扰动归因：逐个特征在基准值上 ±1 个标准差（其余特征不变），归因 = (高值预测 - 低值预测) / 2，
+/// 按绝对值降序返回
+fn perturbation_attributions(
+    features: &[f32],
+    feature_stds: &[f32],
+    predict: impl Fn(&[f32]) -> Result<f64, String>,
+) -> Result<Vec<(String, f64)>, String> {
+    let mut attributions = Vec::with_capacity(features.len());
+    for (i, name) in crate::prediction::model::features::feature_names().into_iter().enumerate() {
+        let (Some(&base), Some(&std)) = (features.get(i), feature_stds.get(i)) else {
+            break;
+        };
+        let mut perturbed = features.to_vec();
+        perturbed[i] = base + std;
+        let high = predict(&perturbed)?;
+        perturbed[i] = base - std;
+        let low = predict(&perturbed)?;
+        attributions.push((name, (high - low) / 2.0));
+    }
+    attributions.sort_by(|a, b| b.1.abs().total_cmp(&a.1.abs()));
+    Ok(attributions)
+}
+
+/// 各特征列的总体标准差（`features` 为 n×`FEATURE_DIM` 的扁平矩阵）
+fn feature_column_stds(features: &[f32], rows: usize) -> Vec<f32> {
+    use crate::prediction::model::features::FEATURE_DIM;
+
+    (0..FEATURE_DIM)
+        .map(|j| {
+            if rows == 0 {
+                return 0.0;
+            }
+            let column = (0..rows).map(|r| features[r * FEATURE_DIM + j] as f64);
+            let mean = column.clone().sum::<f64>() / rows as f64;
+            let var = column.map(|v| (v - mean).powi(2)).sum::<f64>() / rows as f64;
+            var.sqrt() as f32
+        })
+        .collect()
+}
+
+/// 解释模型对个股最新交易日的预测：特征标准差取自近 250 日的样本，
+/// 文字说明附上同一时点的技术面原因（见 `generate_prediction_reason`）
+pub async fn explain_model_prediction(
+    stock_code: &str,
+    model_id: &str,
+) -> Result<PredictionExplanationDetail, String> {
+    use crate::prediction::model::features::{build_dataset, latest_features};
+    use crate::prediction::model::management::get_model_file_path;
+
+    let model = load_model_metadata(model_id)?;
+    let model_path = get_model_file_path(model_id);
+    if !model_path.exists() {
+        return Err("模型权重文件不存在，请先训练".to_string());
+    }
+    let predictor = MlPredictor::for_model(&model)?;
+
+    let pool = create_temp_pool().await?;
+    let historical = get_recent_historical_data(stock_code, 250, &pool)
+        .await
+        .map_err(|e| format!("获取历史数据失败: {e}"))?;
+    if historical.len() < 60 {
+        return Err("历史数据不足60天，无法解释模型预测".to_string());
+    }
+
+    let feats = latest_features(&historical).ok_or("数据不足以构造特征")?;
+    let predicted_return_pct = predictor.predict(&feats)?;
+    let (samples, _, rows) = build_dataset(&historical);
+    let stds = feature_column_stds(&samples, rows);
+    let feature_attributions = perturbation_attributions(&feats, &stds, |x| predictor.predict(x))?;
+
+    let prices: Vec<f64> = historical.iter().map(|bar| bar.close).collect();
+    let highs: Vec<f64> = historical.iter().map(|bar| bar.high).collect();
+    let lows: Vec<f64> = historical.iter().map(|bar| bar.low).collect();
+    let volumes: Vec<i64> = historical.iter().map(|bar| bar.volume).collect();
+    let opens: Vec<f64> = historical.iter().map(|bar| bar.open).collect();
+    let last = historical.last().unwrap();
+    let horizon = model_training_horizon(&model.model_type, model.prediction_days);
+    let analysis = analyze(
+        &prices,
+        &highs,
+        &lows,
+        &volumes,
+        &opens,
+        AnalysisOptions {
+            turnover_rate: last.turnover_rate,
+            prediction_days: horizon,
+            stock_code: Some(stock_code),
+            market_ad_ratio: None,
+            market_fear_greed: None,
+            news_sentiment: None,
+            learned_factor_weights: None,
+            beta: None,
+            sector_relative_strength: None,
+            fundamental: None,
+            indicator_config: None,
+        },
+    );
+    let technical_reason = generate_prediction_reason(
+        &analysis.professional_result,
+        &analysis.regime_analysis,
+        &analysis.divergence_analysis,
+        &analysis.multi_factor_score,
+    );
+    let contributions: Vec<String> = feature_attributions
+        .iter()
+        .take(EXPLANATION_TOP_FEATURES)
+        .map(|(name, value)| format!("{name} 贡献 {value:+.2}%"))
+        .collect();
+    let narrative = format!(
+        "模型预测 {horizon} 日收益 {predicted_return_pct:+.2}%：{}；技术面：{technical_reason}",
+        contributions.join("，")
+    );
+
+    Ok(PredictionExplanationDetail {
+        model_id: model_id.to_string(),
+        predicted_return_pct,
+        feature_attributions,
+        narrative,
+    })
+}
+
+/// 在训练标签截止日之后的样本外数据上校准模型的共形区间，并保存到模型目录。
+///
+/// 样本外数据按时间切分：前段校准区间半宽，最后 30% 留出检验经验覆盖率
+pub async fn calibrate_conformal_predictor(model_id: &str) -> Result<ConformalCalibrationResult, String> {
+    use crate::prediction::model::management::{get_conformal_file_path, get_model_file_path};
+    use crate::prediction::model::ml_inference::horizon_predictions_after;
+
+    let metadata = load_model_metadata(model_id)?;
+    let training_end_date = metadata
+        .training_end_date
+        .as_deref()
+        .ok_or("模型缺少训练窗口元数据，无法区分样本外校准数据，请重新训练")?;
+    let training_end = chrono::NaiveDate::parse_from_str(training_end_date, "%Y-%m-%d")
+        .map_err(|e| format!("模型训练结束日期元数据格式错误: {e}"))?;
+    let model_path = get_model_file_path(model_id);
+    if !model_path.exists() {
+        return Err("模型权重文件不存在，请先训练".to_string());
+    }
+    let predictor = MlPredictor::for_model(&metadata)?;
+
+    let pool = create_temp_pool().await?;
+    let historical = get_historical_data(&metadata.stock_code, "1900-01-01", "9999-12-31", &pool)
+        .await
+        .map_err(|e| format!("获取历史数据失败: {e}"))?;
+    let horizon = model_training_horizon(&metadata.model_type, metadata.prediction_days);
+    let cutoff = training_label_cutoff_date(&historical, training_end, horizon)?;
+    let (predictions, actuals) = horizon_predictions_after(&historical, &predictor, horizon, cutoff);
+    let holdout_samples = (predictions.len() as f64 * CONFORMAL_HOLDOUT_FRACTION).round() as usize;
+    let split = predictions.len() - holdout_samples;
+
+    let mut conformal = ConformalPredictor::new(1.0 - prediction_interval::DEFAULT_COVERAGE);
+    conformal.calibrate(&predictions[..split], &actuals[..split]);
+    let samples = conformal.nonconformity_scores.len();
+    if samples < MIN_CONFORMAL_SAMPLES {
+        return Err(format!(
+            "训练标签截止日 {} 之后留出检验段后仅有 {samples} 个校准样本，共形校准至少需要 {MIN_CONFORMAL_SAMPLES} 个",
+            cutoff.format("%Y-%m-%d")
+        ));
+    }
+
+    let json = serde_json::to_string(&conformal).map_err(|e| format!("序列化共形校准失败: {e}"))?;
+    std::fs::write(get_conformal_file_path(model_id), json)
+        .map_err(|e| format!("写入共形校准失败: {e}"))?;
+
+    let half_width_percent = conformal.quantile().ok_or("共形校准样本不足")?;
+    let empirical_coverage = conformal
+        .empirical_coverage(&predictions[split..], &actuals[split..])
+        .ok_or("留出检验样本不足，无法评估覆盖率")?;
+    Ok(ConformalCalibrationResult {
+        model_id: model_id.to_string(),
+        calibration_samples: samples,
+        holdout_samples,
+        coverage: 1.0 - conformal.alpha,
+        half_width_percent,
+        empirical_coverage,
     })
 }
 
@@ -983,7 +1578,7 @@ fn convert_indicators(ind: &indicators::TechnicalIndicatorValues) -> TechnicalIn
     }
 }
 
-fn model_training_horizon(model_type: &str, prediction_days: usize) -> usize {
+pub(crate) fn model_training_horizon(model_type: &str, prediction_days: usize) -> usize {
     if model_type == HORIZON_AWARE_MODEL_TYPE {
         prediction_days.max(1)
     } else {
@@ -1010,7 +1605,7 @@ fn select_default_model(models: Vec<ModelInfo>, target_horizon: usize) -> Option
         })
 }
 
-fn ml_daily_change_for_day(daily_change: f64, model_horizon: usize, day: usize) -> f64 {
+pub(crate) fn ml_daily_change_for_day(daily_change: f64, model_horizon: usize, day: usize) -> f64 {
     const DECAY: f64 = 0.9;
 
     let model_horizon = model_horizon.max(1);
@@ -1028,6 +1623,44 @@ mod tests {
     };
     use chrono::{Duration, NaiveDate};
 
+    #[test]
+    fn test_perturbation_attributions_recover_linear_weights() {
+        use crate::prediction::model::features::FEATURE_DIM;
+
+        let weights: Vec<f64> = (0..FEATURE_DIM).map(|i| i as f64 - 3.0).collect();
+        let linear = |x: &[f32]| -> Result<f64, String> {
+            Ok(x.iter().zip(&weights).map(|(&v, w)| v as f64 * w).sum())
+        };
+        let features = vec![0.5f32; FEATURE_DIM];
+        let stds = vec![2.0f32; FEATURE_DIM];
+        let attributions = perturbation_attributions(&features, &stds, linear).unwrap();
+
+        // 线性模型的归因 = 权重 × 标准差，按绝对值降序
+        assert_eq!(attributions.len(), FEATURE_DIM);
+        assert_eq!(attributions[0].0, feature_names_at(FEATURE_DIM - 1));
+        assert!((attributions[0].1 - 2.0 * (FEATURE_DIM as f64 - 4.0)).abs() < 1e-9);
+        let last = attributions.last().unwrap();
+        assert_eq!(last.0, feature_names_at(3));
+        assert!(last.1.abs() < 1e-9);
+    }
+
+    fn feature_names_at(i: usize) -> String {
+        crate::prediction::model::features::feature_names()[i].clone()
+    }
+
+    #[test]
+    fn test_feature_column_stds() {
+        use crate::prediction::model::features::FEATURE_DIM;
+
+        let mut samples = vec![1.0f32; FEATURE_DIM * 2];
+        samples[0] = 0.0;
+        samples[FEATURE_DIM] = 2.0;
+        let stds = feature_column_stds(&samples, 2);
+        assert!((stds[0] - 1.0).abs() < 1e-6);
+        assert_eq!(stds[1], 0.0);
+        assert_eq!(feature_column_stds(&[], 0), vec![0.0; FEATURE_DIM]);
+    }
+
     fn history_with_mild_uptrend() -> Vec<HistoricalData> {
         let start = NaiveDate::from_ymd_opt(2026, 1, 1).unwrap();
         let mut close = 100.0;
@@ -1233,6 +1866,10 @@ mod tests {
             test_samples: None,
             mae: None,
             rmse: None,
+            model_status: Default::default(),
+            last_trained_at: None,
+            training_params: None,
+            feature_set_version: None,
         }
     }
 
@@ -1321,6 +1958,9 @@ mod tests {
             model_name: None,
             prediction_days: 0,
             use_candle: false,
+            refresh_if_stale: false,
+            include_news: false,
+            include_events: false,
         };
 
         let response = predict_from_historical(&request, &historical).unwrap();
@@ -1328,4 +1968,62 @@ mod tests {
         assert_eq!(response.predictions.len(), 1);
         assert!(response.predictions[0].predicted_price.is_finite());
     }
+
+    #[test]
+    fn test_conformal_quantile_uses_finite_sample_rank() {
+        let mut conformal = ConformalPredictor::new(0.2);
+        let scores: Vec<f64> = (1..=9).map(f64::from).collect();
+        let predictions = vec![0.0; 9];
+        let actuals: Vec<f64> = scores.iter().rev().map(|s| -s).collect();
+        conformal.calibrate(&predictions, &actuals);
+
+        // ⌈10 × 0.8⌉ = 第 8 小的残差
+        assert_eq!(conformal.nonconformity_scores, scores);
+        assert_eq!(conformal.quantile(), Some(8.0));
+        assert_eq!(conformal.predict_interval(100.0), (92.0, 108.0));
+        // 留出样本的误差 5、8 落在半宽 8 内，9 落在外
+        let coverage = conformal.empirical_coverage(&[0.0, 0.0, 10.0], &[5.0, -8.0, 1.0]);
+        assert!((coverage.unwrap() - 2.0 / 3.0).abs() < 1e-12);
+        assert_eq!(conformal.empirical_coverage(&[], &[]), None);
+
+        // 3 个样本无法支撑 80% 覆盖：⌈4 × 0.8⌉ = 4 > 3
+        conformal.calibrate(&[0.0; 3], &[1.0, 2.0, 3.0]);
+        assert_eq!(conformal.quantile(), None);
+        assert_eq!(conformal.predict_interval(1.0), (f64::NEG_INFINITY, f64::INFINITY));
+    }
+
+    #[test]
+    fn test_attach_conformal_intervals_replaces_volatility_bands() {
+        let historical = history_with_mild_uptrend();
+        let request = PredictionRequest {
+            stock_code: "600000".to_string(),
+            model_name: None,
+            prediction_days: 4,
+            use_candle: false,
+            refresh_if_stale: false,
+            include_news: false,
+            include_events: false,
+        };
+        let mut predictions = predict_from_historical(&request, &historical).unwrap().predictions;
+        let base = historical.last().unwrap().close;
+
+        let mut conformal = ConformalPredictor::new(0.2);
+        let scores: Vec<f64> = (1..=25).map(f64::from).collect();
+        conformal.calibrate(&[0.0; 25], &scores);
+        assert!(attach_conformal_intervals(&mut predictions, &conformal, base, 4));
+
+        for (idx, prediction) in predictions.iter().enumerate() {
+            let cum_change = (prediction.predicted_price - base) / base * 100.0;
+            let scale = ((idx + 1) as f64 / 4.0).sqrt();
+            let interval = prediction.interval.as_ref().unwrap();
+            let stress = prediction.stress_interval.as_ref().unwrap();
+            assert_eq!(interval.method, CONFORMAL_METHOD);
+            assert!((interval.confidence - 0.8).abs() < 1e-9);
+            // ⌈26 × 0.8⌉ = 21，⌈26 × 0.95⌉ = 25
+            assert!((interval.upper_change_percent - (cum_change + 21.0 * scale)).abs() < 1e-9);
+            assert!((stress.lower_change_percent - (cum_change - 25.0 * scale)).abs() < 1e-9);
+            assert_eq!(prediction.prediction_low, interval.lower_price);
+        }
+        assert!(!attach_conformal_intervals(&mut predictions, &ConformalPredictor::new(0.2), base, 4));
+    }
 }
@@ -0,0 +1,128 @@
+//! 线性回归基线模型
+//!
+//! 以岭回归（带微小 L2 正则的正规方程）拟合特征 → 周期收益率，训练近乎瞬时，
+//! 适合需要在大量滚动窗口上反复重训的稳定性检验。
+
+use super::features::FEATURE_DIM;
+
+/// 岭回归正则系数，仅用于保证正规方程可解
+const RIDGE_LAMBDA: f64 = 1e-3;
+
+/// 线性回归模型：y = intercept + Σ wᵢ·xᵢ
+#[derive(Debug, Clone)]
+pub struct LinearRegression {
+    pub weights: [f64; FEATURE_DIM],
+    pub intercept: f64,
+}
+
+impl LinearRegression {
+    /// 在扁平特征（n×FEATURE_DIM）与标签上拟合；样本不足或方程奇异时返回 None
+    pub fn fit(features: &[[f32; FEATURE_DIM]], labels: &[f64]) -> Option<Self> {
+        let n = features.len().min(labels.len());
+        if n <= FEATURE_DIM {
+            return None;
+        }
+
+        // 先中心化再解 (XᵀX + λI)w = Xᵀy，截距由均值恢复
+        let mut x_mean = [0.0; FEATURE_DIM];
+        for row in &features[..n] {
+            for (mean, &value) in x_mean.iter_mut().zip(row) {
+                *mean += value as f64 / n as f64;
+            }
+        }
+        let y_mean = labels[..n].iter().sum::<f64>() / n as f64;
+
+        let mut xtx = [[0.0; FEATURE_DIM]; FEATURE_DIM];
+        let mut xty = [0.0; FEATURE_DIM];
+        for (row, &y) in features[..n].iter().zip(&labels[..n]) {
+            let centered: Vec<f64> = row
+                .iter()
+                .zip(&x_mean)
+                .map(|(&v, mean)| v as f64 - mean)
+                .collect();
+            for i in 0..FEATURE_DIM {
+                xty[i] += centered[i] * (y - y_mean);
+                for j in 0..FEATURE_DIM {
+                    xtx[i][j] += centered[i] * centered[j];
+                }
+            }
+        }
+        for (i, row) in xtx.iter_mut().enumerate() {
+            row[i] += RIDGE_LAMBDA * n as f64;
+        }
+
+        let weights = solve_linear_system(xtx, xty)?;
+        let intercept = y_mean - weights.iter().zip(&x_mean).map(|(w, m)| w * m).sum::<f64>();
+        Some(Self { weights, intercept })
+    }
+
+    pub fn predict(&self, features: &[f32; FEATURE_DIM]) -> f64 {
+        self.intercept
+            + self
+                .weights
+                .iter()
+                .zip(features)
+                .map(|(w, &x)| w * x as f64)
+                .sum::<f64>()
+    }
+}
+
+/// 部分主元高斯消元
+fn solve_linear_system(
+    mut a: [[f64; FEATURE_DIM]; FEATURE_DIM],
+    mut b: [f64; FEATURE_DIM],
+) -> Option<[f64; FEATURE_DIM]> {
+    for col in 0..FEATURE_DIM {
+        let pivot =
+            (col..FEATURE_DIM).max_by(|&i, &j| a[i][col].abs().total_cmp(&a[j][col].abs()))?;
+        if a[pivot][col].abs() < 1e-12 {
+            return None;
+        }
+        a.swap(col, pivot);
+        b.swap(col, pivot);
+        for row in col + 1..FEATURE_DIM {
+            let factor = a[row][col] / a[col][col];
+            let pivot_row = a[col];
+            for (value, pivot_value) in a[row].iter_mut().zip(pivot_row).skip(col) {
+                *value -= factor * pivot_value;
+            }
+            b[row] -= factor * b[col];
+        }
+    }
+
+    let mut x = [0.0; FEATURE_DIM];
+    for row in (0..FEATURE_DIM).rev() {
+        let tail: f64 = (row + 1..FEATURE_DIM).map(|k| a[row][k] * x[k]).sum();
+        x[row] = (b[row] - tail) / a[row][row];
+    }
+    Some(x)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_linear_regression_recovers_coefficients() {
+        let features: Vec<[f32; FEATURE_DIM]> = (0..200)
+            .map(|i| {
+                let mut row = [0.0f32; FEATURE_DIM];
+                for (j, value) in row.iter_mut().enumerate() {
+                    *value = ((i * (j + 3)) as f32 * 0.37).sin();
+                }
+                row
+            })
+            .collect();
+        let labels: Vec<f64> = features
+            .iter()
+            .map(|row| 0.5 + 2.0 * row[0] as f64 - 1.0 * row[3] as f64)
+            .collect();
+
+        let model = LinearRegression::fit(&features, &labels).unwrap();
+        assert!((model.weights[0] - 2.0).abs() < 0.05);
+        assert!((model.weights[3] + 1.0).abs() < 0.05);
+        assert!((model.intercept - 0.5).abs() < 0.05);
+        assert!((model.predict(&features[10]) - labels[10]).abs() < 0.05);
+        assert!(LinearRegression::fit(&features[..5], &labels[..5]).is_none());
+    }
+}
@@ -1,6 +1,8 @@
 //! 模型管理模块
 
-use crate::prediction::types::ModelInfo;
+use crate::prediction::types::{ModelInfo, ModelStatus};
+use log::warn;
+use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::PathBuf;
 use uuid::Uuid;
@@ -27,6 +29,11 @@ pub fn get_metadata_file_path(model_id: &str) -> PathBuf {
     get_models_dir().join(format!("{model_id}.json"))
 }
 
+/// 获取共形校准残差路径
+pub fn get_conformal_file_path(model_id: &str) -> PathBuf {
+    get_models_dir().join(format!("{model_id}.conformal"))
+}
+
 /// 生成模型 ID
 pub fn generate_model_id() -> String {
     Uuid::new_v4().to_string()
@@ -62,8 +69,8 @@ pub fn load_model_metadata(model_id: &str) -> Result<ModelInfo, String> {
         .map_err(|e| format!("解析元数据失败: {e}"))
 }
 
-/// 列出指定股票的所有模型
-pub fn list_models(stock_code: &str) -> Vec<ModelInfo> {
+/// 列出全部股票的所有模型
+fn list_all_models() -> Vec<ModelInfo> {
     let models_dir = get_models_dir();
     let mut models = Vec::new();
     
@@ -73,9 +80,7 @@ pub fn list_models(stock_code: &str) -> Vec<ModelInfo> {
             if path.extension().map_or(false, |ext| ext == "json") {
                 if let Ok(json) = fs::read_to_string(&path) {
                     if let Ok(metadata) = serde_json::from_str::<ModelInfo>(&json) {
-                        if metadata.stock_code == stock_code {
-                            models.push(metadata);
-                        }
+                        models.push(metadata);
                     }
                 }
             }
@@ -87,6 +92,14 @@ pub fn list_models(stock_code: &str) -> Vec<ModelInfo> {
     models
 }
 
+/// 列出指定股票的所有模型
+pub fn list_models(stock_code: &str) -> Vec<ModelInfo> {
+    list_all_models()
+        .into_iter()
+        .filter(|model| model.stock_code == stock_code)
+        .collect()
+}
+
 /// 列出指定股票所有权重文件可用的模型。
 pub fn list_available_models(stock_code: &str) -> Vec<ModelInfo> {
     filter_available_models(list_models(stock_code), model_exists)
@@ -109,10 +122,152 @@ pub fn model_matches_identifier(model: &ModelInfo, identifier: &str) -> bool {
     model.id == identifier || model.name == identifier
 }
 
+// =============================================================================
+// 生命周期
+// =============================================================================
+
+/// 默认模型保留天数：创建超过该天数的模型自动归档
+pub const DEFAULT_MODEL_RETENTION_DAYS: u64 = 90;
+
+/// 模型保留策略，保存在 `~/.biga/model_retention_policy.json`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ModelRetentionPolicy {
+    /// 创建超过该天数的模型自动归档
+    pub max_age_days: u64,
+    /// 生产模型是否也自动归档（默认否，避免默认预测模型被静默撤下）
+    #[serde(default)]
+    pub archive_production: bool,
+}
+
+impl Default for ModelRetentionPolicy {
+    fn default() -> Self {
+        Self {
+            max_age_days: DEFAULT_MODEL_RETENTION_DAYS,
+            archive_production: false,
+        }
+    }
+}
+
+fn retention_policy_path() -> PathBuf {
+    let home = dirs::home_dir().unwrap_or_else(|| PathBuf::from("."));
+    home.join(".biga").join("model_retention_policy.json")
+}
+
+/// 读取保留策略；文件不存在或无法解析时返回默认值
+pub fn load_retention_policy() -> ModelRetentionPolicy {
+    fs::read_to_string(retention_policy_path())
+        .ok()
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+/// 保存保留策略
+pub fn save_retention_policy(policy: &ModelRetentionPolicy) -> Result<(), String> {
+    if policy.max_age_days == 0 {
+        return Err("保留天数必须大于 0".to_string());
+    }
+    let path = retention_policy_path();
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| format!("创建配置目录失败: {e}"))?;
+    }
+    let json = serde_json::to_string_pretty(policy)
+        .map_err(|e| format!("序列化保留策略失败: {e}"))?;
+    fs::write(&path, json).map_err(|e| format!("写入保留策略失败: {e}"))
+}
+
+/// 按保留策略判断模型是否应归档（`now` 为 Unix 秒）
+fn is_expired(model: &ModelInfo, policy: &ModelRetentionPolicy, now: u64) -> bool {
+    let eligible = match model.model_status {
+        ModelStatus::Archived => false,
+        ModelStatus::Production => policy.archive_production,
+        ModelStatus::Candidate => true,
+    };
+    eligible && now.saturating_sub(model.created_at) > policy.max_age_days * 24 * 3600
+}
+
+/// 归档全部过期模型，返回被归档的模型 ID
+pub fn archive_expired_models(policy: &ModelRetentionPolicy, now: u64) -> Vec<String> {
+    let mut archived = Vec::new();
+    for mut model in list_all_models() {
+        if !is_expired(&model, policy, now) {
+            continue;
+        }
+        model.model_status = ModelStatus::Archived;
+        match save_model_metadata(&model) {
+            Ok(()) => archived.push(model.id),
+            Err(e) => warn!("归档过期模型 {} 失败: {e}", model.id),
+        }
+    }
+    archived
+}
+
+/// 将模型设为生产模型，同一股票原有的生产模型降为候选。返回受影响的模型
+fn apply_promotion(models: &mut [ModelInfo], model_id: &str) -> Vec<ModelInfo> {
+    let mut changed = Vec::new();
+    for model in models.iter_mut() {
+        let status = if model.id == model_id {
+            ModelStatus::Production
+        } else if model.model_status == ModelStatus::Production {
+            ModelStatus::Candidate
+        } else {
+            continue;
+        };
+        if model.model_status != status {
+            model.model_status = status;
+            changed.push(model.clone());
+        }
+    }
+    changed
+}
+
+/// 将模型提升为所属股票的生产模型（需权重文件存在）
+pub fn promote_to_production(model_id: &str) -> Result<ModelInfo, String> {
+    let target = load_model_metadata(model_id)?;
+    if !model_exists(model_id) {
+        return Err(format!("模型 `{model_id}` 的权重文件不存在"));
+    }
+    let mut models = list_models(&target.stock_code);
+    for model in apply_promotion(&mut models, model_id) {
+        save_model_metadata(&model)?;
+    }
+    load_model_metadata(model_id)
+}
+
+/// 归档模型：不再参与默认模型选择，仍可按 ID 显式指定
+pub fn archive_model(model_id: &str) -> Result<ModelInfo, String> {
+    let mut model = load_model_metadata(model_id)?;
+    model.model_status = ModelStatus::Archived;
+    save_model_metadata(&model)?;
+    Ok(model)
+}
+
+/// 指定股票当前的生产模型（需权重文件存在）
+pub fn get_production_model(stock_code: &str) -> Option<ModelInfo> {
+    list_available_models(stock_code)
+        .into_iter()
+        .find(|model| model.model_status == ModelStatus::Production)
+}
+
+/// 全部股票中权重文件可用的生产模型
+pub fn list_production_models() -> Vec<ModelInfo> {
+    filter_available_models(list_all_models(), model_exists)
+        .into_iter()
+        .filter(|model| model.model_status == ModelStatus::Production)
+        .collect()
+}
+
+/// 生产模型距上次训练是否已超过 `retrain_days` 天（`now` 为 Unix 秒）
+pub fn is_retrain_due(model: &ModelInfo, retrain_days: u32, now: u64) -> bool {
+    let last_trained_at = model.last_trained_at.unwrap_or(model.created_at);
+    model.model_status == ModelStatus::Production
+        && now.saturating_sub(last_trained_at) > u64::from(retrain_days) * 24 * 3600
+}
+
 /// 删除模型
 pub fn delete_model(model_id: &str) -> Result<(), String> {
     let model_path = get_model_file_path(model_id);
     let metadata_path = get_metadata_file_path(model_id);
+    let conformal_path = get_conformal_file_path(model_id);
     
     if model_path.exists() {
         fs::remove_file(&model_path)
@@ -123,6 +278,11 @@ pub fn delete_model(model_id: &str) -> Result<(), String> {
         fs::remove_file(&metadata_path)
             .map_err(|e| format!("删除元数据文件失败: {e}"))?;
     }
+
+    if conformal_path.exists() {
+        fs::remove_file(&conformal_path)
+            .map_err(|e| format!("删除共形校准文件失败: {e}"))?;
+    }
     
     Ok(())
 }
@@ -159,6 +319,10 @@ mod tests {
             test_samples: None,
             mae: None,
             rmse: None,
+            model_status: Default::default(),
+            last_trained_at: None,
+            training_params: None,
+            feature_set_version: None,
         }
     }
 
@@ -190,6 +354,64 @@ mod tests {
         assert_eq!(model.id, "legacy-id");
         assert_eq!(model.training_end_date, None);
         assert_eq!(model.test_samples, None);
+        assert_eq!(model.model_status, ModelStatus::Candidate);
+    }
+
+    #[test]
+    fn test_promotion_demotes_previous_production_model() {
+        let mut previous = model();
+        previous.id = "previous".to_string();
+        previous.model_status = ModelStatus::Production;
+        let mut archived = model();
+        archived.id = "archived".to_string();
+        archived.model_status = ModelStatus::Archived;
+        let mut models = vec![model(), previous, archived];
+
+        let changed = apply_promotion(&mut models, "model-id");
+
+        assert_eq!(changed.len(), 2);
+        assert_eq!(models[0].model_status, ModelStatus::Production);
+        assert_eq!(models[1].model_status, ModelStatus::Candidate);
+        assert_eq!(models[2].model_status, ModelStatus::Archived);
+        assert!(apply_promotion(&mut models, "model-id").is_empty());
+    }
+
+    #[test]
+    fn test_retention_policy_archives_old_candidates_only() {
+        let policy = ModelRetentionPolicy::default();
+        let day = 24 * 3600;
+        let now = 200 * day;
+        let mut old = model();
+        old.created_at = now - 91 * day;
+        let mut recent = model();
+        recent.created_at = now - 30 * day;
+        let mut old_production = old.clone();
+        old_production.model_status = ModelStatus::Production;
+
+        assert!(is_expired(&old, &policy, now));
+        assert!(!is_expired(&recent, &policy, now));
+        assert!(!is_expired(&old_production, &policy, now));
+        let policy = ModelRetentionPolicy {
+            archive_production: true,
+            ..policy
+        };
+        assert!(is_expired(&old_production, &policy, now));
+    }
+
+    #[test]
+    fn test_retrain_due_uses_last_training_time() {
+        let day = 24 * 3600;
+        let now = 100 * day;
+        let mut production = model();
+        production.model_status = ModelStatus::Production;
+        production.created_at = now - 30 * day;
+
+        assert!(is_retrain_due(&production, 14, now));
+        production.last_trained_at = Some(now - 3 * day);
+        assert!(!is_retrain_due(&production, 14, now));
+        let mut candidate = model();
+        candidate.created_at = now - 30 * day;
+        assert!(!is_retrain_due(&candidate, 14, now));
     }
 
     #[test]
@@ -1,8 +1,13 @@
 //! Candle 模型加载与预测
 
-use super::features::{build_dataset_for_horizon, build_samples, FEATURE_DIM};
-use super::network::Mlp;
+use super::features::{
+    build_dataset_for_horizon, build_samples, mask_unselected_features, model_feature_columns,
+    model_input_dim, truncate_feature_columns, FEATURE_DIM,
+};
+use super::management::get_model_file_path;
+use super::network::{load_weights, Mlp, HIDDEN};
 use crate::db::models::HistoricalData;
+use crate::prediction::types::ModelInfo;
 use candle_core::{DType, Device, Tensor};
 use candle_nn::{VarBuilder, VarMap};
 use chrono::NaiveDate;
@@ -12,18 +17,39 @@ use std::path::Path;
 pub struct MlPredictor {
     mlp: Mlp,
     device: Device,
+    /// 网络输入维度（旧特征集模型小于 FEATURE_DIM，预测时只取前若干列）
+    input_dim: usize,
+    /// 模型训练时选用的特征列，其余列预测时置零
+    feature_columns: Vec<usize>,
 }
 
 impl MlPredictor {
+    /// 加载已保存的模型：按元数据中的特征集版本确定输入维度，并屏蔽未参与训练的特征
+    pub fn for_model(model: &ModelInfo) -> Result<Self, String> {
+        let input_dim = model_input_dim(model.feature_set_version);
+        Ok(Self::load(&get_model_file_path(&model.id), input_dim)?.with_features(&model.features))
+    }
+
     /// 从 safetensors 权重文件加载
-    pub fn load(path: &Path) -> Result<Self, String> {
+    fn load(path: &Path, input_dim: usize) -> Result<Self, String> {
         let device = Device::Cpu;
         let mut varmap = VarMap::new();
         // 先用 VarBuilder 注册结构，再从文件加载权重
         let vb = VarBuilder::from_varmap(&varmap, DType::F32, &device);
-        let mlp = Mlp::new(vb).map_err(|e| e.to_string())?;
-        varmap.load(path).map_err(|e| e.to_string())?;
-        Ok(Self { mlp, device })
+        let mlp = Mlp::with_dims(vb, input_dim, HIDDEN).map_err(|e| e.to_string())?;
+        load_weights(&mut varmap, path)?;
+        Ok(Self {
+            mlp,
+            device,
+            input_dim,
+            feature_columns: (0..FEATURE_DIM).collect(),
+        })
+    }
+
+    /// 按模型元数据中的特征名屏蔽未参与训练的特征（见 [`mask_unselected_features`]）
+    fn with_features(mut self, features: &[String]) -> Self {
+        self.feature_columns = model_feature_columns(features);
+        self
     }
 
     /// 预测一组特征对应的模型训练周期收益率（%）。
@@ -32,7 +58,10 @@ impl MlPredictor {
         if rows == 0 {
             return Err("特征为空".to_string());
         }
-        let x = Tensor::from_vec(features.to_vec(), (rows, FEATURE_DIM), &self.device)
+        let mut features = features.to_vec();
+        mask_unselected_features(&mut features, &self.feature_columns);
+        let features = truncate_feature_columns(&features, self.input_dim);
+        let x = Tensor::from_vec(features, (rows, self.input_dim), &self.device)
             .map_err(|e| e.to_string())?;
         let pred = self.mlp.forward(&x).map_err(|e| e.to_string())?;
         let v: Vec<f32> = pred
@@ -78,6 +107,24 @@ pub fn evaluate_on_horizon_after(
     evaluate_predictions(&features, &labels, n, |feat| predictor.predict(feat))
 }
 
+/// 训练结束日之后各样本的 (模型预测, 实际) 周期收益率（%），用于共形校准
+pub fn horizon_predictions_after(
+    historical: &[HistoricalData],
+    predictor: &MlPredictor,
+    horizon: usize,
+    min_feature_date: NaiveDate,
+) -> (Vec<f64>, Vec<f64>) {
+    let (features, labels, n) =
+        build_evaluation_dataset_after(historical, horizon, min_feature_date);
+    (0..n)
+        .filter_map(|i| {
+            let feat = &features[i * FEATURE_DIM..(i + 1) * FEATURE_DIM];
+            let pred = predictor.predict(feat).ok()?;
+            Some((pred, labels[i] as f64))
+        })
+        .unzip()
+}
+
 fn build_evaluation_dataset_after(
     historical: &[HistoricalData],
     horizon: usize,
@@ -190,8 +237,8 @@ mod tests {
 
     #[test]
     fn test_build_evaluation_dataset_after_filters_training_window() {
-        let historical = make_history(30);
-        let min_feature_date = historical[22].date;
+        let historical = make_history(70);
+        let min_feature_date = historical[62].date;
         let (_, labels, n) = build_evaluation_dataset_after(&historical, 1, min_feature_date);
 
         assert_eq!(n, 6);
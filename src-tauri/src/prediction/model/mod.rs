@@ -8,6 +8,14 @@ pub mod management;
 pub mod features;
 pub mod network;
 pub mod ml_inference;
+pub mod linear;
+pub mod optimization;
+pub mod hyperparameter_optimization;
+pub mod onnx_export;
+pub mod ensemble;
+pub mod quantile;
+pub mod feature_selection;
+pub mod drift;
 
 pub const HORIZON_AWARE_MODEL_TYPE: &str = "candle_mlp_horizon";
 
@@ -1,8 +1,9 @@
 //! Candle MLP 网络定义与训练
 
-use super::features::FEATURE_DIM;
+use super::features::{truncate_feature_columns, FEATURE_DIM};
+use crate::utils::progress::{NoProgress, ProgressSink, OPERATION_CANCELLED};
 use candle_core::{DType, Device, Tensor};
-use candle_nn::{linear, AdamW, Linear, Module, Optimizer, ParamsAdamW, VarBuilder, VarMap};
+use candle_nn::{linear, AdamW, Linear, Module, Optimizer, ParamsAdamW, VarBuilder, VarMap, SGD};
 use std::path::Path;
 
 /// 隐藏层维度
@@ -15,18 +16,43 @@ pub struct Mlp {
     out: Linear,
 }
 
+/// 从 safetensors 文件加载已注册到 `varmap` 的网络权重。
+///
+/// 形状不符通常是特征集变更（FEATURE_DIM 改变）后的旧模型，需重新训练。
+pub fn load_weights(varmap: &mut VarMap, path: &Path) -> Result<(), String> {
+    varmap.load(path).map_err(|e| {
+        format!("加载模型权重失败（当前特征维度 {FEATURE_DIM}，特征集变更前训练的模型需重新训练）: {e}")
+    })
+}
+
 impl Mlp {
     pub fn new(vb: VarBuilder) -> candle_core::Result<Self> {
+        Self::with_dims(vb, FEATURE_DIM, HIDDEN)
+    }
+
+    /// 自定义输入维度与隐藏层宽度（超参数搜索用）
+    pub fn with_dims(vb: VarBuilder, input_size: usize, hidden_size: usize) -> candle_core::Result<Self> {
         Ok(Self {
-            l1: linear(FEATURE_DIM, HIDDEN, vb.pp("l1"))?,
-            l2: linear(HIDDEN, HIDDEN, vb.pp("l2"))?,
-            out: linear(HIDDEN, 1, vb.pp("out"))?,
+            l1: linear(input_size, hidden_size, vb.pp("l1"))?,
+            l2: linear(hidden_size, hidden_size, vb.pp("l2"))?,
+            out: linear(hidden_size, 1, vb.pp("out"))?,
         })
     }
 
     pub fn forward(&self, x: &Tensor) -> candle_core::Result<Tensor> {
-        let x = self.l1.forward(x)?.relu()?;
-        let x = self.l2.forward(&x)?.relu()?;
+        self.forward_train(x, 0.0)
+    }
+
+    /// 训练前向：`dropout > 0` 时在隐藏层后做 dropout
+    pub fn forward_train(&self, x: &Tensor, dropout: f32) -> candle_core::Result<Tensor> {
+        let mut x = self.l1.forward(x)?.relu()?;
+        if dropout > 0.0 {
+            x = candle_nn::ops::dropout(&x, dropout)?;
+        }
+        let mut x = self.l2.forward(&x)?.relu()?;
+        if dropout > 0.0 {
+            x = candle_nn::ops::dropout(&x, dropout)?;
+        }
         self.out.forward(&x)
     }
 }
@@ -54,12 +80,23 @@ pub fn train_and_save(
     split: f64,
     save_path: &Path,
 ) -> Result<TrainOutcome, String> {
-    train_and_save_with_gap(features, labels, n, epochs, learning_rate, split, 0, save_path)
+    train_and_save_with_gap(
+        features,
+        labels,
+        n,
+        epochs,
+        learning_rate,
+        split,
+        0,
+        save_path,
+        &NoProgress,
+    )
 }
 
 /// 训练 MLP 并保存权重，测试集与训练集之间跳过 `test_gap` 个连续样本。
 ///
 /// 多日标签会覆盖未来多个交易日；留出间隔可避免测试样本特征期与训练标签窗口重叠。
+/// 每轮训练后上报进度；`progress` 请求取消时不保存权重并返回错误。
 #[allow(clippy::too_many_arguments)]
 pub fn train_and_save_with_gap(
     features: &[f32],
@@ -70,6 +107,7 @@ pub fn train_and_save_with_gap(
     split: f64,
     test_gap: usize,
     save_path: &Path,
+    progress: &dyn ProgressSink,
 ) -> Result<TrainOutcome, String> {
     if n < 20 {
         return Err(format!("样本不足，无法训练（n={n}）"));
@@ -109,10 +147,15 @@ pub fn train_and_save_with_gap(
     .map_err(|e| e.to_string())?;
 
     // 训练循环（全批量梯度下降，MSE 损失）
-    for _ in 0..epochs.max(1) {
+    let epochs = epochs.max(1);
+    for epoch in 0..epochs {
+        if progress.is_cancelled() {
+            return Err(OPERATION_CANCELLED.to_string());
+        }
         let pred = mlp.forward(&x_train).map_err(|e| e.to_string())?;
         let loss = candle_nn::loss::mse(&pred, &y_train).map_err(|e| e.to_string())?;
         optimizer.backward_step(&loss).map_err(|e| e.to_string())?;
+        progress.report(epoch + 1, epochs, &format!("训练第 {}/{epochs} 轮", epoch + 1));
     }
 
     // 测试集评估
@@ -272,6 +315,188 @@ pub fn train_predict(
         .map_err(|e| e.to_string())
 }
 
+/// 单组超参数
+#[derive(Debug, Clone, Copy)]
+pub struct MlpHyperparams {
+    pub hidden_size: usize,
+    pub dropout: f64,
+    pub learning_rate: f64,
+    pub epochs: usize,
+}
+
+/// 按给定超参数训练（输入维度 `input_size`），返回验证集 (MSE, 方向准确率)
+pub fn train_eval_with_hyperparams(
+    train_x: &[f32],
+    train_y: &[f32],
+    val_x: &[f32],
+    val_y: &[f32],
+    input_size: usize,
+    params: MlpHyperparams,
+) -> Result<(f64, f64), String> {
+    let (n_train, n_val) = (train_y.len(), val_y.len());
+    if n_train < 10 || n_val == 0 {
+        return Err(format!("样本不足（train={n_train}, val={n_val}）"));
+    }
+    let device = Device::Cpu;
+    let x_train = Tensor::from_vec(train_x.to_vec(), (n_train, input_size), &device)
+        .map_err(|e| e.to_string())?;
+    let y_train =
+        Tensor::from_vec(train_y.to_vec(), (n_train, 1), &device).map_err(|e| e.to_string())?;
+    let x_val =
+        Tensor::from_vec(val_x.to_vec(), (n_val, input_size), &device).map_err(|e| e.to_string())?;
+
+    let varmap = VarMap::new();
+    let vb = VarBuilder::from_varmap(&varmap, DType::F32, &device);
+    let mlp = Mlp::with_dims(vb, input_size, params.hidden_size.max(1)).map_err(|e| e.to_string())?;
+    let mut optimizer = AdamW::new(
+        varmap.all_vars(),
+        ParamsAdamW {
+            lr: params.learning_rate.max(1e-5),
+            ..Default::default()
+        },
+    )
+    .map_err(|e| e.to_string())?;
+
+    let dropout = params.dropout.clamp(0.0, 0.9) as f32;
+    for _ in 0..params.epochs.max(1) {
+        let pred = mlp.forward_train(&x_train, dropout).map_err(|e| e.to_string())?;
+        let loss = candle_nn::loss::mse(&pred, &y_train).map_err(|e| e.to_string())?;
+        optimizer.backward_step(&loss).map_err(|e| e.to_string())?;
+    }
+
+    let preds: Vec<f32> = mlp
+        .forward(&x_val)
+        .and_then(|t| t.flatten_all())
+        .and_then(|t| t.to_vec1::<f32>())
+        .map_err(|e| e.to_string())?;
+    let mut direction_correct = 0usize;
+    let mut sq_sum = 0.0f64;
+    for (p, a) in preds.iter().zip(val_y) {
+        let (p, a) = (*p as f64, *a as f64);
+        if (p > 0.0 && a > 0.0) || (p < 0.0 && a < 0.0) {
+            direction_correct += 1;
+        }
+        sq_sum += (p - a).powi(2);
+    }
+    Ok((sq_sum / n_val as f64, direction_correct as f64 / n_val as f64))
+}
+
+/// 在全部样本上训练 MLP 并记录每轮 MSE 损失（不保存权重），用于诊断是否过早收敛/欠拟合。
+pub fn training_loss_curve(
+    features: &[f32],
+    labels: &[f32],
+    n: usize,
+    epochs: usize,
+    learning_rate: f64,
+) -> Result<Vec<f32>, String> {
+    if n < 10 {
+        return Err(format!("样本不足，无法训练（n={n}）"));
+    }
+    let device = Device::Cpu;
+    let x = Tensor::from_vec(features[..n * FEATURE_DIM].to_vec(), (n, FEATURE_DIM), &device)
+        .map_err(|e| e.to_string())?;
+    let y = Tensor::from_vec(labels[..n].to_vec(), (n, 1), &device).map_err(|e| e.to_string())?;
+
+    let varmap = VarMap::new();
+    let vb = VarBuilder::from_varmap(&varmap, DType::F32, &device);
+    let mlp = Mlp::new(vb).map_err(|e| e.to_string())?;
+    let mut optimizer = AdamW::new(
+        varmap.all_vars(),
+        ParamsAdamW {
+            lr: learning_rate.max(1e-5),
+            ..Default::default()
+        },
+    )
+    .map_err(|e| e.to_string())?;
+
+    let mut losses = Vec::with_capacity(epochs.max(1));
+    for _ in 0..epochs.max(1) {
+        let pred = mlp.forward(&x).map_err(|e| e.to_string())?;
+        let loss = candle_nn::loss::mse(&pred, &y).map_err(|e| e.to_string())?;
+        losses.push(loss.to_scalar::<f32>().map_err(|e| e.to_string())?);
+        optimizer.backward_step(&loss).map_err(|e| e.to_string())?;
+    }
+    Ok(losses)
+}
+
+/// 在线微调前后在同一批样本上的表现
+#[derive(Debug, Clone, Copy)]
+pub struct FineTuneOutcome {
+    pub accuracy_before: f64,
+    pub accuracy_after: f64,
+    pub mae_after: f64,
+    pub rmse_after: f64,
+}
+
+/// 在线学习：加载 `weights_path` 的权重，在一个 mini-batch 上累积 MSE 梯度后做一步 SGD 更新并写回。
+///
+/// `features` 为扁平 n×FEATURE_DIM，`labels` 为 n 个实际周期收益率（%）；
+/// `input_dim` 为模型输入维度（旧特征集模型只取前若干列）。
+pub fn fine_tune_step(
+    weights_path: &Path,
+    input_dim: usize,
+    features: &[f32],
+    labels: &[f32],
+    learning_rate: f64,
+) -> Result<FineTuneOutcome, String> {
+    let n = labels.len();
+    if n == 0 || features.len() != n * FEATURE_DIM {
+        return Err(format!("特征与标签数量不匹配（features={}, labels={n}）", features.len()));
+    }
+    let device = Device::Cpu;
+    let mut varmap = VarMap::new();
+    let vb = VarBuilder::from_varmap(&varmap, DType::F32, &device);
+    let mlp = Mlp::with_dims(vb, input_dim, HIDDEN).map_err(|e| e.to_string())?;
+    load_weights(&mut varmap, weights_path)?;
+
+    let x = Tensor::from_vec(truncate_feature_columns(features, input_dim), (n, input_dim), &device)
+        .map_err(|e| e.to_string())?;
+    let y = Tensor::from_vec(labels.to_vec(), (n, 1), &device).map_err(|e| e.to_string())?;
+    let predict = || -> Result<Vec<f32>, String> {
+        mlp.forward(&x)
+            .and_then(|t| t.flatten_all())
+            .and_then(|t| t.to_vec1::<f32>())
+            .map_err(|e| e.to_string())
+    };
+
+    let (accuracy_before, _, _) = batch_metrics(&predict()?, labels);
+    let mut optimizer = SGD::new(varmap.all_vars(), learning_rate).map_err(|e| e.to_string())?;
+    let pred = mlp.forward(&x).map_err(|e| e.to_string())?;
+    let loss = candle_nn::loss::mse(&pred, &y).map_err(|e| e.to_string())?;
+    optimizer.backward_step(&loss).map_err(|e| e.to_string())?;
+
+    let after = predict()?;
+    if after.iter().any(|p| !p.is_finite()) {
+        return Err("在线更新后输出非有限值，已放弃本次更新".to_string());
+    }
+    let (accuracy_after, mae_after, rmse_after) = batch_metrics(&after, labels);
+    varmap.save(weights_path).map_err(|e| e.to_string())?;
+
+    Ok(FineTuneOutcome {
+        accuracy_before,
+        accuracy_after,
+        mae_after,
+        rmse_after,
+    })
+}
+
+/// (方向准确率, MAE, RMSE)
+fn batch_metrics(preds: &[f32], labels: &[f32]) -> (f64, f64, f64) {
+    let count = preds.len().min(labels.len()).max(1) as f64;
+    let mut direction_correct = 0usize;
+    let mut abs_sum = 0.0f64;
+    let mut sq_sum = 0.0f64;
+    for (p, a) in preds.iter().zip(labels) {
+        let (p, a) = (*p as f64, *a as f64);
+        if (p > 0.0 && a > 0.0) || (p < 0.0 && a < 0.0) {
+            direction_correct += 1;
+        }
+        abs_sum += (p - a).abs();
+        sq_sum += (p - a).powi(2);
+    }
+    (direction_correct as f64 / count, abs_sum / count, (sq_sum / count).sqrt())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -320,8 +545,9 @@ mod tests {
 
         let path = std::env::temp_dir()
             .join(format!("biga_test_model_gap_{}.safetensors", std::process::id()));
-        let outcome = train_and_save_with_gap(&features, &labels, n, 100, 0.05, 0.8, 5, &path)
-            .expect("training failed");
+        let outcome =
+            train_and_save_with_gap(&features, &labels, n, 100, 0.05, 0.8, 5, &path, &NoProgress)
+                .expect("training failed");
 
         assert_eq!(outcome.train_samples, 64);
         assert_eq!(outcome.test_samples, 11);
@@ -329,4 +555,68 @@ mod tests {
 
         std::fs::remove_file(&path).ok();
     }
+
+    /// 第 3 轮开始前请求取消
+    struct CancelAfter {
+        epochs: usize,
+        reported: std::sync::atomic::AtomicUsize,
+    }
+
+    impl ProgressSink for CancelAfter {
+        fn report(&self, completed: usize, _total: usize, _message: &str) {
+            self.reported.store(completed, std::sync::atomic::Ordering::Relaxed);
+        }
+
+        fn is_cancelled(&self) -> bool {
+            self.reported.load(std::sync::atomic::Ordering::Relaxed) >= self.epochs
+        }
+    }
+
+    #[test]
+    fn test_cancelled_training_does_not_save() {
+        let n = 80;
+        let features = vec![0.1f32; n * FEATURE_DIM];
+        let labels = vec![1.0f32; n];
+        let path = std::env::temp_dir()
+            .join(format!("biga_test_model_cancel_{}.safetensors", std::process::id()));
+        let progress = CancelAfter {
+            epochs: 2,
+            reported: std::sync::atomic::AtomicUsize::new(0),
+        };
+
+        let result =
+            train_and_save_with_gap(&features, &labels, n, 100, 0.05, 0.8, 0, &path, &progress);
+
+        assert_eq!(result.err().as_deref(), Some(OPERATION_CANCELLED));
+        assert_eq!(progress.reported.load(std::sync::atomic::Ordering::Relaxed), 2);
+        assert!(!path.exists(), "取消后不应保存权重");
+    }
+
+    #[test]
+    fn test_fine_tune_step_reduces_batch_error() {
+        let n = 80;
+        let mut features = Vec::with_capacity(n * FEATURE_DIM);
+        let mut labels = Vec::with_capacity(n);
+        for i in 0..n {
+            let f0 = (i as f32 / n as f32) - 0.5;
+            for j in 0..FEATURE_DIM {
+                features.push(if j == 0 { f0 } else { 0.0 });
+            }
+            labels.push(f0 * 10.0);
+        }
+        let path = std::env::temp_dir()
+            .join(format!("biga_test_model_online_{}.safetensors", std::process::id()));
+        train_and_save(&features, &labels, n, 20, 0.01, 0.8, &path).expect("training failed");
+
+        // 实际收益整体偏移 +2%：一步梯度应缩小批内误差
+        let batch_x = &features[..10 * FEATURE_DIM];
+        let batch_y: Vec<f32> = labels[..10].iter().map(|y| y + 2.0).collect();
+        let first = fine_tune_step(&path, FEATURE_DIM, batch_x, &batch_y, 0.05).expect("fine-tune failed");
+        let second = fine_tune_step(&path, FEATURE_DIM, batch_x, &batch_y, 0.05).expect("fine-tune failed");
+        assert!(second.rmse_after < first.rmse_after, "{first:?} → {second:?}");
+        assert!((0.0..=1.0).contains(&second.accuracy_before));
+
+        assert!(fine_tune_step(&path, FEATURE_DIM, batch_x, &batch_y[..5], 0.05).is_err());
+        std::fs::remove_file(&path).ok();
+    }
 }
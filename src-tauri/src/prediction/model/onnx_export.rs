@@ -0,0 +1,407 @@
+//! 模型导出
+//!
+//! Rust 生态缺少原生 ONNX 导出库，这里手写最小的 protobuf 编码器，按 ONNX 规范输出
+//! 仅含 `MatMul` / `Add` / `Relu` 节点的前馈图：线性回归即单层 MatMul + Add，
+//! candle MLP 逐层展开。MLP 另打包为 zip（各层权重 `.npy` + `manifest.json`），
+//! 清单附带特征缩放参数，便于在 Python 等外部环境复现预测。
+
+use super::features::{feature_names, feature_normalization, model_input_dim};
+use super::linear::LinearRegression;
+use super::management::{get_model_file_path, load_model_metadata};
+use super::network::{load_weights, Mlp, HIDDEN};
+use crate::prediction::types::{ModelConfig, ModelInfo};
+use candle_core::{DType, Device};
+use candle_nn::{VarBuilder, VarMap};
+use std::io::Write;
+use std::path::Path;
+use zip::write::SimpleFileOptions;
+
+/// ONNX IR 版本与算子集版本
+const ONNX_IR_VERSION: i64 = 8;
+const ONNX_OPSET_VERSION: i64 = 13;
+/// TensorProto.DataType.FLOAT
+const ONNX_FLOAT: i64 = 1;
+/// MLP 各层在 VarMap 中的前缀（顺序即前向顺序）
+const MLP_LAYERS: [&str; 3] = ["l1", "l2", "out"];
+
+/// 全连接层：`y = x·W + b`，W 为 [输入, 输出] 行优先
+struct DenseLayer {
+    name: String,
+    weight: Vec<f32>,
+    bias: Vec<f32>,
+    input_size: usize,
+    output_size: usize,
+    relu: bool,
+}
+
+/// 将 candle MLP 导出为 ONNX 文件
+pub fn export_to_onnx(
+    varmap: &VarMap,
+    config: &ModelConfig,
+    output_path: &Path,
+) -> Result<(), String> {
+    let layers = mlp_layers(varmap)?;
+    if layers.first().map(|l| l.input_size) != Some(config.input_size) {
+        return Err(format!(
+            "模型输入维度与配置不一致（配置 {}）",
+            config.input_size
+        ));
+    }
+    std::fs::write(output_path, encode_model(&layers, &config.model_type))
+        .map_err(|e| e.to_string())
+}
+
+/// 将线性回归模型导出为 ONNX 文件（单个 MatMul + Add）
+pub fn export_linear_regression_to_onnx(
+    model: &LinearRegression,
+    output_path: &Path,
+) -> Result<(), String> {
+    let layer = DenseLayer {
+        name: "linear".to_string(),
+        weight: model.weights.iter().map(|&w| w as f32).collect(),
+        bias: vec![model.intercept as f32],
+        input_size: model.weights.len(),
+        output_size: 1,
+        relu: false,
+    };
+    std::fs::write(output_path, encode_model(&[layer], "linear_regression"))
+        .map_err(|e| e.to_string())
+}
+
+/// 将 candle MLP 打包为 zip：`model.onnx`、各参数 `.npy` 与 `manifest.json`
+pub fn export_mlp_archive(
+    varmap: &VarMap,
+    config: &ModelConfig,
+    metadata: &ModelInfo,
+    output_path: &Path,
+) -> Result<(), String> {
+    let layers = mlp_layers(varmap)?;
+    let file = std::fs::File::create(output_path).map_err(|e| format!("创建导出文件失败: {e}"))?;
+    let mut zip = zip::ZipWriter::new(file);
+    let options = SimpleFileOptions::default().compression_method(zip::CompressionMethod::Stored);
+    let mut add_file = |name: &str, bytes: &[u8]| -> Result<(), String> {
+        zip.start_file(name, options).map_err(|e| e.to_string())?;
+        zip.write_all(bytes).map_err(|e| e.to_string())
+    };
+
+    add_file("model.onnx", &encode_model(&layers, &config.model_type))?;
+    let mut layer_manifest = Vec::with_capacity(layers.len());
+    for layer in &layers {
+        let weight_file = format!("{}.weight.npy", layer.name);
+        let bias_file = format!("{}.bias.npy", layer.name);
+        add_file(
+            &weight_file,
+            &encode_npy(&layer.weight, &[layer.input_size, layer.output_size]),
+        )?;
+        add_file(&bias_file, &encode_npy(&layer.bias, &[layer.output_size]))?;
+        layer_manifest.push(serde_json::json!({
+            "name": layer.name,
+            "weight": weight_file,
+            "bias": bias_file,
+            "shape": [layer.input_size, layer.output_size],
+            "activation": if layer.relu { "relu" } else { "identity" },
+        }));
+    }
+
+    let manifest = serde_json::json!({
+        "format": "biga-mlp-v1",
+        "model_id": metadata.id,
+        "model_name": metadata.name,
+        "stock_code": metadata.stock_code,
+        "prediction_days": metadata.prediction_days,
+        "target": format!("未来{}个交易日收益率（%）", metadata.prediction_days),
+        "config": config,
+        "forward": "x(1×input) · weight(input×output) + bias，逐层计算",
+        "layers": layer_manifest,
+        // 旧特征集模型只使用前 input_size 个特征
+        "features": feature_names().into_iter().take(config.input_size).collect::<Vec<_>>(),
+        "feature_normalization": feature_normalization()
+            .into_iter()
+            .take(config.input_size)
+            .collect::<Vec<_>>(),
+    });
+    let manifest = serde_json::to_vec_pretty(&manifest).map_err(|e| e.to_string())?;
+    add_file("manifest.json", &manifest)?;
+    zip.finish().map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// 导出已保存的 candle 模型为 zip 归档
+pub fn export_saved_model(model_id: &str, output_path: &Path) -> Result<(), String> {
+    let metadata = load_model_metadata(model_id)?;
+    let weights_path = get_model_file_path(model_id);
+    if !weights_path.exists() {
+        return Err(format!("模型权重文件不存在: {}", weights_path.display()));
+    }
+
+    let mut varmap = VarMap::new();
+    // 先注册网络结构，再从 safetensors 加载权重
+    let input_size = model_input_dim(metadata.feature_set_version);
+    let vb = VarBuilder::from_varmap(&varmap, DType::F32, &Device::Cpu);
+    Mlp::with_dims(vb, input_size, HIDDEN).map_err(|e| e.to_string())?;
+    load_weights(&mut varmap, &weights_path)?;
+
+    let config = ModelConfig {
+        model_type: metadata.model_type.clone(),
+        input_size,
+        hidden_size: HIDDEN,
+        output_size: 1,
+        dropout: 0.0,
+        learning_rate: 0.0,
+        n_layers: MLP_LAYERS.len() - 1,
+        n_heads: 0,
+        max_seq_len: 1,
+    };
+    export_mlp_archive(&varmap, &config, &metadata, output_path)
+}
+
+/// 从 VarMap 读取 MLP 各层参数，candle 的 [输出, 输入] 权重转置为 [输入, 输出]
+fn mlp_layers(varmap: &VarMap) -> Result<Vec<DenseLayer>, String> {
+    let data = varmap.data().lock().map_err(|e| e.to_string())?;
+    let read = |name: &str| -> Result<(Vec<usize>, Vec<f32>), String> {
+        let var = data
+            .get(name)
+            .ok_or_else(|| format!("模型缺少参数 {name}"))?;
+        let tensor = var
+            .as_tensor()
+            .to_dtype(DType::F32)
+            .map_err(|e| e.to_string())?;
+        let values = tensor
+            .flatten_all()
+            .and_then(|t| t.to_vec1::<f32>())
+            .map_err(|e| e.to_string())?;
+        Ok((tensor.dims().to_vec(), values))
+    };
+
+    MLP_LAYERS
+        .iter()
+        .enumerate()
+        .map(|(idx, prefix)| {
+            let (dims, weight) = read(&format!("{prefix}.weight"))?;
+            let (_, bias) = read(&format!("{prefix}.bias"))?;
+            let [output_size, input_size] = dims[..] else {
+                return Err(format!("{prefix}.weight 形状异常: {dims:?}"));
+            };
+            let transposed = (0..input_size)
+                .flat_map(|i| (0..output_size).map(move |o| (i, o)))
+                .map(|(i, o)| weight[o * input_size + i])
+                .collect();
+            Ok(DenseLayer {
+                name: prefix.to_string(),
+                weight: transposed,
+                bias,
+                input_size,
+                output_size,
+                relu: idx + 1 < MLP_LAYERS.len(),
+            })
+        })
+        .collect()
+}
+
+/// 极简 protobuf 编码器（仅 varint 与 length-delimited 两种线格式）
+#[derive(Default)]
+struct ProtoWriter(Vec<u8>);
+
+impl ProtoWriter {
+    fn varint(&mut self, mut value: u64) {
+        while value >= 0x80 {
+            self.0.push((value as u8) | 0x80);
+            value >>= 7;
+        }
+        self.0.push(value as u8);
+    }
+
+    fn int(&mut self, field: u32, value: i64) {
+        self.varint(u64::from(field) << 3);
+        self.varint(value as u64);
+    }
+
+    fn bytes(&mut self, field: u32, bytes: &[u8]) {
+        self.varint((u64::from(field) << 3) | 2);
+        self.varint(bytes.len() as u64);
+        self.0.extend_from_slice(bytes);
+    }
+
+    fn string(&mut self, field: u32, value: &str) {
+        self.bytes(field, value.as_bytes());
+    }
+
+    fn message(&mut self, field: u32, message: ProtoWriter) {
+        self.bytes(field, &message.0);
+    }
+}
+
+/// NodeProto: input=1, output=2, name=3, op_type=4
+fn node(op_type: &str, name: &str, inputs: &[&str], output: &str) -> ProtoWriter {
+    let mut node = ProtoWriter::default();
+    for input in inputs {
+        node.string(1, input);
+    }
+    node.string(2, output);
+    node.string(3, name);
+    node.string(4, op_type);
+    node
+}
+
+/// TensorProto: dims=1, data_type=2, name=8, raw_data=9（小端 float32）
+fn initializer(name: &str, dims: &[usize], values: &[f32]) -> ProtoWriter {
+    let mut tensor = ProtoWriter::default();
+    for &dim in dims {
+        tensor.int(1, dim as i64);
+    }
+    tensor.int(2, ONNX_FLOAT);
+    tensor.string(8, name);
+    let raw: Vec<u8> = values.iter().flat_map(|v| v.to_le_bytes()).collect();
+    tensor.bytes(9, &raw);
+    tensor
+}
+
+/// ValueInfoProto，形状 [batch, width]，batch 为符号维度
+fn value_info(name: &str, width: usize) -> ProtoWriter {
+    let mut batch = ProtoWriter::default();
+    batch.string(2, "batch");
+    let mut feature = ProtoWriter::default();
+    feature.int(1, width as i64);
+    let mut shape = ProtoWriter::default();
+    shape.message(1, batch);
+    shape.message(1, feature);
+
+    let mut tensor_type = ProtoWriter::default();
+    tensor_type.int(1, ONNX_FLOAT);
+    tensor_type.message(2, shape);
+    let mut type_proto = ProtoWriter::default();
+    type_proto.message(1, tensor_type);
+
+    let mut info = ProtoWriter::default();
+    info.string(1, name);
+    info.message(2, type_proto);
+    info
+}
+
+/// 编码 ModelProto：逐层 MatMul → Add（→ Relu）
+fn encode_model(layers: &[DenseLayer], graph_name: &str) -> Vec<u8> {
+    let mut graph = ProtoWriter::default();
+    let mut current = "input".to_string();
+    for layer in layers {
+        let (weight, bias) = (
+            format!("{}.weight", layer.name),
+            format!("{}.bias", layer.name),
+        );
+        let matmul_out = format!("{}.matmul", layer.name);
+        let add_out = format!("{}.add", layer.name);
+        graph.message(
+            1,
+            node("MatMul", &matmul_out, &[&current, &weight], &matmul_out),
+        );
+        graph.message(1, node("Add", &add_out, &[&matmul_out, &bias], &add_out));
+        current = add_out;
+        if layer.relu {
+            let relu_out = format!("{}.relu", layer.name);
+            graph.message(1, node("Relu", &relu_out, &[&current], &relu_out));
+            current = relu_out;
+        }
+    }
+    // 末层输出统一改名为 output
+    graph.message(1, node("Identity", "output", &[&current], "output"));
+    graph.string(2, graph_name);
+    for layer in layers {
+        graph.message(
+            5,
+            initializer(
+                &format!("{}.weight", layer.name),
+                &[layer.input_size, layer.output_size],
+                &layer.weight,
+            ),
+        );
+        graph.message(
+            5,
+            initializer(
+                &format!("{}.bias", layer.name),
+                &[layer.output_size],
+                &layer.bias,
+            ),
+        );
+    }
+    graph.message(
+        11,
+        value_info("input", layers.first().map_or(0, |l| l.input_size)),
+    );
+    graph.message(
+        12,
+        value_info("output", layers.last().map_or(0, |l| l.output_size)),
+    );
+
+    // ModelProto: ir_version=1, producer_name=2, graph=7, opset_import=8
+    let mut opset = ProtoWriter::default();
+    opset.string(1, "");
+    opset.int(2, ONNX_OPSET_VERSION);
+    let mut model = ProtoWriter::default();
+    model.int(1, ONNX_IR_VERSION);
+    model.string(2, "biga");
+    model.message(7, graph);
+    model.message(8, opset);
+    model.0
+}
+
+/// 编码 NumPy `.npy`（v1.0，小端 float32，C 顺序）
+fn encode_npy(values: &[f32], shape: &[usize]) -> Vec<u8> {
+    let shape = match shape {
+        [single] => format!("({single},)"),
+        dims => format!(
+            "({})",
+            dims.iter()
+                .map(|d| d.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    };
+    let mut header = format!("{{'descr': '<f4', 'fortran_order': False, 'shape': {shape}, }}");
+    // 魔数(6) + 版本(2) + 头长度(2) + 头部，总长对齐到 64 字节，以换行结尾
+    let unpadded = 10 + header.len() + 1;
+    header.push_str(&" ".repeat((64 - unpadded % 64) % 64));
+    header.push('\n');
+
+    let mut bytes = b"\x93NUMPY\x01\x00".to_vec();
+    bytes.extend_from_slice(&(header.len() as u16).to_le_bytes());
+    bytes.extend_from_slice(header.as_bytes());
+    bytes.extend(values.iter().flat_map(|v| v.to_le_bytes()));
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prediction::model::features::FEATURE_DIM;
+
+    #[test]
+    fn test_npy_header_is_aligned() {
+        let bytes = encode_npy(&[1.0, 2.0, 3.0, 4.0], &[2, 2]);
+        assert_eq!(&bytes[..8], b"\x93NUMPY\x01\x00");
+        let header_len = u16::from_le_bytes([bytes[8], bytes[9]]) as usize;
+        assert_eq!((10 + header_len) % 64, 0);
+        let header = std::str::from_utf8(&bytes[10..10 + header_len]).unwrap();
+        assert!(header.contains("'shape': (2, 2)"));
+        assert_eq!(bytes.len(), 10 + header_len + 16);
+    }
+
+    #[test]
+    fn test_linear_regression_onnx_contains_graph() {
+        let model = LinearRegression {
+            weights: [0.5; FEATURE_DIM],
+            intercept: 0.1,
+        };
+        let path =
+            std::env::temp_dir().join(format!("biga_test_linear_{}.onnx", std::process::id()));
+        export_linear_regression_to_onnx(&model, &path).unwrap();
+        let bytes = std::fs::read(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+
+        // 首字段 ir_version = 8（field 1, varint）
+        assert_eq!(&bytes[..2], &[0x08, ONNX_IR_VERSION as u8]);
+        let contains = |needle: &[u8]| bytes.windows(needle.len()).any(|w| w == needle);
+        assert!(contains(b"MatMul"));
+        assert!(contains(b"Add"));
+        assert!(!contains(b"Relu"));
+        assert!(contains(&0.5f32.to_le_bytes()));
+    }
+}
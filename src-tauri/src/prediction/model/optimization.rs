@@ -0,0 +1,211 @@
+//! 模型优化建议引擎
+//!
+//! 基于个股历史数据诊断当前 MLP 配置：特征有效性（前一日特征与次日收益的皮尔逊相关）、
+//! 训练损失是否过早进入平台期、多日模型的方向准确率，给出可执行的调参建议。
+
+use crate::db::{connection::DbPool, repository::get_recent_historical_data};
+use crate::prediction::cross_section::pearson;
+use crate::prediction::model::features::{
+    build_dataset_for_horizon, build_samples, feature_names, DatedSample, FEATURE_DIM,
+};
+use crate::prediction::model::management::list_available_models;
+use crate::prediction::model::network::{training_loss_curve, HIDDEN};
+use crate::prediction::types::ModelInfo;
+use serde::{Deserialize, Serialize};
+
+/// 诊断使用的历史K线数
+const OPTIMIZATION_HISTORY_BARS: usize = 500;
+/// 特征分析至少需要的样本数
+const MIN_FEATURE_SAMPLES: usize = 60;
+/// 与次日收益相关系数绝对值低于该值的特征视为无效
+const WEAK_FEATURE_CORRELATION: f64 = 0.05;
+/// 损失曲线诊断的训练轮数与学习率
+const LOSS_CURVE_EPOCHS: usize = 200;
+const LOSS_CURVE_LEARNING_RATE: f64 = 0.01;
+/// 前 1/4 训练轮之后损失相对改善低于该比例视为过早平台化
+const LOSS_PLATEAU_IMPROVEMENT: f64 = 0.02;
+/// 平台期损失仍高于标签方差的该比例，说明模型只拟合到均值（容量不足）
+const UNDERFIT_LOSS_RATIO: f64 = 0.9;
+/// 多日模型方向准确率低于该值建议回退到单日预测
+const MIN_MULTI_DAY_ACCURACY: f64 = 0.5;
+
+/// 单条优化建议
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OptimizationSuggestion {
+    pub suggestion: String,
+    /// 预期影响
+    pub impact: String,
+    /// 优先级，1 最高
+    pub priority: u8,
+}
+
+/// 模型优化建议引擎
+pub struct OptimizationEngine;
+
+impl OptimizationEngine {
+    /// 诊断个股模型配置并按优先级返回优化建议
+    pub async fn suggest_improvements(
+        stock_code: &str,
+        pool: &DbPool,
+    ) -> Result<Vec<OptimizationSuggestion>, String> {
+        let historical = get_recent_historical_data(stock_code, OPTIMIZATION_HISTORY_BARS, pool)
+            .await
+            .map_err(|e| format!("获取历史数据失败: {e}"))?;
+
+        let mut suggestions = Self::horizon_suggestions(&list_available_models(stock_code));
+
+        let (features, labels, n) = build_dataset_for_horizon(&historical, 1);
+        if n >= MIN_FEATURE_SAMPLES {
+            let losses = training_loss_curve(
+                &features,
+                &labels,
+                n,
+                LOSS_CURVE_EPOCHS,
+                LOSS_CURVE_LEARNING_RATE,
+            )?;
+            if loss_plateaued_early(&losses, &labels) {
+                suggestions.push(OptimizationSuggestion {
+                    suggestion: format!(
+                        "训练损失过早进入平台期且接近标签方差，建议将 hidden_size 从 {HIDDEN} 提升至 {}",
+                        HIDDEN * 2
+                    ),
+                    impact: "提升模型容量，缓解欠拟合".to_string(),
+                    priority: 2,
+                });
+            }
+        }
+
+        let samples = build_samples(&historical, 1);
+        if samples.len() >= MIN_FEATURE_SAMPLES {
+            suggestions.extend(Self::feature_suggestions(&samples));
+        }
+
+        suggestions.sort_by_key(|s| s.priority);
+        Ok(suggestions)
+    }
+
+    /// 多日模型方向准确率不足 50% 时建议改为单日预测
+    fn horizon_suggestions(models: &[ModelInfo]) -> Vec<OptimizationSuggestion> {
+        let weak: Vec<String> = models
+            .iter()
+            .filter(|m| m.prediction_days > 1 && m.accuracy < MIN_MULTI_DAY_ACCURACY)
+            .map(|m| {
+                format!(
+                    "{}({}日, {:.0}%)",
+                    m.name,
+                    m.prediction_days,
+                    m.accuracy * 100.0
+                )
+            })
+            .collect();
+        if weak.is_empty() {
+            return Vec::new();
+        }
+        vec![OptimizationSuggestion {
+            suggestion: format!(
+                "多日模型方向准确率低于50%：{}，建议设置 prediction_days = 1 重新训练",
+                weak.join("、")
+            ),
+            impact: "避免多日标签噪声累积导致方向失效".to_string(),
+            priority: 1,
+        }]
+    }
+
+    /// 前一日特征与次日收益的相关系数过低时建议剔除该特征
+    fn feature_suggestions(samples: &[DatedSample]) -> Vec<OptimizationSuggestion> {
+        let returns: Vec<f64> = samples.iter().map(|s| s.fwd_return).collect();
+        feature_names()
+            .into_iter()
+            .enumerate()
+            .take(FEATURE_DIM)
+            .filter_map(|(idx, name)| {
+                let values: Vec<f64> = samples.iter().map(|s| s.features[idx] as f64).collect();
+                let corr = pearson(&values, &returns);
+                (corr.abs() < WEAK_FEATURE_CORRELATION).then(|| OptimizationSuggestion {
+                    suggestion: format!("特征 {name} 与次日收益相关系数仅 {corr:+.3}，建议剔除"),
+                    impact: "减少噪声特征，降低过拟合风险".to_string(),
+                    priority: 3,
+                })
+            })
+            .collect()
+    }
+}
+
+/// 损失在前 1/4 训练轮后几乎不再下降，且仍接近标签方差（仅拟合到均值）
+fn loss_plateaued_early(losses: &[f32], labels: &[f32]) -> bool {
+    if losses.len() < 8 || labels.is_empty() {
+        return false;
+    }
+    let n = labels.len() as f64;
+    let mean = labels.iter().map(|&y| y as f64).sum::<f64>() / n;
+    let variance = labels
+        .iter()
+        .map(|&y| (y as f64 - mean).powi(2))
+        .sum::<f64>()
+        / n;
+
+    let early = losses[losses.len() / 4] as f64;
+    let last = *losses.last().unwrap() as f64;
+    if early <= 0.0 || variance <= 0.0 {
+        return false;
+    }
+    (early - last) / early < LOSS_PLATEAU_IMPROVEMENT && last > variance * UNDERFIT_LOSS_RATIO
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn model(prediction_days: usize, accuracy: f64) -> ModelInfo {
+        ModelInfo {
+            id: format!("m{prediction_days}"),
+            name: format!("模型{prediction_days}"),
+            stock_code: "600519".to_string(),
+            created_at: 0,
+            model_type: "candle_mlp_horizon".to_string(),
+            features: feature_names(),
+            target: "close".to_string(),
+            prediction_days,
+            accuracy,
+            training_start_date: None,
+            training_end_date: None,
+            training_samples: None,
+            test_samples: None,
+            mae: None,
+            rmse: None,
+            model_status: Default::default(),
+            last_trained_at: None,
+            training_params: None,
+            feature_set_version: None,
+        }
+    }
+
+    #[test]
+    fn test_loss_plateau_detection() {
+        let labels = [1.0, -1.0, 1.0, -1.0];
+        // 损失停在标签方差附近：欠拟合
+        assert!(loss_plateaued_early(
+            &[1.2, 1.0, 1.0, 1.0, 1.0, 1.0, 0.995, 0.99],
+            &labels
+        ));
+        // 损失持续下降：正常
+        assert!(!loss_plateaued_early(
+            &[1.2, 1.0, 0.9, 0.8, 0.6, 0.5, 0.4, 0.3],
+            &labels
+        ));
+    }
+
+    #[test]
+    fn test_horizon_suggestion_targets_weak_multi_day_models() {
+        let suggestions = OptimizationEngine::horizon_suggestions(&[
+            model(1, 0.45),
+            model(5, 0.48),
+            model(3, 0.55),
+        ]);
+        assert_eq!(suggestions.len(), 1);
+        assert_eq!(suggestions[0].priority, 1);
+        assert!(suggestions[0].suggestion.contains("模型5"));
+        assert!(!suggestions[0].suggestion.contains("模型1"));
+        assert!(!suggestions[0].suggestion.contains("模型3"));
+    }
+}
@@ -0,0 +1,365 @@
+//! 线性分位数回归
+//!
+//! 以 pinball 损失 ρτ(r) = max(τ·r, (τ−1)·r) 拟合特征 → 周期收益率的条件分位数，
+//! 次梯度下降求解。集成预测用 τ=0.5（中位数）作点估计，τ=0.1 / 0.9 给出
+//! 由数据决定的 80% 预测区间，替代按已实现波动率推算的区间带。
+
+use serde::{Deserialize, Serialize};
+
+use super::features::{DatedSample, FEATURE_DIM};
+use crate::prediction::types::Prediction;
+
+/// 区间下沿 / 中位数 / 上沿对应的分位数
+pub const LOWER_QUANTILE: f64 = 0.1;
+pub const MEDIAN_QUANTILE: f64 = 0.5;
+pub const UPPER_QUANTILE: f64 = 0.9;
+
+/// 次梯度下降迭代次数
+const QUANTILE_ITERATIONS: usize = 500;
+/// 初始步长（乘以标签标准差，步长按 1/√t 衰减）
+const QUANTILE_LEARNING_RATE: f64 = 0.5;
+/// 权重 L2 正则系数，仅用于抑制共线特征上的漂移
+const QUANTILE_L2: f64 = 1e-4;
+
+/// 线性分位数回归模型：q̂τ = intercept + Σ wᵢ·xᵢ（系数已换算回原始特征尺度）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuantileRegressionModel {
+    pub quantile: f64,
+    pub weights: Vec<f64>,
+    pub intercept: f64,
+}
+
+impl QuantileRegressionModel {
+    pub fn predict(&self, features: &[f64]) -> f64 {
+        self.intercept
+            + self
+                .weights
+                .iter()
+                .zip(features)
+                .map(|(w, x)| w * x)
+                .sum::<f64>()
+    }
+}
+
+/// 单个残差（实际 − 预测）的 pinball 损失
+pub fn pinball_loss(quantile: f64, residual: f64) -> f64 {
+    if residual > 0.0 {
+        quantile * residual
+    } else {
+        (quantile - 1.0) * residual
+    }
+}
+
+/// 样本经验分位数（线性插值前的下分位）
+fn empirical_quantile(values: &[f64], quantile: f64) -> f64 {
+    if values.is_empty() {
+        return 0.0;
+    }
+    let mut sorted = values.to_vec();
+    sorted.sort_by(f64::total_cmp);
+    let idx = ((quantile * sorted.len() as f64) as usize).min(sorted.len() - 1);
+    sorted[idx]
+}
+
+/// 以 pinball 损失拟合 `quantile` 分位的线性模型。
+///
+/// 特征先标准化，截距从标签的经验分位数起步；次梯度下降不保证单调下降，
+/// 返回迭代过程中训练损失最小的一组系数。样本为空时返回常数 0 模型。
+pub fn fit_quantile_regression(
+    features: &[Vec<f64>],
+    targets: &[f64],
+    quantile: f64,
+) -> QuantileRegressionModel {
+    let n = features.len().min(targets.len());
+    let dim = features.first().map_or(0, Vec::len);
+    if n == 0 {
+        return QuantileRegressionModel {
+            quantile,
+            weights: vec![0.0; dim],
+            intercept: 0.0,
+        };
+    }
+    let targets = &targets[..n];
+    let nf = n as f64;
+
+    let means: Vec<f64> = (0..dim)
+        .map(|j| features[..n].iter().map(|row| row[j]).sum::<f64>() / nf)
+        .collect();
+    let stds: Vec<f64> = (0..dim)
+        .map(|j| {
+            let var = features[..n]
+                .iter()
+                .map(|row| (row[j] - means[j]).powi(2))
+                .sum::<f64>()
+                / nf;
+            if var.sqrt() > 1e-12 {
+                var.sqrt()
+            } else {
+                1.0
+            }
+        })
+        .collect();
+    let standardized: Vec<Vec<f64>> = features[..n]
+        .iter()
+        .map(|row| (0..dim).map(|j| (row[j] - means[j]) / stds[j]).collect())
+        .collect();
+    let target_mean = targets.iter().sum::<f64>() / nf;
+    let target_scale = (targets.iter().map(|y| (y - target_mean).powi(2)).sum::<f64>() / nf)
+        .sqrt()
+        .max(1e-8);
+
+    let mut intercept = empirical_quantile(targets, quantile);
+    let mut weights = vec![0.0; dim];
+    let mut best = (f64::INFINITY, intercept, weights.clone());
+    for iteration in 0..QUANTILE_ITERATIONS {
+        let mut loss = 0.0;
+        let mut grad_intercept = 0.0;
+        let mut grad_weights = vec![0.0; dim];
+        for (row, &y) in standardized.iter().zip(targets) {
+            let residual =
+                y - intercept - weights.iter().zip(row).map(|(w, x)| w * x).sum::<f64>();
+            loss += pinball_loss(quantile, residual);
+            let grad = if residual > 0.0 { -quantile } else { 1.0 - quantile };
+            grad_intercept += grad;
+            for (g, x) in grad_weights.iter_mut().zip(row) {
+                *g += grad * x;
+            }
+        }
+        if loss / nf < best.0 {
+            best = (loss / nf, intercept, weights.clone());
+        }
+
+        let step = QUANTILE_LEARNING_RATE * target_scale / ((iteration + 1) as f64).sqrt();
+        intercept -= step * grad_intercept / nf;
+        for (w, g) in weights.iter_mut().zip(&grad_weights) {
+            *w -= step * (g / nf + QUANTILE_L2 * *w);
+        }
+    }
+
+    // 换算回原始特征尺度：w' = w/σ，b' = b − Σ w·μ/σ
+    let (_, intercept, weights) = best;
+    let original: Vec<f64> = weights.iter().zip(&stds).map(|(w, s)| w / s).collect();
+    let intercept = intercept - original.iter().zip(&means).map(|(w, m)| w * m).sum::<f64>();
+    QuantileRegressionModel {
+        quantile,
+        weights: original,
+        intercept,
+    }
+}
+
+/// 一次分位数预测（周期累计收益率，小数）
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct QuantileForecast {
+    pub lower: f64,
+    pub median: f64,
+    pub upper: f64,
+}
+
+/// q0.1 / q0.5 / q0.9 三个分位数模型
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuantileBands {
+    pub lower: QuantileRegressionModel,
+    pub median: QuantileRegressionModel,
+    pub upper: QuantileRegressionModel,
+}
+
+impl QuantileBands {
+    pub fn fit(features: &[Vec<f64>], targets: &[f64]) -> Self {
+        Self {
+            lower: fit_quantile_regression(features, targets, LOWER_QUANTILE),
+            median: fit_quantile_regression(features, targets, MEDIAN_QUANTILE),
+            upper: fit_quantile_regression(features, targets, UPPER_QUANTILE),
+        }
+    }
+
+    /// 三个分位数独立拟合可能交叉，排序后保证 lower ≤ median ≤ upper
+    pub fn predict(&self, features: &[f64]) -> QuantileForecast {
+        let mut values = [
+            self.lower.predict(features),
+            self.median.predict(features),
+            self.upper.predict(features),
+        ];
+        values.sort_by(f64::total_cmp);
+        QuantileForecast {
+            lower: values[0],
+            median: values[1],
+            upper: values[2],
+        }
+    }
+}
+
+/// 样本特征转为分位数回归的输入
+pub fn sample_features(samples: &[DatedSample]) -> Vec<Vec<f64>> {
+    samples
+        .iter()
+        .map(|s| s.features.iter().map(|&x| x as f64).collect())
+        .collect()
+}
+
+/// 扁平特征向量（前 FEATURE_DIM 维）转为分位数回归的输入
+pub fn to_quantile_input(features: &[f32]) -> Vec<f64> {
+    features.iter().take(FEATURE_DIM).map(|&x| x as f64).collect()
+}
+
+/// 用分位数区间覆盖 `prediction_low` / `prediction_high`。
+///
+/// 区间宽度取 horizon 日分位数相对中位数的偏移，第 d 日按 √(d/horizon) 缩放，
+/// 并以各日点预测价为中心（与点预测的组合方式无关）。
+pub fn apply_quantile_bands(predictions: &mut [Prediction], forecast: &QuantileForecast) {
+    let horizon = predictions.len().max(1) as f64;
+    let down = forecast.lower - forecast.median;
+    let up = forecast.upper - forecast.median;
+    for (idx, prediction) in predictions.iter_mut().enumerate() {
+        let scale = ((idx + 1) as f64 / horizon).sqrt();
+        prediction.prediction_low = prediction.predicted_price * (1.0 + down * scale);
+        prediction.prediction_high = prediction.predicted_price * (1.0 + up * scale);
+    }
+}
+
+/// 分位数模型训练报告
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuantileTrainingReport {
+    pub stock_code: String,
+    pub prediction_days: usize,
+    pub train_samples: usize,
+    pub test_samples: usize,
+    /// 留出段各分位数的平均 pinball 损失（按 lower / median / upper 顺序）
+    pub test_pinball_loss: [f64; 3],
+    /// 留出段真实收益落入 [q0.1, q0.9] 的比例，名义值 0.8
+    pub test_interval_coverage: f64,
+    /// 全部样本重新拟合后的模型
+    pub bands: QuantileBands,
+    /// 最新交易日的预测（周期累计收益率，小数）
+    pub latest_forecast: Option<QuantileForecast>,
+}
+
+/// 在时间序列留出段上检验分位数模型，再用全部样本重新拟合。
+///
+/// 训练段末尾留出 `horizon` 个样本的间隔，避免标签窗口与留出段重叠。
+pub fn train_quantile_bands(
+    stock_code: &str,
+    samples: &[DatedSample],
+    latest: Option<&[f32]>,
+    horizon: usize,
+    test_ratio: f64,
+) -> Option<QuantileTrainingReport> {
+    let test_len = ((samples.len() as f64) * test_ratio) as usize;
+    let train_end = samples.len().saturating_sub(test_len + horizon);
+    if test_len == 0 || train_end <= FEATURE_DIM {
+        return None;
+    }
+    let train = &samples[..train_end];
+    let test = &samples[samples.len() - test_len..];
+    let holdout = QuantileBands::fit(
+        &sample_features(train),
+        &train.iter().map(|s| s.fwd_return).collect::<Vec<_>>(),
+    );
+
+    let mut losses = [0.0; 3];
+    let mut covered = 0;
+    for (sample, features) in test.iter().zip(sample_features(test)) {
+        let forecast = holdout.predict(&features);
+        let y = sample.fwd_return;
+        losses[0] += pinball_loss(LOWER_QUANTILE, y - forecast.lower);
+        losses[1] += pinball_loss(MEDIAN_QUANTILE, y - forecast.median);
+        losses[2] += pinball_loss(UPPER_QUANTILE, y - forecast.upper);
+        if (forecast.lower..=forecast.upper).contains(&y) {
+            covered += 1;
+        }
+    }
+    for loss in &mut losses {
+        *loss /= test_len as f64;
+    }
+
+    let bands = QuantileBands::fit(
+        &sample_features(samples),
+        &samples.iter().map(|s| s.fwd_return).collect::<Vec<_>>(),
+    );
+    let latest_forecast = latest.map(|features| bands.predict(&to_quantile_input(features)));
+    Some(QuantileTrainingReport {
+        stock_code: stock_code.to_string(),
+        prediction_days: horizon,
+        train_samples: train.len(),
+        test_samples: test_len,
+        test_pinball_loss: losses,
+        test_interval_coverage: covered as f64 / test_len as f64,
+        bands,
+        latest_forecast,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// y = 0.02·x₁ + U(−0.01, 0.01)，x₂ 为无关特征
+    fn uniform_noise_data() -> (Vec<Vec<f64>>, Vec<f64>) {
+        (0..300)
+            .map(|i| {
+                let x1 = ((i * 7) % 50) as f64 / 50.0 * 2.0 - 1.0;
+                let x2 = ((i * 13) % 30) as f64 / 30.0;
+                let noise = ((i * 37) % 100) as f64 / 100.0 * 0.02 - 0.01;
+                (vec![x1, x2], 0.02 * x1 + noise)
+            })
+            .unzip()
+    }
+
+    #[test]
+    fn test_recovers_conditional_quantiles() {
+        let (features, targets) = uniform_noise_data();
+        let bands = QuantileBands::fit(&features, &targets);
+
+        // 噪声均匀分布于 ±0.01，真实 q0.1 / q0.9 截距为 ∓0.008
+        assert!((bands.lower.intercept + 0.008).abs() < 1e-3);
+        assert!(bands.median.intercept.abs() < 1e-3);
+        assert!((bands.upper.intercept - 0.008).abs() < 1e-3);
+        for model in [&bands.lower, &bands.median, &bands.upper] {
+            assert!((model.weights[0] - 0.02).abs() < 1e-3);
+            assert!(model.weights[1].abs() < 1e-3);
+        }
+
+        let below = |model: &QuantileRegressionModel| {
+            features
+                .iter()
+                .zip(&targets)
+                .filter(|(x, y)| **y <= model.predict(x))
+                .count() as f64
+                / targets.len() as f64
+        };
+        assert!((below(&bands.lower) - 0.1).abs() < 0.03);
+        assert!((below(&bands.upper) - 0.9).abs() < 0.03);
+    }
+
+    #[test]
+    fn test_pinball_loss_is_asymmetric() {
+        assert!((pinball_loss(0.9, 1.0) - 0.9).abs() < 1e-12);
+        assert!((pinball_loss(0.9, -1.0) - 0.1).abs() < 1e-12);
+        assert_eq!(pinball_loss(0.5, 0.0), 0.0);
+    }
+
+    #[test]
+    fn test_forecast_is_ordered_and_empty_input_is_constant() {
+        let crossing = QuantileBands {
+            lower: QuantileRegressionModel {
+                quantile: LOWER_QUANTILE,
+                weights: vec![1.0],
+                intercept: 0.0,
+            },
+            median: QuantileRegressionModel {
+                quantile: MEDIAN_QUANTILE,
+                weights: vec![0.0],
+                intercept: 0.0,
+            },
+            upper: QuantileRegressionModel {
+                quantile: UPPER_QUANTILE,
+                weights: vec![-1.0],
+                intercept: 0.0,
+            },
+        };
+        let forecast = crossing.predict(&[0.5]);
+        assert!(forecast.lower <= forecast.median && forecast.median <= forecast.upper);
+
+        let model = fit_quantile_regression(&[], &[], MEDIAN_QUANTILE);
+        assert_eq!(model.predict(&[]), 0.0);
+    }
+}
@@ -8,38 +8,58 @@ use crate::db::{
     models::HistoricalData,
     repository::{get_historical_data, get_recent_historical_data},
 };
-use crate::prediction::model::features::{build_dataset_for_horizon, build_samples, feature_names};
+use crate::prediction::model::features::{
+    build_dataset_for_horizon, build_samples, feature_names, mask_unselected_features,
+    model_feature_columns, select_feature_columns, FEATURE_SET_VERSION,
+};
 use crate::prediction::model::management::{
     generate_model_id, get_current_timestamp, get_model_file_path, save_model_metadata,
 };
 use crate::prediction::model::network::train_and_save_with_gap;
 use crate::prediction::model::HORIZON_AWARE_MODEL_TYPE;
-use crate::prediction::types::{ModelInfo, TrainingRequest, TrainingResult};
+use crate::prediction::types::{
+    ModelInfo, ModelStatus, TrainingParams, TrainingRequest, TrainingResult,
+};
+use crate::utils::progress::{NoProgress, ProgressSink};
 use chrono::NaiveDate;
+use log::info;
 
 const DEFAULT_TRAINING_BARS: usize = 800;
+/// 训练轮数下限：全批量训练每轮只更新一次参数，过少的轮数下网络几乎停留在初始化状态。
+/// 请求或元数据中的轮数低于该值时按下限训练，元数据记录实际使用的轮数
+const MIN_TRAINING_EPOCHS: usize = 50;
 const LEGACY_CANDLE_MLP_MODEL_TYPE: &str = "candle_mlp";
 
 /// 训练股票预测模型（真实 candle MLP）
 pub async fn train_model(request: TrainingRequest) -> Result<TrainingResult, String> {
-    println!("🚀 开始训练模型: {}", request.model_name);
-    println!("   股票代码: {}", request.stock_code);
+    train_model_with_progress(request, &NoProgress).await
+}
+
+/// 训练股票预测模型，逐轮上报进度；取消时不保存模型
+pub async fn train_model_with_progress(
+    request: TrainingRequest,
+    progress: &dyn ProgressSink,
+) -> Result<TrainingResult, String> {
+    info!("🚀 开始训练模型: {}", request.model_name);
+    info!("   股票代码: {}", request.stock_code);
     validate_training_model_type(&request.model_type)?;
 
     // 加载历史数据
     let pool = create_temp_pool().await?;
     let historical = load_training_history(&request, &pool).await?;
 
-    if historical.len() < 80 {
+    if historical.len() < 100 {
         return Err(format!(
-            "历史数据不足（{}），训练至少需要 80 个交易日",
+            "历史数据不足（{}），训练至少需要 100 个交易日",
             historical.len()
         ));
     }
 
-    // 构造数据集
+    // 构造数据集，未选中的特征列置零
+    let feature_columns = select_feature_columns(&request.features)?;
     let prediction_days = request.prediction_days.max(1);
-    let (features, labels, n) = build_dataset_for_horizon(&historical, prediction_days);
+    let (mut features, labels, n) = build_dataset_for_horizon(&historical, prediction_days);
+    mask_unselected_features(&mut features, &feature_columns);
     if n < 40 {
         return Err(format!("有效样本不足（{n}），无法训练"));
     }
@@ -52,26 +72,29 @@ pub async fn train_model(request: TrainingRequest) -> Result<TrainingResult, Str
     } else {
         0.8
     };
+    let epochs = request.epochs.max(MIN_TRAINING_EPOCHS);
     let outcome = train_and_save_with_gap(
         &features,
         &labels,
         n,
-        request.epochs.max(50),
+        epochs,
         request.learning_rate,
         split,
         prediction_days,
         &model_path,
+        progress,
     )?;
     let (training_start_date, training_end_date) =
         training_sample_date_range(&historical, prediction_days, outcome.train_samples);
 
+    let created_at = get_current_timestamp();
     let metadata = ModelInfo {
         id: model_id.clone(),
         name: request.model_name,
         stock_code: request.stock_code,
-        created_at: get_current_timestamp(),
+        created_at,
         model_type: HORIZON_AWARE_MODEL_TYPE.to_string(),
-        features: feature_names(),
+        features: selected_feature_names(&feature_columns),
         target: request.target,
         prediction_days,
         accuracy: outcome.direction_accuracy,
@@ -81,10 +104,18 @@ pub async fn train_model(request: TrainingRequest) -> Result<TrainingResult, Str
         test_samples: Some(outcome.test_samples),
         mae: Some(outcome.mae),
         rmse: Some(outcome.rmse),
+        model_status: ModelStatus::Candidate,
+        last_trained_at: Some(created_at),
+        training_params: Some(TrainingParams {
+            epochs,
+            learning_rate: request.learning_rate,
+            train_test_split: split,
+        }),
+        feature_set_version: Some(FEATURE_SET_VERSION),
     };
     save_model_metadata(&metadata)?;
 
-    println!(
+    info!(
         "✅ 训练完成：方向准确率 {:.1}%（测试样本 {}，MAE {:.3}）",
         outcome.direction_accuracy * 100.0,
         outcome.test_samples,
@@ -100,13 +131,13 @@ pub async fn train_model(request: TrainingRequest) -> Result<TrainingResult, Str
     })
 }
 
-/// 重新训练模型：按新的超参数对同一标的重新训练并覆盖权重
+/// 重新训练模型：按新的超参数对同一标的重新训练并覆盖权重，返回更新后的元数据
 pub async fn retrain_model(
     model_id: String,
     epochs: u32,
     _batch_size: u32,
     learning_rate: f64,
-) -> Result<(), String> {
+) -> Result<ModelInfo, String> {
     use crate::prediction::model::management::load_model_metadata;
 
     let metadata = load_model_metadata(&model_id)?;
@@ -121,21 +152,29 @@ pub async fn retrain_model(
     } else {
         1
     };
-    let (features, labels, n) = build_dataset_for_horizon(&historical, training_horizon);
+    let (mut features, labels, n) = build_dataset_for_horizon(&historical, training_horizon);
+    mask_unselected_features(&mut features, &model_feature_columns(&metadata.features));
     if n < 40 {
         return Err(format!("有效样本不足（{n}），无法重训练"));
     }
 
+    let split = metadata
+        .training_params
+        .map(|params| params.train_test_split)
+        .filter(|split| *split > 0.0)
+        .unwrap_or(0.8);
     let model_path = get_model_file_path(&model_id);
+    let epochs = (epochs as usize).max(MIN_TRAINING_EPOCHS);
     let outcome = train_and_save_with_gap(
         &features,
         &labels,
         n,
-        (epochs as usize).max(50),
+        epochs,
         learning_rate,
-        0.8,
+        split,
         training_horizon,
         &model_path,
+        &NoProgress,
     )?;
     let (training_start_date, training_end_date) =
         training_sample_date_range(&historical, training_horizon, outcome.train_samples);
@@ -149,13 +188,31 @@ pub async fn retrain_model(
     updated.test_samples = Some(outcome.test_samples);
     updated.mae = Some(outcome.mae);
     updated.rmse = Some(outcome.rmse);
+    updated.last_trained_at = Some(get_current_timestamp());
+    updated.training_params = Some(TrainingParams {
+        epochs,
+        learning_rate,
+        train_test_split: split,
+    });
+    // 按当前特征集重训练，旧特征集模型随之迁移
+    updated.feature_set_version = Some(FEATURE_SET_VERSION);
     save_model_metadata(&updated)?;
 
-    println!(
+    info!(
         "🔄 重训练完成：方向准确率 {:.1}%",
         outcome.direction_accuracy * 100.0
     );
-    Ok(())
+    Ok(updated)
+}
+
+/// 特征列 → 特征名（按 [`feature_names`] 顺序记录到模型元数据）
+fn selected_feature_names(columns: &[usize]) -> Vec<String> {
+    feature_names()
+        .into_iter()
+        .enumerate()
+        .filter(|(j, _)| columns.contains(j))
+        .map(|(_, name)| name)
+        .collect()
 }
 
 fn training_sample_date_range(
@@ -272,12 +329,12 @@ mod tests {
 
     #[test]
     fn test_training_sample_date_range_uses_actual_train_samples() {
-        let history = make_history(60);
+        let history = make_history(100);
 
         let (start, end) = training_sample_date_range(&history, 5, 12);
 
-        assert_eq!(start.as_deref(), Some("2025-01-21"));
-        assert_eq!(end.as_deref(), Some("2025-02-01"));
+        assert_eq!(start.as_deref(), Some("2025-03-01"));
+        assert_eq!(end.as_deref(), Some("2025-03-12"));
     }
 
     #[test]
@@ -313,6 +370,14 @@ mod tests {
         assert!(validate_training_model_type(LEGACY_CANDLE_MLP_MODEL_TYPE).is_ok());
     }
 
+    #[test]
+    fn test_selected_feature_names_follow_model_order() {
+        let columns =
+            select_feature_columns(&["rsi14".to_string(), "ret_1d".to_string()]).unwrap();
+        assert_eq!(selected_feature_names(&columns), vec!["ret_1d", "rsi14"]);
+        assert_eq!(selected_feature_names(&select_feature_columns(&[]).unwrap()), feature_names());
+    }
+
     #[test]
     fn test_validate_training_model_type_rejects_unknown_values() {
         assert!(validate_training_model_type("candle_lstm").is_err());
@@ -7,7 +7,12 @@
 //! 4. 权重平滑更新避免剧烈波动
 
 use serde::{Deserialize, Serialize};
+use crate::db::models::FactorAccuracy;
+use crate::db::repository;
+use crate::error::AppError;
 use crate::prediction::analysis::market_regime::MarketRegime;
+use crate::prediction::strategy::multi_factor::MultiFactorScore;
+use sqlx::SqlitePool;
 
 /// 因子权重配置
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -483,6 +488,131 @@ pub fn blend_weights(
     blended
 }
 
+/// 多因子评分的因子名称（顺序与 [`AdaptiveWeightOptimizer::get_current_weights`] 返回一致）
+pub const OPTIMIZER_FACTOR_NAMES: [&str; 7] = [
+    "trend_factor",
+    "volume_factor",
+    "momentum_factor",
+    "pattern_factor",
+    "support_resistance_factor",
+    "sentiment_factor",
+    "volatility_factor",
+];
+
+/// 准确率 EMA 的平滑系数（约等于最近 20 次预测的窗口）
+const ACCURACY_EMA_ALPHA: f64 = 0.1;
+/// 因子得分（0-100）偏离 50 不足该值时视为无方向，不计入准确率
+const FACTOR_DIRECTION_DEADBAND: f64 = 5.0;
+/// 未学习时的中性准确率
+const NEUTRAL_ACCURACY: f64 = 0.5;
+/// 单因子权重下限，避免某因子被完全淘汰
+const OPTIMIZER_MIN_WEIGHT: f64 = 0.05;
+
+/// 个股自适应权重优化器：按各因子近期方向判断的准确率 EMA 分配权重，并按股票持久化
+#[derive(Debug, Clone)]
+pub struct AdaptiveWeightOptimizer {
+    pub stock_code: String,
+    /// 各因子准确率 EMA（顺序同 [`OPTIMIZER_FACTOR_NAMES`]）
+    accuracies: [f64; 7],
+    sample_counts: [i64; 7],
+}
+
+impl AdaptiveWeightOptimizer {
+    pub fn new(stock_code: &str) -> Self {
+        Self {
+            stock_code: stock_code.to_string(),
+            accuracies: [NEUTRAL_ACCURACY; 7],
+            sample_counts: [0; 7],
+        }
+    }
+
+    /// 从数据库加载某股票已学习的因子准确率，无记录时为中性
+    pub async fn load(stock_code: &str, pool: &SqlitePool) -> Result<Self, AppError> {
+        let mut optimizer = Self::new(stock_code);
+        for row in repository::get_factor_accuracies(stock_code, pool).await? {
+            if let Some(index) = factor_index(&row.factor_name) {
+                optimizer.accuracies[index] = row.accuracy.clamp(0.0, 1.0);
+                optimizer.sample_counts[index] = row.sample_count;
+            }
+        }
+        Ok(optimizer)
+    }
+
+    /// 持久化当前准确率
+    pub async fn save(&self, pool: &SqlitePool) -> Result<(), AppError> {
+        let rows: Vec<FactorAccuracy> = OPTIMIZER_FACTOR_NAMES
+            .iter()
+            .enumerate()
+            .map(|(i, name)| FactorAccuracy {
+                symbol: self.stock_code.clone(),
+                factor_name: name.to_string(),
+                accuracy: self.accuracies[i],
+                sample_count: self.sample_counts[i],
+            })
+            .collect();
+        repository::upsert_factor_accuracies(pool, &rows).await
+    }
+
+    /// 记录一次因子方向判断的结果；未知因子名忽略
+    pub fn update(&mut self, factor_name: &str, was_correct: bool) {
+        let Some(index) = factor_index(factor_name) else {
+            return;
+        };
+        let outcome = if was_correct { 1.0 } else { 0.0 };
+        self.accuracies[index] =
+            ACCURACY_EMA_ALPHA * outcome + (1.0 - ACCURACY_EMA_ALPHA) * self.accuracies[index];
+        self.sample_counts[index] += 1;
+    }
+
+    /// 以实际涨跌幅检验一次多因子评分中各因子的方向判断
+    pub fn record_score_outcome(&mut self, score: &MultiFactorScore, actual_change: f64) {
+        if actual_change == 0.0 {
+            return;
+        }
+        let factor_scores = [
+            score.trend_score,
+            score.volume_price_score,
+            score.momentum_score,
+            score.pattern_score,
+            score.support_resistance_score,
+            score.sentiment_score,
+            score.volatility_score,
+        ];
+        for (name, factor_score) in OPTIMIZER_FACTOR_NAMES.iter().zip(factor_scores) {
+            let bias = factor_score - 50.0;
+            if bias.abs() < FACTOR_DIRECTION_DEADBAND {
+                continue;
+            }
+            self.update(name, bias.signum() == actual_change.signum());
+        }
+    }
+
+    /// 是否已有学习样本
+    pub fn has_samples(&self) -> bool {
+        self.sample_counts.iter().any(|&n| n > 0)
+    }
+
+    /// 当前权重（和为 1）：准确率高于 50% 的因子加权、低于 50% 的降权
+    pub fn get_current_weights(&self) -> Vec<f64> {
+        let raw: Vec<f64> = self
+            .accuracies
+            .iter()
+            .map(|accuracy| (0.5 + accuracy).max(OPTIMIZER_MIN_WEIGHT))
+            .collect();
+        let total: f64 = raw.iter().sum();
+        raw.iter().map(|w| w / total).collect()
+    }
+
+    /// 各因子已累计的样本数
+    pub fn sample_count(&self, factor_name: &str) -> i64 {
+        factor_index(factor_name).map_or(0, |i| self.sample_counts[i])
+    }
+}
+
+fn factor_index(factor_name: &str) -> Option<usize> {
+    OPTIMIZER_FACTOR_NAMES.iter().position(|name| *name == factor_name)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -520,5 +650,23 @@ mod tests {
         // 在强趋势高波动环境下，趋势权重应该较高
         assert!(weights.trend > 0.2);
     }
-}
 
+    #[test]
+    fn test_optimizer_shifts_weight_to_accurate_factor() {
+        let mut optimizer = AdaptiveWeightOptimizer::new("600000");
+        let neutral = optimizer.get_current_weights();
+        assert!(neutral.iter().all(|w| (w - 1.0 / 7.0).abs() < 1e-9));
+
+        for _ in 0..20 {
+            optimizer.update("trend_factor", true);
+            optimizer.update("sentiment_factor", false);
+        }
+        optimizer.update("unknown_factor", true);
+
+        let weights = optimizer.get_current_weights();
+        assert!((weights.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+        assert!(weights[0] > neutral[0]);
+        assert!(weights[5] < neutral[5]);
+        assert_eq!(optimizer.sample_count("trend_factor"), 20);
+    }
+}
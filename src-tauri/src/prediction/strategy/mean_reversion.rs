@@ -0,0 +1,148 @@
+//! 均值回归机会检测
+//!
+//! 价格偏离均线超过给定倍数标准差（类似布林带极值）时触发，
+//! 并以偏离序列的 AR(1) 半衰期估计回归所需交易日数。
+
+use serde::{Deserialize, Serialize};
+
+/// 半衰期估计的上限（交易日），超过视为不具均值回归特性
+const MAX_HALF_LIFE_DAYS: f64 = 120.0;
+
+/// 均值回归信号
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MeanReversionSignal {
+    /// 偏离是否超过阈值
+    pub triggered: bool,
+    /// "看涨回归"（低于均线）/ "看跌回归"（高于均线）/ "无"
+    pub direction: String,
+    /// 回归目标价（均线）
+    pub reversion_target: f64,
+    /// 当前价偏离均线的标准差倍数（正为高于均线）
+    pub distance_std: f64,
+    /// 按历史半衰期估计的回归天数，无法估计时为 0
+    pub estimated_reversion_days: usize,
+    /// 信号强度 (0-1)
+    pub signal_strength: f64,
+}
+
+/// 检测均值回归机会：|价格 - MA| > `std_threshold` × 同窗口标准差时触发
+pub fn detect_mean_reversion_opportunity(
+    prices: &[f64],
+    ma_period: usize,
+    std_threshold: f64,
+) -> MeanReversionSignal {
+    if ma_period < 2 || prices.len() < ma_period {
+        return MeanReversionSignal {
+            direction: "无".to_string(),
+            ..Default::default()
+        };
+    }
+
+    let (ma, std) = window_mean_std(&prices[prices.len() - ma_period..]);
+    let current = *prices.last().unwrap();
+    let distance_std = if std > 0.0 { (current - ma) / std } else { 0.0 };
+    let triggered = std_threshold > 0.0 && distance_std.abs() > std_threshold;
+
+    // 偏离序列：每个时点相对其滚动均线的偏离
+    let deviations: Vec<f64> = (ma_period..=prices.len())
+        .map(|end| prices[end - 1] - window_mean_std(&prices[end - ma_period..end]).0)
+        .collect();
+    let estimated_reversion_days = estimate_half_life(&deviations)
+        .map(|half_life| half_life.round().max(1.0) as usize)
+        .unwrap_or(0);
+
+    let direction = match (triggered, distance_std > 0.0) {
+        (false, _) => "无",
+        (true, true) => "看跌回归",
+        (true, false) => "看涨回归",
+    };
+    // 超出阈值越多越强；无法估计半衰期（不具回归特性）时减半
+    let signal_strength = if triggered {
+        let excess = ((distance_std.abs() - std_threshold) / std_threshold).min(1.0);
+        let strength = 0.5 + 0.5 * excess;
+        if estimated_reversion_days == 0 { strength * 0.5 } else { strength }
+    } else {
+        0.0
+    };
+
+    MeanReversionSignal {
+        triggered,
+        direction: direction.to_string(),
+        reversion_target: ma,
+        distance_std,
+        estimated_reversion_days,
+        signal_strength,
+    }
+}
+
+/// 以 AR(1) 回归 Δx_t = λ·x_{t-1} + c 估计均值回归半衰期 = -ln2 / λ。
+///
+/// λ ≥ 0（不回归）或半衰期超过上限时返回 None。
+pub fn estimate_half_life(series: &[f64]) -> Option<f64> {
+    if series.len() < 10 {
+        return None;
+    }
+    let lagged = &series[..series.len() - 1];
+    let deltas: Vec<f64> = series.windows(2).map(|w| w[1] - w[0]).collect();
+
+    let n = lagged.len() as f64;
+    let mean_x = lagged.iter().sum::<f64>() / n;
+    let mean_y = deltas.iter().sum::<f64>() / n;
+    let (mut cov, mut var) = (0.0, 0.0);
+    for (x, y) in lagged.iter().zip(&deltas) {
+        cov += (x - mean_x) * (y - mean_y);
+        var += (x - mean_x).powi(2);
+    }
+    if var <= 1e-12 {
+        return None;
+    }
+    let lambda = cov / var;
+    if lambda >= 0.0 {
+        return None;
+    }
+    let half_life = -std::f64::consts::LN_2 / lambda;
+    (half_life.is_finite() && half_life <= MAX_HALF_LIFE_DAYS).then_some(half_life)
+}
+
+fn window_mean_std(window: &[f64]) -> (f64, f64) {
+    let n = window.len() as f64;
+    let mean = window.iter().sum::<f64>() / n;
+    let variance = window.iter().map(|p| (p - mean).powi(2)).sum::<f64>() / n;
+    (mean, variance.sqrt())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_half_life_of_ar1_process() {
+        // x_t = 0.5 x_{t-1} + e_t，λ ≈ -0.5，半衰期 ≈ ln2 / 0.5 ≈ 1.39
+        let mut series = vec![0.0; 200];
+        for i in 1..series.len() {
+            series[i] = 0.5 * series[i - 1] + (i as f64 * 7.3).sin();
+        }
+        let half_life = estimate_half_life(&series).unwrap();
+        assert!(half_life > 0.8 && half_life < 2.5, "half_life = {half_life}");
+
+        let trending: Vec<f64> = (0..40).map(|i| (i * i) as f64).collect();
+        assert!(estimate_half_life(&trending).is_none());
+    }
+
+    #[test]
+    fn test_detect_mean_reversion_on_sharp_drop() {
+        let mut prices: Vec<f64> = (0..60).map(|i| 10.0 + if i % 2 == 0 { 0.1 } else { -0.1 }).collect();
+        prices.push(8.5);
+        let signal = detect_mean_reversion_opportunity(&prices, 20, 2.0);
+
+        assert!(signal.triggered);
+        assert_eq!(signal.direction, "看涨回归");
+        assert!(signal.distance_std < -2.0);
+        assert!(signal.reversion_target > 9.5);
+        assert!(signal.signal_strength > 0.0);
+
+        let calm = detect_mean_reversion_opportunity(&prices[..60], 20, 2.0);
+        assert!(!calm.triggered);
+        assert_eq!(calm.signal_strength, 0.0);
+    }
+}
@@ -7,10 +7,18 @@ pub mod multi_timeframe;
 pub mod professional_engine;
 pub mod price_model;
 pub mod adaptive_weights;
+pub mod mean_reversion;
+pub mod position_sizing;
+pub mod regime_conditional;
+pub mod opening_gap;
 
 pub use multi_factor::*;
 pub use multi_timeframe::*;
 pub use professional_engine::*;
 pub use price_model::*;
 pub use adaptive_weights::*;
+pub use mean_reversion::*;
+pub use position_sizing::*;
+pub use regime_conditional::*;
+pub use opening_gap::*;
 
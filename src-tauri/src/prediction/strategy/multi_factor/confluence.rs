@@ -0,0 +1,168 @@
+//! 独立信号共振检测
+//!
+//! 同源指标（如 MACD 与 RSI 都由收盘价动量推导）同时看多只算一次确认；
+//! 真正独立的信号（动量超卖 + 资金流入 + 价格位于支撑）同向时才构成共振。
+
+use crate::prediction::indicators::TradingSignal;
+use serde::{Deserialize, Serialize};
+
+/// 构成强共振所需的同向独立信号数
+const STRONG_CONFLUENCE_COUNT: usize = 3;
+
+/// 预定义的独立指标对：动量类（RSI/MACD/KDJ）之间相关，与量能（OBV）、价格结构（支撑阻力）相互独立
+const INDEPENDENT_PAIRS: [(&str, &str); 7] = [
+    ("rsi", "obv"),
+    ("rsi", "support_resistance"),
+    ("macd", "obv"),
+    ("macd", "support_resistance"),
+    ("kdj", "obv"),
+    ("kdj", "support_resistance"),
+    ("obv", "support_resistance"),
+];
+
+/// 单个指标的方向信号
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IndicatorSignal {
+    pub name: String,
+    /// 1 看多，-1 看空，0 无信号
+    pub direction: i8,
+    pub weight: f64,
+    /// 与本指标相互独立的指标名
+    pub is_independent_of: Vec<String>,
+}
+
+impl IndicatorSignal {
+    /// 按预定义独立关系构造信号
+    pub fn new(name: &str, direction: i8, weight: f64) -> Self {
+        let is_independent_of = INDEPENDENT_PAIRS
+            .iter()
+            .filter_map(|&(a, b)| match name {
+                n if n == a => Some(b.to_string()),
+                n if n == b => Some(a.to_string()),
+                _ => None,
+            })
+            .collect();
+        Self {
+            name: name.to_string(),
+            direction,
+            weight,
+            is_independent_of,
+        }
+    }
+
+    fn independent_of(&self, other: &IndicatorSignal) -> bool {
+        self.is_independent_of.contains(&other.name) || other.is_independent_of.contains(&self.name)
+    }
+}
+
+/// 共振分析结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfluenceAnalysis {
+    /// 两两独立的看多信号数
+    pub independent_bullish_count: usize,
+    /// 两两独立的看空信号数
+    pub independent_bearish_count: usize,
+    /// (独立看多权重 - 独立看空权重) / 全部信号权重，范围 -1 ~ 1
+    pub confluence_score: f64,
+    pub description: String,
+}
+
+impl ConfluenceAnalysis {
+    /// 至少 3 个独立信号同向且无独立反向信号时给出强烈买入/卖出
+    pub fn strong_signal(&self) -> Option<TradingSignal> {
+        match (self.independent_bullish_count, self.independent_bearish_count) {
+            (bull, 0) if bull >= STRONG_CONFLUENCE_COUNT => Some(TradingSignal::StrongBuy),
+            (0, bear) if bear >= STRONG_CONFLUENCE_COUNT => Some(TradingSignal::StrongSell),
+            _ => None,
+        }
+    }
+}
+
+/// 按权重从高到低贪心选出两两独立的同向信号
+fn independent_set(signals: &[IndicatorSignal], direction: i8) -> Vec<&IndicatorSignal> {
+    let mut candidates: Vec<&IndicatorSignal> =
+        signals.iter().filter(|s| s.direction == direction).collect();
+    candidates.sort_by(|a, b| b.weight.total_cmp(&a.weight));
+    let mut selected: Vec<&IndicatorSignal> = Vec::new();
+    for signal in candidates {
+        if selected.iter().all(|chosen| chosen.independent_of(signal)) {
+            selected.push(signal);
+        }
+    }
+    selected
+}
+
+/// 统计相互独立的同向信号，衡量多指标共振程度
+pub fn detect_signal_confluence(signals: &[IndicatorSignal]) -> ConfluenceAnalysis {
+    let bullish = independent_set(signals, 1);
+    let bearish = independent_set(signals, -1);
+    let total_weight: f64 = signals.iter().map(|s| s.weight).sum();
+    let weight_of = |set: &[&IndicatorSignal]| set.iter().map(|s| s.weight).sum::<f64>();
+    let confluence_score = if total_weight > 0.0 {
+        ((weight_of(&bullish) - weight_of(&bearish)) / total_weight).clamp(-1.0, 1.0)
+    } else {
+        0.0
+    };
+    let names = |set: &[&IndicatorSignal]| {
+        set.iter().map(|s| s.name.as_str()).collect::<Vec<_>>().join("、")
+    };
+
+    let description = match (bullish.len(), bearish.len()) {
+        (0, 0) => "无明确方向信号".to_string(),
+        (bull, 0) if bull >= STRONG_CONFLUENCE_COUNT => {
+            format!("{bull} 个独立信号共振看多（{}）", names(&bullish))
+        }
+        (0, bear) if bear >= STRONG_CONFLUENCE_COUNT => {
+            format!("{bear} 个独立信号共振看空（{}）", names(&bearish))
+        }
+        (bull, bear) if bull > 0 && bear > 0 => format!(
+            "独立信号分歧：看多 {}，看空 {}",
+            names(&bullish),
+            names(&bearish)
+        ),
+        (bull, _) if bull > 0 => format!("独立看多信号不足：{}", names(&bullish)),
+        _ => format!("独立看空信号不足：{}", names(&bearish)),
+    };
+
+    ConfluenceAnalysis {
+        independent_bullish_count: bullish.len(),
+        independent_bearish_count: bearish.len(),
+        confluence_score,
+        description,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_correlated_signals_count_once() {
+        // RSI 与 MACD 同源，只计一次
+        let signals = vec![
+            IndicatorSignal::new("rsi", 1, 1.0),
+            IndicatorSignal::new("macd", 1, 0.8),
+            IndicatorSignal::new("obv", 1, 0.8),
+        ];
+        let analysis = detect_signal_confluence(&signals);
+        assert_eq!(analysis.independent_bullish_count, 2);
+        assert!((analysis.confluence_score - 1.8 / 2.6).abs() < 1e-12);
+        assert!(analysis.strong_signal().is_none());
+
+        // RSI 超卖 + OBV 上升 + 价格在支撑：强烈买入
+        let mut signals = signals;
+        signals.push(IndicatorSignal::new("support_resistance", 1, 0.6));
+        let analysis = detect_signal_confluence(&signals);
+        assert_eq!(analysis.independent_bullish_count, 3);
+        assert_eq!(analysis.strong_signal(), Some(TradingSignal::StrongBuy));
+        assert!(analysis.description.contains("rsi、obv、support_resistance"));
+
+        // 出现独立反向信号时不再是强信号
+        signals[2].direction = -1;
+        let analysis = detect_signal_confluence(&signals);
+        assert_eq!(analysis.independent_bullish_count, 2);
+        assert_eq!(analysis.independent_bearish_count, 1);
+        assert!(analysis.strong_signal().is_none());
+        assert_eq!(detect_signal_confluence(&[]).confluence_score, 0.0);
+    }
+}
@@ -56,14 +56,11 @@ pub(super) fn calculate_volume_price_score_enhanced(
         _ => 0.5,
     };
 
-    // OBV趋势确认
-    let obv_confirmation: f64 = if indicators.obv_trend > 0.05 {
-        0.08
-    } else if indicators.obv_trend < -0.05 {
-        -0.08
-    } else {
-        0.0
-    };
+    // OBV趋势确认：按 OBV 偏离均线的百分比连续计分，偏离 ±20% 封顶 ±0.08
+    let obv_confirmation: f64 = (indicators.obv_strength / 20.0).clamp(-1.0, 1.0) * 0.08;
+
+    // Chaikin 吸筹/派发确认：归一化振荡器 ±2（约 2 倍日均量的资金净流）封顶 ±0.06
+    let chaikin_confirmation: f64 = (indicators.chaikin_oscillator / 2.0).clamp(-1.0, 1.0) * 0.06;
 
     // 量比确认：放量配合方向加分，缩量背离减分（系数见 config::weights::VOLUME_RATIO_IMPACT）
     let vr = indicators.volume_ratio;
@@ -101,7 +98,12 @@ pub(super) fn calculate_volume_price_score_enhanced(
         0.0
     };
 
-    (base_score + obv_confirmation + volume_ratio_adjustment + turnover_adjustment).clamp(0.0, 1.0)
+    (base_score
+        + obv_confirmation
+        + chaikin_confirmation
+        + volume_ratio_adjustment
+        + turnover_adjustment)
+        .clamp(0.0, 1.0)
 }
 
 /// 增强版动量评分（多指标综合）
@@ -140,10 +142,12 @@ pub(super) fn calculate_momentum_score_enhanced(indicators: &TechnicalIndicatorV
         macd_score -= 0.25;
     }
 
-    if indicators.macd_histogram > 0.0 {
-        macd_score += 0.1 + (indicators.macd_histogram * 50.0).min(0.15);
-    } else if indicators.macd_histogram < 0.0 {
-        macd_score -= 0.1 + (indicators.macd_histogram.abs() * 50.0).min(0.15);
+    // 有成交量数据时优先用成交量加权 MACD 柱：放量K线（通常对应机构资金）的价格变动权重更高
+    let histogram = indicators.vwmacd_histogram.unwrap_or(indicators.macd_histogram);
+    if histogram > 0.0 {
+        macd_score += 0.1 + (histogram * 50.0).min(0.15);
+    } else if histogram < 0.0 {
+        macd_score -= 0.1 + (histogram.abs() * 50.0).min(0.15);
     }
 
     macd_score = macd_score.clamp(0.0, 1.0);
@@ -175,10 +179,15 @@ pub(super) fn calculate_momentum_score_enhanced(indicators: &TechnicalIndicatorV
         score = weighted_score / weight_sum;
     }
 
+    // 行业轮动：所属行业跑赢市场时顺势加分，跑输时减分（每百分点 0.01，上限 ±0.08）
+    if let Some(strength) = indicators.sector_relative_strength {
+        score += (strength * 0.01).clamp(-0.08, 0.08);
+    }
+
     score.clamp(0.0, 1.0)
 }
 
-/// 增强版形态评分
+/// 增强版形态评分：各形态按 可靠度 × 确认度 加权，未经确认的形态不影响评分
 pub(super) fn calculate_pattern_score_enhanced(patterns: &[PatternRecognition]) -> f64 {
     if patterns.is_empty() {
         return 0.5;
@@ -188,7 +197,7 @@ pub(super) fn calculate_pattern_score_enhanced(patterns: &[PatternRecognition])
     let mut bearish_weight: f64 = 0.0;
 
     for pattern in patterns {
-        let weight = pattern.reliability;
+        let weight = pattern.reliability * pattern.confirmation_score;
         if pattern.is_bullish {
             bullish_weight += weight;
         } else {
@@ -240,7 +249,34 @@ pub(super) fn calculate_sr_score_enhanced(sr: &SupportResistance) -> f64 {
 }
 
 /// 增强版情绪评分
+///
+/// 有全市场恐慌/贪婪指数时以其反向计分替代个股超买超卖推断，否则回退到个股 KDJ/CCI/RSI。
 pub(super) fn calculate_sentiment_score_enhanced(indicators: &TechnicalIndicatorValues) -> f64 {
+    let mut score = match indicators.market_fear_greed {
+        // 0（极度恐慌）→ 0.8，100（极度贪婪）→ 0.2
+        Some(fear_greed) => 0.5 + (50.0 - fear_greed.clamp(0.0, 100.0)) / 50.0 * 0.3,
+        None => individual_sentiment_score(indicators),
+    };
+
+    // 市场宽度：普跌（涨跌比 < 0.4）为全市场恐慌，普涨（> 2.5）为全市场贪婪，同样反向计分
+    if let Some(ad_ratio) = indicators.market_ad_ratio {
+        if ad_ratio < 0.4 {
+            score += 0.06;
+        } else if ad_ratio > 2.5 {
+            score -= 0.06;
+        }
+    }
+
+    // 新闻情绪：个股消息面正向计分，满分 ±1 对应 ±0.08
+    if let Some(news) = indicators.news_sentiment {
+        score += news.clamp(-1.0, 1.0) * 0.08;
+    }
+
+    score.clamp(0.0_f64, 1.0_f64)
+}
+
+/// 个股超买超卖推断的情绪分（无市场情绪指数时使用）
+fn individual_sentiment_score(indicators: &TechnicalIndicatorValues) -> f64 {
     let mut score: f64 = 0.5;
 
     // KDJ超买超卖（反向操作逻辑）
@@ -269,14 +305,23 @@ pub(super) fn calculate_sentiment_score_enhanced(indicators: &TechnicalIndicator
         score -= 0.1;
     }
 
-    score.clamp(0.0_f64, 1.0_f64)
+    score
 }
 
 /// 增强版波动率评分
+///
+/// 高 Beta（> 1）个股按 Beta 放大波动率后评分（最多 2 倍），体现其对市场波动的放大效应；
+/// 低 Beta 不做折减，避免低估个股特有风险。
 pub(super) fn calculate_volatility_score_enhanced(
     volatility: f64,
     level: Option<&VolatilityLevel>,
+    beta: Option<f64>,
 ) -> f64 {
+    let volatility = match beta {
+        Some(beta) if beta > 1.0 => volatility * beta.min(2.0),
+        _ => volatility,
+    };
+
     // 基础评分
     let base: f64 = if volatility < 0.015 {
         0.75 // 极低波动
@@ -349,7 +394,7 @@ pub(super) fn calculate_sentiment_score(indicators: &TechnicalIndicatorValues) -
 
 #[allow(dead_code)]
 pub(super) fn calculate_volatility_score(volatility: f64) -> f64 {
-    calculate_volatility_score_enhanced(volatility, None)
+    calculate_volatility_score_enhanced(volatility, None, None)
 }
 
 #[cfg(test)]
@@ -400,4 +445,96 @@ mod tests {
         let s_none = calculate_volume_price_score_enhanced(&signal, &none);
         assert!(s_healthy >= s_none);
     }
+
+    #[test]
+    fn test_obv_strength_scores_continuously() {
+        let signal = up_signal();
+        let score_at = |obv_strength: f64| {
+            let indicators = TechnicalIndicatorValues {
+                obv_strength,
+                ..Default::default()
+            };
+            calculate_volume_price_score_enhanced(&signal, &indicators)
+        };
+        assert!(score_at(5.0) > score_at(0.0));
+        assert!(score_at(10.0) > score_at(5.0));
+        assert!((score_at(40.0) - score_at(20.0)).abs() < 1e-9, "偏离超过 20% 后封顶");
+        assert!(score_at(-10.0) < score_at(0.0));
+    }
+
+    #[test]
+    fn test_chaikin_oscillator_confirms_volume_score() {
+        let signal = up_signal();
+        let score_at = |chaikin_oscillator: f64| {
+            let indicators = TechnicalIndicatorValues {
+                chaikin_oscillator,
+                ..Default::default()
+            };
+            calculate_volume_price_score_enhanced(&signal, &indicators)
+        };
+        assert!(score_at(1.0) > score_at(0.0));
+        assert!(score_at(-1.0) < score_at(0.0));
+        assert!((score_at(5.0) - score_at(2.0)).abs() < 1e-9, "超过 ±2 后封顶");
+    }
+
+    #[test]
+    fn test_market_breadth_adjusts_sentiment() {
+        let with_ratio = |ratio: Option<f64>| TechnicalIndicatorValues {
+            market_ad_ratio: ratio,
+            ..Default::default()
+        };
+        let neutral = calculate_sentiment_score_enhanced(&with_ratio(None));
+        assert!(calculate_sentiment_score_enhanced(&with_ratio(Some(0.2))) > neutral);
+        assert!(calculate_sentiment_score_enhanced(&with_ratio(Some(4.0))) < neutral);
+        assert_eq!(calculate_sentiment_score_enhanced(&with_ratio(Some(1.0))), neutral);
+    }
+
+    #[test]
+    fn test_news_sentiment_adjusts_sentiment() {
+        let with_news = |news: Option<f64>| TechnicalIndicatorValues {
+            news_sentiment: news,
+            ..Default::default()
+        };
+        let neutral = calculate_sentiment_score_enhanced(&with_news(None));
+        assert!(calculate_sentiment_score_enhanced(&with_news(Some(1.0))) > neutral);
+        assert!(calculate_sentiment_score_enhanced(&with_news(Some(-1.0))) < neutral);
+        assert_eq!(calculate_sentiment_score_enhanced(&with_news(Some(0.0))), neutral);
+    }
+
+    #[test]
+    fn test_market_fear_greed_replaces_individual_sentiment() {
+        let indicators = |fear_greed: Option<f64>| TechnicalIndicatorValues {
+            rsi: 20.0,
+            kdj_oversold: true,
+            market_fear_greed: fear_greed,
+            ..Default::default()
+        };
+        // 个股超卖但全市场极度贪婪：以市场情绪为准
+        assert!(calculate_sentiment_score_enhanced(&indicators(None)) > 0.7);
+        assert!((calculate_sentiment_score_enhanced(&indicators(Some(100.0))) - 0.2).abs() < 1e-9);
+        assert!((calculate_sentiment_score_enhanced(&indicators(Some(0.0))) - 0.8).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_sector_strength_adjusts_momentum() {
+        let with_strength = |strength: Option<f64>| TechnicalIndicatorValues {
+            sector_relative_strength: strength,
+            ..Default::default()
+        };
+        let neutral = calculate_momentum_score_enhanced(&with_strength(None));
+        assert!(calculate_momentum_score_enhanced(&with_strength(Some(5.0))) > neutral);
+        assert!(calculate_momentum_score_enhanced(&with_strength(Some(-5.0))) < neutral);
+        assert!(
+            (calculate_momentum_score_enhanced(&with_strength(Some(30.0))) - neutral - 0.08).abs()
+                < 1e-9,
+            "超过 8 个百分点后封顶"
+        );
+    }
+
+    #[test]
+    fn test_high_beta_amplifies_volatility_penalty() {
+        let base = calculate_volatility_score_enhanced(0.02, None, None);
+        assert_eq!(calculate_volatility_score_enhanced(0.02, None, Some(0.6)), base);
+        assert!(calculate_volatility_score_enhanced(0.02, None, Some(1.8)) < base);
+    }
 }
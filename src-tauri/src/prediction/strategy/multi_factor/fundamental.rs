@@ -0,0 +1,149 @@
+//! 基本面因子评分（占位）
+//!
+//! 基本面数据获取不稳定，所有字段均可缺失：仅对已有字段评分，
+//! 字段全部缺失时返回权重为 0 的中性因子，不影响综合评分。
+
+use serde::{Deserialize, Serialize};
+
+/// 全部字段齐备时基本面因子的权重
+const FUNDAMENTAL_FACTOR_WEIGHT: f64 = 0.10;
+
+/// 基本面原始数据（缺失为 None）
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FundamentalFactor {
+    /// 市盈率（≤0 表示亏损）
+    pub pe_ratio: Option<f64>,
+    /// 市净率
+    pub pb_ratio: Option<f64>,
+    /// 净资产收益率（%）
+    pub roe: Option<f64>,
+    /// 营业收入同比增长（%）
+    pub revenue_growth: Option<f64>,
+}
+
+/// 单个因子评分结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Factor {
+    pub name: String,
+    /// 评分 0-1，0.5 为中性
+    pub score: f64,
+    /// 在综合评分中的权重
+    pub weight: f64,
+    pub description: String,
+}
+
+/// 基本面因子评分：PE < 20 看多、PE > 50 看空，其余字段同理按区间打分后取均值。
+///
+/// 权重按可用字段比例缩放，数据越少越不可信。
+pub fn score_fundamental_factor(data: &FundamentalFactor) -> Factor {
+    let mut scores = Vec::new();
+    let mut notes = Vec::new();
+
+    if let Some(pe) = data.pe_ratio.filter(|v| v.is_finite()) {
+        let score = if pe <= 0.0 {
+            notes.push("亏损");
+            0.25
+        } else if pe < 20.0 {
+            notes.push("低市盈率");
+            0.7
+        } else if pe > 50.0 {
+            notes.push("高市盈率");
+            0.3
+        } else {
+            0.5
+        };
+        scores.push(score);
+    }
+
+    if let Some(pb) = data.pb_ratio.filter(|v| v.is_finite() && *v > 0.0) {
+        let score = if pb < 1.0 {
+            notes.push("破净");
+            0.65
+        } else if pb > 8.0 {
+            notes.push("高市净率");
+            0.35
+        } else {
+            0.5
+        };
+        scores.push(score);
+    }
+
+    if let Some(roe) = data.roe.filter(|v| v.is_finite()) {
+        let score = if roe > 15.0 {
+            notes.push("高ROE");
+            0.7
+        } else if roe < 5.0 {
+            notes.push("低ROE");
+            0.35
+        } else {
+            0.5
+        };
+        scores.push(score);
+    }
+
+    if let Some(growth) = data.revenue_growth.filter(|v| v.is_finite()) {
+        let score = if growth > 20.0 {
+            notes.push("营收高增长");
+            0.7
+        } else if growth < 0.0 {
+            notes.push("营收下滑");
+            0.35
+        } else {
+            0.5
+        };
+        scores.push(score);
+    }
+
+    if scores.is_empty() {
+        return Factor {
+            name: "基本面".to_string(),
+            score: 0.5,
+            weight: 0.0,
+            description: "无基本面数据".to_string(),
+        };
+    }
+
+    let score = scores.iter().sum::<f64>() / scores.len() as f64;
+    Factor {
+        name: "基本面".to_string(),
+        score,
+        weight: FUNDAMENTAL_FACTOR_WEIGHT * scores.len() as f64 / 4.0,
+        description: if notes.is_empty() {
+            "估值与成长性中性".to_string()
+        } else {
+            notes.join("、")
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missing_fundamentals_are_weightless() {
+        let factor = score_fundamental_factor(&FundamentalFactor::default());
+        assert_eq!(factor.weight, 0.0);
+        assert_eq!(factor.score, 0.5);
+    }
+
+    #[test]
+    fn test_score_fundamental_factor_partial_data() {
+        let cheap = score_fundamental_factor(&FundamentalFactor {
+            pe_ratio: Some(12.0),
+            roe: Some(18.0),
+            ..Default::default()
+        });
+        assert!(cheap.score > 0.5);
+        assert!((cheap.weight - FUNDAMENTAL_FACTOR_WEIGHT / 2.0).abs() < 1e-9);
+
+        let expensive = score_fundamental_factor(&FundamentalFactor {
+            pe_ratio: Some(80.0),
+            pb_ratio: Some(10.0),
+            roe: Some(3.0),
+            revenue_growth: Some(-5.0),
+        });
+        assert!(expensive.score < 0.5);
+        assert!((expensive.weight - FUNDAMENTAL_FACTOR_WEIGHT).abs() < 1e-9);
+    }
+}
@@ -9,6 +9,8 @@
 //! - [`factors`]：各因子（趋势/量价/动量/形态/支撑阻力/情绪/波动率）评分
 //! - [`weights`]：市场状态自适应权重
 //! - [`transform`]：非线性变换、信号确认与信号生成
+//! - [`fundamental`]：基本面因子（数据可缺失，缺失时不计入综合评分）
+//! - [`confluence`]：独立信号共振检测
 
 use crate::config::weights::*;
 use crate::prediction::analysis::market_regime::{MarketRegime, VolatilityLevel};
@@ -18,10 +20,15 @@ use crate::prediction::analysis::{
 use crate::prediction::indicators::TechnicalIndicatorValues;
 use serde::{Deserialize, Serialize};
 
+pub mod confluence;
 mod factors;
+pub mod fundamental;
 mod transform;
 mod weights;
 
+pub use confluence::{detect_signal_confluence, ConfluenceAnalysis, IndicatorSignal};
+pub use fundamental::{score_fundamental_factor, Factor, FundamentalFactor};
+
 use factors::{
     calculate_momentum_score_enhanced, calculate_pattern_score_enhanced,
     calculate_sentiment_score_enhanced, calculate_sr_score_enhanced,
@@ -32,7 +39,7 @@ use transform::{
     apply_confirmation_adjustment, count_signal_confirmations, generate_enhanced_signal,
     sigmoid_transform,
 };
-use weights::get_adaptive_weights;
+use weights::{apply_learned_weights, get_adaptive_weights};
 
 /// 多因子评分结果
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -45,6 +52,9 @@ pub struct MultiFactorScore {
     pub support_resistance_score: f64,
     pub sentiment_score: f64,
     pub volatility_score: f64,
+    /// 基本面因子得分（0-100），无基本面数据时为 None
+    #[serde(default)]
+    pub fundamental_score: Option<f64>,
     pub signal: String,
     pub signal_strength: f64,
     /// 市场自适应调整后的得分
@@ -64,6 +74,7 @@ impl Default for MultiFactorScore {
             support_resistance_score: 50.0,
             sentiment_score: 50.0,
             volatility_score: 50.0,
+            fundamental_score: None,
             signal: "中性".to_string(),
             signal_strength: 0.5,
             adaptive_score: 50.0,
@@ -103,9 +114,39 @@ pub fn calculate_adaptive_multi_factor_score(
     volatility: f64,
     market_regime: Option<&MarketRegime>,
     volatility_level: Option<&VolatilityLevel>,
+) -> MultiFactorScore {
+    calculate_learned_multi_factor_score(
+        trend_state,
+        volume_signal,
+        indicators,
+        patterns,
+        support_resistance,
+        volatility,
+        market_regime,
+        volatility_level,
+        None,
+    )
+}
+
+/// 计算自适应多因子综合评分，并叠加个股学习到的因子权重
+/// （见 [`AdaptiveWeightOptimizer`](crate::prediction::strategy::adaptive_weights::AdaptiveWeightOptimizer)）
+#[allow(clippy::too_many_arguments)]
+pub fn calculate_learned_multi_factor_score(
+    trend_state: &TrendState,
+    volume_signal: &VolumePriceSignal,
+    indicators: &TechnicalIndicatorValues,
+    patterns: &[PatternRecognition],
+    support_resistance: &SupportResistance,
+    volatility: f64,
+    market_regime: Option<&MarketRegime>,
+    volatility_level: Option<&VolatilityLevel>,
+    learned_weights: Option<&[f64]>,
 ) -> MultiFactorScore {
     // 获取动态权重
-    let weights = get_adaptive_weights(market_regime, volatility_level);
+    let mut weights = get_adaptive_weights(market_regime, volatility_level);
+    if let Some(learned) = learned_weights {
+        weights = apply_learned_weights(weights, learned);
+    }
 
     // 计算各因子评分（使用非线性变换）
     let trend_score = calculate_trend_score_enhanced(trend_state, indicators);
@@ -114,7 +155,7 @@ pub fn calculate_adaptive_multi_factor_score(
     let pattern_score = calculate_pattern_score_enhanced(patterns);
     let support_resistance_score = calculate_sr_score_enhanced(support_resistance);
     let sentiment_score = calculate_sentiment_score_enhanced(indicators);
-    let volatility_score = calculate_volatility_score_enhanced(volatility, volatility_level);
+    let volatility_score = calculate_volatility_score_enhanced(volatility, volatility_level, indicators.beta);
 
     // 计算信号确认数量
     let confirmation_count = count_signal_confirmations(
@@ -125,8 +166,15 @@ pub fn calculate_adaptive_multi_factor_score(
         sentiment_score,
     );
 
+    // 基本面因子：权重随可用字段数缩放，无数据时权重为 0 不参与
+    let fundamental = indicators
+        .fundamental
+        .as_ref()
+        .map(score_fundamental_factor)
+        .filter(|factor| factor.weight > 0.0);
+
     // 使用非线性组合（避免极端值主导）
-    let weighted_scores = [
+    let mut weighted_scores = vec![
         (sigmoid_transform(trend_score), weights.trend),
         (sigmoid_transform(volume_price_score), weights.volume_price),
         (sigmoid_transform(momentum_score), weights.momentum),
@@ -135,6 +183,9 @@ pub fn calculate_adaptive_multi_factor_score(
         (sigmoid_transform(sentiment_score), weights.sentiment),
         (sigmoid_transform(volatility_score), weights.volatility),
     ];
+    if let Some(factor) = &fundamental {
+        weighted_scores.push((sigmoid_transform(factor.score), factor.weight));
+    }
 
     // 加权平均
     let total_weight: f64 = weighted_scores.iter().map(|(_, w)| w).sum();
@@ -163,6 +214,7 @@ pub fn calculate_adaptive_multi_factor_score(
         support_resistance_score: support_resistance_score * 100.0,
         sentiment_score: sentiment_score * 100.0,
         volatility_score: volatility_score * 100.0,
+        fundamental_score: fundamental.map(|factor| factor.score * 100.0),
         signal,
         signal_strength,
         adaptive_score: confirmation_adjusted,
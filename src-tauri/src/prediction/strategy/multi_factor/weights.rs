@@ -2,6 +2,7 @@
 
 use crate::config::weights::*;
 use crate::prediction::analysis::market_regime::{MarketRegime, VolatilityLevel};
+use crate::prediction::strategy::adaptive_weights::OPTIMIZER_FACTOR_NAMES;
 
 /// 动态权重结构
 pub(super) struct AdaptiveWeights {
@@ -85,3 +86,23 @@ pub(super) fn get_adaptive_weights(
         }
     }
 }
+
+/// 按个股学习到的因子权重微调市场状态权重。
+///
+/// `learned` 顺序同 [`OPTIMIZER_FACTOR_NAMES`]，且和为 1；以均匀权重为基准换算成倍数，
+/// 未学习（均匀）时不改变原权重。长度不符时忽略。
+pub(super) fn apply_learned_weights(weights: AdaptiveWeights, learned: &[f64]) -> AdaptiveWeights {
+    if learned.len() != OPTIMIZER_FACTOR_NAMES.len() {
+        return weights;
+    }
+    let scale = |i: usize| learned[i] * OPTIMIZER_FACTOR_NAMES.len() as f64;
+    AdaptiveWeights {
+        trend: weights.trend * scale(0),
+        volume_price: weights.volume_price * scale(1),
+        momentum: weights.momentum * scale(2),
+        pattern: weights.pattern * scale(3),
+        support_resistance: weights.support_resistance * scale(4),
+        sentiment: weights.sentiment * scale(5),
+        volatility: weights.volatility * scale(6),
+    }
+}
@@ -0,0 +1,204 @@
+//! 开盘跳空预测
+//!
+//! A股隔夜消息常导致次日跳空开盘。以个股近期跳空的频率与幅度为基准概率，
+//! 再按隔夜新闻情绪、多周期趋势方向与盘前期货涨跌（如有）调整向上/向下跳空概率。
+//! 只描述下一交易日开盘，与多日价格预测相互独立。
+
+use crate::db::models::HistoricalData;
+use crate::prediction::strategy::multi_timeframe;
+use crate::utils::math::get_price_limit;
+use serde::{Deserialize, Serialize};
+
+/// 开盘价相对前收盘涨跌幅绝对值不低于该值（%）视为跳空
+pub const GAP_THRESHOLD_PCT: f64 = 1.0;
+/// 统计跳空频率的回看交易日数
+const GAP_LOOKBACK_DAYS: usize = 120;
+/// 统计跳空至少需要的样本数
+const MIN_GAP_SAMPLES: usize = 20;
+/// 新闻情绪、多周期趋势、盘前期货对跳空概率的最大相对调整幅度
+const NEWS_TILT_WEIGHT: f64 = 0.4;
+const TREND_TILT_WEIGHT: f64 = 0.3;
+const FUTURES_TILT_WEIGHT: f64 = 0.3;
+/// 盘前期货涨跌幅达到该值（%）时期货信号取满
+const FUTURES_FULL_SIGNAL_PCT: f64 = 1.0;
+
+/// 隔夜信号；缺失的信号不参与调整
+#[derive(Debug, Clone, Copy, Default)]
+pub struct OvernightSignals {
+    /// 个股新闻关键词情绪分（-1 ~ +1，见 `api::news::score_news_sentiment`）
+    pub news_sentiment: Option<f64>,
+    /// 盘前股指期货涨跌幅（%）
+    pub futures_change_pct: Option<f64>,
+}
+
+/// 次日开盘跳空预测
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GapPrediction {
+    /// 高开不低于 [`GAP_THRESHOLD_PCT`] 的概率 (0-1)
+    pub gap_probability_up: f64,
+    /// 低开不低于 [`GAP_THRESHOLD_PCT`] 的概率 (0-1)
+    pub gap_probability_down: f64,
+    /// 预期开盘涨跌幅（%，正为高开），不超过涨跌停限制
+    pub expected_gap_magnitude_pct: f64,
+    /// 置信度 (0-1)：随历史样本数与可用隔夜信号数增加；样本不足时为 0
+    pub confidence: f64,
+}
+
+/// 近期跳空统计
+struct GapStatistics {
+    samples: usize,
+    up_rate: f64,
+    down_rate: f64,
+    /// 向上/向下跳空的平均幅度（%，均为正数）
+    avg_up_pct: f64,
+    avg_down_pct: f64,
+}
+
+fn gap_statistics(historical: &[HistoricalData]) -> GapStatistics {
+    let start = historical.len().saturating_sub(GAP_LOOKBACK_DAYS + 1);
+    let gaps: Vec<f64> = historical[start..]
+        .windows(2)
+        .filter(|pair| pair[0].close > 0.0)
+        .map(|pair| (pair[1].open / pair[0].close - 1.0) * 100.0)
+        .collect();
+    let summarize = |selected: Vec<f64>| {
+        let mean = if selected.is_empty() {
+            0.0
+        } else {
+            selected.iter().map(|gap| gap.abs()).sum::<f64>() / selected.len() as f64
+        };
+        (selected.len() as f64 / gaps.len().max(1) as f64, mean)
+    };
+    let (up_rate, avg_up_pct) =
+        summarize(gaps.iter().copied().filter(|&gap| gap >= GAP_THRESHOLD_PCT).collect());
+    let (down_rate, avg_down_pct) =
+        summarize(gaps.iter().copied().filter(|&gap| gap <= -GAP_THRESHOLD_PCT).collect());
+    GapStatistics {
+        samples: gaps.len(),
+        up_rate,
+        down_rate,
+        avg_up_pct,
+        avg_down_pct,
+    }
+}
+
+/// 多周期趋势倾向：共振级别 / 3，看涨为正、看跌为负；数据不足 60 天时为 None
+fn trend_bias(historical: &[HistoricalData]) -> Option<f64> {
+    let prices: Vec<f64> = historical.iter().map(|h| h.close).collect();
+    let highs: Vec<f64> = historical.iter().map(|h| h.high).collect();
+    let lows: Vec<f64> = historical.iter().map(|h| h.low).collect();
+    let date = historical.last()?.date.format("%Y-%m-%d").to_string();
+    let signal = multi_timeframe::get_latest_signal(&prices, &highs, &lows, &date)?;
+    let direction = match signal.resonance_direction.as_str() {
+        "看涨" => 1.0,
+        "看跌" => -1.0,
+        _ => 0.0,
+    };
+    Some(direction * f64::from(signal.resonance_level) / 3.0)
+}
+
+/// 预测下一交易日开盘跳空：历史跳空频率为基准，按隔夜信号的综合倾向
+/// 同比例放大一侧、缩小另一侧的概率
+pub fn predict_opening_gap(
+    stock_code: &str,
+    historical_data: &[HistoricalData],
+    overnight: OvernightSignals,
+) -> GapPrediction {
+    let stats = gap_statistics(historical_data);
+    if stats.samples < MIN_GAP_SAMPLES {
+        return GapPrediction::default();
+    }
+
+    let trend = trend_bias(historical_data);
+    let futures = overnight
+        .futures_change_pct
+        .map(|pct| (pct / FUTURES_FULL_SIGNAL_PCT).clamp(-1.0, 1.0));
+    let tilt = (NEWS_TILT_WEIGHT * overnight.news_sentiment.unwrap_or(0.0).clamp(-1.0, 1.0)
+        + TREND_TILT_WEIGHT * trend.unwrap_or(0.0)
+        + FUTURES_TILT_WEIGHT * futures.unwrap_or(0.0))
+    .clamp(-1.0, 1.0);
+
+    let mut up = (stats.up_rate * (1.0 + tilt)).clamp(0.0, 1.0);
+    let mut down = (stats.down_rate * (1.0 - tilt)).clamp(0.0, 1.0);
+    if up + down > 1.0 {
+        let total = up + down;
+        up /= total;
+        down /= total;
+    }
+    let (limit_down, limit_up) = get_price_limit(stock_code, false);
+    let expected_gap_magnitude_pct =
+        (up * stats.avg_up_pct - down * stats.avg_down_pct).clamp(limit_down, limit_up);
+
+    let signal_coverage = 0.4
+        + 0.2 * f64::from(u8::from(overnight.news_sentiment.is_some()))
+        + 0.2 * f64::from(u8::from(futures.is_some()))
+        + 0.2 * f64::from(u8::from(trend.is_some()));
+    let sample_coverage = (stats.samples as f64 / GAP_LOOKBACK_DAYS as f64).min(1.0);
+
+    GapPrediction {
+        gap_probability_up: up,
+        gap_probability_down: down,
+        expected_gap_magnitude_pct,
+        confidence: signal_coverage * sample_coverage,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, NaiveDate};
+
+    /// 收盘价恒为 10，每 4 根K线高开 2%
+    fn history(len: usize) -> Vec<HistoricalData> {
+        let start = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+        (0..len)
+            .map(|i| HistoricalData {
+                symbol: "600000".to_string(),
+                date: start + Duration::days(i as i64),
+                open: if i > 0 && i % 4 == 0 { 10.2 } else { 10.0 },
+                close: 10.0,
+                high: 10.2,
+                low: 9.8,
+                volume: 10_000,
+                amount: 100_000.0,
+                amplitude: 4.0,
+                turnover_rate: 1.0,
+                volume_ratio: 1.0,
+                change_percent: 0.0,
+                change: 0.0,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_gap_statistics() {
+        let stats = gap_statistics(&history(121));
+        assert_eq!(stats.samples, 120);
+        assert!((stats.up_rate - 0.25).abs() < 1e-9);
+        assert!((stats.avg_up_pct - 2.0).abs() < 1e-9);
+        assert_eq!(stats.down_rate, 0.0);
+    }
+
+    #[test]
+    fn test_predict_opening_gap_tilts_with_overnight_signals() {
+        let data = history(121);
+        let news = |sentiment: f64| OvernightSignals {
+            news_sentiment: Some(sentiment),
+            futures_change_pct: None,
+        };
+        let baseline = predict_opening_gap("600000", &data, OvernightSignals::default());
+        let positive = predict_opening_gap("600000", &data, news(1.0));
+        let negative = predict_opening_gap("600000", &data, news(-1.0));
+
+        assert!(positive.gap_probability_up > baseline.gap_probability_up);
+        assert!(negative.gap_probability_up < baseline.gap_probability_up);
+        assert_eq!(baseline.gap_probability_down, 0.0);
+        assert!(baseline.expected_gap_magnitude_pct > 0.0);
+        assert!(positive.confidence > baseline.confidence);
+
+        // 样本不足时不给出预测
+        let sparse = predict_opening_gap("600000", &history(10), news(1.0));
+        assert_eq!(sparse.confidence, 0.0);
+        assert_eq!(sparse.gap_probability_up, 0.0);
+    }
+}
@@ -0,0 +1,205 @@
+//! 仓位计算
+//!
+//! 提供四种常用仓位方法：
+//! 1. 固定比例风险 - 单笔止损亏损不超过账户的固定比例（经典 2% 规则）
+//! 2. 波动率调整 - 按目标波动率 / 个股波动率缩放仓位
+//! 3. 等权 - 账户在 N 个持仓间平均分配
+//! 4. 风险平价 - 按波动率倒数分配，各持仓风险贡献相等
+//!
+//! 建议股数按 A 股每手 100 股向下取整。
+
+use serde::{Deserialize, Serialize};
+
+/// A 股每手股数
+pub const SHARES_PER_LOT: u64 = 100;
+
+/// 仓位计算方法
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PositionSizingMethod {
+    FixedFractional,
+    VolatilityAdjusted,
+    EqualWeight,
+    RiskParity,
+}
+
+/// 仓位计算参数；比例均为小数（0.02 = 2%），各方法只读取自己需要的字段
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PositionSizingParams {
+    pub account_size: f64,
+    /// 当前股价，用于换算建议股数
+    pub price: f64,
+    /// 固定比例风险：单笔可承受亏损占账户比例
+    pub risk_pct: f64,
+    /// 固定比例风险：止损距买入价的比例
+    pub stop_loss_pct: f64,
+    /// 波动率调整：目标波动率
+    pub target_volatility: f64,
+    /// 波动率调整：个股波动率（与目标波动率同一口径）
+    pub stock_volatility: f64,
+    /// 等权：持仓数
+    pub n_positions: usize,
+    /// 风险平价：组合内各股票波动率，第一个为本次计算的股票
+    pub volatilities: Vec<f64>,
+}
+
+/// 仓位计算结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PositionSizeResult {
+    pub method: PositionSizingMethod,
+    /// 按方法算出的目标持仓金额（不超过账户规模）
+    pub position_value: f64,
+    /// 目标持仓占账户比例
+    pub position_pct: f64,
+    /// 按整手向下取整后的建议股数
+    pub recommended_shares: u64,
+    /// 建议股数对应的实际金额
+    pub recommended_value: f64,
+    /// 风险平价下各股票的分配金额（其余方法为空）
+    pub allocations: Vec<f64>,
+}
+
+/// 仓位计算器：各方法返回目标持仓金额，结果不超过账户规模，参数无效时为 0
+pub struct PositionSizingCalculator;
+
+impl PositionSizingCalculator {
+    /// 固定比例风险：仓位 = 账户 × 风险比例 / 止损比例
+    pub fn fixed_fractional(account_size: f64, risk_pct: f64, stop_loss_pct: f64) -> f64 {
+        if account_size <= 0.0 || risk_pct <= 0.0 || stop_loss_pct <= 0.0 {
+            return 0.0;
+        }
+        (account_size * risk_pct / stop_loss_pct).min(account_size)
+    }
+
+    /// 波动率调整：仓位 = 账户 × 目标波动率 / 个股波动率
+    pub fn volatility_adjusted(
+        account_size: f64,
+        target_volatility: f64,
+        stock_volatility: f64,
+    ) -> f64 {
+        if account_size <= 0.0 || target_volatility <= 0.0 || stock_volatility <= 0.0 {
+            return 0.0;
+        }
+        (account_size * target_volatility / stock_volatility).min(account_size)
+    }
+
+    /// 等权：仓位 = 账户 / 持仓数
+    pub fn equal_weight(account_size: f64, n_positions: usize) -> f64 {
+        if account_size <= 0.0 || n_positions == 0 {
+            return 0.0;
+        }
+        account_size / n_positions as f64
+    }
+
+    /// 风险平价：按波动率倒数分配账户；任一波动率无效时返回全 0
+    pub fn risk_parity(account_size: f64, volatilities: &[f64]) -> Vec<f64> {
+        if account_size <= 0.0 || volatilities.iter().any(|v| !v.is_finite() || *v <= 0.0) {
+            return vec![0.0; volatilities.len()];
+        }
+        let inverse_total: f64 = volatilities.iter().map(|v| 1.0 / v).sum();
+        volatilities
+            .iter()
+            .map(|v| account_size * (1.0 / v) / inverse_total)
+            .collect()
+    }
+}
+
+/// 按整手向下取整的建议股数
+pub fn round_to_lot(position_value: f64, price: f64) -> u64 {
+    if position_value <= 0.0 || price <= 0.0 {
+        return 0;
+    }
+    let lots = (position_value / price / SHARES_PER_LOT as f64).floor() as u64;
+    lots * SHARES_PER_LOT
+}
+
+/// 按指定方法计算仓位
+pub fn calculate_position_size(
+    method: PositionSizingMethod,
+    params: PositionSizingParams,
+) -> PositionSizeResult {
+    let mut allocations = Vec::new();
+    let position_value = match method {
+        PositionSizingMethod::FixedFractional => PositionSizingCalculator::fixed_fractional(
+            params.account_size,
+            params.risk_pct,
+            params.stop_loss_pct,
+        ),
+        PositionSizingMethod::VolatilityAdjusted => PositionSizingCalculator::volatility_adjusted(
+            params.account_size,
+            params.target_volatility,
+            params.stock_volatility,
+        ),
+        PositionSizingMethod::EqualWeight => {
+            PositionSizingCalculator::equal_weight(params.account_size, params.n_positions)
+        }
+        PositionSizingMethod::RiskParity => {
+            allocations =
+                PositionSizingCalculator::risk_parity(params.account_size, &params.volatilities);
+            allocations.first().copied().unwrap_or(0.0)
+        }
+    };
+    let recommended_shares = round_to_lot(position_value, params.price);
+    PositionSizeResult {
+        method,
+        position_value,
+        position_pct: if params.account_size > 0.0 {
+            position_value / params.account_size
+        } else {
+            0.0
+        },
+        recommended_shares,
+        recommended_value: recommended_shares as f64 * params.price.max(0.0),
+        allocations,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fixed_fractional_two_percent_rule() {
+        // 10 万账户、2% 风险、8% 止损 → 2.5 万仓位，股价 12 元取整为 2000 股
+        let result = calculate_position_size(
+            PositionSizingMethod::FixedFractional,
+            PositionSizingParams {
+                account_size: 100_000.0,
+                price: 12.0,
+                risk_pct: 0.02,
+                stop_loss_pct: 0.08,
+                ..PositionSizingParams::default()
+            },
+        );
+        assert!((result.position_value - 25_000.0).abs() < 1e-6);
+        assert_eq!(result.recommended_shares, 2000);
+        assert!((result.recommended_value - 24_000.0).abs() < 1e-6);
+
+        // 止损过窄时仓位不超过账户规模
+        assert_eq!(PositionSizingCalculator::fixed_fractional(100_000.0, 0.02, 0.01), 100_000.0);
+    }
+
+    #[test]
+    fn test_volatility_and_equal_weight() {
+        let sized = PositionSizingCalculator::volatility_adjusted(100_000.0, 0.15, 0.30);
+        assert!((sized - 50_000.0).abs() < 1e-6);
+        assert_eq!(PositionSizingCalculator::equal_weight(100_000.0, 4), 25_000.0);
+        assert_eq!(PositionSizingCalculator::equal_weight(100_000.0, 0), 0.0);
+    }
+
+    #[test]
+    fn test_risk_parity_equalizes_risk_contribution() {
+        let allocations = PositionSizingCalculator::risk_parity(90_000.0, &[0.1, 0.2, 0.4]);
+        assert!((allocations.iter().sum::<f64>() - 90_000.0).abs() < 1e-6);
+        let contributions: Vec<f64> = allocations
+            .iter()
+            .zip([0.1, 0.2, 0.4])
+            .map(|(a, v)| a * v)
+            .collect();
+        assert!(contributions.windows(2).all(|w| (w[0] - w[1]).abs() < 1e-6));
+        assert!(PositionSizingCalculator::risk_parity(90_000.0, &[0.1, 0.0])
+            .iter()
+            .all(|a| *a == 0.0));
+    }
+}
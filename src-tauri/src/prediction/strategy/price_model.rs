@@ -5,11 +5,128 @@
 //! 2. 趋势动量结合 - 考虑动量持续性
 //! 3. 波动率自适应 - 高波动时预测保守
 //! 4. 支撑阻力敏感 - 接近关键位时调整预测
+//! 5. ATR 跟踪止损 - 止损距离随波动率动态缩放
+//! 6. 回撤分析 - 最大回撤、历史修复时长与当前回撤
+//! 7. 斐波那契时间周期 - 自摆动高/低点起第 1、2、3、5…55 根K线为潜在变盘时间窗口
 
 use crate::prediction::analysis::{
-    market_regime::{MarketRegime, VolatilityLevel, StrategyType},
+    divergence::find_local_extremes,
+    market_regime::{calculate_atr_ratio, MarketRegime, VolatilityLevel, StrategyType},
     TrendState, SupportResistance,
 };
+use crate::prediction::indicators::calculate_atr;
+use serde::{Deserialize, Serialize};
+
+/// 跟踪止损 ATR 周期
+const TRAILING_STOP_ATR_PERIOD: usize = 14;
+/// 判定高波动时与之比较的 ATR 均值窗口
+const TRAILING_STOP_ATR_MEAN_WINDOW: usize = 90;
+/// 高波动股票的 ATR 倍数
+pub const VOLATILE_ATR_MULTIPLIER: f64 = 2.0;
+/// 低波动股票的 ATR 倍数
+pub const STABLE_ATR_MULTIPLIER: f64 = 1.5;
+
+/// ATR 跟踪止损结果（多头：止损位 = 当前价 - 倍数 × ATR）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrailingStopResult {
+    pub stop_price: f64,
+    /// 止损距当前价的百分比
+    pub stop_distance_pct: f64,
+    pub atr_value: f64,
+    /// 实际使用的 ATR 周期（数据不足 14 日时取可用长度）
+    pub atr_period_used: usize,
+}
+
+/// 计入平均修复时长的最小回撤幅度（%），更小的波动视为噪声
+const SIGNIFICANT_DRAWDOWN_PCT: f64 = 5.0;
+/// 当前回撤达到该幅度（%）视为深度回撤
+pub const DEEP_DRAWDOWN_PCT: f64 = 20.0;
+/// 历史平均修复时长达到该天数视为修复缓慢
+pub const SLOW_RECOVERY_DAYS: usize = 60;
+
+/// 回撤特征
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DrawdownAnalysis {
+    /// 最大峰谷跌幅（%）
+    pub max_drawdown_pct: f64,
+    /// 最长回撤持续天数（前高至收复前高，未收复的计至最新）
+    pub max_drawdown_duration_days: usize,
+    /// 已收复的显著回撤（≥5%）从谷底回到前高的平均天数；无样本时为 0
+    pub avg_recovery_days: f64,
+    /// 距前高的当前跌幅（%）
+    pub current_drawdown_pct: f64,
+    pub days_in_current_drawdown: usize,
+    /// 按历史平均修复时长估计；不在回撤中或无历史样本时为 None
+    pub estimated_recovery_days: Option<usize>,
+}
+
+impl DrawdownAnalysis {
+    /// 深度回撤且历史修复缓慢（或从未收复过显著回撤）
+    pub fn is_high_risk(&self) -> bool {
+        self.current_drawdown_pct >= DEEP_DRAWDOWN_PCT
+            && self
+                .estimated_recovery_days
+                .is_none_or(|days| days >= SLOW_RECOVERY_DAYS)
+    }
+}
+
+/// 分析时间正序收盘价的回撤特征
+pub fn analyze_drawdown_characteristics(prices: &[f64]) -> DrawdownAnalysis {
+    let mut peak = f64::NEG_INFINITY;
+    let mut peak_idx = 0;
+    let mut trough_idx = 0;
+    let mut max_drawdown_pct: f64 = 0.0;
+    let mut max_duration = 0;
+    let mut episode_depth: f64 = 0.0;
+    let mut recoveries = Vec::new();
+
+    for (i, &price) in prices.iter().enumerate() {
+        if price >= peak {
+            // 收复前高：结束一段回撤
+            if episode_depth > 0.0 {
+                max_duration = max_duration.max(i - peak_idx);
+                if episode_depth >= SIGNIFICANT_DRAWDOWN_PCT {
+                    recoveries.push(i - trough_idx);
+                }
+            }
+            peak = price;
+            peak_idx = i;
+            trough_idx = i;
+            episode_depth = 0.0;
+        } else if peak > 0.0 {
+            let depth = (peak - price) / peak * 100.0;
+            if depth > episode_depth {
+                episode_depth = depth;
+                trough_idx = i;
+            }
+            max_drawdown_pct = max_drawdown_pct.max(depth);
+        }
+    }
+
+    let last_idx = prices.len().saturating_sub(1);
+    let days_in_current_drawdown = last_idx.saturating_sub(peak_idx);
+    max_duration = max_duration.max(days_in_current_drawdown);
+    let current_drawdown_pct = match prices.last() {
+        Some(&last) if peak > 0.0 => (peak - last) / peak * 100.0,
+        _ => 0.0,
+    };
+    let avg_recovery_days = if recoveries.is_empty() {
+        0.0
+    } else {
+        recoveries.iter().sum::<usize>() as f64 / recoveries.len() as f64
+    };
+    let estimated_recovery_days = (current_drawdown_pct > 0.0 && !recoveries.is_empty())
+        .then(|| avg_recovery_days.round() as usize);
+
+    DrawdownAnalysis {
+        max_drawdown_pct,
+        max_drawdown_duration_days: max_duration,
+        avg_recovery_days,
+        current_drawdown_pct,
+        days_in_current_drawdown,
+        estimated_recovery_days,
+    }
+}
 
 /// 价格预测上下文
 pub struct PricePredictionContext {
@@ -386,6 +503,114 @@ pub fn calculate_adaptive_weights(
     (trend_weight, mr_weight, momentum_weight)
 }
 
+/// 计算多头 ATR 跟踪止损：止损位 = 当前价 - `atr_multiplier` × ATR(14)；
+/// 数据不足 14 日时以可用长度计算 ATR，不足 2 根 K 线时 ATR 为 0、止损位即当前价
+pub fn calculate_atr_trailing_stop(
+    prices: &[f64],
+    highs: &[f64],
+    lows: &[f64],
+    atr_multiplier: f64,
+) -> TrailingStopResult {
+    let current_price = prices.last().copied().unwrap_or(0.0);
+    let len = prices.len().min(highs.len()).min(lows.len());
+    let atr_period_used = len.saturating_sub(1).min(TRAILING_STOP_ATR_PERIOD);
+    let atr_value = if atr_period_used == 0 {
+        0.0
+    } else {
+        calculate_atr(&highs[..len], &lows[..len], &prices[..len], atr_period_used)
+    };
+
+    let stop_price = (current_price - atr_multiplier * atr_value).max(0.0);
+    let stop_distance_pct = if current_price > 0.0 {
+        (current_price - stop_price) / current_price * 100.0
+    } else {
+        0.0
+    };
+
+    TrailingStopResult {
+        stop_price,
+        stop_distance_pct,
+        atr_value,
+        atr_period_used,
+    }
+}
+
+/// 按波动状态选择 ATR 倍数：当前 ATR 高于近 90 日 ATR 均值视为高波动（2.0），否则 1.5；
+/// 数据不足以计算 90 日均值时按高波动处理，止损留足空间
+pub fn select_atr_multiplier(prices: &[f64], highs: &[f64], lows: &[f64]) -> f64 {
+    match calculate_atr_ratio(highs, lows, prices, TRAILING_STOP_ATR_MEAN_WINDOW) {
+        Some(ratio) if ratio <= 1.0 => STABLE_ATR_MULTIPLIER,
+        _ => VOLATILE_ATR_MULTIPLIER,
+    }
+}
+
+/// 斐波那契时间周期数列（摆动点之后的K线根数）
+pub const FIBONACCI_TIME_CYCLES: [usize; 9] = [1, 2, 3, 5, 8, 13, 21, 34, 55];
+/// 识别摆动高/低点时两侧比较的K线数
+const SWING_WINDOW: usize = 5;
+
+/// 斐波那契时间周期分析
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FibonacciTimeCycleAnalysis {
+    pub swing_date: String,
+    /// 摆动点至最新K线的K线数
+    pub bars_since_swing: usize,
+    pub cycles: Vec<FibonacciTimeCycle>,
+    /// 距最新K线最近的时间周期
+    pub nearest_cycle: Option<FibonacciTimeCycle>,
+    /// 距最近时间周期的K线数：正数在未来，负数已过去
+    pub bars_until_nearest: Option<i64>,
+}
+
+/// 单个时间周期
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FibonacciTimeCycle {
+    /// 距摆动点的K线数（斐波那契数）
+    pub fibonacci: usize,
+    pub date: String,
+    /// 超出已有数据、按交易日历外推的日期
+    pub is_projected: bool,
+}
+
+/// 从摆动点索引起按斐波那契数列推算时间周期的K线索引，仅保留小于 `total_bars` 的索引
+pub fn calculate_fibonacci_time_cycles(swing_date_index: usize, total_bars: usize) -> Vec<usize> {
+    FIBONACCI_TIME_CYCLES
+        .iter()
+        .map(|fib| swing_date_index + fib)
+        .take_while(|&index| index < total_bars)
+        .collect()
+}
+
+/// 距 `current_bar` 最近的时间周期及相距K线数（正数在未来，负数已过去）；
+/// 前后等距时取未来的周期
+pub fn find_nearest_fibonacci_time_cycle(current_bar: usize, cycles: &[usize]) -> Option<(usize, i64)> {
+    cycles
+        .iter()
+        .map(|&cycle| (cycle, cycle as i64 - current_bar as i64))
+        .min_by_key(|&(_, offset)| (offset.unsigned_abs(), offset < 0))
+}
+
+/// 最近一个摆动高点或低点的索引（两侧各 5 根K线内的最高价/最低价）
+pub fn find_latest_swing_index(highs: &[f64], lows: &[f64]) -> Option<usize> {
+    let (_, swing_highs) = find_local_extremes(highs, SWING_WINDOW);
+    let (swing_lows, _) = find_local_extremes(lows, SWING_WINDOW);
+    swing_highs
+        .last()
+        .into_iter()
+        .chain(swing_lows.last())
+        .map(|&(index, _)| index)
+        .max()
+}
+
+/// 最新K线距最近摆动点的斐波那契时间周期的K线数（绝对值）；找不到摆动点时为 None
+pub fn latest_time_cycle_proximity(highs: &[f64], lows: &[f64]) -> Option<usize> {
+    let swing = find_latest_swing_index(highs, lows)?;
+    let current = highs.len().min(lows.len()).checked_sub(1)?;
+    let last_cycle = swing + FIBONACCI_TIME_CYCLES[FIBONACCI_TIME_CYCLES.len() - 1];
+    let cycles = calculate_fibonacci_time_cycles(swing, last_cycle + 1);
+    find_nearest_fibonacci_time_cycle(current, &cycles).map(|(_, offset)| offset.unsigned_abs() as usize)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -404,4 +629,89 @@ mod tests {
         );
         assert!(bearish < 0.0);
     }
+
+    fn bars(ranges: &[f64]) -> (Vec<f64>, Vec<f64>, Vec<f64>) {
+        let closes = vec![10.0; ranges.len()];
+        let highs = ranges.iter().map(|r| 10.0 + r / 2.0).collect();
+        let lows = ranges.iter().map(|r| 10.0 - r / 2.0).collect();
+        (closes, highs, lows)
+    }
+
+    #[test]
+    fn test_atr_trailing_stop() {
+        let (closes, highs, lows) = bars(&[0.4; 30]);
+        let stop = calculate_atr_trailing_stop(&closes, &highs, &lows, 2.0);
+        assert_eq!(stop.atr_period_used, 14);
+        assert!((stop.atr_value - 0.4).abs() < 1e-9);
+        assert!((stop.stop_price - 9.2).abs() < 1e-9);
+        assert!((stop.stop_distance_pct - 8.0).abs() < 1e-9);
+
+        let short = calculate_atr_trailing_stop(&closes[..5], &highs[..5], &lows[..5], 1.5);
+        assert_eq!(short.atr_period_used, 4);
+        let single = calculate_atr_trailing_stop(&closes[..1], &highs[..1], &lows[..1], 1.5);
+        assert_eq!(single.atr_value, 0.0);
+        assert_eq!(single.stop_price, 10.0);
+    }
+
+    #[test]
+    fn test_analyze_drawdown_characteristics() {
+        // 110 → 99 次日收复（回撤 10%），120 → 90 尚未收复
+        let prices = [100.0, 110.0, 99.0, 110.0, 120.0, 90.0, 96.0];
+        let drawdown = analyze_drawdown_characteristics(&prices);
+        assert!((drawdown.max_drawdown_pct - 25.0).abs() < 1e-9);
+        assert_eq!(drawdown.max_drawdown_duration_days, 2);
+        assert_eq!(drawdown.avg_recovery_days, 1.0);
+        assert!((drawdown.current_drawdown_pct - 20.0).abs() < 1e-9);
+        assert_eq!(drawdown.days_in_current_drawdown, 2);
+        assert_eq!(drawdown.estimated_recovery_days, Some(1));
+        assert!(!drawdown.is_high_risk());
+
+        // 深度回撤且从未收复过显著回撤
+        let stuck = analyze_drawdown_characteristics(&[100.0, 70.0, 75.0]);
+        assert_eq!(stuck.estimated_recovery_days, None);
+        assert!(stuck.is_high_risk());
+
+        let rising = analyze_drawdown_characteristics(&[1.0, 2.0, 3.0]);
+        assert_eq!(rising.max_drawdown_pct, 0.0);
+        assert_eq!(rising.days_in_current_drawdown, 0);
+        assert!(!rising.is_high_risk());
+        assert_eq!(analyze_drawdown_characteristics(&[]).current_drawdown_pct, 0.0);
+    }
+
+    #[test]
+    fn test_select_atr_multiplier() {
+        let mut ranges = vec![0.2; 120];
+        ranges.extend(vec![0.8; 14]);
+        let (closes, highs, lows) = bars(&ranges);
+        assert_eq!(select_atr_multiplier(&closes, &highs, &lows), VOLATILE_ATR_MULTIPLIER);
+
+        let mut ranges = vec![0.8; 120];
+        ranges.extend(vec![0.2; 14]);
+        let (closes, highs, lows) = bars(&ranges);
+        assert_eq!(select_atr_multiplier(&closes, &highs, &lows), STABLE_ATR_MULTIPLIER);
+
+        assert_eq!(
+            select_atr_multiplier(&closes[..30], &highs[..30], &lows[..30]),
+            VOLATILE_ATR_MULTIPLIER
+        );
+    }
+
+    #[test]
+    fn test_fibonacci_time_cycles() {
+        let cycles = calculate_fibonacci_time_cycles(10, 30);
+        assert_eq!(cycles, vec![11, 12, 13, 15, 18, 23]);
+        assert_eq!(calculate_fibonacci_time_cycles(10, 100).len(), 9);
+
+        assert_eq!(find_nearest_fibonacci_time_cycle(20, &cycles), Some((18, -2)));
+        assert_eq!(find_nearest_fibonacci_time_cycle(22, &cycles), Some((23, 1)));
+        // 前后等距时取未来的周期
+        assert_eq!(find_nearest_fibonacci_time_cycle(14, &cycles), Some((15, 1)));
+        assert_eq!(find_nearest_fibonacci_time_cycle(14, &[]), None);
+
+        // 第 10 根K线为摆动高点，最新K线（索引 20）距 10 + 8 = 18 两根
+        let highs: Vec<f64> = (0..21).map(|i| 20.0 - (i as f64 - 10.0).abs()).collect();
+        let lows: Vec<f64> = highs.iter().map(|h| h - 1.0).collect();
+        assert_eq!(find_latest_swing_index(&highs, &lows), Some(10));
+        assert_eq!(latest_time_cycle_proximity(&highs, &lows), Some(2));
+    }
 }
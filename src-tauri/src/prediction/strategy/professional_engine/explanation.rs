@@ -0,0 +1,214 @@
+//! 预测解释生成
+//!
+//! 把趋势、技术指标、多因子评分与支撑阻力逐项转成带方向的贡献说明，
+//! 供规则引擎与 Candle 模型两条预测路径统一生成 `prediction_reason`。
+
+use crate::prediction::analysis::{SupportResistance, TrendState};
+use crate::prediction::indicators::TechnicalIndicatorValues;
+use crate::prediction::strategy::multi_factor::MultiFactorScore;
+use serde::{Deserialize, Serialize};
+
+/// 价格距支撑/阻力位在该比例内视为"接近"
+const LEVEL_PROXIMITY_RATIO: f64 = 0.03;
+/// ATR 占价格比例超过该值视为高波动
+const HIGH_ATR_PERCENT: f64 = 4.0;
+/// 多因子评分偏离 50 不足该值时，由逐项贡献的净方向决定主方向
+const SCORE_NEUTRAL_BAND: f64 = 5.0;
+
+/// 结构化预测解释
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PredictionExplanation {
+    /// 贡献最大的单项信号
+    pub primary_driver: String,
+    /// 与主方向一致的信号
+    pub supporting_factors: Vec<String>,
+    /// 与主方向相反的信号及风险提示
+    pub risk_factors: Vec<String>,
+    pub plain_language_summary: String,
+}
+
+/// 单项信号贡献：正为看多，负为看空，0 为仅提示风险
+struct Contribution {
+    text: String,
+    weight: f64,
+}
+
+/// 预测解释器
+pub struct PredictionExplainer<'a> {
+    pub trend: &'a TrendState,
+    pub indicators: &'a TechnicalIndicatorValues,
+    pub multi_factor: &'a MultiFactorScore,
+    pub support_resistance: &'a SupportResistance,
+    pub current_price: f64,
+}
+
+impl PredictionExplainer<'_> {
+    /// 生成解释：逐项列出全部信号贡献，并按主方向分为支撑因素与风险因素
+    pub fn explain(&self) -> PredictionExplanation {
+        let contributions = self.collect_contributions();
+        let net: f64 = contributions.iter().map(|c| c.weight).sum();
+        let score_bias = self.multi_factor.total_score - 50.0;
+        let direction = if score_bias.abs() >= SCORE_NEUTRAL_BAND {
+            score_bias.signum()
+        } else if net.abs() > f64::EPSILON {
+            net.signum()
+        } else {
+            0.0
+        };
+
+        let primary_driver = contributions
+            .iter()
+            .filter(|c| c.weight != 0.0)
+            .max_by(|a, b| a.weight.abs().total_cmp(&b.weight.abs()))
+            .map(|c| c.text.clone())
+            .unwrap_or_else(|| "无明显技术信号".to_string());
+
+        let mut supporting_factors = Vec::new();
+        let mut risk_factors = Vec::new();
+        for c in &contributions {
+            if c.weight == 0.0 || (direction != 0.0 && c.weight.signum() != direction) {
+                risk_factors.push(c.text.clone());
+            } else {
+                supporting_factors.push(c.text.clone());
+            }
+        }
+
+        let direction_text = match direction {
+            d if d > 0.0 => "偏多",
+            d if d < 0.0 => "偏空",
+            _ => "中性",
+        };
+        let all_signals: Vec<&str> = contributions.iter().map(|c| c.text.as_str()).collect();
+        let plain_language_summary = format!(
+            "综合评分 {:.0}，技术面{}。{}。",
+            self.multi_factor.total_score,
+            direction_text,
+            if all_signals.is_empty() {
+                "各项指标均处于常态区间".to_string()
+            } else {
+                all_signals.join("，")
+            }
+        );
+
+        PredictionExplanation {
+            primary_driver,
+            supporting_factors,
+            risk_factors,
+            plain_language_summary,
+        }
+    }
+
+    fn collect_contributions(&self) -> Vec<Contribution> {
+        let ind = self.indicators;
+        let mut items = Vec::new();
+        let mut push = |text: String, weight: f64| items.push(Contribution { text, weight });
+
+        match self.trend {
+            TrendState::StrongBullish => push("均线趋势强烈上涨（+强看多）".to_string(), 2.0),
+            TrendState::Bullish => push("均线趋势上涨（+看多）".to_string(), 1.0),
+            TrendState::Bearish => push("均线趋势下跌（-看空）".to_string(), -1.0),
+            TrendState::StrongBearish => push("均线趋势强烈下跌（-强看空）".to_string(), -2.0),
+            TrendState::Neutral => {}
+        }
+
+        if ind.rsi < 30.0 {
+            push(format!("RSI {:.0}（超卖，+看多）", ind.rsi), 1.0);
+        } else if ind.rsi > 70.0 {
+            push(format!("RSI {:.0}（超买，-看空）", ind.rsi), -1.0);
+        }
+
+        if ind.macd_golden_cross {
+            push("MACD 金叉（+强看多）".to_string(), 1.5);
+        } else if ind.macd_death_cross {
+            push("MACD 死叉（-强看空）".to_string(), -1.5);
+        }
+
+        if ind.kdj_golden_cross {
+            push("KDJ 金叉（+看多）".to_string(), 1.0);
+        } else if ind.kdj_death_cross {
+            push("KDJ 死叉（-看空）".to_string(), -1.0);
+        }
+        if ind.kdj_oversold {
+            push(format!("KDJ J值 {:.0}（超卖，+看多）", ind.kdj_j), 0.5);
+        } else if ind.kdj_overbought {
+            push(format!("KDJ J值 {:.0}（超买，-看空）", ind.kdj_j), -0.5);
+        }
+
+        if self.current_price > 0.0 {
+            let nearest_support = self
+                .support_resistance
+                .support_levels
+                .iter()
+                .copied()
+                .filter(|level| *level <= self.current_price)
+                .max_by(f64::total_cmp);
+            if let Some(level) = nearest_support
+                .filter(|level| (self.current_price - level) / self.current_price <= LEVEL_PROXIMITY_RATIO)
+            {
+                push(format!("接近支撑 ¥{level:.2}（+看多）"), 1.0);
+            }
+            let nearest_resistance = self
+                .support_resistance
+                .resistance_levels
+                .iter()
+                .copied()
+                .filter(|level| *level >= self.current_price)
+                .min_by(f64::total_cmp);
+            if let Some(level) = nearest_resistance
+                .filter(|level| (level - self.current_price) / self.current_price <= LEVEL_PROXIMITY_RATIO)
+            {
+                push(format!("接近阻力 ¥{level:.2}（-看空）"), -1.0);
+            }
+
+            let atr_percent = ind.atr / self.current_price * 100.0;
+            if atr_percent > HIGH_ATR_PERCENT {
+                push(format!("ATR 波动率高 {atr_percent:.1}%（-谨慎）"), 0.0);
+            }
+        }
+
+        if ind.volume_ratio > 2.0 {
+            push(format!("量比 {:.1}（放量，信号可信度提高）", ind.volume_ratio), 0.0);
+        }
+
+        items
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_explain_lists_every_signal_and_splits_by_direction() {
+        let indicators = TechnicalIndicatorValues {
+            rsi: 28.0,
+            macd_golden_cross: true,
+            atr: 1.0,
+            ..Default::default()
+        };
+        let multi_factor = MultiFactorScore {
+            total_score: 65.0,
+            ..Default::default()
+        };
+        let sr = SupportResistance {
+            support_levels: vec![12.5, 11.0],
+            resistance_levels: vec![12.8],
+            current_position: String::new(),
+        };
+        let explanation = PredictionExplainer {
+            trend: &TrendState::Neutral,
+            indicators: &indicators,
+            multi_factor: &multi_factor,
+            support_resistance: &sr,
+            current_price: 12.6,
+        }
+        .explain();
+
+        assert_eq!(explanation.primary_driver, "MACD 金叉（+强看多）");
+        assert_eq!(explanation.supporting_factors.len(), 3);
+        // 接近阻力为反向信号，高 ATR 为风险提示
+        assert_eq!(explanation.risk_factors.len(), 2);
+        assert!(explanation.plain_language_summary.contains("接近支撑 ¥12.50"));
+        assert!(explanation.plain_language_summary.contains("偏多"));
+    }
+}
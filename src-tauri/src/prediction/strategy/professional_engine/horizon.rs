@@ -0,0 +1,134 @@
+//! 自适应预测周期
+//!
+//! 低波动的强趋势延续性好，可看得更远；震荡或高波动时远期预测接近随机，只看 1-3 日。
+
+use crate::prediction::analysis::TrendState;
+use serde::{Deserialize, Serialize};
+
+/// 日收益标准差低于该值视为低波动（与 `calculate_historical_volatility` 口径一致）
+pub const LOW_VOLATILITY: f64 = 0.02;
+/// 日收益标准差达到该值视为高波动
+pub const HIGH_VOLATILITY: f64 = 0.04;
+/// 趋势效率的计算窗口（交易日）
+const EFFICIENCY_WINDOW: usize = 10;
+/// 趋势效率达到该值才认为强趋势在近期仍然顺畅
+const MIN_TREND_EFFICIENCY: f64 = 0.3;
+/// 每日置信度衰减基准（与逐日预测的 0.92 衰减一致），按波动率比例缩放
+const BASE_CONFIDENCE_DECAY: f64 = 0.08;
+
+/// 预测周期建议
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PredictionHorizonRecommendation {
+    pub recommended_days: usize,
+    /// 每多预测一日置信度的衰减比例
+    pub confidence_decay_rate: f64,
+    pub reasoning: String,
+}
+
+/// 近 `window` 日的趋势效率：|净变动| / 逐日变动绝对值之和，1 为单边直线，接近 0 为来回震荡
+fn trend_efficiency(prices: &[f64], window: usize) -> f64 {
+    let recent = &prices[prices.len().saturating_sub(window + 1)..];
+    let (Some(first), Some(last)) = (recent.first(), recent.last()) else {
+        return 0.0;
+    };
+    let path: f64 = recent.windows(2).map(|pair| (pair[1] - pair[0]).abs()).sum();
+    if path > 0.0 {
+        (last - first).abs() / path
+    } else {
+        0.0
+    }
+}
+
+/// 根据趋势状态与日波动率（日收益标准差）推荐预测天数：
+/// - 高波动：震荡 1 日，有趋势 3 日
+/// - 震荡：3 日
+/// - 强趋势、低波动且近 10 日走势顺畅：10 日
+/// - 其余趋势：5 日
+pub fn recommend_prediction_horizon(
+    prices: &[f64],
+    trend_state: &TrendState,
+    volatility: f64,
+) -> PredictionHorizonRecommendation {
+    let efficiency = trend_efficiency(prices, EFFICIENCY_WINDOW);
+    let strong_trend = matches!(trend_state, TrendState::StrongBullish | TrendState::StrongBearish);
+    let trend = trend_state.to_string();
+    let vol_pct = volatility * 100.0;
+
+    let (recommended_days, reasoning) = if volatility >= HIGH_VOLATILITY {
+        if *trend_state == TrendState::Neutral {
+            (1, format!("震荡且日波动率 {vol_pct:.1}% 偏高，仅预测次日"))
+        } else {
+            (3, format!("{trend}趋势但日波动率 {vol_pct:.1}% 偏高，远期误差大，预测 3 日"))
+        }
+    } else if *trend_state == TrendState::Neutral {
+        (3, "趋势不明，远期方向接近随机，预测 3 日".to_string())
+    } else if strong_trend && volatility < LOW_VOLATILITY && efficiency >= MIN_TREND_EFFICIENCY {
+        (
+            10,
+            format!(
+                "{trend}、日波动率 {vol_pct:.1}% 较低且近 {EFFICIENCY_WINDOW} 日趋势效率 {efficiency:.2}，趋势延续性好，预测 10 日"
+            ),
+        )
+    } else {
+        (5, format!("{trend}趋势，日波动率 {vol_pct:.1}%，预测 5 日"))
+    };
+
+    PredictionHorizonRecommendation {
+        recommended_days,
+        confidence_decay_rate: (BASE_CONFIDENCE_DECAY * volatility / LOW_VOLATILITY).clamp(0.04, 0.2),
+        reasoning,
+    }
+}
+
+/// 以建议的衰减比例替换逐日预测的基准衰减：第 `day_index` 日（首日为 0）的置信度
+/// 乘 ((1 - rate) / (1 - 基准))^day_index，高波动时远期置信度下降更快。
+/// 只下调不上调：衰减比例低于基准时保持原置信度
+pub fn decayed_confidence(confidence: f64, day_index: usize, decay_rate: f64) -> f64 {
+    let ratio = ((1.0 - decay_rate) / (1.0 - BASE_CONFIDENCE_DECAY)).min(1.0);
+    (confidence * ratio.powi(day_index as i32)).max(0.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recommend_prediction_horizon() {
+        let rising: Vec<f64> = (0..30).map(|i| 10.0 + i as f64 * 0.1).collect();
+        let steady = recommend_prediction_horizon(&rising, &TrendState::StrongBullish, 0.01);
+        assert_eq!(steady.recommended_days, 10);
+        assert!((steady.confidence_decay_rate - 0.04).abs() < 1e-12);
+
+        // 强趋势但近期来回震荡，不延长
+        let choppy: Vec<f64> = (0..30).map(|i| if i % 2 == 0 { 10.0 } else { 10.5 }).collect();
+        assert_eq!(
+            recommend_prediction_horizon(&choppy, &TrendState::StrongBullish, 0.01).recommended_days,
+            5
+        );
+        assert_eq!(
+            recommend_prediction_horizon(&rising, &TrendState::Neutral, 0.03).recommended_days,
+            3
+        );
+
+        let volatile = recommend_prediction_horizon(&rising, &TrendState::Neutral, 0.05);
+        assert_eq!(volatile.recommended_days, 1);
+        assert!((volatile.confidence_decay_rate - 0.2).abs() < 1e-12);
+        assert_eq!(
+            recommend_prediction_horizon(&rising, &TrendState::Bearish, 0.05).recommended_days,
+            3
+        );
+    }
+
+    #[test]
+    fn test_decayed_confidence_follows_volatility() {
+        // 首日不变；基准衰减率下逐日置信度不变
+        assert_eq!(decayed_confidence(0.6, 0, 0.2), 0.6);
+        assert!((decayed_confidence(0.6, 3, BASE_CONFIDENCE_DECAY) - 0.6).abs() < 1e-12);
+        // 高波动衰减更快；低波动不上调，已被压低的置信度不被抬回
+        assert!((decayed_confidence(0.6, 1, 0.2) - 0.6 * 0.8 / 0.92).abs() < 1e-12);
+        assert_eq!(decayed_confidence(0.6, 2, 0.04), 0.6);
+        assert_eq!(decayed_confidence(0.5, 9, 0.04), 0.5);
+        assert!(decayed_confidence(0.2, 5, 0.2) < 0.2);
+        assert_eq!(decayed_confidence(0.0, 3, 0.2), 0.0);
+    }
+}
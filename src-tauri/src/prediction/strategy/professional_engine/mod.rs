@@ -13,23 +13,33 @@
 //! - [`change`]：预期涨跌幅与 A 股涨跌停限制
 //! - [`risk`]：风险评估
 //! - [`output`]：关键因素与操作建议
+//! - [`explanation`]：结构化预测解释
+//! - [`trend_filter`]：DMI/ADX 趋势强度过滤
+//! - [`horizon`]：自适应预测周期
 
 use crate::prediction::analysis::{
     divergence::DivergenceAnalysis,
+    dtw::PatternConsensus,
     market_regime::{MarketRegime, MarketRegimeAnalysis, StrategyType},
     PatternRecognition, SupportResistance, TrendAnalysis, VolumePriceSignal,
 };
 use crate::prediction::indicators::TechnicalIndicatorValues;
 use crate::prediction::strategy::multi_factor::MultiFactorScore;
+use crate::utils::math::get_price_limit;
 use serde::{Deserialize, Serialize};
 
 mod change;
 mod direction;
+pub mod explanation;
+mod horizon;
 mod output;
 mod risk;
 mod signals;
+mod trend_filter;
 
 use change::calculate_expected_change;
+pub use explanation::{PredictionExplainer, PredictionExplanation};
+pub use horizon::{decayed_confidence, recommend_prediction_horizon, PredictionHorizonRecommendation};
 use direction::{
     calculate_comprehensive_confidence, calculate_signal_confirmation,
     determine_prediction_direction,
@@ -37,6 +47,7 @@ use direction::{
 use output::{generate_key_factors, generate_suggested_action};
 use risk::assess_risk;
 use signals::collect_all_signals;
+pub use trend_filter::{filter_signal_by_trend_strength, FilteredSignal, ADX_NO_TREND, ADX_STRONG_TREND};
 
 // =============================================================================
 // 核心类型定义
@@ -183,6 +194,8 @@ pub struct PredictionContext {
     pub support_resistance: SupportResistance,
     pub multi_factor_score: MultiFactorScore,
     pub volatility: f64,
+    /// DTW 历史形态类比（历史不足时为 None）
+    pub pattern_analogy: Option<PatternConsensus>,
 }
 
 // =============================================================================
@@ -208,27 +221,17 @@ pub mod a_share_limits {
     pub const DEFAULT_LIMIT_DOWN: f64 = -9.5;
 }
 
-/// 根据股票代码判断市场类型并返回对应的涨跌停限制
+/// 根据股票代码判断市场类型并返回对应的涨跌停限制（板块规则见 `utils::math::get_price_limit`）；
+/// 主板收窄到 ±9.5% 留出预测裕度
 pub fn get_stock_price_limits(stock_code: Option<&str>) -> (f64, f64) {
+    // ST股：名称中包含ST（这里简化处理，实际应查询数据库），暂时无法判断，使用板块规则
     match stock_code {
-        Some(code) => {
-            let code = code.trim();
-            let code = code.trim_start_matches(|c: char| !c.is_ascii_digit());
-            // 科创板：688开头
-            if code.starts_with("688") {
-                (a_share_limits::KC_CY_LIMIT_DOWN, a_share_limits::KC_CY_LIMIT_UP)
-            }
-            // 创业板：300开头
-            else if code.starts_with("300") || code.starts_with("301") {
-                (a_share_limits::KC_CY_LIMIT_DOWN, a_share_limits::KC_CY_LIMIT_UP)
-            }
-            // ST股：名称中包含ST（这里简化处理，实际应查询数据库）
-            // 暂时无法判断，使用主板规则
-            else {
-                // 主板：60/00开头
+        Some(code) => match get_price_limit(code, false) {
+            (_, up) if up <= a_share_limits::MAIN_BOARD_LIMIT_UP => {
                 (a_share_limits::DEFAULT_LIMIT_DOWN, a_share_limits::DEFAULT_LIMIT_UP)
             }
-        }
+            limits => limits,
+        },
         None => {
             // 未知股票，使用保守的主板规则
             (a_share_limits::DEFAULT_LIMIT_DOWN, a_share_limits::DEFAULT_LIMIT_UP)
@@ -1,6 +1,7 @@
 //! 信号收集
 
 use super::{PredictionContext, SignalDetail, SignalSummary};
+use crate::prediction::analysis::dtw::PatternConsensus;
 use crate::prediction::analysis::market_regime::MarketRegime;
 use crate::prediction::analysis::support_resistance::{is_breakdown, is_breakout};
 use crate::prediction::analysis::TrendState;
@@ -306,6 +307,31 @@ pub(super) fn collect_all_signals(ctx: &PredictionContext) -> SignalSummary {
         }
     }
 
+    // 8. 历史形态类比 (权重: 0.10，仅在能找到相似窗口时参与投票)
+    if let Some(analogy) = &ctx.pattern_analogy {
+        let analogy_weight = 0.10;
+        total_weight += analogy_weight;
+        if let Some((direction, strength)) = pattern_analogy_vote(analogy) {
+            if direction > 0.0 {
+                bullish_signals += 1;
+            } else {
+                bearish_signals += 1;
+            }
+            weighted_score += direction * strength * analogy_weight;
+            signal_details.push(SignalDetail {
+                source: "形态类比".to_string(),
+                direction: if direction > 0.0 { "看涨" } else { "看跌" }.to_string(),
+                strength,
+                description: format!(
+                    "{} 段相似历史走势后 5 日加权平均 {:+.2}%（上涨占比 {:.0}%）",
+                    analogy.matches.len(),
+                    analogy.expected_return_5d,
+                    analogy.up_ratio_5d * 100.0
+                ),
+            });
+        }
+    }
+
     // 计算净信号得分
     let net_signal_score = if total_weight > 0.0 {
         (weighted_score / total_weight).clamp(-1.0, 1.0)
@@ -320,3 +346,17 @@ pub(super) fn collect_all_signals(ctx: &PredictionContext) -> SignalSummary {
         net_signal_score,
     }
 }
+
+/// 形态类比投票：多数相似窗口同向且加权平均收益超过 0.5% 时投票，
+/// 返回 (方向 ±1, 强度 0.3–1.0)
+fn pattern_analogy_vote(analogy: &PatternConsensus) -> Option<(f64, f64)> {
+    let expected = analogy.expected_return_5d;
+    let direction = if expected > 0.5 && analogy.up_ratio_5d >= 0.6 {
+        1.0
+    } else if expected < -0.5 && analogy.up_ratio_5d <= 0.4 {
+        -1.0
+    } else {
+        return None;
+    };
+    Some((direction, (expected.abs() / 5.0).clamp(0.3, 1.0)))
+}
@@ -0,0 +1,125 @@
+//! DMI/ADX 趋势强度过滤
+//!
+//! ADX 衡量趋势强度而非方向：无趋势时强信号多为震荡中的假突破，
+//! 强趋势且 DI 方向一致时顺势信号更可靠。
+
+use super::{generate_suggested_action, PredictionDirection, ProfessionalPredictionResult};
+use crate::prediction::indicators::TradingSignal;
+
+/// 低于该 ADX 视为无明确趋势
+pub const ADX_NO_TREND: f64 = 20.0;
+/// 高于该 ADX 视为强趋势
+pub const ADX_STRONG_TREND: f64 = 40.0;
+
+/// 趋势过滤结果
+#[derive(Debug, Clone, PartialEq)]
+pub struct FilteredSignal {
+    pub original_signal: TradingSignal,
+    pub filtered_signal: TradingSignal,
+    /// 信号被调整时的说明；未调整时为空串
+    pub filter_reason: String,
+}
+
+/// 按趋势强度过滤信号：
+/// - ADX < 20：强烈买入 / 强烈卖出降为持有
+/// - ADX > 40 且 DI 方向一致：买入升为强烈买入、卖出升为强烈卖出
+pub fn filter_signal_by_trend_strength(
+    signal: TradingSignal,
+    adx: f64,
+    di_plus: f64,
+    di_minus: f64,
+) -> FilteredSignal {
+    let (filtered_signal, filter_reason) = match signal {
+        TradingSignal::StrongBuy | TradingSignal::StrongSell if adx < ADX_NO_TREND => (
+            TradingSignal::Hold,
+            format!("ADX {adx:.1} 低于 {ADX_NO_TREND:.0}，无明确趋势，强信号降为观望"),
+        ),
+        TradingSignal::Buy if adx > ADX_STRONG_TREND && di_plus > di_minus => (
+            TradingSignal::StrongBuy,
+            format!("ADX {adx:.1} 强趋势且 +DI 占优，买入信号增强"),
+        ),
+        TradingSignal::Sell if adx > ADX_STRONG_TREND && di_minus > di_plus => (
+            TradingSignal::StrongSell,
+            format!("ADX {adx:.1} 强趋势且 -DI 占优，卖出信号增强"),
+        ),
+        _ => (signal.clone(), String::new()),
+    };
+    FilteredSignal {
+        original_signal: signal,
+        filtered_signal,
+        filter_reason,
+    }
+}
+
+impl PredictionDirection {
+    pub fn to_trading_signal(self) -> TradingSignal {
+        match self {
+            Self::StrongBullish => TradingSignal::StrongBuy,
+            Self::Bullish => TradingSignal::Buy,
+            Self::Neutral => TradingSignal::Hold,
+            Self::Bearish => TradingSignal::Sell,
+            Self::StrongBearish => TradingSignal::StrongSell,
+        }
+    }
+
+    pub fn from_trading_signal(signal: &TradingSignal) -> Self {
+        match signal {
+            TradingSignal::StrongBuy => Self::StrongBullish,
+            TradingSignal::Buy => Self::Bullish,
+            TradingSignal::Hold => Self::Neutral,
+            TradingSignal::Sell => Self::Bearish,
+            TradingSignal::StrongSell => Self::StrongBearish,
+        }
+    }
+}
+
+impl ProfessionalPredictionResult {
+    /// 对最终方向应用趋势强度过滤；方向改变时更新操作建议并把原因加入关键因素
+    pub fn apply_trend_filter(&mut self, adx: f64, di_plus: f64, di_minus: f64) -> FilteredSignal {
+        let filtered =
+            filter_signal_by_trend_strength(self.direction.to_trading_signal(), adx, di_plus, di_minus);
+        if filtered.filtered_signal != filtered.original_signal {
+            self.direction = PredictionDirection::from_trading_signal(&filtered.filtered_signal);
+            self.suggested_action = generate_suggested_action(
+                &self.direction,
+                &self.signal_confirmation,
+                self.confidence,
+                &self.risk_assessment,
+                &self.market_regime,
+            );
+            self.key_factors.push(filtered.filter_reason.clone());
+        }
+        filtered
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_weak_trend_downgrades_strong_signals() {
+        let filtered = filter_signal_by_trend_strength(TradingSignal::StrongBuy, 15.0, 30.0, 10.0);
+        assert_eq!(filtered.filtered_signal, TradingSignal::Hold);
+        assert_eq!(filtered.original_signal, TradingSignal::StrongBuy);
+        assert!(!filtered.filter_reason.is_empty());
+
+        // 普通信号不受弱趋势影响
+        let filtered = filter_signal_by_trend_strength(TradingSignal::Buy, 15.0, 30.0, 10.0);
+        assert_eq!(filtered.filtered_signal, TradingSignal::Buy);
+        assert!(filtered.filter_reason.is_empty());
+    }
+
+    #[test]
+    fn test_strong_trend_upgrades_aligned_signals() {
+        let up = filter_signal_by_trend_strength(TradingSignal::Buy, 45.0, 30.0, 10.0);
+        assert_eq!(up.filtered_signal, TradingSignal::StrongBuy);
+
+        // DI 方向与信号相反时不增强
+        let against = filter_signal_by_trend_strength(TradingSignal::Buy, 45.0, 10.0, 30.0);
+        assert_eq!(against.filtered_signal, TradingSignal::Buy);
+
+        let down = filter_signal_by_trend_strength(TradingSignal::Sell, 45.0, 10.0, 30.0);
+        assert_eq!(down.filtered_signal, TradingSignal::StrongSell);
+    }
+}
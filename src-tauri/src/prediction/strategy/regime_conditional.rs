@@ -0,0 +1,188 @@
+//! 市场状态条件预测
+//!
+//! 按当前市场状态选用的子策略调整预测结果：震荡市把点预测改为沿半衰期向均线回归的路径，
+//! 高波动环境下压低置信度。子策略映射见 `config::regime`。
+
+use crate::prediction::analysis::{MarketRegimeAnalysis, VolatilityLevel};
+use crate::prediction::model::inference::signal_from_change_percent;
+use crate::prediction::strategy::mean_reversion::MeanReversionSignal;
+use crate::prediction::types::{PredictionInterval, PredictionResponse};
+
+/// 高波动环境下的置信度上限
+pub const HIGH_VOLATILITY_CONFIDENCE_CAP: f64 = 0.5;
+
+/// 是否属于高波动环境
+pub fn is_high_volatility(regime: &MarketRegimeAnalysis) -> bool {
+    matches!(
+        regime.volatility_level,
+        VolatilityLevel::High | VolatilityLevel::VeryHigh
+    )
+}
+
+/// 区间整体平移：涨跌幅加 `shift_percent` 个百分点，价格加 `shift_price`
+fn shift_interval(interval: &mut PredictionInterval, shift_percent: f64, shift_price: f64) {
+    interval.lower_change_percent += shift_percent;
+    interval.upper_change_percent += shift_percent;
+    interval.lower_price += shift_price;
+    interval.upper_price += shift_price;
+}
+
+/// 把逐日点预测替换为均值回归路径：第 t 日偏离 = 当前偏离 × 0.5^(t / 半衰期)。
+/// 区间带宽度来自波动率估计，随点预测整体平移，仍以新路径为中心。
+///
+/// 无法估计半衰期（不具回归特性）或缺少发起日真实价时保持原预测，返回是否已调整。
+pub fn apply_mean_reversion_path(
+    response: &mut PredictionResponse,
+    signal: &MeanReversionSignal,
+) -> bool {
+    let Some(base_price) = response.last_real_data.as_ref().map(|last| last.price) else {
+        return false;
+    };
+    if signal.estimated_reversion_days == 0 || base_price <= 0.0 || signal.reversion_target <= 0.0 {
+        return false;
+    }
+    let half_life = signal.estimated_reversion_days as f64;
+    let gap = base_price - signal.reversion_target;
+    for (i, prediction) in response.predictions.iter_mut().enumerate() {
+        let days = (i + 1) as f64;
+        let price = signal.reversion_target + gap * 0.5f64.powf(days / half_life);
+        let change_percent = (price / base_price - 1.0) * 100.0;
+        let shift_percent = change_percent - prediction.predicted_change_percent;
+        let shift_price = price - prediction.predicted_price;
+        for interval in [&mut prediction.interval, &mut prediction.stress_interval]
+            .into_iter()
+            .flatten()
+        {
+            shift_interval(interval, shift_percent, shift_price);
+        }
+        prediction.prediction_low += shift_price;
+        prediction.prediction_high += shift_price;
+        prediction.predicted_price = price;
+        prediction.predicted_change_percent = change_percent;
+        prediction.trading_signal = Some(signal_from_change_percent(change_percent).to_string());
+        prediction.prediction_reason = Some(format!(
+            "震荡市均值回归：现价偏离 MA20 {:.2} 倍标准差，按半衰期 {} 日向 {:.2} 回归",
+            signal.distance_std, signal.estimated_reversion_days, signal.reversion_target
+        ));
+    }
+    true
+}
+
+/// 置信度与信号强度不超过 `cap`
+pub fn cap_prediction_confidence(response: &mut PredictionResponse, cap: f64) {
+    for prediction in &mut response.predictions {
+        prediction.confidence = prediction.confidence.min(cap);
+        if let Some(strength) = prediction.signal_strength.as_mut() {
+            *strength = strength.min(cap);
+        }
+    }
+}
+
+/// 在各预测的关键因素中注明市场状态与所用子策略
+pub fn annotate_regime_strategy(
+    response: &mut PredictionResponse,
+    regime: &MarketRegimeAnalysis,
+    strategy: &str,
+) {
+    let note = format!(
+        "市场状态: {}（波动率{}），采用 {strategy} 子策略",
+        regime.regime.to_string(),
+        regime.volatility_level.to_string()
+    );
+    for prediction in &mut response.predictions {
+        prediction
+            .key_factors
+            .get_or_insert_with(Vec::new)
+            .push(note.clone());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prediction::types::{LastRealData, Prediction};
+
+    fn response(days: usize) -> PredictionResponse {
+        let predictions = (0..days)
+            .map(|i| Prediction {
+                target_date: format!("2026-01-0{}", i + 2),
+                predicted_price: 11.0,
+                predicted_change_percent: (11.0 / 12.0 - 1.0) * 100.0,
+                confidence: 0.8,
+                trading_signal: Some("看涨".to_string()),
+                signal_strength: Some(0.8),
+                technical_indicators: None,
+                prediction_reason: None,
+                key_factors: None,
+                interval: Some(PredictionInterval {
+                    confidence: 0.8,
+                    lower_change_percent: (10.5 / 12.0 - 1.0) * 100.0,
+                    upper_change_percent: (11.5 / 12.0 - 1.0) * 100.0,
+                    lower_price: 10.5,
+                    upper_price: 11.5,
+                    method: "volatility".to_string(),
+                    lookback_days: 20,
+                }),
+                stress_interval: None,
+                explanation: None,
+                prediction_low: 10.5,
+                prediction_high: 11.5,
+            })
+            .collect();
+        PredictionResponse {
+            predictions,
+            last_real_data: Some(LastRealData {
+                date: "2026-01-01".to_string(),
+                price: 12.0,
+                change_percent: 0.0,
+            }),
+            diagnostics: None,
+            data_warning: None,
+        }
+    }
+
+    #[test]
+    fn test_mean_reversion_path_halves_gap_each_half_life() {
+        let mut reverted = response(4);
+        let signal = MeanReversionSignal {
+            triggered: true,
+            direction: "看跌回归".to_string(),
+            reversion_target: 10.0,
+            distance_std: 2.5,
+            estimated_reversion_days: 2,
+            signal_strength: 0.8,
+        };
+        assert!(apply_mean_reversion_path(&mut reverted, &signal));
+        let prices: Vec<f64> = reverted.predictions.iter().map(|p| p.predicted_price).collect();
+        assert!((prices[1] - 11.0).abs() < 1e-9);
+        assert!((prices[3] - 10.5).abs() < 1e-9);
+        assert!(prices.windows(2).all(|w| w[1] < w[0]));
+        assert_eq!(reverted.predictions[0].trading_signal.as_deref(), Some("看跌"));
+        // 区间带随点预测平移：第 4 日点预测 11.0 → 10.5，原 10.5~11.5 区间同步下移 0.5
+        let band = &reverted.predictions[3];
+        assert!((band.prediction_low - 10.0).abs() < 1e-9);
+        assert!((band.prediction_high - 11.0).abs() < 1e-9);
+        let interval = band.interval.as_ref().unwrap();
+        assert!((interval.lower_price - 10.0).abs() < 1e-9);
+        assert!((interval.upper_change_percent - (11.0 / 12.0 - 1.0) * 100.0).abs() < 1e-9);
+        assert!(band.stress_interval.is_none());
+
+        let mut untouched = response(2);
+        let no_half_life = MeanReversionSignal {
+            estimated_reversion_days: 0,
+            ..signal
+        };
+        assert!(!apply_mean_reversion_path(&mut untouched, &no_half_life));
+        assert_eq!(untouched.predictions[0].predicted_price, 11.0);
+    }
+
+    #[test]
+    fn test_cap_prediction_confidence() {
+        let mut response = response(2);
+        response.predictions[1].confidence = 0.3;
+        cap_prediction_confidence(&mut response, HIGH_VOLATILITY_CONFIDENCE_CAP);
+        assert_eq!(response.predictions[0].confidence, 0.5);
+        assert_eq!(response.predictions[0].signal_strength, Some(0.5));
+        assert_eq!(response.predictions[1].confidence, 0.3);
+    }
+}
@@ -1,8 +1,11 @@
 //! 预测模块类型定义
 
 use serde::{Deserialize, Serialize};
-use crate::prediction::analysis::{PatternRecognition, SupportResistance};
-use crate::prediction::strategy::{MultiFactorScore, MultiTimeframeSignal};
+use crate::prediction::analysis::{DivergenceAnalysis, PatternRecognition, SupportResistance};
+use crate::prediction::strategy::{
+    DrawdownAnalysis, GapPrediction, MeanReversionSignal, MultiFactorScore, MultiTimeframeSignal, PredictionExplanation,
+    PredictionHorizonRecommendation, TrailingStopResult,
+};
 
 // =============================================================================
 // 预测请求/响应类型
@@ -19,6 +22,7 @@ pub struct TrainingRequest {
     pub target: String,
     pub prediction_days: usize,
     pub model_type: String,
+    /// 训练轮数，低于 50 时按 50 轮训练
     pub epochs: usize,
     pub batch_size: usize,
     pub learning_rate: f64,
@@ -33,6 +37,69 @@ pub struct PredictionRequest {
     pub model_name: Option<String>,
     pub prediction_days: usize,
     pub use_candle: bool,
+    /// 历史数据过期时先刷新再预测（默认仅在响应中提示）
+    #[serde(default)]
+    pub refresh_if_stale: bool,
+    /// 拉取个股新闻情绪计入情绪因子（联网请求，最长等待 5 秒）；
+    /// 默认关闭，批量、对比与测试路径不开启
+    #[serde(default)]
+    pub include_news: bool,
+    /// 查询预测期内的公司事件（联网请求，按股票每日缓存），据此抑制信号、提示风险；
+    /// 默认关闭，对比与测试路径不开启
+    #[serde(default)]
+    pub include_events: bool,
+}
+
+/// 批量预测配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BatchPredictionConfig {
+    /// 同时进行的预测数
+    pub max_concurrent: usize,
+    /// 股票有已训练模型时使用 Candle 模型，否则直接使用规则引擎
+    pub use_model_if_available: bool,
+    /// 模型预测失败时改用规则引擎
+    pub fallback_to_technical: bool,
+}
+
+impl Default for BatchPredictionConfig {
+    fn default() -> Self {
+        Self {
+            max_concurrent: 4,
+            use_model_if_available: true,
+            fallback_to_technical: true,
+        }
+    }
+}
+
+/// 批量预测中单只股票的结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchPredictionResult {
+    pub stock_code: String,
+    pub result: Result<PredictionResponse, String>,
+}
+
+/// 多股预测对比中单只股票的摘要（取首日预测）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StockPredictionSummary {
+    pub stock_code: String,
+    pub predicted_return_pct: f64,
+    pub confidence: f64,
+    pub signal: String,
+    pub multi_factor_score: f64,
+    pub risk_level: String,
+    /// 对比排名（1 为最优）
+    pub recommendation_rank: usize,
+}
+
+/// 背离扫描入选的股票
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DivergenceOpportunity {
+    pub stock_code: String,
+    pub divergence: DivergenceAnalysis,
+    pub current_price: f64,
+    /// 背离强度排名（1 为最强）
+    pub strength_rank: usize,
 }
 
 /// 纯技术分析请求
@@ -40,7 +107,9 @@ pub struct PredictionRequest {
 pub struct TechnicalOnlyRequest {
     pub stock_code: String,
     pub history_days: Option<usize>,
-    pub prediction_days: usize,
+    /// None 时按趋势与波动率自动推荐（见 `recommend_prediction_horizon`）
+    #[serde(default)]
+    pub prediction_days: Option<usize>,
 }
 
 // =============================================================================
@@ -66,6 +135,15 @@ pub struct Prediction {
     /// 95% 压力区间，用于观察低概率但影响较大的尾部波动。
     #[serde(default)]
     pub stress_interval: Option<PredictionInterval>,
+    /// 逐项信号贡献的结构化解释
+    #[serde(default)]
+    pub explanation: Option<PredictionExplanation>,
+    /// 80% 置信带下沿价格（同 `interval`，展开为平铺字段便于前端绘制扇形图；无区间时等于点预测价）
+    #[serde(default)]
+    pub prediction_low: f64,
+    /// 80% 置信带上沿价格
+    #[serde(default)]
+    pub prediction_high: f64,
 }
 
 /// 校准涨跌区间带。
@@ -121,6 +199,9 @@ pub struct PredictionResponse {
     /// 预测口径、风险事实与不确定性诊断。旧响应反序列化时允许缺省。
     #[serde(default)]
     pub diagnostics: Option<PredictionDiagnostics>,
+    /// 历史数据过期提示（见 `check_data_freshness`），数据新鲜时为 None
+    #[serde(default)]
+    pub data_warning: Option<String>,
 }
 
 /// 风险等级。它表示已触发事实规则的最高严重度，不是风险发生概率。
@@ -141,6 +222,24 @@ impl RiskLevel {
             Self::High => "高风险",
         }
     }
+
+    /// 由中文标签解析；兼容专业引擎风险评估输出的"中等风险"
+    pub fn from_label(label: &str) -> Option<Self> {
+        if label == "中等风险" {
+            return Some(Self::Medium);
+        }
+        [Self::Low, Self::Medium, Self::High]
+            .into_iter()
+            .find(|level| level.label() == label)
+    }
+
+    /// 上调一档（已是最高档时不变）
+    pub fn escalate(self) -> Self {
+        match self {
+            Self::Low => Self::Medium,
+            Self::Medium | Self::High => Self::High,
+        }
+    }
 }
 
 /// 风险来源分类。
@@ -240,6 +339,24 @@ pub struct ModelConfig {
     pub max_seq_len: usize,
 }
 
+/// 模型生命周期阶段：新训练的模型为候选，每只股票至多一个生产模型
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ModelStatus {
+    #[default]
+    Candidate,
+    Production,
+    Archived,
+}
+
+/// 训练超参数，随元数据保存，自动重训练时沿用
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct TrainingParams {
+    pub epochs: usize,
+    pub learning_rate: f64,
+    pub train_test_split: f64,
+}
+
 /// 模型信息
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ModelInfo {
@@ -258,6 +375,18 @@ pub struct ModelInfo {
     pub test_samples: Option<usize>,
     pub mae: Option<f64>,
     pub rmse: Option<f64>,
+    /// 生命周期阶段；旧元数据缺省为候选
+    #[serde(default)]
+    pub model_status: ModelStatus,
+    /// 最近一次（重）训练时间（Unix 秒）；旧元数据缺省，视同创建时间
+    #[serde(default)]
+    pub last_trained_at: Option<u64>,
+    /// 训练超参数；旧元数据缺省
+    #[serde(default)]
+    pub training_params: Option<TrainingParams>,
+    /// 训练时的特征集版本；旧元数据缺省，按不含 hurst60 的版本 1 加载
+    #[serde(default)]
+    pub feature_set_version: Option<u32>,
 }
 
 /// 训练结果
@@ -285,6 +414,79 @@ pub struct EvaluationResult {
     pub evaluation_date: String,
     pub evaluation_scope: String,
     pub evaluation_note: String,
+    /// 同一评估区间内标准 MACD 与 VWMACD 的方向命中率对比；样本不足时为 None
+    #[serde(default)]
+    pub macd_comparison: Option<MacdVariantComparison>,
+}
+
+/// 以 MACD 柱符号预测模型周期涨跌时，标准 MACD 与成交量加权 MACD 的方向命中率（0-1）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MacdVariantComparison {
+    pub samples: usize,
+    pub macd_direction_accuracy: f64,
+    pub vwmacd_direction_accuracy: f64,
+}
+
+/// 模型预测的特征归因
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PredictionExplanationDetail {
+    pub model_id: String,
+    /// 模型训练周期上的预期收益率（%）
+    pub predicted_return_pct: f64,
+    /// (特征名, 归因)：该特征 ±1 个标准差时模型输出之差的一半（收益率百分点），按绝对值降序
+    pub feature_attributions: Vec<(String, f64)>,
+    /// 归因与技术面原因合成的文字说明
+    pub narrative: String,
+}
+
+/// 共形校准结果
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ConformalCalibrationResult {
+    pub model_id: String,
+    /// 校准样本数（训练标签截止日之后、留出检验段之前）
+    pub calibration_samples: usize,
+    /// 留出检验样本数（样本外数据按时间的最后一段，不参与校准）
+    pub holdout_samples: usize,
+    /// 名义覆盖率 1 - alpha
+    pub coverage: f64,
+    /// 模型训练周期上的区间半宽（收益率 %）
+    pub half_width_percent: f64,
+    /// 留出检验样本上的经验覆盖率
+    pub empirical_coverage: f64,
+}
+
+// =============================================================================
+// 滚动窗口稳定性检验类型
+// =============================================================================
+
+/// 单个滚动窗口的样本外表现
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RollingWindowResult {
+    /// 样本外测试段起止日期（预测发起日）
+    pub test_start_date: String,
+    pub test_end_date: String,
+    pub test_samples: usize,
+    pub direction_accuracy: f64,
+    /// 周期收益率均方根误差（百分点）
+    pub rmse: f64,
+    /// 按预测方向做多/做空的年化夏普比率
+    pub sharpe: f64,
+}
+
+/// 滚动窗口稳定性报告
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RollingWindowReport {
+    pub stock_code: String,
+    pub window_size: usize,
+    pub step_size: usize,
+    pub prediction_days: usize,
+    pub windows: Vec<RollingWindowResult>,
+    /// (测试段起始日, 方向准确率)
+    pub time_series_of_accuracy: Vec<(String, f64)>,
+    pub mean_accuracy: f64,
+    /// 各窗口方向准确率的标准差，越小越稳定
+    pub stability_score: f64,
+    pub recommendation: String,
 }
 
 // =============================================================================
@@ -340,6 +542,14 @@ pub struct BacktestReport {
     pub average_interval_80_width: f64,
     #[serde(default)]
     pub average_stress_95_width: f64,
+    /// 本次回测的交易记录 ID，用于 `analyze_backtest_by_signal`
+    #[serde(default)]
+    pub backtest_id: String,
+    #[serde(default)]
+    pub trade_records: Vec<crate::prediction::backtest::attribution::BacktestTradeRecord>,
+    /// 按入场市场状态分组的交易表现（趋势市 / 震荡市下模型是否有效）
+    #[serde(default)]
+    pub regime_breakdown: Vec<crate::prediction::backtest::regime::BacktestRegimeBreakdown>,
 }
 
 /// 单次回测记录
@@ -406,7 +616,14 @@ pub struct BuySellPoint {
     pub point_type: String,
     pub signal_strength: f64,
     pub price_level: f64,
+    /// 止损价：按 ATR 跟踪止损距离相对 `price_level` 放置（ATR 不可用时回退固定百分比）
     pub stop_loss: f64,
+    /// ATR 跟踪止损明细（相对当前价计算，止损在现价下方）；仅买点提供，卖点为 None
+    #[serde(default)]
+    pub trailing_stop: Option<TrailingStopResult>,
+    /// 最新K线距最近摆动点的斐波那契时间周期的K线数；找不到摆动点时为 None
+    #[serde(default)]
+    pub time_cycle_proximity: Option<usize>,
     pub take_profit: Vec<f64>,
     pub risk_reward_ratio: f64,
     pub reasons: Vec<String>,
@@ -426,6 +643,21 @@ pub struct ProfessionalPrediction {
     pub candle_patterns: Vec<PatternRecognition>,
     pub volume_analysis: VolumeAnalysisInfo,
     pub multi_factor_score: MultiFactorScore,
+    /// 均值回归机会（价格偏离 20 日均线超过 2 倍标准差时触发）
+    #[serde(default)]
+    pub mean_reversion: Option<MeanReversionSignal>,
+    /// 流动性极低（近 20 日日均成交额不足 2000 万元）时的风险提示
+    #[serde(default)]
+    pub liquidity_warning: Option<String>,
+    /// 按趋势与波动率推荐的预测周期
+    #[serde(default)]
+    pub horizon_recommendation: Option<PredictionHorizonRecommendation>,
+    /// 回撤特征；深度回撤且历史修复缓慢时风险等级为高风险
+    #[serde(default)]
+    pub drawdown: Option<DrawdownAnalysis>,
+    /// 次日开盘跳空预测，独立于多日价格预测
+    #[serde(default)]
+    pub opening_gap: Option<GapPrediction>,
 }
 
 /// 量价/指标背离概要
@@ -442,6 +674,7 @@ pub struct VolumePriceDivergence {
 pub struct VolumeAnalysisInfo {
     pub volume_trend: String,
     pub volume_price_sync: bool,
+    /// Chaikin 振荡器（按均量归一化，见 `indicators::adl`）：正值吸筹、负值派发
     pub accumulation_signal: f64,
     pub obv_trend: String,
 }
@@ -0,0 +1,197 @@
+//! 个股预警监控服务
+//!
+//! 后台定期刷新有活跃预警的股票行情，经 [`compute_stock_signals`] 计算最新K线的
+//! 技术指标与背离，命中条件时发出 `alert_triggered` 事件（由通知模块转为系统通知）
+//! 并停用该预警。
+
+use crate::db::models::{AlertType, StockAlert};
+use crate::db::repository;
+use crate::error::AppError;
+use crate::prediction::analysis::divergence::{DivergenceSignal, DivergenceStrength, DivergenceType};
+use crate::prediction::analysis::stock_signals::StockSignals;
+use crate::services::historical::refresh_stock_full;
+use crate::services::prediction::compute_stock_signals;
+use log::{error, warn};
+use serde::Serialize;
+use sqlx::SqlitePool;
+use std::collections::BTreeMap;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+
+/// 预警触发事件名
+pub const ALERT_TRIGGERED_EVENT: &str = "alert_triggered";
+
+/// 后台预警检查间隔
+pub const ALERT_POLL_INTERVAL: Duration = Duration::from_secs(30 * 60);
+
+/// `alert_triggered` 事件负载
+#[derive(Debug, Clone, Serialize)]
+pub struct AlertTriggeredEvent {
+    pub alert_id: i64,
+    pub stock_code: String,
+    pub title: String,
+    pub body: String,
+}
+
+/// 背离是否满足预警条件：类型一致且强度不低于最低要求
+fn divergence_matches(
+    signal: Option<&DivergenceSignal>,
+    divergence_type: DivergenceType,
+    min_strength: DivergenceStrength,
+) -> Option<&DivergenceSignal> {
+    signal.filter(|signal| {
+        signal.divergence_type == divergence_type
+            && signal.strength.to_score() >= min_strength.to_score()
+    })
+}
+
+/// 判断预警条件是否命中，命中时返回通知正文
+pub fn evaluate_alert(alert_type: &AlertType, signals: &StockSignals) -> Option<String> {
+    let divergence = &signals.signals.divergence;
+    match *alert_type {
+        AlertType::PriceAbove { price } => (signals.close >= price)
+            .then(|| format!("收盘价 {:.2} 已突破 {price:.2}", signals.close)),
+        AlertType::PriceBelow { price } => (signals.close <= price)
+            .then(|| format!("收盘价 {:.2} 已跌破 {price:.2}", signals.close)),
+        AlertType::RsiDivergence {
+            divergence_type,
+            min_strength,
+        } => divergence_matches(divergence.rsi_divergence.as_ref(), divergence_type, min_strength)
+            .map(|signal| signal.description.clone()),
+        AlertType::MacdDivergence {
+            divergence_type,
+            min_strength,
+        } => divergence_matches(divergence.macd_divergence.as_ref(), divergence_type, min_strength)
+            .map(|signal| signal.description.clone()),
+    }
+}
+
+fn alert_title(alert: &StockAlert) -> String {
+    let kind = match alert.alert_type {
+        AlertType::PriceAbove { .. } | AlertType::PriceBelow { .. } => "价格预警".to_string(),
+        AlertType::RsiDivergence { divergence_type, .. } => {
+            format!("RSI {}", divergence_type.to_string())
+        }
+        AlertType::MacdDivergence { divergence_type, .. } => {
+            format!("MACD {}", divergence_type.to_string())
+        }
+    };
+    format!("{} {kind}", alert.stock_code)
+}
+
+/// 检查全部活跃预警，返回本次触发的数量。
+///
+/// 每只股票先尝试刷新行情（失败时沿用本地K线），只计算一次信号供其全部预警共用。
+pub async fn check_alerts(app: &AppHandle, pool: &SqlitePool) -> Result<usize, AppError> {
+    let mut by_stock: BTreeMap<String, Vec<StockAlert>> = BTreeMap::new();
+    for alert in repository::get_active_alerts(pool).await? {
+        by_stock.entry(alert.stock_code.clone()).or_default().push(alert);
+    }
+
+    let mut triggered = 0;
+    for (stock_code, alerts) in by_stock {
+        if let Err(e) = refresh_stock_full(&stock_code, pool).await {
+            error!("预警行情刷新失败 {stock_code}: {e}");
+        }
+        let signals = match compute_stock_signals(&stock_code, pool).await {
+            Ok(signals) => signals,
+            Err(e) => {
+                warn!("预警信号计算失败 {stock_code}: {e}");
+                continue;
+            }
+        };
+        for alert in alerts {
+            let Some(body) = evaluate_alert(&alert.alert_type, &signals) else {
+                continue;
+            };
+            repository::mark_alert_triggered(pool, alert.id).await?;
+            let event = AlertTriggeredEvent {
+                alert_id: alert.id,
+                stock_code: stock_code.clone(),
+                title: alert_title(&alert),
+                body,
+            };
+            if let Err(e) = app.emit(ALERT_TRIGGERED_EVENT, event) {
+                warn!("预警事件推送失败: {e}");
+            }
+            triggered += 1;
+        }
+    }
+    Ok(triggered)
+}
+
+/// 启动后台预警监控循环（失败仅打印，不中断循环）
+pub fn spawn_alert_monitor(app: AppHandle, pool: SqlitePool) {
+    tauri::async_runtime::spawn(async move {
+        let mut interval = tokio::time::interval(ALERT_POLL_INTERVAL);
+        loop {
+            interval.tick().await;
+            if let Err(e) = check_alerts(&app, &pool).await {
+                error!("预警检查失败: {e}");
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prediction::analysis::divergence::DivergenceAnalysis;
+    use crate::prediction::analysis::stock_signals::TechnicalSignalSet;
+    use crate::prediction::indicators::TechnicalIndicatorValues;
+    use chrono::NaiveDate;
+
+    fn signals(close: f64, rsi_divergence: Option<DivergenceSignal>) -> StockSignals {
+        StockSignals {
+            stock_code: "600000".to_string(),
+            date: NaiveDate::from_ymd_opt(2025, 6, 16).unwrap(),
+            close,
+            signals: TechnicalSignalSet {
+                indicators: TechnicalIndicatorValues::default(),
+                divergence: DivergenceAnalysis {
+                    rsi_divergence,
+                    ..DivergenceAnalysis::default()
+                },
+            },
+        }
+    }
+
+    fn divergence(divergence_type: DivergenceType, strength: DivergenceStrength) -> DivergenceSignal {
+        DivergenceSignal {
+            divergence_type,
+            indicator: "RSI".to_string(),
+            strength,
+            confidence: 0.7,
+            price_change: -5.0,
+            indicator_change: 3.0,
+            duration_bars: 12,
+            description: "RSI 底背离".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_price_alerts() {
+        let above = AlertType::PriceAbove { price: 10.0 };
+        let below = AlertType::PriceBelow { price: 9.0 };
+
+        assert!(evaluate_alert(&above, &signals(10.5, None)).is_some());
+        assert!(evaluate_alert(&above, &signals(9.5, None)).is_none());
+        assert!(evaluate_alert(&below, &signals(8.8, None)).is_some());
+    }
+
+    #[test]
+    fn test_rsi_divergence_alert_requires_type_and_min_strength() {
+        let alert = AlertType::RsiDivergence {
+            divergence_type: DivergenceType::RegularBullish,
+            min_strength: DivergenceStrength::Moderate,
+        };
+        let strong = divergence(DivergenceType::RegularBullish, DivergenceStrength::Strong);
+        let weak = divergence(DivergenceType::RegularBullish, DivergenceStrength::Weak);
+        let bearish = divergence(DivergenceType::RegularBearish, DivergenceStrength::Strong);
+
+        assert!(evaluate_alert(&alert, &signals(10.0, Some(strong))).is_some());
+        assert!(evaluate_alert(&alert, &signals(10.0, Some(weak))).is_none());
+        assert!(evaluate_alert(&alert, &signals(10.0, Some(bearish))).is_none());
+        assert!(evaluate_alert(&alert, &signals(10.0, None)).is_none());
+    }
+}
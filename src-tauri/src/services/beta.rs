@@ -0,0 +1,171 @@
+//! Beta 系数服务
+//!
+//! 以指数收益为市场基准，计算个股的 Beta / Alpha / R² / 相关系数。
+//! 指数行情不随股票刷新入库，需以精确代码（如 sh000001）导入 historical_data。
+
+use crate::db::{repository, DbPool};
+use crate::error::AppError;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// 默认市场基准：上证综指
+pub const DEFAULT_BETA_INDEX: &str = "sh000001";
+
+/// 预测链路计算 Beta 的默认回看交易日数
+pub const DEFAULT_BETA_LOOKBACK_DAYS: usize = 250;
+
+/// 计算 Beta 至少需要的对齐收益样本数
+pub const MIN_BETA_SAMPLES: usize = 30;
+
+/// Beta 分析结果
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BetaAnalysis {
+    pub beta: f64,
+    /// 日度 Alpha（回归截距，收益率口径）
+    pub alpha: f64,
+    pub r_squared: f64,
+    pub correlation: f64,
+}
+
+/// 由等长的个股/指数收益序列计算 Beta = Cov(s, m) / Var(m)
+pub fn calculate_beta(stock_returns: &[f64], index_returns: &[f64]) -> BetaAnalysis {
+    let n = stock_returns.len().min(index_returns.len());
+    if n < 2 {
+        return BetaAnalysis::default();
+    }
+    let (stock, index) = (&stock_returns[..n], &index_returns[..n]);
+    let nf = n as f64;
+    let mean_s = stock.iter().sum::<f64>() / nf;
+    let mean_m = index.iter().sum::<f64>() / nf;
+
+    let (mut cov, mut var_m, mut var_s) = (0.0, 0.0, 0.0);
+    for (s, m) in stock.iter().zip(index) {
+        let (ds, dm) = (s - mean_s, m - mean_m);
+        cov += ds * dm;
+        var_m += dm * dm;
+        var_s += ds * ds;
+    }
+    if var_m <= 1e-12 {
+        return BetaAnalysis::default();
+    }
+
+    let beta = cov / var_m;
+    let correlation = if var_s > 1e-12 {
+        cov / (var_m.sqrt() * var_s.sqrt())
+    } else {
+        0.0
+    };
+    BetaAnalysis {
+        beta,
+        alpha: mean_s - beta * mean_m,
+        r_squared: correlation * correlation,
+        correlation,
+    }
+}
+
+fn daily_returns(closes: &[f64]) -> Vec<f64> {
+    closes
+        .windows(2)
+        .map(|w| if w[0] > 0.0 { w[1] / w[0] - 1.0 } else { 0.0 })
+        .collect()
+}
+
+/// 获取指数最近 `days` 个交易日的日收益率（时间正序），每个收益标注其所在交易日
+pub async fn fetch_index_returns(
+    index_code: &str,
+    days: usize,
+    pool: &DbPool,
+) -> Result<Vec<(NaiveDate, f64)>, AppError> {
+    let closes = repository::get_recent_closes_exact(index_code, days + 1, pool).await?;
+    let values: Vec<f64> = closes.iter().map(|(_, close)| *close).collect();
+    Ok(closes
+        .iter()
+        .skip(1)
+        .map(|(date, _)| *date)
+        .zip(daily_returns(&values))
+        .collect())
+}
+
+/// 个股相邻两根K线恰好对应指数相邻两个交易日时，配对两者的日收益；
+/// 停牌后复牌的跨多日收益不与指数单日收益配对
+fn align_daily_returns(stock: &[(NaiveDate, f64)], index_returns: &[(NaiveDate, f64)]) -> Vec<(f64, f64)> {
+    let position: HashMap<NaiveDate, usize> = index_returns
+        .iter()
+        .enumerate()
+        .map(|(k, (date, _))| (*date, k))
+        .collect();
+    stock
+        .windows(2)
+        .filter_map(|pair| {
+            let (prev_date, prev_close) = pair[0];
+            let (date, close) = pair[1];
+            let k = *position.get(&date)?;
+            if k == 0 || index_returns[k - 1].0 != prev_date || prev_close <= 0.0 {
+                return None;
+            }
+            Some((close / prev_close - 1.0, index_returns[k].1))
+        })
+        .collect()
+}
+
+/// 按交易日对齐个股与指数日收益后计算 Beta；对齐样本不足时返回 None
+pub async fn calculate_stock_beta(
+    stock_code: &str,
+    index_code: &str,
+    lookback_days: usize,
+    pool: &DbPool,
+) -> Result<Option<BetaAnalysis>, AppError> {
+    let stock: Vec<(NaiveDate, f64)> =
+        repository::get_recent_historical_data(stock_code, lookback_days + 1, pool)
+            .await?
+            .into_iter()
+            .map(|bar| (bar.date, bar.close))
+            .collect();
+    let index_returns = fetch_index_returns(index_code, lookback_days, pool).await?;
+
+    let aligned = align_daily_returns(&stock, &index_returns);
+    if aligned.len() < MIN_BETA_SAMPLES {
+        return Ok(None);
+    }
+    let stock_returns: Vec<f64> = aligned.iter().map(|(s, _)| *s).collect();
+    let index_returns: Vec<f64> = aligned.iter().map(|(_, m)| *m).collect();
+    Ok(Some(calculate_beta(&stock_returns, &index_returns)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_calculate_beta_recovers_linear_relation() {
+        let index = [0.01, -0.02, 0.015, 0.005, -0.01, 0.02];
+        let stock: Vec<f64> = index.iter().map(|m| 0.001 + 1.5 * m).collect();
+        let analysis = calculate_beta(&stock, &index);
+
+        assert!((analysis.beta - 1.5).abs() < 1e-9);
+        assert!((analysis.alpha - 0.001).abs() < 1e-9);
+        assert!((analysis.correlation - 1.0).abs() < 1e-9);
+        assert!((analysis.r_squared - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_align_daily_returns_skips_suspended_gaps() {
+        let day = |d: u32| NaiveDate::from_ymd_opt(2025, 3, d).unwrap();
+        let index_returns = [(day(4), 0.01), (day(5), -0.02), (day(6), 0.03), (day(7), 0.01)];
+        // 5 日停牌：6 日相对 4 日的收益跨两个交易日，不配对
+        let stock = [(day(3), 10.0), (day(4), 10.2), (day(6), 10.5), (day(7), 10.71)];
+        let aligned = align_daily_returns(&stock, &index_returns);
+
+        assert_eq!(aligned.len(), 1);
+        assert!((aligned[0].0 - 0.02).abs() < 1e-9);
+        assert_eq!(aligned[0].1, 0.01);
+    }
+
+    #[test]
+    fn test_calculate_beta_flat_index_is_neutral() {
+        let analysis = calculate_beta(&[0.01, 0.02, -0.01], &[0.0, 0.0, 0.0]);
+        assert_eq!(analysis.beta, 0.0);
+        assert_eq!(analysis.r_squared, 0.0);
+    }
+}
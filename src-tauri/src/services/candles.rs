@@ -0,0 +1,197 @@
+//! K线图数据服务：日线直接输出，周线/月线按自然周/自然月聚合日线
+
+use crate::db::models::HistoricalData;
+use crate::db::repository;
+use crate::error::AppError;
+use chrono::Datelike;
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+
+/// K线周期
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CandlePeriod {
+    Daily,
+    Weekly,
+    Monthly,
+}
+
+impl CandlePeriod {
+    /// 解析前端周期参数："1d" / "1w" / "1mo"
+    pub fn parse(period: &str) -> Option<Self> {
+        match period.trim() {
+            "1d" => Some(Self::Daily),
+            "1w" => Some(Self::Weekly),
+            "1mo" => Some(Self::Monthly),
+            _ => None,
+        }
+    }
+
+    /// 每根K线大约包含的交易日数，用于估算需要读取的日线条数
+    fn trading_days(self) -> usize {
+        match self {
+            Self::Daily => 1,
+            Self::Weekly => 5,
+            Self::Monthly => 23,
+        }
+    }
+
+    /// 聚合分组键：同一键的日线归入同一根K线
+    fn bucket(self, bar: &HistoricalData) -> (i32, u32, u32) {
+        let date = bar.date;
+        match self {
+            Self::Daily => (date.year(), date.month(), date.day()),
+            Self::Weekly => {
+                let week = date.iso_week();
+                (week.year(), week.week(), 0)
+            }
+            Self::Monthly => (date.year(), date.month(), 0),
+        }
+    }
+}
+
+/// 图表用K线
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CandleStick {
+    /// 周期内最后一个交易日（YYYY-MM-DD）
+    pub date: String,
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+    pub volume: i64,
+    /// 相对上一根K线收盘价的涨跌幅(%)
+    pub change_pct: f64,
+}
+
+/// 将时间正序的日线聚合为指定周期K线：
+/// 开盘取首日开盘、最高/最低取区间极值、收盘取末日收盘、成交量求和。
+/// 首根K线的前收盘由首日收盘价与涨跌额反推。
+pub fn aggregate_candles(bars: &[HistoricalData], period: CandlePeriod) -> Vec<CandleStick> {
+    let Some(first) = bars.first() else {
+        return Vec::new();
+    };
+    let mut prev_close = first.close - first.change;
+    let mut candles = Vec::new();
+
+    for group in bars.chunk_by(|a, b| period.bucket(a) == period.bucket(b)) {
+        let (head, tail) = (&group[0], &group[group.len() - 1]);
+        let close = tail.close;
+        let change_pct = if prev_close > 0.0 {
+            (close - prev_close) / prev_close * 100.0
+        } else {
+            0.0
+        };
+        candles.push(CandleStick {
+            date: tail.date.format("%Y-%m-%d").to_string(),
+            open: head.open,
+            high: group.iter().map(|b| b.high).fold(f64::NEG_INFINITY, f64::max),
+            low: group.iter().map(|b| b.low).fold(f64::INFINITY, f64::min),
+            close,
+            volume: group.iter().map(|b| b.volume).sum(),
+            change_pct,
+        });
+        prev_close = close;
+    }
+    candles
+}
+
+/// 读取最近 `limit` 根指定周期的K线（时间正序）
+pub async fn get_candles(
+    stock_code: &str,
+    period: CandlePeriod,
+    limit: usize,
+    pool: &SqlitePool,
+) -> Result<Vec<CandleStick>, AppError> {
+    if limit == 0 {
+        return Ok(Vec::new());
+    }
+    // 多读一个周期：最早的一组可能被截断，聚合后丢弃
+    let days = (limit + 1) * period.trading_days();
+    let bars = repository::get_recent_historical_data(stock_code, days, pool).await?;
+    let mut candles = aggregate_candles(&bars, period);
+    if bars.len() == days && period != CandlePeriod::Daily && !candles.is_empty() {
+        candles.remove(0);
+    }
+    let skip = candles.len().saturating_sub(limit);
+    Ok(candles.split_off(skip))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, NaiveDate};
+
+    /// 2024-01-29(周一) 起连续 10 个交易日（跳过周末），跨月、跨周
+    fn bars() -> Vec<HistoricalData> {
+        let start = NaiveDate::from_ymd_opt(2024, 1, 29).unwrap();
+        (0..14)
+            .map(|offset| start + Duration::days(offset))
+            .filter(|date| date.weekday().number_from_monday() <= 5)
+            .enumerate()
+            .map(|(i, date)| {
+                let close = 10.0 + i as f64;
+                HistoricalData {
+                    symbol: "600000".to_string(),
+                    date,
+                    open: close - 0.5,
+                    close,
+                    high: close + 1.0,
+                    low: close - 1.0,
+                    volume: 100 * (i as i64 + 1),
+                    amount: 0.0,
+                    amplitude: 0.0,
+                    turnover_rate: 0.0,
+                    volume_ratio: 0.0,
+                    change_percent: 0.0,
+                    change: 1.0,
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_period() {
+        assert_eq!(CandlePeriod::parse("1d"), Some(CandlePeriod::Daily));
+        assert_eq!(CandlePeriod::parse("1w"), Some(CandlePeriod::Weekly));
+        assert_eq!(CandlePeriod::parse("1mo"), Some(CandlePeriod::Monthly));
+        assert_eq!(CandlePeriod::parse("1y"), None);
+    }
+
+    #[test]
+    fn test_aggregate_weekly() {
+        let weekly = aggregate_candles(&bars(), CandlePeriod::Weekly);
+        assert_eq!(weekly.len(), 2);
+
+        let first = &weekly[0];
+        assert_eq!(first.date, "2024-02-02");
+        assert_eq!(first.open, 9.5);
+        assert_eq!(first.high, 15.0);
+        assert_eq!(first.low, 9.0);
+        assert_eq!(first.close, 14.0);
+        assert_eq!(first.volume, 1500);
+        // 前收盘 = 10 - 1
+        assert!((first.change_pct - (14.0 - 9.0) / 9.0 * 100.0).abs() < 1e-9);
+
+        let second = &weekly[1];
+        assert_eq!(second.date, "2024-02-09");
+        assert_eq!(second.open, 14.5);
+        assert_eq!(second.close, 19.0);
+        assert_eq!(second.volume, 4000);
+        assert!((second.change_pct - (19.0 - 14.0) / 14.0 * 100.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_aggregate_monthly_and_daily() {
+        let monthly = aggregate_candles(&bars(), CandlePeriod::Monthly);
+        assert_eq!(monthly.len(), 2);
+        assert_eq!(monthly[0].date, "2024-01-31");
+        assert_eq!(monthly[0].close, 12.0);
+        assert_eq!(monthly[0].volume, 600);
+        assert_eq!(monthly[1].open, 12.5);
+        assert_eq!(monthly[1].high, 20.0);
+
+        let daily = aggregate_candles(&bars(), CandlePeriod::Daily);
+        assert_eq!(daily.len(), 10);
+        assert!((daily[1].change_pct - 10.0).abs() < 1e-9);
+    }
+}
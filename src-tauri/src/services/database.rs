@@ -0,0 +1,199 @@
+//! 数据库性能检查服务
+//!
+//! 对常用查询计时，并根据空闲页占比判断是否需要 VACUUM；
+//! 后台定期探活连接池，健康状态变化时推送 `db_health_changed` 事件。
+
+use crate::db::connection::{check_pool_health, DbHealthState};
+use crate::db::repository::{self, DbStorageStats};
+use crate::error::AppError;
+use log::{info, warn};
+use serde::Serialize;
+use sqlx::SqlitePool;
+use std::future::Future;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter};
+
+/// 空闲页占比超过该值时建议 VACUUM
+const VACUUM_FREE_RATIO: f64 = 0.2;
+/// 空闲空间低于该值时不建议 VACUUM（重写整个文件的收益太小）
+const VACUUM_MIN_FREE_BYTES: i64 = 16 * 1024 * 1024;
+/// 超过该耗时视为慢查询
+const SLOW_QUERY_MS: f64 = 100.0;
+
+/// 单个查询的计时
+#[derive(Debug, Clone, Serialize)]
+pub struct QueryTiming {
+    pub name: String,
+    pub elapsed_ms: f64,
+    pub rows: usize,
+}
+
+/// 数据库性能报告
+#[derive(Debug, Clone, Serialize)]
+pub struct DbPerformanceReport {
+    /// 计时所用的样本股票（库中无历史K线时为空，仅统计行情列表）
+    pub sample_symbol: Option<String>,
+    pub query_timings: Vec<QueryTiming>,
+    pub storage: DbStorageStats,
+    pub database_size_bytes: i64,
+    /// 空闲页占比
+    pub free_ratio: f64,
+    pub vacuum_recommended: bool,
+    pub suggestions: Vec<String>,
+}
+
+/// 空闲页占比与是否建议 VACUUM
+pub fn vacuum_recommendation(stats: &DbStorageStats) -> (f64, bool) {
+    if stats.page_count <= 0 {
+        return (0.0, false);
+    }
+    let free_ratio = stats.freelist_count as f64 / stats.page_count as f64;
+    let free_bytes = stats.freelist_count * stats.page_size;
+    (
+        free_ratio,
+        free_ratio > VACUUM_FREE_RATIO && free_bytes >= VACUUM_MIN_FREE_BYTES,
+    )
+}
+
+async fn time_query<F>(name: &str, query: F) -> Result<QueryTiming, AppError>
+where
+    F: Future<Output = Result<usize, AppError>>,
+{
+    let start = Instant::now();
+    let rows = query.await?;
+    Ok(QueryTiming {
+        name: name.to_string(),
+        elapsed_ms: start.elapsed().as_secs_f64() * 1000.0,
+        rows,
+    })
+}
+
+/// 对常用查询计时并检查存储碎片
+pub async fn check_db_performance(pool: &SqlitePool) -> Result<DbPerformanceReport, AppError> {
+    let sample_symbol = repository::get_any_historical_symbol(pool).await?;
+    let mut query_timings = Vec::new();
+    if let Some(symbol) = &sample_symbol {
+        query_timings.push(
+            time_query("最近250根K线", async {
+                Ok(repository::get_recent_historical_data(symbol, 250, pool).await?.len())
+            })
+            .await?,
+        );
+        query_timings.push(
+            time_query("K线新鲜度检查", async {
+                // 仅计时；样本股票若无有效K线会返回错误，不影响报告
+                let fresh = repository::check_data_freshness(symbol, pool).await;
+                Ok(usize::from(fresh.is_ok()))
+            })
+            .await?,
+        );
+        query_timings.push(
+            time_query("最近预测记录", repository::count_recent_predictions(pool, symbol, 20))
+                .await?,
+        );
+    }
+    query_timings.push(
+        time_query("实时行情首页", async {
+            Ok(repository::get_realtime_list(None, 1, 50, pool).await?.0.len())
+        })
+        .await?,
+    );
+
+    let storage = repository::get_storage_stats(pool).await?;
+    let (free_ratio, vacuum_recommended) = vacuum_recommendation(&storage);
+    let mut suggestions: Vec<String> = query_timings
+        .iter()
+        .filter(|t| t.elapsed_ms > SLOW_QUERY_MS)
+        .map(|t| {
+            format!(
+                "{} 耗时 {:.0}ms，建议确认索引迁移已执行并运行 ANALYZE",
+                t.name, t.elapsed_ms
+            )
+        })
+        .collect();
+    if vacuum_recommended {
+        suggestions.push(format!(
+            "空闲页占 {:.0}%，建议执行 VACUUM 回收空间",
+            free_ratio * 100.0
+        ));
+    }
+
+    Ok(DbPerformanceReport {
+        sample_symbol,
+        query_timings,
+        database_size_bytes: storage.page_count * storage.page_size,
+        storage,
+        free_ratio,
+        vacuum_recommended,
+        suggestions,
+    })
+}
+
+/// 数据库健康状态变化事件名
+pub const DB_HEALTH_CHANGED_EVENT: &str = "db_health_changed";
+
+/// 连接池探活间隔
+pub const DB_HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
+/// `db_health_changed` 事件负载
+#[derive(Debug, Clone, Serialize)]
+pub struct DbHealthChangedEvent {
+    pub status: DbHealthState,
+    pub message: String,
+}
+
+/// 启动后台连接池探活：缓慢或异常时打印警告，状态变化（含恢复）时通知前端
+pub fn spawn_db_health_monitor(app: AppHandle, pool: SqlitePool) {
+    tauri::async_runtime::spawn(async move {
+        let mut interval = tokio::time::interval(DB_HEALTH_CHECK_INTERVAL);
+        let mut last_status = DbHealthState::Healthy;
+        loop {
+            interval.tick().await;
+            let health = check_pool_health(&pool).await;
+            match health.status {
+                DbHealthState::Healthy if last_status != DbHealthState::Healthy => {
+                    info!("数据库连接已恢复: {}", health.message)
+                }
+                DbHealthState::Healthy => {}
+                _ => warn!(
+                    "{}（连接数 {}，空闲 {}）",
+                    health.message, health.connections, health.idle_connections
+                ),
+            }
+            if health.status != last_status {
+                let event = DbHealthChangedEvent {
+                    status: health.status,
+                    message: health.message,
+                };
+                if let Err(e) = app.emit(DB_HEALTH_CHANGED_EVENT, event) {
+                    warn!("数据库健康事件推送失败: {e}");
+                }
+                last_status = health.status;
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats(page_count: i64, freelist_count: i64) -> DbStorageStats {
+        DbStorageStats {
+            page_size: 4096,
+            page_count,
+            freelist_count,
+        }
+    }
+
+    #[test]
+    fn test_vacuum_recommendation() {
+        // 40% 空闲、约 64MB 可回收
+        assert!(vacuum_recommendation(&stats(40_000, 16_000)).1);
+        // 占比高但空间太小
+        assert!(!vacuum_recommendation(&stats(100, 50)).1);
+        // 空间大但占比低
+        assert!(!vacuum_recommendation(&stats(1_000_000, 10_000)).1);
+        assert_eq!(vacuum_recommendation(&stats(0, 0)), (0.0, false));
+    }
+}
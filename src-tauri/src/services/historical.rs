@@ -3,6 +3,8 @@
 use crate::api::stock;
 use crate::db::{models::*, repository, DbPool};
 use crate::error::AppError;
+use chrono::Local;
+use log::warn;
 
 /// 单只股票一键全量刷新的结果汇总
 #[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
@@ -13,6 +15,8 @@ pub struct RefreshSummary {
     pub capital_updated: bool,
     /// 写入的基本面报告期行数
     pub fundamental_reports: u32,
+    /// 刷新后的 OHLCV 完整性检查结果（检查失败时为空）
+    pub integrity: Option<repository::IntegrityReport>,
 }
 
 /// **一键刷新单只股票的全部所需数据**：历史K线 + 股本/估值(PE/PB) + 基本面财务指标
@@ -24,9 +28,15 @@ pub async fn refresh_stock_full(symbol: &str, pool: &DbPool) -> Result<RefreshSu
     let api_data = stock::fetch_historical_data(symbol).await?;
     let bars = repository::batch_insert_historical_data(symbol, pool, api_data).await?;
 
-    // 2. 股本 + 估值（ssjy 一次返回 lt/sz/hs/lb/pe/sjl）
+    // 2. 股本 + 估值（ssjy 一次返回 lt/sz/hs/lb/pe/sjl）+ 行情快照
     let mut capital_updated = false;
     if let Ok(quote) = stock::fetch_stock_capital(symbol).await {
+        // 同一份 ssjy 行情顺带存为快照，供当日分时走势使用（失败不影响刷新）
+        if let Some(snapshot) = quote.to_realtime_quote(symbol, Local::now().naive_local()) {
+            if let Err(e) = repository::upsert_realtime_quote(pool, &snapshot).await {
+                warn!("保存 {symbol} 行情快照失败: {e}");
+            }
+        }
         if let Some(close) = repository::get_latest_close_price(symbol, pool).await? {
             if close > 0.0 {
                 let capital = StockCapital {
@@ -56,10 +66,28 @@ pub async fn refresh_stock_full(symbol: &str, pool: &DbPool) -> Result<RefreshSu
     // 4. 量比/换手率回填（量比始终可算；换手率依赖上面的股本）
     repository::backfill_volume_metrics(symbol, pool).await?;
 
+    // 5. OHLCV 完整性检查（只报告，修复由用户确认后执行）
+    let integrity = match repository::check_ohlcv_integrity(symbol, pool).await {
+        Ok(report) => {
+            if report.issues_found > 0 {
+                warn!(
+                    "{symbol} K线存在 {} 处逻辑错误（可修复 {} 处）",
+                    report.issues_found, report.issues_repaired
+                );
+            }
+            Some(report)
+        }
+        Err(e) => {
+            warn!("{symbol} K线完整性检查失败: {e}");
+            None
+        }
+    };
+
     Ok(RefreshSummary {
         bars,
         capital_updated,
         fundamental_reports,
+        integrity,
     })
 }
 
@@ -0,0 +1,137 @@
+//! 交易日志对账服务
+//!
+//! 定期为已过去的预测回填实际价格，使准确率统计基于真实样本外结果，
+//! 而非回测中的样本内指标。预测历史表的回填在同一循环中进行。
+
+use crate::db::repository;
+use crate::error::AppError;
+use crate::services::prediction::reconcile_prediction_history;
+use log::{info, warn};
+use sqlx::SqlitePool;
+use std::time::Duration;
+
+/// 后台对账间隔
+pub const JOURNAL_RECONCILE_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// 为尚无实际价格的日志条目回填操作日之后首个交易日的收盘价。
+///
+/// 本地历史数据尚未覆盖该交易日的条目保持待对账；返回本次回填的条目数。
+pub async fn reconcile_journal_entries(pool: &SqlitePool) -> Result<usize, AppError> {
+    let mut reconciled = 0;
+    for entry in repository::get_unreconciled_journal_entries(pool).await? {
+        let Some(next_day) = entry.action_date.succ_opt() else {
+            continue;
+        };
+        let bars = repository::get_historical_data(
+            &entry.stock_code,
+            &next_day.format("%Y-%m-%d").to_string(),
+            "9999-12-31",
+            pool,
+        )
+        .await?;
+        if let Some(bar) = bars.first() {
+            repository::set_journal_actual_price(pool, entry.id, bar.close).await?;
+            reconciled += 1;
+        }
+    }
+    Ok(reconciled)
+}
+
+/// 启动后台对账循环：交易日志与预测历史（见 [`reconcile_prediction_history`]）共用，
+/// 失败仅打印，不中断循环
+pub fn spawn_journal_reconciler(pool: SqlitePool) {
+    tauri::async_runtime::spawn(async move {
+        let mut interval = tokio::time::interval(JOURNAL_RECONCILE_INTERVAL);
+        loop {
+            interval.tick().await;
+            if let Err(e) = reconcile_journal_entries(&pool).await {
+                warn!("交易日志对账失败: {e}");
+            }
+            match reconcile_prediction_history(&pool).await {
+                Ok(0) => {}
+                Ok(n) => info!("已回填 {n} 条预测的实际价格"),
+                Err(e) => warn!("预测实际值回填失败: {e}"),
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::models::{HistoricalData, NewTradeJournalEntry};
+    use chrono::NaiveDate;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    async fn journal_pool() -> SqlitePool {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("应创建内存 SQLite");
+        for sql in [
+            include_str!("../../migrations/01_create_tables.sql"),
+            include_str!("../../migrations/03_volume_metrics.sql"),
+            include_str!("../../migrations/10_trade_journal.sql"),
+        ] {
+            for statement in sql.split(';').map(str::trim).filter(|s| !s.is_empty()) {
+                sqlx::query(statement)
+                    .execute(&pool)
+                    .await
+                    .expect("应创建交易日志测试表");
+            }
+        }
+        pool
+    }
+
+    fn bar(date: &str, close: f64) -> HistoricalData {
+        HistoricalData {
+            symbol: "600000".to_string(),
+            date: NaiveDate::parse_from_str(date, "%Y-%m-%d").unwrap(),
+            open: close,
+            close,
+            high: close,
+            low: close,
+            volume: 1000,
+            amount: close * 1000.0,
+            amplitude: 0.0,
+            turnover_rate: 0.0,
+            volume_ratio: 0.0,
+            change_percent: 0.0,
+            change: 0.0,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_reconcile_fills_next_trading_day_close() {
+        let pool = journal_pool().await;
+        repository::upsert_historical_data(
+            "600000",
+            &pool,
+            &[bar("2026-03-06", 10.0), bar("2026-03-09", 10.5)],
+        )
+        .await
+        .unwrap();
+
+        let entry = |date: &str| NewTradeJournalEntry {
+            stock_code: "600000".to_string(),
+            action_date: NaiveDate::parse_from_str(date, "%Y-%m-%d").unwrap(),
+            predicted_signal: "买入".to_string(),
+            predicted_price: 10.2,
+            action_taken: "买入".to_string(),
+            notes: None,
+        };
+        let reconcilable = repository::insert_journal_entry(&pool, &entry("2026-03-06"))
+            .await
+            .unwrap();
+        repository::insert_journal_entry(&pool, &entry("2026-03-09"))
+            .await
+            .unwrap();
+
+        assert_eq!(reconcile_journal_entries(&pool).await.unwrap(), 1);
+        let entries = repository::get_journal_entries(Some("600000"), &pool).await.unwrap();
+        let filled = entries.iter().find(|e| e.id == reconcilable).unwrap();
+        assert_eq!(filled.actual_price, Some(10.5));
+        assert_eq!(repository::get_unreconciled_journal_entries(&pool).await.unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,161 @@
+//! 流动性风险评分
+//!
+//! 以近 20 日成交额、换手率与 Amihud 非流动性指标衡量个股流动性。
+//! 成交稀少的小盘股价格易被少量资金推动，技术信号可靠性低，预测时需降低置信度。
+
+use serde::{Deserialize, Serialize};
+
+/// 统计窗口（交易日）
+pub const LIQUIDITY_WINDOW: usize = 20;
+
+/// 极低流动性股票的预测置信度上限
+pub const VERY_LOW_LIQUIDITY_CONFIDENCE_CAP: f64 = 0.5;
+
+/// 日均成交额分档阈值（元）：≥5 亿为高、≥1 亿为中、≥2000 万为低，其余为极低
+const HIGH_TURNOVER_AMOUNT: f64 = 5.0e8;
+const MEDIUM_TURNOVER_AMOUNT: f64 = 1.0e8;
+const LOW_TURNOVER_AMOUNT: f64 = 2.0e7;
+
+/// 流动性等级
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum LiquidityGrade {
+    VeryLow,
+    Low,
+    Medium,
+    High,
+}
+
+impl LiquidityGrade {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::High => "高",
+            Self::Medium => "中",
+            Self::Low => "低",
+            Self::VeryLow => "极低",
+        }
+    }
+
+    fn from_daily_amount(amount: f64) -> Self {
+        if amount >= HIGH_TURNOVER_AMOUNT {
+            Self::High
+        } else if amount >= MEDIUM_TURNOVER_AMOUNT {
+            Self::Medium
+        } else if amount >= LOW_TURNOVER_AMOUNT {
+            Self::Low
+        } else {
+            Self::VeryLow
+        }
+    }
+}
+
+/// 流动性评分
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LiquidityScore {
+    /// 日均成交额（元）= 收盘价 × 成交量（股）
+    pub avg_daily_turnover: f64,
+    /// 日均换手率（%），流通股本未知时为 None
+    pub turnover_rate: Option<f64>,
+    /// Amihud 非流动性：日均 |收益率(%)| / 成交额(亿元)，越大价格冲击越强
+    pub amihud_ratio: f64,
+    pub liquidity_grade: LiquidityGrade,
+}
+
+impl LiquidityScore {
+    /// 极低流动性时的风险提示
+    pub fn warning(&self) -> Option<String> {
+        (self.liquidity_grade == LiquidityGrade::VeryLow).then(|| {
+            format!(
+                "流动性极低：近 {LIQUIDITY_WINDOW} 日日均成交额 {:.0} 万元，价格易受少量资金影响，预测置信度已限制在 {:.0}% 以内",
+                self.avg_daily_turnover / 1.0e4,
+                VERY_LOW_LIQUIDITY_CONFIDENCE_CAP * 100.0
+            )
+        })
+    }
+}
+
+/// 由时间正序的成交量（股）与收盘价计算近 20 日流动性评分。
+///
+/// 等级按日均成交额划分；成交量与价格长度不一致时按较短者对齐末尾。
+pub fn calculate_liquidity_score(
+    volumes: &[i64],
+    prices: &[f64],
+    free_float_shares: Option<i64>,
+) -> LiquidityScore {
+    let len = volumes.len().min(prices.len());
+    let volumes = &volumes[volumes.len() - len..];
+    let prices = &prices[prices.len() - len..];
+    let start = len.saturating_sub(LIQUIDITY_WINDOW);
+
+    let amounts: Vec<f64> = (start..len)
+        .map(|i| prices[i] * volumes[i] as f64)
+        .collect();
+    let avg_daily_turnover = if amounts.is_empty() {
+        0.0
+    } else {
+        amounts.iter().sum::<f64>() / amounts.len() as f64
+    };
+
+    let turnover_rate = free_float_shares.filter(|&shares| shares > 0).map(|shares| {
+        let avg_volume =
+            volumes[start..].iter().map(|&v| v as f64).sum::<f64>() / (len - start).max(1) as f64;
+        avg_volume / shares as f64 * 100.0
+    });
+
+    // 零成交日（停牌）无价格冲击可言，不计入
+    let impacts: Vec<f64> = (start.max(1)..len)
+        .filter(|&i| prices[i - 1] > 0.0 && volumes[i] > 0)
+        .map(|i| {
+            let ret = (prices[i] / prices[i - 1] - 1.0).abs() * 100.0;
+            ret / (prices[i] * volumes[i] as f64 / 1.0e8)
+        })
+        .collect();
+    let amihud_ratio = if impacts.is_empty() {
+        0.0
+    } else {
+        impacts.iter().sum::<f64>() / impacts.len() as f64
+    };
+
+    LiquidityScore {
+        avg_daily_turnover,
+        turnover_rate,
+        amihud_ratio,
+        liquidity_grade: LiquidityGrade::from_daily_amount(avg_daily_turnover),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_liquidity_grade_by_daily_amount() {
+        // 10 元 × 1000 万股 = 1 亿元
+        let liquid = calculate_liquidity_score(&[10_000_000; 30], &[10.0; 30], Some(500_000_000));
+        assert_eq!(liquid.liquidity_grade, LiquidityGrade::Medium);
+        assert!((liquid.avg_daily_turnover - 1.0e8).abs() < 1e-3);
+        assert_eq!(liquid.turnover_rate, Some(2.0));
+        assert!(liquid.warning().is_none());
+
+        // 5 元 × 10 万股 = 50 万元
+        let illiquid = calculate_liquidity_score(&[100_000; 30], &[5.0; 30], None);
+        assert_eq!(illiquid.liquidity_grade, LiquidityGrade::VeryLow);
+        assert!(illiquid.turnover_rate.is_none());
+        assert!(illiquid.warning().unwrap().contains("50 万元"));
+    }
+
+    #[test]
+    fn test_amihud_ratio_measures_price_impact() {
+        // 每日 ±1% 交替，成交额 1 亿元 → |r| / 成交额(亿元) = 1
+        let prices: Vec<f64> = (0..21).map(|i| if i % 2 == 0 { 10.0 } else { 10.1 }).collect();
+        let volumes: Vec<i64> = prices.iter().map(|p| (1.0e8 / p).round() as i64).collect();
+        let score = calculate_liquidity_score(&volumes, &prices, None);
+        assert!((score.amihud_ratio - 0.995).abs() < 0.01, "{}", score.amihud_ratio);
+
+        // 成交额缩小 10 倍，同样波动的价格冲击放大 10 倍
+        let thin: Vec<i64> = volumes.iter().map(|v| v / 10).collect();
+        let thin_score = calculate_liquidity_score(&thin, &prices, None);
+        assert!((thin_score.amihud_ratio / score.amihud_ratio - 10.0).abs() < 0.01);
+
+        assert_eq!(calculate_liquidity_score(&[], &[], None).amihud_ratio, 0.0);
+    }
+}
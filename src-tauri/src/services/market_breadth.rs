@@ -0,0 +1,197 @@
+//! 市场宽度服务
+//!
+//! 统计股票池的涨跌家数、腾落线与 52 周新高/新低，衡量行情的参与广度。
+
+use crate::db::models::HistoricalData;
+use crate::db::{repository, DbPool};
+use crate::error::AppError;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// 52 周约 250 个交易日
+pub const BREADTH_LOOKBACK_DAYS: usize = 250;
+
+/// 纳入宽度统计的股票至少需要的历史K线数（剔除新股/数据残缺的代码）
+pub const MARKET_BREADTH_MIN_BARS: i64 = 60;
+
+/// 股票池少于该数量时宽度统计不具代表性
+pub const MARKET_BREADTH_MIN_SYMBOLS: usize = 20;
+
+/// 涨跌幅绝对值低于该值（%）视为平盘
+const UNCHANGED_THRESHOLD_PCT: f64 = 0.01;
+
+/// 涨跌家数比上限：下跌家数极少（或为 0）时避免比值失真
+const AD_RATIO_CAP: f64 = 10.0;
+
+/// 市场宽度
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MarketBreadth {
+    /// 统计日（股票池最新交易日）
+    pub date: Option<String>,
+    pub advancing: usize,
+    pub declining: usize,
+    pub unchanged: usize,
+    /// 上涨家数 / 下跌家数，上限 10；无涨跌（全部平盘或无数据）时为 1
+    pub ad_ratio: f64,
+    /// 腾落线：回看期内每日（上涨家数 - 下跌家数）的累计和
+    pub ad_line_cumulative: f64,
+    pub new_52w_highs: usize,
+    pub new_52w_lows: usize,
+}
+
+/// 由各股票时间正序的历史数据计算市场宽度
+pub fn compute_market_breadth(stocks: &[(String, Vec<HistoricalData>)]) -> MarketBreadth {
+    let Some(latest_date) = stocks
+        .iter()
+        .filter_map(|(_, history)| history.last().map(|bar| bar.date))
+        .max()
+    else {
+        return MarketBreadth::default();
+    };
+
+    let mut daily_net: BTreeMap<chrono::NaiveDate, i64> = BTreeMap::new();
+    let mut breadth = MarketBreadth {
+        date: Some(latest_date.format("%Y-%m-%d").to_string()),
+        ..MarketBreadth::default()
+    };
+
+    for (_, history) in stocks {
+        for bar in history {
+            let net = daily_net.entry(bar.date).or_insert(0);
+            if bar.change_percent > UNCHANGED_THRESHOLD_PCT {
+                *net += 1;
+            } else if bar.change_percent < -UNCHANGED_THRESHOLD_PCT {
+                *net -= 1;
+            }
+        }
+
+        // 仅统计最新交易日有数据的股票（停牌/未刷新的不计入当日涨跌）
+        let Some(today) = history.last().filter(|bar| bar.date == latest_date) else {
+            continue;
+        };
+        if today.change_percent > UNCHANGED_THRESHOLD_PCT {
+            breadth.advancing += 1;
+        } else if today.change_percent < -UNCHANGED_THRESHOLD_PCT {
+            breadth.declining += 1;
+        } else {
+            breadth.unchanged += 1;
+        }
+
+        let prior = &history[..history.len() - 1];
+        if prior.is_empty() {
+            continue;
+        }
+        let prior_high = prior.iter().map(|bar| bar.high).fold(f64::MIN, f64::max);
+        let prior_low = prior.iter().map(|bar| bar.low).fold(f64::MAX, f64::min);
+        if today.high > prior_high {
+            breadth.new_52w_highs += 1;
+        }
+        if today.low < prior_low {
+            breadth.new_52w_lows += 1;
+        }
+    }
+
+    breadth.ad_ratio = advance_decline_ratio(breadth.advancing, breadth.declining);
+    breadth.ad_line_cumulative = daily_net.values().sum::<i64>() as f64;
+    breadth
+}
+
+/// 涨跌家数比：两者均为 0 时视为均衡（1.0），无下跌时取上限
+fn advance_decline_ratio(advancing: usize, declining: usize) -> f64 {
+    match (advancing, declining) {
+        (0, 0) => 1.0,
+        (_, 0) => AD_RATIO_CAP,
+        _ => (advancing as f64 / declining as f64).min(AD_RATIO_CAP),
+    }
+}
+
+/// 计算股票池的涨跌家数与新高新低（回看 52 周）
+pub async fn calculate_advance_decline(
+    stock_codes: &[String],
+    pool: &DbPool,
+) -> Result<MarketBreadth, AppError> {
+    let stocks =
+        repository::get_recent_historical_data_for_symbols(stock_codes, BREADTH_LOOKBACK_DAYS, pool)
+            .await?;
+    Ok(compute_market_breadth(&stocks))
+}
+
+/// 仅计算股票池最新交易日的涨跌家数比（只取每只股票最近 1 根K线，供预测链路低成本调用）
+pub async fn calculate_latest_ad_ratio(
+    stock_codes: &[String],
+    pool: &DbPool,
+) -> Result<f64, AppError> {
+    let stocks = repository::get_recent_historical_data_for_symbols(stock_codes, 1, pool).await?;
+    Ok(compute_market_breadth(&stocks).ad_ratio)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, NaiveDate};
+
+    fn history(changes: &[f64]) -> Vec<HistoricalData> {
+        let start = NaiveDate::from_ymd_opt(2026, 1, 1).unwrap();
+        let mut close = 10.0;
+        changes
+            .iter()
+            .enumerate()
+            .map(|(i, &change_percent)| {
+                close *= 1.0 + change_percent / 100.0;
+                HistoricalData {
+                    symbol: "test".to_string(),
+                    date: start + Duration::days(i as i64),
+                    open: close,
+                    close,
+                    high: close,
+                    low: close,
+                    volume: 1000,
+                    amount: close * 1000.0,
+                    amplitude: 0.0,
+                    turnover_rate: 0.0,
+                    volume_ratio: 1.0,
+                    change_percent,
+                    change: 0.0,
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_compute_market_breadth_counts_latest_day() {
+        let stocks = vec![
+            ("a".to_string(), history(&[1.0, 1.0, 2.0])),
+            ("b".to_string(), history(&[-1.0, -1.0, -2.0])),
+            ("c".to_string(), history(&[1.0, -1.0, 0.0])),
+            ("d".to_string(), history(&[1.0, 3.0])), // 最新日缺数据，不计入当日
+        ];
+        let breadth = compute_market_breadth(&stocks);
+
+        assert_eq!(breadth.date.as_deref(), Some("2026-01-03"));
+        assert_eq!(
+            (breadth.advancing, breadth.declining, breadth.unchanged),
+            (1, 1, 1)
+        );
+        assert!((breadth.ad_ratio - 1.0).abs() < 1e-9);
+        // 每日净上涨家数：+2, 0, 0
+        assert!((breadth.ad_line_cumulative - 2.0).abs() < 1e-9);
+        assert_eq!(breadth.new_52w_highs, 1);
+        assert_eq!(breadth.new_52w_lows, 1);
+    }
+
+    #[test]
+    fn test_compute_market_breadth_empty() {
+        let breadth = compute_market_breadth(&[]);
+        assert!(breadth.date.is_none());
+        assert_eq!(breadth.ad_ratio, 1.0);
+    }
+
+    #[test]
+    fn test_advance_decline_ratio_edge_cases() {
+        assert_eq!(advance_decline_ratio(0, 0), 1.0);
+        assert_eq!(advance_decline_ratio(3, 0), AD_RATIO_CAP);
+        assert_eq!(advance_decline_ratio(0, 4), 0.0);
+        assert_eq!(advance_decline_ratio(300, 2), AD_RATIO_CAP);
+        assert!((advance_decline_ratio(6, 4) - 1.5).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,187 @@
+//! 市场概览服务
+//!
+//! 一次汇总主要指数点位、市场情绪、涨跌幅榜/成交额榜与市场宽度。
+//! 交易时段内结果缓存 60 秒；情绪指数同时供个股预测的情绪因子复用。
+
+use crate::api::market_index::{
+    fetch_index_quotes, CSI300, SHANGHAI_COMPOSITE, SHENZHEN_COMPONENT, STAR50,
+};
+use crate::api::stock::normalize_exchange;
+use crate::db::models::{RealtimeData, StockSearchResult};
+use crate::db::{repository, DbPool};
+use crate::error::AppError;
+use crate::services::market_breadth::{calculate_advance_decline, MarketBreadth, MARKET_BREADTH_MIN_BARS};
+use crate::services::market_sentiment::{calculate_market_sentiment_index, MarketSentimentIndex};
+use crate::services::stop_orders::in_trading_session;
+use crate::utils::canonical_stock_symbol;
+use chrono::Local;
+use log::warn;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// 交易时段内概览的缓存时长
+const OVERVIEW_CACHE_TTL: Duration = Duration::from_secs(60);
+/// 各榜单的股票数
+const MOVERS_LIMIT: usize = 10;
+
+static OVERVIEW_CACHE: Mutex<Option<(Instant, MarketOverview)>> = Mutex::new(None);
+
+/// 市场概览
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MarketOverview {
+    /// 上证指数
+    pub shanghai_composite: Option<f64>,
+    /// 深证成指
+    pub shenzhen_composite: Option<f64>,
+    pub csi300: Option<f64>,
+    pub star50: Option<f64>,
+    pub market_sentiment: MarketSentimentIndex,
+    /// 涨幅榜
+    pub top_gainers: Vec<StockSearchResult>,
+    /// 跌幅榜
+    pub top_losers: Vec<StockSearchResult>,
+    /// 成交额榜
+    pub most_active: Vec<StockSearchResult>,
+    pub breadth: MarketBreadth,
+}
+
+/// 涨跌幅榜与成交额榜
+struct MarketMovers {
+    gainers: Vec<StockSearchResult>,
+    losers: Vec<StockSearchResult>,
+    active: Vec<StockSearchResult>,
+}
+
+fn search_result(data: &RealtimeData) -> StockSearchResult {
+    let code = canonical_stock_symbol(&data.symbol);
+    StockSearchResult {
+        exchange: normalize_exchange("", &code),
+        is_st: data.name.to_ascii_uppercase().contains("ST"),
+        name: data.name.clone(),
+        code,
+    }
+}
+
+/// 由最新交易日快照生成各榜单；同一股票的多种代码写法只保留一条
+fn rank_market_movers(snapshot: &[RealtimeData], limit: usize) -> MarketMovers {
+    let mut seen = HashSet::new();
+    let mut unique: Vec<&RealtimeData> = snapshot
+        .iter()
+        .filter(|data| seen.insert(canonical_stock_symbol(&data.symbol)))
+        .collect();
+    let top = |rows: &[&RealtimeData]| rows.iter().take(limit).map(|data| search_result(data)).collect();
+
+    unique.sort_by(|a, b| b.change_percent.total_cmp(&a.change_percent));
+    let gainers = top(&unique);
+    let losers = top(&unique.iter().rev().copied().collect::<Vec<_>>());
+    unique.sort_by(|a, b| b.amount.total_cmp(&a.amount));
+    MarketMovers {
+        gainers,
+        losers,
+        active: top(&unique),
+    }
+}
+
+fn cached_overview() -> Option<MarketOverview> {
+    let cache = OVERVIEW_CACHE.lock().ok()?;
+    cache
+        .as_ref()
+        .filter(|(at, _)| at.elapsed() < OVERVIEW_CACHE_TTL)
+        .map(|(_, overview)| overview.clone())
+}
+
+/// 汇总市场概览；交易时段内 60 秒内重复调用直接返回缓存。
+///
+/// 指数接口失败时对应点位为 None，不影响其余统计
+pub async fn calculate_market_overview(pool: &DbPool) -> Result<MarketOverview, AppError> {
+    let in_session = in_trading_session(Local::now().naive_local());
+    if in_session {
+        if let Some(overview) = cached_overview() {
+            return Ok(overview);
+        }
+    }
+
+    let (index_quotes, market_sentiment, snapshot) = futures::join!(
+        fetch_index_quotes(&[SHANGHAI_COMPOSITE, SHENZHEN_COMPONENT, CSI300, STAR50]),
+        calculate_market_sentiment_index(pool),
+        repository::get_latest_realtime_snapshot(pool),
+    );
+    let index_quotes = index_quotes.unwrap_or_else(|e| {
+        warn!("获取指数行情失败: {e}");
+        Default::default()
+    });
+    let symbols = repository::get_symbols_with_min_bars(MARKET_BREADTH_MIN_BARS, pool).await?;
+    let breadth = calculate_advance_decline(&symbols, pool).await?;
+    let movers = rank_market_movers(&snapshot?, MOVERS_LIMIT);
+
+    let overview = MarketOverview {
+        shanghai_composite: index_quotes.get(SHANGHAI_COMPOSITE).copied(),
+        shenzhen_composite: index_quotes.get(SHENZHEN_COMPONENT).copied(),
+        csi300: index_quotes.get(CSI300).copied(),
+        star50: index_quotes.get(STAR50).copied(),
+        market_sentiment: market_sentiment?,
+        top_gainers: movers.gainers,
+        top_losers: movers.losers,
+        most_active: movers.active,
+        breadth,
+    };
+    if in_session {
+        if let Ok(mut cache) = OVERVIEW_CACHE.lock() {
+            *cache = Some((Instant::now(), overview.clone()));
+        }
+    }
+    Ok(overview)
+}
+
+/// 预测链路使用的全市场情绪：概览缓存有效时直接复用，否则重新统计
+pub async fn market_sentiment_for_prediction(
+    pool: &DbPool,
+) -> Result<MarketSentimentIndex, AppError> {
+    match cached_overview() {
+        Some(overview) => Ok(overview.market_sentiment),
+        None => calculate_market_sentiment_index(pool).await,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn quote(symbol: &str, name: &str, change_percent: f64, amount: f64) -> RealtimeData {
+        RealtimeData {
+            symbol: symbol.to_string(),
+            name: name.to_string(),
+            date: NaiveDate::from_ymd_opt(2026, 7, 15).unwrap(),
+            close: 10.0,
+            volume: 1000,
+            amount,
+            amplitude: 0.0,
+            turnover_rate: 0.0,
+            volume_ratio: 1.0,
+            change_percent,
+            change: 0.0,
+        }
+    }
+
+    #[test]
+    fn test_rank_market_movers() {
+        let snapshot = vec![
+            quote("600519", "贵州茅台", 1.5, 9e9),
+            quote("002466", "天齐锂业", 10.0, 3e9),
+            quote("002466.SZ", "天齐锂业", 10.0, 3e9),
+            quote("000004", "*ST国华", -5.0, 1e7),
+            quote("300750", "宁德时代", -1.0, 8e9),
+        ];
+        let movers = rank_market_movers(&snapshot, 2);
+        let codes = |rows: &[StockSearchResult]| rows.iter().map(|r| r.code.clone()).collect::<Vec<_>>();
+        assert_eq!(codes(&movers.gainers), vec!["002466", "600519"]);
+        assert_eq!(codes(&movers.losers), vec!["000004", "300750"]);
+        assert_eq!(codes(&movers.active), vec!["600519", "300750"]);
+        assert!(movers.losers[0].is_st);
+        assert_eq!(movers.gainers[1].exchange, "SH");
+        assert_eq!(movers.gainers[0].exchange, "SZ");
+    }
+}
@@ -0,0 +1,189 @@
+//! 市场情绪指数服务
+//!
+//! 汇总全市场股票池的技术面宽度（站上 20 日均线比例、RSI 分布），
+//! 合成 0-100 的恐慌/贪婪指数，替代单只股票自身指标推断出的"情绪"。
+
+use crate::db::models::HistoricalData;
+use crate::db::{repository, DbPool};
+use crate::error::AppError;
+use crate::prediction::indicators::rsi::calculate_rsi;
+use crate::services::market_breadth::MARKET_BREADTH_MIN_BARS;
+use crate::services::market_timing::compute_market_timing_signal;
+use serde::{Deserialize, Serialize};
+
+/// 计算 MA20 / RSI14 所需的回看K线数（RSI 需更长序列做 Wilder 平滑）
+pub const SENTIMENT_LOOKBACK_DAYS: usize = 60;
+const MA_PERIOD: usize = 20;
+/// RSI 超买/超卖阈值
+const RSI_OVERBOUGHT: f64 = 70.0;
+const RSI_OVERSOLD: f64 = 30.0;
+
+/// 市场情绪指数
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MarketSentimentIndex {
+    /// 统计日（股票池最新交易日）
+    pub date: Option<String>,
+    /// 纳入统计的股票数
+    pub sample_size: usize,
+    /// 收盘价站上 20 日均线的股票占比（%）
+    pub pct_above_ma20: f64,
+    pub avg_rsi: f64,
+    /// 恐慌/贪婪指数（0 极度恐慌 - 100 极度贪婪）
+    pub fear_greed_index: f64,
+    pub market_phase: String,
+    /// RSI > 50 与 RSI < 50 的股票数之比
+    pub bull_bear_ratio: f64,
+    /// 择时得分（0-100，见 `market_timing`）
+    #[serde(default)]
+    pub timing_score: f64,
+}
+
+/// 由各股票时间正序的历史数据计算市场情绪指数；仅统计最新交易日有数据的股票
+pub fn compute_market_sentiment(stocks: &[(String, Vec<HistoricalData>)]) -> MarketSentimentIndex {
+    let Some(latest_date) = stocks
+        .iter()
+        .filter_map(|(_, history)| history.last().map(|bar| bar.date))
+        .max()
+    else {
+        return MarketSentimentIndex::default();
+    };
+
+    let (mut above_ma, mut bulls, mut bears, mut overbought, mut oversold) = (0, 0, 0, 0, 0);
+    let mut rsi_sum = 0.0;
+    let mut sample_size = 0usize;
+    for (_, history) in stocks {
+        if history.len() < MA_PERIOD || history.last().map(|bar| bar.date) != Some(latest_date) {
+            continue;
+        }
+        let closes: Vec<f64> = history.iter().map(|bar| bar.close).collect();
+        let close = closes[closes.len() - 1];
+        let ma20 = closes[closes.len() - MA_PERIOD..].iter().sum::<f64>() / MA_PERIOD as f64;
+        let rsi = calculate_rsi(&closes);
+
+        sample_size += 1;
+        rsi_sum += rsi;
+        if close > ma20 {
+            above_ma += 1;
+        }
+        if rsi > 50.0 {
+            bulls += 1;
+        } else if rsi < 50.0 {
+            bears += 1;
+        }
+        if rsi > RSI_OVERBOUGHT {
+            overbought += 1;
+        } else if rsi < RSI_OVERSOLD {
+            oversold += 1;
+        }
+    }
+    if sample_size == 0 {
+        return MarketSentimentIndex::default();
+    }
+
+    let n = sample_size as f64;
+    let pct_above_ma20 = above_ma as f64 / n * 100.0;
+    let avg_rsi = rsi_sum / n;
+    // 平均 RSI 的常见区间 30-70 线性映射到 0-100；超买/超卖家数差映射到 0-100
+    let rsi_component = ((avg_rsi - 30.0) / 40.0 * 100.0).clamp(0.0, 100.0);
+    let extreme_component = 50.0 + (overbought as f64 - oversold as f64) / n * 50.0;
+    let fear_greed_index =
+        (0.4 * pct_above_ma20 + 0.4 * rsi_component + 0.2 * extreme_component).clamp(0.0, 100.0);
+
+    let mut index = MarketSentimentIndex {
+        date: Some(latest_date.format("%Y-%m-%d").to_string()),
+        sample_size,
+        pct_above_ma20,
+        avg_rsi,
+        fear_greed_index,
+        market_phase: market_phase(fear_greed_index).to_string(),
+        bull_bear_ratio: if bears > 0 {
+            bulls as f64 / bears as f64
+        } else {
+            bulls as f64
+        },
+        timing_score: 0.0,
+    };
+    index.timing_score = compute_market_timing_signal(stocks, &index).timing_score;
+    index
+}
+
+fn market_phase(fear_greed_index: f64) -> &'static str {
+    if fear_greed_index < 20.0 {
+        "极度恐慌"
+    } else if fear_greed_index < 40.0 {
+        "恐慌"
+    } else if fear_greed_index < 60.0 {
+        "中性"
+    } else if fear_greed_index < 80.0 {
+        "贪婪"
+    } else {
+        "极度贪婪"
+    }
+}
+
+/// 计算本地股票池的市场情绪指数
+pub async fn calculate_market_sentiment_index(
+    pool: &DbPool,
+) -> Result<MarketSentimentIndex, AppError> {
+    let symbols = repository::get_symbols_with_min_bars(MARKET_BREADTH_MIN_BARS, pool).await?;
+    let stocks =
+        repository::get_recent_historical_data_for_symbols(&symbols, SENTIMENT_LOOKBACK_DAYS, pool)
+            .await?;
+    Ok(compute_market_sentiment(&stocks))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, NaiveDate};
+
+    fn history(daily_change: f64) -> Vec<HistoricalData> {
+        let start = NaiveDate::from_ymd_opt(2026, 1, 1).unwrap();
+        let mut close = 10.0;
+        (0..40)
+            .map(|i| {
+                // 交替小幅波动叠加趋势，避免 RSI 退化为 0/100
+                close *= 1.0 + daily_change / 100.0 + if i % 2 == 0 { 0.005 } else { -0.005 };
+                HistoricalData {
+                    symbol: "test".to_string(),
+                    date: start + Duration::days(i),
+                    open: close,
+                    close,
+                    high: close,
+                    low: close,
+                    volume: 1000,
+                    amount: 0.0,
+                    amplitude: 0.0,
+                    turnover_rate: 0.0,
+                    volume_ratio: 1.0,
+                    change_percent: daily_change,
+                    change: 0.0,
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_rising_market_reads_greedy() {
+        let stocks: Vec<_> = (0..10).map(|i| (format!("s{i}"), history(1.0))).collect();
+        let index = compute_market_sentiment(&stocks);
+        assert_eq!(index.sample_size, 10);
+        assert_eq!(index.pct_above_ma20, 100.0);
+        assert!(index.avg_rsi > 60.0);
+        assert!(index.fear_greed_index > 60.0);
+        assert!(index.market_phase.contains("贪婪"));
+        assert!(index.timing_score > 70.0);
+    }
+
+    #[test]
+    fn test_falling_market_reads_fearful() {
+        let mut stocks: Vec<_> = (0..8).map(|i| (format!("s{i}"), history(-1.0))).collect();
+        stocks.push(("up".to_string(), history(1.0)));
+        let index = compute_market_sentiment(&stocks);
+        assert!(index.pct_above_ma20 < 20.0);
+        assert!(index.fear_greed_index < 40.0);
+        assert!(index.bull_bear_ratio < 0.2);
+        assert!(index.market_phase.contains("恐慌"));
+        assert_eq!(compute_market_sentiment(&[]).sample_size, 0);
+    }
+}
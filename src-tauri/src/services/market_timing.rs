@@ -0,0 +1,229 @@
+//! 量化择时模型
+//!
+//! 把市场宽度与情绪合成 0-100 的择时得分，四个分量各自映射到 0-100 后等权平均：
+//! - 涨跌家数：上涨家数 / (上涨 + 下跌家数)
+//! - 站上 20 日均线的股票占比
+//! - 平均 RSI：30-70 线性映射到 0-100
+//! - 市场动量：按成交额加权的 20 日平均涨跌幅，±10% 映射到 0/100
+
+use crate::db::models::HistoricalData;
+use crate::db::{repository, DbPool};
+use crate::error::AppError;
+use crate::services::market_breadth::{compute_market_breadth, MARKET_BREADTH_MIN_BARS};
+use crate::services::market_sentiment::{
+    compute_market_sentiment, MarketSentimentIndex, SENTIMENT_LOOKBACK_DAYS,
+};
+use serde::{Deserialize, Serialize};
+
+/// 动量回看交易日数
+const MOMENTUM_DAYS: usize = 20;
+/// 20 日加权涨跌幅达到该值（小数）时动量分量取满分
+const MOMENTUM_FULL_RETURN: f64 = 0.10;
+/// 择时得分不低于该值为强烈买入区
+pub const STRONG_BUY_THRESHOLD: f64 = 70.0;
+/// 择时得分不高于该值为强烈卖出区
+pub const STRONG_SELL_THRESHOLD: f64 = 30.0;
+/// 建议仓位线性区间：得分 20 对应空仓、80 对应满仓
+const ZERO_ALLOCATION_SCORE: f64 = 20.0;
+const FULL_ALLOCATION_SCORE: f64 = 80.0;
+
+/// 市场阶段
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MarketPhase {
+    Bear,
+    Weak,
+    Range,
+    Strong,
+    Bull,
+}
+
+impl MarketPhase {
+    pub fn from_score(timing_score: f64) -> Self {
+        if timing_score <= STRONG_SELL_THRESHOLD {
+            Self::Bear
+        } else if timing_score < 45.0 {
+            Self::Weak
+        } else if timing_score <= 55.0 {
+            Self::Range
+        } else if timing_score < STRONG_BUY_THRESHOLD {
+            Self::Strong
+        } else {
+            Self::Bull
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Bear => "弱市（强烈卖出区）",
+            Self::Weak => "偏弱",
+            Self::Range => "震荡",
+            Self::Strong => "偏强",
+            Self::Bull => "强市（强烈买入区）",
+        }
+    }
+}
+
+/// 择时信号
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MarketTimingSignal {
+    /// 择时得分（0-100）
+    pub timing_score: f64,
+    /// 建议股票仓位（%）
+    pub suggested_allocation_pct: f64,
+    pub phase: MarketPhase,
+    pub strong_buy_threshold: f64,
+    pub strong_sell_threshold: f64,
+    /// 各分量得分（0-100）
+    pub advance_decline_score: f64,
+    pub above_ma20_score: f64,
+    pub rsi_score: f64,
+    pub momentum_score: f64,
+}
+
+/// 按成交额加权的 20 日平均涨跌幅（小数）；仅统计最新交易日有数据且K线足够的股票
+fn weighted_market_momentum(stocks: &[(String, Vec<HistoricalData>)]) -> Option<f64> {
+    let latest_date = stocks
+        .iter()
+        .filter_map(|(_, history)| history.last().map(|bar| bar.date))
+        .max()?;
+    let (mut weighted_sum, mut weight_total) = (0.0, 0.0);
+    for (_, history) in stocks {
+        if history.len() <= MOMENTUM_DAYS || history.last().map(|bar| bar.date) != Some(latest_date) {
+            continue;
+        }
+        let window = &history[history.len() - 1 - MOMENTUM_DAYS..];
+        let base = window[0].close;
+        if base <= 0.0 {
+            continue;
+        }
+        let ret = window[MOMENTUM_DAYS].close / base - 1.0;
+        let weight = window[1..].iter().map(|bar| bar.amount).sum::<f64>() / MOMENTUM_DAYS as f64;
+        if weight > 0.0 && ret.is_finite() {
+            weighted_sum += ret * weight;
+            weight_total += weight;
+        }
+    }
+    (weight_total > 0.0).then(|| weighted_sum / weight_total)
+}
+
+/// 由各股票时间正序的历史数据与同一股票池的情绪指数计算择时信号
+pub fn compute_market_timing_signal(
+    stocks: &[(String, Vec<HistoricalData>)],
+    sentiment: &MarketSentimentIndex,
+) -> MarketTimingSignal {
+    let breadth = compute_market_breadth(stocks);
+    let moving = breadth.advancing + breadth.declining;
+    let advance_decline_score = if moving > 0 {
+        breadth.advancing as f64 / moving as f64 * 100.0
+    } else {
+        50.0
+    };
+    let (above_ma20_score, rsi_score) = if sentiment.sample_size > 0 {
+        (
+            sentiment.pct_above_ma20,
+            ((sentiment.avg_rsi - 30.0) / 40.0 * 100.0).clamp(0.0, 100.0),
+        )
+    } else {
+        (50.0, 50.0)
+    };
+    let momentum_score = weighted_market_momentum(stocks)
+        .map_or(50.0, |ret| (50.0 + ret / MOMENTUM_FULL_RETURN * 50.0).clamp(0.0, 100.0));
+
+    let timing_score =
+        (advance_decline_score + above_ma20_score + rsi_score + momentum_score) / 4.0;
+    let suggested_allocation_pct = ((timing_score - ZERO_ALLOCATION_SCORE)
+        / (FULL_ALLOCATION_SCORE - ZERO_ALLOCATION_SCORE)
+        * 100.0)
+        .clamp(0.0, 100.0);
+
+    MarketTimingSignal {
+        timing_score,
+        suggested_allocation_pct,
+        phase: MarketPhase::from_score(timing_score),
+        strong_buy_threshold: STRONG_BUY_THRESHOLD,
+        strong_sell_threshold: STRONG_SELL_THRESHOLD,
+        advance_decline_score,
+        above_ma20_score,
+        rsi_score,
+        momentum_score,
+    }
+}
+
+/// 计算本地股票池的择时信号
+pub async fn calculate_market_timing_signal(pool: &DbPool) -> Result<MarketTimingSignal, AppError> {
+    let symbols = repository::get_symbols_with_min_bars(MARKET_BREADTH_MIN_BARS, pool).await?;
+    let stocks =
+        repository::get_recent_historical_data_for_symbols(&symbols, SENTIMENT_LOOKBACK_DAYS, pool)
+            .await?;
+    let sentiment = compute_market_sentiment(&stocks);
+    Ok(compute_market_timing_signal(&stocks, &sentiment))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, NaiveDate};
+
+    fn history(daily_change: f64, amount: f64) -> Vec<HistoricalData> {
+        let start = NaiveDate::from_ymd_opt(2026, 1, 1).unwrap();
+        let mut close = 10.0;
+        (0..30)
+            .map(|i| {
+                close *= 1.0 + daily_change / 100.0;
+                HistoricalData {
+                    symbol: "test".to_string(),
+                    date: start + Duration::days(i),
+                    open: close,
+                    close,
+                    high: close,
+                    low: close,
+                    volume: 1000,
+                    amount,
+                    amplitude: 0.0,
+                    turnover_rate: 0.0,
+                    volume_ratio: 1.0,
+                    change_percent: daily_change,
+                    change: 0.0,
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_weighted_market_momentum() {
+        // 20 日涨幅约 +22.0% 与 -18.2%，成交额 1:3 加权
+        let stocks = vec![
+            ("up".to_string(), history(1.0, 1000.0)),
+            ("down".to_string(), history(-1.0, 3000.0)),
+        ];
+        let up = 1.01f64.powi(20) - 1.0;
+        let down = 0.99f64.powi(20) - 1.0;
+        let momentum = weighted_market_momentum(&stocks).unwrap();
+        assert!((momentum - (up + 3.0 * down) / 4.0).abs() < 1e-12);
+        assert!(weighted_market_momentum(&[]).is_none());
+    }
+
+    #[test]
+    fn test_compute_market_timing_signal() {
+        let rising: Vec<_> = (0..10).map(|i| (format!("s{i}"), history(1.0, 1000.0))).collect();
+        let sentiment = MarketSentimentIndex {
+            sample_size: 10,
+            pct_above_ma20: 100.0,
+            avg_rsi: 80.0,
+            ..MarketSentimentIndex::default()
+        };
+        let signal = compute_market_timing_signal(&rising, &sentiment);
+        assert_eq!(signal.advance_decline_score, 100.0);
+        assert_eq!(signal.momentum_score, 100.0);
+        assert_eq!(signal.timing_score, 100.0);
+        assert_eq!(signal.suggested_allocation_pct, 100.0);
+        assert_eq!(signal.phase, MarketPhase::Bull);
+
+        // 无数据时各分量取中性值
+        let empty = compute_market_timing_signal(&[], &MarketSentimentIndex::default());
+        assert_eq!(empty.timing_score, 50.0);
+        assert_eq!(empty.suggested_allocation_pct, 50.0);
+        assert_eq!(empty.phase, MarketPhase::Range);
+    }
+}
@@ -5,8 +5,48 @@
 pub mod stock;
 pub mod historical;
 pub mod prediction;
+pub mod market_breadth;
+pub mod journal;
+pub mod beta;
+pub mod pairs_trading;
+pub mod market_sentiment;
+pub mod market_timing;
+pub mod market_overview;
+pub mod stress_test;
+pub mod portfolio_optimizer;
+pub mod performance_attribution;
+pub mod alerts;
+pub mod database;
+pub mod progress;
+pub mod universe;
+pub mod paper_trading;
+pub mod candles;
+pub mod online_learning;
+pub mod stop_orders;
+pub mod sector;
+pub mod liquidity;
 
 pub use stock::*;
 pub use historical::*;
 pub use prediction::*;
+pub use market_breadth::*;
+pub use journal::*;
+pub use beta::*;
+pub use pairs_trading::*;
+pub use market_sentiment::*;
+pub use market_timing::*;
+pub use market_overview::*;
+pub use stress_test::*;
+pub use portfolio_optimizer::*;
+pub use performance_attribution::*;
+pub use alerts::*;
+pub use database::*;
+pub use progress::*;
+pub use universe::*;
+pub use paper_trading::*;
+pub use candles::*;
+pub use online_learning::*;
+pub use stop_orders::*;
+pub use sector::*;
+pub use liquidity::*;
 
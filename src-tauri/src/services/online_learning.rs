@@ -0,0 +1,301 @@
+//! 在线学习服务
+//!
+//! 预测目标日过后由 [`reconcile_prediction_history`](super::prediction::reconcile_prediction_history)
+//! 回填实际价格；本服务每日扫描新回填的记录，重建预测当日的特征，
+//! 以实际周期收益为标签，每凑满 [`ONLINE_BATCH_SIZE`] 条误差对 Candle 模型权重做一步梯度更新，
+//! 并把更新前后的批内方向准确率写入 `model_history`（source = online）。
+
+use crate::db::models::{HistoricalData, OnlineLearningConfig, PredictionHistoryRecord};
+use crate::db::repository::{self, insert_model_history};
+use crate::prediction::model::features::{
+    latest_features, mask_unselected_features, model_feature_columns, model_input_dim,
+};
+use crate::prediction::model::inference::model_training_horizon;
+use crate::prediction::model::management;
+use crate::prediction::model::network::fine_tune_step;
+use crate::prediction::types::ModelInfo;
+use chrono::Duration as ChronoDuration;
+use log::{error, info, warn};
+use sqlx::SqlitePool;
+use std::time::Duration;
+
+/// 在线学习检查间隔（每日一次）
+pub const ONLINE_LEARNING_INTERVAL: Duration = Duration::from_secs(24 * 3600);
+
+/// 每次梯度更新累积的误差样本数
+pub const ONLINE_BATCH_SIZE: usize = 10;
+
+/// 重建预测当日特征时回看的自然日数（覆盖特征所需的 20 个交易日）
+const FEATURE_HISTORY_CALENDAR_DAYS: i64 = 60;
+
+/// 一次在线更新的汇总
+#[derive(Debug, Clone)]
+pub struct OnlineUpdate {
+    pub model_id: String,
+    pub batches: usize,
+    pub samples: usize,
+    /// 更新前后在所用样本上的平均方向准确率
+    pub accuracy_before: f64,
+    pub accuracy_after: f64,
+}
+
+/// 在线学习调度器
+pub struct OnlineLearningService {
+    pool: SqlitePool,
+}
+
+impl OnlineLearningService {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// 启动后台调度：立即检查一次，此后按 [`ONLINE_LEARNING_INTERVAL`] 检查
+    pub fn spawn(self) {
+        tauri::async_runtime::spawn(async move {
+            let mut interval = tokio::time::interval(ONLINE_LEARNING_INTERVAL);
+            loop {
+                interval.tick().await;
+                self.run_updates().await;
+            }
+        });
+    }
+
+    /// 对全部启用在线学习的模型执行一次更新，返回发生权重更新的模型数
+    pub async fn run_updates(&self) -> usize {
+        let configs = match repository::get_enabled_online_learning(&self.pool).await {
+            Ok(configs) => configs,
+            Err(e) => {
+                error!("读取在线学习配置失败: {e}");
+                return 0;
+            }
+        };
+        let mut updated = 0;
+        for config in configs {
+            match self.update_model(&config).await {
+                Ok(Some(update)) => {
+                    updated += 1;
+                    info!(
+                        "🔁 模型 {} 在线更新 {} 批（{} 条），批内方向准确率 {:.1}% → {:.1}%",
+                        update.model_id,
+                        update.batches,
+                        update.samples,
+                        update.accuracy_before * 100.0,
+                        update.accuracy_after * 100.0
+                    );
+                }
+                Ok(None) => {}
+                Err(e) => error!("模型 {} 在线学习失败: {e}", config.model_id),
+            }
+        }
+        updated
+    }
+
+    /// 消费该模型新回填的预测记录；不足一个批次时不更新权重（记录留待下次）
+    pub async fn update_model(
+        &self,
+        config: &OnlineLearningConfig,
+    ) -> Result<Option<OnlineUpdate>, String> {
+        let model = management::load_model_metadata(&config.model_id)?;
+        let weights_path = management::get_model_file_path(&model.id);
+        if !weights_path.exists() {
+            return Err(format!("模型权重文件不存在: {}", weights_path.display()));
+        }
+        let horizon = model_training_horizon(&model.model_type, model.prediction_days);
+        let feature_columns = model_feature_columns(&model.features);
+        let input_dim = model_input_dim(model.feature_set_version);
+
+        let records =
+            repository::get_reconciled_prediction_records_after(&self.pool, config.last_record_id)
+                .await
+                .map_err(|e| e.to_string())?;
+        let last_scanned = records.last().map_or(config.last_record_id, |r| r.id);
+        let mut samples: Vec<(i64, Vec<f32>, f32)> = Vec::new();
+        for record in records
+            .iter()
+            .filter(|r| management::model_matches_identifier(&model, &r.model_id))
+        {
+            let bars = self.feature_history(record).await?;
+            if let Some((features, label)) = online_sample(&bars, record, horizon) {
+                samples.push((record.id, features, label));
+            }
+        }
+
+        let sample_ids: Vec<i64> = samples.iter().map(|(id, _, _)| *id).collect();
+        let (full, cursor) = batch_cursor(&sample_ids, last_scanned);
+        if full == 0 {
+            self.save_cursor(&model.id, cursor).await?;
+            return Ok(None);
+        }
+
+        let mut before_sum = 0.0;
+        let mut after_sum = 0.0;
+        let mut last_outcome = None;
+        for batch in samples[..full].chunks(ONLINE_BATCH_SIZE) {
+            let mut features: Vec<f32> =
+                batch.iter().flat_map(|(_, f, _)| f.iter().copied()).collect();
+            mask_unselected_features(&mut features, &feature_columns);
+            let labels: Vec<f32> = batch.iter().map(|(_, _, label)| *label).collect();
+            let outcome =
+                fine_tune_step(&weights_path, input_dim, &features, &labels, config.learning_rate)?;
+            before_sum += outcome.accuracy_before;
+            after_sum += outcome.accuracy_after;
+            last_outcome = Some(outcome);
+        }
+        self.save_cursor(&model.id, cursor).await?;
+
+        let batches = full / ONLINE_BATCH_SIZE;
+        let update = OnlineUpdate {
+            model_id: model.id.clone(),
+            batches,
+            samples: full,
+            accuracy_before: before_sum / batches as f64,
+            accuracy_after: after_sum / batches as f64,
+        };
+        let entry = ModelInfo {
+            accuracy: update.accuracy_after,
+            mae: last_outcome.map(|o| o.mae_after),
+            rmse: last_outcome.map(|o| o.rmse_after),
+            ..model
+        };
+        if let Err(e) =
+            insert_model_history(&self.pool, &entry, "online", Some(update.accuracy_before)).await
+        {
+            warn!("写入模型训练记录失败 {}: {e}", entry.id);
+        }
+        Ok(Some(update))
+    }
+
+    /// 预测当日前约两个月至目标日的日线
+    async fn feature_history(
+        &self,
+        record: &PredictionHistoryRecord,
+    ) -> Result<Vec<HistoricalData>, String> {
+        let start = record.prediction_date - ChronoDuration::days(FEATURE_HISTORY_CALENDAR_DAYS);
+        repository::get_historical_data(
+            &record.stock_code,
+            &start.format("%Y-%m-%d").to_string(),
+            &record.target_date.format("%Y-%m-%d").to_string(),
+            &self.pool,
+        )
+        .await
+        .map_err(|e| e.to_string())
+    }
+
+    async fn save_cursor(&self, model_id: &str, cursor: i64) -> Result<(), String> {
+        repository::set_online_learning_cursor(&self.pool, model_id, cursor)
+            .await
+            .map_err(|e| e.to_string())
+    }
+}
+
+/// 由预测记录重建训练样本：(预测当日特征, 实际周期收益率%)。
+///
+/// 预测日到目标日须恰好相隔模型训练周期个交易日，否则标签口径与模型不一致，跳过。
+fn online_sample(
+    bars: &[HistoricalData],
+    record: &PredictionHistoryRecord,
+    horizon: usize,
+) -> Option<(Vec<f32>, f32)> {
+    let actual = record.actual_price?;
+    if record.base_price <= 0.0 {
+        return None;
+    }
+    let split = bars.partition_point(|bar| bar.date <= record.prediction_date);
+    if split == 0 || bars[split - 1].date != record.prediction_date {
+        return None;
+    }
+    let elapsed = bars[split..]
+        .iter()
+        .filter(|bar| bar.date <= record.target_date)
+        .count();
+    if elapsed != horizon {
+        return None;
+    }
+    let features = latest_features(&bars[..split])?;
+    let label = ((actual / record.base_price - 1.0) * 100.0) as f32;
+    Some((features, label))
+}
+
+/// 按批次切分可用样本，返回 (本次使用的样本数, 新的消费进度)。
+///
+/// 不足一个批次的尾部样本不消费：进度停在其首条记录之前，下次连同新记录一起处理。
+fn batch_cursor(sample_ids: &[i64], last_scanned: i64) -> (usize, i64) {
+    let full = sample_ids.len() / ONLINE_BATCH_SIZE * ONLINE_BATCH_SIZE;
+    let cursor = sample_ids
+        .get(full)
+        .map_or(last_scanned, |first_pending| first_pending - 1);
+    (full, cursor)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn bars(n: usize) -> Vec<HistoricalData> {
+        let start = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+        (0..n)
+            .map(|i| {
+                let close = 10.0 + i as f64 * 0.1;
+                HistoricalData {
+                    symbol: "600519".to_string(),
+                    date: start + ChronoDuration::days(i as i64),
+                    open: close,
+                    close,
+                    high: close + 0.2,
+                    low: close - 0.2,
+                    volume: 1_000_000,
+                    amount: close * 1_000_000.0,
+                    amplitude: 4.0,
+                    turnover_rate: 1.0,
+                    volume_ratio: 1.0,
+                    change_percent: 1.0,
+                    change: 0.1,
+                }
+            })
+            .collect()
+    }
+
+    fn record(bars: &[HistoricalData], prediction_idx: usize, target_idx: usize) -> PredictionHistoryRecord {
+        PredictionHistoryRecord {
+            id: 1,
+            stock_code: "600519".to_string(),
+            model_id: "m1".to_string(),
+            prediction_date: bars[prediction_idx].date,
+            target_date: bars[target_idx].date,
+            base_price: bars[prediction_idx].close,
+            predicted_price: bars[prediction_idx].close,
+            predicted_direction: "横盘".to_string(),
+            actual_price: Some(bars[target_idx].close),
+            actual_direction: None,
+            was_direction_correct: None,
+            price_error_pct: None,
+        }
+    }
+
+    #[test]
+    fn test_online_sample_uses_prediction_day_features() {
+        let bars = bars(40);
+        let (features, label) = online_sample(&bars, &record(&bars, 30, 35), 5).unwrap();
+        assert_eq!(features, latest_features(&bars[..31]).unwrap());
+        let expected = (bars[35].close / bars[30].close - 1.0) * 100.0;
+        assert!((label as f64 - expected).abs() < 1e-4);
+
+        // 周期与模型不一致、特征窗口不足或缺少实际价格时跳过
+        assert!(online_sample(&bars, &record(&bars, 30, 33), 5).is_none());
+        assert!(online_sample(&bars, &record(&bars, 10, 15), 5).is_none());
+        let mut pending = record(&bars, 30, 35);
+        pending.actual_price = None;
+        assert!(online_sample(&bars, &pending, 5).is_none());
+    }
+
+    #[test]
+    fn test_batch_cursor_keeps_partial_batch() {
+        let ids: Vec<i64> = (1..=23).map(|i| i * 2).collect();
+        // 两个完整批次，第 21 个样本（id 42）起留待下次
+        assert_eq!(batch_cursor(&ids, 60), (20, 41));
+        assert_eq!(batch_cursor(&ids[..20], 60), (20, 60));
+        assert_eq!(batch_cursor(&ids[..3], 60), (0, 1));
+        assert_eq!(batch_cursor(&[], 60), (0, 60));
+    }
+}
@@ -0,0 +1,253 @@
+//! 配对交易服务
+//!
+//! 以 Engle-Granger 两步法检验股票对的协整关系：先对对数价格做 OLS 回归
+//! ln(A) = α + β·ln(B) + ε，再对残差（价差）做 ADF 检验；协整的价差
+//! 偏离均值过远时给出做多/做空价差的市场中性信号。
+
+use crate::db::{repository, DbPool};
+use crate::error::AppError;
+use crate::prediction::strategy::mean_reversion::estimate_half_life;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// 默认回看交易日数
+pub const DEFAULT_PAIRS_LOOKBACK_DAYS: usize = 250;
+
+/// 协整检验至少需要的对齐样本数
+pub const MIN_PAIR_SAMPLES: usize = 60;
+
+/// 价差 Z 分数绝对值超过该值视为可交易信号
+pub const PAIRS_ENTRY_Z_SCORE: f64 = 2.0;
+
+/// 两变量 Engle-Granger 检验 5% 显著性临界值（MacKinnon）
+const ENGLE_GRANGER_CRITICAL_5PCT: f64 = -3.34;
+
+/// 配对交易信号（价差 = ln(A) - β·ln(B) - α）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PairsSignal {
+    /// 价差过低：做多 A、做空 B
+    LongAShortB,
+    /// 价差过高：做空 A、做多 B
+    ShortALongB,
+    /// 价差处于正常区间
+    Neutral,
+}
+
+/// 股票对协整分析结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PairAnalysis {
+    pub stock_a: String,
+    pub stock_b: String,
+    /// 对冲比例 β（每单位 A 对应的 B 头寸，对数价格口径）
+    pub hedge_ratio: f64,
+    /// 价差残差的 ADF t 统计量，越负协整越显著
+    pub adf_statistic: f64,
+    pub spread_mean: f64,
+    pub spread_std: f64,
+    pub current_z_score: f64,
+    /// 价差回归半衰期（交易日）
+    pub half_life_days: f64,
+    pub signal: PairsSignal,
+}
+
+/// 对齐后的两条收盘价序列做 Engle-Granger 协整检验；不协整或不具均值回归特性时返回 None
+pub fn analyze_pair(
+    stock_a: &str,
+    stock_b: &str,
+    closes_a: &[f64],
+    closes_b: &[f64],
+) -> Option<PairAnalysis> {
+    let n = closes_a.len().min(closes_b.len());
+    if n < MIN_PAIR_SAMPLES
+        || closes_a[..n]
+            .iter()
+            .chain(&closes_b[..n])
+            .any(|&p| p <= 0.0)
+    {
+        return None;
+    }
+    let log_a: Vec<f64> = closes_a[..n].iter().map(|p| p.ln()).collect();
+    let log_b: Vec<f64> = closes_b[..n].iter().map(|p| p.ln()).collect();
+
+    // 第一步：OLS 求对冲比例与价差残差
+    let (alpha, hedge_ratio) = ols(&log_b, &log_a)?;
+    let spread: Vec<f64> = log_a
+        .iter()
+        .zip(&log_b)
+        .map(|(a, b)| a - alpha - hedge_ratio * b)
+        .collect();
+
+    // 第二步：残差 ADF 检验
+    let adf_statistic = adf_statistic(&spread)?;
+    if adf_statistic >= ENGLE_GRANGER_CRITICAL_5PCT {
+        return None;
+    }
+    let half_life_days = estimate_half_life(&spread)?;
+
+    let spread_mean = spread.iter().sum::<f64>() / n as f64;
+    let spread_std = (spread
+        .iter()
+        .map(|s| (s - spread_mean).powi(2))
+        .sum::<f64>()
+        / n as f64)
+        .sqrt();
+    if spread_std <= 1e-12 {
+        return None;
+    }
+    let current_z_score = (spread[n - 1] - spread_mean) / spread_std;
+    let signal = if current_z_score < -PAIRS_ENTRY_Z_SCORE {
+        PairsSignal::LongAShortB
+    } else if current_z_score > PAIRS_ENTRY_Z_SCORE {
+        PairsSignal::ShortALongB
+    } else {
+        PairsSignal::Neutral
+    };
+
+    Some(PairAnalysis {
+        stock_a: stock_a.to_string(),
+        stock_b: stock_b.to_string(),
+        hedge_ratio,
+        adf_statistic,
+        spread_mean,
+        spread_std,
+        current_z_score,
+        half_life_days,
+        signal,
+    })
+}
+
+/// 简单线性回归 y = a + b·x，返回 (a, b)
+fn ols(x: &[f64], y: &[f64]) -> Option<(f64, f64)> {
+    let n = x.len() as f64;
+    let mean_x = x.iter().sum::<f64>() / n;
+    let mean_y = y.iter().sum::<f64>() / n;
+    let (mut cov, mut var) = (0.0, 0.0);
+    for (xi, yi) in x.iter().zip(y) {
+        cov += (xi - mean_x) * (yi - mean_y);
+        var += (xi - mean_x).powi(2);
+    }
+    if var <= 1e-12 {
+        return None;
+    }
+    let slope = cov / var;
+    Some((mean_y - slope * mean_x, slope))
+}
+
+/// 无常数项、零滞后的 ADF 回归 Δe_t = γ·e_{t-1} + u_t，返回 γ 的 t 统计量
+fn adf_statistic(residuals: &[f64]) -> Option<f64> {
+    let lagged = &residuals[..residuals.len() - 1];
+    let deltas: Vec<f64> = residuals.windows(2).map(|w| w[1] - w[0]).collect();
+
+    let sxx: f64 = lagged.iter().map(|e| e * e).sum();
+    if sxx <= 1e-12 || deltas.len() < 3 {
+        return None;
+    }
+    let gamma = lagged.iter().zip(&deltas).map(|(e, d)| e * d).sum::<f64>() / sxx;
+    let sse: f64 = lagged
+        .iter()
+        .zip(&deltas)
+        .map(|(e, d)| (d - gamma * e).powi(2))
+        .sum();
+    let se = (sse / (deltas.len() - 1) as f64 / sxx).sqrt();
+    (se > 0.0).then(|| gamma / se)
+}
+
+/// 在给定股票池中两两检验协整关系，按 |Z| 从大到小返回协整的股票对
+pub async fn find_cointegrated_pairs(
+    stock_codes: &[String],
+    pool: &DbPool,
+    lookback_days: usize,
+) -> Result<Vec<PairAnalysis>, AppError> {
+    let mut series: Vec<(&String, HashMap<NaiveDate, f64>)> = Vec::with_capacity(stock_codes.len());
+    for code in stock_codes {
+        let history = repository::get_recent_historical_data(code, lookback_days, pool).await?;
+        if history.len() >= MIN_PAIR_SAMPLES {
+            series.push((
+                code,
+                history
+                    .into_iter()
+                    .map(|bar| (bar.date, bar.close))
+                    .collect(),
+            ));
+        }
+    }
+
+    let mut pairs = Vec::new();
+    for (i, (code_a, closes_a)) in series.iter().enumerate() {
+        for (code_b, closes_b) in &series[i + 1..] {
+            let mut dates: Vec<&NaiveDate> = closes_a
+                .keys()
+                .filter(|d| closes_b.contains_key(d))
+                .collect();
+            dates.sort();
+            let aligned_a: Vec<f64> = dates.iter().map(|d| closes_a[*d]).collect();
+            let aligned_b: Vec<f64> = dates.iter().map(|d| closes_b[*d]).collect();
+            if let Some(pair) = analyze_pair(code_a, code_b, &aligned_a, &aligned_b) {
+                pairs.push(pair);
+            }
+        }
+    }
+
+    pairs.sort_by(|a, b| b.current_z_score.abs().total_cmp(&a.current_z_score.abs()));
+    Ok(pairs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 确定性伪随机序列（LCG），映射到 [-0.5, 0.5)
+    fn noise(seed: u64, len: usize) -> Vec<f64> {
+        let mut state = seed;
+        (0..len)
+            .map(|_| {
+                state = state
+                    .wrapping_mul(6364136223846793005)
+                    .wrapping_add(1442695040888963407);
+                (state >> 11) as f64 / (1u64 << 53) as f64 - 0.5
+            })
+            .collect()
+    }
+
+    fn random_walk(seed: u64, len: usize) -> Vec<f64> {
+        noise(seed, len)
+            .iter()
+            .scan(3.0, |level, e| {
+                *level += 0.04 * e;
+                Some(level.exp())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_cointegrated_pair_is_detected() {
+        let b = random_walk(7, 200);
+        // ln(A) = 0.2 + ln(B) + AR(1) 噪声，末日价差大幅走低
+        let mut spread = 0.0;
+        let mut a: Vec<f64> = b
+            .iter()
+            .zip(noise(11, 200))
+            .map(|(pb, e)| {
+                spread = 0.5 * spread + 0.02 * e;
+                (0.2 + pb.ln() + spread).exp()
+            })
+            .collect();
+        *a.last_mut().unwrap() *= 0.95;
+
+        let pair = analyze_pair("A", "B", &a, &b).expect("should be cointegrated");
+        assert!((pair.hedge_ratio - 1.0).abs() < 0.2);
+        assert!(pair.adf_statistic < ENGLE_GRANGER_CRITICAL_5PCT);
+        assert!(pair.half_life_days < 5.0);
+        assert!(pair.current_z_score < -PAIRS_ENTRY_Z_SCORE);
+        assert_eq!(pair.signal, PairsSignal::LongAShortB);
+    }
+
+    #[test]
+    fn test_independent_random_walks_are_rejected() {
+        let a = random_walk(3, 200);
+        let b = random_walk(5, 200);
+        assert!(analyze_pair("A", "B", &a, &b).is_none());
+        assert!(analyze_pair("A", "B", &a[..30], &b[..30]).is_none());
+    }
+}
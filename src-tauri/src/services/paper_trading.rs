@@ -0,0 +1,427 @@
+//! 模拟交易（纸面交易）
+//!
+//! 以虚拟资金按用户给定价格成交，账户保存在 Tauri 托管状态 [`PaperTradingState`] 中，
+//! 应用重启后重置。成交按 A 股规则计费（佣金、印花税、过户费，可选滑点），
+//! 买入须为 100 股整数倍，当日买入的股份次一交易日才可卖出（T+1）。
+
+use crate::error::AppError;
+use crate::utils::canonical_stock_symbol;
+use chrono::{NaiveDate, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// 成交事件名
+pub const PAPER_TRADE_EXECUTED_EVENT: &str = "paper_trade_executed";
+/// 默认初始资金（元）
+pub const DEFAULT_INITIAL_CAPITAL: f64 = 1_000_000.0;
+/// 买入最小单位（股）
+pub const LOT_SIZE: u64 = 100;
+
+/// 交易成本模型（费率均为成交金额的比例）
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct TransactionCostModel {
+    /// 佣金费率（双向）
+    pub commission_rate: f64,
+    /// 单笔最低佣金（元）
+    pub min_commission: f64,
+    /// 印花税（仅卖出）
+    pub stamp_duty_rate: f64,
+    /// 过户费（双向）
+    pub transfer_fee_rate: f64,
+    /// 滑点：买入按价格上浮、卖出按价格下浮该比例成交
+    pub slippage_rate: f64,
+}
+
+impl Default for TransactionCostModel {
+    /// 万 2.5 佣金最低 5 元、卖出千 0.5 印花税、十万分之一过户费、无滑点
+    fn default() -> Self {
+        Self {
+            commission_rate: 0.000_25,
+            min_commission: 5.0,
+            stamp_duty_rate: 0.000_5,
+            transfer_fee_rate: 0.000_01,
+            slippage_rate: 0.0,
+        }
+    }
+}
+
+impl TransactionCostModel {
+    /// 计入滑点后的成交价
+    pub fn execution_price(&self, side: TradeSide, price: f64) -> f64 {
+        match side {
+            TradeSide::Buy => price * (1.0 + self.slippage_rate),
+            TradeSide::Sell => price * (1.0 - self.slippage_rate),
+        }
+    }
+
+    /// 成交金额对应的全部交易费用
+    pub fn costs(&self, side: TradeSide, amount: f64) -> f64 {
+        let commission = (amount * self.commission_rate).max(self.min_commission);
+        let stamp_duty = match side {
+            TradeSide::Buy => 0.0,
+            TradeSide::Sell => amount * self.stamp_duty_rate,
+        };
+        commission + stamp_duty + amount * self.transfer_fee_rate
+    }
+}
+
+/// 买卖方向
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TradeSide {
+    Buy,
+    Sell,
+}
+
+/// 持仓
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Position {
+    pub stock_code: String,
+    pub shares: u64,
+    /// 每股持仓成本（含买入费用）
+    pub avg_cost: f64,
+    pub last_buy_date: NaiveDate,
+    /// `last_buy_date` 当日买入的股数，当日不可卖出
+    pub shares_bought_on_last_buy_date: u64,
+}
+
+impl Position {
+    /// `today` 可卖出的股数
+    pub fn available_shares(&self, today: NaiveDate) -> u64 {
+        if self.last_buy_date == today {
+            self.shares - self.shares_bought_on_last_buy_date
+        } else {
+            self.shares
+        }
+    }
+}
+
+/// 成交记录
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Transaction {
+    pub id: u64,
+    pub stock_code: String,
+    pub side: TradeSide,
+    pub shares: u64,
+    /// 成交价（含滑点）
+    pub price: f64,
+    /// 成交金额 = 成交价 × 股数
+    pub amount: f64,
+    pub costs: f64,
+    /// 卖出的已实现盈亏（扣除买卖双边费用）；买入为空
+    pub realized_pnl: Option<f64>,
+    pub executed_at: NaiveDateTime,
+}
+
+/// 模拟交易账户
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PaperTradingAccount {
+    pub initial_capital: f64,
+    pub positions: HashMap<String, Position>,
+    pub cash: f64,
+    pub transaction_history: Vec<Transaction>,
+    pub cost_model: TransactionCostModel,
+}
+
+/// 持仓估值
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PositionValue {
+    pub stock_code: String,
+    pub shares: u64,
+    pub avg_cost: f64,
+    pub current_price: f64,
+    pub market_value: f64,
+    pub unrealized_pnl: f64,
+}
+
+/// 账户估值与交易统计
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PaperAccountValue {
+    pub initial_capital: f64,
+    pub cash: f64,
+    pub market_value: f64,
+    pub total_value: f64,
+    /// 总收益率（%）
+    pub total_return_pct: f64,
+    pub realized_pnl: f64,
+    pub unrealized_pnl: f64,
+    pub total_costs: f64,
+    /// 卖出笔数
+    pub closed_trades: usize,
+    /// 盈利卖出笔数 / 卖出笔数
+    pub win_rate: f64,
+    /// 按代码升序
+    pub positions: Vec<PositionValue>,
+}
+
+impl Default for PaperTradingAccount {
+    fn default() -> Self {
+        Self::new(DEFAULT_INITIAL_CAPITAL)
+    }
+}
+
+impl PaperTradingAccount {
+    pub fn new(initial_capital: f64) -> Self {
+        Self {
+            initial_capital,
+            positions: HashMap::new(),
+            cash: initial_capital,
+            transaction_history: Vec::new(),
+            cost_model: TransactionCostModel::default(),
+        }
+    }
+
+    /// 按给定价格买入；资金须覆盖成交金额与费用
+    pub fn buy(
+        &mut self,
+        stock_code: &str,
+        shares: u64,
+        price: f64,
+        executed_at: NaiveDateTime,
+    ) -> Result<Transaction, AppError> {
+        validate_order(shares, price)?;
+        if !shares.is_multiple_of(LOT_SIZE) {
+            return Err(AppError::InvalidInput(format!(
+                "买入股数须为 {LOT_SIZE} 股的整数倍"
+            )));
+        }
+        let code = canonical_stock_symbol(stock_code);
+        let price = self.cost_model.execution_price(TradeSide::Buy, price);
+        let amount = price * shares as f64;
+        let costs = self.cost_model.costs(TradeSide::Buy, amount);
+        if amount + costs > self.cash {
+            return Err(AppError::InvalidInput(format!(
+                "可用资金不足：需要 {:.2} 元，可用 {:.2} 元",
+                amount + costs,
+                self.cash
+            )));
+        }
+
+        self.cash -= amount + costs;
+        let today = executed_at.date();
+        let position = self.positions.entry(code.clone()).or_insert(Position {
+            stock_code: code.clone(),
+            shares: 0,
+            avg_cost: 0.0,
+            last_buy_date: today,
+            shares_bought_on_last_buy_date: 0,
+        });
+        let total_cost = position.avg_cost * position.shares as f64 + amount + costs;
+        position.shares += shares;
+        position.avg_cost = total_cost / position.shares as f64;
+        if position.last_buy_date == today {
+            position.shares_bought_on_last_buy_date += shares;
+        } else {
+            position.last_buy_date = today;
+            position.shares_bought_on_last_buy_date = shares;
+        }
+
+        Ok(self.record(Transaction {
+            id: 0,
+            stock_code: code,
+            side: TradeSide::Buy,
+            shares,
+            price,
+            amount,
+            costs,
+            realized_pnl: None,
+            executed_at,
+        }))
+    }
+
+    /// 按给定价格卖出；当日买入的股份不可卖出
+    pub fn sell(
+        &mut self,
+        stock_code: &str,
+        shares: u64,
+        price: f64,
+        executed_at: NaiveDateTime,
+    ) -> Result<Transaction, AppError> {
+        validate_order(shares, price)?;
+        let code = canonical_stock_symbol(stock_code);
+        let position = self
+            .positions
+            .get(&code)
+            .ok_or_else(|| AppError::InvalidInput(format!("未持有 {code}")))?;
+        let available = position.available_shares(executed_at.date());
+        if shares > available {
+            return Err(AppError::InvalidInput(format!(
+                "{code} 可卖出 {available} 股（当日买入部分 T+1 后可卖），委托 {shares} 股"
+            )));
+        }
+
+        let price = self.cost_model.execution_price(TradeSide::Sell, price);
+        let amount = price * shares as f64;
+        let costs = self.cost_model.costs(TradeSide::Sell, amount);
+        let realized_pnl = amount - costs - position.avg_cost * shares as f64;
+        self.cash += amount - costs;
+        if shares == position.shares {
+            self.positions.remove(&code);
+        } else if let Some(position) = self.positions.get_mut(&code) {
+            position.shares -= shares;
+        }
+
+        Ok(self.record(Transaction {
+            id: 0,
+            stock_code: code,
+            side: TradeSide::Sell,
+            shares,
+            price,
+            amount,
+            costs,
+            realized_pnl: Some(realized_pnl),
+            executed_at,
+        }))
+    }
+
+    /// 编号并写入成交记录
+    fn record(&mut self, mut transaction: Transaction) -> Transaction {
+        transaction.id = self.transaction_history.len() as u64 + 1;
+        self.transaction_history.push(transaction.clone());
+        transaction
+    }
+
+    /// 按最新价估值；未提供价格的持仓按持仓成本计
+    pub fn account_value(&self, current_prices: &HashMap<String, f64>) -> PaperAccountValue {
+        let prices: HashMap<String, f64> = current_prices
+            .iter()
+            .map(|(code, price)| (canonical_stock_symbol(code), *price))
+            .collect();
+        let mut positions: Vec<PositionValue> = self
+            .positions
+            .values()
+            .map(|position| {
+                let current_price = prices
+                    .get(&position.stock_code)
+                    .copied()
+                    .filter(|price| *price > 0.0)
+                    .unwrap_or(position.avg_cost);
+                let market_value = current_price * position.shares as f64;
+                PositionValue {
+                    stock_code: position.stock_code.clone(),
+                    shares: position.shares,
+                    avg_cost: position.avg_cost,
+                    current_price,
+                    market_value,
+                    unrealized_pnl: market_value - position.avg_cost * position.shares as f64,
+                }
+            })
+            .collect();
+        positions.sort_by(|a, b| a.stock_code.cmp(&b.stock_code));
+
+        let closed: Vec<f64> = self
+            .transaction_history
+            .iter()
+            .filter_map(|transaction| transaction.realized_pnl)
+            .collect();
+        let market_value: f64 = positions.iter().map(|p| p.market_value).sum();
+        let total_value = self.cash + market_value;
+        PaperAccountValue {
+            initial_capital: self.initial_capital,
+            cash: self.cash,
+            market_value,
+            total_value,
+            total_return_pct: if self.initial_capital > 0.0 {
+                (total_value / self.initial_capital - 1.0) * 100.0
+            } else {
+                0.0
+            },
+            realized_pnl: closed.iter().sum(),
+            unrealized_pnl: positions.iter().map(|p| p.unrealized_pnl).sum(),
+            total_costs: self.transaction_history.iter().map(|t| t.costs).sum(),
+            closed_trades: closed.len(),
+            win_rate: if closed.is_empty() {
+                0.0
+            } else {
+                closed.iter().filter(|pnl| **pnl > 0.0).count() as f64 / closed.len() as f64
+            },
+            positions,
+        }
+    }
+}
+
+fn validate_order(shares: u64, price: f64) -> Result<(), AppError> {
+    if shares == 0 {
+        return Err(AppError::InvalidInput("委托股数必须大于 0".to_string()));
+    }
+    if !(price.is_finite() && price > 0.0) {
+        return Err(AppError::InvalidInput("委托价格必须大于 0".to_string()));
+    }
+    Ok(())
+}
+
+/// 模拟交易账户（Tauri 托管状态）
+#[derive(Default)]
+pub struct PaperTradingState {
+    pub account: Mutex<PaperTradingAccount>,
+}
+
+/// `paper_trade_executed` 事件负载
+#[derive(Debug, Clone, Serialize)]
+pub struct PaperTradeExecutedEvent {
+    pub transaction: Transaction,
+    pub cash: f64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(day: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2026, 3, day)
+            .unwrap()
+            .and_hms_opt(10, 0, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_buy_sell_with_costs_and_t_plus_one() {
+        let mut account = PaperTradingAccount::default();
+
+        // 佣金 2.5 元按最低 5 元收取，过户费 0.1 元
+        let buy = account.buy("600519", 1000, 10.0, at(2)).unwrap();
+        assert!((buy.costs - 5.1).abs() < 1e-9);
+        assert!((account.cash - 989_994.9).abs() < 1e-6);
+        assert!((account.positions["600519"].avg_cost - 10.0051).abs() < 1e-9);
+
+        assert!(account.sell("600519", 1000, 11.0, at(2)).is_err(), "当日买入不可卖出");
+        assert!(account.buy("600519", 150, 10.0, at(2)).is_err(), "须整手买入");
+
+        // 卖出费用：佣金 5 + 印花税 5.5 + 过户费 0.11
+        let sell = account.sell("600519", 1000, 11.0, at(3)).unwrap();
+        assert!((sell.costs - 10.61).abs() < 1e-9);
+        assert!((sell.realized_pnl.unwrap() - 984.29).abs() < 1e-6);
+        assert!(account.positions.is_empty());
+
+        account.buy("000001", 500, 20.0, at(3)).unwrap();
+        account.sell("000001", 500, 19.0, at(4)).unwrap();
+
+        let value = account.account_value(&HashMap::new());
+        assert_eq!(value.closed_trades, 2);
+        assert!((value.win_rate - 0.5).abs() < 1e-9);
+        assert!((value.realized_pnl - (984.29 - 514.945)).abs() < 1e-6);
+        assert!((value.total_value - (DEFAULT_INITIAL_CAPITAL + 984.29 - 514.945)).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_account_value_marks_positions_to_market() {
+        let mut account = PaperTradingAccount::default();
+        account.buy("sh600519", 1000, 10.0, at(2)).unwrap();
+
+        let prices = HashMap::from([("600519.SH".to_string(), 12.0)]);
+        let value = account.account_value(&prices);
+        assert_eq!(value.positions.len(), 1);
+        assert!((value.market_value - 12_000.0).abs() < 1e-9);
+        assert!((value.unrealized_pnl - 1_994.9).abs() < 1e-6);
+        assert!((value.total_value - (989_994.9 + 12_000.0)).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_insufficient_cash_is_rejected() {
+        let mut account = PaperTradingAccount::new(10_000.0);
+        assert!(account.buy("600519", 1000, 10.0, at(2)).is_err());
+        assert_eq!(account.cash, 10_000.0);
+        assert!(account.transaction_history.is_empty());
+    }
+}
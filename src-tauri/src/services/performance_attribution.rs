@@ -0,0 +1,268 @@
+//! 预测表现归因服务
+//!
+//! 以交易日志中已回填实际价格的预测为样本（真实样本外结果），按预测发出当日的
+//! 市场状态、趋势状态与波动率分组统计方向准确率，回答"模型在什么行情下可信"。
+
+use crate::db::{models::HistoricalData, repository};
+use crate::error::AppError;
+use crate::prediction::analysis::{market_regime, prediction_interval, trend};
+use chrono::{Duration, Local};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use std::collections::HashMap;
+
+/// 默认回看天数（自然日）
+pub const DEFAULT_ATTRIBUTION_LOOKBACK_DAYS: usize = 365;
+/// 状态分类使用的历史窗口（交易日），需覆盖趋势分析的 120 日要求
+const CONTEXT_BARS: usize = 250;
+/// 计算波动率的近期窗口（交易日）
+const VOLATILITY_WINDOW: usize = 20;
+/// 波动率分组数
+const VOLATILITY_QUINTILES: usize = 5;
+
+/// 单个分组的方向准确率
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RegimeAccuracy {
+    pub samples: usize,
+    pub correct: usize,
+    pub accuracy: f64,
+}
+
+/// 预测表现归因结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PerformanceAttribution {
+    pub sample_count: usize,
+    pub overall_accuracy: f64,
+    /// 按预测发出日市场状态分组
+    pub by_regime: HashMap<String, RegimeAccuracy>,
+    /// 按预测发出日趋势状态分组的方向准确率
+    pub by_trend_state: HashMap<String, f64>,
+    /// 按近 20 日波动率五分位（由低到高）分组的方向准确率，空组为 0
+    pub by_volatility_quintile: Vec<f64>,
+    /// 准确率最低/最高的市场状态；无样本时为空
+    pub weakest_regime: String,
+    pub strongest_regime: String,
+}
+
+/// 一条已归因的预测结果
+#[derive(Debug, Clone)]
+pub struct AttributedOutcome {
+    pub regime: String,
+    pub trend_state: String,
+    pub volatility: f64,
+    pub correct: bool,
+}
+
+fn accuracy(correct: usize, samples: usize) -> f64 {
+    if samples == 0 {
+        0.0
+    } else {
+        correct as f64 / samples as f64
+    }
+}
+
+/// 汇总已归因的预测结果
+pub fn summarize_attribution(outcomes: &[AttributedOutcome]) -> PerformanceAttribution {
+    let mut by_regime: HashMap<String, RegimeAccuracy> = HashMap::new();
+    let mut trend_counts: HashMap<String, (usize, usize)> = HashMap::new();
+    for outcome in outcomes {
+        let regime = by_regime.entry(outcome.regime.clone()).or_default();
+        regime.samples += 1;
+        regime.correct += usize::from(outcome.correct);
+        let trend = trend_counts.entry(outcome.trend_state.clone()).or_default();
+        trend.0 += 1;
+        trend.1 += usize::from(outcome.correct);
+    }
+    for regime in by_regime.values_mut() {
+        regime.accuracy = accuracy(regime.correct, regime.samples);
+    }
+    let by_trend_state = trend_counts
+        .into_iter()
+        .map(|(state, (samples, correct))| (state, accuracy(correct, samples)))
+        .collect();
+
+    // 按波动率排名等分五组，保证样本充足时各组数量均衡
+    let mut ranked: Vec<&AttributedOutcome> = outcomes.iter().collect();
+    ranked.sort_by(|a, b| a.volatility.total_cmp(&b.volatility));
+    let mut quintiles = [(0usize, 0usize); VOLATILITY_QUINTILES];
+    for (rank, outcome) in ranked.iter().enumerate() {
+        let bucket = rank * VOLATILITY_QUINTILES / ranked.len();
+        quintiles[bucket].0 += 1;
+        quintiles[bucket].1 += usize::from(outcome.correct);
+    }
+
+    // 同准确率按名称排序，保证结果稳定
+    let mut regimes: Vec<(&String, &RegimeAccuracy)> = by_regime.iter().collect();
+    regimes.sort_by(|a, b| a.1.accuracy.total_cmp(&b.1.accuracy).then(a.0.cmp(b.0)));
+    let weakest_regime = regimes
+        .first()
+        .map(|(name, _)| (*name).clone())
+        .unwrap_or_default();
+    let strongest_regime = regimes
+        .last()
+        .map(|(name, _)| (*name).clone())
+        .unwrap_or_default();
+
+    let correct = outcomes.iter().filter(|o| o.correct).count();
+    PerformanceAttribution {
+        sample_count: outcomes.len(),
+        overall_accuracy: accuracy(correct, outcomes.len()),
+        by_regime,
+        by_trend_state,
+        by_volatility_quintile: quintiles
+            .iter()
+            .map(|&(samples, correct)| accuracy(correct, samples))
+            .collect(),
+        weakest_regime,
+        strongest_regime,
+    }
+}
+
+/// 以预测发出日（含）之前的行情对一条预测做归因；方向以发出日收盘价为基准，
+/// 预测价与实际价同向（含同为持平）记为正确。数据不足时返回 None
+fn attribute_entry(
+    history: &[HistoricalData],
+    action_date: chrono::NaiveDate,
+    predicted_price: f64,
+    actual_price: f64,
+) -> Option<AttributedOutcome> {
+    let end = history.partition_point(|bar| bar.date <= action_date);
+    let context = &history[end.saturating_sub(CONTEXT_BARS)..end];
+    let base = context.last()?.close;
+    if base <= 0.0 {
+        return None;
+    }
+
+    let closes: Vec<f64> = context.iter().map(|bar| bar.close).collect();
+    let highs: Vec<f64> = context.iter().map(|bar| bar.high).collect();
+    let lows: Vec<f64> = context.iter().map(|bar| bar.low).collect();
+    let regime = market_regime::classify_market_regime(&closes, &highs, &lows);
+    let trend = trend::analyze_trend(&closes, &highs, &lows);
+    let recent = &closes[closes.len().saturating_sub(VOLATILITY_WINDOW + 1)..];
+
+    let predicted = (predicted_price - base).signum();
+    let actual = (actual_price - base).signum();
+    Some(AttributedOutcome {
+        regime: regime.regime.to_string(),
+        trend_state: trend.overall_trend.to_string(),
+        volatility: prediction_interval::realized_daily_vol(recent),
+        correct: predicted == actual,
+    })
+}
+
+/// 统计某只股票近 `lookback_days` 个自然日内已回填实际价格的预测，按行情状态归因方向准确率
+pub async fn attribute_prediction_performance(
+    stock_code: &str,
+    pool: &SqlitePool,
+    lookback_days: usize,
+) -> Result<PerformanceAttribution, AppError> {
+    let since = Local::now().date_naive() - Duration::days(lookback_days as i64);
+    let entries: Vec<_> = repository::get_journal_entries(Some(stock_code), pool)
+        .await?
+        .into_iter()
+        .filter(|entry| entry.action_date >= since)
+        .filter_map(|entry| entry.actual_price.map(|actual| (entry, actual)))
+        .collect();
+    let Some(earliest) = entries.iter().map(|(entry, _)| entry.action_date).min() else {
+        return Ok(summarize_attribution(&[]));
+    };
+
+    // 多取约 CONTEXT_BARS 个交易日（折合自然日）用于状态分类
+    let start = earliest - Duration::days((CONTEXT_BARS * 3 / 2) as i64);
+    let history = repository::get_historical_data(
+        stock_code,
+        &start.format("%Y-%m-%d").to_string(),
+        "9999-12-31",
+        pool,
+    )
+    .await?;
+
+    let outcomes: Vec<AttributedOutcome> = entries
+        .iter()
+        .filter_map(|(entry, actual)| {
+            attribute_entry(&history, entry.action_date, entry.predicted_price, *actual)
+        })
+        .collect();
+    Ok(summarize_attribution(&outcomes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn outcome(regime: &str, trend: &str, volatility: f64, correct: bool) -> AttributedOutcome {
+        AttributedOutcome {
+            regime: regime.to_string(),
+            trend_state: trend.to_string(),
+            volatility,
+            correct,
+        }
+    }
+
+    #[test]
+    fn test_summarize_attribution_groups_by_regime_and_volatility() {
+        let outcomes: Vec<AttributedOutcome> = (0..10)
+            .map(|i| {
+                let regime = if i < 4 {
+                    "震荡整理"
+                } else {
+                    "强势上涨"
+                };
+                // 震荡市 1/4 正确；趋势市 5/6 正确
+                let correct = if i < 4 { i == 0 } else { i != 9 };
+                outcome(regime, "上涨", i as f64 * 0.01, correct)
+            })
+            .collect();
+        let result = summarize_attribution(&outcomes);
+
+        assert_eq!(result.sample_count, 10);
+        assert!((result.overall_accuracy - 0.6).abs() < 1e-9);
+        assert_eq!(result.by_regime["震荡整理"].samples, 4);
+        assert!((result.by_regime["震荡整理"].accuracy - 0.25).abs() < 1e-9);
+        assert!((result.by_regime["强势上涨"].accuracy - 5.0 / 6.0).abs() < 1e-9);
+        assert_eq!(result.weakest_regime, "震荡整理");
+        assert_eq!(result.strongest_regime, "强势上涨");
+        assert!((result.by_trend_state["上涨"] - 0.6).abs() < 1e-9);
+        assert_eq!(result.by_volatility_quintile, vec![0.5, 0.0, 1.0, 1.0, 0.5]);
+    }
+
+    #[test]
+    fn test_summarize_attribution_empty() {
+        let result = summarize_attribution(&[]);
+        assert_eq!(result.sample_count, 0);
+        assert_eq!(result.by_volatility_quintile, vec![0.0; 5]);
+        assert!(result.weakest_regime.is_empty());
+    }
+
+    #[test]
+    fn test_attribute_entry_uses_action_day_close_as_base() {
+        let start = chrono::NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+        let history: Vec<HistoricalData> = (0..80)
+            .map(|i| {
+                let close = 10.0 + i as f64 * 0.1;
+                HistoricalData {
+                    symbol: "600000".to_string(),
+                    date: start + Duration::days(i),
+                    open: close,
+                    close,
+                    high: close + 0.2,
+                    low: close - 0.2,
+                    volume: 1000,
+                    amount: close * 1000.0,
+                    amplitude: 0.0,
+                    turnover_rate: 0.0,
+                    volume_ratio: 0.0,
+                    change_percent: 0.0,
+                    change: 0.0,
+                }
+            })
+            .collect();
+        let action_date = start + Duration::days(70);
+        // 发出日收盘 17.0：预测上涨、实际上涨 → 正确；后续数据不参与分类
+        let hit = attribute_entry(&history, action_date, 17.5, 17.2).unwrap();
+        assert!(hit.correct);
+        let miss = attribute_entry(&history, action_date, 16.5, 17.2).unwrap();
+        assert!(!miss.correct);
+        assert!(attribute_entry(&history, start - Duration::days(1), 10.0, 10.0).is_none());
+    }
+}
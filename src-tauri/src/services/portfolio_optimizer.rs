@@ -0,0 +1,485 @@
+//! 均值-方差组合优化服务
+//!
+//! 由多只股票按交易日对齐的日收益率估计相关系数矩阵与协方差矩阵，
+//! 求解"权重和为 1（可选：达到目标收益）"约束下的最小方差组合。
+//! 单只股票权重限制在 [0, 30%]，以原始有效集法求解带上下界的二次规划：
+//! 触及边界的权重加入工作集，KKT 乘子符号错误的权重再移出工作集。
+
+use crate::db::{repository, DbPool};
+use crate::error::AppError;
+use crate::prediction::cross_section::pearson;
+use crate::utils::math::calculate_std_dev;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+/// 默认回看交易日数
+pub const DEFAULT_PORTFOLIO_LOOKBACK_DAYS: usize = 250;
+/// 单只股票权重上限（集中度约束）
+pub const MAX_POSITION_WEIGHT: f64 = 0.30;
+/// 对齐后至少需要的日收益样本数
+const MIN_PORTFOLIO_SAMPLES: usize = 60;
+/// 无风险年化收益率（夏普比率基准）
+const RISK_FREE_RATE: f64 = 0.02;
+/// 每年交易日数（年化）
+const TRADING_DAYS_PER_YEAR: f64 = 252.0;
+/// 有效前沿采样点数
+const FRONTIER_POINTS: usize = 20;
+/// 权重越界判定容差
+const WEIGHT_TOLERANCE: f64 = 1e-9;
+/// 目标（日）收益可行性判定的相对容差
+const RETURN_TOLERANCE: f64 = 1e-12;
+/// KKT 乘子符号判定容差（日收益协方差量级约 1e-4）
+const MULTIPLIER_TOLERANCE: f64 = 1e-12;
+/// 有效集迭代上限：每步加入或移出一个约束，正常远少于该值
+const MAX_ACTIVE_SET_ITERATIONS: usize = 1000;
+
+/// 优化后的组合；收益率与波动率均为年化小数
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OptimizedPortfolio {
+    pub weights: HashMap<String, f64>,
+    pub expected_return: f64,
+    pub expected_volatility: f64,
+    /// (预期收益 - 无风险收益) / 预期波动率
+    pub sharpe_ratio: f64,
+    /// 有效前沿上的 (年化波动率, 年化收益) 点，按收益升序
+    pub efficient_frontier_points: Vec<(f64, f64)>,
+}
+
+/// 各收益序列的日均收益
+fn mean_returns(returns: &[Vec<f64>]) -> Vec<f64> {
+    returns
+        .iter()
+        .map(|r| r.iter().sum::<f64>() / r.len().max(1) as f64)
+        .collect()
+}
+
+/// 各收益序列两两的皮尔逊相关系数矩阵（序列须等长）；零方差序列与其他序列的相关系数为 0
+pub fn calculate_correlation_matrix(returns: &[Vec<f64>]) -> Vec<Vec<f64>> {
+    let n = returns.len();
+    let mut matrix = vec![vec![0.0; n]; n];
+    for i in 0..n {
+        matrix[i][i] = 1.0;
+        for j in i + 1..n {
+            let corr = pearson(&returns[i], &returns[j]);
+            matrix[i][j] = corr;
+            matrix[j][i] = corr;
+        }
+    }
+    matrix
+}
+
+/// 协方差矩阵：相关系数 × 两序列标准差
+fn covariance_matrix(returns: &[Vec<f64>]) -> Vec<Vec<f64>> {
+    let stds: Vec<f64> = returns.iter().map(|r| calculate_std_dev(r)).collect();
+    calculate_correlation_matrix(returns)
+        .into_iter()
+        .enumerate()
+        .map(|(i, row)| row.into_iter().enumerate().map(|(j, c)| c * stds[i] * stds[j]).collect())
+        .collect()
+}
+
+/// 部分主元高斯消元；矩阵奇异时返回 None
+fn solve_linear_system(mut a: Vec<Vec<f64>>, mut b: Vec<f64>) -> Option<Vec<f64>> {
+    let n = b.len();
+    for col in 0..n {
+        let pivot = (col..n).max_by(|&i, &j| a[i][col].abs().total_cmp(&a[j][col].abs()))?;
+        if a[pivot][col].abs() < 1e-14 {
+            return None;
+        }
+        a.swap(col, pivot);
+        b.swap(col, pivot);
+        for row in col + 1..n {
+            let factor = a[row][col] / a[col][col];
+            let pivot_row = a[col].clone();
+            for (value, pivot_value) in a[row].iter_mut().zip(pivot_row).skip(col) {
+                *value -= factor * pivot_value;
+            }
+            b[row] -= factor * b[col];
+        }
+    }
+    let mut x = vec![0.0; n];
+    for row in (0..n).rev() {
+        let tail: f64 = (row + 1..n).map(|k| a[row][k] * x[k]).sum();
+        x[row] = (b[row] - tail) / a[row][row];
+    }
+    Some(x)
+}
+
+/// 固定 `fixed` 中的权重，求其余权重的等式约束最小方差解（KKT 方程组）。
+///
+/// 返回 (权重, 预算约束乘子 λ₁, 收益约束乘子 λ₂)；方程组奇异时返回 None
+fn solve_equality_qp(
+    cov: &[Vec<f64>],
+    mu: &[f64],
+    target: Option<f64>,
+    fixed: &[Option<f64>],
+) -> Option<(Vec<f64>, f64, f64)> {
+    let n = mu.len();
+    let fixed_weight = |i: usize| fixed[i].unwrap_or(0.0);
+    let free: Vec<usize> = (0..n).filter(|&i| fixed[i].is_none()).collect();
+    let budget = 1.0 - (0..n).map(fixed_weight).sum::<f64>();
+    let target_rest = target.map(|t| t - (0..n).map(|i| fixed_weight(i) * mu[i]).sum::<f64>());
+
+    // 2Σ_FF·w_F + λ₁·1 + λ₂·μ_F = -2Σ_FX·w_X，1'w_F = 预算，μ_F'w_F = 剩余目标收益
+    let m = free.len();
+    let size = m + 1 + usize::from(target_rest.is_some());
+    let mut a = vec![vec![0.0; size]; size];
+    let mut b = vec![0.0; size];
+    for (r, &i) in free.iter().enumerate() {
+        for (c, &j) in free.iter().enumerate() {
+            a[r][c] = 2.0 * cov[i][j];
+        }
+        a[r][m] = 1.0;
+        a[m][r] = 1.0;
+        if target_rest.is_some() {
+            a[r][m + 1] = mu[i];
+            a[m + 1][r] = mu[i];
+        }
+        b[r] = -2.0 * (0..n).map(|j| cov[i][j] * fixed_weight(j)).sum::<f64>();
+    }
+    b[m] = budget;
+    if let Some(t) = target_rest {
+        b[m + 1] = t;
+    }
+    let solution = solve_linear_system(a, b)?;
+
+    let mut weights: Vec<f64> = (0..n).map(fixed_weight).collect();
+    for (&i, &w) in free.iter().zip(&solution) {
+        weights[i] = w;
+    }
+    let lambda_return = if target_rest.is_some() { solution[m + 1] } else { 0.0 };
+    Some((weights, solution[m], lambda_return))
+}
+
+/// 按收益排序依次配满上限的极端组合：`highest_first` 为真时得到可行域内收益最高的组合，否则最低
+fn greedy_fill_weights(mu: &[f64], highest_first: bool) -> Vec<f64> {
+    let mut order: Vec<usize> = (0..mu.len()).collect();
+    order.sort_by(|&a, &b| mu[a].total_cmp(&mu[b]));
+    if highest_first {
+        order.reverse();
+    }
+    let mut weights = vec![0.0; mu.len()];
+    let mut remaining: f64 = 1.0;
+    for i in order {
+        let w = remaining.min(MAX_POSITION_WEIGHT);
+        weights[i] = w;
+        remaining -= w;
+        if remaining <= 0.0 {
+            break;
+        }
+    }
+    weights
+}
+
+fn dot(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
+
+/// 满足全部约束的初始权重：无目标收益时等权；有目标时取收益最低与最高两个极端组合的凸组合。
+///
+/// 目标收益超出可行范围时返回 None；可行域内收益恒定时目标约束冗余，返回的目标为 None
+fn feasible_start(mu: &[f64], target: Option<f64>) -> Option<(Vec<f64>, Option<f64>)> {
+    let n = mu.len();
+    let Some(t) = target else {
+        return Some((vec![1.0 / n as f64; n], None));
+    };
+    let (low, high) = (greedy_fill_weights(mu, false), greedy_fill_weights(mu, true));
+    let (low_return, high_return) = (dot(&low, mu), dot(&high, mu));
+    let tolerance = RETURN_TOLERANCE * (1.0 + t.abs());
+    if t < low_return - tolerance || t > high_return + tolerance {
+        return None;
+    }
+    if high_return - low_return <= tolerance {
+        return Some((low, None));
+    }
+    let alpha = ((t - low_return) / (high_return - low_return)).clamp(0.0, 1.0);
+    let start = low.iter().zip(&high).map(|(l, h)| l + alpha * (h - l)).collect();
+    Some((start, target))
+}
+
+/// 权重约束在 [0, MAX_POSITION_WEIGHT] 内的最小方差权重；`target` 为目标（日）收益，
+/// 不可行或协方差矩阵奇异时返回 None。
+///
+/// 原始有效集法：从可行点出发，在当前工作集（固定在边界上的权重）下求等式约束 QP 的解，
+/// 沿该方向前进至首个触及边界的权重并将其加入工作集；到达子问题最优点后，
+/// 若某个固定权重的 KKT 乘子符号错误（放开后方差还能下降），将其移出工作集继续迭代。
+fn min_variance_weights(cov: &[Vec<f64>], mu: &[f64], target: Option<f64>) -> Option<Vec<f64>> {
+    let n = mu.len();
+    let (mut weights, target) = feasible_start(mu, target)?;
+    let mut fixed: Vec<Option<f64>> = vec![None; n];
+    for _ in 0..MAX_ACTIVE_SET_ITERATIONS {
+        let (candidate, lambda_budget, lambda_return) = solve_equality_qp(cov, mu, target, &fixed)?;
+        let step: Vec<f64> = candidate.iter().zip(&weights).map(|(c, w)| c - w).collect();
+
+        if step.iter().all(|p| p.abs() <= WEIGHT_TOLERANCE) {
+            // 固定权重的乘子：g_i = 2(Σw)_i + λ₁ + λ₂μ_i，下界处须 ≥ 0，上界处须 ≤ 0
+            let wrong_sign = (0..n)
+                .filter_map(|i| {
+                    let bound = fixed[i]?;
+                    let gradient = 2.0 * dot(&cov[i], &candidate) + lambda_budget + lambda_return * mu[i];
+                    let violation = if bound > 0.0 { gradient } else { -gradient };
+                    (violation > MULTIPLIER_TOLERANCE).then_some((i, violation))
+                })
+                .max_by(|a, b| a.1.total_cmp(&b.1));
+            match wrong_sign {
+                Some((i, _)) => fixed[i] = None,
+                None => {
+                    return Some(
+                        candidate
+                            .into_iter()
+                            .map(|w| w.clamp(0.0, MAX_POSITION_WEIGHT))
+                            .collect(),
+                    )
+                }
+            }
+            weights = candidate;
+            continue;
+        }
+
+        // 步长：不越过任何自由权重的边界
+        let mut alpha = 1.0;
+        let mut blocking = None;
+        for i in (0..n).filter(|&i| fixed[i].is_none()) {
+            let (w, p) = (weights[i], step[i]);
+            let (limit, bound) = if p < -WEIGHT_TOLERANCE {
+                (-w / p, 0.0)
+            } else if p > WEIGHT_TOLERANCE {
+                ((MAX_POSITION_WEIGHT - w) / p, MAX_POSITION_WEIGHT)
+            } else {
+                continue;
+            };
+            if limit < alpha {
+                alpha = limit.max(0.0);
+                blocking = Some((i, bound));
+            }
+        }
+        for (w, p) in weights.iter_mut().zip(&step) {
+            *w += alpha * p;
+        }
+        if let Some((i, bound)) = blocking {
+            weights[i] = bound;
+            fixed[i] = Some(bound);
+        }
+    }
+    None
+}
+
+/// 组合年化 (收益, 波动率)
+fn portfolio_stats(weights: &[f64], cov: &[Vec<f64>], mu: &[f64]) -> (f64, f64) {
+    let daily_return: f64 = weights.iter().zip(mu).map(|(w, r)| w * r).sum();
+    let variance: f64 = (0..weights.len())
+        .flat_map(|i| (0..weights.len()).map(move |j| (i, j)))
+        .map(|(i, j)| weights[i] * weights[j] * cov[i][j])
+        .sum();
+    (
+        daily_return * TRADING_DAYS_PER_YEAR,
+        (variance.max(0.0) * TRADING_DAYS_PER_YEAR).sqrt(),
+    )
+}
+
+/// 由对齐的日收益序列求最优组合；`target_return` 为年化目标收益（小数），None 时取最小方差组合
+pub fn optimize_weights(
+    stock_codes: &[String],
+    returns: &[Vec<f64>],
+    target_return: Option<f64>,
+) -> Result<OptimizedPortfolio, AppError> {
+    let n = stock_codes.len();
+    if (n as f64) * MAX_POSITION_WEIGHT < 1.0 - WEIGHT_TOLERANCE {
+        return Err(AppError::InvalidInput(format!(
+            "单只股票权重上限 {:.0}%，组合至少需要 {} 只股票",
+            MAX_POSITION_WEIGHT * 100.0,
+            (1.0 / MAX_POSITION_WEIGHT).ceil()
+        )));
+    }
+
+    let mu = mean_returns(returns);
+    let cov = covariance_matrix(returns);
+    let singular =
+        || AppError::InvalidInput("协方差矩阵奇异，无法求解最小方差组合".to_string());
+
+    let min_variance = min_variance_weights(&cov, &mu, None).ok_or_else(singular)?;
+    let min_return = portfolio_stats(&min_variance, &cov, &mu).0 / TRADING_DAYS_PER_YEAR;
+    let max_return = dot(&greedy_fill_weights(&mu, true), &mu);
+
+    let weights = match target_return {
+        None => min_variance,
+        Some(target) => {
+            let daily_target = target / TRADING_DAYS_PER_YEAR;
+            if feasible_start(&mu, Some(daily_target)).is_none() {
+                let lowest_return = dot(&greedy_fill_weights(&mu, false), &mu);
+                return Err(AppError::InvalidInput(format!(
+                    "目标年化收益 {:.2}% 不可达，可行范围约 {:.2}% ~ {:.2}%",
+                    target * 100.0,
+                    lowest_return * TRADING_DAYS_PER_YEAR * 100.0,
+                    max_return * TRADING_DAYS_PER_YEAR * 100.0
+                )));
+            }
+            min_variance_weights(&cov, &mu, Some(daily_target)).ok_or_else(singular)?
+        }
+    };
+
+    let efficient_frontier_points = (0..FRONTIER_POINTS)
+        .filter_map(|k| {
+            let target = min_return + (max_return - min_return) * k as f64 / (FRONTIER_POINTS - 1) as f64;
+            let frontier_weights = min_variance_weights(&cov, &mu, Some(target))?;
+            let (ret, vol) = portfolio_stats(&frontier_weights, &cov, &mu);
+            Some((vol, ret))
+        })
+        .collect();
+
+    let (expected_return, expected_volatility) = portfolio_stats(&weights, &cov, &mu);
+    Ok(OptimizedPortfolio {
+        weights: stock_codes.iter().cloned().zip(weights).collect(),
+        expected_return,
+        expected_volatility,
+        sharpe_ratio: if expected_volatility > 1e-12 {
+            (expected_return - RISK_FREE_RATE) / expected_volatility
+        } else {
+            0.0
+        },
+        efficient_frontier_points,
+    })
+}
+
+/// 取各股票最近 `DEFAULT_PORTFOLIO_LOOKBACK_DAYS` 日行情，按共同交易日对齐后优化组合权重
+pub async fn optimize_portfolio(
+    stock_codes: Vec<String>,
+    target_return: Option<f64>,
+    pool: &DbPool,
+) -> Result<OptimizedPortfolio, AppError> {
+    let mut seen = HashSet::new();
+    let stock_codes: Vec<String> = stock_codes
+        .into_iter()
+        .map(|code| code.trim().to_string())
+        .filter(|code| !code.is_empty() && seen.insert(code.clone()))
+        .collect();
+
+    let mut series: Vec<HashMap<NaiveDate, f64>> = Vec::with_capacity(stock_codes.len());
+    for code in &stock_codes {
+        let history =
+            repository::get_recent_historical_data(code, DEFAULT_PORTFOLIO_LOOKBACK_DAYS + 1, pool).await?;
+        series.push(history.into_iter().map(|bar| (bar.date, bar.close)).collect());
+    }
+    let mut dates: Vec<NaiveDate> = series
+        .first()
+        .map(|first| {
+            first
+                .keys()
+                .filter(|date| series.iter().all(|closes| closes.contains_key(*date)))
+                .copied()
+                .collect()
+        })
+        .unwrap_or_default();
+    dates.sort();
+    if dates.len() <= MIN_PORTFOLIO_SAMPLES {
+        return Err(AppError::InvalidInput(format!(
+            "共同交易日仅 {} 天，组合优化至少需要 {} 天对齐行情",
+            dates.len(),
+            MIN_PORTFOLIO_SAMPLES + 1
+        )));
+    }
+
+    let returns: Vec<Vec<f64>> = series
+        .iter()
+        .map(|closes| {
+            dates
+                .windows(2)
+                .map(|w| {
+                    let (prev, curr) = (closes[&w[0]], closes[&w[1]]);
+                    if prev > 0.0 { curr / prev - 1.0 } else { 0.0 }
+                })
+                .collect()
+        })
+        .collect();
+    optimize_weights(&stock_codes, &returns, target_return)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 确定性伪随机序列（LCG），映射到 [-0.5, 0.5)
+    fn noise(seed: u64, len: usize) -> Vec<f64> {
+        let mut state = seed;
+        (0..len)
+            .map(|_| {
+                state = state
+                    .wrapping_mul(6364136223846793005)
+                    .wrapping_add(1442695040888963407);
+                (state >> 11) as f64 / (1u64 << 53) as f64 - 0.5
+            })
+            .collect()
+    }
+
+    fn assets() -> (Vec<String>, Vec<Vec<f64>>) {
+        let codes: Vec<String> = ["600000", "600036", "000001", "000002", "601318"]
+            .iter()
+            .map(|c| c.to_string())
+            .collect();
+        let returns = (0..5)
+            .map(|i| {
+                let scale = 0.01 * (i + 1) as f64;
+                let drift = 0.0002 * (i + 1) as f64;
+                noise(i as u64 + 11, 250).iter().map(|e| drift + scale * e).collect()
+            })
+            .collect();
+        (codes, returns)
+    }
+
+    #[test]
+    fn test_correlation_matrix() {
+        let a = vec![1.0, 2.0, 3.0, 4.0];
+        let b = vec![2.0, 4.0, 6.0, 8.0];
+        let c = vec![4.0, 3.0, 2.0, 1.0];
+        let matrix = calculate_correlation_matrix(&[a, b, c]);
+        assert!((matrix[0][1] - 1.0).abs() < 1e-12);
+        assert!((matrix[0][2] + 1.0).abs() < 1e-12);
+        assert_eq!(matrix[2][2], 1.0);
+    }
+
+    #[test]
+    fn test_optimize_weights_respects_bounds() {
+        let (codes, returns) = assets();
+        let portfolio = optimize_weights(&codes, &returns, None).unwrap();
+        let total: f64 = portfolio.weights.values().sum();
+        assert!((total - 1.0).abs() < 1e-9);
+        assert!(portfolio
+            .weights
+            .values()
+            .all(|&w| (0.0..=MAX_POSITION_WEIGHT + 1e-9).contains(&w)));
+        // 最低波动的股票配满上限
+        assert!((portfolio.weights["600000"] - MAX_POSITION_WEIGHT).abs() < 1e-9);
+        assert!(!portfolio.efficient_frontier_points.is_empty());
+
+        // 最小方差组合的波动率不高于等权组合
+        let mu = mean_returns(&returns);
+        let cov = covariance_matrix(&returns);
+        let (_, equal_vol) = portfolio_stats(&[0.2; 5], &cov, &mu);
+        assert!(portfolio.expected_volatility <= equal_vol);
+
+        // 指定目标收益时组合收益等于目标，且波动率不低于最小方差组合
+        let max_return = dot(&greedy_fill_weights(&mu, true), &mu);
+        let target = (portfolio.expected_return + max_return * TRADING_DAYS_PER_YEAR) / 2.0;
+        let targeted = optimize_weights(&codes, &returns, Some(target)).unwrap();
+        assert!((targeted.expected_return - target).abs() < 1e-6);
+        assert!(targeted.expected_volatility >= portfolio.expected_volatility - 1e-12);
+        assert!(optimize_weights(&codes, &returns, Some(10.0)).is_err());
+        assert!(optimize_weights(&codes[..3], &returns[..3], None).is_err());
+    }
+
+    #[test]
+    fn test_min_variance_weights_releases_pinned_weights() {
+        // 只固定不放开的做法在此会误判目标收益不可达
+        let variances = [1.0, 1.0, 9.0, 9.0, 1.0];
+        let cov: Vec<Vec<f64>> = (0..5)
+            .map(|i| (0..5).map(|j| if i == j { variances[i] } else { 0.0 }).collect())
+            .collect();
+        let mu = [3.0, 2.0, 0.0, 0.0, 0.0];
+        let weights = min_variance_weights(&cov, &mu, Some(0.5)).unwrap();
+        let expected = [0.0, 0.25, 0.225, 0.225, 0.3];
+        assert!(weights.iter().zip(expected).all(|(w, e)| (w - e).abs() < 1e-9));
+        assert!(min_variance_weights(&cov, &mu, Some(1.5)).is_some());
+        assert!(min_variance_weights(&cov, &mu, Some(1.6)).is_none());
+    }
+}
@@ -2,10 +2,58 @@
 
 use crate::prediction::{
     types::*,
-    model::{training, inference, management},
+    model::{training, inference, management, drift},
     strategy::multi_timeframe,
 };
-use crate::db::{connection::create_temp_pool, repository::get_recent_historical_data};
+use crate::db::{
+    connection::create_temp_pool,
+    repository::{get_active_indicator_config, get_recent_historical_data},
+};
+use crate::prediction::analysis::stock_signals::{
+    compute_technical_signals, StockSignals, STOCK_SIGNAL_BARS,
+};
+use crate::prediction::backtest::rolling::{run_rolling_windows, summarize_rolling_windows};
+use crate::prediction::backtest::signal_accuracy::{
+    evaluate_signal_history, SignalAccuracyReport, SignalType, MAX_SIGNAL_HORIZON,
+    SIGNAL_WARMUP_BARS,
+};
+use crate::prediction::model::features::{build_samples, latest_features};
+use crate::prediction::model::quantile::{train_quantile_bands, QuantileTrainingReport};
+use crate::config::retrain::load_auto_retrain_config;
+use crate::db::models::{NewPredictionRecord, PredictionHistoryRecord};
+use crate::db::repository::{self, insert_model_history};
+use crate::error::AppError;
+use crate::services::historical::refresh_stock_full;
+use chrono::{Duration as ChronoDuration, Local, NaiveDate};
+use log::{debug, error, info, warn};
+use serde::Serialize;
+use sqlx::SqlitePool;
+use std::collections::HashSet;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+
+/// 滚动窗口检验使用的历史K线数
+const ROLLING_WINDOW_HISTORY_BARS: usize = 1500;
+
+/// 分位数回归训练使用的历史K线数与留出比例
+const QUANTILE_HISTORY_BARS: usize = 750;
+const QUANTILE_TEST_RATIO: f64 = 0.2;
+
+/// 模型重训练完成事件
+pub const MODEL_RETRAINED_EVENT: &str = "model_retrained";
+
+/// 自动重训练检查间隔：启动时检查一次，此后每周一次
+pub const AUTO_RETRAIN_CHECK_INTERVAL: Duration = Duration::from_secs(7 * 24 * 3600);
+
+/// 漂移检测使用的训练后近期样本数
+pub const DRIFT_LOOKBACK_DAYS: usize = 60;
+
+/// 滚动准确率窗口（已回填的预测条数）
+pub const ACCURACY_ROLLING_WINDOW: usize = 20;
+
+/// 旧元数据未记录训练超参数时，自动重训练使用的默认值
+const DEFAULT_RETRAIN_EPOCHS: usize = 100;
+const DEFAULT_RETRAIN_LEARNING_RATE: f64 = 0.001;
 
 /// 训练模型
 pub async fn train_model(request: TrainingRequest) -> Result<TrainingResult, String> {
@@ -18,10 +66,112 @@ pub async fn retrain_model(
     epochs: u32,
     batch_size: u32,
     learning_rate: f64,
-) -> Result<(), String> {
+) -> Result<ModelInfo, String> {
     training::retrain_model(model_id, epochs, batch_size, learning_rate).await
 }
 
+/// 模型重训练完成事件负载
+#[derive(Debug, Clone, Serialize)]
+pub struct ModelRetrainedEvent {
+    pub model_id: String,
+    pub stock_code: String,
+    pub new_accuracy: f64,
+    pub old_accuracy: f64,
+}
+
+/// 重训练模型并写入 `model_history`；`source` 为 manual / auto / drift，记录失败仅打印
+pub async fn retrain_and_record(
+    model_id: &str,
+    epochs: u32,
+    learning_rate: f64,
+    source: &str,
+    pool: &SqlitePool,
+) -> Result<ModelRetrainedEvent, String> {
+    let old_accuracy = management::load_model_metadata(model_id)?.accuracy;
+    let updated = training::retrain_model(model_id.to_string(), epochs, 0, learning_rate).await?;
+    if let Err(e) = insert_model_history(pool, &updated, source, Some(old_accuracy)).await {
+        warn!("写入模型训练记录失败 {model_id}: {e}");
+    }
+    Ok(ModelRetrainedEvent {
+        model_id: updated.id,
+        stock_code: updated.stock_code,
+        new_accuracy: updated.accuracy,
+        old_accuracy,
+    })
+}
+
+/// 自动重训练调度器：生产模型距上次训练超过 `auto_retrain_days` 天或检测到漂移时，
+/// 沿用原训练超参数重训练并发出 [`MODEL_RETRAINED_EVENT`]
+pub struct RetrainScheduler {
+    app: AppHandle,
+    pool: SqlitePool,
+}
+
+impl RetrainScheduler {
+    pub fn new(app: AppHandle, pool: SqlitePool) -> Self {
+        Self { app, pool }
+    }
+
+    /// 启动后台调度：立即检查一次，此后按 [`AUTO_RETRAIN_CHECK_INTERVAL`] 检查
+    pub fn spawn(self) {
+        tauri::async_runtime::spawn(async move {
+            let mut interval = tokio::time::interval(AUTO_RETRAIN_CHECK_INTERVAL);
+            loop {
+                interval.tick().await;
+                self.retrain_due_models().await;
+            }
+        });
+    }
+
+    /// 未到期的模型是否检测到漂移；无法检测（旧模型缺少训练窗口、训练后样本不足等）时视为未漂移
+    async fn is_drifted(&self, model: &ModelInfo) -> bool {
+        match drift::detect_model_drift(&model.id, &self.pool, DRIFT_LOOKBACK_DAYS).await {
+            Ok(report) => {
+                if report.drift_detected {
+                    info!("模型 {} 检测到漂移: {}", model.id, report.recommendation);
+                }
+                report.drift_detected
+            }
+            Err(e) => {
+                debug!("模型 {} 跳过漂移检测: {e}", model.id);
+                false
+            }
+        }
+    }
+
+    /// 重训练全部到期或漂移的生产模型（逐个执行，避免同时占满 CPU），返回成功数量
+    pub async fn retrain_due_models(&self) -> usize {
+        let config = load_auto_retrain_config();
+        if !config.enabled {
+            return 0;
+        }
+        let now = management::get_current_timestamp();
+        let mut retrained = 0;
+        for model in management::list_production_models() {
+            let source = if management::is_retrain_due(&model, config.auto_retrain_days, now) {
+                "auto"
+            } else if self.is_drifted(&model).await {
+                "drift"
+            } else {
+                continue;
+            };
+            let (epochs, learning_rate) = model
+                .training_params
+                .map(|params| (params.epochs, params.learning_rate))
+                .unwrap_or((DEFAULT_RETRAIN_EPOCHS, DEFAULT_RETRAIN_LEARNING_RATE));
+            info!("⏰ 自动重训练模型 {}（{}）", model.id, model.stock_code);
+            match retrain_and_record(&model.id, epochs as u32, learning_rate, source, &self.pool).await {
+                Ok(event) => {
+                    retrained += 1;
+                    let _ = self.app.emit(MODEL_RETRAINED_EVENT, event);
+                }
+                Err(e) => error!("自动重训练模型 {} 失败: {e}", model.id),
+            }
+        }
+        retrained
+    }
+}
+
 /// 进行预测
 pub async fn predict(request: PredictionRequest) -> Result<PredictionResponse, String> {
     inference::predict(request).await
@@ -88,3 +238,360 @@ pub async fn get_latest_multi_timeframe_signal(symbol: String) -> Result<Option<
     
     Ok(signal)
 }
+
+/// 滚动窗口稳定性检验：逐窗口重训线性回归并评估紧随其后的 `step_size` 个样本
+pub async fn run_rolling_window_analysis(
+    stock_code: &str,
+    window_size: usize,
+    step_size: usize,
+    prediction_days: usize,
+) -> Result<RollingWindowReport, String> {
+    if window_size < 30 {
+        return Err("滚动窗口至少需要 30 个样本".to_string());
+    }
+    let prediction_days = prediction_days.max(1);
+    let pool = create_temp_pool().await?;
+    let historical = get_recent_historical_data(stock_code, ROLLING_WINDOW_HISTORY_BARS, &pool)
+        .await
+        .map_err(|e| format!("获取历史数据失败: {e}"))?;
+
+    let samples = build_samples(&historical, prediction_days);
+    if samples.len() < window_size + prediction_days + step_size.max(1) {
+        return Err(format!(
+            "有效样本不足（{}），无法完成一个 {window_size} 窗口的滚动检验",
+            samples.len()
+        ));
+    }
+
+    let windows = run_rolling_windows(&samples, window_size, step_size, prediction_days);
+    Ok(summarize_rolling_windows(
+        stock_code,
+        window_size,
+        step_size.max(1),
+        prediction_days,
+        windows,
+    ))
+}
+
+/// 技术信号历史胜率：最近 `lookback_days` 个交易日内该信号每次出现后 T+1/3/5 的表现
+pub async fn evaluate_signal_accuracy(
+    stock_code: &str,
+    signal_type: SignalType,
+    lookback_days: usize,
+    pool: &SqlitePool,
+) -> Result<SignalAccuracyReport, String> {
+    if lookback_days <= MAX_SIGNAL_HORIZON {
+        return Err(format!("回看天数需大于 {MAX_SIGNAL_HORIZON}"));
+    }
+    let historical = get_recent_historical_data(stock_code, lookback_days + SIGNAL_WARMUP_BARS, pool)
+        .await
+        .map_err(|e| format!("获取历史数据失败: {e}"))?;
+    if historical.len() <= SIGNAL_WARMUP_BARS + MAX_SIGNAL_HORIZON {
+        return Err(format!("历史数据不足（{}），无法检验信号", historical.len()));
+    }
+    Ok(evaluate_signal_history(&historical, signal_type, lookback_days))
+}
+
+/// 训练 q0.1 / q0.5 / q0.9 分位数回归并在时间序列留出段上检验区间覆盖率
+pub async fn train_quantile_model(
+    stock_code: &str,
+    prediction_days: usize,
+    pool: &SqlitePool,
+) -> Result<QuantileTrainingReport, String> {
+    let horizon = prediction_days.max(1);
+    let historical = get_recent_historical_data(stock_code, QUANTILE_HISTORY_BARS, pool)
+        .await
+        .map_err(|e| format!("获取历史数据失败: {e}"))?;
+    let samples = build_samples(&historical, horizon);
+    let latest = latest_features(&historical);
+    train_quantile_bands(stock_code, &samples, latest.as_deref(), horizon, QUANTILE_TEST_RATIO)
+        .ok_or_else(|| format!("样本不足（{}），无法训练分位数模型", samples.len()))
+}
+
+/// 计算股票最新K线的技术指标与背离（指标参数取当前预设），预测管线与预警监控共用
+pub async fn compute_stock_signals(
+    stock_code: &str,
+    pool: &SqlitePool,
+) -> Result<StockSignals, String> {
+    let historical = get_recent_historical_data(stock_code, STOCK_SIGNAL_BARS, pool)
+        .await
+        .map_err(|e| format!("获取历史数据失败: {e}"))?;
+    let Some(latest) = historical.last() else {
+        return Err(format!("{stock_code} 无历史数据"));
+    };
+    let prices: Vec<f64> = historical.iter().map(|h| h.close).collect();
+    let highs: Vec<f64> = historical.iter().map(|h| h.high).collect();
+    let lows: Vec<f64> = historical.iter().map(|h| h.low).collect();
+    let volumes: Vec<i64> = historical.iter().map(|h| h.volume).collect();
+    let config = get_active_indicator_config(pool).await;
+
+    Ok(StockSignals {
+        stock_code: stock_code.to_string(),
+        date: latest.date,
+        close: latest.close,
+        signals: compute_technical_signals(&prices, &highs, &lows, &volumes, &config),
+    })
+}
+
+// =============================================================================
+// 预测准确率跟踪
+// =============================================================================
+
+/// 将预测响应展开为逐目标日的记录；缺少最新K线或日期无法解析的条目跳过
+pub fn prediction_records(
+    stock_code: &str,
+    model_id: &str,
+    response: &PredictionResponse,
+) -> Vec<NewPredictionRecord> {
+    let Some(last) = &response.last_real_data else {
+        return Vec::new();
+    };
+    let Ok(prediction_date) = NaiveDate::parse_from_str(&last.date, "%Y-%m-%d") else {
+        return Vec::new();
+    };
+    response
+        .predictions
+        .iter()
+        .filter_map(|p| {
+            let target_date = NaiveDate::parse_from_str(&p.target_date, "%Y-%m-%d").ok()?;
+            Some(NewPredictionRecord {
+                stock_code: stock_code.to_string(),
+                model_id: model_id.to_string(),
+                prediction_date,
+                target_date,
+                base_price: last.price,
+                predicted_price: p.predicted_price,
+                predicted_direction: Direction::from_change_percent(p.predicted_change_percent)
+                    .to_string(),
+            })
+        })
+        .collect()
+}
+
+/// 记录一次预测供事后核对准确率；写入失败仅打印，不影响预测结果
+pub async fn record_prediction_history(
+    pool: &SqlitePool,
+    stock_code: &str,
+    model_id: &str,
+    response: &PredictionResponse,
+) {
+    let records = prediction_records(stock_code, model_id, response);
+    if records.is_empty() {
+        return;
+    }
+    if let Err(e) = repository::insert_prediction_records(pool, &records).await {
+        warn!("写入预测记录失败 {stock_code}: {e}");
+    }
+}
+
+/// 由实际价格计算（实际方向, 方向是否正确, 价格误差%）
+fn evaluate_prediction(record: &PredictionHistoryRecord, actual_price: f64) -> (String, bool, f64) {
+    let actual_change = if record.base_price > 0.0 {
+        (actual_price / record.base_price - 1.0) * 100.0
+    } else {
+        0.0
+    };
+    let actual_direction = Direction::from_change_percent(actual_change).to_string();
+    let correct = actual_direction == record.predicted_direction;
+    let error_pct = if actual_price > 0.0 {
+        (record.predicted_price - actual_price) / actual_price * 100.0
+    } else {
+        0.0
+    };
+    (actual_direction, correct, error_pct)
+}
+
+/// 为目标日已过的预测回填目标日（遇停牌取其后首个交易日）收盘价。
+///
+/// 本地K线未覆盖目标日的股票先刷新一次；仍无数据的记录保持待回填。返回回填条数
+pub async fn reconcile_prediction_history(pool: &SqlitePool) -> Result<usize, AppError> {
+    let today = Local::now().date_naive();
+    let mut refreshed: HashSet<String> = HashSet::new();
+    let mut reconciled = 0;
+    for record in repository::get_pending_prediction_records(pool, today).await? {
+        let target = record.target_date.format("%Y-%m-%d").to_string();
+        let mut bars =
+            repository::get_historical_data(&record.stock_code, &target, "9999-12-31", pool).await?;
+        if bars.is_empty() && refreshed.insert(record.stock_code.clone()) {
+            if let Err(e) = refresh_stock_full(&record.stock_code, pool).await {
+                error!("预测回填刷新数据失败 {}: {e}", record.stock_code);
+            }
+            bars = repository::get_historical_data(&record.stock_code, &target, "9999-12-31", pool)
+                .await?;
+        }
+        let Some(bar) = bars.first() else {
+            continue;
+        };
+        let (actual_direction, correct, error_pct) = evaluate_prediction(&record, bar.close);
+        repository::set_prediction_actual(pool, record.id, bar.close, &actual_direction, correct, error_pct)
+            .await?;
+        reconciled += 1;
+    }
+    Ok(reconciled)
+}
+
+/// 截至某目标日的滚动准确率
+#[derive(Debug, Clone, Serialize)]
+pub struct RollingAccuracyPoint {
+    pub target_date: NaiveDate,
+    /// 窗口内已回填的预测数（不超过 [`ACCURACY_ROLLING_WINDOW`]）
+    pub samples: usize,
+    pub direction_accuracy: f64,
+    pub mean_abs_error_pct: f64,
+}
+
+/// 预测准确率历史
+#[derive(Debug, Clone, Serialize)]
+pub struct AccuracyHistory {
+    pub stock_code: String,
+    pub days: usize,
+    pub total_predictions: usize,
+    /// 已回填实际值的预测数
+    pub evaluated: usize,
+    pub pending: usize,
+    /// 整体方向准确率（无已回填预测时为空）
+    pub direction_accuracy: Option<f64>,
+    pub mean_abs_error_pct: Option<f64>,
+    /// 按目标日排列的滚动统计
+    pub rolling: Vec<RollingAccuracyPoint>,
+}
+
+fn accuracy_of(records: &[&PredictionHistoryRecord]) -> (f64, f64) {
+    let n = records.len() as f64;
+    let correct = records
+        .iter()
+        .filter(|r| r.was_direction_correct == Some(true))
+        .count() as f64;
+    let abs_error: f64 = records
+        .iter()
+        .filter_map(|r| r.price_error_pct)
+        .map(f64::abs)
+        .sum();
+    (correct / n, abs_error / n)
+}
+
+/// 汇总整体与滚动准确率；`records` 需按目标日正序
+pub fn summarize_prediction_accuracy(
+    stock_code: &str,
+    days: usize,
+    records: &[PredictionHistoryRecord],
+    window: usize,
+) -> AccuracyHistory {
+    let evaluated: Vec<&PredictionHistoryRecord> = records
+        .iter()
+        .filter(|r| r.was_direction_correct.is_some())
+        .collect();
+    let window = window.max(1);
+    let rolling = (0..evaluated.len())
+        .map(|i| {
+            let slice = &evaluated[(i + 1).saturating_sub(window)..=i];
+            let (direction_accuracy, mean_abs_error_pct) = accuracy_of(slice);
+            RollingAccuracyPoint {
+                target_date: evaluated[i].target_date,
+                samples: slice.len(),
+                direction_accuracy,
+                mean_abs_error_pct,
+            }
+        })
+        .collect();
+    let overall = (!evaluated.is_empty()).then(|| accuracy_of(&evaluated));
+
+    AccuracyHistory {
+        stock_code: stock_code.to_string(),
+        days,
+        total_predictions: records.len(),
+        evaluated: evaluated.len(),
+        pending: records.len() - evaluated.len(),
+        direction_accuracy: overall.map(|(accuracy, _)| accuracy),
+        mean_abs_error_pct: overall.map(|(_, error)| error),
+        rolling,
+    }
+}
+
+/// 最近 `days` 天（按目标日）的预测准确率历史
+pub async fn get_prediction_accuracy_history(
+    stock_code: &str,
+    days: usize,
+    pool: &SqlitePool,
+) -> Result<AccuracyHistory, AppError> {
+    let since = Local::now().date_naive() - ChronoDuration::days(days as i64);
+    let records = repository::get_prediction_records_since(pool, stock_code, since).await?;
+    Ok(summarize_prediction_accuracy(
+        stock_code,
+        days,
+        &records,
+        ACCURACY_ROLLING_WINDOW,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(day: u32, base: f64, predicted: f64, actual: Option<f64>) -> PredictionHistoryRecord {
+        let mut record = PredictionHistoryRecord {
+            id: day as i64,
+            stock_code: "600519".to_string(),
+            model_id: "rule".to_string(),
+            prediction_date: NaiveDate::from_ymd_opt(2025, 6, day).unwrap(),
+            target_date: NaiveDate::from_ymd_opt(2025, 6, day + 1).unwrap(),
+            base_price: base,
+            predicted_price: predicted,
+            predicted_direction: Direction::from_change_percent((predicted / base - 1.0) * 100.0)
+                .to_string(),
+            actual_price: None,
+            actual_direction: None,
+            was_direction_correct: None,
+            price_error_pct: None,
+        };
+        if let Some(actual) = actual {
+            let (direction, correct, error) = evaluate_prediction(&record, actual);
+            record.actual_price = Some(actual);
+            record.actual_direction = Some(direction);
+            record.was_direction_correct = Some(correct);
+            record.price_error_pct = Some(error);
+        }
+        record
+    }
+
+    #[test]
+    fn test_evaluate_prediction() {
+        // 预测上涨 2%，实际上涨 1%：方向正确，预测价高估约 0.99%
+        let (direction, correct, error) = evaluate_prediction(&record(2, 100.0, 102.0, None), 101.0);
+        assert_eq!(direction, "上涨");
+        assert!(correct);
+        assert!((error - 100.0 / 101.0).abs() < 1e-9);
+
+        // 实际横盘（±0.5% 以内）视为方向错误
+        let (direction, correct, _) = evaluate_prediction(&record(2, 100.0, 102.0, None), 100.2);
+        assert_eq!(direction, "横盘");
+        assert!(!correct);
+    }
+
+    #[test]
+    fn test_summarize_prediction_accuracy() {
+        let records = vec![
+            record(2, 100.0, 102.0, Some(101.0)),
+            record(3, 100.0, 98.0, Some(102.0)),
+            record(4, 100.0, 102.0, Some(104.0)),
+            record(5, 100.0, 102.0, None),
+        ];
+        let history = summarize_prediction_accuracy("600519", 30, &records, 2);
+
+        assert_eq!(history.total_predictions, 4);
+        assert_eq!(history.evaluated, 3);
+        assert_eq!(history.pending, 1);
+        assert!((history.direction_accuracy.unwrap() - 2.0 / 3.0).abs() < 1e-9);
+
+        let rolling: Vec<(usize, f64)> = history
+            .rolling
+            .iter()
+            .map(|p| (p.samples, p.direction_accuracy))
+            .collect();
+        assert_eq!(rolling, vec![(1, 1.0), (2, 0.5), (2, 0.5)]);
+
+        let empty = summarize_prediction_accuracy("600519", 30, &[], 2);
+        assert_eq!(empty.direction_accuracy, None);
+        assert!(empty.rolling.is_empty());
+    }
+}
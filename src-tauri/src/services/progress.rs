@@ -0,0 +1,176 @@
+//! 长任务进度推送与取消
+//!
+//! 命令开始时以前端给定的 `operation_id` 创建 [`ProgressReporter`]：取消令牌登记到
+//! 托管状态 [`OperationRegistry`]，`cancel_operation` 命令据此取消；上报器释放时自动注销。
+//! 进度以 `operation_progress` 事件推送，百分比未变化时不重复推送。
+
+use crate::utils::progress::ProgressSink;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::Mutex;
+use std::time::Instant;
+use tauri::{AppHandle, Emitter, Manager};
+use tokio_util::sync::CancellationToken;
+
+/// 进度事件名
+pub const OPERATION_PROGRESS_EVENT: &str = "operation_progress";
+
+/// `operation_progress` 事件负载
+#[derive(Debug, Clone, Serialize)]
+pub struct OperationProgressEvent {
+    pub id: String,
+    /// 0-100
+    pub percent: f64,
+    pub message: String,
+    /// 按已用时间线性外推的剩余秒数（尚无进度时为空）
+    pub eta_seconds: Option<f64>,
+}
+
+/// 进行中的可取消操作（Tauri 托管状态）
+#[derive(Default)]
+pub struct OperationRegistry {
+    operations: Mutex<HashMap<String, CancellationToken>>,
+}
+
+impl OperationRegistry {
+    /// 登记操作；同 id 的操作仍在进行时返回 None
+    pub fn register(&self, operation_id: &str) -> Option<CancellationToken> {
+        let mut operations = self.operations.lock().unwrap_or_else(|e| e.into_inner());
+        if operations.contains_key(operation_id) {
+            return None;
+        }
+        let token = CancellationToken::new();
+        operations.insert(operation_id.to_string(), token.clone());
+        Some(token)
+    }
+
+    /// 取消操作；操作不存在（已结束或 id 错误）时返回 false
+    pub fn cancel(&self, operation_id: &str) -> bool {
+        let operations = self.operations.lock().unwrap_or_else(|e| e.into_inner());
+        match operations.get(operation_id) {
+            Some(token) => {
+                token.cancel();
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn finish(&self, operation_id: &str) {
+        let mut operations = self.operations.lock().unwrap_or_else(|e| e.into_inner());
+        operations.remove(operation_id);
+    }
+}
+
+/// 前端未提供 operation_id 时生成的默认 id（仍会推送进度，但无法取消）；
+/// 以 UUID 区分，同一毫秒内启动的同类操作也不会冲突
+pub fn default_operation_id(kind: &str) -> String {
+    format!("{kind}-{}", uuid::Uuid::new_v4())
+}
+
+/// 线性外推剩余时间
+fn estimate_eta(elapsed_secs: f64, completed: usize, total: usize) -> Option<f64> {
+    if completed == 0 || total == 0 {
+        return None;
+    }
+    let remaining = total.saturating_sub(completed) as f64;
+    Some(elapsed_secs / completed as f64 * remaining)
+}
+
+/// 向前端推送进度并响应取消的上报器
+pub struct ProgressReporter {
+    app: AppHandle,
+    operation_id: String,
+    token: CancellationToken,
+    started: Instant,
+    /// 上次推送的整数百分比（-1 表示尚未推送）
+    last_percent: AtomicI64,
+}
+
+impl ProgressReporter {
+    /// 登记操作并创建上报器；同 id 操作仍在进行时返回错误
+    pub fn start(app: &AppHandle, operation_id: String) -> Result<Self, String> {
+        let token = app
+            .state::<OperationRegistry>()
+            .register(&operation_id)
+            .ok_or_else(|| format!("操作 {operation_id} 正在进行中"))?;
+        Ok(Self {
+            app: app.clone(),
+            operation_id,
+            token,
+            started: Instant::now(),
+            last_percent: AtomicI64::new(-1),
+        })
+    }
+
+    pub fn operation_id(&self) -> &str {
+        &self.operation_id
+    }
+}
+
+impl ProgressSink for ProgressReporter {
+    fn report(&self, completed: usize, total: usize, message: &str) {
+        let percent = if total == 0 {
+            100.0
+        } else {
+            (completed as f64 / total as f64 * 100.0).min(100.0)
+        };
+        if self.last_percent.swap(percent as i64, Ordering::Relaxed) == percent as i64 {
+            return;
+        }
+        let event = OperationProgressEvent {
+            id: self.operation_id.clone(),
+            percent,
+            message: message.to_string(),
+            eta_seconds: estimate_eta(self.started.elapsed().as_secs_f64(), completed, total),
+        };
+        // 推送失败（窗口已关闭等）不影响任务本身
+        let _ = self.app.emit(OPERATION_PROGRESS_EVENT, event);
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.token.is_cancelled()
+    }
+}
+
+impl Drop for ProgressReporter {
+    fn drop(&mut self) {
+        self.app
+            .state::<OperationRegistry>()
+            .finish(&self.operation_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registry_cancel_and_finish() {
+        let registry = OperationRegistry::default();
+        let token = registry.register("train-1").expect("首次登记应成功");
+        assert!(registry.register("train-1").is_none());
+
+        assert!(registry.cancel("train-1"));
+        assert!(token.is_cancelled());
+
+        registry.finish("train-1");
+        assert!(!registry.cancel("train-1"));
+        assert!(registry.register("train-1").is_some());
+    }
+
+    #[test]
+    fn test_estimate_eta() {
+        assert_eq!(estimate_eta(10.0, 0, 100), None);
+        assert_eq!(estimate_eta(10.0, 25, 100), Some(30.0));
+        assert_eq!(estimate_eta(10.0, 100, 100), Some(0.0));
+    }
+
+    #[test]
+    fn test_default_operation_ids_are_unique() {
+        let (a, b) = (default_operation_id("train"), default_operation_id("train"));
+        assert!(a.starts_with("train-"));
+        assert_ne!(a, b);
+    }
+}
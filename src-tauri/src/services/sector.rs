@@ -0,0 +1,316 @@
+//! 行业动量轮动服务
+//!
+//! 按股票基础信息中的行业分组，以成分股日收益等权合成行业指数，
+//! 比较行业近 20 日收益与全市场等权平均，给出进入/持有/退出的轮动信号。
+
+use crate::db::models::HistoricalData;
+use crate::db::repository;
+use crate::error::AppError;
+use crate::prediction::indicators::rsi::calculate_rsi;
+use crate::services::market_breadth::MARKET_BREADTH_MIN_BARS;
+use crate::utils::canonical_stock_symbol;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Mutex;
+
+/// 轮动判断使用的近期收益窗口（交易日）
+pub const SECTOR_RECENT_RETURN_DAYS: usize = 20;
+
+/// 默认动量回看期（交易日）
+pub const DEFAULT_SECTOR_LOOKBACK_DAYS: usize = 60;
+
+/// 默认持有期（交易日）
+pub const DEFAULT_SECTOR_FORWARD_DAYS: usize = 20;
+
+/// 行业成分股少于该数量时指数代表性不足，不参与轮动
+pub const SECTOR_MIN_STOCKS: usize = 3;
+
+/// 行业指数 RSI 达到该值视为过热，跑赢市场也不再提示进入
+const SECTOR_OVERBOUGHT_RSI: f64 = 70.0;
+
+/// 默认参数下的行业动量表，按本地数据最新交易日缓存
+static SECTOR_TABLE_CACHE: Mutex<Option<(NaiveDate, Vec<SectorMomentum>)>> = Mutex::new(None);
+
+/// 行业轮动信号
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RotationSignal {
+    /// 跑赢市场且未过热
+    Enter,
+    /// 跑赢市场但已过热，或与市场持平
+    Stay,
+    /// 跑输市场
+    Exit,
+}
+
+/// 单个行业的动量统计
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SectorMomentum {
+    pub sector: String,
+    pub stock_count: usize,
+    /// 回看期几何日均收益外推到持有期的预期收益（%），用于排序
+    pub momentum_score: f64,
+    /// 近 20 日行业指数收益（%）
+    pub recent_return: f64,
+    /// 近 20 日收益减全市场等权平均收益（百分点）
+    pub relative_strength_vs_market: f64,
+    /// 行业指数 RSI(14)
+    pub rsi: f64,
+    pub rotation_signal: RotationSignal,
+}
+
+/// 由各股时间正序日线合成等权指数（首日为 1.0）。
+///
+/// 每日收益取当日有数据且前一根K线有效的成分股收益均值，停牌股当日不计入。
+fn equal_weight_index(members: &[&[HistoricalData]]) -> Vec<f64> {
+    let mut daily: BTreeMap<NaiveDate, (f64, usize)> = BTreeMap::new();
+    for history in members {
+        for pair in history.windows(2) {
+            if pair[0].close <= 0.0 {
+                continue;
+            }
+            let entry = daily.entry(pair[1].date).or_insert((0.0, 0));
+            entry.0 += pair[1].close / pair[0].close - 1.0;
+            entry.1 += 1;
+        }
+    }
+
+    let mut level = 1.0;
+    let mut index = vec![level];
+    for (sum, count) in daily.into_values() {
+        level *= 1.0 + sum / count as f64;
+        index.push(level);
+    }
+    index
+}
+
+/// 指数最近 `days` 日收益（%）；长度不足时取全区间
+fn trailing_return(index: &[f64], days: usize) -> f64 {
+    let Some(&last) = index.last() else {
+        return 0.0;
+    };
+    let start = index[index.len().saturating_sub(days + 1)];
+    if start > 0.0 {
+        (last / start - 1.0) * 100.0
+    } else {
+        0.0
+    }
+}
+
+/// 按行业计算动量与轮动信号，按动量得分降序返回。
+///
+/// `stocks` 为各股最近的时间正序日线，`industries` 以规范化代码为键；
+/// 无行业或成分股不足 [`SECTOR_MIN_STOCKS`] 的行业不输出。
+pub fn compute_sector_momentum(
+    stocks: &[(String, Vec<HistoricalData>)],
+    industries: &HashMap<String, String>,
+    lookback_days: usize,
+    forward_days: usize,
+) -> Vec<SectorMomentum> {
+    let mut sectors: BTreeMap<&str, Vec<&[HistoricalData]>> = BTreeMap::new();
+    let mut market: Vec<&[HistoricalData]> = Vec::new();
+    for (symbol, history) in stocks {
+        let Some(sector) = industries.get(&canonical_stock_symbol(symbol)) else {
+            continue;
+        };
+        let recent = &history[history.len().saturating_sub(lookback_days + 1)..];
+        sectors.entry(sector.as_str()).or_default().push(recent);
+        market.push(recent);
+    }
+
+    let market_return = trailing_return(&equal_weight_index(&market), SECTOR_RECENT_RETURN_DAYS);
+    let mut result: Vec<SectorMomentum> = sectors
+        .into_iter()
+        .filter(|(_, members)| members.len() >= SECTOR_MIN_STOCKS)
+        .map(|(sector, members)| {
+            let index = equal_weight_index(&members);
+            let recent_return = trailing_return(&index, SECTOR_RECENT_RETURN_DAYS);
+            let relative_strength = recent_return - market_return;
+            let rsi = calculate_rsi(&index);
+            let days = (index.len() - 1).max(1) as f64;
+            let growth = index.last().copied().unwrap_or(1.0) / index[0];
+            let daily_return = (growth.powf(1.0 / days) - 1.0) * 100.0;
+            let rotation_signal = if relative_strength > 0.0 && rsi < SECTOR_OVERBOUGHT_RSI {
+                RotationSignal::Enter
+            } else if relative_strength < 0.0 {
+                RotationSignal::Exit
+            } else {
+                RotationSignal::Stay
+            };
+            SectorMomentum {
+                sector: sector.to_string(),
+                stock_count: members.len(),
+                momentum_score: daily_return * forward_days as f64,
+                recent_return,
+                relative_strength_vs_market: relative_strength,
+                rsi,
+                rotation_signal,
+            }
+        })
+        .collect();
+    result.sort_by(|a, b| b.momentum_score.total_cmp(&a.momentum_score));
+    result
+}
+
+/// 计算本地股票池的行业动量排名。
+///
+/// `lookback_days` 为动量回看期（不足 20 日时按 20 日取数），`forward_days` 为计划持有期，
+/// 动量得分为回看期几何日均收益乘以持有期天数。
+pub async fn calculate_sector_momentum(
+    pool: &SqlitePool,
+    lookback_days: usize,
+    forward_days: usize,
+) -> Result<Vec<SectorMomentum>, AppError> {
+    let lookback_days = lookback_days.max(SECTOR_RECENT_RETURN_DAYS);
+    let industries: HashMap<String, String> = repository::get_stock_industries(pool)
+        .await?
+        .into_iter()
+        .filter(|(_, industry)| !industry.trim().is_empty())
+        .map(|(symbol, industry)| (canonical_stock_symbol(&symbol), industry.trim().to_string()))
+        .collect();
+    if industries.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let symbols: Vec<String> = repository::get_symbols_with_min_bars(MARKET_BREADTH_MIN_BARS, pool)
+        .await?
+        .into_iter()
+        .filter(|symbol| industries.contains_key(&canonical_stock_symbol(symbol)))
+        .collect();
+    let stocks =
+        repository::get_recent_historical_data_for_symbols(&symbols, lookback_days + 1, pool).await?;
+    Ok(compute_sector_momentum(&stocks, &industries, lookback_days, forward_days))
+}
+
+fn cached_sector_table(trading_day: NaiveDate) -> Option<Vec<SectorMomentum>> {
+    let cache = SECTOR_TABLE_CACHE.lock().ok()?;
+    cache
+        .as_ref()
+        .filter(|(day, _)| *day == trading_day)
+        .map(|(_, sectors)| sectors.clone())
+}
+
+/// 默认参数下的行业动量表；同一最新交易日内复用首次计算结果，新交易日数据入库后重新计算
+async fn default_sector_table(pool: &SqlitePool) -> Result<Vec<SectorMomentum>, AppError> {
+    let Some(trading_day) = repository::get_latest_historical_date(pool).await? else {
+        return Ok(Vec::new());
+    };
+    if let Some(sectors) = cached_sector_table(trading_day) {
+        return Ok(sectors);
+    }
+    let sectors =
+        calculate_sector_momentum(pool, DEFAULT_SECTOR_LOOKBACK_DAYS, DEFAULT_SECTOR_FORWARD_DAYS)
+            .await?;
+    if let Ok(mut cache) = SECTOR_TABLE_CACHE.lock() {
+        *cache = Some((trading_day, sectors.clone()));
+    }
+    Ok(sectors)
+}
+
+/// 个股所属行业相对全市场的近 20 日强度（百分点）；行业未知或样本不足时返回 None
+pub async fn get_stock_sector_strength(
+    stock_code: &str,
+    pool: &SqlitePool,
+) -> Result<Option<f64>, AppError> {
+    let Some(industry) = repository::get_stock_industry(stock_code, pool).await? else {
+        return Ok(None);
+    };
+    let sectors = default_sector_table(pool).await?;
+    Ok(sectors
+        .into_iter()
+        .find(|sector| sector.sector == industry.trim())
+        .map(|sector| sector.relative_strength_vs_market))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn history(symbol: &str, daily_change_pct: f64, days: usize) -> (String, Vec<HistoricalData>) {
+        let start = NaiveDate::from_ymd_opt(2026, 1, 1).unwrap();
+        let mut close = 10.0;
+        let bars = (0..days)
+            .map(|i| {
+                if i > 0 {
+                    close *= 1.0 + daily_change_pct / 100.0;
+                }
+                HistoricalData {
+                    symbol: symbol.to_string(),
+                    date: start + Duration::days(i as i64),
+                    open: close,
+                    close,
+                    high: close,
+                    low: close,
+                    volume: 1000,
+                    amount: close * 1000.0,
+                    amplitude: 0.0,
+                    turnover_rate: 0.0,
+                    volume_ratio: 1.0,
+                    change_percent: daily_change_pct,
+                    change: 0.0,
+                }
+            })
+            .collect();
+        (symbol.to_string(), bars)
+    }
+
+    #[test]
+    fn test_equal_weight_index_averages_daily_returns() {
+        let up = history("000001", 2.0, 3);
+        let down = history("000002", -2.0, 3);
+        let index = equal_weight_index(&[&up.1, &down.1]);
+        assert_eq!(index.len(), 3);
+        assert!(index.iter().all(|level| (level - 1.0).abs() < 1e-12));
+        assert!((trailing_return(&[1.0, 1.1, 1.21], 1) - 10.0).abs() < 1e-9);
+        assert!((trailing_return(&[1.0, 1.21], 20) - 21.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_compute_sector_momentum_signals() {
+        let mut stocks = Vec::new();
+        let mut industries = HashMap::new();
+        // 银行：稳步小涨且有回撤，跑赢市场且未过热
+        for (i, code) in ["600000", "600016", "600036"].iter().enumerate() {
+            let (symbol, mut bars) = history(code, 0.0, 61);
+            for (day, bar) in bars.iter_mut().enumerate() {
+                let wave = if day % 2 == 0 { 0.0 } else { -0.05 };
+                bar.close = 10.0 + day as f64 * 0.01 + wave + i as f64;
+            }
+            stocks.push((symbol, bars));
+            industries.insert(code.to_string(), "银行".to_string());
+        }
+        // 煤炭：单边下跌
+        for code in ["601088", "601225", "600188"] {
+            stocks.push(history(code, -1.0, 61));
+            industries.insert(code.to_string(), "煤炭".to_string());
+        }
+        // 半导体：单边上涨，RSI 过热
+        for code in ["688981", "603986", "688012"] {
+            stocks.push(history(code, 1.0, 61));
+            industries.insert(code.to_string(), "半导体".to_string());
+        }
+        // 成分股不足的行业与无行业股票不输出
+        stocks.push(history("000651", 0.0, 61));
+        industries.insert("000651".to_string(), "家电".to_string());
+        stocks.push(history("000002", 3.0, 61));
+
+        let sectors = compute_sector_momentum(&stocks, &industries, 60, 20);
+        let names: Vec<&str> = sectors.iter().map(|s| s.sector.as_str()).collect();
+        assert_eq!(names, vec!["半导体", "银行", "煤炭"]);
+
+        let semis = &sectors[0];
+        assert!(semis.rsi >= 70.0);
+        assert!(semis.relative_strength_vs_market > 0.0);
+        assert_eq!(semis.rotation_signal, RotationSignal::Stay);
+        // 日涨 1%：日均收益 1% × 持有期 20 日
+        assert!((semis.momentum_score - 20.0).abs() < 1e-9);
+        assert!((semis.recent_return - (1.01f64.powi(20) - 1.0) * 100.0).abs() < 1e-9);
+
+        assert_eq!(sectors[1].rotation_signal, RotationSignal::Enter);
+        assert!(sectors[1].rsi < 70.0);
+        assert_eq!(sectors[2].rotation_signal, RotationSignal::Exit);
+        assert!(sectors[2].relative_strength_vs_market < 0.0);
+    }
+}
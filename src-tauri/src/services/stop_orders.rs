@@ -0,0 +1,210 @@
+//! 止损止盈监控服务
+//!
+//! 交易时段内定期拉取有生效单据的股票实时行情（同时写入行情快照），
+//! 现价触及触发价时将单据置为已触发并推送 `stop_order_triggered` 事件；
+//! 模拟账户持有该股时按现价卖出全部可卖股份。
+
+use crate::api::stock;
+use crate::db::models::StopOrder;
+use crate::db::repository;
+use crate::error::AppError;
+use crate::services::paper_trading::{
+    PaperTradeExecutedEvent, PaperTradingAccount, PaperTradingState, Transaction,
+    PAPER_TRADE_EXECUTED_EVENT,
+};
+use crate::utils::canonical_stock_symbol;
+use crate::utils::date::is_trading_day;
+use chrono::{Local, NaiveDateTime, NaiveTime};
+use log::{error, info, warn};
+use serde::Serialize;
+use sqlx::SqlitePool;
+use std::collections::BTreeMap;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+
+/// 止损止盈触发事件名
+pub const STOP_ORDER_TRIGGERED_EVENT: &str = "stop_order_triggered";
+
+/// 行情检查间隔
+pub const STOP_ORDER_POLL_INTERVAL: Duration = Duration::from_secs(60);
+
+/// `stop_order_triggered` 事件负载
+#[derive(Debug, Clone, Serialize)]
+pub struct StopOrderTriggeredEvent {
+    pub order_id: i64,
+    pub stock_code: String,
+    pub trigger_price: f64,
+    pub current_price: f64,
+}
+
+/// 是否处于 A 股连续竞价时段（9:30-11:30、13:00-15:00）
+pub fn in_trading_session(now: NaiveDateTime) -> bool {
+    let time = now.time();
+    let between = |start: (u32, u32), end: (u32, u32)| {
+        let start = NaiveTime::from_hms_opt(start.0, start.1, 0).unwrap_or_default();
+        let end = NaiveTime::from_hms_opt(end.0, end.1, 0).unwrap_or_default();
+        (start..=end).contains(&time)
+    };
+    is_trading_day(now.date()) && (between((9, 30), (11, 30)) || between((13, 0), (15, 0)))
+}
+
+/// 模拟账户按现价卖出该股全部可卖股份；未持有或均为当日买入时返回 None
+pub fn execute_paper_stop(
+    account: &mut PaperTradingAccount,
+    stock_code: &str,
+    price: f64,
+    executed_at: NaiveDateTime,
+) -> Result<Option<Transaction>, AppError> {
+    let available = account
+        .positions
+        .get(&canonical_stock_symbol(stock_code))
+        .map_or(0, |position| position.available_shares(executed_at.date()));
+    if available == 0 {
+        return Ok(None);
+    }
+    account.sell(stock_code, available, price, executed_at).map(Some)
+}
+
+/// 检查全部生效单据，返回本次触发的数量。
+///
+/// 每只股票只拉取一次行情；拉取失败的股票跳过，下个周期重试。
+pub async fn check_stop_orders(app: &AppHandle, pool: &SqlitePool) -> Result<usize, AppError> {
+    let mut by_stock: BTreeMap<String, Vec<StopOrder>> = BTreeMap::new();
+    for order in repository::get_active_stop_orders(pool).await? {
+        by_stock.entry(order.stock_code.clone()).or_default().push(order);
+    }
+
+    let mut triggered = 0;
+    for (stock_code, orders) in by_stock {
+        let now = Local::now().naive_local();
+        let quote = match stock::fetch_stock_capital(&stock_code).await {
+            Ok(item) => item.to_realtime_quote(&stock_code, now),
+            Err(e) => {
+                warn!("止损止盈行情获取失败 {stock_code}: {e}");
+                continue;
+            }
+        };
+        let Some(quote) = quote else {
+            continue;
+        };
+        if let Err(e) = repository::upsert_realtime_quote(pool, &quote).await {
+            warn!("保存 {stock_code} 行情快照失败: {e}");
+        }
+
+        for order in orders
+            .iter()
+            .filter(|order| order.order_type.is_triggered(order.trigger_price, quote.price))
+        {
+            if !repository::mark_stop_order_triggered(pool, order.id).await? {
+                continue;
+            }
+            triggered += 1;
+            let event = StopOrderTriggeredEvent {
+                order_id: order.id,
+                stock_code: stock_code.clone(),
+                trigger_price: order.trigger_price,
+                current_price: quote.price,
+            };
+            if let Err(e) = app.emit(STOP_ORDER_TRIGGERED_EVENT, event) {
+                warn!("止损止盈事件推送失败: {e}");
+            }
+            execute_in_paper_account(app, order, quote.price, now);
+        }
+    }
+    Ok(triggered)
+}
+
+/// 触发后在模拟账户中执行卖出并推送成交事件
+fn execute_in_paper_account(app: &AppHandle, order: &StopOrder, price: f64, now: NaiveDateTime) {
+    let Some(state) = app.try_state::<PaperTradingState>() else {
+        return;
+    };
+    let mut account = state.account.lock().unwrap_or_else(|e| e.into_inner());
+    match execute_paper_stop(&mut account, &order.stock_code, price, now) {
+        Ok(Some(transaction)) => {
+            info!(
+                "{:?} {} 触发，模拟卖出 {} 股 @ {:.2}",
+                order.order_type, order.stock_code, transaction.shares, transaction.price
+            );
+            let event = PaperTradeExecutedEvent {
+                transaction,
+                cash: account.cash,
+            };
+            if let Err(e) = app.emit(PAPER_TRADE_EXECUTED_EVENT, event) {
+                warn!("模拟成交事件推送失败: {e}");
+            }
+        }
+        Ok(None) => {}
+        Err(e) => warn!("止损止盈单 {} 模拟卖出失败: {e}", order.id),
+    }
+}
+
+/// 启动后台止损止盈监控循环（仅在交易时段拉取行情，失败仅打印）
+pub fn spawn_stop_order_monitor(app: AppHandle, pool: SqlitePool) {
+    tauri::async_runtime::spawn(async move {
+        let mut interval = tokio::time::interval(STOP_ORDER_POLL_INTERVAL);
+        loop {
+            interval.tick().await;
+            if !in_trading_session(Local::now().naive_local()) {
+                continue;
+            }
+            if let Err(e) = check_stop_orders(&app, &pool).await {
+                error!("止损止盈检查失败: {e}");
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::models::StopOrderType;
+    use chrono::NaiveDate;
+
+    fn at(day: u32, hour: u32, minute: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2025, 6, day)
+            .unwrap()
+            .and_hms_opt(hour, minute, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_trigger_direction() {
+        assert!(StopOrderType::StopLoss.is_triggered(10.0, 9.98));
+        assert!(StopOrderType::StopLoss.is_triggered(10.0, 10.0));
+        assert!(!StopOrderType::StopLoss.is_triggered(10.0, 10.01));
+        assert!(StopOrderType::TakeProfit.is_triggered(12.0, 12.0));
+        assert!(!StopOrderType::TakeProfit.is_triggered(12.0, 11.99));
+    }
+
+    #[test]
+    fn test_in_trading_session() {
+        // 2025-06-13 为周五，06-14 为周六
+        assert!(in_trading_session(at(13, 9, 30)));
+        assert!(in_trading_session(at(13, 14, 59)));
+        assert!(!in_trading_session(at(13, 12, 0)));
+        assert!(!in_trading_session(at(13, 15, 1)));
+        assert!(!in_trading_session(at(14, 10, 0)));
+    }
+
+    #[test]
+    fn test_execute_paper_stop_sells_available_shares() {
+        let mut account = PaperTradingAccount::default();
+        account.buy("600519", 200, 10.0, at(12, 10, 0)).unwrap();
+        account.buy("600519", 100, 10.5, at(13, 10, 0)).unwrap();
+
+        // 当日买入的 100 股 T+1 后可卖
+        let transaction = execute_paper_stop(&mut account, "600519.SH", 9.5, at(13, 14, 0))
+            .unwrap()
+            .unwrap();
+        assert_eq!(transaction.shares, 200);
+        assert_eq!(account.positions["600519"].shares, 100);
+
+        assert!(execute_paper_stop(&mut account, "600519", 9.5, at(13, 14, 30))
+            .unwrap()
+            .is_none());
+        assert!(execute_paper_stop(&mut account, "000001", 9.5, at(13, 14, 30))
+            .unwrap()
+            .is_none());
+    }
+}
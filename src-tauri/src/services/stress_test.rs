@@ -0,0 +1,287 @@
+//! 组合压力测试服务
+//!
+//! 在预设或自定义的极端情景下估算持仓组合的盈亏冲击：
+//! 大盘暴跌按个股 Beta 传导，加息按行业利率敏感度传导，
+//! 板块轮动仅作用于匹配行业，黑天鹅在暴跌基础上叠加流动性枯竭（跌停卖不出）带来的额外损失。
+
+use crate::db::{repository, DbPool};
+use crate::error::AppError;
+use crate::services::beta::{calculate_stock_beta, DEFAULT_BETA_INDEX, DEFAULT_BETA_LOOKBACK_DAYS};
+use serde::{Deserialize, Serialize};
+
+/// 组合亏损达到该比例视为无法承受（未"存活"）
+pub const MAX_TOLERABLE_LOSS_PCT: f64 = 30.0;
+/// 加息每 100 个基点对股价的基准冲击（%）
+const RATE_HIKE_IMPACT_PER_100BP: f64 = 3.0;
+/// 黑天鹅中波动率每放大 1 倍带来的额外损失比例
+const BLACK_SWAN_LIQUIDITY_PENALTY: f64 = 0.1;
+
+/// 压力情景
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum StressScenario {
+    /// 大盘下跌 `drop_pct`%，个股按 Beta 放大/缩小
+    MarketCrash { drop_pct: f64 },
+    /// 加息 `bp` 个基点
+    RateHike { bp: f64 },
+    /// 行业名包含 `sector` 的持仓涨跌 `direction`%（负值为资金流出）
+    SectorRotation { sector: String, direction: f64 },
+    /// 突发暴跌 `price_drop_pct`%，波动率放大 `vol_spike_multiplier` 倍
+    BlackSwan {
+        price_drop_pct: f64,
+        vol_spike_multiplier: f64,
+    },
+}
+
+impl StressScenario {
+    pub fn label(&self) -> String {
+        match self {
+            Self::MarketCrash { drop_pct } => format!("大盘暴跌 {drop_pct:.0}%"),
+            Self::RateHike { bp } => format!("加息 {bp:.0}bp"),
+            Self::SectorRotation { sector, direction } => {
+                format!("{sector}板块轮动 {direction:+.0}%")
+            }
+            Self::BlackSwan {
+                price_drop_pct,
+                vol_spike_multiplier,
+            } => format!("黑天鹅 -{price_drop_pct:.0}%（波动放大 {vol_spike_multiplier:.1} 倍）"),
+        }
+    }
+
+    /// 单只持仓在该情景下的涨跌幅（%），最多亏损 100%
+    fn position_impact_pct(&self, position: &StressPosition) -> f64 {
+        let impact = match self {
+            Self::MarketCrash { drop_pct } => -drop_pct * position.beta,
+            Self::RateHike { bp } => {
+                -bp / 100.0 * RATE_HIKE_IMPACT_PER_100BP * rate_sensitivity(&position.industry)
+            }
+            Self::SectorRotation { sector, direction } => {
+                if !sector.is_empty() && position.industry.contains(sector.as_str()) {
+                    *direction
+                } else {
+                    0.0
+                }
+            }
+            Self::BlackSwan {
+                price_drop_pct,
+                vol_spike_multiplier,
+            } => {
+                let liquidity =
+                    1.0 + BLACK_SWAN_LIQUIDITY_PENALTY * (vol_spike_multiplier - 1.0).max(0.0);
+                -price_drop_pct * position.beta * liquidity
+            }
+        };
+        impact.max(-100.0)
+    }
+}
+
+/// 行业利率敏感度：地产/建筑高度依赖融资，银行/保险息差受益
+fn rate_sensitivity(industry: &str) -> f64 {
+    if ["地产", "建筑"].iter().any(|kw| industry.contains(kw)) {
+        1.5
+    } else if ["银行", "保险"].iter().any(|kw| industry.contains(kw)) {
+        -0.5
+    } else {
+        1.0
+    }
+}
+
+/// A 股历史极端行情预设：2015 年股灾（-30%）、2020 年新冠疫情冲击（-15%）
+pub fn historical_stress_scenarios() -> Vec<StressScenario> {
+    vec![
+        StressScenario::MarketCrash { drop_pct: 30.0 },
+        StressScenario::MarketCrash { drop_pct: 15.0 },
+    ]
+}
+
+/// 参与压力测试的持仓
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StressPosition {
+    pub stock_code: String,
+    /// 持仓市值（元）
+    pub market_value: f64,
+    /// 相对大盘的 Beta，缺失时取 1
+    pub beta: f64,
+    pub industry: String,
+}
+
+/// 单只持仓在某情景下的冲击
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PositionImpact {
+    pub stock_code: String,
+    pub impact_pct: f64,
+    pub pnl: f64,
+}
+
+/// 单个情景的组合冲击
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScenarioImpact {
+    pub scenario: StressScenario,
+    pub label: String,
+    pub pnl: f64,
+    pub pnl_pct: f64,
+    /// 组合亏损未超过 `MAX_TOLERABLE_LOSS_PCT`
+    pub survives: bool,
+    pub positions: Vec<PositionImpact>,
+}
+
+/// 压力测试结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StressTestResult {
+    pub portfolio_value: f64,
+    pub scenarios: Vec<ScenarioImpact>,
+    /// 最差情景的组合盈亏（%）
+    pub worst_case_pnl_pct: f64,
+    /// 所有情景下组合均能存活
+    pub survives_all: bool,
+}
+
+/// 在给定情景下对持仓组合做压力测试
+pub fn run_stress_test(
+    positions: &[StressPosition],
+    scenarios: &[StressScenario],
+) -> StressTestResult {
+    let portfolio_value: f64 = positions.iter().map(|p| p.market_value).sum();
+    let scenarios: Vec<ScenarioImpact> = scenarios
+        .iter()
+        .map(|scenario| {
+            let positions: Vec<PositionImpact> = positions
+                .iter()
+                .map(|position| {
+                    let impact_pct = scenario.position_impact_pct(position);
+                    PositionImpact {
+                        stock_code: position.stock_code.clone(),
+                        impact_pct,
+                        pnl: position.market_value * impact_pct / 100.0,
+                    }
+                })
+                .collect();
+            let pnl: f64 = positions.iter().map(|p| p.pnl).sum();
+            let pnl_pct = if portfolio_value > 0.0 {
+                pnl / portfolio_value * 100.0
+            } else {
+                0.0
+            };
+            ScenarioImpact {
+                label: scenario.label(),
+                scenario: scenario.clone(),
+                pnl,
+                pnl_pct,
+                survives: pnl_pct > -MAX_TOLERABLE_LOSS_PCT,
+                positions,
+            }
+        })
+        .collect();
+
+    StressTestResult {
+        portfolio_value,
+        worst_case_pnl_pct: scenarios.iter().map(|s| s.pnl_pct).fold(0.0, f64::min),
+        survives_all: scenarios.iter().all(|s| s.survives),
+        scenarios,
+    }
+}
+
+/// 以 (股票代码, 持仓市值) 构建压力测试持仓：Beta 取相对上证综指的历史值（指数未入库时为 1），
+/// 行业取自股票基础信息
+pub async fn build_stress_positions(
+    holdings: &[(String, f64)],
+    pool: &DbPool,
+) -> Result<Vec<StressPosition>, AppError> {
+    let mut positions = Vec::with_capacity(holdings.len());
+    for (stock_code, market_value) in holdings {
+        let beta = calculate_stock_beta(
+            stock_code,
+            DEFAULT_BETA_INDEX,
+            DEFAULT_BETA_LOOKBACK_DAYS,
+            pool,
+        )
+        .await?
+        .map(|analysis| analysis.beta)
+        .unwrap_or(1.0);
+        let industry = repository::get_stock_industry(stock_code, pool)
+            .await?
+            .unwrap_or_default();
+        positions.push(StressPosition {
+            stock_code: stock_code.clone(),
+            market_value: *market_value,
+            beta,
+            industry,
+        });
+    }
+    Ok(positions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn position(code: &str, value: f64, beta: f64, industry: &str) -> StressPosition {
+        StressPosition {
+            stock_code: code.to_string(),
+            market_value: value,
+            beta,
+            industry: industry.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_market_crash_scales_with_beta() {
+        let positions = [
+            position("600000", 50_000.0, 1.2, "银行"),
+            position("300750", 50_000.0, 0.8, "电气设备"),
+        ];
+        let result = run_stress_test(&positions, &historical_stress_scenarios());
+        assert_eq!(result.portfolio_value, 100_000.0);
+
+        let crash_2015 = &result.scenarios[0];
+        assert!((crash_2015.positions[0].impact_pct + 36.0).abs() < 1e-9);
+        assert!((crash_2015.positions[1].impact_pct + 24.0).abs() < 1e-9);
+        assert!((crash_2015.pnl_pct + 30.0).abs() < 1e-9);
+        assert!(!crash_2015.survives);
+        assert!(result.scenarios[1].survives);
+        assert!(!result.survives_all);
+        assert!((result.worst_case_pnl_pct + 30.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_rate_hike_and_sector_rotation_by_industry() {
+        let positions = [
+            position("000001", 10_000.0, 1.0, "银行"),
+            position("000002", 10_000.0, 1.0, "全国地产"),
+        ];
+        let result = run_stress_test(
+            &positions,
+            &[
+                StressScenario::RateHike { bp: 100.0 },
+                StressScenario::SectorRotation {
+                    sector: "地产".to_string(),
+                    direction: -10.0,
+                },
+            ],
+        );
+        let hike = &result.scenarios[0].positions;
+        assert!(hike[0].impact_pct > 0.0, "银行受益于加息");
+        assert!((hike[1].impact_pct + 4.5).abs() < 1e-9);
+
+        let rotation = &result.scenarios[1].positions;
+        assert_eq!(rotation[0].impact_pct, 0.0);
+        assert_eq!(rotation[1].impact_pct, -10.0);
+    }
+
+    #[test]
+    fn test_black_swan_adds_liquidity_loss_and_caps_at_total_loss() {
+        let positions = [position("600519", 10_000.0, 1.0, "白酒")];
+        let result = run_stress_test(
+            &positions,
+            &[
+                StressScenario::BlackSwan {
+                    price_drop_pct: 20.0,
+                    vol_spike_multiplier: 3.0,
+                },
+                StressScenario::MarketCrash { drop_pct: 200.0 },
+            ],
+        );
+        assert!((result.scenarios[0].pnl_pct + 24.0).abs() < 1e-9);
+        assert_eq!(result.scenarios[1].pnl_pct, -100.0);
+    }
+}
@@ -0,0 +1,75 @@
+//! 全市场股票池服务
+//!
+//! 从接口拉取全部 A 股列表，用本地股票详情补充行业、用股本快照补充市值分档后整体替换股票池；
+//! 本次未返回的股票（退市、暂停上市）置为不活跃。
+
+use crate::api::stock::fetch_universe_from_api;
+use crate::db::models::{MarketCapCategory, UniverseEntry};
+use crate::db::repository;
+use crate::error::AppError;
+use log::info;
+use sqlx::SqlitePool;
+use std::collections::HashMap;
+
+/// 用本地行业与流通市值补全股票池条目（无本地数据的字段保持为空串）
+pub fn enrich_universe_entries(
+    entries: &mut [UniverseEntry],
+    industries: &HashMap<String, String>,
+    circulating_caps: &HashMap<String, f64>,
+) {
+    for entry in entries.iter_mut() {
+        if let Some(industry) = industries.get(&entry.code) {
+            entry.industry = industry.clone();
+        }
+        if let Some(category) = circulating_caps
+            .get(&entry.code)
+            .and_then(|cap| MarketCapCategory::from_circulating_cap(*cap))
+        {
+            entry.market_cap_category = category.label().to_string();
+        }
+    }
+}
+
+/// 拉取并替换股票池，返回活跃股票数
+pub async fn update_universe(pool: &SqlitePool) -> Result<usize, AppError> {
+    let mut entries = fetch_universe_from_api().await?;
+    let industries = repository::get_stock_industries(pool).await?;
+    let circulating_caps = repository::get_circulating_market_caps(pool).await?;
+    enrich_universe_entries(&mut entries, &industries, &circulating_caps);
+    repository::replace_universe(pool, &entries).await?;
+    info!("股票池已更新：{} 只活跃股票", entries.len());
+    Ok(entries.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_enrich_universe_entries() {
+        let blank = |code: &str| UniverseEntry {
+            code: code.to_string(),
+            name: code.to_string(),
+            exchange: "SZ".to_string(),
+            industry: String::new(),
+            market_cap_category: String::new(),
+            is_st: false,
+            is_active: true,
+        };
+        let mut entries = vec![blank("000001"), blank("300750"), blank("000004")];
+        let industries = HashMap::from([("000001".to_string(), "银行".to_string())]);
+        let caps = HashMap::from([
+            ("000001".to_string(), 2_000.0e8),
+            ("300750".to_string(), 300.0e8),
+            ("000004".to_string(), 0.0),
+        ]);
+        enrich_universe_entries(&mut entries, &industries, &caps);
+
+        assert_eq!(entries[0].industry, "银行");
+        assert_eq!(entries[0].market_cap_category, "大盘");
+        assert_eq!(entries[1].industry, "");
+        assert_eq!(entries[1].market_cap_category, "中盘");
+        // 市值无效时不分档
+        assert_eq!(entries[2].market_cap_category, "");
+    }
+}
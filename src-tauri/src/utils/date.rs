@@ -3,6 +3,50 @@
 //! 提供A股交易日判断、节假日处理等功能
 
 use chrono::{Datelike, NaiveDate, Weekday};
+use log::warn;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, OnceLock, RwLock};
+
+/// 动态节假日日历：按年份保存接口获取的法定节假日，未加载的年份回退到内置规则。
+///
+/// 调休补班日均为周末，交易所照常休市，因此只需记录节假日。
+#[derive(Debug, Clone, Default)]
+pub struct HolidayCalendar {
+    years: Arc<RwLock<HashMap<i32, HashSet<NaiveDate>>>>,
+}
+
+impl HolidayCalendar {
+    /// 全局日历，交易日判断函数与应用托管状态共用同一份数据
+    pub fn global() -> &'static HolidayCalendar {
+        static CALENDAR: OnceLock<HolidayCalendar> = OnceLock::new();
+        CALENDAR.get_or_init(HolidayCalendar::default)
+    }
+
+    /// 写入（覆盖）某年的节假日
+    pub fn set_year(&self, year: i32, holidays: impl IntoIterator<Item = NaiveDate>) {
+        let holidays = holidays.into_iter().filter(|d| d.year() == year).collect();
+        if let Ok(mut years) = self.years.write() {
+            years.insert(year, holidays);
+        }
+    }
+
+    /// 已加载的年份（升序）
+    pub fn loaded_years(&self) -> Vec<i32> {
+        let mut years: Vec<i32> = self
+            .years
+            .read()
+            .map(|years| years.keys().copied().collect())
+            .unwrap_or_default();
+        years.sort_unstable();
+        years
+    }
+
+    /// 日期是否为节假日；该年份未加载时返回 None
+    pub fn is_holiday(&self, date: NaiveDate) -> Option<bool> {
+        let years = self.years.read().ok()?;
+        years.get(&date.year()).map(|holidays| holidays.contains(&date))
+    }
+}
 
 /// 判断是否为交易日
 pub fn is_trading_day(date: NaiveDate) -> bool {
@@ -12,6 +56,11 @@ pub fn is_trading_day(date: NaiveDate) -> bool {
         _ => {}
     }
 
+    // 优先使用接口获取的节假日
+    if let Some(is_holiday) = HolidayCalendar::global().is_holiday(date) {
+        return !is_holiday;
+    }
+
     let year = date.year();
     let month = date.month();
     let day = date.day();
@@ -26,38 +75,27 @@ pub fn is_trading_day(date: NaiveDate) -> bool {
     }
 
     // 春节假期（按年份判断）
-    match year {
+    let year_holiday = match year {
+        2023 => month == 1 && (21..=27).contains(&day),
         2024 => {
-            if month == 2 && (10..=17).contains(&day) {
-                return false;
-            }
-        }
-        2025 => {
-            if (month == 1 && day >= 29) || (month == 2 && day <= 4) {
-                return false;
-            }
+            (month == 2 && (9..=17).contains(&day))
+                || (month == 6 && day == 10)
+                || (month == 9 && (16..=17).contains(&day))
         }
+        2025 => (month == 1 && day >= 29) || (month == 2 && day <= 4),
         2026 => {
-            if (month == 1 && (1..=3).contains(&day))
+            (month == 1 && (1..=3).contains(&day))
                 || (month == 2 && (15..=23).contains(&day))
                 || (month == 4 && (4..=6).contains(&day))
                 || (month == 5 && (1..=5).contains(&day))
                 || (month == 6 && (19..=21).contains(&day))
                 || (month == 9 && (25..=27).contains(&day))
                 || (month == 10 && (1..=7).contains(&day))
-            {
-                return false;
-            }
-        }
-        2023 => {
-            if month == 1 && (21..=27).contains(&day) {
-                return false;
-            }
         }
-        _ => {}
-    }
+        _ => false,
+    };
 
-    true
+    !year_holiday
 }
 
 /// 获取下一个交易日
@@ -71,7 +109,7 @@ pub fn get_next_trading_day(date: NaiveDate) -> NaiveDate {
     }
     
     if count >= 30 {
-        println!("⚠️ 警告：查找下一个交易日超过30天");
+        warn!("查找下一个交易日超过30天");
     }
     
     next_date
@@ -148,4 +186,21 @@ mod tests {
         assert!(!is_trading_day(NaiveDate::from_ymd_opt(2026, 6, 19).unwrap()));
         assert!(!is_trading_day(NaiveDate::from_ymd_opt(2026, 9, 25).unwrap()));
     }
+
+    #[test]
+    fn test_holiday_calendar() {
+        let calendar = HolidayCalendar::default();
+        let date = |m, d| NaiveDate::from_ymd_opt(2027, m, d).unwrap();
+        assert_eq!(calendar.is_holiday(date(2, 8)), None);
+
+        // 其他年份的日期不写入
+        calendar.set_year(2027, [date(2, 8), date(2, 9), NaiveDate::from_ymd_opt(2028, 1, 1).unwrap()]);
+        assert_eq!(calendar.is_holiday(date(2, 8)), Some(true));
+        assert_eq!(calendar.is_holiday(date(2, 10)), Some(false));
+        assert_eq!(calendar.loaded_years(), vec![2027]);
+        assert_eq!(
+            calendar.is_holiday(NaiveDate::from_ymd_opt(2028, 1, 1).unwrap()),
+            None
+        );
+    }
 }
@@ -1,10 +1,40 @@
 //! 数学工具函数
 
-use crate::config::constants::{A_STOCK_LIMIT_DOWN, A_STOCK_LIMIT_UP};
+use crate::config::constants::{
+    A_STOCK_LIMIT_DOWN, A_STOCK_LIMIT_UP, BSE_LIMIT_DOWN, BSE_LIMIT_UP, GROWTH_BOARD_LIMIT_DOWN,
+    GROWTH_BOARD_LIMIT_UP,
+};
+use crate::utils::{canonical_stock_symbol, is_etf};
+
+/// 按股票代码所属板块返回单日涨跌幅限制 (跌停%, 涨停%)：
+/// 科创板（688/689）与创业板（300/301/302）±20%，北交所（8/43/92 开头）±30%，其余主板 ±10%。
+/// 注册制新股上市前 5 个交易日（北交所为首日）不设涨跌幅限制，`is_new_listing` 为 true 时
+/// 返回 (-100%, +∞)。场内 ETF（含跟踪科创板、创业板指数的 588/159 系列）不论板块与上市天数
+/// 均为 ±10%
+pub fn get_price_limit(stock_code: &str, is_new_listing: bool) -> (f64, f64) {
+    if is_etf(stock_code) {
+        return (A_STOCK_LIMIT_DOWN, A_STOCK_LIMIT_UP);
+    }
+    if is_new_listing {
+        return (-100.0, f64::INFINITY);
+    }
+    let code = canonical_stock_symbol(stock_code);
+    if ["688", "689", "300", "301", "302"]
+        .iter()
+        .any(|prefix| code.starts_with(prefix))
+    {
+        (GROWTH_BOARD_LIMIT_DOWN, GROWTH_BOARD_LIMIT_UP)
+    } else if ["8", "43", "92"].iter().any(|prefix| code.starts_with(prefix)) {
+        (BSE_LIMIT_DOWN, BSE_LIMIT_UP)
+    } else {
+        (A_STOCK_LIMIT_DOWN, A_STOCK_LIMIT_UP)
+    }
+}
 
-/// A股涨跌停限制
-pub fn clamp_daily_change(change_percent: f64) -> f64 {
-    change_percent.clamp(A_STOCK_LIMIT_DOWN, A_STOCK_LIMIT_UP)
+/// A股涨跌停限制（按股票代码所属板块，非新股）
+pub fn clamp_daily_change(change_percent: f64, stock_code: &str) -> f64 {
+    let (limit_down, limit_up) = get_price_limit(stock_code, false);
+    change_percent.clamp(limit_down, limit_up)
 }
 
 /// 计算标准差
@@ -119,6 +149,15 @@ pub fn normalize(values: &[f64]) -> Vec<f64> {
     values.iter().map(|&v| (v - min) / range).collect()
 }
 
+/// [`normalize`] 的逆变换：按原序列的最小值、最大值还原；原序列恒定时还原为 `min`
+pub fn denormalize(values: &[f64], min: f64, max: f64) -> Vec<f64> {
+    let range = max - min;
+    if range == 0.0 {
+        return vec![min; values.len()];
+    }
+    values.iter().map(|&v| v * range + min).collect()
+}
+
 /// 标准化数据 (z-score)
 pub fn standardize(values: &[f64]) -> Vec<f64> {
     if values.is_empty() {
@@ -172,6 +211,23 @@ mod tests {
         assert!((std - 2.0).abs() < 0.01);
     }
 
+    #[test]
+    fn test_price_limit_by_board() {
+        assert_eq!(get_price_limit("600519", false), (-10.0, 10.0));
+        assert_eq!(get_price_limit("sz000001", false), (-10.0, 10.0));
+        assert_eq!(get_price_limit("688981.SH", false), (-20.0, 20.0));
+        assert_eq!(get_price_limit("300750", false), (-20.0, 20.0));
+        assert_eq!(get_price_limit("bj830799", false), (-30.0, 30.0));
+        assert_eq!(get_price_limit("688981", true).1, f64::INFINITY);
+        assert_eq!(get_price_limit("588000", false), (-10.0, 10.0));
+        assert_eq!(get_price_limit("159915", true), (-10.0, 10.0));
+        assert_eq!(clamp_daily_change(15.0, "510050"), 10.0);
+
+        assert_eq!(clamp_daily_change(15.0, "600519"), 10.0);
+        assert_eq!(clamp_daily_change(15.0, "300750"), 15.0);
+        assert_eq!(clamp_daily_change(-25.0, "688981"), -20.0);
+    }
+
     #[test]
     fn test_normalize() {
         let values = vec![0.0, 50.0, 100.0];
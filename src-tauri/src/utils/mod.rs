@@ -2,10 +2,14 @@
 
 pub mod date;
 pub mod math;
+pub mod pinyin;
+pub mod progress;
 pub mod symbol;
 pub mod volume_metrics;
 
 pub use date::*;
 pub use math::*;
+pub use pinyin::*;
+pub use progress::*;
 pub use symbol::*;
 pub use volume_metrics::*;
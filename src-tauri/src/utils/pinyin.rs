@@ -0,0 +1,80 @@
+//! 汉字拼音首字母
+//!
+//! GB2312 一级汉字按拼音排序，按编码区间即可查得声母，无需完整拼音字典；
+//! 二级汉字（生僻字）不在区间内，直接跳过。多音字按股票名称中的常见读音修正。
+
+use encoding_rs::GBK;
+
+/// 各声母在 GB2312 一级汉字中的起始编码（升序），最后一项为一级汉字结束位置
+const INITIAL_BOUNDARIES: [(u16, char); 23] = [
+    (0xB0A1, 'a'),
+    (0xB0C5, 'b'),
+    (0xB2C1, 'c'),
+    (0xB4EE, 'd'),
+    (0xB6EA, 'e'),
+    (0xB7A2, 'f'),
+    (0xB8C1, 'g'),
+    (0xB9FE, 'h'),
+    (0xBBF7, 'j'),
+    (0xBFA6, 'k'),
+    (0xC0AC, 'l'),
+    (0xC2E8, 'm'),
+    (0xC4C3, 'n'),
+    (0xC5B6, 'o'),
+    (0xC5BE, 'p'),
+    (0xC6DA, 'q'),
+    (0xC8BB, 'r'),
+    (0xC8F6, 's'),
+    (0xCBFA, 't'),
+    (0xCDDA, 'w'),
+    (0xCEF4, 'x'),
+    (0xD1B9, 'y'),
+    (0xD4D1, 'z'),
+];
+const LEVEL1_END: u16 = 0xD7F9;
+
+/// 股票名称中常见的多音字读音（GB2312 按另一读音排序）
+const POLYPHONE_INITIALS: [(char, char); 3] = [('行', 'h'), ('重', 'c'), ('藏', 'z')];
+
+fn initial_of(ch: char) -> Option<char> {
+    if ch.is_ascii_alphanumeric() {
+        return Some(ch.to_ascii_lowercase());
+    }
+    if let Some(&(_, initial)) = POLYPHONE_INITIALS.iter().find(|(c, _)| *c == ch) {
+        return Some(initial);
+    }
+    let mut buf = [0u8; 4];
+    let (bytes, _, had_errors) = GBK.encode(ch.encode_utf8(&mut buf));
+    if had_errors || bytes.len() != 2 {
+        return None;
+    }
+    let code = u16::from_be_bytes([bytes[0], bytes[1]]);
+    if !(INITIAL_BOUNDARIES[0].0..LEVEL1_END).contains(&code) {
+        return None;
+    }
+    INITIAL_BOUNDARIES
+        .iter()
+        .rev()
+        .find(|(start, _)| code >= *start)
+        .map(|&(_, initial)| initial)
+}
+
+/// 名称的拼音首字母缩写（小写），如 "平安银行" → "payh"；字母数字保留，符号与生僻字跳过
+pub fn pinyin_abbr(name: &str) -> String {
+    name.chars().filter_map(initial_of).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pinyin_abbr() {
+        assert_eq!(pinyin_abbr("平安银行"), "payh");
+        assert_eq!(pinyin_abbr("贵州茅台"), "gzmt");
+        assert_eq!(pinyin_abbr("重庆啤酒"), "cqpj");
+        assert_eq!(pinyin_abbr("*ST康美"), "stkm");
+        assert_eq!(pinyin_abbr("TCL科技"), "tclkj");
+        assert_eq!(pinyin_abbr(""), "");
+    }
+}
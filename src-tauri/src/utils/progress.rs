@@ -0,0 +1,25 @@
+//! 长任务进度回调
+//!
+//! 训练、回测、网格搜索等计算逻辑只依赖 [`ProgressSink`]，不感知 Tauri；
+//! 命令层传入向前端推送事件并支持取消的实现（`services::progress::ProgressReporter`）。
+
+/// 操作被取消时返回的错误信息
+pub const OPERATION_CANCELLED: &str = "操作已取消";
+
+/// 进度回调
+pub trait ProgressSink: Send + Sync {
+    /// 已完成 `completed`/`total` 步
+    fn report(&self, completed: usize, total: usize, message: &str);
+
+    /// 调用方是否已请求取消；长循环在每步开始前检查
+    fn is_cancelled(&self) -> bool {
+        false
+    }
+}
+
+/// 不上报进度、不可取消（后台任务与测试使用）
+pub struct NoProgress;
+
+impl ProgressSink for NoProgress {
+    fn report(&self, _completed: usize, _total: usize, _message: &str) {}
+}
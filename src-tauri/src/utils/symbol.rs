@@ -15,6 +15,15 @@ pub fn canonical_stock_symbol(symbol: &str) -> String {
     }
 }
 
+/// 是否为场内 ETF：沪市 51/56/58 开头（如 510050、588000），深市 159 开头（如 159915）
+pub fn is_etf(stock_code: &str) -> bool {
+    let code = canonical_stock_symbol(stock_code);
+    code.len() == 6
+        && ["51", "56", "58", "159"]
+            .iter()
+            .any(|prefix| code.starts_with(prefix))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -33,4 +42,17 @@ mod tests {
         assert_eq!(canonical_stock_symbol("12345"), "12345");
         assert_eq!(canonical_stock_symbol("1234567"), "1234567");
     }
+
+    #[test]
+    fn detects_exchange_traded_funds() {
+        assert!(is_etf("510050"));
+        assert!(is_etf("sz159915"));
+        assert!(is_etf("588000.SH"));
+        assert!(is_etf("563300"));
+        assert!(!is_etf("600519"));
+        assert!(!is_etf("000001"));
+        assert!(!is_etf("688981"));
+        assert!(!is_etf("300750"));
+        assert!(!is_etf("5100"));
+    }
 }
@@ -0,0 +1,34 @@
+//! 端到端测试与性能基准共用的 fixture 行情
+
+use biga_lib::db::connection::run_migrations;
+use biga_lib::db::models::HistoricalData;
+use biga_lib::db::repository::upsert_historical_data;
+use sqlx::sqlite::{SqlitePool, SqlitePoolOptions};
+use std::path::Path;
+
+pub const FIXTURE_CODE: &str = "600000";
+/// 合成的 250 个交易日行情：温和加速上涨叠加小幅正弦波动，人工标注为上涨趋势
+const FIXTURE_CSV: &str = include_str!("../fixtures/prediction_uptrend_250d.csv");
+
+pub fn load_fixture() -> Vec<HistoricalData> {
+    csv::Reader::from_reader(FIXTURE_CSV.as_bytes())
+        .deserialize::<HistoricalData>()
+        .map(|row| row.expect("fixture 行应可解析"))
+        .collect()
+}
+
+/// 执行全部迁移并写入 fixture 的内存数据库（单连接：每个内存连接是独立数据库）
+pub async fn fixture_pool(historical: &[HistoricalData]) -> SqlitePool {
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .expect("应创建内存 SQLite");
+    run_migrations(&pool, Path::new("migrations"))
+        .await
+        .expect("迁移应执行成功");
+    upsert_historical_data(FIXTURE_CODE, &pool, historical)
+        .await
+        .expect("fixture 应写入历史数据表");
+    pool
+}
@@ -0,0 +1,131 @@
+//! 技术指标不变量的性质测试：随机价格序列上检验取值范围、恒等式与切片一致性，
+//! 捕捉在错误切片上计算或差一错误导致的隐性偏差。
+
+use biga_lib::prediction::indicators::{
+    calculate_bollinger_bands, calculate_cci, calculate_cci_series, calculate_dif_series,
+    calculate_kdj, calculate_kdj_series, calculate_macd_full, calculate_rsi_with_period,
+    is_golden_cross,
+};
+use biga_lib::prediction::indicators::kdj::{KDJ_D_SMOOTH, KDJ_K_SMOOTH};
+use biga_lib::utils::math::{denormalize, normalize};
+use proptest::prelude::*;
+
+/// 正价格序列
+fn prices(max_len: usize) -> impl Strategy<Value = Vec<f64>> {
+    prop::collection::vec(1.0f64..1000.0, 0..max_len)
+}
+
+/// (最高价, 最低价, 收盘价) 序列，保证 最低 ≤ 收盘 ≤ 最高
+fn bars(max_len: usize) -> impl Strategy<Value = (Vec<f64>, Vec<f64>, Vec<f64>)> {
+    prop::collection::vec((10.0f64..1000.0, 0.0f64..5.0, 0.0f64..5.0), 0..max_len).prop_map(
+        |rows| {
+            let highs = rows.iter().map(|(close, up, _)| close + up).collect();
+            let lows = rows.iter().map(|(close, _, down)| close - down).collect();
+            let closes = rows.iter().map(|(close, _, _)| *close).collect();
+            (highs, lows, closes)
+        },
+    )
+}
+
+proptest! {
+    #[test]
+    fn rsi_is_bounded(prices in prices(200), period in 1usize..30) {
+        let rsi = calculate_rsi_with_period(&prices, period);
+        prop_assert!((0.0..=100.0).contains(&rsi), "RSI = {rsi}");
+    }
+
+    #[test]
+    fn bollinger_bands_are_ordered(
+        prices in prices(120),
+        period in 1usize..60,
+        multiplier in 0.0f64..4.0,
+    ) {
+        let bands = calculate_bollinger_bands(&prices, period, multiplier);
+        prop_assert!(bands.upper >= bands.middle);
+        prop_assert!(bands.middle >= bands.lower);
+    }
+
+    #[test]
+    fn cci_of_constant_bars_is_zero(
+        close in 10.0f64..1000.0,
+        spread in 0.0f64..5.0,
+        len in 1usize..80,
+        period in 1usize..30,
+    ) {
+        let highs = vec![close + spread; len];
+        let lows = vec![close - spread; len];
+        let closes = vec![close; len];
+        prop_assert_eq!(calculate_cci(&highs, &lows, &closes, period), 0.0);
+        prop_assert!(calculate_cci_series(&highs, &lows, &closes, period)
+            .iter()
+            .all(|&cci| cci == 0.0));
+    }
+
+    #[test]
+    fn cci_latest_matches_series((highs, lows, closes) in bars(100), period in 1usize..30) {
+        let series = calculate_cci_series(&highs, &lows, &closes, period);
+        let latest = calculate_cci(&highs, &lows, &closes, period);
+        prop_assert_eq!(series.last().copied().unwrap_or(0.0), latest);
+    }
+
+    #[test]
+    fn macd_golden_cross_implies_dif_above_dea(prices in prices(150)) {
+        prop_assume!(prices.len() >= 2);
+        let (prev_dif, prev_dea, _) = calculate_macd_full(&prices[..prices.len() - 1]);
+        let (dif, dea, histogram) = calculate_macd_full(&prices);
+        if is_golden_cross(prev_dif, prev_dea, dif, dea) {
+            prop_assert!(dif > dea);
+            prop_assert!(histogram > 0.0);
+        }
+    }
+
+    #[test]
+    fn macd_dif_is_latest_of_series(prices in prices(150)) {
+        let series = calculate_dif_series(&prices);
+        let (dif, _, _) = calculate_macd_full(&prices);
+        prop_assert_eq!(series.last().copied().unwrap_or(0.0), dif);
+    }
+
+    #[test]
+    fn kdj_k_d_bounded_and_j_identity((highs, lows, closes) in bars(120), period in 1usize..20) {
+        let series = calculate_kdj_series(&highs, &lows, &closes, period, KDJ_K_SMOOTH, KDJ_D_SMOOTH);
+        for point in &series {
+            prop_assert!((0.0..=100.0).contains(&point.k), "K = {}", point.k);
+            prop_assert!((0.0..=100.0).contains(&point.d), "D = {}", point.d);
+            prop_assert!((point.j - (3.0 * point.k - 2.0 * point.d)).abs() < 1e-9);
+        }
+        let latest = calculate_kdj(&highs, &lows, &closes, period);
+        let expected = series
+            .last()
+            .map(|p| (p.k, p.d, p.j))
+            .unwrap_or((50.0, 50.0, 50.0));
+        prop_assert_eq!(latest, expected);
+    }
+
+    #[test]
+    fn normalize_denormalize_roundtrip(values in prop::collection::vec(0.01f64..10_000.0, 1..100)) {
+        let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+        let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        let restored = denormalize(&normalize(&values), min, max);
+        for (original, restored) in values.iter().zip(&restored) {
+            prop_assert!(
+                (original - restored).abs() <= 1e-9 * original.abs().max(1.0),
+                "{original} → {restored}"
+            );
+        }
+    }
+}
+
+/// J = 3K - 2D 不受 0-100 约束：单边上涨时 K 领先 D，J 超过 100；单边下跌时低于 0
+#[test]
+fn kdj_j_overshoots_in_one_sided_moves() {
+    let rising: Vec<f64> = (0..20).map(|i| 10.0 + i as f64).collect();
+    let lows_rising: Vec<f64> = rising.iter().map(|c| c - 1.0).collect();
+    let (_, _, j) = calculate_kdj(&rising, &lows_rising, &rising, 9);
+    assert!(j > 100.0, "J = {j}");
+
+    let falling: Vec<f64> = (0..20).map(|i| 40.0 - i as f64).collect();
+    let highs_falling: Vec<f64> = falling.iter().map(|c| c + 1.0).collect();
+    let (_, _, j) = calculate_kdj(&highs_falling, &falling, &falling, 9);
+    assert!(j < 0.0, "J = {j}");
+}
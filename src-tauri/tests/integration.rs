@@ -60,6 +60,14 @@ fn test_analyze_pipeline_runs() {
             turnover_rate: 3.5,
             prediction_days: 5,
             stock_code: Some("sh600000"),
+            market_ad_ratio: None,
+            market_fear_greed: None,
+            news_sentiment: None,
+            learned_factor_weights: None,
+            beta: None,
+            sector_relative_strength: None,
+            fundamental: None,
+            indicator_config: None,
         },
     );
 
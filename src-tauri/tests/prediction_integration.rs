@@ -0,0 +1,105 @@
+//! 预测管线端到端测试：fixture 行情写入内存 SQLite，走完纯技术分析预测命令的完整流程
+//! （历史数据读取、专业引擎、校准、趋势过滤、买卖点与多周期信号）。
+//! 纯技术分析请求不开启新闻情绪与公司事件查询，整个流程不发起网络请求。
+
+mod common;
+
+use biga_lib::commands::stock_prediction::predict_with_technical_only_with_pool;
+use biga_lib::prediction::types::TechnicalOnlyRequest;
+use common::{fixture_pool, load_fixture, FIXTURE_CODE};
+
+/// 专业引擎方向标签（`PredictionDirection::to_string`）
+const VALID_SIGNALS: [&str; 5] = ["强烈看涨", "看涨", "中性", "看跌", "强烈看跌"];
+
+#[tokio::test]
+async fn test_technical_only_prediction_pipeline() {
+    let historical = load_fixture();
+    assert_eq!(historical.len(), 250);
+    let pool = fixture_pool(&historical).await;
+
+    let prediction_days = 5;
+    let response = predict_with_technical_only_with_pool(
+        TechnicalOnlyRequest {
+            stock_code: FIXTURE_CODE.to_string(),
+            history_days: None,
+            prediction_days: Some(prediction_days),
+        },
+        &pool,
+    )
+    .await
+    .expect("纯技术分析预测应成功");
+
+    let predictions = &response.predictions.predictions;
+    assert_eq!(predictions.len(), prediction_days);
+    for prediction in predictions {
+        assert!(prediction.predicted_price > 0.0, "{prediction:?}");
+        assert!(
+            (0.0..=1.0).contains(&prediction.confidence),
+            "置信度越界: {}",
+            prediction.confidence
+        );
+        let signal = prediction.trading_signal.as_deref().expect("应给出交易信号");
+        assert!(VALID_SIGNALS.contains(&signal), "未知信号: {signal}");
+    }
+
+    let last = historical.last().unwrap();
+    let last_real = response
+        .predictions
+        .last_real_data
+        .as_ref()
+        .expect("应返回最新真实数据");
+    assert_eq!(last_real.date, last.date.format("%Y-%m-%d").to_string());
+    assert!((last_real.price - last.close).abs() < 1e-9);
+
+    // fixture 标注为上涨趋势：日线多周期信号应为看涨
+    let daily_trend = &response.professional_analysis.multi_timeframe.daily_trend;
+    assert!(
+        daily_trend == "看涨" || daily_trend == "强势看涨",
+        "日线趋势应与上涨 fixture 一致，实际为 {daily_trend}"
+    );
+}
+
+#[tokio::test]
+async fn test_technical_only_prediction_recommends_horizon_when_unspecified() {
+    let historical = load_fixture();
+    let pool = fixture_pool(&historical).await;
+
+    let response = predict_with_technical_only_with_pool(
+        TechnicalOnlyRequest {
+            stock_code: FIXTURE_CODE.to_string(),
+            history_days: None,
+            prediction_days: None,
+        },
+        &pool,
+    )
+    .await
+    .expect("自动预测周期应成功");
+
+    let recommendation = response
+        .professional_analysis
+        .horizon_recommendation
+        .as_ref()
+        .expect("应返回预测周期建议");
+    assert!([1, 3, 5, 10].contains(&recommendation.recommended_days));
+    assert_eq!(
+        response.predictions.predictions.len(),
+        recommendation.recommended_days
+    );
+}
+
+#[tokio::test]
+async fn test_technical_only_prediction_rejects_short_history() {
+    let historical = load_fixture();
+    let pool = fixture_pool(&historical[..40]).await;
+
+    let result = predict_with_technical_only_with_pool(
+        TechnicalOnlyRequest {
+            stock_code: FIXTURE_CODE.to_string(),
+            history_days: None,
+            prediction_days: Some(5),
+        },
+        &pool,
+    )
+    .await;
+    assert!(result.is_err(), "不足 60 个交易日应返回错误");
+}
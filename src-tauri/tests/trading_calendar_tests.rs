@@ -0,0 +1,62 @@
+//! 交易日历测试：未加载接口节假日时走内置规则，覆盖周末、2024 年法定节假日与调休补班日。
+//!
+//! 调休补班日虽是法定工作日，但均为周末，沪深交易所照常休市，因此不是交易日。
+
+use biga_lib::utils::date::{get_next_trading_day, is_trading_day};
+use chrono::{Datelike, NaiveDate, Weekday};
+
+fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, day).unwrap()
+}
+
+#[test]
+fn weekends_are_not_trading_days() {
+    let mut day = date(2024, 3, 4);
+    while day < date(2024, 4, 1) {
+        let weekend = matches!(day.weekday(), Weekday::Sat | Weekday::Sun);
+        assert_eq!(is_trading_day(day), !weekend, "{day}");
+        day = day.succ_opt().unwrap();
+    }
+}
+
+#[test]
+fn known_2024_holidays_are_closed() {
+    // 春节 2 月 9 日（除夕）至 2 月 17 日休市
+    for d in 9..=17 {
+        assert!(!is_trading_day(date(2024, 2, d)), "2024-02-{d:02}");
+    }
+    assert!(is_trading_day(date(2024, 2, 8)));
+    assert!(is_trading_day(date(2024, 2, 19)));
+
+    // 端午节
+    assert!(!is_trading_day(date(2024, 6, 10)));
+    assert!(is_trading_day(date(2024, 6, 11)));
+
+    // 国庆节
+    for d in 1..=7 {
+        assert!(!is_trading_day(date(2024, 10, d)), "2024-10-{d:02}");
+    }
+    assert!(is_trading_day(date(2024, 10, 8)));
+}
+
+#[test]
+fn weekend_make_up_workdays_stay_closed() {
+    // 国庆调休补班：9 月 29 日（周日）、10 月 12 日（周六）
+    assert_eq!(date(2024, 9, 29).weekday(), Weekday::Sun);
+    assert!(!is_trading_day(date(2024, 9, 29)));
+    assert_eq!(date(2024, 10, 12).weekday(), Weekday::Sat);
+    assert!(!is_trading_day(date(2024, 10, 12)));
+}
+
+#[test]
+fn next_trading_day_after_friday_is_monday() {
+    assert_eq!(get_next_trading_day(date(2024, 3, 15)), date(2024, 3, 18));
+    assert_eq!(get_next_trading_day(date(2024, 11, 29)), date(2024, 12, 2));
+}
+
+#[test]
+fn next_trading_day_skips_holidays() {
+    assert_eq!(get_next_trading_day(date(2024, 2, 8)), date(2024, 2, 19));
+    assert_eq!(get_next_trading_day(date(2024, 6, 7)), date(2024, 6, 11));
+    assert_eq!(get_next_trading_day(date(2024, 9, 30)), date(2024, 10, 8));
+}
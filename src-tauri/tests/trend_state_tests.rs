@@ -0,0 +1,83 @@
+//! 日线 / 周线趋势综合的全组合测试，以及趋势状态逐日变化的一致性检查。
+//!
+//! 查找表依据多周期分析：两个周期同向时趋势叠加（一方强烈、另一方同向即为强烈），
+//! 一方震荡时沿用另一方方向但不升为强烈，方向冲突时视为震荡。
+
+use biga_lib::prediction::analysis::trend::{
+    combine_trends, TrendAnalysis, TrendState, TrendStateTransition,
+};
+use TrendState::{Bearish, Bullish, Neutral, StrongBearish, StrongBullish};
+
+const STATES: [TrendState; 5] = [StrongBullish, Bullish, Neutral, Bearish, StrongBearish];
+
+/// 行：日线；列：周线（顺序同 `STATES`）
+const EXPECTED_OVERALL: [[TrendState; 5]; 5] = [
+    [StrongBullish, StrongBullish, Bullish, Neutral, Neutral],
+    [StrongBullish, Bullish, Bullish, Neutral, Neutral],
+    [Bullish, Bullish, Neutral, Bearish, Bearish],
+    [Neutral, Neutral, Bearish, Bearish, StrongBearish],
+    [Neutral, Neutral, Bearish, StrongBearish, StrongBearish],
+];
+
+#[test]
+fn combine_trends_matches_lookup_table() {
+    for (daily, row) in STATES.iter().zip(EXPECTED_OVERALL.iter()) {
+        for (weekly, expected) in STATES.iter().zip(row.iter()) {
+            assert_eq!(
+                &combine_trends(daily, weekly),
+                expected,
+                "daily {daily:?} + weekly {weekly:?}"
+            );
+        }
+    }
+}
+
+#[test]
+fn combine_trends_is_symmetric_between_directions() {
+    // 多空镜像：把两个周期同时反向，综合结果也反向
+    let mirror = |state: &TrendState| STATES[4 - STATES.iter().position(|s| s == state).unwrap()].clone();
+    for daily in &STATES {
+        for weekly in &STATES {
+            assert_eq!(
+                combine_trends(&mirror(daily), &mirror(weekly)),
+                mirror(&combine_trends(daily, weekly)),
+                "daily {daily:?} + weekly {weekly:?}"
+            );
+        }
+    }
+}
+
+#[test]
+fn conflicting_timeframes_are_neutral() {
+    assert_eq!(combine_trends(&StrongBullish, &StrongBearish), Neutral);
+    assert_eq!(combine_trends(&Bearish, &Bullish), Neutral);
+}
+
+#[test]
+fn trend_transitions_move_at_most_two_levels() {
+    assert!(TrendStateTransition::is_consistent(&StrongBearish, &Neutral));
+    assert!(TrendStateTransition::is_consistent(&Bullish, &Bearish));
+    assert!(TrendStateTransition::is_consistent(&Neutral, &Neutral));
+    assert!(!TrendStateTransition::is_consistent(&StrongBearish, &StrongBullish));
+    assert!(!TrendStateTransition::is_consistent(&StrongBullish, &Bearish));
+    assert!(!TrendStateTransition::is_consistent(&Bullish, &StrongBearish));
+}
+
+#[test]
+fn inconsistent_transition_lowers_trend_confidence() {
+    let mut analysis = TrendAnalysis {
+        daily_trend: StrongBullish,
+        weekly_trend: StrongBullish,
+        overall_trend: StrongBullish,
+        trend_strength: 0.8,
+        trend_confidence: 0.8,
+        bias_multiplier: 1.2,
+        description: "多周期共振上涨".to_string(),
+    };
+    assert!(analysis.flag_transition_from(&Neutral));
+    assert_eq!(analysis.trend_confidence, 0.8);
+
+    assert!(!analysis.flag_transition_from(&StrongBearish));
+    assert!((analysis.trend_confidence - 0.4).abs() < 1e-12);
+    assert!(analysis.description.contains("由强烈下跌跳变为强烈上涨"));
+}
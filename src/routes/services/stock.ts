@@ -6,6 +6,7 @@ import type {
   SortDirection,
   Stock,
   StockInfo,
+  StockSearchResult,
 } from '../types';
 import { invokeCommand } from './core';
 
@@ -17,6 +18,10 @@ export function getStockList(
   return invokeCommand('get_stock_list', { search, page, pageSize });
 }
 
+export function searchStocks(query: string, maxResults = 10): Promise<StockSearchResult[]> {
+  return invokeCommand('search_stocks', { query, maxResults });
+}
+
 export function getStockInfos(): Promise<StockInfo[]> {
   return invokeCommand('get_stock_infos');
 }
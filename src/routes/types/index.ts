@@ -25,6 +25,13 @@ export interface Stock {
   category: string;
 }
 
+export interface StockSearchResult {
+  code: string;
+  name: string;
+  exchange: string;
+  is_st: boolean;
+}
+
 // =============================================================================
 // 历史数据
 // =============================================================================
@@ -87,12 +94,17 @@ export interface PredictionRequest {
   model_name?: string;
   prediction_days: number;
   use_candle: boolean;
+  /** 联网获取个股新闻情绪计入情绪因子，默认关闭 */
+  include_news?: boolean;
+  /** 联网查询预测期内的公司事件，据此抑制信号并提示风险，默认关闭 */
+  include_events?: boolean;
 }
 
 export interface TechnicalOnlyRequest {
   stock_code: string;
   history_days?: number;
-  prediction_days: number;
+  /** 省略时按趋势与波动率自动推荐 */
+  prediction_days?: number;
 }
 
 export interface Prediction {
@@ -239,6 +251,14 @@ export interface EvaluationResult {
   evaluation_date: string;
   evaluation_scope: string;
   evaluation_note: string;
+  macd_comparison?: MacdVariantComparison | null;
+}
+
+/** 标准 MACD 与成交量加权 MACD 的方向命中率对比 */
+export interface MacdVariantComparison {
+  samples: number;
+  macd_direction_accuracy: number;
+  vwmacd_direction_accuracy: number;
 }
 
 // =============================================================================
@@ -297,6 +317,17 @@ export interface BacktestReport {
   stress_95_coverage: number;
   average_interval_80_width: number;
   average_stress_95_width: number;
+  regime_breakdown?: BacktestRegimeBreakdown[];
+}
+
+/** 按入场市场状态分组的回测交易表现 */
+export interface BacktestRegimeBreakdown {
+  regime: string;
+  trade_count: number;
+  win_rate: number;
+  /** 平均单笔收益（百分点） */
+  avg_return: number;
+  sharpe: number;
 }
 
 // =============================================================================
@@ -308,12 +339,23 @@ export interface BuySellPoint {
   signal_strength: number;
   price_level: number;
   stop_loss: number;
+  trailing_stop?: TrailingStopResult | null;
+  /** 最新K线距最近摆动点的斐波那契时间周期的K线数 */
+  time_cycle_proximity?: number | null;
   take_profit: number[];
   risk_reward_ratio: number;
   reasons: string[];
   confidence: number;
 }
 
+/** ATR 跟踪止损（多头：当前价 - 倍数 × ATR） */
+export interface TrailingStopResult {
+  stop_price: number;
+  stop_distance_pct: number;
+  atr_value: number;
+  atr_period_used: number;
+}
+
 export interface SupportResistance {
   support_levels: number[];
   resistance_levels: number[];
@@ -344,11 +386,14 @@ export interface PatternRecognition {
   is_bullish: boolean;
   reliability: number;
   description: string;
+  /** 形态确认度 0-1（放量、次日同向、关键位、多周期同向） */
+  confirmation_score?: number;
 }
 
 export interface VolumeAnalysisInfo {
   volume_trend: string;
   volume_price_sync: boolean;
+  /** Chaikin 振荡器（按均量归一化）：正值吸筹、负值派发 */
   accumulation_signal: number;
   obv_trend: string;
 }
@@ -362,6 +407,8 @@ export interface MultiFactorScore {
   support_resistance_score: number;
   sentiment_score: number;
   volatility_score: number;
+  /** 基本面因子得分，无基本面数据时为 null */
+  fundamental_score?: number | null;
   signal: string;
   signal_strength: number;
   adaptive_score: number;
@@ -379,6 +426,33 @@ export interface ProfessionalPrediction {
   candle_patterns: PatternRecognition[];
   volume_analysis: VolumeAnalysisInfo;
   multi_factor_score: MultiFactorScore;
+  horizon_recommendation?: PredictionHorizonRecommendation;
+  drawdown?: DrawdownAnalysis;
+  opening_gap?: GapPrediction | null;
+}
+
+/** 次日开盘跳空预测（独立于多日价格预测） */
+export interface GapPrediction {
+  gap_probability_up: number;
+  gap_probability_down: number;
+  /** 预期开盘涨跌幅（%，正为高开） */
+  expected_gap_magnitude_pct: number;
+  confidence: number;
+}
+
+export interface DrawdownAnalysis {
+  max_drawdown_pct: number;
+  max_drawdown_duration_days: number;
+  avg_recovery_days: number;
+  current_drawdown_pct: number;
+  days_in_current_drawdown: number;
+  estimated_recovery_days: number | null;
+}
+
+export interface PredictionHorizonRecommendation {
+  recommended_days: number;
+  confidence_decay_rate: number;
+  reasoning: string;
 }
 
 export interface ProfessionalPredictionResponse {
@@ -390,11 +464,18 @@ export interface ProfessionalPredictionResponse {
 // 优化建议相关
 // =============================================================================
 
+export interface OptimizationSuggestion {
+  suggestion: string;
+  impact: string;
+  priority: number;
+}
+
 export interface OptimizationSuggestions {
   stock_code: string;
   model_name: string;
   suggestions: string[];
   expected_improvement: number;
+  details: OptimizationSuggestion[];
 }
 
 // =============================================================================
@@ -421,6 +502,16 @@ export interface NavTarget {
   symbol?: string;
   name?: string;
   action?: 'history' | 'predict';
+  /** 分享链接指定的模型 ID 与预测天数 */
+  modelId?: string;
+  days?: number;
+}
+
+/** `deep_link_received` 事件负载（`biga://analyze/{stock_code}?model=&days=`） */
+export interface DeepLinkTarget {
+  stock_code: string;
+  model_name: string | null;
+  days: number | null;
 }
 
 export type ApiTokenSource = 'keyring' | 'environment' | 'none';